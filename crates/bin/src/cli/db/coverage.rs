@@ -0,0 +1,397 @@
+use std::{
+    fmt::Write,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use alloy_primitives::{Address, FixedBytes};
+use brontes_classifier::{
+    declarative::{init_declarative_classifiers, DeclarativeClassifiers},
+    Classifier,
+};
+use brontes_core::decoding::TracingProvider;
+use brontes_database::libmdbx::LibmdbxReader;
+use brontes_types::{
+    constants::USDT_ADDRESS,
+    db::dex::{DexQuotes, PriceAt},
+    init_thread_pools,
+    normalized_actions::Action,
+    pair::Pair,
+    structured_trace::TraceActions,
+    tree::{Node, Root},
+    FastHashMap, FastHashSet, ToFloatNearest,
+};
+use clap::{Parser, ValueEnum};
+use comfy_table::Table as ComfyTable;
+use itertools::Itertools;
+use serde::Serialize;
+use tokio::sync::mpsc::unbounded_channel;
+
+use crate::{
+    cli::{determine_max_tasks, get_env_vars, get_tracing_provider, load_libmdbx, static_object},
+    runner::CliContext,
+};
+
+#[derive(Debug, Parser)]
+pub struct Coverage {
+    /// Start Block
+    #[arg(long, short)]
+    pub start_block:     u64,
+    /// End Block, inclusive
+    #[arg(long, short)]
+    pub end_block:       u64,
+    /// Only print the top entries
+    #[arg(long, short)]
+    pub limit:           Option<usize>,
+    /// Output format of the report
+    #[arg(long, short, default_value = "table")]
+    pub format:          ReportFormat,
+    /// TOML file with classifiers defined from contract ABIs, see
+    /// `config/declarative_classifiers.toml`
+    #[arg(long)]
+    pub classifier_defs: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ReportFormat {
+    Table,
+    Csv,
+    Json,
+}
+
+impl Coverage {
+    pub async fn execute(self, brontes_db_endpoint: String, ctx: CliContext) -> eyre::Result<()> {
+        let db_path = get_env_vars()?;
+        if let Some(path) = &self.classifier_defs {
+            init_declarative_classifiers(DeclarativeClassifiers::load(path)?)?;
+        }
+
+        let max_tasks = determine_max_tasks(None);
+        init_thread_pools(max_tasks as usize);
+
+        // the classifier sends the pool updates of every block to the pricing, which
+        // isn't run, so they are dropped as they come in
+        let (pricing_tx, mut pricing_rx) = unbounded_channel();
+        ctx.task_executor
+            .spawn(async move { while pricing_rx.recv().await.is_some() {} });
+
+        let libmdbx = static_object(load_libmdbx(&ctx.task_executor, brontes_db_endpoint)?);
+        let tracer = Arc::new(get_tracing_provider(
+            Path::new(&db_path),
+            max_tasks,
+            ctx.task_executor.clone(),
+        ));
+        // pools the classifier discovers while building the trees are written to
+        // libmdbx, as they are when running brontes over the range
+        let classifier = Classifier::new(libmdbx, pricing_tx, tracer.clone());
+
+        let mut report = CoverageReport::default();
+        for block in self.start_block..=self.end_block {
+            let traces = match libmdbx.load_trace(block) {
+                Ok(traces) => traces,
+                Err(e) => {
+                    tracing::warn!(block, error = %e, "no traces, skipping block");
+                    continue
+                }
+            };
+            let Some(header) = tracer.header_by_number(block).await? else {
+                tracing::warn!(block, "no header, skipping block");
+                continue
+            };
+            let quotes = libmdbx
+                .has_dex_quotes(block)?
+                .then(|| libmdbx.get_dex_quotes(block))
+                .transpose()?;
+
+            let tree = classifier.build_block_tree(traces, header, false).await;
+            for root in &tree.tx_roots {
+                let (calls, transfers) = collect_tx(root, quotes.as_ref());
+                report.record_tx(&calls, &transfers);
+            }
+            report.blocks += 1;
+        }
+
+        let mut entries = report.ranked();
+        if let Some(limit) = self.limit {
+            entries.truncate(limit);
+        }
+        let labels = entries
+            .iter()
+            .map(|entry| {
+                libmdbx
+                    .try_fetch_address_metadata(entry.target)
+                    .ok()
+                    .flatten()
+                    .and_then(|metadata| metadata.describe())
+            })
+            .collect_vec();
+
+        match self.format {
+            ReportFormat::Table => {
+                println!(
+                    "{} unclassified calls in {} transactions over {} blocks",
+                    report.calls, report.txs, report.blocks
+                );
+                println!("{}", coverage_table(&entries, &labels));
+            }
+            ReportFormat::Csv => print!("{}", coverage_csv(&entries, &labels)?),
+            ReportFormat::Json => {
+                let entries = entries
+                    .into_iter()
+                    .zip(labels)
+                    .map(|(entry, label)| LabeledEntry { entry, label })
+                    .collect_vec();
+                println!("{}", serde_json::to_string_pretty(&entries)?);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The unclassified calls by target and selector, with the token flow into
+/// and out of their targets
+#[derive(Debug, Default)]
+struct CoverageReport {
+    entries: FastHashMap<(Address, FixedBytes<4>), CoverageEntry>,
+    blocks:  u64,
+    /// Transactions with at least one unclassified call
+    txs:     u64,
+    calls:   u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct CoverageEntry {
+    target:     Address,
+    selector:   FixedBytes<4>,
+    calls:      u64,
+    txs:        u64,
+    /// ERC-20 transfers into and out of the target
+    transfers:  u64,
+    /// Usd value of the transfers, transfers of tokens without a price are
+    /// left out
+    volume_usd: f64,
+}
+
+#[derive(Debug, Serialize)]
+struct LabeledEntry {
+    #[serde(flatten)]
+    entry: CoverageEntry,
+    label: Option<String>,
+}
+
+/// A transfer from the first to the second address with its usd value, if the
+/// token has a price
+type ValuedTransfer = (Address, Address, Option<f64>);
+
+impl CoverageReport {
+    /// Adds the unclassified calls of a transaction. The transfers into and
+    /// out of a target are attributed to its first unclassified call in the
+    /// transaction, so that they aren't counted once per selector
+    fn record_tx(&mut self, calls: &[(Address, FixedBytes<4>)], transfers: &[ValuedTransfer]) {
+        if calls.is_empty() {
+            return
+        }
+        self.txs += 1;
+
+        let mut seen_targets = FastHashSet::default();
+        let mut seen_calls = FastHashSet::default();
+        for (target, selector) in calls {
+            self.calls += 1;
+            let entry = self
+                .entries
+                .entry((*target, *selector))
+                .or_insert_with(|| CoverageEntry {
+                    target:     *target,
+                    selector:   *selector,
+                    calls:      0,
+                    txs:        0,
+                    transfers:  0,
+                    volume_usd: 0.0,
+                });
+            entry.calls += 1;
+
+            if seen_calls.insert((*target, *selector)) {
+                entry.txs += 1;
+            }
+            if seen_targets.insert(*target) {
+                for (.., usd) in transfers
+                    .iter()
+                    .filter(|(from, to, _)| from == target || to == target)
+                {
+                    entry.transfers += 1;
+                    entry.volume_usd += usd.unwrap_or_default();
+                }
+            }
+        }
+    }
+
+    /// The entries by volume, then by calls
+    fn ranked(&self) -> Vec<CoverageEntry> {
+        self.entries
+            .values()
+            .cloned()
+            .sorted_by(|a, b| {
+                b.volume_usd
+                    .total_cmp(&a.volume_usd)
+                    .then(b.calls.cmp(&a.calls))
+            })
+            .collect()
+    }
+}
+
+/// The unclassified state changing calls of the transaction and its ERC-20
+/// transfers. Static calls never need a classifier and delegate calls are
+/// counted on the proxy that was called
+fn collect_tx(
+    root: &Root<Action>,
+    quotes: Option<&DexQuotes>,
+) -> (Vec<(Address, FixedBytes<4>)>, Vec<ValuedTransfer>) {
+    let mut calls = Vec::new();
+    let mut transfers = Vec::new();
+    collect_node(root, &root.head, quotes, &mut calls, &mut transfers);
+
+    (calls, transfers)
+}
+
+fn collect_node(
+    root: &Root<Action>,
+    node: &Node,
+    quotes: Option<&DexQuotes>,
+    calls: &mut Vec<(Address, FixedBytes<4>)>,
+    transfers: &mut Vec<ValuedTransfer>,
+) {
+    for action in root.data_store.get_ref(node.data).into_iter().flatten() {
+        match action {
+            Action::Unclassified(trace) => {
+                if trace.is_static_call() || trace.is_delegate_call() || trace.is_create() {
+                    continue
+                }
+                let calldata = trace.get_calldata();
+                let selector = calldata
+                    .get(..4)
+                    .map(FixedBytes::from_slice)
+                    .unwrap_or_default();
+                calls.push((trace.get_to_address(), selector));
+            }
+            Action::Transfer(transfer) => {
+                let usd = quotes
+                    .and_then(|quotes| {
+                        quotes.price_at_or_before(
+                            Pair(transfer.token.address, USDT_ADDRESS),
+                            root.position,
+                        )
+                    })
+                    .map(|price| (&transfer.amount * price.get_price(PriceAt::Average)).to_float());
+                transfers.push((transfer.from, transfer.to, usd));
+            }
+            _ => {}
+        }
+    }
+
+    node.inner
+        .iter()
+        .for_each(|child| collect_node(root, child, quotes, calls, transfers));
+}
+
+fn coverage_table(entries: &[CoverageEntry], labels: &[Option<String>]) -> ComfyTable {
+    let mut table = ComfyTable::new();
+    table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+    table.set_header(["Target", "Selector", "Label", "Calls", "Txs", "Transfers", "Volume (USD)"]);
+
+    for (entry, label) in entries.iter().zip(labels) {
+        table.add_row([
+            format!("{:?}", entry.target),
+            entry.selector.to_string(),
+            label.clone().unwrap_or_default(),
+            entry.calls.to_string(),
+            entry.txs.to_string(),
+            entry.transfers.to_string(),
+            format!("{:.2}", entry.volume_usd),
+        ]);
+    }
+
+    table
+}
+
+fn coverage_csv(
+    entries: &[CoverageEntry],
+    labels: &[Option<String>],
+) -> Result<String, std::fmt::Error> {
+    let mut out = String::from("target,selector,label,calls,txs,transfers,volume_usd\n");
+    for (entry, label) in entries.iter().zip(labels) {
+        let label = label.as_deref().unwrap_or_default().replace('"', "\"\"");
+        writeln!(
+            out,
+            "{:?},{},\"{label}\",{},{},{},{:.2}",
+            entry.target, entry.selector, entry.calls, entry.txs, entry.transfers, entry.volume_usd
+        )?;
+    }
+
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::fixed_bytes;
+
+    use super::*;
+
+    const POOL: Address = Address::with_last_byte(1);
+    const VAULT: Address = Address::with_last_byte(2);
+    const USER: Address = Address::with_last_byte(3);
+    const SWAP: FixedBytes<4> = fixed_bytes!("022c0d9f");
+    const SYNC: FixedBytes<4> = fixed_bytes!("fff6cae9");
+
+    #[test]
+    fn test_transfers_are_attributed_once_per_target() {
+        let mut report = CoverageReport::default();
+        report.record_tx(
+            &[(POOL, SWAP), (POOL, SYNC), (POOL, SWAP)],
+            &[(USER, POOL, Some(100.0)), (USER, POOL, None), (USER, VAULT, Some(1_000.0))],
+        );
+        report.record_tx(&[(POOL, SYNC)], &[(USER, POOL, Some(5.0))]);
+        // no unclassified calls
+        report.record_tx(&[], &[(USER, POOL, Some(5.0))]);
+
+        assert_eq!(report.txs, 2);
+        assert_eq!(report.calls, 4);
+
+        let ranked = report.ranked();
+        assert_eq!(ranked.len(), 2);
+
+        let swap = &ranked[0];
+        assert_eq!((swap.target, swap.selector), (POOL, SWAP));
+        assert_eq!((swap.calls, swap.txs, swap.transfers), (2, 1, 2));
+        assert_eq!(swap.volume_usd, 100.0);
+
+        let sync = &ranked[1];
+        assert_eq!((sync.calls, sync.txs, sync.transfers), (2, 2, 1));
+        assert_eq!(sync.volume_usd, 5.0);
+    }
+
+    #[test]
+    fn test_csv_quotes_labels() {
+        let mut report = CoverageReport::default();
+        report.record_tx(&[(POOL, SWAP)], &[(USER, POOL, Some(1.5))]);
+
+        let csv = coverage_csv(&report.ranked(), &[Some("Pool \"A\", v2".to_string())]).unwrap();
+        assert_eq!(
+            csv.lines().nth(1).unwrap(),
+            format!("{POOL:?},0x022c0d9f,\"Pool \"\"A\"\", v2\",1,1,1,1.50")
+        );
+    }
+
+    #[test]
+    fn test_transfers_out_of_the_target_are_counted() {
+        let mut report = CoverageReport::default();
+        report.record_tx(
+            &[(POOL, SWAP)],
+            &[(USER, POOL, Some(100.0)), (POOL, USER, Some(99.0)), (VAULT, USER, Some(1.0))],
+        );
+
+        let swap = &report.ranked()[0];
+        assert_eq!(swap.transfers, 2);
+        assert_eq!(swap.volume_usd, 199.0);
+    }
+}
@@ -13,7 +13,9 @@ pub struct Clear {
         value_delimiter = ',',
         default_value = "CexPrice,DexPrice,CexTrades,BlockInfo,InitializedState,MevBlocks,\
                          TokenDecimals,AddressToProtocolInfo,PoolCreationBlocks,Builder,\
-                         AddressMeta,SearcherEOAs,SearcherContracts,SubGraphs,TxTraces"
+                         AddressMeta,SearcherEOAs,SearcherContracts,SubGraphs,TxTraces,\
+                         AnalysisRollups,PoolLvr,AddressBundles,TxBundles,ForkDetections,\
+                         UniswapV4PoolKeys"
     )]
     pub tables:                  Vec<Tables>,
     /// Mark metadata as uninitialized in the initialized state table
@@ -66,7 +68,14 @@ impl Clear {
                 AddressMeta,
                 SearcherEOAs,
                 SearcherContracts,
-                TxTraces
+                TxTraces,
+                AnalysisRollups,
+                PoolLvr,
+                AddressBundles,
+                TxBundles,
+                SchemaVersions,
+                ForkDetections,
+                UniswapV4PoolKeys
             )
         });
 
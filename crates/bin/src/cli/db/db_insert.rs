@@ -53,6 +53,13 @@ impl Insert {
             SearcherEOAs,
             SearcherContracts,
             InitializedState,
+            AnalysisRollups,
+            PoolLvr,
+            AddressBundles,
+            TxBundles,
+            SchemaVersions,
+            ForkDetections,
+            UniswapV4PoolKeys,
             PoolCreationBlocks = &self.key,
             &self.value
         );
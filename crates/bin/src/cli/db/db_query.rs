@@ -3,26 +3,58 @@ use brontes_database::{
     CompressedTable, IntoTableKey, Tables,
 };
 use brontes_libmdbx::RO;
-use brontes_types::init_thread_pools;
+use brontes_types::{db::traits::LibmdbxReader, init_thread_pools};
 use clap::Parser;
 use itertools::Itertools;
 use reth_interfaces::db::DatabaseErrorInfo;
+use reth_primitives::{Address, B256};
+
+use crate::{cli::load_libmdbx, runner::CliContext};
 
 #[derive(Debug, Parser)]
 pub struct DatabaseQuery {
     /// Table to query
-    #[arg(long, short)]
-    pub table: Tables,
+    #[arg(long, short, required_unless_present_any = ["address", "tx_hash"])]
+    pub table: Option<Tables>,
     /// Key for table query. Use Rust range syntax for ranges:
     /// --key 80 (single key)
     /// --key 80..100 (range)
-    #[arg(long, short)]
-    pub key:   String,
+    #[arg(long, short, required_unless_present_any = ["address", "tx_hash"])]
+    pub key:   Option<String>,
+    #[clap(flatten)]
+    pub index: BundleLookup,
+}
+
+/// Looks up bundles through the `AddressBundles` and `TxBundles` indexes
+/// instead of a table
+#[derive(Debug, Parser)]
+pub struct BundleLookup {
+    /// Fetch all bundles involving this address, as the searcher eoa or
+    /// contract, or with a balance delta
+    #[arg(long, conflicts_with_all = ["table", "key", "tx_hash"])]
+    pub address:     Option<Address>,
+    /// Fetch all bundles containing this transaction
+    #[arg(long, conflicts_with_all = ["table", "key"])]
+    pub tx_hash:     Option<B256>,
+    /// First block to fetch bundles of the address from
+    #[arg(long, requires = "address")]
+    pub start_block: Option<u64>,
+    /// Last block to fetch bundles of the address from, inclusive
+    #[arg(long, requires = "address")]
+    pub end_block:   Option<u64>,
 }
 
 impl DatabaseQuery {
-    pub async fn execute(self, brontes_db_endpoint: String) -> eyre::Result<()> {
+    pub async fn execute(self, brontes_db_endpoint: String, ctx: CliContext) -> eyre::Result<()> {
         init_thread_pools(10);
+
+        if self.index.address.is_some() || self.index.tx_hash.is_some() {
+            return self.index.execute(brontes_db_endpoint, ctx)
+        }
+
+        let (Some(table), Some(key)) = (self.table, self.key) else {
+            eyre::bail!("--table and --key are required to query a table")
+        };
         let db = Libmdbx::init_db(brontes_db_endpoint, None)?;
 
         db.view_db(|tx| {
@@ -50,7 +82,7 @@ impl DatabaseQuery {
                         println!(
                             "{:#?}",
                             $fn(
-                                tx.$query::<brontes_database::libmdbx::tables::$tables>()?, self
+                                tx.$query::<brontes_database::libmdbx::tables::$tables>()?, &key
                             )?
                         )
                     }
@@ -59,9 +91,9 @@ impl DatabaseQuery {
         };
     }
 
-            if self.key.contains("..") {
+            if key.contains("..") {
                 match_table!(
-                    self.table,
+                    table,
                     process_range_query,
                     new_cursor,
                     CexPrice,
@@ -77,11 +109,18 @@ impl DatabaseQuery {
                     AddressMeta,
                     SearcherEOAs,
                     SearcherContracts,
-                    TxTraces
+                    TxTraces,
+                    AnalysisRollups,
+                    PoolLvr,
+                    AddressBundles,
+                    TxBundles,
+                    SchemaVersions,
+                    ForkDetections,
+                    UniswapV4PoolKeys
                 );
             } else {
                 match_table!(
-                    self.table,
+                    table,
                     process_single_query,
                     get,
                     CexPrice,
@@ -97,7 +136,14 @@ impl DatabaseQuery {
                     SearcherEOAs,
                     SearcherContracts,
                     TxTraces,
-                    PoolCreationBlocks = &self.key
+                    AnalysisRollups,
+                    PoolLvr,
+                    AddressBundles,
+                    TxBundles,
+                    SchemaVersions,
+                    ForkDetections,
+                    UniswapV4PoolKeys,
+                    PoolCreationBlocks = &key
                 );
             }
 
@@ -107,16 +153,37 @@ impl DatabaseQuery {
     }
 }
 
+impl BundleLookup {
+    fn execute(self, brontes_db_endpoint: String, ctx: CliContext) -> eyre::Result<()> {
+        let db = load_libmdbx(&ctx.task_executor, brontes_db_endpoint)?;
+
+        let bundles = if let Some(address) = self.address {
+            db.try_fetch_bundles_by_address(address, self.start_block, self.end_block)?
+        } else if let Some(tx_hash) = self.tx_hash {
+            db.try_fetch_bundles_by_tx_hash(tx_hash)?
+        } else {
+            unreachable!("either an address or a tx hash is set")
+        };
+
+        println!("found {} bundles", bundles.len());
+        for bundle in bundles {
+            println!("{bundle}");
+        }
+
+        Ok(())
+    }
+}
+
 fn process_range_query<T, E>(
     mut cursor: CompressedCursor<T, RO>,
-    config: DatabaseQuery,
+    key: &str,
 ) -> eyre::Result<Vec<T::DecompressedValue>>
 where
     T: CompressedTable,
     T: for<'a> IntoTableKey<&'a str, T::Key, E>,
     T::Value: From<T::DecompressedValue> + Into<T::DecompressedValue>,
 {
-    let range = config.key.split("..").collect_vec();
+    let range = key.split("..").collect_vec();
     let start = range[0];
     let end = range[1];
 
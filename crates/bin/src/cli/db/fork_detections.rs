@@ -0,0 +1,126 @@
+use alloy_primitives::Address;
+use brontes_database::libmdbx::{
+    tables::{AddressToProtocolInfo, ForkDetections, ForkDetectionsData},
+    Libmdbx,
+};
+use brontes_types::{
+    db::fork_detection::{ForkDetection, ForkReviewStatus},
+    init_thread_pools,
+};
+use clap::Parser;
+use comfy_table::Table as ComfyTable;
+use itertools::Itertools;
+
+#[derive(Debug, Parser)]
+pub struct ForkDetectionsCmd {
+    /// Only list the detections with this review status
+    #[arg(long, short)]
+    pub status:  Option<ForkReviewStatus>,
+    /// Marks the pools as reviewed and correctly classified
+    #[arg(long, value_delimiter = ',')]
+    pub approve: Vec<Address>,
+    /// Marks the pools as misclassified and removes them from the protocol
+    /// info, so they are no longer classified as the detected protocol
+    #[arg(long, value_delimiter = ',')]
+    pub reject:  Vec<Address>,
+    /// Print the detections as json
+    #[arg(long, default_value = "false")]
+    pub json:    bool,
+}
+
+impl ForkDetectionsCmd {
+    pub async fn execute(self, brontes_db_endpoint: String) -> eyre::Result<()> {
+        init_thread_pools(10);
+        let db = Libmdbx::init_db(&brontes_db_endpoint, None)?;
+
+        let mut detections = db.view_db(|tx| {
+            Ok(tx
+                .cursor_read::<ForkDetections>()?
+                .walk(None)?
+                .collect::<Result<Vec<_>, _>>()?)
+        })?;
+
+        let reviews = self
+            .approve
+            .iter()
+            .map(|pool| (*pool, ForkReviewStatus::Approved))
+            .chain(
+                self.reject
+                    .iter()
+                    .map(|pool| (*pool, ForkReviewStatus::Rejected)),
+            )
+            .collect_vec();
+
+        if !reviews.is_empty() {
+            let mut updated = Vec::with_capacity(reviews.len());
+            for (pool, status) in reviews {
+                let Some((_, detection)) = detections.iter_mut().find(|(p, _)| *p == pool) else {
+                    eyre::bail!("{pool:?} wasn't registered by the fork detection")
+                };
+                detection.status = status;
+                updated.push(ForkDetectionsData::new(pool, *detection));
+            }
+
+            db.write_table::<ForkDetections, ForkDetectionsData>(&updated)?;
+            db.update_db(|tx| {
+                for rejected in &self.reject {
+                    tx.delete::<AddressToProtocolInfo>(*rejected, None)?;
+                }
+                Ok::<_, eyre::Report>(())
+            })??;
+
+            println!("reviewed {} fork detections", updated.len());
+            return Ok(())
+        }
+
+        let detections = detections
+            .into_iter()
+            .filter(|(_, detection)| {
+                self.status
+                    .map_or(true, |status| detection.status == status)
+            })
+            .sorted_by_key(|(_, detection)| detection.block)
+            .collect_vec();
+
+        if self.json {
+            let detections = detections
+                .iter()
+                .map(|(pool, detection)| {
+                    serde_json::json!({
+                        "pool": pool,
+                        "protocol": detection.protocol,
+                        "reference": detection.reference,
+                        "similarity": detection.similarity,
+                        "block": detection.block,
+                        "status": detection.status,
+                    })
+                })
+                .collect_vec();
+            println!("{}", serde_json::to_string_pretty(&detections)?);
+            return Ok(())
+        }
+
+        println!("{}", detections_table(&detections));
+
+        Ok(())
+    }
+}
+
+fn detections_table(detections: &[(Address, ForkDetection)]) -> ComfyTable {
+    let mut table = ComfyTable::new();
+    table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+    table.set_header(["Pool", "Protocol", "Reference", "Similarity", "Block", "Status"]);
+
+    for (pool, detection) in detections {
+        table.add_row([
+            format!("{pool:?}"),
+            detection.protocol.to_string(),
+            format!("{:?}", detection.reference),
+            format!("{:.3}", detection.similarity),
+            detection.block.to_string(),
+            detection.status.to_string(),
+        ]);
+    }
+
+    table
+}
@@ -0,0 +1,64 @@
+use std::path::PathBuf;
+
+use brontes_database::libmdbx::{
+    migrations::{migrate, plan_migrations},
+    Libmdbx,
+};
+use brontes_types::init_thread_pools;
+use clap::Parser;
+
+#[derive(Debug, Parser)]
+pub struct Migrate {
+    /// Write the migrated db to this path instead of migrating in place. The
+    /// tables that are up to date are copied over
+    #[arg(long, short)]
+    pub output:  Option<PathBuf>,
+    /// Only print the schema version of every table and the migrations that
+    /// would run
+    #[arg(long, default_value = "false")]
+    pub dry_run: bool,
+}
+
+impl Migrate {
+    pub async fn execute(self, brontes_db_endpoint: String) -> eyre::Result<()> {
+        init_thread_pools(10);
+        let db = Libmdbx::init_db_unchecked(&brontes_db_endpoint, None)?;
+
+        let plan = plan_migrations(&db)?;
+
+        if self.dry_run {
+            for (table, version) in db.schema_versions()? {
+                let version = version.map_or_else(|| "-".to_string(), |v| v.to_string());
+                println!("{:<24} {:>4} (current {})", table, version, table.schema_version());
+            }
+            println!();
+            if plan.is_empty() {
+                println!("all tables are up to date");
+            }
+            for migration in plan.iter().flat_map(|(_, path)| path) {
+                println!("{migration}");
+            }
+
+            return Ok(())
+        }
+
+        let Some(output) = self.output else {
+            if plan.is_empty() {
+                println!("all tables are up to date");
+                return Ok(())
+            }
+            migrate(&db, None)?;
+            println!("migrated {brontes_db_endpoint}");
+            return Ok(())
+        };
+
+        if output.read_dir().is_ok_and(|mut dir| dir.next().is_some()) {
+            eyre::bail!("the output db {} has to be empty", output.display())
+        }
+        let output_db = Libmdbx::init_db(&output, None)?;
+        migrate(&db, Some(&output_db))?;
+        println!("migrated {brontes_db_endpoint} to {}", output.display());
+
+        Ok(())
+    }
+}
@@ -5,6 +5,7 @@ use crate::runner::CliContext;
 mod cex_data;
 #[cfg(feature = "local-clickhouse")]
 mod clickhouse_download;
+mod coverage;
 mod db_clear;
 mod db_insert;
 mod db_query;
@@ -13,7 +14,11 @@ mod discovery;
 #[cfg(feature = "local-clickhouse")]
 mod ensure_test_traces;
 mod export;
+mod fork_detections;
 mod init;
+mod migrate;
+mod rollup;
+mod simulate_victims;
 mod table_stats;
 #[cfg(feature = "local-clickhouse")]
 mod tip_tracer;
@@ -37,10 +42,30 @@ pub enum DatabaseCommands {
     /// Clear a libmdbx table
     #[command(name = "clear")]
     DbClear(db_clear::Clear),
+    /// Migrates the libmdbx tables written with an older schema version to the
+    /// current one
+    #[command(name = "migrate")]
+    Migrate(migrate::Migrate),
+    /// Lists the pools registered by the fork detection and records their
+    /// review
+    #[command(name = "fork-detections")]
+    ForkDetections(fork_detections::ForkDetectionsCmd),
     /// Generates traces and store them in libmdbx (also clickhouse if
     /// --feature local-clickhouse)
     #[command(name = "generate-traces")]
     TraceRange(trace_range::TraceArgs),
+    /// Re-executes the victims of the stored sandwiches without the frontruns
+    /// and records their loss
+    #[command(name = "simulate-victims")]
+    SimulateVictims(simulate_victims::SimulateVictims),
+    /// Rolls the block analysis of the stored mev blocks up into hourly, daily
+    /// and weekly windows
+    #[command(name = "rollup")]
+    Rollup(rollup::Rollup),
+    /// Runs the classifier over the stored traces of a block range and ranks
+    /// the calls it couldn't classify by the token volume they move
+    #[command(name = "coverage")]
+    Coverage(coverage::Coverage),
     /// Fetches Cex data from the Sorella DB
     #[command(name = "cex-query")]
     CexData(cex_data::CexDB),
@@ -86,10 +111,15 @@ impl Database {
     pub async fn execute(self, brontes_db_endpoint: String, ctx: CliContext) -> eyre::Result<()> {
         match self.command {
             DatabaseCommands::DbInserts(cmd) => cmd.execute(brontes_db_endpoint).await,
-            DatabaseCommands::DbQuery(cmd) => cmd.execute(brontes_db_endpoint).await,
+            DatabaseCommands::DbQuery(cmd) => cmd.execute(brontes_db_endpoint, ctx).await,
             DatabaseCommands::TraceRange(cmd) => cmd.execute(brontes_db_endpoint, ctx).await,
+            DatabaseCommands::SimulateVictims(cmd) => cmd.execute(brontes_db_endpoint, ctx).await,
+            DatabaseCommands::Rollup(cmd) => cmd.execute(brontes_db_endpoint, ctx).await,
+            DatabaseCommands::Coverage(cmd) => cmd.execute(brontes_db_endpoint, ctx).await,
             DatabaseCommands::Init(cmd) => cmd.execute(brontes_db_endpoint, ctx).await,
             DatabaseCommands::DbClear(cmd) => cmd.execute(brontes_db_endpoint).await,
+            DatabaseCommands::Migrate(cmd) => cmd.execute(brontes_db_endpoint).await,
+            DatabaseCommands::ForkDetections(cmd) => cmd.execute(brontes_db_endpoint).await,
             DatabaseCommands::UploadSnapshot(cmd) => cmd.execute(brontes_db_endpoint, ctx).await,
             DatabaseCommands::Export(cmd) => cmd.execute(brontes_db_endpoint, ctx).await,
             DatabaseCommands::TableStats(cmd) => cmd.execute(brontes_db_endpoint),
@@ -0,0 +1,139 @@
+use std::collections::{btree_map::Entry, BTreeMap};
+
+use brontes_database::libmdbx::{DBWriter, LibmdbxReader};
+use brontes_types::{
+    constants::USDT_ADDRESS,
+    db::{
+        analysis_rollup::{BlockAnalysisRollup, RollupWindow},
+        block_analysis::BlockAnalysis,
+        mev_block::MevBlockWithClassified,
+    },
+};
+use clap::Parser;
+use strum::IntoEnumIterator;
+
+use crate::{
+    cli::{load_database, static_object},
+    runner::CliContext,
+};
+
+#[derive(Debug, Parser)]
+pub struct Rollup {
+    /// Start Block
+    #[arg(long, short)]
+    pub start_block: u64,
+    /// End Block, inclusive
+    #[arg(long, short)]
+    pub end_block:   u64,
+    /// Windows to roll the blocks up into, defaults to all of them
+    #[arg(long, short, value_delimiter = ',')]
+    pub windows:     Option<Vec<RollupWindow>>,
+}
+
+impl Rollup {
+    pub async fn execute(self, brontes_db_endpoint: String, ctx: CliContext) -> eyre::Result<()> {
+        let windows = self
+            .windows
+            .unwrap_or_else(|| RollupWindow::iter().collect());
+
+        let db = static_object(
+            load_database(&ctx.task_executor, brontes_db_endpoint, None, None).await?,
+        );
+        // the clickhouse middleware doesn't read mev blocks, so we go to libmdbx
+        // directly
+        #[cfg(feature = "local-clickhouse")]
+        let reader = db.inner();
+        #[cfg(not(feature = "local-clickhouse"))]
+        let reader = db;
+
+        let mev_blocks = self.fetch_mev_blocks(reader)?;
+
+        let mut rollups = BTreeMap::new();
+        let mut folded = 0usize;
+
+        for MevBlockWithClassified { block, mev } in mev_blocks {
+            let block_number = block.block_number;
+            let timestamp = match reader.get_metadata_no_dex_price(block_number, USDT_ADDRESS) {
+                Ok(metadata) => metadata.block_timestamp,
+                Err(e) => {
+                    tracing::warn!(block_number, error = %e, "no block timestamp, skipping block");
+                    continue
+                }
+            };
+            let analysis = BlockAnalysis::new(&block, &mev);
+
+            for window in &windows {
+                let window_start = window.window_start(timestamp);
+                // continue from the stored rollup so that ranges can be rolled up in parts
+                let rollup = match rollups.entry((*window, window_start)) {
+                    Entry::Occupied(entry) => entry.into_mut(),
+                    Entry::Vacant(entry) => entry.insert(
+                        reader
+                            .try_fetch_analysis_rollups(*window, window_start, window_start)?
+                            .pop()
+                            .unwrap_or_else(|| BlockAnalysisRollup::new(*window, window_start)),
+                    ),
+                };
+
+                if rollup.fold(timestamp, &analysis)? {
+                    folded += 1;
+                }
+            }
+        }
+
+        let windows_written = rollups.len();
+        db.write_analysis_rollups(rollups.into_values().collect())
+            .await?;
+
+        tracing::info!(folded, windows_written, "finished rolling up block analysis");
+
+        Ok(())
+    }
+
+    /// The mev blocks of the inclusive block range
+    fn fetch_mev_blocks<DB: LibmdbxReader>(
+        &self,
+        db: &DB,
+    ) -> eyre::Result<Vec<MevBlockWithClassified>> {
+        db.try_fetch_mev_blocks(Some(self.start_block), self.end_block)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use brontes_database::libmdbx::{
+        tables::{MevBlocks, MevBlocksData},
+        LibmdbxReadWriter,
+    };
+    use brontes_types::mev::MevBlock;
+
+    use super::*;
+
+    #[test]
+    fn test_fetches_start_block() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = LibmdbxReadWriter::init_db_tests(dir.path()).unwrap();
+        let blocks = [10, 11].map(|block_number| {
+            MevBlocksData::new(
+                block_number,
+                MevBlockWithClassified {
+                    block: MevBlock { block_number, ..Default::default() },
+                    mev:   vec![],
+                },
+            )
+        });
+        db.db.write_table::<MevBlocks, _>(&blocks).unwrap();
+
+        let block_numbers = |start_block, end_block| {
+            Rollup { start_block, end_block, windows: None }
+                .fetch_mev_blocks(&db)
+                .unwrap()
+                .into_iter()
+                .map(|mev_block| mev_block.block.block_number)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(block_numbers(10, 10), vec![10]);
+        assert_eq!(block_numbers(10, 11), vec![10, 11]);
+    }
+}
@@ -0,0 +1,439 @@
+use std::path::Path;
+
+use alloy_primitives::{Address, Log, B256, U256};
+use alloy_sol_types::SolEvent;
+use brontes_classifier::Transfer;
+use brontes_core::decoding::TracingProvider;
+use brontes_database::libmdbx::{DBWriter, LibmdbxReader};
+use brontes_types::{
+    constants::{USDT_ADDRESS_STRING, WETH_ADDRESS},
+    db::{
+        dex::{DexQuotes, PriceAt},
+        mev_block::MevBlockWithClassified,
+    },
+    init_thread_pools,
+    mev::{BundleData, Sandwich, VictimLossAmount},
+    normalized_actions::NormalizedSwap,
+    pair::Pair,
+    traits::ReplayedTransaction,
+    ToScaledRational,
+};
+use clap::Parser;
+use malachite::{num::basic::traits::Zero, Rational};
+
+use crate::{
+    cli::{determine_max_tasks, get_env_vars, get_tracing_provider, load_libmdbx, static_object},
+    runner::CliContext,
+};
+
+#[derive(Debug, Parser)]
+pub struct SimulateVictims {
+    /// Start Block
+    #[arg(long, short)]
+    pub start_block: u64,
+    /// End Block, inclusive
+    #[arg(long, short)]
+    pub end_block:   u64,
+    /// Quote asset the usd loss is priced in, defaults to USDT
+    #[arg(long, short, default_value = USDT_ADDRESS_STRING)]
+    pub quote_asset: String,
+}
+
+impl SimulateVictims {
+    pub async fn execute(self, brontes_db_endpoint: String, ctx: CliContext) -> eyre::Result<()> {
+        let db_path = get_env_vars()?;
+        let quote_asset = self.quote_asset.parse()?;
+
+        let max_tasks = determine_max_tasks(None);
+        init_thread_pools(max_tasks as usize);
+
+        let libmdbx = static_object(load_libmdbx(&ctx.task_executor, brontes_db_endpoint)?);
+        let tracer =
+            get_tracing_provider(Path::new(&db_path), max_tasks, ctx.task_executor.clone());
+
+        let mev_blocks = self.fetch_mev_blocks(libmdbx)?;
+        let mut stats = SimulationStats::default();
+
+        for MevBlockWithClassified { block, mut mev } in mev_blocks {
+            let block_number = block.block_number;
+            let quotes = libmdbx
+                .has_dex_quotes(block_number)?
+                .then(|| libmdbx.get_dex_quotes(block_number))
+                .transpose()?;
+
+            let mut updated = false;
+            for bundle in &mut mev {
+                let BundleData::Sandwich(sandwich) = &mut bundle.data else { continue };
+                // the state between the blocks would have to be rebuilt as well
+                if sandwich.is_multi_block() {
+                    tracing::debug!(
+                        block_number,
+                        backrun = ?sandwich.backrun_tx_hash,
+                        "skipping multi block sandwich"
+                    );
+                    stats.multi_block_sandwiches += 1;
+                    continue
+                }
+
+                match simulate_sandwich(&tracer, sandwich, quotes.as_ref(), quote_asset).await {
+                    Ok((losses, unmeasured_victims)) => {
+                        stats.simulated_sandwiches += 1;
+                        stats.measured_victims += losses.len();
+                        stats.unmeasured_victims += unmeasured_victims;
+                        sandwich.victim_losses = losses;
+                        updated = true;
+                    }
+                    Err(e) => {
+                        tracing::warn!(
+                            block_number,
+                            backrun = ?sandwich.backrun_tx_hash,
+                            error = %e,
+                            "failed to simulate sandwich victims"
+                        );
+                        stats.failed_sandwiches += 1;
+                    }
+                }
+            }
+
+            if updated {
+                libmdbx.save_mev_blocks(block_number, block, mev).await?;
+            }
+        }
+
+        tracing::info!(
+            simulated_sandwiches = stats.simulated_sandwiches,
+            failed_sandwiches = stats.failed_sandwiches,
+            multi_block_sandwiches = stats.multi_block_sandwiches,
+            measured_victims = stats.measured_victims,
+            unmeasured_victims = stats.unmeasured_victims,
+            "finished simulating sandwich victims"
+        );
+
+        Ok(())
+    }
+
+    /// The mev blocks of the inclusive block range
+    fn fetch_mev_blocks<DB: LibmdbxReader>(
+        &self,
+        db: &DB,
+    ) -> eyre::Result<Vec<MevBlockWithClassified>> {
+        db.try_fetch_mev_blocks(Some(self.start_block), self.end_block)
+    }
+}
+
+/// What was skipped while simulating, so that the stored losses can be put
+/// into relation
+#[derive(Debug, Default)]
+struct SimulationStats {
+    simulated_sandwiches:   usize,
+    /// Sandwiches whose frontruns or victims couldn't be replayed
+    failed_sandwiches:      usize,
+    /// Sandwiches that span two blocks, which aren't simulated
+    multi_block_sandwiches: usize,
+    measured_victims:       usize,
+    /// Victims that reverted in either replay or whose output couldn't be
+    /// found, they have no loss stored
+    unmeasured_victims:     usize,
+}
+
+/// Replays every victim of the sandwich with and without the frontruns.
+/// Returns the losses of the victims that could be measured and the number of
+/// victims that couldn't, because they reverted in either replay or their
+/// output wasn't received by them or the swap recipient.
+async fn simulate_sandwich<T: TracingProvider>(
+    tracer: &T,
+    sandwich: &Sandwich,
+    quotes: Option<&DexQuotes>,
+    quote_asset: Address,
+) -> eyre::Result<(Vec<VictimLossAmount>, usize)> {
+    let mut frontruns = Vec::with_capacity(sandwich.frontrun_tx_hash.len());
+    for tx_hash in &sandwich.frontrun_tx_hash {
+        frontruns.push(tracer.block_and_tx_index(*tx_hash).await?.1);
+    }
+
+    let mut losses = Vec::new();
+    let mut unmeasured = 0;
+    for (tx_hash, swaps) in sandwich
+        .victim_swaps_tx_hashes
+        .iter()
+        .flatten()
+        .zip(&sandwich.victim_swaps)
+    {
+        let Some(output) = swaps.last() else {
+            tracing::debug!(?tx_hash, "victim has no swaps");
+            unmeasured += 1;
+            continue
+        };
+        let (block_number, tx_index) = tracer.block_and_tx_index(*tx_hash).await?;
+
+        let actual = tracer
+            .replay_transaction_without(block_number, tx_index, vec![])
+            .await?;
+        let counterfactual = tracer
+            .replay_transaction_without(block_number, tx_index, frontruns.clone())
+            .await?;
+
+        let price = quotes
+            .and_then(|quotes| {
+                quotes.price_at(Pair(output.token_out.address, quote_asset), tx_index)
+            })
+            .map(|price| price.get_price(PriceAt::Average));
+
+        match victim_loss(*tx_hash, output, &actual, &counterfactual, price) {
+            Some(loss) => losses.push(loss),
+            None => {
+                tracing::debug!(?tx_hash, "couldn't measure victim output");
+                unmeasured += 1;
+            }
+        }
+    }
+
+    Ok((losses, unmeasured))
+}
+
+/// The loss of a victim whose last swap is `output`, measured by the amount of
+/// the output token received in both replays. Routers often swap to themselves
+/// and forward the output or unwrap it to eth, so what the victim itself
+/// received takes precedence over the recipient of the swap. Eth counts as
+/// weth.
+fn victim_loss(
+    tx_hash: B256,
+    output: &NormalizedSwap,
+    actual: &ReplayedTransaction,
+    counterfactual: &ReplayedTransaction,
+    price: Option<Rational>,
+) -> Option<VictimLossAmount> {
+    if !actual.is_success || !counterfactual.is_success {
+        return None
+    }
+
+    let token = &output.token_out;
+    let (recipient, amount_out) = [actual.from, output.recipient]
+        .into_iter()
+        .map(|account| (account, received(actual, token.address, account)))
+        .find(|(_, amount)| !amount.is_zero())?;
+    let counterfactual_amount_out = received(counterfactual, token.address, recipient);
+
+    let amount_out = amount_out.to_scaled_rational(token.inner.decimals);
+    let counterfactual_amount_out =
+        counterfactual_amount_out.to_scaled_rational(token.inner.decimals);
+    let token_amount_lost = &counterfactual_amount_out - &amount_out;
+    let amount_lost_usd = price
+        .map(|price| &token_amount_lost * price)
+        .unwrap_or(Rational::ZERO);
+
+    Some(VictimLossAmount {
+        tx_hash,
+        victim_eoa: actual.from,
+        token: token.clone(),
+        amount_out,
+        counterfactual_amount_out,
+        token_amount_lost,
+        amount_lost_usd,
+    })
+}
+
+/// The amount of `token` the account received in the replay, including eth if
+/// the token is weth
+fn received(replay: &ReplayedTransaction, token: Address, account: Address) -> U256 {
+    let amount = transferred_to(&replay.logs, token, account);
+
+    if token == WETH_ADDRESS {
+        amount.saturating_add(replay.eth_received(account))
+    } else {
+        amount
+    }
+}
+
+/// Sum of all erc20 transfers of `token` to `recipient`
+fn transferred_to(logs: &[Log], token: Address, recipient: Address) -> U256 {
+    logs.iter()
+        .filter(|log| log.address == token)
+        .filter_map(|log| {
+            let topics = log.topics();
+            if topics.len() != 3 || topics[0] != Transfer::SIGNATURE_HASH {
+                return None
+            }
+            (Address::from_word(topics[2]) == recipient)
+                .then(|| U256::try_from_be_slice(&log.data.data))
+                .flatten()
+        })
+        .fold(U256::ZERO, |acc, amount| acc.saturating_add(amount))
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{address, b256, LogData, I256};
+    use brontes_database::libmdbx::{
+        tables::{MevBlocks, MevBlocksData},
+        LibmdbxReadWriter,
+    };
+    use brontes_types::{
+        db::token_info::{TokenInfo, TokenInfoWithAddress},
+        mev::MevBlock,
+    };
+
+    use super::*;
+
+    const WETH: Address = address!("C02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2");
+    const VICTIM: Address = address!("eeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee");
+    const POOL: Address = address!("dddddddddddddddddddddddddddddddddddddddd");
+
+    fn transfer(token: Address, to: Address, amount: u128) -> Log {
+        Log {
+            address: token,
+            data:    LogData::new_unchecked(
+                vec![Transfer::SIGNATURE_HASH, POOL.into_word(), to.into_word()],
+                U256::from(amount).to_be_bytes_vec().into(),
+            ),
+        }
+    }
+
+    fn replay(is_success: bool, logs: Vec<Log>) -> ReplayedTransaction {
+        ReplayedTransaction { from: VICTIM, is_success, logs, eth_balance_deltas: vec![] }
+    }
+
+    fn eth_replay(eth_received: u128) -> ReplayedTransaction {
+        ReplayedTransaction {
+            eth_balance_deltas: vec![(VICTIM, I256::from_raw(U256::from(eth_received)))],
+            ..replay(true, vec![])
+        }
+    }
+
+    fn weth_swap() -> NormalizedSwap {
+        NormalizedSwap {
+            recipient: VICTIM,
+            token_out: TokenInfoWithAddress {
+                address: WETH,
+                inner:   TokenInfo { decimals: 18, symbol: "WETH".to_string() },
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_fetches_start_block() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = LibmdbxReadWriter::init_db_tests(dir.path()).unwrap();
+        let blocks = [10, 11].map(|block_number| {
+            MevBlocksData::new(
+                block_number,
+                MevBlockWithClassified {
+                    block: MevBlock { block_number, ..Default::default() },
+                    mev:   vec![],
+                },
+            )
+        });
+        db.db.write_table::<MevBlocks, _>(&blocks).unwrap();
+
+        let block_numbers = |start_block, end_block| {
+            SimulateVictims { start_block, end_block, quote_asset: String::new() }
+                .fetch_mev_blocks(&db)
+                .unwrap()
+                .into_iter()
+                .map(|mev_block| mev_block.block.block_number)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(block_numbers(10, 10), vec![10]);
+        assert_eq!(block_numbers(10, 11), vec![10, 11]);
+        assert_eq!(block_numbers(11, 11), vec![11]);
+    }
+
+    #[test]
+    fn test_transferred_to() {
+        let other = address!("1111111111111111111111111111111111111111");
+        let logs = vec![
+            transfer(WETH, VICTIM, 10),
+            transfer(WETH, other, 20),
+            transfer(other, VICTIM, 40),
+            transfer(WETH, VICTIM, 5),
+            // approvals share the layout but not the signature
+            Log {
+                address: WETH,
+                data:    LogData::new_unchecked(
+                    vec![B256::ZERO, POOL.into_word(), VICTIM.into_word()],
+                    U256::from(100).to_be_bytes_vec().into(),
+                ),
+            },
+        ];
+
+        assert_eq!(transferred_to(&logs, WETH, VICTIM), U256::from(15));
+        assert_eq!(transferred_to(&logs, WETH, POOL), U256::ZERO);
+    }
+
+    #[test]
+    fn test_victim_loss() {
+        let tx_hash = b256!("2222222222222222222222222222222222222222222222222222222222222222");
+        let actual = replay(true, vec![transfer(WETH, VICTIM, 9 * 10u128.pow(17))]);
+        let counterfactual = replay(true, vec![transfer(WETH, VICTIM, 10u128.pow(18))]);
+
+        let loss = victim_loss(
+            tx_hash,
+            &weth_swap(),
+            &actual,
+            &counterfactual,
+            Some(Rational::from(3000)),
+        )
+        .unwrap();
+
+        assert_eq!(loss.victim_eoa, VICTIM);
+        assert_eq!(loss.amount_out, Rational::from_unsigneds(9u8, 10u8));
+        assert_eq!(loss.counterfactual_amount_out, Rational::from(1));
+        assert_eq!(loss.token_amount_lost, Rational::from_unsigneds(1u8, 10u8));
+        assert_eq!(loss.amount_lost_usd, Rational::from(300));
+
+        // without a price the usd loss is zero
+        let loss = victim_loss(tx_hash, &weth_swap(), &actual, &counterfactual, None).unwrap();
+        assert_eq!(loss.amount_lost_usd, Rational::ZERO);
+    }
+
+    #[test]
+    fn test_victim_loss_through_router() {
+        let router = address!("3333333333333333333333333333333333333333");
+        let tx_hash = B256::ZERO;
+        let mut output = weth_swap();
+        output.recipient = router;
+
+        // the router unwraps the weth and sends the victim eth
+        let unwrapped = |amount| {
+            let mut replay = eth_replay(amount);
+            replay.logs.push(transfer(WETH, router, amount));
+            replay
+        };
+        let loss = victim_loss(
+            tx_hash,
+            &output,
+            &unwrapped(9 * 10u128.pow(17)),
+            &unwrapped(10u128.pow(18)),
+            None,
+        )
+        .unwrap();
+        assert_eq!(loss.amount_out, Rational::from_unsigneds(9u8, 10u8));
+        assert_eq!(loss.token_amount_lost, Rational::from_unsigneds(1u8, 10u8));
+
+        // the output stays with the swap recipient
+        let actual = replay(true, vec![transfer(WETH, router, 9 * 10u128.pow(17))]);
+        let counterfactual = replay(true, vec![transfer(WETH, router, 10u128.pow(18))]);
+        let loss = victim_loss(tx_hash, &output, &actual, &counterfactual, None).unwrap();
+        assert_eq!(loss.victim_eoa, VICTIM);
+        assert_eq!(loss.token_amount_lost, Rational::from_unsigneds(1u8, 10u8));
+    }
+
+    #[test]
+    fn test_unmeasurable_victims() {
+        let tx_hash = B256::ZERO;
+        let output = replay(true, vec![transfer(WETH, VICTIM, 100)]);
+
+        assert!(victim_loss(tx_hash, &weth_swap(), &replay(false, vec![]), &output, None).is_none());
+        assert!(victim_loss(tx_hash, &weth_swap(), &output, &replay(false, vec![]), None).is_none());
+        assert!(victim_loss(tx_hash, &weth_swap(), &replay(true, vec![]), &output, None).is_none());
+
+        // eth only counts for weth
+        let mut usdc_swap = weth_swap();
+        usdc_swap.token_out.address = address!("A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48");
+        assert!(
+            victim_loss(tx_hash, &usdc_swap, &eth_replay(100), &eth_replay(200), None).is_none()
+        );
+    }
+}
@@ -0,0 +1,634 @@
+use std::{
+    fmt::Write,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use alloy_primitives::{hex, Address, B256};
+use brontes_classifier::{
+    declarative::{init_declarative_classifiers, DeclarativeClassifiers},
+    Classifier,
+};
+use brontes_core::decoding::{Parser as DParser, TracingProvider};
+use brontes_inspect::{
+    composer::{init_composition_config, run_block_inspection, ComposerResults, CompositionConfig},
+    rejections::{record_rejections, Rejection},
+    sniping::DEFAULT_SNIPING_MARKOUT_BLOCKS,
+    InspectorSelection,
+};
+use brontes_types::{
+    constants::{START_OF_CHAINBOUND_MEMPOOL_DATA, USDT_ADDRESS_STRING},
+    db::{
+        cex::{trades::CexDexTradeConfig, CexExchange},
+        dex::DexPrices,
+        metadata::Metadata,
+        traits::LibmdbxReader,
+    },
+    init_thread_pools,
+    mev::{Bundle, Mev},
+    normalized_actions::{accounting::ActionAccounting, Action},
+    pair::Pair,
+    structured_trace::TraceActions,
+    tree::{Node, Root},
+    BlockData, FastHashMap, MultiBlockData, ToFloatNearest, ToScaledRational,
+};
+use clap::Parser;
+use eyre::{eyre, WrapErr};
+use itertools::Itertools;
+use tokio::sync::mpsc::unbounded_channel;
+
+use super::{
+    determine_max_tasks, get_env_vars, get_tracing_provider, init_inspectors, load_libmdbx,
+    static_object, PluginArgs,
+};
+use crate::runner::CliContext;
+
+#[derive(Debug, Parser)]
+pub struct ExplainArgs {
+    /// Hash of the transaction to explain
+    pub tx_hash:         B256,
+    /// Optional quote asset, if omitted it will default to USDT
+    #[arg(long, short, default_value = USDT_ADDRESS_STRING)]
+    pub quote_asset:     String,
+    /// Inspectors to run, built-in ones or `plugin:<name>` for a plugin in
+    /// `--plugin-dir`. If omitted it defaults to running all inspectors
+    #[arg(long, short, value_delimiter = ',')]
+    pub inspectors:      Option<Vec<InspectorSelection>>,
+    /// CEX exchanges to consider for cex-dex analysis
+    #[arg(
+        long,
+        short,
+        default_value = "Binance,Coinbase,Okex,BybitSpot,Kucoin",
+        value_delimiter = ','
+    )]
+    pub cex_exchanges:   Vec<CexExchange>,
+    /// TOML file with the composition and dedup rules of the composer. If
+    /// omitted it defaults to `config/composition_config.toml`
+    #[arg(long)]
+    pub compose_rules:   Option<PathBuf>,
+    /// TOML file with classifiers defined from contract ABIs, see
+    /// `config/declarative_classifiers.toml`
+    #[arg(long)]
+    pub classifier_defs: Option<PathBuf>,
+    /// WASM inspector plugins
+    #[clap(flatten)]
+    pub plugin_args:     PluginArgs,
+}
+
+impl ExplainArgs {
+    pub async fn execute(self, brontes_db_endpoint: String, ctx: CliContext) -> eyre::Result<()> {
+        let reth_db_path = get_env_vars()?;
+        let quote_asset = self.quote_asset.parse()?;
+        if let Some(path) = &self.compose_rules {
+            init_composition_config(CompositionConfig::load(path)?)?;
+        }
+        if let Some(path) = &self.classifier_defs {
+            init_declarative_classifiers(DeclarativeClassifiers::load(path)?)?;
+        }
+
+        let max_tasks = determine_max_tasks(None);
+        init_thread_pools(max_tasks as usize);
+
+        // the parser and classifier expect a listener on the other end
+        let (metrics_tx, _metrics_rx) = unbounded_channel();
+        let (pricing_tx, _pricing_rx) = unbounded_channel();
+
+        let libmdbx = static_object(load_libmdbx(&ctx.task_executor, brontes_db_endpoint)?);
+        let tracer =
+            get_tracing_provider(Path::new(&reth_db_path), max_tasks, ctx.task_executor.clone());
+        let parser = static_object(DParser::new(metrics_tx, libmdbx, tracer).await);
+
+        let (block, tx_idx) = parser
+            .get_tracer()
+            .block_and_tx_index(self.tx_hash)
+            .await
+            .wrap_err("failed to find the transaction")?;
+        let (traces, header) = parser
+            .execute(block, 0, None)
+            .await
+            .ok_or_else(|| eyre!("failed to trace block {block}"))?;
+
+        let mut out = String::new();
+        writeln!(out, "Transaction {:?} (block {block}, index {tx_idx})", self.tx_hash)?;
+
+        if traces.get(tx_idx).is_some_and(|trace| !trace.is_success) {
+            writeln!(out, "\nThe transaction reverted, reverted transactions aren't classified")?;
+            println!("{out}");
+            return Ok(())
+        }
+
+        let classifier = Classifier::new(libmdbx, pricing_tx, parser.get_tracer());
+        let mut tree = classifier.build_block_tree(traces, header, false).await;
+        let root = tree
+            .get_root(self.tx_hash)
+            .ok_or_else(|| eyre!("transaction {:?} is missing from the tree", self.tx_hash))?;
+        let token_symbol = |token| fetch_token_symbol(libmdbx, token);
+
+        write_call_tree(&mut out, root)?;
+        write_token_deltas(&mut out, root, token_symbol)?;
+
+        let metadata = match load_metadata(libmdbx, block, quote_asset) {
+            Ok(metadata) => metadata,
+            Err(e) => {
+                writeln!(out, "\nNo metadata for block {block}, inspectors can't run: {e}")?;
+                println!("{out}");
+                return Ok(())
+            }
+        };
+        write_dex_prices(&mut out, &metadata, tx_idx, token_symbol)?;
+
+        if block >= START_OF_CHAINBOUND_MEMPOOL_DATA {
+            tree.label_private_txes(&metadata);
+        }
+
+        let inspectors = init_inspectors(
+            quote_asset,
+            libmdbx,
+            self.inspectors,
+            self.cex_exchanges,
+            CexDexTradeConfig::default(),
+            DEFAULT_SNIPING_MARKOUT_BLOCKS,
+            false,
+            &self.plugin_args,
+        )?;
+        let data = MultiBlockData {
+            per_block_data: vec![BlockData {
+                metadata: Arc::new(metadata),
+                tree:     Arc::new(tree),
+            }],
+            blocks:         1,
+        };
+
+        writeln!(out, "\nInspectors")?;
+        for inspector in inspectors {
+            let window = inspector.block_window();
+            let outcome = if window > 1 {
+                format!("skipped, needs a {window} block window")
+            } else {
+                let (bundles, rejections) =
+                    record_rejections(|| inspector.inspect_block(data.clone()));
+                describe_outcome(&bundles, &rejections, self.tx_hash)
+            };
+            writeln!(out, "  {:<20} {outcome}", inspector.get_id())?;
+        }
+
+        let ComposerResults { mev_details, possible_mev_txes, .. } =
+            run_block_inspection(inspectors, data, libmdbx);
+        let bundles = mev_details
+            .into_iter()
+            .filter(|bundle| bundle_contains_tx(bundle, self.tx_hash))
+            .collect_vec();
+
+        writeln!(out, "\nResult")?;
+        writeln!(
+            out,
+            "  {}",
+            describe_bundles(
+                &bundles,
+                "not classified as mev, matching bundles were dropped during composition and \
+                 deduplication or none matched"
+            )
+        )?;
+        if let Some(possible) = possible_mev_txes
+            .0
+            .iter()
+            .find(|possible| possible.tx_hash == self.tx_hash)
+        {
+            writeln!(out, "  flagged as possible mev: {}", possible.triggers)?;
+        }
+
+        println!("{out}");
+
+        Ok(())
+    }
+}
+
+fn load_metadata<DB: LibmdbxReader>(
+    db: &DB,
+    block: u64,
+    quote_asset: Address,
+) -> eyre::Result<Metadata> {
+    if db.has_dex_quotes(block)? {
+        db.get_metadata(block, quote_asset)
+    } else {
+        db.get_metadata_no_dex_price(block, quote_asset)
+    }
+}
+
+fn fetch_token_symbol<DB: LibmdbxReader>(db: &DB, token: Address) -> String {
+    db.try_fetch_token_info(token)
+        .map(|info| info.symbol.clone())
+        .unwrap_or_else(|_| format!("{token:?}"))
+}
+
+fn bundle_contains_tx(bundle: &Bundle, tx_hash: B256) -> bool {
+    bundle.data.mev_transaction_hashes().contains(&tx_hash)
+}
+
+/// Describes the bundles of an inspector that contain the transaction, or the
+/// checks it failed if there are none
+fn describe_outcome(bundles: &[Bundle], rejections: &[Rejection], tx_hash: B256) -> String {
+    let bundles = bundles
+        .iter()
+        .filter(|bundle| bundle_contains_tx(bundle, tx_hash))
+        .cloned()
+        .collect_vec();
+    if !bundles.is_empty() {
+        return describe_bundles(&bundles, "")
+    }
+
+    let reasons = rejections
+        .iter()
+        .filter(|rejection| rejection.contains(&tx_hash))
+        .map(ToString::to_string)
+        .unique()
+        .collect_vec();
+    if reasons.is_empty() {
+        return "no bundle contains the transaction".to_string()
+    }
+
+    format!("rejected, {}", reasons.join("; "))
+}
+
+fn describe_bundles(bundles: &[Bundle], none: &str) -> String {
+    if bundles.is_empty() {
+        return none.to_string()
+    }
+
+    bundles
+        .iter()
+        .map(|bundle| {
+            format!(
+                "{} bundle with ${:.2} profit",
+                bundle.header.mev_type, bundle.header.profit_usd
+            )
+        })
+        .join(", ")
+}
+
+/// Writes every call of the transaction with the actions it was classified as.
+fn write_call_tree(out: &mut impl Write, root: &Root<Action>) -> std::fmt::Result {
+    writeln!(out, "\nCall tree")?;
+    write_node(out, root, &root.head, 1)
+}
+
+fn write_node(
+    out: &mut impl Write,
+    root: &Root<Action>,
+    node: &Node,
+    depth: usize,
+) -> std::fmt::Result {
+    let actions = root
+        .data_store
+        .get_ref(node.data)
+        .map(|actions| actions.iter().map(describe_action).join("; "))
+        .unwrap_or_else(|| "merged into a parent action".to_string());
+
+    writeln!(
+        out,
+        "{:indent$}{:?} {:?}: {actions}",
+        "",
+        node.trace_address,
+        node.address,
+        indent = depth * 2
+    )?;
+
+    node.inner
+        .iter()
+        .try_for_each(|child| write_node(out, root, child, depth + 1))
+}
+
+fn describe_action(action: &Action) -> String {
+    match action {
+        Action::Swap(swap) => swap.to_string(),
+        Action::SwapWithFee(swap) => swap.swap.to_string(),
+        Action::Mint(mint) => mint.to_string(),
+        Action::Burn(burn) => burn.to_string(),
+        Action::Collect(collect) => collect.to_string(),
+        Action::Liquidation(liquidation) => liquidation.to_string(),
+        Action::Loan(loan) => loan.to_string(),
+        Action::Repayment(repayment) => repayment.to_string(),
+        Action::Deposit(deposit) => deposit.to_string(),
+        Action::Withdraw(withdraw) => withdraw.to_string(),
+        Action::VaultDeposit(deposit) => deposit.to_string(),
+        Action::VaultWithdraw(withdraw) => withdraw.to_string(),
+        Action::OracleUpdate(update) => update.to_string(),
+        Action::Transfer(transfer) => format!(
+            "Transfer {:.4} {} from {:?} to {:?}",
+            transfer.amount.clone().to_float(),
+            transfer.token.symbol,
+            transfer.from,
+            transfer.to
+        ),
+        Action::EthTransfer(transfer) => format!(
+            "{}Transfer {:.4} ETH from {:?} to {:?}",
+            if transfer.coinbase_transfer { "Coinbase " } else { "" },
+            transfer.value.to_scaled_rational(18).to_float(),
+            transfer.from,
+            transfer.to
+        ),
+        Action::FlashLoan(flash_loan) => format!(
+            "FlashLoan of {} from {:?} via {}",
+            flash_loan
+                .assets
+                .iter()
+                .map(|asset| &asset.symbol)
+                .join(", "),
+            flash_loan.pool,
+            flash_loan.protocol
+        ),
+        Action::Batch(batch) => format!(
+            "Batch of {} user swaps via {} settled by {:?}",
+            batch.user_swaps.len(),
+            batch.protocol,
+            batch.solver
+        ),
+        Action::Aggregator(aggregator) => format!(
+            "Aggregator {} swap from {:?} to {:?}",
+            aggregator.protocol, aggregator.from, aggregator.recipient
+        ),
+        Action::NewPool(pool) => format!("NewPool {:?} on {}", pool.pool_address, pool.protocol),
+        Action::PoolConfigUpdate(pool) => {
+            format!("PoolConfigUpdate {:?} on {}", pool.pool_address, pool.protocol)
+        }
+        Action::SelfDestruct(selfdestruct) => {
+            format!("SelfDestruct {:?}", selfdestruct.get_address())
+        }
+        Action::Unclassified(trace) => match &trace.decoded_data {
+            Some(decoded) => format!(
+                "Unclassified {}({})",
+                decoded.function_name,
+                decoded
+                    .call_data
+                    .iter()
+                    .map(|param| format!(
+                        "{} {}: {}",
+                        param.field_type, param.field_name, param.value
+                    ))
+                    .join(", ")
+            ),
+            None => {
+                let calldata = trace.get_calldata();
+                if calldata.len() < 4 {
+                    "Unclassified call without calldata".to_string()
+                } else {
+                    format!("Unclassified call to selector 0x{}", hex::encode(&calldata[..4]))
+                }
+            }
+        },
+        Action::Revert => "Revert".to_string(),
+    }
+}
+
+/// Writes the net token balance changes of every address the transaction
+/// touched.
+fn write_token_deltas(
+    out: &mut impl Write,
+    root: &Root<Action>,
+    token_symbol: impl Fn(Address) -> String,
+) -> std::fmt::Result {
+    let deltas = root
+        .data_store
+        .0
+        .iter()
+        .flatten()
+        .flatten()
+        .cloned()
+        .account_for_actions();
+
+    writeln!(out, "\nToken deltas")?;
+    if deltas.values().all(|tokens| tokens.is_empty()) {
+        return writeln!(out, "  none")
+    }
+
+    for (address, tokens) in deltas.into_iter().sorted_by_key(|(address, _)| *address) {
+        if tokens.is_empty() {
+            continue
+        }
+        writeln!(out, "  {address:?}")?;
+        for (token, amount) in tokens.into_iter().sorted_by_key(|(token, _)| *token) {
+            writeln!(out, "    {:+.6} {}", amount.to_float(), token_symbol(token))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes the dex prices that were calculated after the transaction
+fn write_dex_prices(
+    out: &mut impl Write,
+    metadata: &Metadata,
+    tx_idx: usize,
+    token_symbol: impl Fn(Address) -> String,
+) -> std::fmt::Result {
+    writeln!(out, "\nDEX prices")?;
+    let Some(quotes) = metadata.dex_quotes.as_ref() else {
+        return writeln!(out, "  no dex pricing for this block, only token pnl can be calculated")
+    };
+    let Some(prices) = quotes.0.get(tx_idx).and_then(Option::as_ref) else {
+        return writeln!(out, "  none")
+    };
+
+    write_prices(out, prices, token_symbol)
+}
+
+fn write_prices(
+    out: &mut impl Write,
+    prices: &FastHashMap<Pair, DexPrices>,
+    token_symbol: impl Fn(Address) -> String,
+) -> std::fmt::Result {
+    for (pair, price) in prices.iter().sorted_by_key(|(pair, _)| (pair.0, pair.1)) {
+        writeln!(
+            out,
+            "  {}/{} pre {:.6} post {:.6}{}",
+            token_symbol(pair.0),
+            token_symbol(pair.1),
+            price.pre_state.clone().to_float(),
+            price.post_state.clone().to_float(),
+            if price.is_transfer { " (transfer)" } else { "" }
+        )?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{address, b256, Bytes, U256};
+    use brontes_inspect::rejections::RejectionCheck;
+    use brontes_types::{
+        db::token_info::{TokenInfo, TokenInfoWithAddress},
+        normalized_actions::NormalizedTransfer,
+        structured_trace::{DecodedCallData, DecodedParams, TransactionTraceWithLogs},
+        tree::{GasDetails, NodeData},
+    };
+    use malachite::Rational;
+    use reth_rpc_types::trace::parity::{
+        Action as TraceAction, CallAction, CallType, TransactionTrace,
+    };
+
+    use super::*;
+
+    const ROUTER: Address = address!("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa");
+    const TOKEN: Address = address!("bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb");
+    const SENDER: Address = address!("cccccccccccccccccccccccccccccccccccccccc");
+
+    fn call(to: Address, input: Bytes, decoded_data: Option<DecodedCallData>) -> Action {
+        Action::Unclassified(TransactionTraceWithLogs {
+            trace: TransactionTrace {
+                action:        TraceAction::Call(CallAction {
+                    from: SENDER,
+                    to,
+                    value: U256::ZERO,
+                    gas: Default::default(),
+                    input,
+                    call_type: CallType::Call,
+                }),
+                error:         None,
+                result:        None,
+                trace_address: vec![],
+                subtraces:     0,
+            },
+            logs: vec![],
+            msg_sender: SENDER,
+            trace_idx: 0,
+            decoded_data,
+        })
+    }
+
+    fn root() -> Root<Action> {
+        let execute = call(
+            ROUTER,
+            Bytes::from_static(&[0x12, 0x34, 0x56, 0x78, 0x00]),
+            Some(DecodedCallData {
+                function_name: "execute".to_string(),
+                call_data:     vec![DecodedParams {
+                    field_name: "amount".to_string(),
+                    field_type: "uint256".to_string(),
+                    value:      "100".to_string(),
+                }],
+                return_data:   vec![],
+            }),
+        );
+        let transfer = Action::Transfer(NormalizedTransfer {
+            trace_index: 1,
+            from: SENDER,
+            to: ROUTER,
+            token: TokenInfoWithAddress {
+                address: TOKEN,
+                inner:   TokenInfo { decimals: 18, symbol: "TKN".to_string() },
+            },
+            amount: Rational::from_signeds(3, 2),
+            ..Default::default()
+        });
+
+        let mut head = Node::new(0, ROUTER, vec![]);
+        let mut transfer_node = Node::new(1, TOKEN, vec![0]);
+        transfer_node.data = 1;
+        let mut merged_node = Node::new(2, TOKEN, vec![1]);
+        merged_node.data = 2;
+        head.inner = vec![transfer_node, merged_node];
+
+        Root {
+            head,
+            position: 0,
+            tx_hash: b256!("1111111111111111111111111111111111111111111111111111111111111111"),
+            private: false,
+            gas_details: GasDetails::default(),
+            total_msg_value_transfers: vec![],
+            data_store: NodeData(vec![Some(vec![execute]), Some(vec![transfer]), None]),
+        }
+    }
+
+    #[test]
+    fn test_call_tree() {
+        let mut out = String::new();
+        write_call_tree(&mut out, &root()).unwrap();
+
+        let lines = out.lines().skip(2).collect_vec();
+        assert_eq!(lines[0], format!("  [] {ROUTER:?}: Unclassified execute(uint256 amount: 100)"));
+        assert_eq!(
+            lines[1],
+            format!("    [0] {TOKEN:?}: Transfer 1.5000 TKN from {SENDER:?} to {ROUTER:?}")
+        );
+        assert_eq!(lines[2], format!("    [1] {TOKEN:?}: merged into a parent action"));
+    }
+
+    #[test]
+    fn test_undecoded_call() {
+        assert_eq!(
+            describe_action(&call(
+                ROUTER,
+                Bytes::from_static(&[0xa9, 0x05, 0x9c, 0xbb, 0x01]),
+                None
+            )),
+            "Unclassified call to selector 0xa9059cbb"
+        );
+        assert_eq!(
+            describe_action(&call(ROUTER, Bytes::new(), None)),
+            "Unclassified call without calldata"
+        );
+    }
+
+    #[test]
+    fn test_token_deltas() {
+        let mut out = String::new();
+        write_token_deltas(&mut out, &root(), |_| "TKN".to_string()).unwrap();
+
+        assert_eq!(
+            out,
+            format!(
+                "\nToken deltas\n  {ROUTER:?}\n    +1.500000 TKN\n  {SENDER:?}\n    -1.500000 \
+                 TKN\n"
+            )
+        );
+    }
+
+    #[test]
+    fn test_prices() {
+        let mut prices = FastHashMap::default();
+        prices.insert(
+            Pair(TOKEN, ROUTER),
+            DexPrices {
+                pre_state:    Rational::from(2),
+                post_state:   Rational::from_signeds(5, 2),
+                goes_through: Pair(TOKEN, ROUTER),
+                is_transfer:  true,
+            },
+        );
+
+        let mut out = String::new();
+        write_prices(&mut out, &prices, |token| {
+            if token == TOKEN { "TKN" } else { "USDT" }.to_string()
+        })
+        .unwrap();
+
+        assert_eq!(out, "  TKN/USDT pre 2.000000 post 2.500000 (transfer)\n");
+    }
+
+    #[test]
+    fn test_rejections() {
+        let tx_hash = B256::with_last_byte(1);
+        let rejection = |check, tx_hashes: &[B256], reason: &str| Rejection {
+            check,
+            tx_hashes: tx_hashes.to_vec(),
+            reason: reason.to_string(),
+        };
+        let rejections = [
+            rejection(RejectionCheck::Victims, &[tx_hash, B256::ZERO], "no victims"),
+            // the sandwich inspector checks the subsets of a possible sandwich again
+            rejection(RejectionCheck::Victims, &[tx_hash], "no victims"),
+            rejection(RejectionCheck::Profit, &[B256::ZERO], "not profitable"),
+            rejection(RejectionCheck::Ordering, &[tx_hash], "no common pool"),
+        ];
+
+        assert_eq!(
+            describe_outcome(&[], &rejections, tx_hash),
+            "rejected, failed victim check: no victims; failed ordering check: no common pool"
+        );
+        assert_eq!(
+            describe_outcome(&[], &rejections[2..3], tx_hash),
+            "no bundle contains the transaction"
+        );
+    }
+}
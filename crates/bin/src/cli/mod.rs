@@ -1,8 +1,10 @@
 use clap::{Parser, Subcommand};
 
 mod db;
+mod explain;
 mod misc;
 mod run;
+mod serve;
 mod utils;
 
 pub use utils::*;
@@ -35,4 +37,10 @@ pub enum Commands {
     /// Brontes database commands
     #[command(name = "db")]
     Database(db::Database),
+    /// Serve the brontes database over a read only http api
+    #[command(name = "serve")]
+    Serve(serve::ServeArgs),
+    /// Explain how brontes classified and inspected a transaction
+    #[command(name = "explain")]
+    Explain(explain::ExplainArgs),
 }
@@ -1,8 +1,20 @@
-use std::{path::Path, time::Duration};
+use std::{
+    net::{IpAddr, SocketAddr},
+    path::{Path, PathBuf},
+    time::Duration,
+};
 
+use brontes_classifier::{
+    declarative::{init_declarative_classifiers, DeclarativeClassifiers},
+    fork_detection::{init_fork_detector, ForkDetector, DEFAULT_SIMILARITY_THRESHOLD},
+};
 use brontes_core::decoding::Parser as DParser;
 use brontes_database::clickhouse::cex_config::CexDownloadConfig;
-use brontes_inspect::Inspectors;
+use brontes_inspect::{
+    composer::{init_composition_config, CompositionConfig},
+    sniping::DEFAULT_SNIPING_MARKOUT_BLOCKS,
+    InspectorSelection, Inspectors,
+};
 use brontes_metrics::ParserMetricsListener;
 use brontes_types::{
     constants::USDT_ADDRESS_STRING,
@@ -13,12 +25,14 @@ use brontes_types::{
 use clap::Parser;
 use tokio::sync::mpsc::unbounded_channel;
 
-use super::{determine_max_tasks, get_env_vars, load_clickhouse, load_database, static_object};
+use super::{
+    determine_max_tasks, get_env_vars, load_clickhouse, load_database, static_object, PluginArgs,
+};
 use crate::{
     banner::rain,
     cli::{get_tracing_provider, init_inspectors, load_tip_database},
     runner::CliContext,
-    BrontesRunConfig, MevProcessor, RangeType,
+    server, BrontesRunConfig, MevProcessor, RangeType,
 };
 
 const SECONDS_TO_US_FLOAT: f64 = 1_000_000.0;
@@ -47,9 +61,33 @@ pub struct RunArgs {
     /// Optional quote asset, if omitted it will default to USDT
     #[arg(long, short, default_value = USDT_ADDRESS_STRING)]
     pub quote_asset:          String,
-    /// Inspectors to run. If omitted it defaults to running all inspectors
+    /// Inspectors to run, built-in ones or `plugin:<name>` for a plugin in
+    /// `--plugin-dir`. If omitted it defaults to running all inspectors
     #[arg(long, short, value_delimiter = ',')]
-    pub inspectors:           Option<Vec<Inspectors>>,
+    pub inspectors:           Option<Vec<InspectorSelection>>,
+    /// Number of blocks after a token launch at which the positions of its
+    /// snipers are marked to market
+    #[arg(long, default_value_t = DEFAULT_SNIPING_MARKOUT_BLOCKS)]
+    pub snipe_markout_blocks: usize,
+    /// TOML file with the composition and dedup rules of the composer. If
+    /// omitted it defaults to `config/composition_config.toml`
+    #[arg(long)]
+    pub compose_rules:        Option<PathBuf>,
+    /// TOML file with classifiers defined from contract ABIs, see
+    /// `config/declarative_classifiers.toml`
+    #[arg(long)]
+    pub classifier_defs:      Option<PathBuf>,
+    /// Registers unclassified pools that emit Uniswap V2 or V3 swap logs
+    /// when their bytecode matches a known pool, review them with `brontes
+    /// db fork-detections`
+    #[arg(long, default_value = "false")]
+    pub detect_forks:         bool,
+    /// Minimum bytecode similarity for a pool to be registered as a fork
+    #[arg(long, default_value_t = DEFAULT_SIMILARITY_THRESHOLD, requires = "detect_forks")]
+    pub fork_similarity:      f64,
+    /// WASM inspector plugins
+    #[clap(flatten)]
+    pub plugin_args:          PluginArgs,
     /// Time window arguments for cex data downloads
     #[clap(flatten)]
     pub time_window_args:     TimeWindowArgs,
@@ -94,6 +132,13 @@ pub struct RunArgs {
     /// stored in the Clickhouse database.
     #[arg(long, short)]
     pub run_id:               Option<u64>,
+    /// Serves the api, including the live `/stream` of processed blocks, on
+    /// this port
+    #[arg(long)]
+    pub api_port:             Option<u16>,
+    /// Address to serve the api on
+    #[arg(long, default_value = "127.0.0.1")]
+    pub api_host:             IpAddr,
 
     /// shows a cool display at startup
     #[arg(long, short, default_value_t = false)]
@@ -120,6 +165,17 @@ impl RunArgs {
         tracing::info!(target: "brontes", "got env vars");
         let quote_asset = self.quote_asset.parse()?;
         tracing::info!(target: "brontes", "parsed quote asset");
+        if let Some(path) = &self.compose_rules {
+            init_composition_config(CompositionConfig::load(path)?)?;
+            tracing::info!(target: "brontes", "loaded composition rules");
+        }
+        if let Some(path) = &self.classifier_defs {
+            init_declarative_classifiers(DeclarativeClassifiers::load(path)?)?;
+            tracing::info!(target: "brontes", "loaded declarative classifiers");
+        }
+        if self.detect_forks {
+            init_fork_detector(ForkDetector::new(self.fork_similarity))?;
+        }
         let task_executor = ctx.task_executor;
 
         let max_tasks = determine_max_tasks(self.max_tasks);
@@ -143,6 +199,16 @@ impl RunArgs {
         let tip = static_object(load_tip_database(libmdbx)?);
         tracing::info!(target: "brontes", "initialized libmdbx database");
 
+        if let Some(api_port) = self.api_port {
+            server::enable_bundle_feed();
+            let listen_addr = SocketAddr::new(self.api_host, api_port);
+            task_executor.spawn_critical("api", async move {
+                if let Err(e) = server::serve(listen_addr, libmdbx).await {
+                    tracing::error!(target: "brontes", err=%e, "api server failed");
+                }
+            });
+        }
+
         let load_window = self.load_time_window();
 
         let cex_download_config = CexDownloadConfig::new(
@@ -159,8 +225,8 @@ impl RunArgs {
             .inspectors
             .as_ref()
             .map(|f| {
-                f.len() == 1 && f.contains(&Inspectors::CexDex)
-                    || f.contains(&Inspectors::CexDexMarkout)
+                f.len() == 1 && f.contains(&Inspectors::CexDex.into())
+                    || f.contains(&Inspectors::CexDexMarkout.into())
             })
             .unwrap_or(false);
 
@@ -176,8 +242,10 @@ impl RunArgs {
             self.inspectors,
             self.cex_exchanges,
             trade_config,
+            self.snipe_markout_blocks,
             self.with_metrics,
-        );
+            &self.plugin_args,
+        )?;
 
         let tracer =
             get_tracing_provider(Path::new(&reth_db_path), max_tasks, task_executor.clone());
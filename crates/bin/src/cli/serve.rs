@@ -0,0 +1,24 @@
+use std::net::{IpAddr, SocketAddr};
+
+use clap::Parser;
+
+use super::{load_libmdbx, static_object};
+use crate::{runner::CliContext, server};
+
+#[derive(Debug, Parser)]
+pub struct ServeArgs {
+    /// Address to serve the api on
+    #[arg(long, default_value = "127.0.0.1")]
+    pub host: IpAddr,
+    /// Port to serve the api on
+    #[arg(long, default_value = "6924")]
+    pub port: u16,
+}
+
+impl ServeArgs {
+    pub async fn execute(self, brontes_db_endpoint: String, ctx: CliContext) -> eyre::Result<()> {
+        let libmdbx = static_object(load_libmdbx(&ctx.task_executor, brontes_db_endpoint)?);
+
+        server::serve(SocketAddr::new(self.host, self.port), libmdbx).await
+    }
+}
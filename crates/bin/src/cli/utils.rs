@@ -1,8 +1,11 @@
-use std::{env, path::Path};
+use std::{
+    env,
+    path::{Path, PathBuf},
+};
 
 use alloy_primitives::Address;
 #[cfg(not(feature = "local-reth"))]
-use brontes_core::local_provider::LocalProvider;
+use brontes_core::rpc_provider::RpcTracingProvider;
 #[cfg(feature = "local-clickhouse")]
 use brontes_database::clickhouse::clickhouse_config;
 #[cfg(feature = "local-clickhouse")]
@@ -16,7 +19,12 @@ use brontes_database::clickhouse::ReadOnlyMiddleware;
 #[cfg(feature = "local-clickhouse")]
 use brontes_database::clickhouse::{dbms::BrontesClickhouseData, ClickhouseBuffered};
 use brontes_database::{clickhouse::cex_config::CexDownloadConfig, libmdbx::LibmdbxReadWriter};
-use brontes_inspect::{Inspector, Inspectors};
+#[cfg(feature = "wasm-plugins")]
+use brontes_inspect::plugin::{plugin_path, PluginLimits, WasmInspector};
+use brontes_inspect::{
+    plugin::{discover_plugins, DEFAULT_PLUGIN_FUEL, DEFAULT_PLUGIN_MEMORY_MB},
+    Inspector, InspectorSelection, Inspectors,
+};
 use brontes_metrics::inspectors::OutlierMetrics;
 #[cfg(feature = "local-clickhouse")]
 use brontes_types::UnboundedYapperReceiver;
@@ -29,6 +37,7 @@ use brontes_types::{
     mev::Bundle,
     BrontesTaskExecutor,
 };
+use clap::Parser;
 use itertools::Itertools;
 #[cfg(feature = "local-reth")]
 use reth_tracing_ext::TracingClient;
@@ -120,11 +129,11 @@ pub async fn load_clickhouse(
 }
 
 #[cfg(not(feature = "local-reth"))]
-pub fn get_tracing_provider(_: &Path, _: u64, _: BrontesTaskExecutor) -> LocalProvider {
+pub fn get_tracing_provider(_: &Path, _: u64, _: BrontesTaskExecutor) -> RpcTracingProvider {
     let db_endpoint = env::var("RETH_ENDPOINT").expect("No db Endpoint in .env");
     let db_port = env::var("RETH_PORT").expect("No DB port.env");
     let url = format!("{db_endpoint}:{db_port}");
-    LocalProvider::new(url, 5)
+    RpcTracingProvider::new(url, 5)
 }
 
 #[cfg(feature = "local-reth")]
@@ -150,30 +159,101 @@ pub fn static_object<T>(obj: T) -> &'static T {
     &*Box::leak(Box::new(obj))
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn init_inspectors<DB: LibmdbxReader>(
     quote_token: Address,
     db: &'static DB,
-    inspectors: Option<Vec<Inspectors>>,
+    inspectors: Option<Vec<InspectorSelection>>,
     cex_exchanges: Vec<CexExchange>,
     trade_config: CexDexTradeConfig,
+    sniping_markout_blocks: usize,
     metrics: bool,
-) -> &'static [&'static dyn Inspector<Result = Vec<Bundle>>] {
+    plugin_args: &PluginArgs,
+) -> eyre::Result<&'static [&'static dyn Inspector<Result = Vec<Bundle>>]> {
     let mut res = Vec::new();
     let metrics = metrics.then(OutlierMetrics::new);
-    for inspector in inspectors
-        .map(|i| i.into_iter())
-        .unwrap_or_else(|| Inspectors::iter().collect_vec().into_iter())
-    {
-        res.push(inspector.init_mev_inspector(
-            quote_token,
-            db,
-            &cex_exchanges,
-            trade_config,
-            metrics.clone(),
-        ));
+    let inspectors = match inspectors {
+        Some(inspectors) => inspectors,
+        None => Inspectors::iter()
+            .map(InspectorSelection::Builtin)
+            .chain(
+                plugin_args
+                    .discover()?
+                    .into_iter()
+                    .map(InspectorSelection::Plugin),
+            )
+            .collect_vec(),
+    };
+
+    for inspector in inspectors {
+        match inspector {
+            InspectorSelection::Builtin(inspector) => res.push(inspector.init_mev_inspector(
+                quote_token,
+                db,
+                &cex_exchanges,
+                trade_config,
+                sniping_markout_blocks,
+                metrics.clone(),
+            )),
+            InspectorSelection::Plugin(name) => res.push(plugin_args.load(&name, quote_token)?),
+        }
     }
 
-    &*Box::leak(res.into_boxed_slice())
+    Ok(&*Box::leak(res.into_boxed_slice()))
+}
+
+#[derive(Debug, Clone, Parser)]
+pub struct PluginArgs {
+    /// Directory with the WASM inspector plugins. Plugins are selected with
+    /// `--inspectors plugin:<name>`, where the name is their file name without
+    /// the `.wasm` extension, all of them run if `--inspectors` is omitted
+    #[arg(long)]
+    pub plugin_dir:       Option<PathBuf>,
+    /// Instructions a plugin can execute per block
+    #[arg(long, default_value_t = DEFAULT_PLUGIN_FUEL)]
+    pub plugin_fuel:      u64,
+    /// Memory a plugin can use, in MiB
+    #[arg(long, default_value_t = DEFAULT_PLUGIN_MEMORY_MB)]
+    pub plugin_memory_mb: usize,
+}
+
+impl PluginArgs {
+    fn discover(&self) -> eyre::Result<Vec<String>> {
+        match &self.plugin_dir {
+            Some(dir) if cfg!(feature = "wasm-plugins") => discover_plugins(dir),
+            Some(_) => eyre::bail!("brontes was built without the wasm-plugins feature"),
+            None => Ok(vec![]),
+        }
+    }
+
+    #[cfg(feature = "wasm-plugins")]
+    fn load(
+        &self,
+        name: &str,
+        quote_token: Address,
+    ) -> eyre::Result<&'static dyn Inspector<Result = Vec<Bundle>>> {
+        let Some(dir) = &self.plugin_dir else {
+            eyre::bail!("pass --plugin-dir to load the plugin {name}")
+        };
+        let limits = PluginLimits {
+            fuel:             self.plugin_fuel,
+            max_memory_bytes: self.plugin_memory_mb << 20,
+        };
+
+        Ok(static_object(WasmInspector::load(&plugin_path(dir, name), quote_token, limits)?))
+    }
+
+    #[cfg(not(feature = "wasm-plugins"))]
+    fn load(
+        &self,
+        name: &str,
+        _: Address,
+    ) -> eyre::Result<&'static dyn Inspector<Result = Vec<Bundle>>> {
+        eyre::bail!(
+            "can't load the plugin {name}, plugins need brontes to be built with the wasm-plugins \
+             feature"
+        )
+    }
 }
 
 pub fn get_env_vars() -> eyre::Result<String> {
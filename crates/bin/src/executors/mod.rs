@@ -14,35 +14,29 @@ pub use processors::*;
 mod shared;
 use brontes_database::{clickhouse::ClickhouseHandle, Tables};
 use futures::pin_mut;
-use shared::multi_block_window::MultiBlockWindow;
 mod tip;
 use std::{
     marker::PhantomData,
     pin::Pin,
-    sync::{atomic::AtomicBool, Arc},
+    sync::Arc,
     task::{Context, Poll},
 };
 
 use alloy_primitives::Address;
-use brontes_classifier::Classifier;
 use brontes_core::decoding::{Parser, TracingProvider};
 use brontes_database::libmdbx::LibmdbxInit;
 use brontes_inspect::Inspector;
-use brontes_pricing::{BrontesBatchPricer, GraphManager, LoadState};
-use brontes_types::{BrontesTaskExecutor, FastHashMap, UnboundedYapperReceiver};
+use brontes_pricing::LoadState;
+use brontes_types::BrontesTaskExecutor;
 use futures::{stream::FuturesUnordered, Future, StreamExt};
 use indicatif::MultiProgress;
 use itertools::Itertools;
 pub use range::RangeExecutorWithPricing;
 use reth_tasks::shutdown::GracefulShutdown;
 pub use tip::TipInspector;
-use tokio::{sync::mpsc::unbounded_channel, task::JoinHandle};
+use tokio::task::JoinHandle;
 
-use self::shared::{
-    dex_pricing::WaitingForPricerFuture, metadata_loader::MetadataLoader,
-    state_collector::StateCollector,
-};
-use crate::cli::static_object;
+use self::shared::state_collector::{StateCollector, StateCollectorConfig};
 
 pub const PROMETHEUS_ENDPOINT_IP: [u8; 4] = [0u8, 0u8, 0u8, 0u8];
 
@@ -330,18 +324,12 @@ impl<T: TracingProvider, DB: LibmdbxInit, CH: ClickhouseHandle, P: Processor>
         back_from_tip: u64,
         pricing_metrics: Option<DexPricingMetrics>,
     ) -> TipInspector<T, DB, CH, P> {
-        let state_collector = self.init_state_collector(
-            range_id,
-            executor,
-            start_block,
-            start_block,
-            true,
-            pricing_metrics,
-        );
+        let state_collector_config = self.state_collector_config(executor, pricing_metrics);
         TipInspector::new(
+            range_id,
             start_block,
             back_from_tip,
-            state_collector,
+            state_collector_config,
             self.parser,
             self.tip_db,
             self.inspectors,
@@ -376,54 +364,15 @@ impl<T: TracingProvider, DB: LibmdbxInit, CH: ClickhouseHandle, P: Processor>
         tip: bool,
         pricing_metrics: Option<DexPricingMetrics>,
     ) -> StateCollector<T, DB, CH> {
-        let shutdown = Arc::new(AtomicBool::new(false));
-        let (tx, rx) = unbounded_channel();
-        let classifier = static_object(Classifier::new(self.libmdbx, tx, self.parser.get_tracer()));
-
-        let pairs = self.libmdbx.protocols_created_before(start_block).unwrap();
-
-        let rest_pairs = self
-            .libmdbx
-            .protocols_created_range(start_block + 1, end_block)
-            .unwrap()
-            .into_iter()
-            .flat_map(|(_, pools)| {
-                pools
-                    .into_iter()
-                    .filter(|(_, p, _)| p.has_state_updater())
-                    .map(|(addr, protocol, pair)| (addr, (protocol, pair)))
-                    .collect::<Vec<_>>()
-            })
-            .collect::<FastHashMap<_, _>>();
-
-        let pair_graph = GraphManager::init_from_db_state(pairs, pricing_metrics.clone());
-
-        let data_req = Arc::new(AtomicBool::new(true));
-
-        let pricer = BrontesBatchPricer::new(
-            range_id,
-            shutdown.clone(),
-            self.quote_asset,
-            pair_graph,
-            UnboundedYapperReceiver::new(rx, 100_000, "batch pricer".into()),
-            self.parser.get_tracer(),
-            start_block,
-            rest_pairs,
-            data_req.clone(),
-            pricing_metrics.clone(),
-            executor.clone(),
-        );
-
-        let pricing = WaitingForPricerFuture::new(pricer, executor);
-        let fetcher = MetadataLoader::new(
-            tip.then_some(self.clickhouse),
-            pricing,
-            self.force_dex_pricing,
-            self.force_no_dex_pricing,
-            data_req,
-            self.cex_window,
-        );
+        self.state_collector_config(executor, pricing_metrics)
+            .build(range_id, start_block, end_block, tip)
+    }
 
+    fn state_collector_config(
+        &self,
+        executor: BrontesTaskExecutor,
+        pricing_metrics: Option<DexPricingMetrics>,
+    ) -> StateCollectorConfig<T, DB, CH> {
         let block_window_size = self
             .inspectors
             .iter()
@@ -431,17 +380,18 @@ impl<T: TracingProvider, DB: LibmdbxInit, CH: ClickhouseHandle, P: Processor>
             .map(|v| v.block_window())
             .expect("no inspectors loaded");
 
-        let window = MultiBlockWindow::new(block_window_size);
-
-        StateCollector::new(
-            shutdown,
-            fetcher,
-            classifier,
-            self.parser,
-            self.libmdbx,
-            window,
-            self.quote_asset,
-        )
+        StateCollectorConfig {
+            parser: self.parser,
+            libmdbx: self.libmdbx,
+            clickhouse: self.clickhouse,
+            quote_asset: self.quote_asset,
+            force_dex_pricing: self.force_dex_pricing,
+            force_no_dex_pricing: self.force_no_dex_pricing,
+            cex_window: self.cex_window,
+            block_window_size,
+            executor,
+            pricing_metrics,
+        }
     }
 
     async fn init_block_range_tables(
@@ -15,14 +15,14 @@ use brontes_types::normalized_actions::Action;
 #[cfg(feature = "local-clickhouse")]
 use brontes_types::tree::BlockTree;
 use brontes_types::{
-    db::block_analysis::BlockAnalysis,
+    db::{block_analysis::BlockAnalysis, pool_lvr::BlockPoolLvr},
     execute_on,
     mev::{Bundle, MevBlock, MevType},
     BlockData, MultiBlockData,
 };
 use tracing::debug;
 
-use crate::Processor;
+use crate::{server::publish_block, Processor};
 
 #[derive(Debug, Clone, Copy)]
 pub struct MevProcessor;
@@ -54,10 +54,10 @@ impl Processor for MevProcessor {
             return
         }
 
-        let ComposerResults { block_details, mev_details, block_analysis, .. } =
+        let ComposerResults { block_details, mev_details, block_analysis, pool_lvr, .. } =
             execute_on!(async_inspect, { run_block_inspection(inspectors, data, db) }).await;
 
-        insert_mev_results(db, block_details, mev_details, block_analysis).await;
+        insert_mev_results(db, block_details, mev_details, block_analysis, pool_lvr).await;
     }
 }
 
@@ -82,6 +82,7 @@ async fn insert_mev_results<DB: DBWriter + LibmdbxReader>(
     block_details: MevBlock,
     mev_details: Vec<Bundle>,
     analysis: BlockAnalysis,
+    pool_lvr: BlockPoolLvr,
 ) {
     debug!(
         target: "brontes::results",
@@ -90,6 +91,7 @@ async fn insert_mev_results<DB: DBWriter + LibmdbxReader>(
     );
 
     let block_number = block_details.block_number;
+    publish_block(&block_details, &mev_details);
     output_mev_and_update_searcher_info(database, &mev_details).await;
 
     // Attempt to save the MEV block details
@@ -110,6 +112,13 @@ async fn insert_mev_results<DB: DBWriter + LibmdbxReader>(
             block_number
         );
     }
+    if let Err(e) = database.write_pool_lvr(pool_lvr).await {
+        tracing::error!(
+            "Failed to insert pool lvr data into db: {:?} at block: {}",
+            e,
+            block_number
+        );
+    }
 }
 async fn output_mev_and_update_searcher_info<DB: DBWriter + LibmdbxReader>(
     database: &DB,
@@ -130,6 +139,15 @@ async fn output_mev_and_update_searcher_info<DB: DBWriter + LibmdbxReader>(
             .try_fetch_searcher_info(mev.header.eoa, mev.header.mev_contract)
             .expect("Failed to fetch searcher info from the database");
 
+        // a failed attempt alone doesn't make an address a searcher, so we only account
+        // for them on searchers we already know of
+        if mev.header.mev_type == MevType::FailedAttempt
+            && eoa_info.is_none()
+            && contract_info.is_none()
+        {
+            continue
+        }
+
         let mut eoa_info = eoa_info.unwrap_or_default();
         let mut contract_info = contract_info.unwrap_or_default();
 
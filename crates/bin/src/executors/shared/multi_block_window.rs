@@ -27,4 +27,10 @@ impl MultiBlockWindow {
 
         MultiBlockData { blocks: block_count, per_block_data: block_data }
     }
+
+    /// drops the data of the given block and all blocks after it
+    pub fn remove_from(&mut self, block: u64) {
+        self.block_window_queue
+            .retain(|data| data.block_number() < block);
+    }
 }
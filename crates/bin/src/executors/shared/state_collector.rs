@@ -11,20 +11,26 @@ use alloy_primitives::Address;
 use brontes_classifier::Classifier;
 use brontes_core::decoding::Parser;
 use brontes_database::clickhouse::ClickhouseHandle;
-use brontes_metrics::range::GlobalRangeMetrics;
+use brontes_metrics::{pricing::DexPricingMetrics, range::GlobalRangeMetrics};
+use brontes_pricing::{BrontesBatchPricer, GraphManager};
 use brontes_types::{
     db::traits::{DBWriter, LibmdbxReader},
     normalized_actions::Action,
     structured_trace::TxTrace,
     traits::TracingProvider,
-    BlockTree, MultiBlockData,
+    BlockTree, BrontesTaskExecutor, FastHashMap, MultiBlockData, UnboundedYapperReceiver,
 };
 use eyre::eyre;
 use futures::{Future, FutureExt, Stream, StreamExt};
 use reth_primitives::Header;
+use tokio::sync::mpsc::unbounded_channel;
 use tracing::{span, trace, Instrument, Level};
 
-use super::{metadata_loader::MetadataLoader, multi_block_window::MultiBlockWindow};
+use super::{
+    dex_pricing::WaitingForPricerFuture, metadata_loader::MetadataLoader,
+    multi_block_window::MultiBlockWindow,
+};
+use crate::cli::static_object;
 
 type CollectionFut<'a> = Pin<Box<dyn Future<Output = eyre::Result<BlockTree<Action>>> + Send + 'a>>;
 type ExecutionFut<'a> = Pin<Box<dyn Future<Output = Option<(Vec<TxTrace>, Header)>> + Send + 'a>>;
@@ -123,6 +129,20 @@ impl<T: TracingProvider, DB: LibmdbxReader + DBWriter, CH: ClickhouseHandle>
         ))
     }
 
+    /// Replaces this collector with `canonical`, which was built to start at
+    /// `block`. The dex pricer only moves forward and has already applied the
+    /// state of the reorged out blocks, so it is stopped together with all
+    /// blocks that are buffered or being priced. Only the blocks before
+    /// `block` are kept in the multi block window.
+    pub fn rollback_to(&mut self, block: u64, canonical: Self) {
+        let reorged = std::mem::replace(self, canonical);
+        // lets the old pricer close instead of waiting for updates forever
+        reorged.mark_as_finished.store(true, SeqCst);
+
+        self.multi_block = reorged.multi_block;
+        self.multi_block.remove_from(block);
+    }
+
     pub fn range_finished(&self, waker: &Waker) {
         if !self.mark_as_finished.swap(true, SeqCst) {
             waker.wake_by_ref();
@@ -130,6 +150,99 @@ impl<T: TracingProvider, DB: LibmdbxReader + DBWriter, CH: ClickhouseHandle>
     }
 }
 
+/// Everything needed to build a [`StateCollector`], kept by the tip inspector
+/// to rebuild its collector when a reorg is detected
+pub struct StateCollectorConfig<
+    T: TracingProvider,
+    DB: LibmdbxReader + DBWriter,
+    CH: ClickhouseHandle,
+> {
+    pub parser:               &'static Parser<T, DB>,
+    pub libmdbx:              &'static DB,
+    pub clickhouse:           &'static CH,
+    pub quote_asset:          Address,
+    pub force_dex_pricing:    bool,
+    pub force_no_dex_pricing: bool,
+    pub cex_window:           usize,
+    pub block_window_size:    usize,
+    pub executor:             BrontesTaskExecutor,
+    pub pricing_metrics:      Option<DexPricingMetrics>,
+}
+
+impl<T: TracingProvider, DB: LibmdbxReader + DBWriter, CH: ClickhouseHandle>
+    StateCollectorConfig<T, DB, CH>
+{
+    /// Builds a collector with a new classifier and dex pricer for the blocks
+    /// `start_block..=end_block`. Metadata is only loaded from clickhouse at
+    /// the `tip`.
+    pub fn build(
+        &self,
+        range_id: usize,
+        start_block: u64,
+        end_block: u64,
+        tip: bool,
+    ) -> StateCollector<T, DB, CH> {
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let (tx, rx) = unbounded_channel();
+        let classifier = static_object(Classifier::new(self.libmdbx, tx, self.parser.get_tracer()));
+
+        let pairs = self.libmdbx.protocols_created_before(start_block).unwrap();
+
+        let rest_pairs = self
+            .libmdbx
+            .protocols_created_range(start_block + 1, end_block)
+            .unwrap()
+            .into_iter()
+            .flat_map(|(_, pools)| {
+                pools
+                    .into_iter()
+                    .filter(|(_, p, _)| p.has_state_updater())
+                    .map(|(addr, protocol, pair)| (addr, (protocol, pair)))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<FastHashMap<_, _>>();
+
+        let pair_graph = GraphManager::init_from_db_state(pairs, self.pricing_metrics.clone());
+
+        let data_req = Arc::new(AtomicBool::new(true));
+
+        let pricer = BrontesBatchPricer::new(
+            range_id,
+            shutdown.clone(),
+            self.quote_asset,
+            pair_graph,
+            UnboundedYapperReceiver::new(rx, 100_000, "batch pricer".into()),
+            self.parser.get_tracer(),
+            Arc::new(self.libmdbx),
+            start_block,
+            rest_pairs,
+            data_req.clone(),
+            self.pricing_metrics.clone(),
+            self.executor.clone(),
+        );
+
+        let pricing = WaitingForPricerFuture::new(pricer, self.executor.clone());
+        let fetcher = MetadataLoader::new(
+            tip.then_some(self.clickhouse),
+            pricing,
+            self.force_dex_pricing,
+            self.force_no_dex_pricing,
+            data_req,
+            self.cex_window,
+        );
+
+        StateCollector::new(
+            shutdown,
+            fetcher,
+            classifier,
+            self.parser,
+            self.libmdbx,
+            MultiBlockWindow::new(self.block_window_size),
+            self.quote_asset,
+        )
+    }
+}
+
 impl<T: TracingProvider, DB: LibmdbxReader + DBWriter, CH: ClickhouseHandle> Stream
     for StateCollector<T, DB, CH>
 {
@@ -171,3 +284,96 @@ impl<T: TracingProvider, DB: LibmdbxReader + DBWriter, CH: ClickhouseHandle> Str
             .map(|inner| inner.map(|data| self.multi_block.new_block_data(data)))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use brontes_core::test_utils::{init_tracer, load_clickhouse, TraceLoader};
+    use brontes_types::{constants::USDT_ADDRESS, BlockData, BrontesTaskManager};
+    use itertools::Itertools;
+
+    use super::*;
+
+    const BLOCK: u64 = 18500018;
+
+    /// Drives the collector like the tip inspector until `block` comes out of
+    /// the pricer, which needs the next block to be classified to finish it
+    async fn price_block<T: TracingProvider, DB: LibmdbxReader + DBWriter, CH: ClickhouseHandle>(
+        collector: &mut StateCollector<T, DB, CH>,
+        next_block: &mut u64,
+        block: u64,
+    ) -> MultiBlockData {
+        loop {
+            if *next_block <= block + 1
+                && !collector.is_collecting_state()
+                && collector.should_process_next_block()
+            {
+                collector.fetch_state_for(*next_block, 0, None);
+                *next_block += 1;
+            }
+
+            match tokio::time::timeout(Duration::from_millis(100), collector.next()).await {
+                Ok(Some(data)) if data.get_most_recent_block().block_number() == block => {
+                    return data
+                }
+                Ok(Some(_)) | Err(_) => {}
+                Ok(None) => panic!("state collector closed before block {block} was priced"),
+            }
+        }
+    }
+
+    #[brontes_macros::test]
+    async fn test_reorged_block_is_priced_again() {
+        let loader = TraceLoader::new().await;
+        for block in BLOCK..=BLOCK + 2 {
+            loader.get_metadata(block, false).await.unwrap();
+        }
+
+        let (metrics_tx, _metrics_rx) = tokio::sync::mpsc::unbounded_channel();
+        let handle = tokio::runtime::Handle::current();
+        let config = StateCollectorConfig {
+            parser:               static_object(
+                Parser::new(metrics_tx, loader.libmdbx, init_tracer(handle, 10)).await,
+            ),
+            libmdbx:              loader.libmdbx,
+            clickhouse:           static_object(load_clickhouse().await),
+            quote_asset:          USDT_ADDRESS,
+            force_dex_pricing:    true,
+            force_no_dex_pricing: false,
+            cex_window:           12,
+            block_window_size:    2,
+            executor:             BrontesTaskManager::current().executor(),
+            pricing_metrics:      None,
+        };
+
+        let mut collector = config.build(0, BLOCK, BLOCK, false);
+        let mut next_block = BLOCK;
+        price_block(&mut collector, &mut next_block, BLOCK).await;
+        price_block(&mut collector, &mut next_block, BLOCK + 1).await;
+
+        // BLOCK + 1 is reorged out, the pricer has already moved past it
+        collector.rollback_to(BLOCK + 1, config.build(0, BLOCK + 1, BLOCK + 1, false));
+        let mut next_block = BLOCK + 1;
+        let canonical = tokio::time::timeout(
+            Duration::from_secs(300),
+            price_block(&mut collector, &mut next_block, BLOCK + 1),
+        )
+        .await
+        .expect("the canonical block was never priced");
+
+        assert!(canonical
+            .get_most_recent_block()
+            .metadata
+            .dex_quotes
+            .is_some());
+        assert_eq!(
+            canonical
+                .per_block_data
+                .iter()
+                .map(BlockData::block_number)
+                .collect_vec(),
+            vec![BLOCK, BLOCK + 1]
+        );
+    }
+}
@@ -1,10 +1,12 @@
 use std::{
+    collections::VecDeque,
     marker::PhantomData,
     pin::Pin,
     task::{Context, Poll},
     time::Duration,
 };
 
+use alloy_primitives::B256;
 use brontes_core::decoding::{Parser, TracingProvider};
 use brontes_database::{
     clickhouse::ClickhouseHandle,
@@ -12,44 +14,81 @@ use brontes_database::{
 };
 use brontes_inspect::Inspector;
 use brontes_types::MultiBlockData;
-use futures::{pin_mut, stream::FuturesUnordered, Future, StreamExt};
+use futures::{pin_mut, stream::FuturesUnordered, Future, FutureExt, StreamExt};
 use reth_tasks::shutdown::GracefulShutdown;
 use tokio::time::{interval, Interval};
-use tracing::debug;
+use tracing::{debug, warn};
 
-use super::shared::state_collector::StateCollector;
+use super::shared::state_collector::{StateCollector, StateCollectorConfig};
 use crate::Processor;
 
+/// the amount of processed blocks we keep the hashes of to detect reorgs
+const MAX_REORG_DEPTH: usize = 64;
+
+type ReorgCheckFuture = Pin<Box<dyn Future<Output = ReorgCheck> + Send + 'static>>;
+
+/// Result of checking the next block against the processed ones, or of rolling
+/// back the orphaned blocks once a reorg was detected
+enum ReorgCheck {
+    /// the header of the next block isn't available yet or couldn't be fetched
+    Unavailable,
+    /// the next block builds on the last processed block and has this hash
+    Canonical(B256),
+    /// the next block doesn't build on the last processed block
+    Reorged,
+    /// the results of the orphaned blocks were deleted, processing restarts
+    /// from this block. `None` if nothing was rolled back
+    RolledBack(Option<u64>),
+}
+
 pub struct TipInspector<
     T: TracingProvider,
     DB: LibmdbxReader + DBWriter,
     CH: ClickhouseHandle,
     P: Processor,
 > {
-    current_block:      u64,
-    back_from_tip:      u64,
-    parser:             &'static Parser<T, DB>,
-    state_collector:    StateCollector<T, DB, CH>,
-    database:           &'static DB,
-    inspectors:         &'static [&'static dyn Inspector<Result = P::InspectType>],
-    processing_futures: FuturesUnordered<Pin<Box<dyn Future<Output = ()> + Send + 'static>>>,
-    poll_interval:      Interval,
-    _p:                 PhantomData<P>,
+    range_id:               usize,
+    current_block:          u64,
+    back_from_tip:          u64,
+    parser:                 &'static Parser<T, DB>,
+    /// used to rebuild the state collector at the first reorged out block
+    state_collector_config: StateCollectorConfig<T, DB, CH>,
+    state_collector:        StateCollector<T, DB, CH>,
+    database:               &'static DB,
+    inspectors:             &'static [&'static dyn Inspector<Result = P::InspectType>],
+    processing_futures:     FuturesUnordered<Pin<Box<dyn Future<Output = ()> + Send + 'static>>>,
+    poll_interval:          Interval,
+    /// number and hash of the last [`MAX_REORG_DEPTH`] blocks we started
+    processed_blocks:       VecDeque<(u64, B256)>,
+    /// the last block that came out of the state collector
+    last_priced_block:      Option<u64>,
+    /// the parent hash check of the next block or the rollback of the orphaned
+    /// blocks, at most one runs at a time
+    reorg_check:            Option<ReorgCheckFuture>,
+    /// whether `reorg_check` is deleting the orphaned blocks
+    rolling_back:           bool,
+    _p:                     PhantomData<P>,
 }
 
 impl<T: TracingProvider, DB: DBWriter + LibmdbxReader, CH: ClickhouseHandle, P: Processor>
     TipInspector<T, DB, CH, P>
 {
     pub fn new(
+        range_id: usize,
         current_block: u64,
         back_from_tip: u64,
-        state_collector: StateCollector<T, DB, CH>,
+        state_collector_config: StateCollectorConfig<T, DB, CH>,
         parser: &'static Parser<T, DB>,
         database: &'static DB,
         inspectors: &'static [&'static dyn Inspector<Result = P::InspectType>],
     ) -> Self {
+        let state_collector =
+            state_collector_config.build(range_id, current_block, current_block, true);
+
         Self {
+            range_id,
             back_from_tip,
+            state_collector_config,
             state_collector,
             inspectors,
             current_block,
@@ -57,6 +96,10 @@ impl<T: TracingProvider, DB: DBWriter + LibmdbxReader, CH: ClickhouseHandle, P:
             processing_futures: FuturesUnordered::new(),
             database,
             poll_interval: interval(Duration::from_secs(3)),
+            processed_blocks: VecDeque::with_capacity(MAX_REORG_DEPTH),
+            last_priced_block: None,
+            reorg_check: None,
+            rolling_back: false,
             _p: PhantomData,
         }
     }
@@ -114,8 +157,130 @@ impl<T: TracingProvider, DB: DBWriter + LibmdbxReader, CH: ClickhouseHandle, P:
         }
     }
 
+    /// Checks that the next block builds on the last block we processed
+    fn check_parent_hash(&self) -> ReorgCheckFuture {
+        let block = self.current_block;
+        let last_processed = self.processed_blocks.back().copied();
+        let tracer = self.parser.get_tracer();
+
+        Box::pin(async move {
+            let header = match tracer.header_by_number(block).await {
+                Ok(Some(header)) => header,
+                Ok(None) => return ReorgCheck::Unavailable,
+                Err(e) => {
+                    tracing::error!(error = %e, %block, "failed to fetch header");
+                    return ReorgCheck::Unavailable
+                }
+            };
+
+            if last_processed
+                .is_some_and(|(number, hash)| number + 1 == block && hash != header.parent_hash)
+            {
+                return ReorgCheck::Reorged
+            }
+
+            ReorgCheck::Canonical(header.hash_slow())
+        })
+    }
+
+    /// Deletes the results of all processed blocks that are no longer part of
+    /// the canonical chain. Processing restarts at the first of them, or at the
+    /// first block that was still being priced if that's earlier, since the
+    /// state collector is rebuilt, dropping the dex pricer state of the
+    /// reorged out blocks and of any block in flight.
+    fn rollback_orphaned_blocks(&self) -> ReorgCheckFuture {
+        let processed_blocks = self.processed_blocks.clone();
+        let first_in_flight = self
+            .processed_blocks
+            .iter()
+            .map(|(number, _)| *number)
+            .find(|number| {
+                self.last_priced_block
+                    .map_or(true, |priced| *number > priced)
+            });
+        let current_block = self.current_block;
+        let tracer = self.parser.get_tracer();
+        let database = self.database;
+
+        Box::pin(async move {
+            let mut orphaned = 0;
+            for (number, hash) in processed_blocks.iter().rev() {
+                match tracer.block_hash_for_id(*number).await {
+                    Ok(canonical) if canonical == Some(*hash) => break,
+                    Ok(_) => orphaned += 1,
+                    Err(e) => {
+                        tracing::error!(error = %e, block = number, "failed to fetch block hash");
+                        return ReorgCheck::RolledBack(None)
+                    }
+                }
+            }
+
+            let Some(&(first_orphaned, _)) =
+                processed_blocks.get(processed_blocks.len() - orphaned)
+            else {
+                return ReorgCheck::RolledBack(None)
+            };
+            let restart_from = first_in_flight.map_or(first_orphaned, |b| b.min(first_orphaned));
+
+            warn!(
+                target: "brontes::tip_inspector",
+                from_block = first_orphaned,
+                depth = orphaned,
+                %restart_from,
+                "reorg detected, rolling back blocks"
+            );
+
+            for block in first_orphaned..current_block {
+                if let Err(e) = database.delete_block(block).await {
+                    tracing::error!(error = %e, %block, "failed to delete reorged block");
+                }
+            }
+
+            ReorgCheck::RolledBack(Some(restart_from))
+        })
+    }
+
+    fn on_reorg_check(&mut self, check: ReorgCheck) {
+        match check {
+            ReorgCheck::Unavailable => {}
+            ReorgCheck::Canonical(hash) => {
+                let block = self.current_block;
+                if self.processed_blocks.len() == MAX_REORG_DEPTH {
+                    self.processed_blocks.pop_front();
+                }
+                self.processed_blocks.push_back((block, hash));
+
+                tracing::info!(%block,"starting new tip block");
+                self.state_collector.fetch_state_for(block, 0, None);
+                self.current_block += 1;
+            }
+            // the results of the previous blocks have to be written before we can
+            // delete them, otherwise the check is retried on the next tick
+            ReorgCheck::Reorged if self.processing_futures.is_empty() => {
+                self.reorg_check = Some(self.rollback_orphaned_blocks());
+                self.rolling_back = true;
+            }
+            ReorgCheck::Reorged => {}
+            ReorgCheck::RolledBack(Some(restart_from)) => {
+                self.rolling_back = false;
+                self.processed_blocks
+                    .retain(|(number, _)| *number < restart_from);
+                let canonical = self.state_collector_config.build(
+                    self.range_id,
+                    restart_from,
+                    restart_from,
+                    true,
+                );
+                self.state_collector.rollback_to(restart_from, canonical);
+                self.current_block = restart_from;
+            }
+            ReorgCheck::RolledBack(None) => self.rolling_back = false,
+        }
+    }
+
     fn on_price_finish(&mut self, data: MultiBlockData) {
         debug!(target:"brontes::tip_inspector","Completed DEX pricing");
+        self.last_priced_block = Some(data.get_most_recent_block().block_number());
         self.processing_futures.push(Box::pin(P::process_results(
             self.database,
             self.inspectors,
@@ -134,18 +299,34 @@ impl<T: TracingProvider, DB: DBWriter + LibmdbxReader, CH: ClickhouseHandle, P:
         // for the next block.
         while self.poll_interval.poll_tick(cx).is_ready() {}
 
-        if self.start_block_inspector() && self.state_collector.should_process_next_block() {
-            let block = self.current_block;
-            tracing::info!(%block,"starting new tip block");
-            self.state_collector.fetch_state_for(block, 0, None);
-            self.current_block += 1;
+        if self.reorg_check.is_none()
+            && self.start_block_inspector()
+            && self.state_collector.should_process_next_block()
+        {
+            self.reorg_check = Some(self.check_parent_hash());
+        }
+
+        // a rollback started from a finished check is polled right away
+        while let Some(mut check) = self.reorg_check.take() {
+            match check.poll_unpin(cx) {
+                Poll::Ready(result) => self.on_reorg_check(result),
+                Poll::Pending => {
+                    self.reorg_check = Some(check);
+                    break
+                }
+            }
         }
 
-        if let Poll::Ready(item) = self.state_collector.poll_next_unpin(cx) {
-            match item {
-                Some(data) => self.on_price_finish(data),
-                None if self.processing_futures.is_empty() => return Poll::Ready(()),
-                _ => {}
+        // blocks that finish pricing while orphaned blocks are being deleted
+        // would have their results written, they're dropped by the rollback
+        // instead
+        if !self.rolling_back {
+            if let Poll::Ready(item) = self.state_collector.poll_next_unpin(cx) {
+                match item {
+                    Some(data) => self.on_price_finish(data),
+                    None if self.processing_futures.is_empty() => return Poll::Ready(()),
+                    _ => {}
+                }
             }
         }
         while let Poll::Ready(Some(_)) = self.processing_futures.poll_next_unpin(cx) {}
@@ -30,3 +30,4 @@ pub use executors::*;
 pub use misc::banner;
 
 pub mod runner;
+pub mod server;
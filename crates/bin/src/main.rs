@@ -61,6 +61,16 @@ fn run() -> eyre::Result<()> {
                 command.execute(brontes_db_endpoint, ctx)
             })
         }
+        Commands::Serve(command) => {
+            runner::run_command_until_exit(None, Duration::from_secs(5), |ctx| {
+                command.execute(brontes_db_endpoint, ctx)
+            })
+        }
+        Commands::Explain(command) => {
+            runner::run_command_until_exit(None, Duration::from_secs(5), |ctx| {
+                command.execute(brontes_db_endpoint, ctx)
+            })
+        }
     }
 }
 
@@ -0,0 +1,79 @@
+//! Read only http api over the brontes database. All responses are json, errors
+//! are returned as `{"error": "..."}`. While brontes is running the processed
+//! blocks can also be streamed from `/stream`.
+use std::{convert::Infallible, net::SocketAddr};
+
+use brontes_types::db::traits::LibmdbxReader;
+use eyre::WrapErr;
+use hyper::{
+    header::CONTENT_TYPE,
+    service::{make_service_fn, service_fn},
+    Body, Method, Request, Response, Server, StatusCode,
+};
+use serde_json::{json, Value};
+use tracing::{error, info};
+
+mod routes;
+pub use routes::*;
+mod stream;
+pub use stream::*;
+
+/// Serves the api until the server fails
+pub async fn serve<DB: LibmdbxReader>(
+    listen_addr: SocketAddr,
+    db: &'static DB,
+) -> eyre::Result<()> {
+    let make_svc = make_service_fn(move |_| async move {
+        Ok::<_, Infallible>(service_fn(move |req| handle_request(req, db)))
+    });
+
+    let server = Server::try_bind(&listen_addr)
+        .wrap_err("Could not bind to address")?
+        .serve(make_svc);
+
+    info!(%listen_addr, "serving brontes api");
+    server.await.wrap_err("brontes api crashed")
+}
+
+async fn handle_request<DB: LibmdbxReader>(
+    req: Request<Body>,
+    db: &'static DB,
+) -> Result<Response<Body>, Infallible> {
+    if req.method() != Method::GET {
+        return Ok(error_response(StatusCode::METHOD_NOT_ALLOWED, "only GET is supported"))
+    }
+
+    if req.uri().path().trim_end_matches('/') == "/stream" {
+        return Ok(subscribe(req.uri().query()))
+    }
+
+    let route = match Route::parse(req.uri().path(), req.uri().query()) {
+        Ok(route) => route,
+        Err(e) => return Ok(error_response(StatusCode::BAD_REQUEST, e)),
+    };
+
+    // libmdbx reads block, so we keep them off the runtime
+    let res = tokio::task::spawn_blocking(move || route.query(db)).await;
+
+    Ok(match res {
+        Ok(Ok(Some(body))) => json_response(StatusCode::OK, body),
+        Ok(Ok(None)) => error_response(StatusCode::NOT_FOUND, "not found"),
+        Ok(Err(e)) => {
+            error!(error = %e, "api query failed");
+            error_response(StatusCode::INTERNAL_SERVER_ERROR, e.to_string())
+        }
+        Err(e) => error_response(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+    })
+}
+
+fn json_response(status: StatusCode, body: Value) -> Response<Body> {
+    Response::builder()
+        .status(status)
+        .header(CONTENT_TYPE, "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+fn error_response(status: StatusCode, error: impl ToString) -> Response<Body> {
+    json_response(status, json!({ "error": error.to_string() }))
+}
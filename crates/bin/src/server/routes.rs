@@ -0,0 +1,394 @@
+use std::str::FromStr;
+
+use alloy_primitives::{Address, B256};
+use brontes_types::{
+    db::{dex::DexQuotes, traits::LibmdbxReader},
+    ToFloatNearest,
+};
+use serde_json::{json, Value};
+
+/// the max amount of blocks that can be requested at once
+pub const MAX_BLOCK_RANGE: u64 = 1000;
+
+/// The queries supported by the api. All block ranges are inclusive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Route {
+    /// `/mev_blocks/{block}` or `/mev_blocks?start=&end=`
+    MevBlocks { start: u64, end: u64 },
+    /// `/bundles/{tx_hash}`
+    Bundles(B256),
+    /// `/addresses/{address}/bundles`, optionally `?start=&end=`
+    AddressBundles { address: Address, start: Option<u64>, end: Option<u64> },
+    /// `/searchers/{address}`
+    Searcher(Address),
+    /// `/builders/{address}`
+    Builder(Address),
+    /// `/addresses/{address}`
+    AddressMetadata(Address),
+    /// `/tokens/{address}`
+    Token(Address),
+    /// `/dex_quotes/{block}`
+    DexQuotes(u64),
+}
+
+impl Route {
+    pub fn parse(path: &str, query: Option<&str>) -> Result<Self, String> {
+        let segments = path
+            .trim_matches('/')
+            .split('/')
+            .filter(|s| !s.is_empty())
+            .collect::<Vec<_>>();
+        let params = QueryParams::new(query);
+
+        match segments.as_slice() {
+            ["mev_blocks"] => {
+                let (start, end) = params.block_range()?;
+                Ok(Self::MevBlocks { start, end })
+            }
+            ["mev_blocks", block] => {
+                let block = parse_param::<u64>("block", block)?;
+                Ok(Self::MevBlocks { start: block, end: block })
+            }
+            ["bundles", tx_hash] => Ok(Self::Bundles(parse_param("tx hash", tx_hash)?)),
+            ["searchers", address] => Ok(Self::Searcher(parse_param("address", address)?)),
+            ["builders", address] => Ok(Self::Builder(parse_param("address", address)?)),
+            ["addresses", address] => Ok(Self::AddressMetadata(parse_param("address", address)?)),
+            ["addresses", address, "bundles"] => {
+                let address = parse_param("address", address)?;
+                let (start, end) = (params.get::<u64>("start")?, params.get::<u64>("end")?);
+                if let (Some(start), Some(end)) = (start, end) {
+                    if start > end {
+                        return Err(format!("start block {start} is after end block {end}"))
+                    }
+                }
+                Ok(Self::AddressBundles { address, start, end })
+            }
+            ["tokens", address] => Ok(Self::Token(parse_param("address", address)?)),
+            ["dex_quotes", block] => Ok(Self::DexQuotes(parse_param("block", block)?)),
+            _ => Err(format!("unknown route {path}")),
+        }
+    }
+
+    /// Runs the query against the database. Returns `None` if there is no
+    /// entry for the query.
+    pub fn query<DB: LibmdbxReader>(&self, db: &DB) -> eyre::Result<Option<Value>> {
+        let res = match self {
+            Self::MevBlocks { start, end } => {
+                serde_json::to_value(db.try_fetch_mev_blocks(Some(*start), *end)?)?
+            }
+            Self::Bundles(tx_hash) => {
+                let bundles = db.try_fetch_bundles_by_tx_hash(*tx_hash)?;
+                if bundles.is_empty() {
+                    return Ok(None)
+                }
+                serde_json::to_value(bundles)?
+            }
+            Self::AddressBundles { address, start, end } => {
+                let bundles = db.try_fetch_bundles_by_address(*address, *start, *end)?;
+                if bundles.is_empty() {
+                    return Ok(None)
+                }
+                serde_json::to_value(bundles)?
+            }
+            Self::Searcher(address) => {
+                let eoa = db.try_fetch_searcher_eoa_info(*address)?;
+                let contract = db.try_fetch_searcher_contract_info(*address)?;
+                if eoa.is_none() && contract.is_none() {
+                    return Ok(None)
+                }
+
+                json!({ "eoa": eoa, "contract": contract })
+            }
+            Self::Builder(address) => {
+                let Some(info) = db.try_fetch_builder_info(*address)? else { return Ok(None) };
+                serde_json::to_value(info)?
+            }
+            Self::AddressMetadata(address) => {
+                let Some(metadata) = db.try_fetch_address_metadata(*address)? else {
+                    return Ok(None)
+                };
+                serde_json::to_value(metadata)?
+            }
+            // missing tokens are returned as an error
+            Self::Token(address) => {
+                let Ok(info) = db.try_fetch_token_info(*address) else { return Ok(None) };
+                serde_json::to_value(info)?
+            }
+            Self::DexQuotes(block) => {
+                if !db.has_dex_quotes(*block)? {
+                    return Ok(None)
+                }
+                dex_quotes_to_json(db.get_dex_quotes(*block)?)
+            }
+        };
+
+        Ok(Some(res))
+    }
+}
+
+/// The quotes are keyed by pair which can't be a json key, so they are
+/// flattened into one entry per tx and pair.
+pub fn dex_quotes_to_json(quotes: DexQuotes) -> Value {
+    Value::Array(
+        quotes
+            .0
+            .into_iter()
+            .enumerate()
+            .filter_map(|(tx_idx, quotes)| Some((tx_idx, quotes?)))
+            .flat_map(|(tx_idx, quotes)| {
+                quotes.into_iter().map(move |(pair, price)| {
+                    json!({
+                        "tx_idx": tx_idx,
+                        "pair": pair,
+                        "pre_state": price.pre_state.to_float(),
+                        "post_state": price.post_state.to_float(),
+                        "goes_through": price.goes_through,
+                        "is_transfer": price.is_transfer,
+                    })
+                })
+            })
+            .collect(),
+    )
+}
+
+fn parse_param<T: FromStr>(name: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("invalid {name}: {value}"))
+}
+
+pub(crate) struct QueryParams<'a>(Vec<(&'a str, &'a str)>);
+
+impl<'a> QueryParams<'a> {
+    pub(crate) fn new(query: Option<&'a str>) -> Self {
+        Self(
+            query
+                .into_iter()
+                .flat_map(|query| query.split('&'))
+                .filter_map(|param| param.split_once('='))
+                .collect(),
+        )
+    }
+
+    pub(crate) fn get<T: FromStr>(&self, name: &str) -> Result<Option<T>, String> {
+        self.0
+            .iter()
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| parse_param(name, value))
+            .transpose()
+    }
+
+    /// All values of a comma separated param
+    pub(crate) fn list<'b>(&'b self, name: &'b str) -> impl Iterator<Item = &'a str> + 'b {
+        self.0
+            .iter()
+            .filter(move |(key, _)| *key == name)
+            .flat_map(|&(_, values)| values.split(','))
+            .filter(|value| !value.is_empty())
+    }
+
+    /// Either a single `block` or a `start` and `end` block
+    fn block_range(&self) -> Result<(u64, u64), String> {
+        if let Some(block) = self.get("block")? {
+            return Ok((block, block))
+        }
+
+        let (Some(start), Some(end)) = (self.get::<u64>("start")?, self.get::<u64>("end")?) else {
+            return Err("either block or start and end are required".to_string())
+        };
+
+        if start > end {
+            return Err(format!("start block {start} is after end block {end}"))
+        }
+        if end - start >= MAX_BLOCK_RANGE {
+            return Err(format!("at most {MAX_BLOCK_RANGE} blocks can be queried at once"))
+        }
+
+        Ok((start, end))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{address, b256};
+    use brontes_database::libmdbx::{
+        tables::{AddressBundles, MevBlocks, MevBlocksData, TxBundles},
+        LibmdbxReadWriter,
+    };
+    use brontes_types::{
+        db::{bundle_index::BlockBundleIndex, dex::DexPrices, mev_block::MevBlockWithClassified},
+        mev::{Bundle, BundleHeader, MevBlock},
+        pair::Pair,
+        FastHashMap,
+    };
+    use malachite::Rational;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_routes() {
+        assert_eq!(
+            Route::parse("/mev_blocks/19000000", None),
+            Ok(Route::MevBlocks { start: 19_000_000, end: 19_000_000 })
+        );
+        assert_eq!(
+            Route::parse("/mev_blocks/", Some("start=10&end=20")),
+            Ok(Route::MevBlocks { start: 10, end: 20 })
+        );
+        assert_eq!(
+            Route::parse(
+                "/bundles/0x1111111111111111111111111111111111111111111111111111111111111111",
+                None
+            ),
+            Ok(Route::Bundles(b256!(
+                "1111111111111111111111111111111111111111111111111111111111111111"
+            )))
+        );
+        assert_eq!(
+            Route::parse(
+                "/addresses/0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2/bundles",
+                Some("start=5")
+            ),
+            Ok(Route::AddressBundles {
+                address: address!("C02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2"),
+                start:   Some(5),
+                end:     None,
+            })
+        );
+        assert_eq!(
+            Route::parse("/tokens/0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2", None),
+            Ok(Route::Token(address!("C02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2")))
+        );
+    }
+
+    #[test]
+    fn test_query_mev_blocks() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = LibmdbxReadWriter::init_db_tests(dir.path()).unwrap();
+        let blocks = [10, 11, 12].map(|block_number| {
+            MevBlocksData::new(
+                block_number,
+                MevBlockWithClassified {
+                    block: MevBlock { block_number, ..Default::default() },
+                    mev:   vec![],
+                },
+            )
+        });
+        db.db.write_table::<MevBlocks, _>(&blocks).unwrap();
+
+        let block_numbers = |start, end| {
+            let res = Route::MevBlocks { start, end }.query(&db).unwrap().unwrap();
+            res.as_array()
+                .unwrap()
+                .iter()
+                .map(|block| block["block"]["block_number"].as_u64().unwrap())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(block_numbers(10, 10), vec![10]);
+        assert_eq!(block_numbers(11, 11), vec![11]);
+        assert_eq!(block_numbers(11, 12), vec![11, 12]);
+        assert_eq!(block_numbers(0, 10), vec![10]);
+        assert_eq!(block_numbers(13, 20), Vec::<u64>::new());
+    }
+
+    #[test]
+    fn test_query_bundles() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = LibmdbxReadWriter::init_db_tests(dir.path()).unwrap();
+        let eoa = address!("1111111111111111111111111111111111111111");
+        let tx_hash = |block: u64| B256::with_last_byte(block as u8);
+
+        db.db
+            .update_db(|tx| {
+                for block_number in [10, 11] {
+                    let bundle = Bundle {
+                        header: BundleHeader {
+                            block_number,
+                            tx_hash: tx_hash(block_number),
+                            eoa,
+                            ..Default::default()
+                        },
+                        data:   Default::default(),
+                    };
+                    let index = BlockBundleIndex::new(block_number, &[bundle.clone()]);
+                    for (key, entry) in index.addresses {
+                        tx.put::<AddressBundles>(key, entry)?;
+                    }
+                    for (key, entry) in index.txs {
+                        tx.put::<TxBundles>(key, entry)?;
+                    }
+                    tx.put::<MevBlocks>(
+                        block_number,
+                        MevBlockWithClassified {
+                            block: MevBlock { block_number, ..Default::default() },
+                            mev:   vec![bundle],
+                        },
+                    )?;
+                }
+
+                Ok::<_, reth_db::DatabaseError>(())
+            })
+            .unwrap()
+            .unwrap();
+
+        let block_numbers = |route: Route| {
+            let Some(res) = route.query(&db).unwrap() else { return vec![] };
+            res.as_array()
+                .unwrap()
+                .iter()
+                .map(|bundle| bundle["header"]["block_number"].as_u64().unwrap())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(block_numbers(Route::Bundles(tx_hash(11))), vec![11]);
+        assert_eq!(block_numbers(Route::Bundles(tx_hash(12))), Vec::<u64>::new());
+        assert_eq!(
+            block_numbers(Route::AddressBundles { address: eoa, start: None, end: None }),
+            vec![10, 11]
+        );
+        assert_eq!(
+            block_numbers(Route::AddressBundles { address: eoa, start: Some(11), end: None }),
+            vec![11]
+        );
+    }
+
+    #[test]
+    fn test_invalid_routes() {
+        assert!(Route::parse("/unknown", None).is_err());
+        assert!(Route::parse("/tokens/not_an_address", None).is_err());
+        assert!(Route::parse("/mev_blocks", None).is_err());
+        assert!(Route::parse("/mev_blocks", Some("start=20&end=10")).is_err());
+        assert!(Route::parse("/mev_blocks", Some("start=0&end=1000")).is_err());
+        assert!(Route::parse(
+            "/addresses/0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2/bundles",
+            Some("start=20&end=10")
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_dex_quotes_json() {
+        let weth = address!("C02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2");
+        let usdc = address!("A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48");
+
+        let mut prices = FastHashMap::default();
+        prices.insert(
+            Pair(weth, usdc),
+            DexPrices {
+                pre_state:    Rational::from(3000),
+                post_state:   Rational::from_signeds(6001, 2),
+                goes_through: Pair(weth, usdc),
+                is_transfer:  false,
+            },
+        );
+
+        let json = dex_quotes_to_json(DexQuotes(vec![None, Some(prices)]));
+        let quotes = json.as_array().unwrap();
+
+        assert_eq!(quotes.len(), 1);
+        assert_eq!(quotes[0]["tx_idx"], 1);
+        assert_eq!(quotes[0]["pre_state"], 3000.0);
+        assert_eq!(quotes[0]["post_state"], 3000.5);
+    }
+}
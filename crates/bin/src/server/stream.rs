@@ -0,0 +1,274 @@
+//! Server sent event stream of the blocks brontes finishes processing. The
+//! processor publishes every block to a broadcast channel that each subscriber
+//! reads from at its own pace. A subscriber that falls more than
+//! [`FEED_CAPACITY`] blocks behind skips the oldest blocks and gets a `lagged`
+//! event instead, so a slow client never holds up the processor.
+use std::{
+    convert::Infallible,
+    sync::{Arc, OnceLock},
+    time::Duration,
+};
+
+use alloy_primitives::Address;
+use brontes_types::{
+    mev::{Bundle, Mev, MevBlock, MevType},
+    Protocol,
+};
+use clap::ValueEnum;
+use hyper::{
+    body::{Bytes, Sender},
+    header::{CACHE_CONTROL, CONTENT_TYPE},
+    Body, Response, StatusCode,
+};
+use serde_json::{json, Value};
+use tokio::sync::broadcast::{self, error::RecvError};
+
+use super::{error_response, routes::QueryParams};
+
+/// The amount of blocks buffered for each subscriber
+pub const FEED_CAPACITY: usize = 128;
+
+const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(15);
+
+static BUNDLE_FEED: OnceLock<broadcast::Sender<Arc<FeedBlock>>> = OnceLock::new();
+
+/// A processed block with all of its bundles
+#[derive(Debug, Clone)]
+pub struct FeedBlock {
+    pub block:   MevBlock,
+    pub bundles: Vec<Bundle>,
+}
+
+/// Enables publishing processed blocks to the `/stream` endpoint. Until this is
+/// called [`publish_block`] is a noop.
+pub fn enable_bundle_feed() {
+    BUNDLE_FEED.get_or_init(|| broadcast::channel(FEED_CAPACITY).0);
+}
+
+/// Sends the block to all subscribers. The block is only cloned if someone is
+/// listening.
+pub fn publish_block(block: &MevBlock, bundles: &[Bundle]) {
+    let Some(feed) = BUNDLE_FEED.get() else { return };
+    if feed.receiver_count() == 0 {
+        return
+    }
+
+    let _ = feed.send(Arc::new(FeedBlock { block: block.clone(), bundles: bundles.to_vec() }));
+}
+
+/// Filters applied to the bundles sent to a subscriber. Each filter that is set
+/// has to match, lists match if any of their entries do.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StreamFilter {
+    /// `mev_type=Sandwich,AtomicArb`
+    pub mev_types:  Vec<MevType>,
+    /// `searcher=0x..`, matches the eoa or the mev contract
+    pub searcher:   Option<Address>,
+    /// `protocol=UniswapV2,UniswapV3`
+    pub protocols:  Vec<Protocol>,
+    /// `min_profit=100.0` in usd
+    pub min_profit: Option<f64>,
+}
+
+impl StreamFilter {
+    pub fn parse(query: Option<&str>) -> Result<Self, String> {
+        let params = QueryParams::new(query);
+
+        let mev_types = params
+            .list("mev_type")
+            .map(|mev_type| {
+                MevType::from_str(mev_type, true)
+                    .map_err(|_| format!("invalid mev type: {mev_type}"))
+            })
+            .collect::<Result<_, _>>()?;
+        let protocols = params
+            .list("protocol")
+            .map(|protocol| {
+                protocol
+                    .parse()
+                    .map_err(|_| format!("invalid protocol: {protocol}"))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            mev_types,
+            searcher: params.get("searcher")?,
+            protocols,
+            min_profit: params.get("min_profit")?,
+        })
+    }
+
+    pub fn matches(&self, bundle: &Bundle) -> bool {
+        let header = &bundle.header;
+
+        (self.mev_types.is_empty() || self.mev_types.contains(&header.mev_type))
+            && self.searcher.map_or(true, |searcher| {
+                header.eoa == searcher || header.mev_contract == Some(searcher)
+            })
+            && self
+                .min_profit
+                .map_or(true, |min_profit| header.profit_usd >= min_profit)
+            && (self.protocols.is_empty()
+                || bundle
+                    .data
+                    .protocols()
+                    .iter()
+                    .any(|protocol| self.protocols.contains(protocol)))
+    }
+
+    /// The `block` event sent for a processed block. The block is always sent,
+    /// only the bundles are filtered.
+    fn block_event(&self, feed_block: &FeedBlock) -> Value {
+        let bundles = feed_block
+            .bundles
+            .iter()
+            .filter(|bundle| self.matches(bundle))
+            .collect::<Vec<_>>();
+
+        json!({ "block": feed_block.block, "bundles": bundles })
+    }
+}
+
+/// Subscribes to the bundle feed and streams the blocks until the client
+/// disconnects.
+pub fn subscribe(query: Option<&str>) -> Response<Body> {
+    let filter = match StreamFilter::parse(query) {
+        Ok(filter) => filter,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, e),
+    };
+    let Some(feed) = BUNDLE_FEED.get() else {
+        return error_response(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "the stream is only available while brontes is running",
+        )
+    };
+
+    let (sender, body) = Body::channel();
+    tokio::spawn(stream_blocks(feed.subscribe(), sender, filter));
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(CONTENT_TYPE, "text/event-stream")
+        .header(CACHE_CONTROL, "no-cache")
+        .body(body)
+        .unwrap()
+}
+
+async fn stream_blocks(
+    mut feed: broadcast::Receiver<Arc<FeedBlock>>,
+    mut sender: Sender,
+    filter: StreamFilter,
+) -> Result<(), Infallible> {
+    let mut keep_alive = tokio::time::interval(KEEP_ALIVE_INTERVAL);
+
+    loop {
+        let event = tokio::select! {
+            block = feed.recv() => match block {
+                Ok(block) => sse_event("block", &filter.block_event(&block)),
+                Err(RecvError::Lagged(skipped)) => sse_event("lagged", &json!({ "skipped": skipped })),
+                Err(RecvError::Closed) => break,
+            },
+            _ = keep_alive.tick() => Bytes::from_static(b": keep-alive\n\n"),
+        };
+
+        // waits until the client has read the last event, if the client is too slow
+        // the feed lags behind instead
+        if sender.send_data(event).await.is_err() {
+            break
+        }
+    }
+
+    Ok(())
+}
+
+fn sse_event(event: &str, data: &Value) -> Bytes {
+    Bytes::from(format!("event: {event}\ndata: {data}\n\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::address;
+    use brontes_types::mev::BundleHeader;
+
+    use super::*;
+
+    const SEARCHER: Address = address!("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa");
+    const CONTRACT: Address = address!("bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb");
+
+    fn bundle(mev_type: MevType, profit_usd: f64) -> Bundle {
+        Bundle {
+            header: BundleHeader {
+                eoa: SEARCHER,
+                mev_contract: Some(CONTRACT),
+                mev_type,
+                profit_usd,
+                ..Default::default()
+            },
+            data:   Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_parse_filter() {
+        assert_eq!(StreamFilter::parse(None), Ok(StreamFilter::default()));
+        assert_eq!(
+            StreamFilter::parse(Some(
+                "mev_type=sandwich,AtomicArb&searcher=0xbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb&\
+                 protocol=UniswapV2&min_profit=10.5"
+            )),
+            Ok(StreamFilter {
+                mev_types:  vec![MevType::Sandwich, MevType::AtomicArb],
+                searcher:   Some(CONTRACT),
+                protocols:  vec![Protocol::UniswapV2],
+                min_profit: Some(10.5),
+            })
+        );
+
+        assert!(StreamFilter::parse(Some("mev_type=not_a_type")).is_err());
+        assert!(StreamFilter::parse(Some("protocol=not_a_protocol")).is_err());
+        assert!(StreamFilter::parse(Some("min_profit=lots")).is_err());
+    }
+
+    #[test]
+    fn test_filter_bundles() {
+        let sandwich = bundle(MevType::Sandwich, 100.0);
+        let arb = bundle(MevType::AtomicArb, 5.0);
+
+        assert!(StreamFilter::default().matches(&sandwich));
+
+        let filter = StreamFilter::parse(Some("mev_type=Sandwich")).unwrap();
+        assert!(filter.matches(&sandwich));
+        assert!(!filter.matches(&arb));
+
+        let filter = StreamFilter::parse(Some("min_profit=10")).unwrap();
+        assert!(filter.matches(&sandwich));
+        assert!(!filter.matches(&arb));
+
+        let filter = StreamFilter { searcher: Some(CONTRACT), ..Default::default() };
+        assert!(filter.matches(&arb));
+        let filter = StreamFilter { searcher: Some(Address::ZERO), ..Default::default() };
+        assert!(!filter.matches(&arb));
+
+        // searcher txs don't touch any protocol
+        let filter = StreamFilter::parse(Some("protocol=UniswapV2")).unwrap();
+        assert!(!filter.matches(&sandwich));
+    }
+
+    #[test]
+    fn test_block_event() {
+        let feed_block = FeedBlock {
+            block:   MevBlock { block_number: 10, ..Default::default() },
+            bundles: vec![bundle(MevType::Sandwich, 100.0), bundle(MevType::AtomicArb, 5.0)],
+        };
+
+        let filter = StreamFilter::parse(Some("mev_type=AtomicArb")).unwrap();
+        let event = filter.block_event(&feed_block);
+
+        assert_eq!(event["block"]["block_number"], 10);
+        assert_eq!(event["bundles"].as_array().unwrap().len(), 1);
+        assert_eq!(
+            sse_event("lagged", &json!({ "skipped": 3 })),
+            Bytes::from("event: lagged\ndata: {\"skipped\":3}\n\n")
+        );
+    }
+}
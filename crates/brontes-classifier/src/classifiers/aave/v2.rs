@@ -1,6 +1,9 @@
 use brontes_macros::action_impl;
 use brontes_types::{
-    normalized_actions::{NormalizedFlashLoan, NormalizedLiquidation},
+    normalized_actions::{
+        NormalizedDeposit, NormalizedFlashLoan, NormalizedLiquidation, NormalizedLoan,
+        NormalizedRepayment, NormalizedWithdraw,
+    },
     structured_trace::CallInfo,
     utils::ToScaledRational,
     Protocol,
@@ -74,3 +77,231 @@ action_impl!(
 
     }
 );
+
+action_impl!(
+    Protocol::AaveV2,
+    crate::AaveV2::depositCall,
+    Deposit,
+    [],
+    call_data: true,
+    |
+    info: CallInfo,
+    call_data: depositCall,
+    db_tx: &DB| {
+        let token_info = db_tx.try_fetch_token_info(call_data.asset)?;
+        let deposit_amount = call_data.amount.to_scaled_rational(token_info.decimals);
+
+        return Ok(NormalizedDeposit {
+            protocol: Protocol::AaveV2,
+            trace_index: info.trace_idx,
+            pool: info.from_address,
+            depositor: info.msg_sender,
+            on_behalf_of: call_data.onBehalfOf,
+            deposited_token: token_info,
+            deposit_amount,
+            msg_value: info.msg_value,
+        })
+    }
+);
+
+action_impl!(
+    Protocol::AaveV2,
+    crate::AaveV2::withdrawCall,
+    Withdraw,
+    [],
+    call_data: true,
+    return_data: true,
+    |
+    info: CallInfo,
+    call_data: withdrawCall,
+    return_data: withdrawReturn,
+    db_tx: &DB| {
+        let token_info = db_tx.try_fetch_token_info(call_data.asset)?;
+        // the requested amount can be type(uint256).max, the return value is what was
+        // actually withdrawn
+        let withdraw_amount = return_data._0.to_scaled_rational(token_info.decimals);
+
+        return Ok(NormalizedWithdraw {
+            protocol: Protocol::AaveV2,
+            trace_index: info.trace_idx,
+            pool: info.from_address,
+            withdrawer: info.msg_sender,
+            recipient: call_data.to,
+            withdrawn_token: token_info,
+            withdraw_amount,
+            msg_value: info.msg_value,
+        })
+    }
+);
+
+action_impl!(
+    Protocol::AaveV2,
+    crate::AaveV2::borrowCall,
+    Loan,
+    [],
+    call_data: true,
+    |
+    info: CallInfo,
+    call_data: borrowCall,
+    db_tx: &DB| {
+        let token_info = db_tx.try_fetch_token_info(call_data.asset)?;
+        let loan_amount = call_data.amount.to_scaled_rational(token_info.decimals);
+
+        // debt is opened for `onBehalfOf`, the funds are always sent to the caller
+        return Ok(NormalizedLoan {
+            protocol: Protocol::AaveV2,
+            trace_index: info.trace_idx,
+            pool: info.from_address,
+            borrower: call_data.onBehalfOf,
+            recipient: info.msg_sender,
+            loaned_token: token_info,
+            loan_amount,
+            msg_value: info.msg_value,
+        })
+    }
+);
+
+action_impl!(
+    Protocol::AaveV2,
+    crate::AaveV2::repayCall,
+    Repayment,
+    [],
+    call_data: true,
+    return_data: true,
+    |
+    info: CallInfo,
+    call_data: repayCall,
+    return_data: repayReturn,
+    db_tx: &DB| {
+        let token_info = db_tx.try_fetch_token_info(call_data.asset)?;
+        // the requested amount can be type(uint256).max, the return value is what was
+        // actually repaid
+        let repayment_amount = return_data._0.to_scaled_rational(token_info.decimals);
+
+        return Ok(NormalizedRepayment {
+            protocol: Protocol::AaveV2,
+            trace_index: info.trace_idx,
+            pool: info.from_address,
+            payer: info.msg_sender,
+            borrower: call_data.onBehalfOf,
+            repayed_token: token_info,
+            repayment_amount,
+            msg_value: info.msg_value,
+        })
+    }
+);
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{hex, Address, Bytes, U256};
+    use alloy_sol_types::SolCall;
+    use brontes_types::{
+        constants::{USDC_ADDRESS, WETH_ADDRESS},
+        normalized_actions::{Action, NormalizedLoan, NormalizedRepayment},
+        structured_trace::CallFrameInfo,
+        Protocol,
+    };
+    use malachite::Rational;
+
+    use crate::{test_utils::ClassifierTestUtils, AaveV2};
+
+    const POOL: Address = Address::new(hex!("7d2768de32b0b80b7a3454c06bdac94a69ddc7a9"));
+    const POOL_IMPL: Address = Address::new(hex!("b9184a4480830bf89b55b73631e287df9079f466"));
+    const ACCOUNT: Address = Address::new(hex!("de74395831f3ba9edc7cbee1fcb441cf24c0af4d"));
+    const DELEGATOR: Address = Address::new(hex!("d911560979b78821d7b045c79e36e9cbfc2f6c6f"));
+
+    /// The pool proxy delegates to the implementation, which is the address
+    /// the protocol is registered at
+    fn pool_frame(call_data: Vec<u8>, return_data: Bytes) -> CallFrameInfo<'static> {
+        CallFrameInfo {
+            trace_idx: 4,
+            call_data: call_data.into(),
+            return_data,
+            target_address: POOL_IMPL,
+            from_address: POOL,
+            logs: &[],
+            delegate_logs: vec![],
+            msg_sender: ACCOUNT,
+            msg_value: U256::ZERO,
+        }
+    }
+
+    fn ensure_pool(classifier_utils: &ClassifierTestUtils) {
+        classifier_utils.ensure_protocol(
+            Protocol::AaveV2,
+            POOL_IMPL,
+            Address::ZERO,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+    }
+
+    #[brontes_macros::test]
+    async fn test_aave_v2_borrow_on_behalf_of() {
+        let classifier_utils = ClassifierTestUtils::new().await;
+        ensure_pool(&classifier_utils);
+
+        // the debt is opened for the delegator, the funds go to the caller
+        let call = AaveV2::borrowCall {
+            asset:            WETH_ADDRESS,
+            amount:           U256::from(10u64).pow(U256::from(18)) * U256::from(3u64),
+            interestRateMode: U256::from(2u64),
+            referralCode:     0,
+            onBehalfOf:       DELEGATOR,
+        };
+
+        let action = classifier_utils
+            .dispatch_call_frame(pool_frame(call.abi_encode(), Bytes::new()), 15_000_000)
+            .expect("borrow wasn't classified");
+
+        assert_eq!(
+            action,
+            Action::Loan(NormalizedLoan {
+                protocol:     Protocol::AaveV2,
+                trace_index:  4,
+                pool:         POOL,
+                borrower:     DELEGATOR,
+                recipient:    ACCOUNT,
+                loaned_token: classifier_utils.get_token_info(WETH_ADDRESS),
+                loan_amount:  Rational::from(3),
+                msg_value:    U256::ZERO,
+            })
+        );
+    }
+
+    #[brontes_macros::test]
+    async fn test_aave_v2_repay_max() {
+        let classifier_utils = ClassifierTestUtils::new().await;
+        ensure_pool(&classifier_utils);
+
+        // repays the full debt, the amount is only known from the return value
+        let call = AaveV2::repayCall {
+            asset:      USDC_ADDRESS,
+            amount:     U256::MAX,
+            rateMode:   U256::from(2u64),
+            onBehalfOf: DELEGATOR,
+        };
+        let repaid = U256::from(500_250_000u64).to_be_bytes_vec().into();
+
+        let action = classifier_utils
+            .dispatch_call_frame(pool_frame(call.abi_encode(), repaid), 15_000_000)
+            .expect("repay wasn't classified");
+
+        assert_eq!(
+            action,
+            Action::Repayment(NormalizedRepayment {
+                protocol:         Protocol::AaveV2,
+                trace_index:      4,
+                pool:             POOL,
+                payer:            ACCOUNT,
+                borrower:         DELEGATOR,
+                repayed_token:    classifier_utils.get_token_info(USDC_ADDRESS),
+                repayment_amount: Rational::from_signeds(2001, 4),
+                msg_value:        U256::ZERO,
+            })
+        );
+    }
+}
@@ -1,6 +1,9 @@
 use brontes_macros::action_impl;
 use brontes_types::{
-    normalized_actions::{NormalizedFlashLoan, NormalizedLiquidation},
+    normalized_actions::{
+        NormalizedDeposit, NormalizedFlashLoan, NormalizedLiquidation, NormalizedLoan,
+        NormalizedRepayment, NormalizedWithdraw,
+    },
     structured_trace::CallInfo,
     utils::ToScaledRational,
     Protocol,
@@ -113,16 +116,259 @@ action_impl!(
     }
 );
 
+action_impl!(
+    Protocol::AaveV3,
+    crate::AaveV3::depositCall,
+    Deposit,
+    [],
+    call_data: true,
+    |
+    info: CallInfo,
+    call_data: depositCall,
+    db_tx: &DB| {
+        let token_info = db_tx.try_fetch_token_info(call_data.asset)?;
+        let deposit_amount = call_data.amount.to_scaled_rational(token_info.decimals);
+
+        return Ok(NormalizedDeposit {
+            protocol: Protocol::AaveV3,
+            trace_index: info.trace_idx,
+            pool: info.from_address,
+            depositor: info.msg_sender,
+            on_behalf_of: call_data.onBehalfOf,
+            deposited_token: token_info,
+            deposit_amount,
+            msg_value: info.msg_value,
+        })
+    }
+);
+
+action_impl!(
+    Protocol::AaveV3,
+    crate::AaveV3::supplyCall,
+    Deposit,
+    [],
+    call_data: true,
+    |
+    info: CallInfo,
+    call_data: supplyCall,
+    db_tx: &DB| {
+        let token_info = db_tx.try_fetch_token_info(call_data.asset)?;
+        let deposit_amount = call_data.amount.to_scaled_rational(token_info.decimals);
+
+        return Ok(NormalizedDeposit {
+            protocol: Protocol::AaveV3,
+            trace_index: info.trace_idx,
+            pool: info.from_address,
+            depositor: info.msg_sender,
+            on_behalf_of: call_data.onBehalfOf,
+            deposited_token: token_info,
+            deposit_amount,
+            msg_value: info.msg_value,
+        })
+    }
+);
+
+action_impl!(
+    Protocol::AaveV3,
+    crate::AaveV3::withdrawCall,
+    Withdraw,
+    [],
+    call_data: true,
+    return_data: true,
+    |
+    info: CallInfo,
+    call_data: withdrawCall,
+    return_data: withdrawReturn,
+    db_tx: &DB| {
+        let token_info = db_tx.try_fetch_token_info(call_data.asset)?;
+        // the requested amount can be type(uint256).max, the return value is what was
+        // actually withdrawn
+        let withdraw_amount = return_data._0.to_scaled_rational(token_info.decimals);
+
+        return Ok(NormalizedWithdraw {
+            protocol: Protocol::AaveV3,
+            trace_index: info.trace_idx,
+            pool: info.from_address,
+            withdrawer: info.msg_sender,
+            recipient: call_data.to,
+            withdrawn_token: token_info,
+            withdraw_amount,
+            msg_value: info.msg_value,
+        })
+    }
+);
+
+action_impl!(
+    Protocol::AaveV3,
+    crate::AaveV3::borrowCall,
+    Loan,
+    [],
+    call_data: true,
+    |
+    info: CallInfo,
+    call_data: borrowCall,
+    db_tx: &DB| {
+        let token_info = db_tx.try_fetch_token_info(call_data.asset)?;
+        let loan_amount = call_data.amount.to_scaled_rational(token_info.decimals);
+
+        // debt is opened for `onBehalfOf`, the funds are always sent to the caller
+        return Ok(NormalizedLoan {
+            protocol: Protocol::AaveV3,
+            trace_index: info.trace_idx,
+            pool: info.from_address,
+            borrower: call_data.onBehalfOf,
+            recipient: info.msg_sender,
+            loaned_token: token_info,
+            loan_amount,
+            msg_value: info.msg_value,
+        })
+    }
+);
+
+action_impl!(
+    Protocol::AaveV3,
+    crate::AaveV3::repayCall,
+    Repayment,
+    [],
+    call_data: true,
+    return_data: true,
+    |
+    info: CallInfo,
+    call_data: repayCall,
+    return_data: repayReturn,
+    db_tx: &DB| {
+        let token_info = db_tx.try_fetch_token_info(call_data.asset)?;
+        // the requested amount can be type(uint256).max, the return value is what was
+        // actually repaid
+        let repayment_amount = return_data._0.to_scaled_rational(token_info.decimals);
+
+        return Ok(NormalizedRepayment {
+            protocol: Protocol::AaveV3,
+            trace_index: info.trace_idx,
+            pool: info.from_address,
+            payer: info.msg_sender,
+            borrower: call_data.onBehalfOf,
+            repayed_token: token_info,
+            repayment_amount,
+            msg_value: info.msg_value,
+        })
+    }
+);
+
 #[cfg(test)]
 mod tests {
-    use alloy_primitives::{hex, Address, B256, U256};
+    use alloy_primitives::{hex, Address, Bytes, B256, U256};
+    use alloy_sol_types::SolCall;
     use brontes_types::{
-        normalized_actions::{Action, NormalizedLiquidation},
+        constants::USDC_ADDRESS,
+        normalized_actions::{
+            Action, NormalizedDeposit, NormalizedLiquidation, NormalizedWithdraw,
+        },
+        structured_trace::CallFrameInfo,
         Protocol, TreeSearchBuilder,
     };
     use malachite::Rational;
 
-    use crate::test_utils::ClassifierTestUtils;
+    use crate::{test_utils::ClassifierTestUtils, AaveV3};
+
+    const POOL: Address = Address::new(hex!("87870bca3f3fd6335c3f4ce8392d69350b4fa4e2"));
+    const POOL_IMPL: Address = Address::new(hex!("5faab9e1adbddad0a08734be8a52185fd6558e14"));
+    const ACCOUNT: Address = Address::new(hex!("e967954b9b48cb1a0079d76466e82c4d52a8f5d3"));
+
+    /// The pool proxy delegates to the implementation, which is the address
+    /// the protocol is registered at
+    fn pool_frame(call_data: Vec<u8>, return_data: Bytes) -> CallFrameInfo<'static> {
+        CallFrameInfo {
+            trace_idx: 2,
+            call_data: call_data.into(),
+            return_data,
+            target_address: POOL_IMPL,
+            from_address: POOL,
+            logs: &[],
+            delegate_logs: vec![],
+            msg_sender: ACCOUNT,
+            msg_value: U256::ZERO,
+        }
+    }
+
+    #[brontes_macros::test]
+    async fn test_aave_v3_supply() {
+        let classifier_utils = ClassifierTestUtils::new().await;
+        classifier_utils.ensure_protocol(
+            Protocol::AaveV3,
+            POOL_IMPL,
+            Address::ZERO,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+
+        let call = AaveV3::supplyCall {
+            asset:        USDC_ADDRESS,
+            amount:       U256::from(25_000_000_000u64),
+            onBehalfOf:   ACCOUNT,
+            referralCode: 0,
+        };
+
+        let action = classifier_utils
+            .dispatch_call_frame(pool_frame(call.abi_encode(), Bytes::new()), 19_000_000)
+            .expect("supply wasn't classified");
+
+        assert_eq!(
+            action,
+            Action::Deposit(NormalizedDeposit {
+                protocol:        Protocol::AaveV3,
+                trace_index:     2,
+                pool:            POOL,
+                depositor:       ACCOUNT,
+                on_behalf_of:    ACCOUNT,
+                deposited_token: classifier_utils.get_token_info(USDC_ADDRESS),
+                deposit_amount:  Rational::from(25_000),
+                msg_value:       U256::ZERO,
+            })
+        );
+    }
+
+    #[brontes_macros::test]
+    async fn test_aave_v3_withdraw_max() {
+        let classifier_utils = ClassifierTestUtils::new().await;
+        classifier_utils.ensure_protocol(
+            Protocol::AaveV3,
+            POOL_IMPL,
+            Address::ZERO,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+
+        let recipient = Address::new(hex!("80d4230c0a68fc59cb264329d3a717fcaa472a13"));
+        // withdraws the full balance, the amount is only known from the return value
+        let call =
+            AaveV3::withdrawCall { asset: USDC_ADDRESS, amount: U256::MAX, to: recipient };
+        let withdrawn = U256::from(1_000_500_000u64).to_be_bytes_vec().into();
+
+        let action = classifier_utils
+            .dispatch_call_frame(pool_frame(call.abi_encode(), withdrawn), 19_000_000)
+            .expect("withdraw wasn't classified");
+
+        assert_eq!(
+            action,
+            Action::Withdraw(NormalizedWithdraw {
+                protocol: Protocol::AaveV3,
+                trace_index: 2,
+                pool: POOL,
+                withdrawer: ACCOUNT,
+                recipient,
+                withdrawn_token: classifier_utils.get_token_info(USDC_ADDRESS),
+                withdraw_amount: Rational::from_signeds(2001, 2),
+                msg_value: U256::ZERO,
+            })
+        );
+    }
 
     #[brontes_macros::test]
     async fn test_aave_v3_liquidation() {
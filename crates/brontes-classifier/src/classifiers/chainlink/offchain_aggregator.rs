@@ -0,0 +1,216 @@
+use std::sync::Arc;
+
+use brontes_macros::action_impl;
+use brontes_types::{
+    make_call_request, normalized_actions::NormalizedOracleUpdate, structured_trace::CallInfo,
+    traits::TracingProvider, Protocol, ToScaledRational,
+};
+use tracing::debug;
+
+use crate::ChainlinkOffchainAggregator::latestAnswerCall;
+
+// The aggregator is registered with the asset it prices as token0 and the
+// denomination of the answer as token1, the decimals of token1 are the decimals
+// of the feed
+action_impl!(
+    Protocol::Chainlink,
+    crate::ChainlinkOffchainAggregator::transmitCall,
+    OracleUpdate,
+    [NewTransmission],
+    logs: true,
+    |info: CallInfo, log_data: ChainlinkTransmitCallLogs, db_tx: &DB| {
+        let transmission = log_data.new_transmission_field?;
+
+        let details = db_tx.get_protocol_details(info.target_address)?;
+        let base_token = db_tx.try_fetch_token_info(details.token0)?;
+        let quote_token = db_tx.try_fetch_token_info(details.token1)?;
+
+        let price = transmission.answer.to_scaled_rational(quote_token.decimals);
+
+        Ok(NormalizedOracleUpdate {
+            protocol: Protocol::Chainlink,
+            trace_index: info.trace_idx,
+            from: info.from_address,
+            oracle: info.target_address,
+            round_id: transmission.aggregatorRoundId as u64,
+            base_token,
+            quote_token,
+            // the report only carries the new answer, this is filled in by
+            // `load_previous_answer`
+            previous_price: Default::default(),
+            price,
+        })
+    }
+);
+
+/// Sets the previous price of the update to the answer the feed had at the end
+/// of the previous block. Left at zero if the aggregator can't be queried.
+pub async fn load_previous_answer<T: TracingProvider>(
+    provider: &Arc<T>,
+    block: u64,
+    update: &mut NormalizedOracleUpdate,
+) {
+    match make_call_request(latestAnswerCall::new(()), provider, update.oracle, Some(block - 1))
+        .await
+    {
+        Ok(answer) => {
+            update.previous_price = answer._0.to_scaled_rational(update.quote_token.decimals);
+        }
+        Err(e) => {
+            debug!(oracle = ?update.oracle, error = %e, "failed to query previous oracle answer");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{aliases::I192, hex, Address, Bytes, Log, B256, U256};
+    use alloy_sol_types::{SolCall, SolEvent, SolValue};
+    use brontes_types::{
+        constants::{USDC_ADDRESS, WETH_ADDRESS},
+        db::token_info::{TokenInfo, TokenInfoWithAddress},
+        normalized_actions::{Action, NormalizedOracleUpdate},
+        structured_trace::CallFrameInfo,
+        Protocol,
+    };
+    use malachite::Rational;
+
+    use crate::{test_utils::ClassifierTestUtils, ChainlinkOffchainAggregator};
+
+    const USDC_USD: Address = Address::new(hex!("789190466E21a8b78b8027866CBBDc151542A26C"));
+    const USDC_ETH: Address = Address::new(hex!("e5BbBdb2Bb953371841318E1Edfbf727447CeF2E"));
+    /// Chainlink's placeholder address for USD
+    const USD: Address = Address::new(hex!("0000000000000000000000000000000000000348"));
+    const TRANSMITTER: Address = Address::new(hex!("cc4f1a6d6e0f1f6a1b1ad6f1b8bdba2a0e6f1c7e"));
+
+    /// A transmit of a report with the median `answer`, the report is encoded
+    /// the way the aggregator decodes it
+    fn transmit(feed: Address, round_id: u32, answer: i128) -> (Vec<u8>, Vec<Log>) {
+        let observations = [answer - 2, answer, answer + 3]
+            .map(|o| I192::try_from(o).unwrap())
+            .to_vec();
+        let raw_report_context = B256::repeat_byte(0x01);
+
+        let report = (raw_report_context, B256::ZERO, observations.clone()).abi_encode_params();
+        let call = ChainlinkOffchainAggregator::transmitCall {
+            _report: report.into(),
+            _rs:     vec![B256::repeat_byte(0x02); 2],
+            _ss:     vec![B256::repeat_byte(0x03); 2],
+            _rawVs:  B256::ZERO,
+        };
+
+        let log = ChainlinkOffchainAggregator::NewTransmission {
+            aggregatorRoundId: round_id,
+            answer: I192::try_from(answer).unwrap(),
+            transmitter: TRANSMITTER,
+            observations,
+            observers: Bytes::from(vec![0, 1, 2]),
+            rawReportContext: raw_report_context,
+        };
+
+        (call.abi_encode(), vec![Log { address: feed, data: log.encode_log_data() }])
+    }
+
+    fn feed_frame(feed: Address, call_data: Vec<u8>, logs: &[Log]) -> CallFrameInfo<'_> {
+        CallFrameInfo {
+            trace_idx: 0,
+            call_data: call_data.into(),
+            return_data: Bytes::new(),
+            target_address: feed,
+            from_address: TRANSMITTER,
+            logs,
+            delegate_logs: vec![],
+            msg_sender: TRANSMITTER,
+            msg_value: U256::ZERO,
+        }
+    }
+
+    fn usd() -> TokenInfoWithAddress {
+        TokenInfoWithAddress { address: USD, inner: TokenInfo::new(8, "USD".to_string()) }
+    }
+
+    #[brontes_macros::test]
+    async fn test_usd_feed_transmit() {
+        let classifier_utils = ClassifierTestUtils::new().await;
+        classifier_utils.ensure_token(usd());
+        classifier_utils.ensure_protocol(
+            Protocol::Chainlink,
+            USDC_USD,
+            USDC_ADDRESS,
+            Some(USD),
+            None,
+            None,
+            None,
+            None,
+        );
+
+        let (call_data, logs) = transmit(USDC_USD, 29_410, 99_991_234);
+        let action = classifier_utils
+            .dispatch_call_frame(feed_frame(USDC_USD, call_data, &logs), 19_000_000)
+            .expect("transmit wasn't classified");
+
+        assert_eq!(
+            action,
+            Action::OracleUpdate(NormalizedOracleUpdate {
+                protocol:       Protocol::Chainlink,
+                trace_index:    0,
+                from:           TRANSMITTER,
+                oracle:         USDC_USD,
+                round_id:       29_410,
+                base_token:     classifier_utils.get_token_info(USDC_ADDRESS),
+                quote_token:    usd(),
+                previous_price: Rational::default(),
+                price:          Rational::from_signeds(99_991_234, 100_000_000),
+            })
+        );
+    }
+
+    #[brontes_macros::test]
+    async fn test_eth_feed_transmit() {
+        let classifier_utils = ClassifierTestUtils::new().await;
+        classifier_utils.ensure_protocol(
+            Protocol::Chainlink,
+            USDC_ETH,
+            USDC_ADDRESS,
+            Some(WETH_ADDRESS),
+            None,
+            None,
+            None,
+            None,
+        );
+
+        // 0.000294 ETH per USDC, with the 18 decimals of the feed
+        let (call_data, logs) = transmit(USDC_ETH, 11_025, 294_000_000_000_000);
+        let Some(Action::OracleUpdate(update)) = classifier_utils
+            .dispatch_call_frame(feed_frame(USDC_ETH, call_data, &logs), 19_000_000)
+        else {
+            panic!("transmit wasn't classified as an oracle update")
+        };
+
+        assert_eq!(update.round_id, 11_025);
+        assert_eq!(update.base_token.address, USDC_ADDRESS);
+        assert_eq!(update.quote_token.address, WETH_ADDRESS);
+        assert_eq!(update.price, Rational::from_signeds(294, 1_000_000));
+    }
+
+    #[brontes_macros::test]
+    async fn test_transmit_without_transmission_log() {
+        let classifier_utils = ClassifierTestUtils::new().await;
+        classifier_utils.ensure_token(usd());
+        classifier_utils.ensure_protocol(
+            Protocol::Chainlink,
+            USDC_USD,
+            USDC_ADDRESS,
+            Some(USD),
+            None,
+            None,
+            None,
+            None,
+        );
+
+        let (call_data, _) = transmit(USDC_USD, 29_410, 99_991_234);
+        assert!(classifier_utils
+            .dispatch_call_frame(feed_frame(USDC_USD, call_data, &[]), 19_000_000)
+            .is_none());
+    }
+}
@@ -1,20 +1,39 @@
+use std::sync::Arc;
+
+use alloy_primitives::{address, Address};
+use brontes_core::missing_token_info::load_missing_token_info;
+use brontes_database::libmdbx::{DBWriter, LibmdbxReader};
 use brontes_macros::action_impl;
 use brontes_pricing::Protocol;
 use brontes_types::{
-    normalized_actions::NormalizedLiquidation, structured_trace::CallInfo, utils::ToScaledRational,
+    db::token_info::TokenInfoWithAddress,
+    make_call_request,
+    normalized_actions::{
+        pool::NormalizedNewPool, NormalizedDeposit, NormalizedLiquidation, NormalizedLoan,
+        NormalizedRepayment, NormalizedWithdraw,
+    },
+    structured_trace::CallInfo,
+    traits::TracingProvider,
+    utils::ToScaledRational,
 };
+use tracing::debug;
+
+use crate::CompoundV2CToken::underlyingCall;
+
+/// cEther holds native eth and has no `underlying()`
+pub const CETH_ADDRESS: Address = address!("4ddc2d193948926d02f9b1fe9e1daa0718270ed5");
 
 action_impl!(
     Protocol::CompoundV2,
-    crate::CompoundV2CToken::liquidateBorrowCall,
+    crate::CompoundV2CToken::liquidateBorrow_0Call,
     Liquidation,
     [..LiquidateBorrow],
     call_data: true,
     logs: true,
     |
     info: CallInfo,
-    call_data: liquidateBorrowCall,
-    log_data: CompoundV2LiquidateBorrowCallLogs,
+    call_data: liquidateBorrow_0Call,
+    log_data: CompoundV2LiquidateBorrow_0CallLogs,
     db_tx: &DB | {
         let logs = log_data.liquidate_borrow_field?;
         let debt_asset = info.target_address;
@@ -37,17 +56,573 @@ action_impl!(
     }
 );
 
+action_impl!(
+    Protocol::CompoundV2,
+    crate::CompoundV2CToken::mint_0Call,
+    Deposit,
+    [..Mint],
+    logs: true,
+    include_delegated_logs: true,
+    |
+    info: CallInfo,
+    log_data: CompoundV2Mint_0CallLogs,
+    db_tx: &DB | {
+        let logs = log_data.mint_field?;
+        let token_info = underlying_token_info(db_tx, info.target_address)?;
+
+        return Ok(NormalizedDeposit {
+            protocol: Protocol::CompoundV2,
+            trace_index: info.trace_idx,
+            pool: info.target_address,
+            depositor: logs.minter,
+            on_behalf_of: logs.minter,
+            deposit_amount: logs.mintAmount.to_scaled_rational(token_info.decimals),
+            deposited_token: token_info,
+            msg_value: info.msg_value,
+        })
+    }
+);
+
+action_impl!(
+    Protocol::CompoundV2,
+    crate::CompoundV2CToken::redeemCall,
+    Withdraw,
+    [..Redeem],
+    logs: true,
+    include_delegated_logs: true,
+    |
+    info: CallInfo,
+    log_data: CompoundV2RedeemCallLogs,
+    db_tx: &DB | {
+        let logs = log_data.redeem_field?;
+        let token_info = underlying_token_info(db_tx, info.target_address)?;
+
+        return Ok(NormalizedWithdraw {
+            protocol: Protocol::CompoundV2,
+            trace_index: info.trace_idx,
+            pool: info.target_address,
+            withdrawer: logs.redeemer,
+            recipient: logs.redeemer,
+            withdraw_amount: logs.redeemAmount.to_scaled_rational(token_info.decimals),
+            withdrawn_token: token_info,
+            msg_value: info.msg_value,
+        })
+    }
+);
+
+action_impl!(
+    Protocol::CompoundV2,
+    crate::CompoundV2CToken::redeemUnderlyingCall,
+    Withdraw,
+    [..Redeem],
+    logs: true,
+    include_delegated_logs: true,
+    |
+    info: CallInfo,
+    log_data: CompoundV2RedeemUnderlyingCallLogs,
+    db_tx: &DB | {
+        let logs = log_data.redeem_field?;
+        let token_info = underlying_token_info(db_tx, info.target_address)?;
+
+        return Ok(NormalizedWithdraw {
+            protocol: Protocol::CompoundV2,
+            trace_index: info.trace_idx,
+            pool: info.target_address,
+            withdrawer: logs.redeemer,
+            recipient: logs.redeemer,
+            withdraw_amount: logs.redeemAmount.to_scaled_rational(token_info.decimals),
+            withdrawn_token: token_info,
+            msg_value: info.msg_value,
+        })
+    }
+);
+
+action_impl!(
+    Protocol::CompoundV2,
+    crate::CompoundV2CToken::borrowCall,
+    Loan,
+    [..Borrow],
+    logs: true,
+    include_delegated_logs: true,
+    |
+    info: CallInfo,
+    log_data: CompoundV2BorrowCallLogs,
+    db_tx: &DB | {
+        let logs = log_data.borrow_field?;
+        let token_info = underlying_token_info(db_tx, info.target_address)?;
+
+        return Ok(NormalizedLoan {
+            protocol: Protocol::CompoundV2,
+            trace_index: info.trace_idx,
+            pool: info.target_address,
+            borrower: logs.borrower,
+            recipient: logs.borrower,
+            loan_amount: logs.borrowAmount.to_scaled_rational(token_info.decimals),
+            loaned_token: token_info,
+            msg_value: info.msg_value,
+        })
+    }
+);
+
+action_impl!(
+    Protocol::CompoundV2,
+    crate::CompoundV2CToken::repayBorrow_0Call,
+    Repayment,
+    [..RepayBorrow],
+    logs: true,
+    include_delegated_logs: true,
+    |
+    info: CallInfo,
+    log_data: CompoundV2RepayBorrow_0CallLogs,
+    db_tx: &DB | {
+        let logs = log_data.repay_borrow_field?;
+        let token_info = underlying_token_info(db_tx, info.target_address)?;
+
+        return Ok(NormalizedRepayment {
+            protocol: Protocol::CompoundV2,
+            trace_index: info.trace_idx,
+            pool: info.target_address,
+            payer: logs.payer,
+            borrower: logs.borrower,
+            repayment_amount: logs.repayAmount.to_scaled_rational(token_info.decimals),
+            repayed_token: token_info,
+            msg_value: info.msg_value,
+        })
+    }
+);
+
+action_impl!(
+    Protocol::CompoundV2,
+    crate::CompoundV2CToken::repayBorrowBehalf_0Call,
+    Repayment,
+    [..RepayBorrow],
+    logs: true,
+    include_delegated_logs: true,
+    |
+    info: CallInfo,
+    log_data: CompoundV2RepayBorrowBehalf_0CallLogs,
+    db_tx: &DB | {
+        let logs = log_data.repay_borrow_field?;
+        let token_info = underlying_token_info(db_tx, info.target_address)?;
+
+        return Ok(NormalizedRepayment {
+            protocol: Protocol::CompoundV2,
+            trace_index: info.trace_idx,
+            pool: info.target_address,
+            payer: logs.payer,
+            borrower: logs.borrower,
+            repayment_amount: logs.repayAmount.to_scaled_rational(token_info.decimals),
+            repayed_token: token_info,
+            msg_value: info.msg_value,
+        })
+    }
+);
+
+// cEther takes native eth through payable overloads without an amount, the
+// amount is the value of the call
+
+action_impl!(
+    Protocol::CompoundV2,
+    crate::CompoundV2CToken::liquidateBorrow_1Call,
+    Liquidation,
+    [..LiquidateBorrow],
+    call_data: true,
+    logs: true,
+    |
+    info: CallInfo,
+    call_data: liquidateBorrow_1Call,
+    log_data: CompoundV2LiquidateBorrow_1CallLogs,
+    db_tx: &DB | {
+        let logs = log_data.liquidate_borrow_field?;
+        let collateral = db_tx.try_fetch_token_info(call_data.cTokenCollateral)?;
+        let collateral_liquidated = logs.seizeTokens.to_scaled_rational(collateral.decimals);
+        return Ok(NormalizedLiquidation {
+            protocol: Protocol::CompoundV2,
+            trace_index: info.trace_idx,
+            pool: info.target_address,
+            liquidator: logs.liquidator,
+            debtor: call_data.borrower,
+            collateral_asset: collateral,
+            debt_asset: TokenInfoWithAddress::native_eth(),
+            covered_debt: info.msg_value.to_scaled_rational(18),
+            liquidated_collateral: collateral_liquidated,
+            msg_value: info.msg_value,
+        })
+    }
+);
+
+action_impl!(
+    Protocol::CompoundV2,
+    crate::CompoundV2CToken::mint_1Call,
+    Deposit,
+    [..Mint],
+    logs: true,
+    include_delegated_logs: true,
+    |
+    info: CallInfo,
+    log_data: CompoundV2Mint_1CallLogs,
+    _db_tx: &DB | {
+        let logs = log_data.mint_field?;
+
+        return Ok(NormalizedDeposit {
+            protocol: Protocol::CompoundV2,
+            trace_index: info.trace_idx,
+            pool: info.target_address,
+            depositor: logs.minter,
+            on_behalf_of: logs.minter,
+            deposit_amount: info.msg_value.to_scaled_rational(18),
+            deposited_token: TokenInfoWithAddress::native_eth(),
+            msg_value: info.msg_value,
+        })
+    }
+);
+
+action_impl!(
+    Protocol::CompoundV2,
+    crate::CompoundV2CToken::repayBorrow_1Call,
+    Repayment,
+    [..RepayBorrow],
+    logs: true,
+    include_delegated_logs: true,
+    |
+    info: CallInfo,
+    log_data: CompoundV2RepayBorrow_1CallLogs,
+    _db_tx: &DB | {
+        let logs = log_data.repay_borrow_field?;
+
+        return Ok(NormalizedRepayment {
+            protocol: Protocol::CompoundV2,
+            trace_index: info.trace_idx,
+            pool: info.target_address,
+            payer: logs.payer,
+            borrower: logs.borrower,
+            repayment_amount: info.msg_value.to_scaled_rational(18),
+            repayed_token: TokenInfoWithAddress::native_eth(),
+            msg_value: info.msg_value,
+        })
+    }
+);
+
+action_impl!(
+    Protocol::CompoundV2,
+    crate::CompoundV2CToken::repayBorrowBehalf_1Call,
+    Repayment,
+    [..RepayBorrow],
+    logs: true,
+    include_delegated_logs: true,
+    |
+    info: CallInfo,
+    log_data: CompoundV2RepayBorrowBehalf_1CallLogs,
+    _db_tx: &DB | {
+        let logs = log_data.repay_borrow_field?;
+
+        return Ok(NormalizedRepayment {
+            protocol: Protocol::CompoundV2,
+            trace_index: info.trace_idx,
+            pool: info.target_address,
+            payer: logs.payer,
+            borrower: logs.borrower,
+            repayment_amount: info.msg_value.to_scaled_rational(18),
+            repayed_token: TokenInfoWithAddress::native_eth(),
+            msg_value: info.msg_value,
+        })
+    }
+);
+
+/// The underlying asset of a cToken is stored as its second token when the
+/// cToken is discovered, see [`load_underlying`], or seeded from the
+/// classifier config.
+fn underlying_token_info<DB: LibmdbxReader>(
+    db_tx: &DB,
+    c_token: Address,
+) -> eyre::Result<TokenInfoWithAddress> {
+    if c_token == CETH_ADDRESS {
+        return Ok(TokenInfoWithAddress::native_eth())
+    }
+
+    let underlying = db_tx.get_protocol_details(c_token)?.token1;
+    if underlying == Address::ZERO {
+        eyre::bail!("no underlying registered for cToken {c_token:?}")
+    }
+    db_tx.try_fetch_token_info(underlying)
+}
+
+/// Adds the underlying to a cToken discovered without one, e.g. through
+/// `initialize_1`, before it's registered. Does nothing for any other pool.
+pub async fn load_underlying<T: TracingProvider, DB: LibmdbxReader + DBWriter>(
+    provider: &Arc<T>,
+    db: &DB,
+    block: u64,
+    pool: &mut NormalizedNewPool,
+) {
+    if pool.protocol != Protocol::CompoundV2
+        || pool.pool_address == CETH_ADDRESS
+        || pool.tokens.len() > 1
+    {
+        return
+    }
+    let c_token = pool.pool_address;
+
+    let underlying =
+        match make_call_request(underlyingCall::new(()), provider, c_token, Some(block)).await {
+            Ok(underlying) => underlying._0,
+            Err(e) => {
+                debug!(?c_token, error = %e, "failed to query the underlying of a cToken");
+                return
+            }
+        };
+
+    if db.try_fetch_token_info(underlying).is_err() {
+        load_missing_token_info(provider, db, block, underlying).await;
+    }
+
+    pool.tokens = vec![c_token, underlying];
+}
+
 #[cfg(test)]
 mod tests {
-    use alloy_primitives::{hex, Address, B256, U256};
+    use alloy_primitives::{hex, Address, Log, B256, U256};
+    use alloy_sol_types::{SolCall, SolEvent};
     use brontes_types::{
-        db::token_info::TokenInfoWithAddress,
-        normalized_actions::{Action, NormalizedLiquidation},
+        constants::{DAI_ADDRESS, USDC_ADDRESS},
+        db::token_info::{TokenInfo, TokenInfoWithAddress},
+        normalized_actions::{Action, NormalizedDeposit, NormalizedLiquidation, NormalizedLoan},
+        structured_trace::CallFrameInfo,
         Protocol, TreeSearchBuilder,
     };
     use malachite::Rational;
 
-    use crate::test_utils::ClassifierTestUtils;
+    use super::*;
+    use crate::{test_utils::ClassifierTestUtils, CompoundV2CToken};
+
+    const CDAI: Address = Address::new(hex!("5d3a536E4D6DbD6114cc1Ead35777bAB948E3643"));
+    const CUSDC: Address = Address::new(hex!("39AA39c021dfbaE8faC545936693aC917d5E7563"));
+    const ACCOUNT: Address = Address::new(hex!("De74395831F3Ba9EdC7cBEE1fcB441cf24c0AF4d"));
+    const LIQUIDATOR: Address = Address::new(hex!("D911560979B78821D7b045C79E36E9CbfC2F6C6F"));
+
+    fn c_token_frame(c_token: Address, call_data: Vec<u8>, logs: &[Log]) -> CallFrameInfo<'_> {
+        CallFrameInfo {
+            trace_idx: 1,
+            call_data: call_data.into(),
+            return_data: U256::ZERO.to_be_bytes_vec().into(),
+            target_address: c_token,
+            from_address: ACCOUNT,
+            logs,
+            delegate_logs: vec![],
+            msg_sender: ACCOUNT,
+            msg_value: U256::ZERO,
+        }
+    }
+
+    #[brontes_macros::test]
+    async fn test_compound_v2_mint_prices_underlying() {
+        let classifier_utils = ClassifierTestUtils::new().await;
+        classifier_utils.ensure_protocol(
+            Protocol::CompoundV2,
+            CDAI,
+            CDAI,
+            Some(DAI_ADDRESS),
+            None,
+            None,
+            None,
+            None,
+        );
+
+        let mint_amount = U256::from(1_500u64) * U256::from(10u64).pow(U256::from(18));
+        let mint = CompoundV2CToken::Mint {
+            minter:     ACCOUNT,
+            mintAmount: mint_amount,
+            mintTokens: U256::from(6_800_000_000_000u64),
+        };
+        let logs = vec![Log { address: CDAI, data: mint.encode_log_data() }];
+        let call = CompoundV2CToken::mint_0Call { mintAmount: mint_amount };
+
+        let action = classifier_utils
+            .dispatch_call_frame(c_token_frame(CDAI, call.abi_encode(), &logs), 19_000_000)
+            .expect("mint wasn't classified");
+
+        assert_eq!(
+            action,
+            Action::Deposit(NormalizedDeposit {
+                protocol:        Protocol::CompoundV2,
+                trace_index:     1,
+                pool:            CDAI,
+                depositor:       ACCOUNT,
+                on_behalf_of:    ACCOUNT,
+                deposited_token: classifier_utils.get_token_info(DAI_ADDRESS),
+                deposit_amount:  Rational::from(1_500),
+                msg_value:       U256::ZERO,
+            })
+        );
+    }
+
+    #[brontes_macros::test]
+    async fn test_compound_v2_ceth_borrow_is_native_eth() {
+        let classifier_utils = ClassifierTestUtils::new().await;
+        // cEther is registered without an underlying
+        classifier_utils.ensure_protocol(
+            Protocol::CompoundV2,
+            CETH_ADDRESS,
+            CETH_ADDRESS,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+
+        let borrow_amount = U256::from(10u64).pow(U256::from(18)) * U256::from(2u64);
+        let borrow = CompoundV2CToken::Borrow {
+            borrower:       ACCOUNT,
+            borrowAmount:   borrow_amount,
+            accountBorrows: borrow_amount,
+            totalBorrows:   borrow_amount * U256::from(1_000u64),
+        };
+        let logs = vec![Log { address: CETH_ADDRESS, data: borrow.encode_log_data() }];
+        let call = CompoundV2CToken::borrowCall { borrowAmount: borrow_amount };
+
+        let action = classifier_utils
+            .dispatch_call_frame(c_token_frame(CETH_ADDRESS, call.abi_encode(), &logs), 19_000_000)
+            .expect("borrow wasn't classified");
+
+        assert_eq!(
+            action,
+            Action::Loan(NormalizedLoan {
+                protocol:     Protocol::CompoundV2,
+                trace_index:  1,
+                pool:         CETH_ADDRESS,
+                borrower:     ACCOUNT,
+                recipient:    ACCOUNT,
+                loaned_token: TokenInfoWithAddress::native_eth(),
+                loan_amount:  Rational::from(2),
+                msg_value:    U256::ZERO,
+            })
+        );
+    }
+
+    #[brontes_macros::test]
+    async fn test_compound_v2_ceth_liquidation_repays_msg_value() {
+        let classifier_utils = ClassifierTestUtils::new().await;
+        classifier_utils.ensure_protocol(
+            Protocol::CompoundV2,
+            CETH_ADDRESS,
+            CETH_ADDRESS,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        let collateral = TokenInfoWithAddress {
+            address: CUSDC,
+            inner:   TokenInfo { decimals: 8, symbol: "cUSDC".to_string() },
+        };
+        classifier_utils.ensure_token(collateral.clone());
+
+        let repay_amount = U256::from(10u64).pow(U256::from(18)) / U256::from(2u64);
+        let liquidate = CompoundV2CToken::LiquidateBorrow {
+            liquidator:       LIQUIDATOR,
+            borrower:         ACCOUNT,
+            repayAmount:      repay_amount,
+            cTokenCollateral: CUSDC,
+            seizeTokens:      U256::from(5_000_000_000u64),
+        };
+        let logs = vec![Log { address: CETH_ADDRESS, data: liquidate.encode_log_data() }];
+        let call = CompoundV2CToken::liquidateBorrow_1Call {
+            borrower:         ACCOUNT,
+            cTokenCollateral: CUSDC,
+        };
+
+        let mut frame = c_token_frame(CETH_ADDRESS, call.abi_encode(), &logs);
+        frame.from_address = LIQUIDATOR;
+        frame.msg_sender = LIQUIDATOR;
+        frame.msg_value = repay_amount;
+
+        let action = classifier_utils
+            .dispatch_call_frame(frame, 19_000_000)
+            .expect("liquidation wasn't classified");
+
+        assert_eq!(
+            action,
+            Action::Liquidation(NormalizedLiquidation {
+                protocol:              Protocol::CompoundV2,
+                trace_index:           1,
+                pool:                  CETH_ADDRESS,
+                liquidator:            LIQUIDATOR,
+                debtor:                ACCOUNT,
+                collateral_asset:      collateral,
+                debt_asset:            TokenInfoWithAddress::native_eth(),
+                covered_debt:          Rational::from_signeds(1, 2),
+                liquidated_collateral: Rational::from(50),
+                msg_value:             repay_amount,
+            })
+        );
+    }
+
+    #[brontes_macros::test]
+    async fn test_compound_v2_load_underlying() {
+        let classifier_utils = ClassifierTestUtils::new().await;
+        // registered the way `initialize_1` discovery registers cTokens
+        classifier_utils.ensure_protocol(
+            Protocol::CompoundV2,
+            CUSDC,
+            CUSDC,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+
+        let redeem = CompoundV2CToken::Redeem {
+            redeemer:     ACCOUNT,
+            redeemAmount: U256::from(2_000_000u64),
+            redeemTokens: U256::from(9_000_000_000u64),
+        };
+        let logs = vec![Log { address: CUSDC, data: redeem.encode_log_data() }];
+        let call = CompoundV2CToken::redeemCall { redeemTokens: U256::from(9_000_000_000u64) };
+
+        let frame = c_token_frame(CUSDC, call.abi_encode(), &logs);
+        assert!(classifier_utils
+            .dispatch_call_frame(frame, 19_000_000)
+            .is_none());
+
+        let mut pool = NormalizedNewPool {
+            trace_index:  0,
+            protocol:     Protocol::CompoundV2,
+            pool_address: CUSDC,
+            tokens:       vec![CUSDC],
+        };
+        load_underlying(
+            &classifier_utils.get_tracing_provider(),
+            classifier_utils.libmdbx,
+            19_000_000,
+            &mut pool,
+        )
+        .await;
+        assert_eq!(pool.tokens, vec![CUSDC, USDC_ADDRESS]);
+
+        // registered the way discovery registers it once the underlying is loaded
+        classifier_utils.ensure_protocol(
+            Protocol::CompoundV2,
+            CUSDC,
+            CUSDC,
+            Some(USDC_ADDRESS),
+            None,
+            None,
+            None,
+            None,
+        );
+
+        let frame = c_token_frame(CUSDC, call.abi_encode(), &logs);
+        let Some(Action::Withdraw(withdraw)) =
+            classifier_utils.dispatch_call_frame(frame, 19_000_000)
+        else {
+            panic!("redeem wasn't classified as a withdraw")
+        };
+        assert_eq!(withdraw.withdrawn_token.address, USDC_ADDRESS);
+        assert_eq!(withdraw.withdraw_amount, Rational::from(2));
+    }
 
     #[brontes_macros::test]
     async fn test_compound_v2_liquidation() {
@@ -8,12 +8,13 @@ action_impl!(
     NewPool,
     [],
     call_data: true,
-    |info: CallInfo, _call_data: initialize_0Call, _| {
+    |info: CallInfo, call_data: initialize_0Call, _| {
         Ok(NormalizedNewPool {
             trace_index: info.trace_idx,
             protocol: Protocol::CompoundV2,
             pool_address: info.from_address,
-            tokens: vec![info.from_address]
+            // keep the underlying so lending actions can be priced in it
+            tokens: vec![info.from_address, call_data.underlying_]
         })
     }
 );
@@ -55,7 +56,10 @@ mod tests {
             trace_index:  1,
             protocol:     Protocol::CompoundV2,
             pool_address: hex!("5d3a536e4d6dbd6114cc1ead35777bab948e3643").into(),
-            tokens:       vec![hex!("5d3a536e4d6dbd6114cc1ead35777bab948e3643").into()],
+            tokens:       vec![
+                hex!("5d3a536e4d6dbd6114cc1ead35777bab948e3643").into(),
+                hex!("6b175474e89094c44da98b954eedeac495271d0f").into(),
+            ],
         });
         let search = TreeSearchBuilder::default().with_action(Action::is_new_pool);
 
@@ -0,0 +1,63 @@
+use brontes_macros::action_impl;
+use brontes_pricing::Protocol;
+use brontes_types::{normalized_actions::NormalizedAggregator, structured_trace::CallInfo};
+
+action_impl!(
+    Protocol::KyberSwap,
+    crate::KyberSwapMetaAggregationRouterV2::swapCall,
+    Aggregator,
+    [],
+    call_data: true,
+    |info: CallInfo, call_data: swapCall, _db_tx: &DB| {
+        Ok(NormalizedAggregator {
+            protocol: Protocol::KyberSwap,
+            trace_index: info.trace_idx,
+            from: info.from_address,
+            to: info.target_address,
+            recipient: call_data.execution.desc.dstReceiver,
+            child_actions: vec![],
+            msg_value: info.msg_value,
+        })
+    }
+);
+
+action_impl!(
+    Protocol::KyberSwap,
+    crate::KyberSwapMetaAggregationRouterV2::swapSimpleModeCall,
+    Aggregator,
+    [],
+    call_data: true,
+    |info: CallInfo, call_data: swapSimpleModeCall, _db_tx: &DB| {
+        Ok(NormalizedAggregator {
+            protocol: Protocol::KyberSwap,
+            trace_index: info.trace_idx,
+            from: info.from_address,
+            to: info.target_address,
+            recipient: call_data.desc.dstReceiver,
+            child_actions: vec![],
+            msg_value: info.msg_value,
+        })
+    }
+);
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{hex, Address};
+    use brontes_types::Protocol;
+
+    use crate::test_utils::ClassifierTestUtils;
+
+    const ROUTER: Address = Address::new(hex!("6131B5fae19EA4f9D964eAc0408E4408b66337b5"));
+
+    #[brontes_macros::test]
+    async fn test_kyberswap_pays_out_to_dst_receiver() {
+        let classifier_utils = ClassifierTestUtils::new().await;
+
+        let swaps = classifier_utils
+            .detects_aggregator_in_block(19_000_000, ROUTER, Protocol::KyberSwap)
+            .await
+            .unwrap();
+
+        assert!(swaps.iter().all(|swap| swap.recipient != Address::ZERO));
+    }
+}
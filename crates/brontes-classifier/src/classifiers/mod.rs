@@ -41,6 +41,18 @@ pub use clipper::*;
 pub mod dodo;
 pub use dodo::*;
 
+pub mod chainlink;
+pub use chainlink::*;
+
+pub mod vaults;
+pub use vaults::*;
+
+pub mod paraswap;
+pub use paraswap::*;
+
+pub mod kyberswap;
+pub use kyberswap::*;
+
 discovery_dispatch!(
     DiscoveryClassifier,
     SushiSwapV2Discovery,
@@ -81,6 +93,9 @@ action_dispatch!(
     UniswapV3MintCall,
     UniswapV3BurnCall,
     UniswapV3CollectCall,
+    UniswapV4InitializeCall,
+    UniswapV4SwapCall,
+    UniswapV4ModifyLiquidityCall,
     SushiSwapV3SwapCall,
     SushiSwapV3MintCall,
     SushiSwapV3BurnCall,
@@ -150,6 +165,15 @@ action_dispatch!(
     AaveV2FlashLoanCall,
     AaveV3FlashLoanCall,
     AaveV3FlashLoanSimpleCall,
+    AaveV2DepositCall,
+    AaveV2WithdrawCall,
+    AaveV2BorrowCall,
+    AaveV2RepayCall,
+    AaveV3DepositCall,
+    AaveV3SupplyCall,
+    AaveV3WithdrawCall,
+    AaveV3BorrowCall,
+    AaveV3RepayCall,
     BalancerV1SwapExactAmountInCall,
     BalancerV1SwapExactAmountOutCall,
     BalancerV1BindCall,
@@ -159,7 +183,17 @@ action_dispatch!(
     BalancerV2JoinPoolCall,
     BalancerV2ExitPoolCall,
     BalancerV2RegisterTokensCall,
-    CompoundV2LiquidateBorrowCall,
+    CompoundV2LiquidateBorrow_0Call,
+    CompoundV2LiquidateBorrow_1Call,
+    CompoundV2Mint_0Call,
+    CompoundV2Mint_1Call,
+    CompoundV2RedeemCall,
+    CompoundV2RedeemUnderlyingCall,
+    CompoundV2BorrowCall,
+    CompoundV2RepayBorrow_0Call,
+    CompoundV2RepayBorrow_1Call,
+    CompoundV2RepayBorrowBehalf_0Call,
+    CompoundV2RepayBorrowBehalf_1Call,
     CompoundV2Initialize_0Call,
     CompoundV2Initialize_1Call,
     OneInchV5SwapCall,
@@ -208,5 +242,28 @@ action_dispatch!(
     DodoSellSharesCall,
     DodoSellBaseCall,
     DodoSellQuoteCall,
-    DodoFlashLoanCall
+    DodoFlashLoanCall,
+    ChainlinkTransmitCall,
+    Erc4626DepositCall,
+    Erc4626MintCall,
+    Erc4626WithdrawCall,
+    Erc4626RedeemCall,
+    WethDepositCall,
+    WethWithdrawCall,
+    LidoSubmitCall,
+    LidoWrapCall,
+    LidoUnwrapCall,
+    RocketPoolMintCall,
+    RocketPoolBurnCall,
+    ParaswapV5SimpleSwapCall,
+    ParaswapV5MultiSwapCall,
+    ParaswapV5MegaSwapCall,
+    ParaswapV6SwapExactAmountInCall,
+    ParaswapV6SwapExactAmountOutCall,
+    KyberSwapSwapCall,
+    KyberSwapSwapSimpleModeCall,
+    ZeroXSettlerExecuteCall,
+    ZeroXSettlerExecuteMetaTxnCall,
+    UniswapUniversalRouterExecute_0Call,
+    UniswapUniversalRouterExecute_1Call
 );
@@ -0,0 +1,62 @@
+use brontes_macros::action_impl;
+use brontes_pricing::Protocol;
+use brontes_types::{normalized_actions::NormalizedAggregator, structured_trace::CallInfo};
+
+use super::beneficiary_or_sender;
+
+action_impl!(
+    Protocol::ParaswapV5,
+    crate::ParaswapAugustusV5::simpleSwapCall,
+    Aggregator,
+    [],
+    call_data: true,
+    |info: CallInfo, call_data: simpleSwapCall, _db_tx: &DB| {
+        Ok(NormalizedAggregator {
+            protocol: Protocol::ParaswapV5,
+            trace_index: info.trace_idx,
+            from: info.from_address,
+            to: info.target_address,
+            recipient: beneficiary_or_sender(call_data.data.beneficiary, info.msg_sender),
+            child_actions: vec![],
+            msg_value: info.msg_value,
+        })
+    }
+);
+
+action_impl!(
+    Protocol::ParaswapV5,
+    crate::ParaswapAugustusV5::multiSwapCall,
+    Aggregator,
+    [],
+    call_data: true,
+    |info: CallInfo, call_data: multiSwapCall, _db_tx: &DB| {
+        Ok(NormalizedAggregator {
+            protocol: Protocol::ParaswapV5,
+            trace_index: info.trace_idx,
+            from: info.from_address,
+            to: info.target_address,
+            recipient: beneficiary_or_sender(call_data.data.beneficiary, info.msg_sender),
+            child_actions: vec![],
+            msg_value: info.msg_value,
+        })
+    }
+);
+
+action_impl!(
+    Protocol::ParaswapV5,
+    crate::ParaswapAugustusV5::megaSwapCall,
+    Aggregator,
+    [],
+    call_data: true,
+    |info: CallInfo, call_data: megaSwapCall, _db_tx: &DB| {
+        Ok(NormalizedAggregator {
+            protocol: Protocol::ParaswapV5,
+            trace_index: info.trace_idx,
+            from: info.from_address,
+            to: info.target_address,
+            recipient: beneficiary_or_sender(call_data.data.beneficiary, info.msg_sender),
+            child_actions: vec![],
+            msg_value: info.msg_value,
+        })
+    }
+);
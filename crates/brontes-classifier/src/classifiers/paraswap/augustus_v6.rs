@@ -0,0 +1,43 @@
+use brontes_macros::action_impl;
+use brontes_pricing::Protocol;
+use brontes_types::{normalized_actions::NormalizedAggregator, structured_trace::CallInfo};
+
+use super::beneficiary_or_sender;
+
+action_impl!(
+    Protocol::ParaswapV6,
+    crate::ParaswapAugustusV6::swapExactAmountInCall,
+    Aggregator,
+    [],
+    call_data: true,
+    |info: CallInfo, call_data: swapExactAmountInCall, _db_tx: &DB| {
+        Ok(NormalizedAggregator {
+            protocol: Protocol::ParaswapV6,
+            trace_index: info.trace_idx,
+            from: info.from_address,
+            to: info.target_address,
+            recipient: beneficiary_or_sender(call_data.swapData.beneficiary, info.msg_sender),
+            child_actions: vec![],
+            msg_value: info.msg_value,
+        })
+    }
+);
+
+action_impl!(
+    Protocol::ParaswapV6,
+    crate::ParaswapAugustusV6::swapExactAmountOutCall,
+    Aggregator,
+    [],
+    call_data: true,
+    |info: CallInfo, call_data: swapExactAmountOutCall, _db_tx: &DB| {
+        Ok(NormalizedAggregator {
+            protocol: Protocol::ParaswapV6,
+            trace_index: info.trace_idx,
+            from: info.from_address,
+            to: info.target_address,
+            recipient: beneficiary_or_sender(call_data.swapData.beneficiary, info.msg_sender),
+            child_actions: vec![],
+            msg_value: info.msg_value,
+        })
+    }
+);
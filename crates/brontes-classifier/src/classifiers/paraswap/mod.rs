@@ -0,0 +1,50 @@
+mod augustus_v5;
+mod augustus_v6;
+
+use alloy_primitives::Address;
+pub use augustus_v5::*;
+pub use augustus_v6::*;
+
+/// Augustus pays out to the caller if no beneficiary is set
+fn beneficiary_or_sender(beneficiary: Address, msg_sender: Address) -> Address {
+    if beneficiary == Address::ZERO {
+        msg_sender
+    } else {
+        beneficiary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{hex, Address};
+    use brontes_types::Protocol;
+
+    use crate::test_utils::ClassifierTestUtils;
+
+    const AUGUSTUS_V5: Address = Address::new(hex!("DEF171Fe48CF0115B1d80b88dc8eAB59176FEe57"));
+    const AUGUSTUS_V6: Address = Address::new(hex!("6A000F20005980200259B80c5102003040001068"));
+
+    #[brontes_macros::test]
+    async fn test_paraswap_v5_pays_out_to_sender_without_beneficiary() {
+        let classifier_utils = ClassifierTestUtils::new().await;
+
+        let swaps = classifier_utils
+            .detects_aggregator_in_block(19_000_000, AUGUSTUS_V5, Protocol::ParaswapV5)
+            .await
+            .unwrap();
+
+        assert!(swaps.iter().all(|swap| swap.recipient != Address::ZERO));
+    }
+
+    #[brontes_macros::test]
+    async fn test_paraswap_v6_swap() {
+        let classifier_utils = ClassifierTestUtils::new().await;
+
+        let swaps = classifier_utils
+            .detects_aggregator_in_block(20_000_000, AUGUSTUS_V6, Protocol::ParaswapV6)
+            .await
+            .unwrap();
+
+        assert!(swaps.iter().all(|swap| swap.recipient != Address::ZERO));
+    }
+}
@@ -0,0 +1,227 @@
+use alloy_primitives::{Address, I256};
+use alloy_sol_types::SolCall;
+use brontes_macros::action_impl;
+use brontes_pricing::{uniswap_v4, Protocol};
+use brontes_types::{
+    db::uniswap_v4::UniswapV4PoolKey,
+    normalized_actions::{
+        Action, NormalizedBurn, NormalizedCollect, NormalizedMint, NormalizedPoolConfigUpdate,
+        NormalizedSwap,
+    },
+    structured_trace::{CallFrameInfo, CallInfo},
+    ToScaledRational,
+};
+
+use crate::UniswapV4PoolManager::{initializeCall, modifyLiquidityCall, swapCall, PoolKey};
+
+action_impl!(
+    Protocol::UniswapV4,
+    crate::UniswapV4PoolManager::initializeCall,
+    PoolConfigUpdate,
+    [Initialize],
+    logs: true,
+    |info: CallInfo, log_data: UniswapV4InitializeCallLogs, _| {
+        let logs = log_data.initialize_field?;
+        let key = uniswap_v4::PoolKey {
+            currency0:   logs.currency0,
+            currency1:   logs.currency1,
+            fee:         logs.fee,
+            tickSpacing: logs.tickSpacing,
+            hooks:       logs.hooks,
+        };
+        let tokens = key.tokens().to_vec();
+
+        Ok(NormalizedPoolConfigUpdate {
+            trace_index: info.trace_idx,
+            protocol: Protocol::UniswapV4,
+            pool_address: key.pool_address(),
+            tokens,
+        })
+    }
+);
+
+action_impl!(
+    Protocol::UniswapV4,
+    crate::UniswapV4PoolManager::swapCall,
+    Swap,
+    [Swap],
+    call_data: true,
+    return_data: true,
+    |
+    info: CallInfo,
+    call_data: swapCall,
+    return_data: swapReturn,
+    db_tx: &DB| {
+        let (pool, [token_0, token_1]) = pool_of(call_data.key);
+        let (token_0_delta, token_1_delta) = unpack_balance_delta(return_data.swapDelta);
+
+        let t0_info = db_tx.try_fetch_token_info(token_0)?;
+        let t1_info = db_tx.try_fetch_token_info(token_1)?;
+
+        // the deltas are from the perspective of the caller, the negative side is
+        // owed to the pool
+        let (amount_in, amount_out, token_in, token_out) = if token_0_delta.is_negative() {
+            (
+                token_0_delta.abs().to_scaled_rational(t0_info.decimals),
+                token_1_delta.to_scaled_rational(t1_info.decimals),
+                t0_info,
+                t1_info,
+            )
+        } else {
+            (
+                token_1_delta.abs().to_scaled_rational(t1_info.decimals),
+                token_0_delta.to_scaled_rational(t0_info.decimals),
+                t1_info,
+                t0_info,
+            )
+        };
+
+        Ok(NormalizedSwap {
+            protocol: Protocol::UniswapV4,
+            trace_index: info.trace_idx,
+            from: info.from_address,
+            recipient: info.from_address,
+            pool,
+            token_in,
+            token_out,
+            amount_in,
+            amount_out,
+            msg_value: info.msg_value
+        })
+    }
+);
+
+action_impl!(
+    Protocol::UniswapV4,
+    crate::UniswapV4PoolManager::modifyLiquidityCall,
+    Action,
+    [ModifyLiquidity],
+    call_data: true,
+    return_data: true,
+    |
+    info: CallInfo,
+    call_data: modifyLiquidityCall,
+    return_data: modifyLiquidityReturn,
+    db_tx: &DB| {
+        let (pool, [token_0, token_1]) = pool_of(call_data.key);
+
+        let t0_info = db_tx.try_fetch_token_info(token_0)?;
+        let t1_info = db_tx.try_fetch_token_info(token_1)?;
+
+        // the caller delta includes the fees of the position, which are collected on
+        // every modification
+        let (delta_0, delta_1) = unpack_balance_delta(return_data.callerDelta);
+        let (fees_0, fees_1) = unpack_balance_delta(return_data.feesAccrued);
+        let (principal_0, principal_1) = (delta_0 - fees_0, delta_1 - fees_1);
+
+        let liquidity_delta = call_data.params.liquidityDelta;
+        let action = if liquidity_delta.is_zero() {
+            Action::Collect(NormalizedCollect {
+                protocol: Protocol::UniswapV4,
+                trace_index: info.trace_idx,
+                from: info.from_address,
+                recipient: info.from_address,
+                pool,
+                amount: vec![
+                    fees_0.to_scaled_rational(t0_info.decimals),
+                    fees_1.to_scaled_rational(t1_info.decimals),
+                ],
+                token: vec![t0_info, t1_info],
+            })
+        } else if liquidity_delta.is_positive() {
+            Action::Mint(NormalizedMint {
+                protocol: Protocol::UniswapV4,
+                trace_index: info.trace_idx,
+                from: info.from_address,
+                recipient: info.from_address,
+                pool,
+                amount: vec![
+                    principal_0.abs().to_scaled_rational(t0_info.decimals),
+                    principal_1.abs().to_scaled_rational(t1_info.decimals),
+                ],
+                token: vec![t0_info, t1_info],
+            })
+        } else {
+            Action::Burn(NormalizedBurn {
+                protocol: Protocol::UniswapV4,
+                trace_index: info.trace_idx,
+                from: info.from_address,
+                recipient: info.from_address,
+                pool,
+                amount: vec![
+                    principal_0.to_scaled_rational(t0_info.decimals),
+                    principal_1.to_scaled_rational(t1_info.decimals),
+                ],
+                token: vec![t0_info, t1_info],
+            })
+        };
+
+        Ok(action)
+    }
+);
+
+/// The address we use for the pool together with its tokens.
+fn pool_of(key: PoolKey) -> (Address, [Address; 2]) {
+    let key = pricing_key(key);
+
+    (key.pool_address(), key.tokens())
+}
+
+fn pricing_key(key: PoolKey) -> uniswap_v4::PoolKey {
+    uniswap_v4::PoolKey {
+        currency0:   key.currency0,
+        currency1:   key.currency1,
+        fee:         key.fee,
+        tickSpacing: key.tickSpacing,
+        hooks:       key.hooks,
+    }
+}
+
+/// The key of the pool a classified call to the PoolManager acts on, together
+/// with the address we use for the pool. The classifier writes it to the
+/// database so that the pool can be loaded for pricing.
+pub(crate) fn pool_key_of_call(
+    call_info: &CallFrameInfo<'_>,
+) -> Option<(Address, UniswapV4PoolKey)> {
+    if call_info.target_address != uniswap_v4::POOL_MANAGER {
+        return None
+    }
+
+    let call_data = &call_info.call_data;
+    let key = initializeCall::abi_decode(call_data, false)
+        .map(|call| call.key)
+        .or_else(|_| swapCall::abi_decode(call_data, false).map(|call| call.key))
+        .or_else(|_| modifyLiquidityCall::abi_decode(call_data, false).map(|call| call.key))
+        .ok()
+        .map(pricing_key)?;
+
+    Some((key.pool_address(), key.into()))
+}
+
+/// A `BalanceDelta` packs the delta of currency0 into the upper and the delta
+/// of currency1 into the lower 128 bits.
+fn unpack_balance_delta(delta: I256) -> (I256, I256) {
+    (delta.asr(128), delta.wrapping_shl(128).asr(128))
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::U256;
+
+    use super::*;
+
+    #[test]
+    fn test_unpack_balance_delta() {
+        let amount_0 = I256::try_from(-1_000_000i64).unwrap();
+        let amount_1 = I256::try_from(42i64).unwrap();
+        let packed =
+            amount_0.wrapping_shl(128) | (amount_1 & I256::from_raw(U256::from(u128::MAX)));
+
+        assert_eq!(unpack_balance_delta(packed), (amount_0, amount_1));
+
+        let packed =
+            amount_1.wrapping_shl(128) | (amount_0 & I256::from_raw(U256::from(u128::MAX)));
+
+        assert_eq!(unpack_balance_delta(packed), (amount_1, amount_0));
+    }
+}
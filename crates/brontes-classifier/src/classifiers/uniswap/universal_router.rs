@@ -0,0 +1,63 @@
+use brontes_macros::action_impl;
+use brontes_pricing::Protocol;
+use brontes_types::{normalized_actions::NormalizedAggregator, structured_trace::CallInfo};
+
+// The recipients are encoded in the commands, the recipient of the output is
+// set from the router's transfers during multi frame classification
+action_impl!(
+    Protocol::UniswapUniversalRouter,
+    crate::UniswapUniversalRouter::execute_0Call,
+    Aggregator,
+    [],
+    |info: CallInfo, _db_tx: &DB| {
+        Ok(NormalizedAggregator {
+            protocol:      Protocol::UniswapUniversalRouter,
+            trace_index:   info.trace_idx,
+            from:          info.from_address,
+            to:            info.target_address,
+            recipient:     info.msg_sender,
+            child_actions: vec![],
+            msg_value:     info.msg_value,
+        })
+    }
+);
+
+action_impl!(
+    Protocol::UniswapUniversalRouter,
+    crate::UniswapUniversalRouter::execute_1Call,
+    Aggregator,
+    [],
+    |info: CallInfo, _db_tx: &DB| {
+        Ok(NormalizedAggregator {
+            protocol:      Protocol::UniswapUniversalRouter,
+            trace_index:   info.trace_idx,
+            from:          info.from_address,
+            to:            info.target_address,
+            recipient:     info.msg_sender,
+            child_actions: vec![],
+            msg_value:     info.msg_value,
+        })
+    }
+);
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{hex, Address};
+    use brontes_types::Protocol;
+
+    use crate::test_utils::ClassifierTestUtils;
+
+    const ROUTER: Address = Address::new(hex!("3fC91A3afd70395Cd496C647d5a6CC9D4B2b7FAD"));
+
+    #[brontes_macros::test]
+    async fn test_universal_router_defaults_recipient_to_sender() {
+        let classifier_utils = ClassifierTestUtils::new().await;
+
+        let swaps = classifier_utils
+            .detects_aggregator_in_block(19_000_000, ROUTER, Protocol::UniswapUniversalRouter)
+            .await
+            .unwrap();
+
+        assert!(swaps.iter().all(|swap| swap.recipient == swap.from));
+    }
+}
@@ -0,0 +1,227 @@
+use brontes_macros::action_impl;
+use brontes_types::{
+    normalized_actions::{share_price, NormalizedVaultDeposit, NormalizedVaultWithdraw},
+    structured_trace::CallInfo,
+    Protocol, ToScaledRational,
+};
+
+use super::vault_tokens;
+
+action_impl!(
+    Protocol::Erc4626,
+    crate::Erc4626Vault::depositCall,
+    VaultDeposit,
+    [],
+    call_data: true,
+    return_data: true,
+    |
+    info: CallInfo,
+    call_data: depositCall,
+    return_data: depositReturn,
+    db_tx: &DB | {
+        let (asset, share) = vault_tokens(db_tx, info.target_address)?;
+        let asset_amount = call_data.assets.to_scaled_rational(asset.decimals);
+        let share_amount = return_data.shares.to_scaled_rational(share.decimals);
+
+        Ok(NormalizedVaultDeposit {
+            protocol: Protocol::Erc4626,
+            trace_index: info.trace_idx,
+            vault: info.target_address,
+            depositor: info.from_address,
+            recipient: call_data.receiver,
+            share_price: share_price(&asset_amount, &share_amount),
+            asset,
+            asset_amount,
+            share,
+            share_amount,
+            msg_value: info.msg_value,
+        })
+    }
+);
+
+action_impl!(
+    Protocol::Erc4626,
+    crate::Erc4626Vault::mintCall,
+    VaultDeposit,
+    [],
+    call_data: true,
+    return_data: true,
+    |
+    info: CallInfo,
+    call_data: mintCall,
+    return_data: mintReturn,
+    db_tx: &DB | {
+        let (asset, share) = vault_tokens(db_tx, info.target_address)?;
+        let asset_amount = return_data.assets.to_scaled_rational(asset.decimals);
+        let share_amount = call_data.shares.to_scaled_rational(share.decimals);
+
+        Ok(NormalizedVaultDeposit {
+            protocol: Protocol::Erc4626,
+            trace_index: info.trace_idx,
+            vault: info.target_address,
+            depositor: info.from_address,
+            recipient: call_data.receiver,
+            share_price: share_price(&asset_amount, &share_amount),
+            asset,
+            asset_amount,
+            share,
+            share_amount,
+            msg_value: info.msg_value,
+        })
+    }
+);
+
+action_impl!(
+    Protocol::Erc4626,
+    crate::Erc4626Vault::withdrawCall,
+    VaultWithdraw,
+    [],
+    call_data: true,
+    return_data: true,
+    |
+    info: CallInfo,
+    call_data: withdrawCall,
+    return_data: withdrawReturn,
+    db_tx: &DB | {
+        let (asset, share) = vault_tokens(db_tx, info.target_address)?;
+        let asset_amount = call_data.assets.to_scaled_rational(asset.decimals);
+        let share_amount = return_data.shares.to_scaled_rational(share.decimals);
+
+        Ok(NormalizedVaultWithdraw {
+            protocol: Protocol::Erc4626,
+            trace_index: info.trace_idx,
+            vault: info.target_address,
+            owner: call_data.owner,
+            recipient: call_data.receiver,
+            share_price: share_price(&asset_amount, &share_amount),
+            share,
+            share_amount,
+            asset,
+            asset_amount,
+            msg_value: info.msg_value,
+        })
+    }
+);
+
+action_impl!(
+    Protocol::Erc4626,
+    crate::Erc4626Vault::redeemCall,
+    VaultWithdraw,
+    [],
+    call_data: true,
+    return_data: true,
+    |
+    info: CallInfo,
+    call_data: redeemCall,
+    return_data: redeemReturn,
+    db_tx: &DB | {
+        let (asset, share) = vault_tokens(db_tx, info.target_address)?;
+        let asset_amount = return_data.assets.to_scaled_rational(asset.decimals);
+        let share_amount = call_data.shares.to_scaled_rational(share.decimals);
+
+        Ok(NormalizedVaultWithdraw {
+            protocol: Protocol::Erc4626,
+            trace_index: info.trace_idx,
+            vault: info.target_address,
+            owner: call_data.owner,
+            recipient: call_data.receiver,
+            share_price: share_price(&asset_amount, &share_amount),
+            share,
+            share_amount,
+            asset,
+            asset_amount,
+            msg_value: info.msg_value,
+        })
+    }
+);
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{hex, Address, U256};
+    use alloy_sol_types::{SolCall, SolValue};
+    use brontes_types::{constants::DAI_ADDRESS, normalized_actions::Action};
+    use malachite::Rational;
+
+    use super::*;
+    use crate::{
+        classifiers::vaults::test_utils::{ensure_vault, token, units, vault_frame, SEARCHER},
+        test_utils::ClassifierTestUtils,
+        Erc4626Vault,
+    };
+
+    const SDAI: Address = Address::new(hex!("83F20F44975D03b1b09e64809B757c47f942BEeA"));
+    const RECEIVER: Address = Address::repeat_byte(0x7e);
+
+    #[brontes_macros::test]
+    async fn test_deposit() {
+        let classifier_utils = ClassifierTestUtils::new().await;
+        let (dai, sdai) = (token(DAI_ADDRESS, "DAI"), token(SDAI, "sDAI"));
+        ensure_vault(&classifier_utils, Protocol::Erc4626, &dai, &sdai);
+
+        // 1.05 DAI per sDAI
+        let call_data =
+            Erc4626Vault::depositCall { assets: units(105), receiver: RECEIVER }.abi_encode();
+        let action = classifier_utils
+            .dispatch_call_frame(
+                vault_frame(SDAI, SEARCHER, call_data, units(100).abi_encode(), U256::ZERO, &[]),
+                19_000_000,
+            )
+            .expect("deposit wasn't classified");
+
+        assert_eq!(
+            action,
+            Action::VaultDeposit(NormalizedVaultDeposit {
+                protocol:     Protocol::Erc4626,
+                trace_index:  0,
+                vault:        SDAI,
+                depositor:    SEARCHER,
+                recipient:    RECEIVER,
+                asset:        dai,
+                asset_amount: Rational::from_signeds(21, 20),
+                share:        sdai,
+                share_amount: Rational::from(1),
+                share_price:  Rational::from_signeds(21, 20),
+                msg_value:    U256::ZERO,
+            })
+        );
+    }
+
+    #[brontes_macros::test]
+    async fn test_redeem() {
+        let classifier_utils = ClassifierTestUtils::new().await;
+        let (dai, sdai) = (token(DAI_ADDRESS, "DAI"), token(SDAI, "sDAI"));
+        ensure_vault(&classifier_utils, Protocol::Erc4626, &dai, &sdai);
+
+        // redeeming shares of the searcher to another account
+        let call_data = Erc4626Vault::redeemCall {
+            shares:   units(200),
+            receiver: RECEIVER,
+            owner:    SEARCHER,
+        }
+        .abi_encode();
+        let Some(Action::VaultWithdraw(redeem)) = classifier_utils.dispatch_call_frame(
+            vault_frame(SDAI, SEARCHER, call_data, units(210).abi_encode(), U256::ZERO, &[]),
+            19_000_000,
+        ) else {
+            panic!("redeem wasn't classified as a vault withdrawal")
+        };
+
+        assert_eq!(redeem.owner, SEARCHER);
+        assert_eq!(redeem.recipient, RECEIVER);
+        assert_eq!(redeem.share, sdai);
+        assert_eq!(redeem.share_amount, Rational::from(2));
+        assert_eq!(redeem.asset, dai);
+        assert_eq!(redeem.asset_amount, Rational::from_signeds(21, 10));
+        assert_eq!(redeem.share_price, Rational::from_signeds(21, 20));
+    }
+
+    #[brontes_macros::test]
+    async fn test_traced_block() {
+        let classifier_utils = ClassifierTestUtils::new().await;
+
+        classifier_utils
+            .detects_vault_in_block(19_000_000, SDAI, Protocol::Erc4626)
+            .await
+            .unwrap();
+    }
+}
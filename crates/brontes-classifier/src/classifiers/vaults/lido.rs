@@ -0,0 +1,247 @@
+use brontes_macros::action_impl;
+use brontes_types::{
+    normalized_actions::{share_price, NormalizedVaultDeposit, NormalizedVaultWithdraw},
+    structured_trace::CallInfo,
+    Protocol, ToScaledRational,
+};
+use malachite::{num::basic::traits::One, Rational};
+
+use super::vault_tokens;
+
+// stETH rebases, so the balance minted for the staked ETH is one to one even
+// though the shares of the pool it returns aren't
+action_impl!(
+    Protocol::Lido,
+    crate::LidoStETH::submitCall,
+    VaultDeposit,
+    [Submitted],
+    logs: true,
+    include_delegated_logs: true,
+    |
+    info: CallInfo,
+    log_data: LidoSubmitCallLogs,
+    db_tx: &DB | {
+        let submitted = log_data.submitted_field?;
+        let (asset, share) = vault_tokens(db_tx, info.target_address)?;
+        let amount = submitted.amount.to_scaled_rational(asset.decimals);
+
+        Ok(NormalizedVaultDeposit {
+            protocol: Protocol::Lido,
+            trace_index: info.trace_idx,
+            vault: info.target_address,
+            depositor: submitted.sender,
+            recipient: submitted.sender,
+            asset,
+            asset_amount: amount.clone(),
+            share,
+            share_amount: amount,
+            share_price: Rational::ONE,
+            msg_value: info.msg_value,
+        })
+    }
+);
+
+action_impl!(
+    Protocol::Lido,
+    crate::LidoWstETH::wrapCall,
+    VaultDeposit,
+    [],
+    call_data: true,
+    return_data: true,
+    |
+    info: CallInfo,
+    call_data: wrapCall,
+    return_data: wrapReturn,
+    db_tx: &DB | {
+        let (asset, share) = vault_tokens(db_tx, info.target_address)?;
+        let asset_amount = call_data._stETHAmount.to_scaled_rational(asset.decimals);
+        let share_amount = return_data._0.to_scaled_rational(share.decimals);
+
+        Ok(NormalizedVaultDeposit {
+            protocol: Protocol::Lido,
+            trace_index: info.trace_idx,
+            vault: info.target_address,
+            depositor: info.from_address,
+            recipient: info.from_address,
+            share_price: share_price(&asset_amount, &share_amount),
+            asset,
+            asset_amount,
+            share,
+            share_amount,
+            msg_value: info.msg_value,
+        })
+    }
+);
+
+action_impl!(
+    Protocol::Lido,
+    crate::LidoWstETH::unwrapCall,
+    VaultWithdraw,
+    [],
+    call_data: true,
+    return_data: true,
+    |
+    info: CallInfo,
+    call_data: unwrapCall,
+    return_data: unwrapReturn,
+    db_tx: &DB | {
+        let (asset, share) = vault_tokens(db_tx, info.target_address)?;
+        let share_amount = call_data._wstETHAmount.to_scaled_rational(share.decimals);
+        let asset_amount = return_data._0.to_scaled_rational(asset.decimals);
+
+        Ok(NormalizedVaultWithdraw {
+            protocol: Protocol::Lido,
+            trace_index: info.trace_idx,
+            vault: info.target_address,
+            owner: info.from_address,
+            recipient: info.from_address,
+            share_price: share_price(&asset_amount, &share_amount),
+            share,
+            share_amount,
+            asset,
+            asset_amount,
+            msg_value: info.msg_value,
+        })
+    }
+);
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{hex, Address, Log};
+    use alloy_sol_types::{SolCall, SolEvent, SolValue};
+    use brontes_types::{constants::ETH_ADDRESS, normalized_actions::Action};
+
+    use super::*;
+    use crate::{
+        classifiers::vaults::test_utils::{ensure_vault, token, units, vault_frame, SEARCHER},
+        test_utils::ClassifierTestUtils,
+        LidoStETH, LidoWstETH,
+    };
+
+    const STETH: Address = Address::new(hex!("ae7ab96520DE3A18E5e111B5EaAb095312D7fE84"));
+    const WSTETH: Address = Address::new(hex!("7f39C581F595B53c5cb19bD0b3f8dA6c935E2Ca0"));
+
+    #[brontes_macros::test]
+    async fn test_submit() {
+        let classifier_utils = ClassifierTestUtils::new().await;
+        let (eth, steth) = (token(ETH_ADDRESS, "ETH"), token(STETH, "stETH"));
+        ensure_vault(&classifier_utils, Protocol::Lido, &eth, &steth);
+
+        let call_data = LidoStETH::submitCall { _referral: Address::ZERO }.abi_encode();
+        let submitted = LidoStETH::Submitted {
+            sender:   SEARCHER,
+            amount:   units(200),
+            referral: Address::ZERO,
+        };
+        let logs = [Log { address: STETH, data: submitted.encode_log_data() }];
+
+        let action = classifier_utils
+            .dispatch_call_frame(
+                vault_frame(STETH, SEARCHER, call_data, vec![], units(200), &logs),
+                19_000_000,
+            )
+            .expect("submit wasn't classified");
+
+        assert_eq!(
+            action,
+            Action::VaultDeposit(NormalizedVaultDeposit {
+                protocol:     Protocol::Lido,
+                trace_index:  0,
+                vault:        STETH,
+                depositor:    SEARCHER,
+                recipient:    SEARCHER,
+                asset:        eth,
+                asset_amount: Rational::from(2),
+                share:        steth,
+                share_amount: Rational::from(2),
+                share_price:  Rational::ONE,
+                msg_value:    units(200),
+            })
+        );
+    }
+
+    #[brontes_macros::test]
+    async fn test_submit_without_submitted_log() {
+        let classifier_utils = ClassifierTestUtils::new().await;
+        let (eth, steth) = (token(ETH_ADDRESS, "ETH"), token(STETH, "stETH"));
+        ensure_vault(&classifier_utils, Protocol::Lido, &eth, &steth);
+
+        let call_data = LidoStETH::submitCall { _referral: Address::ZERO }.abi_encode();
+        assert!(classifier_utils
+            .dispatch_call_frame(
+                vault_frame(STETH, SEARCHER, call_data, vec![], units(200), &[]),
+                19_000_000,
+            )
+            .is_none());
+    }
+
+    #[brontes_macros::test]
+    async fn test_wrap_and_unwrap() {
+        let classifier_utils = ClassifierTestUtils::new().await;
+        let (steth, wsteth) = (token(STETH, "stETH"), token(WSTETH, "wstETH"));
+        ensure_vault(&classifier_utils, Protocol::Lido, &steth, &wsteth);
+
+        // 1.2 stETH per wstETH
+        let call_data = LidoWstETH::wrapCall { _stETHAmount: units(120) }.abi_encode();
+        let Some(Action::VaultDeposit(wrap)) = classifier_utils.dispatch_call_frame(
+            vault_frame(
+                WSTETH,
+                SEARCHER,
+                call_data,
+                units(100).abi_encode(),
+                Default::default(),
+                &[],
+            ),
+            19_000_000,
+        ) else {
+            panic!("wrap wasn't classified as a vault deposit")
+        };
+
+        assert_eq!(wrap.depositor, SEARCHER);
+        assert_eq!(wrap.asset, steth);
+        assert_eq!(wrap.asset_amount, Rational::from_signeds(6, 5));
+        assert_eq!(wrap.share, wsteth);
+        assert_eq!(wrap.share_amount, Rational::from(1));
+        assert_eq!(wrap.share_price, Rational::from_signeds(6, 5));
+
+        let call_data = LidoWstETH::unwrapCall { _wstETHAmount: units(100) }.abi_encode();
+        let Some(Action::VaultWithdraw(unwrap)) = classifier_utils.dispatch_call_frame(
+            vault_frame(
+                WSTETH,
+                SEARCHER,
+                call_data,
+                units(120).abi_encode(),
+                Default::default(),
+                &[],
+            ),
+            19_000_000,
+        ) else {
+            panic!("unwrap wasn't classified as a vault withdrawal")
+        };
+
+        assert_eq!(unwrap.owner, SEARCHER);
+        assert_eq!(unwrap.share_amount, Rational::from(1));
+        assert_eq!(unwrap.asset_amount, Rational::from_signeds(6, 5));
+        assert_eq!(unwrap.share_price, Rational::from_signeds(6, 5));
+    }
+
+    #[brontes_macros::test]
+    async fn test_steth_traced_block() {
+        let classifier_utils = ClassifierTestUtils::new().await;
+
+        classifier_utils
+            .detects_vault_in_block(19_000_000, STETH, Protocol::Lido)
+            .await
+            .unwrap();
+    }
+
+    #[brontes_macros::test]
+    async fn test_wsteth_traced_block() {
+        let classifier_utils = ClassifierTestUtils::new().await;
+
+        classifier_utils
+            .detects_vault_in_block(19_000_000, WSTETH, Protocol::Lido)
+            .await
+            .unwrap();
+    }
+}
@@ -0,0 +1,88 @@
+mod erc4626;
+mod lido;
+mod rocket_pool;
+mod weth;
+
+use alloy_primitives::Address;
+use brontes_database::libmdbx::LibmdbxReader;
+use brontes_types::db::token_info::TokenInfoWithAddress;
+pub use erc4626::*;
+pub use lido::*;
+pub use rocket_pool::*;
+pub use weth::*;
+
+/// Vaults are registered with the asset they hold as token0 and their share
+/// token as token1. Vaults of ETH use the ETH placeholder address as asset.
+fn vault_tokens<DB: LibmdbxReader>(
+    db_tx: &DB,
+    vault: Address,
+) -> eyre::Result<(TokenInfoWithAddress, TokenInfoWithAddress)> {
+    let details = db_tx.get_protocol_details(vault)?;
+
+    Ok((db_tx.try_fetch_token_info(details.token0)?, db_tx.try_fetch_token_info(details.token1)?))
+}
+
+#[cfg(test)]
+mod test_utils {
+    use alloy_primitives::{Address, Bytes, Log, U256};
+    use brontes_types::{
+        db::token_info::{TokenInfo, TokenInfoWithAddress},
+        structured_trace::CallFrameInfo,
+        Protocol,
+    };
+
+    use crate::test_utils::ClassifierTestUtils;
+
+    pub(super) const SEARCHER: Address = Address::repeat_byte(0x5e);
+
+    /// `amount` hundredths of a token with 18 decimals
+    pub(super) fn units(amount: u128) -> U256 {
+        U256::from(amount) * U256::from(10u128.pow(18)) / U256::from(100)
+    }
+
+    pub(super) fn token(address: Address, symbol: &str) -> TokenInfoWithAddress {
+        TokenInfoWithAddress { address, inner: TokenInfo::new(18, symbol.to_string()) }
+    }
+
+    /// Registers a vault that is its own share token
+    pub(super) fn ensure_vault(
+        classifier_utils: &ClassifierTestUtils,
+        protocol: Protocol,
+        asset: &TokenInfoWithAddress,
+        share: &TokenInfoWithAddress,
+    ) {
+        classifier_utils.ensure_token(asset.clone());
+        classifier_utils.ensure_token(share.clone());
+        classifier_utils.ensure_protocol(
+            protocol,
+            share.address,
+            asset.address,
+            Some(share.address),
+            None,
+            None,
+            None,
+            None,
+        );
+    }
+
+    pub(super) fn vault_frame(
+        vault: Address,
+        from: Address,
+        call_data: Vec<u8>,
+        return_data: Vec<u8>,
+        msg_value: U256,
+        logs: &[Log],
+    ) -> CallFrameInfo<'_> {
+        CallFrameInfo {
+            trace_idx: 0,
+            call_data: call_data.into(),
+            return_data: Bytes::from(return_data),
+            target_address: vault,
+            from_address: from,
+            logs,
+            delegate_logs: vec![],
+            msg_sender: from,
+            msg_value,
+        }
+    }
+}
@@ -0,0 +1,172 @@
+use brontes_macros::action_impl;
+use brontes_types::{
+    normalized_actions::{share_price, NormalizedVaultDeposit, NormalizedVaultWithdraw},
+    structured_trace::CallInfo,
+    Protocol, ToScaledRational,
+};
+
+use super::vault_tokens;
+
+// rETH is minted by the deposit pool, which received the ETH from the
+// depositor in the call before
+action_impl!(
+    Protocol::RocketPool,
+    crate::RocketPoolRETH::mintCall,
+    VaultDeposit,
+    [TokensMinted],
+    logs: true,
+    |
+    info: CallInfo,
+    log_data: RocketPoolMintCallLogs,
+    db_tx: &DB | {
+        let minted = log_data.tokens_minted_field?;
+        let (asset, share) = vault_tokens(db_tx, info.target_address)?;
+        let asset_amount = minted.ethAmount.to_scaled_rational(asset.decimals);
+        let share_amount = minted.amount.to_scaled_rational(share.decimals);
+
+        Ok(NormalizedVaultDeposit {
+            protocol: Protocol::RocketPool,
+            trace_index: info.trace_idx,
+            vault: info.target_address,
+            depositor: info.from_address,
+            recipient: minted.to,
+            share_price: share_price(&asset_amount, &share_amount),
+            asset,
+            asset_amount,
+            share,
+            share_amount,
+            msg_value: info.msg_value,
+        })
+    }
+);
+
+action_impl!(
+    Protocol::RocketPool,
+    crate::RocketPoolRETH::burnCall,
+    VaultWithdraw,
+    [TokensBurned],
+    logs: true,
+    |
+    info: CallInfo,
+    log_data: RocketPoolBurnCallLogs,
+    db_tx: &DB | {
+        let burned = log_data.tokens_burned_field?;
+        let (asset, share) = vault_tokens(db_tx, info.target_address)?;
+        let asset_amount = burned.ethAmount.to_scaled_rational(asset.decimals);
+        let share_amount = burned.amount.to_scaled_rational(share.decimals);
+
+        Ok(NormalizedVaultWithdraw {
+            protocol: Protocol::RocketPool,
+            trace_index: info.trace_idx,
+            vault: info.target_address,
+            owner: burned.from,
+            recipient: burned.from,
+            share_price: share_price(&asset_amount, &share_amount),
+            share,
+            share_amount,
+            asset,
+            asset_amount,
+            msg_value: info.msg_value,
+        })
+    }
+);
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{hex, Address, Log, U256};
+    use alloy_sol_types::{SolCall, SolEvent};
+    use brontes_types::{constants::ETH_ADDRESS, normalized_actions::Action};
+    use malachite::Rational;
+
+    use super::*;
+    use crate::{
+        classifiers::vaults::test_utils::{ensure_vault, token, units, vault_frame, SEARCHER},
+        test_utils::ClassifierTestUtils,
+        RocketPoolRETH,
+    };
+
+    const RETH: Address = Address::new(hex!("ae78736Cd615f374D3085123A210448E74Fc6393"));
+    const DEPOSIT_POOL: Address = Address::new(hex!("DD3f50F8A6CafbE9b31a427582963f465E745AF8"));
+
+    #[brontes_macros::test]
+    async fn test_mint() {
+        let classifier_utils = ClassifierTestUtils::new().await;
+        let (eth, reth) = (token(ETH_ADDRESS, "ETH"), token(RETH, "rETH"));
+        ensure_vault(&classifier_utils, Protocol::RocketPool, &eth, &reth);
+
+        // 1.1 ETH per rETH, minted by the deposit pool for the depositor
+        let call_data =
+            RocketPoolRETH::mintCall { _ethAmount: units(110), _to: SEARCHER }.abi_encode();
+        let minted = RocketPoolRETH::TokensMinted {
+            to:        SEARCHER,
+            amount:    units(100),
+            ethAmount: units(110),
+            time:      U256::from(1_700_000_000),
+        };
+        let logs = [Log { address: RETH, data: minted.encode_log_data() }];
+
+        let action = classifier_utils
+            .dispatch_call_frame(
+                vault_frame(RETH, DEPOSIT_POOL, call_data, vec![], U256::ZERO, &logs),
+                19_000_000,
+            )
+            .expect("mint wasn't classified");
+
+        assert_eq!(
+            action,
+            Action::VaultDeposit(NormalizedVaultDeposit {
+                protocol:     Protocol::RocketPool,
+                trace_index:  0,
+                vault:        RETH,
+                depositor:    DEPOSIT_POOL,
+                recipient:    SEARCHER,
+                asset:        eth,
+                asset_amount: Rational::from_signeds(11, 10),
+                share:        reth,
+                share_amount: Rational::from(1),
+                share_price:  Rational::from_signeds(11, 10),
+                msg_value:    U256::ZERO,
+            })
+        );
+    }
+
+    #[brontes_macros::test]
+    async fn test_burn() {
+        let classifier_utils = ClassifierTestUtils::new().await;
+        let (eth, reth) = (token(ETH_ADDRESS, "ETH"), token(RETH, "rETH"));
+        ensure_vault(&classifier_utils, Protocol::RocketPool, &eth, &reth);
+
+        let call_data = RocketPoolRETH::burnCall { _rethAmount: units(100) }.abi_encode();
+        let burned = RocketPoolRETH::TokensBurned {
+            from:      SEARCHER,
+            amount:    units(100),
+            ethAmount: units(110),
+            time:      U256::from(1_700_000_000),
+        };
+        let logs = [Log { address: RETH, data: burned.encode_log_data() }];
+
+        let Some(Action::VaultWithdraw(burn)) = classifier_utils.dispatch_call_frame(
+            vault_frame(RETH, SEARCHER, call_data, vec![], U256::ZERO, &logs),
+            19_000_000,
+        ) else {
+            panic!("burn wasn't classified as a vault withdrawal")
+        };
+
+        assert_eq!(burn.owner, SEARCHER);
+        assert_eq!(burn.recipient, SEARCHER);
+        assert_eq!(burn.share, reth);
+        assert_eq!(burn.share_amount, Rational::from(1));
+        assert_eq!(burn.asset, eth);
+        assert_eq!(burn.asset_amount, Rational::from_signeds(11, 10));
+    }
+
+    #[brontes_macros::test]
+    async fn test_traced_block() {
+        let classifier_utils = ClassifierTestUtils::new().await;
+
+        classifier_utils
+            .detects_vault_in_block(19_000_000, RETH, Protocol::RocketPool)
+            .await
+            .unwrap();
+    }
+}
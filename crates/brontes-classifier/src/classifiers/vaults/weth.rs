@@ -0,0 +1,146 @@
+use brontes_macros::action_impl;
+use brontes_types::{
+    normalized_actions::{NormalizedVaultDeposit, NormalizedVaultWithdraw},
+    structured_trace::CallInfo,
+    Protocol, ToScaledRational,
+};
+use malachite::{num::basic::traits::One, Rational};
+
+use super::vault_tokens;
+
+// WETH is registered with ETH as its asset, it wraps one to one
+action_impl!(
+    Protocol::Weth,
+    crate::Weth9::depositCall,
+    VaultDeposit,
+    [],
+    |info: CallInfo, db_tx: &DB| {
+        let (asset, share) = vault_tokens(db_tx, info.target_address)?;
+        let amount = info.msg_value.to_scaled_rational(asset.decimals);
+
+        Ok(NormalizedVaultDeposit {
+            protocol: Protocol::Weth,
+            trace_index: info.trace_idx,
+            vault: info.target_address,
+            depositor: info.from_address,
+            recipient: info.from_address,
+            asset,
+            asset_amount: amount.clone(),
+            share,
+            share_amount: amount,
+            share_price: Rational::ONE,
+            msg_value: info.msg_value,
+        })
+    }
+);
+
+action_impl!(
+    Protocol::Weth,
+    crate::Weth9::withdrawCall,
+    VaultWithdraw,
+    [],
+    call_data: true,
+    |
+    info: CallInfo,
+    call_data: withdrawCall,
+    db_tx: &DB | {
+        let (asset, share) = vault_tokens(db_tx, info.target_address)?;
+        let amount = call_data.wad.to_scaled_rational(share.decimals);
+
+        Ok(NormalizedVaultWithdraw {
+            protocol: Protocol::Weth,
+            trace_index: info.trace_idx,
+            vault: info.target_address,
+            owner: info.from_address,
+            recipient: info.from_address,
+            share,
+            share_amount: amount.clone(),
+            asset,
+            asset_amount: amount,
+            share_price: Rational::ONE,
+            msg_value: info.msg_value,
+        })
+    }
+);
+
+#[cfg(test)]
+mod tests {
+    use alloy_sol_types::SolCall;
+    use brontes_types::{
+        constants::{ETH_ADDRESS, WETH_ADDRESS},
+        normalized_actions::Action,
+    };
+
+    use super::*;
+    use crate::{
+        classifiers::vaults::test_utils::{ensure_vault, token, units, vault_frame, SEARCHER},
+        test_utils::ClassifierTestUtils,
+        Weth9,
+    };
+
+    #[brontes_macros::test]
+    async fn test_deposit() {
+        let classifier_utils = ClassifierTestUtils::new().await;
+        let (eth, weth) = (token(ETH_ADDRESS, "ETH"), token(WETH_ADDRESS, "WETH"));
+        ensure_vault(&classifier_utils, Protocol::Weth, &eth, &weth);
+
+        let call_data = Weth9::depositCall {}.abi_encode();
+        let action = classifier_utils
+            .dispatch_call_frame(
+                vault_frame(WETH_ADDRESS, SEARCHER, call_data, vec![], units(150), &[]),
+                19_000_000,
+            )
+            .expect("deposit wasn't classified");
+
+        assert_eq!(
+            action,
+            Action::VaultDeposit(NormalizedVaultDeposit {
+                protocol:     Protocol::Weth,
+                trace_index:  0,
+                vault:        WETH_ADDRESS,
+                depositor:    SEARCHER,
+                recipient:    SEARCHER,
+                asset:        eth,
+                asset_amount: Rational::from_signeds(3, 2),
+                share:        weth,
+                share_amount: Rational::from_signeds(3, 2),
+                share_price:  Rational::ONE,
+                msg_value:    units(150),
+            })
+        );
+    }
+
+    #[brontes_macros::test]
+    async fn test_withdraw() {
+        let classifier_utils = ClassifierTestUtils::new().await;
+        let (eth, weth) = (token(ETH_ADDRESS, "ETH"), token(WETH_ADDRESS, "WETH"));
+        ensure_vault(&classifier_utils, Protocol::Weth, &eth, &weth);
+
+        let call_data = Weth9::withdrawCall { wad: units(250) }.abi_encode();
+        let Some(Action::VaultWithdraw(withdraw)) = classifier_utils.dispatch_call_frame(
+            vault_frame(WETH_ADDRESS, SEARCHER, call_data, vec![], Default::default(), &[]),
+            19_000_000,
+        ) else {
+            panic!("withdraw wasn't classified as a vault withdrawal")
+        };
+
+        assert_eq!(withdraw.owner, SEARCHER);
+        assert_eq!(withdraw.recipient, SEARCHER);
+        assert_eq!(withdraw.share, weth);
+        assert_eq!(withdraw.share_amount, Rational::from_signeds(5, 2));
+        // unwrapping pays out ETH one to one
+        assert_eq!(withdraw.asset, eth);
+        assert_eq!(withdraw.asset_amount, Rational::from_signeds(5, 2));
+        assert_eq!(withdraw.share_price, Rational::ONE);
+    }
+
+    #[brontes_macros::test]
+    async fn test_traced_block() {
+        let classifier_utils = ClassifierTestUtils::new().await;
+
+        classifier_utils
+            .detects_vault_in_block(19_000_000, WETH_ADDRESS, Protocol::Weth)
+            .await
+            .unwrap();
+    }
+}
@@ -1,3 +1,5 @@
+mod settler;
+
 use alloy_primitives::U256;
 use brontes_macros::action_impl;
 use brontes_pricing::Protocol;
@@ -6,6 +8,7 @@ use brontes_types::{
     structured_trace::CallInfo,
     ToScaledRational,
 };
+pub use settler::*;
 
 // Uniswap
 action_impl!(
@@ -0,0 +1,63 @@
+use brontes_macros::action_impl;
+use brontes_pricing::Protocol;
+use brontes_types::{normalized_actions::NormalizedAggregator, structured_trace::CallInfo};
+
+action_impl!(
+    Protocol::ZeroXSettler,
+    crate::ZeroXSettler::executeCall,
+    Aggregator,
+    [],
+    call_data: true,
+    |info: CallInfo, call_data: executeCall, _db_tx: &DB| {
+        Ok(NormalizedAggregator {
+            protocol: Protocol::ZeroXSettler,
+            trace_index: info.trace_idx,
+            from: info.from_address,
+            to: info.target_address,
+            recipient: call_data.slippage.recipient,
+            child_actions: vec![],
+            msg_value: info.msg_value,
+        })
+    }
+);
+
+action_impl!(
+    Protocol::ZeroXSettler,
+    crate::ZeroXSettler::executeMetaTxnCall,
+    Aggregator,
+    [],
+    call_data: true,
+    |info: CallInfo, call_data: executeMetaTxnCall, _db_tx: &DB| {
+        Ok(NormalizedAggregator {
+            protocol: Protocol::ZeroXSettler,
+            trace_index: info.trace_idx,
+            from: info.from_address,
+            to: info.target_address,
+            recipient: call_data.slippage.recipient,
+            child_actions: vec![],
+            msg_value: info.msg_value,
+        })
+    }
+);
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{hex, Address};
+    use brontes_types::Protocol;
+
+    use crate::test_utils::ClassifierTestUtils;
+
+    const SETTLER: Address = Address::new(hex!("70bf6634eE8Cb27D04478f184b9b8BB13E5f4710"));
+
+    #[brontes_macros::test]
+    async fn test_settler_pays_out_to_slippage_recipient() {
+        let classifier_utils = ClassifierTestUtils::new().await;
+
+        let swaps = classifier_utils
+            .detects_aggregator_in_block(20_500_000, SETTLER, Protocol::ZeroXSettler)
+            .await
+            .unwrap();
+
+        assert!(swaps.iter().all(|swap| swap.recipient != Address::ZERO));
+    }
+}
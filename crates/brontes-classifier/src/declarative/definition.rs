@@ -0,0 +1,481 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use alloy_json_abi::{Event, Function, JsonAbi};
+use alloy_primitives::{Address, Selector, B256, U256};
+use brontes_types::{
+    db::token_info::TokenInfoWithAddress,
+    normalized_actions::{
+        Action, NormalizedBurn, NormalizedMint, NormalizedSwap, NormalizedTransfer,
+    },
+    structured_trace::CallFrameInfo,
+    Protocol, ToScaledRational,
+};
+use malachite::Rational;
+use serde::Deserialize;
+
+use super::field::{DecodedFrame, FieldExpr, Source};
+
+/// A protocol classified from its ABI instead of an `action_impl!`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ClassifierDefinition {
+    /// Name used in errors and logs
+    pub name:      String,
+    /// The protocol the actions are attributed to
+    pub protocol:  Protocol,
+    /// The contracts the classifier is applied to
+    pub addresses: Vec<Address>,
+    /// The JSON ABI of the contracts
+    pub abi:       Option<String>,
+    /// Path to the JSON ABI, relative to the config file
+    pub abi_path:  Option<PathBuf>,
+    #[serde(rename = "action")]
+    pub actions:   Vec<ActionDefinition>,
+}
+
+/// Maps the decoded values of a function call to a normalized action
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ActionDefinition {
+    /// Function name, or its selector if the name is overloaded
+    pub function: String,
+    /// Event emitted by the call whose fields can be used through `log.`
+    pub event:    Option<String>,
+    pub action:   DeclarativeActionKind,
+    /// Normalized action field to expression
+    pub fields:   BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeclarativeActionKind {
+    Swap,
+    Mint,
+    Burn,
+    Transfer,
+}
+
+#[derive(Debug)]
+pub struct CompiledClassifier {
+    pub name:      String,
+    pub protocol:  Protocol,
+    pub addresses: Vec<Address>,
+    pub actions:   Vec<CompiledAction>,
+}
+
+#[derive(Debug)]
+pub struct CompiledAction {
+    pub function: Function,
+    pub event:    Option<Event>,
+    pub mapping:  ActionMapping,
+}
+
+#[derive(Debug)]
+pub enum ActionMapping {
+    Swap {
+        tokens:    SwapTokens,
+        pool:      Option<FieldExpr>,
+        from:      Option<FieldExpr>,
+        recipient: Option<FieldExpr>,
+    },
+    Liquidity {
+        mint:      bool,
+        tokens:    Vec<(FieldExpr, FieldExpr)>,
+        pool:      Option<FieldExpr>,
+        from:      Option<FieldExpr>,
+        recipient: Option<FieldExpr>,
+    },
+    Transfer {
+        from:   Option<FieldExpr>,
+        to:     FieldExpr,
+        token:  FieldExpr,
+        amount: FieldExpr,
+    },
+}
+
+#[derive(Debug)]
+pub enum SwapTokens {
+    /// Explicit direction
+    InOut {
+        token_in:   FieldExpr,
+        token_out:  FieldExpr,
+        amount_in:  FieldExpr,
+        amount_out: FieldExpr,
+    },
+    /// Signed pool balance deltas, positive amounts flow into the pool
+    Deltas { token0: FieldExpr, token1: FieldExpr, amount0: FieldExpr, amount1: FieldExpr },
+}
+
+impl ClassifierDefinition {
+    /// Loads the ABI and checks every field mapping against it
+    pub fn compile(&self, base_dir: &Path) -> eyre::Result<CompiledClassifier> {
+        let abi = match (&self.abi, &self.abi_path) {
+            (Some(abi), None) => JsonAbi::from_json_str(abi)?,
+            (None, Some(path)) => {
+                let path = base_dir.join(path);
+                let abi = std::fs::read_to_string(&path)
+                    .map_err(|e| eyre::eyre!("failed to read abi at {}: {e}", path.display()))?;
+                JsonAbi::from_json_str(&abi)?
+            }
+            _ => eyre::bail!("exactly one of abi and abi_path has to be set"),
+        };
+
+        if self.addresses.is_empty() {
+            eyre::bail!("no addresses")
+        }
+        if self.actions.is_empty() {
+            eyre::bail!("no actions")
+        }
+
+        let actions = self
+            .actions
+            .iter()
+            .map(|action| {
+                action
+                    .compile(&abi)
+                    .map_err(|e| eyre::eyre!("action for {}: {e}", action.function))
+            })
+            .collect::<eyre::Result<Vec<_>>>()?;
+
+        if let Some(dup) = actions
+            .iter()
+            .enumerate()
+            .find(|(i, a)| actions[..*i].iter().any(|b| b.selector() == a.selector()))
+        {
+            eyre::bail!("{} is mapped twice", dup.1.function.name)
+        }
+
+        Ok(CompiledClassifier {
+            name: self.name.clone(),
+            protocol: self.protocol,
+            addresses: self.addresses.clone(),
+            actions,
+        })
+    }
+}
+
+impl ActionDefinition {
+    fn compile(&self, abi: &JsonAbi) -> eyre::Result<CompiledAction> {
+        let function = find_function(abi, &self.function)?;
+        let event = self
+            .event
+            .as_deref()
+            .map(|event| find_event(abi, event))
+            .transpose()?;
+
+        let mut fields = self
+            .fields
+            .iter()
+            .map(|(name, expr)| {
+                FieldExpr::parse(expr, &function, event.as_ref())
+                    .map(|expr| (name.as_str(), expr))
+                    .map_err(|e| eyre::eyre!("field {name}: {e}"))
+            })
+            .collect::<eyre::Result<BTreeMap<_, _>>>()?;
+
+        let mapping = match self.action {
+            DeclarativeActionKind::Swap => {
+                let tokens = if fields.contains_key("token_in") {
+                    SwapTokens::InOut {
+                        token_in:   take_address(&mut fields, "token_in")?,
+                        token_out:  take_address(&mut fields, "token_out")?,
+                        amount_in:  take(&mut fields, "amount_in")?,
+                        amount_out: take(&mut fields, "amount_out")?,
+                    }
+                } else {
+                    SwapTokens::Deltas {
+                        token0:  take_address(&mut fields, "token0")?,
+                        token1:  take_address(&mut fields, "token1")?,
+                        amount0: take(&mut fields, "amount0")?,
+                        amount1: take(&mut fields, "amount1")?,
+                    }
+                };
+
+                ActionMapping::Swap {
+                    tokens,
+                    pool: take_optional_address(&mut fields, "pool")?,
+                    from: take_optional_address(&mut fields, "from")?,
+                    recipient: take_optional_address(&mut fields, "recipient")?,
+                }
+            }
+            DeclarativeActionKind::Mint | DeclarativeActionKind::Burn => {
+                let mut tokens = Vec::new();
+                while fields.contains_key(format!("token{}", tokens.len()).as_str()) {
+                    let i = tokens.len();
+                    tokens.push((
+                        take_address(&mut fields, &format!("token{i}"))?,
+                        take(&mut fields, &format!("amount{i}"))?,
+                    ));
+                }
+                if tokens.is_empty() {
+                    eyre::bail!("missing field token0")
+                }
+
+                ActionMapping::Liquidity {
+                    mint: self.action == DeclarativeActionKind::Mint,
+                    tokens,
+                    pool: take_optional_address(&mut fields, "pool")?,
+                    from: take_optional_address(&mut fields, "from")?,
+                    recipient: take_optional_address(&mut fields, "recipient")?,
+                }
+            }
+            DeclarativeActionKind::Transfer => ActionMapping::Transfer {
+                from:   take_optional_address(&mut fields, "from")?,
+                to:     take_address(&mut fields, "to")?,
+                token:  take_address(&mut fields, "token")?,
+                amount: take(&mut fields, "amount")?,
+            },
+        };
+
+        if let Some(unused) = fields.keys().next() {
+            eyre::bail!("unknown field {unused} for a {:?} action", self.action)
+        }
+
+        Ok(CompiledAction { function, event, mapping })
+    }
+}
+
+impl CompiledAction {
+    pub fn selector(&self) -> Selector {
+        self.function.selector()
+    }
+
+    fn sources(&self) -> Vec<&FieldExpr> {
+        match &self.mapping {
+            ActionMapping::Swap { tokens, pool, from, recipient } => {
+                let tokens = match tokens {
+                    SwapTokens::InOut { token_in, token_out, amount_in, amount_out } => {
+                        [token_in, token_out, amount_in, amount_out]
+                    }
+                    SwapTokens::Deltas { token0, token1, amount0, amount1 } => {
+                        [token0, token1, amount0, amount1]
+                    }
+                };
+                tokens
+                    .into_iter()
+                    .chain([pool, from, recipient].into_iter().flatten())
+                    .collect()
+            }
+            ActionMapping::Liquidity { tokens, pool, from, recipient, .. } => tokens
+                .iter()
+                .flat_map(|(token, amount)| [token, amount])
+                .chain([pool, from, recipient].into_iter().flatten())
+                .collect(),
+            ActionMapping::Transfer { from, to, token, amount } => {
+                [to, token, amount].into_iter().chain(from).collect()
+            }
+        }
+    }
+
+    fn uses(&self, f: impl Fn(&Source) -> bool) -> bool {
+        self.sources()
+            .into_iter()
+            .any(|expr| expr.sources().any(&f))
+    }
+
+    /// Decodes the call frame and maps it to a normalized action. Token info
+    /// and pool tokens are looked up through the given closures so the
+    /// mapping doesn't depend on a database
+    pub fn classify(
+        &self,
+        protocol: Protocol,
+        info: &CallFrameInfo<'_>,
+        token_info: impl Fn(Address) -> eyre::Result<TokenInfoWithAddress>,
+        pool_tokens: impl Fn(Address) -> eyre::Result<[Address; 2]>,
+    ) -> eyre::Result<Action> {
+        let returns = if self.uses(|s| matches!(s, Source::Return(_))) {
+            DecodedFrame::decode_return(&self.function, info)?
+        } else {
+            vec![]
+        };
+        let log = match &self.event {
+            Some(event) if self.uses(|s| matches!(s, Source::Log(_))) => {
+                DecodedFrame::decode_log(event, info)?
+            }
+            _ => vec![],
+        };
+        let pool_tokens = if self.uses(|s| matches!(s, Source::PoolToken0 | Source::PoolToken1)) {
+            Some(pool_tokens(info.target_address)?)
+        } else {
+            None
+        };
+
+        let frame = DecodedFrame {
+            info,
+            call: DecodedFrame::decode_call(&self.function, info)?,
+            returns,
+            log,
+            pool_tokens,
+        };
+        let address_or = |expr: &Option<FieldExpr>, default: Address| {
+            expr.as_ref()
+                .map_or(Ok(default), |expr| expr.address(&frame))
+        };
+        let amount = |expr: &FieldExpr, token: &TokenInfoWithAddress| -> eyre::Result<Rational> {
+            Ok(scaled(expr.int(&frame)?.unsigned_abs(), token))
+        };
+
+        Ok(match &self.mapping {
+            ActionMapping::Swap { tokens, pool, from, recipient } => {
+                let from = address_or(from, info.from_address)?;
+                let (token_in, token_out, amount_in, amount_out) = match tokens {
+                    SwapTokens::InOut { token_in, token_out, amount_in, amount_out } => {
+                        let token_in = token_info(token_in.address(&frame)?)?;
+                        let token_out = token_info(token_out.address(&frame)?)?;
+                        let amount_in = amount(amount_in, &token_in)?;
+                        let amount_out = amount(amount_out, &token_out)?;
+                        (token_in, token_out, amount_in, amount_out)
+                    }
+                    SwapTokens::Deltas { token0, token1, amount0, amount1 } => {
+                        let (delta0, delta1) = (amount0.int(&frame)?, amount1.int(&frame)?);
+                        let token0 = token_info(token0.address(&frame)?)?;
+                        let token1 = token_info(token1.address(&frame)?)?;
+
+                        let (token_in, token_out, delta_in, delta_out) =
+                            if delta0.is_positive() && !delta1.is_positive() {
+                                (token0, token1, delta0, delta1)
+                            } else if delta1.is_positive() && !delta0.is_positive() {
+                                (token1, token0, delta1, delta0)
+                            } else {
+                                eyre::bail!("pool deltas {delta0} and {delta1} aren't a swap")
+                            };
+                        let amount_in = scaled(delta_in.unsigned_abs(), &token_in);
+                        let amount_out = scaled(delta_out.unsigned_abs(), &token_out);
+                        (token_in, token_out, amount_in, amount_out)
+                    }
+                };
+
+                Action::Swap(NormalizedSwap {
+                    protocol,
+                    trace_index: info.trace_idx,
+                    from,
+                    recipient: address_or(recipient, from)?,
+                    pool: address_or(pool, info.target_address)?,
+                    token_in,
+                    token_out,
+                    amount_in,
+                    amount_out,
+                    msg_value: info.msg_value,
+                })
+            }
+            ActionMapping::Liquidity { mint, tokens, pool, from, recipient } => {
+                let from = address_or(from, info.from_address)?;
+                let (token, amounts): (Vec<_>, Vec<_>) = tokens
+                    .iter()
+                    .map(|(token, value)| {
+                        let token = token_info(token.address(&frame)?)?;
+                        let value = amount(value, &token)?;
+                        Ok((token, value))
+                    })
+                    .collect::<eyre::Result<Vec<_>>>()?
+                    .into_iter()
+                    .unzip();
+                let (recipient, pool) =
+                    (address_or(recipient, from)?, address_or(pool, info.target_address)?);
+                let trace_index = info.trace_idx;
+
+                if *mint {
+                    Action::Mint(NormalizedMint {
+                        protocol,
+                        trace_index,
+                        from,
+                        recipient,
+                        pool,
+                        token,
+                        amount: amounts,
+                    })
+                } else {
+                    Action::Burn(NormalizedBurn {
+                        protocol,
+                        trace_index,
+                        from,
+                        recipient,
+                        pool,
+                        token,
+                        amount: amounts,
+                    })
+                }
+            }
+            ActionMapping::Transfer { from, to, token, amount: value } => {
+                let token = token_info(token.address(&frame)?)?;
+
+                Action::Transfer(NormalizedTransfer {
+                    trace_index: info.trace_idx,
+                    from: address_or(from, info.from_address)?,
+                    to: to.address(&frame)?,
+                    amount: amount(value, &token)?,
+                    token,
+                    fee: Rational::ZERO,
+                    msg_value: info.msg_value,
+                })
+            }
+        })
+    }
+}
+
+fn scaled(value: U256, token: &TokenInfoWithAddress) -> Rational {
+    value.to_scaled_rational(token.decimals)
+}
+
+fn find_function(abi: &JsonAbi, function: &str) -> eyre::Result<Function> {
+    if function.starts_with("0x") {
+        let selector: Selector = function
+            .parse()
+            .map_err(|e| eyre::eyre!("invalid selector {function}: {e}"))?;
+        return abi
+            .functions()
+            .find(|f| f.selector() == selector)
+            .cloned()
+            .ok_or_else(|| eyre::eyre!("no function with selector {function} in the abi"))
+    }
+
+    match abi.function(function).map(Vec::as_slice) {
+        Some([function]) => Ok(function.clone()),
+        Some(_) => eyre::bail!("{function} is overloaded, use its selector instead"),
+        None => eyre::bail!("no function {function} in the abi"),
+    }
+}
+
+fn find_event(abi: &JsonAbi, event: &str) -> eyre::Result<Event> {
+    if event.starts_with("0x") {
+        let topic: B256 = event
+            .parse()
+            .map_err(|e| eyre::eyre!("invalid event topic {event}: {e}"))?;
+        return abi
+            .events()
+            .find(|e| e.selector() == topic)
+            .cloned()
+            .ok_or_else(|| eyre::eyre!("no event with topic {event} in the abi"))
+    }
+
+    match abi.event(event).map(Vec::as_slice) {
+        Some([event]) => Ok(event.clone()),
+        Some(_) => eyre::bail!("{event} is overloaded, use its topic instead"),
+        None => eyre::bail!("no event {event} in the abi"),
+    }
+}
+
+fn take(fields: &mut BTreeMap<&str, FieldExpr>, name: &str) -> eyre::Result<FieldExpr> {
+    fields
+        .remove(name)
+        .ok_or_else(|| eyre::eyre!("missing field {name}"))
+}
+
+fn take_address(fields: &mut BTreeMap<&str, FieldExpr>, name: &str) -> eyre::Result<FieldExpr> {
+    take_optional_address(fields, name)?.ok_or_else(|| eyre::eyre!("missing field {name}"))
+}
+
+fn take_optional_address(
+    fields: &mut BTreeMap<&str, FieldExpr>,
+    name: &str,
+) -> eyre::Result<Option<FieldExpr>> {
+    let Some(expr) = fields.remove(name) else { return Ok(None) };
+    if !expr.is_single_term() {
+        eyre::bail!("address field {name} takes a single term")
+    }
+
+    Ok(Some(expr))
+}
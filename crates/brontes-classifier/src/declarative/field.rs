@@ -0,0 +1,269 @@
+use alloy_dyn_abi::{DynSolType, DynSolValue, Specifier};
+use alloy_json_abi::{Event, Function};
+use alloy_primitives::{Address, Log, I256};
+use brontes_types::structured_trace::CallFrameInfo;
+
+/// Where the value of a term is read from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Source {
+    /// Function argument, by position
+    Call(usize),
+    /// Function return value, by position
+    Return(usize),
+    /// Event field, by position in the event signature
+    Log(usize),
+    PoolToken0,
+    PoolToken1,
+    From,
+    MsgSender,
+    Target,
+    Literal(Address),
+}
+
+/// A field mapping, e.g. `log.amount0In - log.amount0Out`. Address fields are
+/// a single term, amount fields the signed sum of their terms
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldExpr {
+    terms: Vec<(bool, Source)>,
+}
+
+impl FieldExpr {
+    pub fn parse(expr: &str, function: &Function, event: Option<&Event>) -> eyre::Result<Self> {
+        let mut terms = Vec::new();
+        let mut negate = false;
+        let mut expect_term = true;
+
+        for token in expr.split_whitespace() {
+            match (token, expect_term) {
+                ("+", false) => {
+                    negate = false;
+                    expect_term = true;
+                }
+                ("-", false) => {
+                    negate = true;
+                    expect_term = true;
+                }
+                ("-", true) if terms.is_empty() && !negate => negate = true,
+                (term, true) => {
+                    terms.push((negate, Source::parse(term, function, event)?));
+                    expect_term = false;
+                }
+                (token, false) => eyre::bail!("expected + or - before {token} in `{expr}`"),
+            }
+        }
+
+        if expect_term {
+            eyre::bail!("incomplete expression `{expr}`")
+        }
+
+        Ok(Self { terms })
+    }
+
+    pub fn sources(&self) -> impl Iterator<Item = &Source> {
+        self.terms.iter().map(|(_, source)| source)
+    }
+
+    pub fn is_single_term(&self) -> bool {
+        matches!(self.terms.as_slice(), [(false, _)])
+    }
+
+    pub fn address(&self, frame: &DecodedFrame<'_>) -> eyre::Result<Address> {
+        let [(false, source)] = self.terms.as_slice() else {
+            eyre::bail!("an address field takes a single term")
+        };
+
+        match frame.value(source)? {
+            Value::Address(address) => Ok(address),
+            Value::Int(_) => eyre::bail!("{source:?} is not an address"),
+        }
+    }
+
+    pub fn int(&self, frame: &DecodedFrame<'_>) -> eyre::Result<I256> {
+        self.terms
+            .iter()
+            .try_fold(I256::ZERO, |acc, (negate, source)| {
+                let Value::Int(value) = frame.value(source)? else {
+                    eyre::bail!("{source:?} is not an integer")
+                };
+
+                let sum = if *negate { acc.checked_sub(value) } else { acc.checked_add(value) };
+                sum.ok_or_else(|| eyre::eyre!("overflow evaluating {source:?}"))
+            })
+    }
+}
+
+impl Source {
+    fn parse(term: &str, function: &Function, event: Option<&Event>) -> eyre::Result<Self> {
+        if let Some(field) = term.strip_prefix("call.") {
+            let names = function.inputs.iter().map(|p| p.name.as_str());
+            return Ok(Self::Call(position(field, names, &function.name)?))
+        }
+        if let Some(field) = term.strip_prefix("return.") {
+            let names = function.outputs.iter().map(|p| p.name.as_str());
+            return Ok(Self::Return(position(field, names, &function.name)?))
+        }
+        if let Some(field) = term.strip_prefix("log.") {
+            let event = event.ok_or_else(|| eyre::eyre!("{term} is used without an event"))?;
+            let idx = position(field, event.inputs.iter().map(|p| p.name.as_str()), &event.name)?;
+            let param = &event.inputs[idx];
+            // indexed dynamic values are only stored as their hash
+            if param.indexed && resolve(param)?.is_dynamic() {
+                eyre::bail!("{term} is an indexed dynamic value and can't be decoded")
+            }
+            return Ok(Self::Log(idx))
+        }
+
+        Ok(match term {
+            "pool.token0" => Self::PoolToken0,
+            "pool.token1" => Self::PoolToken1,
+            "info.from" => Self::From,
+            "info.msg_sender" => Self::MsgSender,
+            "info.target" => Self::Target,
+            literal if literal.starts_with("0x") => Self::Literal(
+                literal
+                    .parse()
+                    .map_err(|e| eyre::eyre!("invalid address {literal}: {e}"))?,
+            ),
+            _ => eyre::bail!("unknown field {term}"),
+        })
+    }
+}
+
+/// Resolves a parameter by name or position
+fn position<'a>(
+    field: &str,
+    mut names: impl ExactSizeIterator<Item = &'a str>,
+    item: &str,
+) -> eyre::Result<usize> {
+    let len = names.len();
+    if let Ok(idx) = field.parse::<usize>() {
+        return (idx < len)
+            .then_some(idx)
+            .ok_or_else(|| eyre::eyre!("{item} has {len} parameters, {idx} is out of range"))
+    }
+
+    names
+        .position(|name| name == field)
+        .ok_or_else(|| eyre::eyre!("{item} has no parameter named {field}"))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Value {
+    Address(Address),
+    Int(I256),
+}
+
+/// The decoded values of a call frame the field expressions are evaluated
+/// against
+#[derive(Debug)]
+pub struct DecodedFrame<'a> {
+    pub info:        &'a CallFrameInfo<'a>,
+    pub call:        Vec<DynSolValue>,
+    pub returns:     Vec<DynSolValue>,
+    pub log:         Vec<DynSolValue>,
+    pub pool_tokens: Option<[Address; 2]>,
+}
+
+impl<'a> DecodedFrame<'a> {
+    pub fn decode_call(
+        function: &Function,
+        info: &CallFrameInfo<'_>,
+    ) -> eyre::Result<Vec<DynSolValue>> {
+        let data = info
+            .call_data
+            .get(4..)
+            .ok_or_else(|| eyre::eyre!("call data is shorter than a selector"))?;
+
+        decode_tuple(function.inputs.iter().map(resolve), data)
+    }
+
+    pub fn decode_return(
+        function: &Function,
+        info: &CallFrameInfo<'_>,
+    ) -> eyre::Result<Vec<DynSolValue>> {
+        decode_tuple(function.outputs.iter().map(resolve), &info.return_data)
+    }
+
+    /// Decodes the first log of the frame that was emitted by the event,
+    /// including the logs of calls delegated to by the target
+    pub fn decode_log(event: &Event, info: &CallFrameInfo<'_>) -> eyre::Result<Vec<DynSolValue>> {
+        let selector = event.selector();
+        let log = info
+            .logs
+            .iter()
+            .chain(info.delegate_logs.iter().copied())
+            .find(|log: &&Log| log.data.topics().first() == Some(&selector))
+            .ok_or_else(|| eyre::eyre!("no {} log in the call frame", event.name))?;
+
+        let mut topics = log.data.topics().iter().skip(1);
+        let mut body = decode_tuple(
+            event
+                .inputs
+                .iter()
+                .filter(|param| !param.indexed)
+                .map(resolve),
+            &log.data.data,
+        )?
+        .into_iter();
+
+        event
+            .inputs
+            .iter()
+            .map(|param| {
+                if !param.indexed {
+                    return Ok(body.next().expect("decoded all body params"))
+                }
+                let topic = topics
+                    .next()
+                    .ok_or_else(|| eyre::eyre!("{} log is missing a topic", event.name))?;
+
+                Ok(resolve(param)?.abi_decode(topic.as_slice())?)
+            })
+            .collect()
+    }
+
+    fn value(&self, source: &Source) -> eyre::Result<Value> {
+        let decoded = match source {
+            Source::Call(idx) => &self.call[*idx],
+            Source::Return(idx) => &self.returns[*idx],
+            Source::Log(idx) => &self.log[*idx],
+            Source::PoolToken0 | Source::PoolToken1 => {
+                let [token0, token1] = self
+                    .pool_tokens
+                    .ok_or_else(|| eyre::eyre!("pool tokens were not loaded"))?;
+                let token = if *source == Source::PoolToken0 { token0 } else { token1 };
+                return Ok(Value::Address(token))
+            }
+            Source::From => return Ok(Value::Address(self.info.from_address)),
+            Source::MsgSender => return Ok(Value::Address(self.info.msg_sender)),
+            Source::Target => return Ok(Value::Address(self.info.target_address)),
+            Source::Literal(address) => return Ok(Value::Address(*address)),
+        };
+
+        match decoded {
+            DynSolValue::Address(address) => Ok(Value::Address(*address)),
+            DynSolValue::Uint(value, _) => Ok(Value::Int(
+                I256::try_from(*value)
+                    .map_err(|_| eyre::eyre!("{source:?} overflows an int256"))?,
+            )),
+            DynSolValue::Int(value, _) => Ok(Value::Int(*value)),
+            other => eyre::bail!("{source:?} has the unsupported type {other:?}"),
+        }
+    }
+}
+
+fn resolve(param: &impl Specifier<DynSolType>) -> alloy_dyn_abi::Result<DynSolType> {
+    param.resolve()
+}
+
+fn decode_tuple(
+    types: impl Iterator<Item = alloy_dyn_abi::Result<DynSolType>>,
+    data: &[u8],
+) -> eyre::Result<Vec<DynSolValue>> {
+    let ty = DynSolType::Tuple(types.collect::<Result<_, _>>()?);
+
+    match ty.abi_decode_params(data)? {
+        DynSolValue::Tuple(values) => Ok(values),
+        _ => unreachable!("decoded a tuple"),
+    }
+}
@@ -0,0 +1,292 @@
+//! Classifiers defined in TOML from a protocol's JSON ABI, so long tail forks
+//! of supported DEXes can be classified without an `action_impl!` and a new
+//! release. See `config/declarative_classifiers.toml` for the format.
+use std::{path::Path, sync::OnceLock};
+
+use alloy_primitives::Address;
+use brontes_database::libmdbx::LibmdbxReader;
+use brontes_pricing::types::{DexPriceMsg, PoolUpdate};
+use brontes_types::{normalized_actions::Action, structured_trace::CallFrameInfo, FastHashMap};
+use serde::Deserialize;
+
+mod definition;
+mod field;
+
+pub use definition::{
+    ActionDefinition, ClassifierDefinition, CompiledAction, CompiledClassifier,
+    DeclarativeActionKind,
+};
+
+static DECLARATIVE_CLASSIFIERS: OnceLock<DeclarativeClassifiers> = OnceLock::new();
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DeclarativeConfig {
+    #[serde(default, rename = "classifier")]
+    classifiers: Vec<ClassifierDefinition>,
+}
+
+/// The declarative classifiers by the contract address they classify
+#[derive(Debug, Default)]
+pub struct DeclarativeClassifiers {
+    classifiers: FastHashMap<Address, usize>,
+    compiled:    Vec<CompiledClassifier>,
+}
+
+impl DeclarativeClassifiers {
+    /// Parses the definitions, `abi_path`s are relative to `base_dir`
+    pub fn from_toml(config: &str, base_dir: &Path) -> eyre::Result<Self> {
+        let config: DeclarativeConfig = toml::from_str(config)?;
+        let mut this = Self::default();
+
+        for definition in config.classifiers {
+            let compiled = definition
+                .compile(base_dir)
+                .map_err(|e| eyre::eyre!("classifier {}: {e}", definition.name))?;
+
+            for address in &compiled.addresses {
+                if this
+                    .classifiers
+                    .insert(*address, this.compiled.len())
+                    .is_some()
+                {
+                    eyre::bail!("{address} has more than one declarative classifier")
+                }
+            }
+            this.compiled.push(compiled);
+        }
+
+        Ok(this)
+    }
+
+    pub fn load(path: &Path) -> eyre::Result<Self> {
+        let config = std::fs::read_to_string(path).map_err(|e| {
+            eyre::eyre!("failed to read declarative classifiers at {}: {e}", path.display())
+        })?;
+        let base_dir = path.parent().unwrap_or(Path::new("."));
+
+        Self::from_toml(&config, base_dir)
+            .map_err(|e| eyre::eyre!("invalid declarative classifiers at {}: {e}", path.display()))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.compiled.is_empty()
+    }
+
+    pub fn get(&self, address: &Address) -> Option<&CompiledClassifier> {
+        self.classifiers
+            .get(address)
+            .map(|idx| &self.compiled[*idx])
+    }
+
+    /// Classifies the call if its target has a declarative classifier with a
+    /// mapping for the called function. Returns the same pricing update and
+    /// action as the `action_impl!` classifiers
+    pub fn dispatch<DB: LibmdbxReader>(
+        &self,
+        call_info: &CallFrameInfo<'_>,
+        db_tx: &DB,
+        block: u64,
+        tx_idx: u64,
+    ) -> Option<(DexPriceMsg, Action)> {
+        let classifier = self.get(&call_info.target_address)?;
+        let selector = call_info.call_data.get(..4)?;
+        let mapping = classifier
+            .actions
+            .iter()
+            .find(|action| action.selector().as_slice() == selector)?;
+
+        mapping
+            .classify(
+                classifier.protocol,
+                call_info,
+                |token| db_tx.try_fetch_token_info(token),
+                |pool| {
+                    db_tx
+                        .get_protocol_details_sorted(pool)
+                        .map(|details| [details.token0, details.token1])
+                },
+            )
+            .map(|action| {
+                let update = PoolUpdate {
+                    block,
+                    tx_idx,
+                    logs: call_info.logs.to_vec(),
+                    action: action.clone(),
+                };
+                Some((DexPriceMsg::Update(update), action))
+            })
+            .unwrap_or_else(|e| {
+                tracing::error!(error=%e,
+                    "declarative classifier: {} failed on function: {} for address: {:?}",
+                    classifier.name,
+                    mapping.function.name,
+                    call_info.target_address,
+                );
+                None
+            })
+    }
+}
+
+/// Sets the declarative classifiers used by the
+/// [`Classifier`](crate::Classifier), can only be called once
+pub fn init_declarative_classifiers(classifiers: DeclarativeClassifiers) -> eyre::Result<()> {
+    DECLARATIVE_CLASSIFIERS
+        .set(classifiers)
+        .map_err(|_| eyre::eyre!("declarative classifiers were already initialized"))
+}
+
+/// The loaded declarative classifiers, empty unless
+/// [`init_declarative_classifiers`] was called
+pub fn declarative_classifiers() -> &'static DeclarativeClassifiers {
+    DECLARATIVE_CLASSIFIERS.get_or_init(DeclarativeClassifiers::default)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{Bytes, Log, U256};
+    use alloy_sol_types::{SolCall, SolEvent};
+    use brontes_types::{
+        db::token_info::{TokenInfo, TokenInfoWithAddress},
+        Protocol, ToScaledRational,
+    };
+
+    use super::*;
+    use crate::UniswapV2;
+
+    const PAIR: Address = Address::with_last_byte(1);
+    const TOKEN0: Address = Address::with_last_byte(2);
+    const TOKEN1: Address = Address::with_last_byte(3);
+    const ROUTER: Address = Address::with_last_byte(4);
+    const USER: Address = Address::with_last_byte(5);
+
+    const V2_FORK: &str = r#"
+        [[classifier]]
+        name = "v2-fork"
+        protocol = "UniswapV2"
+        addresses = ["0x0000000000000000000000000000000000000001"]
+        abi = '''[
+            {"type": "function", "name": "swap", "stateMutability": "nonpayable", "outputs": [],
+             "inputs": [{"name": "amount0Out", "type": "uint256"}, {"name": "amount1Out", "type": "uint256"},
+                        {"name": "to", "type": "address"}, {"name": "data", "type": "bytes"}]},
+            {"type": "event", "name": "Swap", "anonymous": false,
+             "inputs": [{"indexed": true, "name": "sender", "type": "address"},
+                        {"indexed": false, "name": "amount0In", "type": "uint256"},
+                        {"indexed": false, "name": "amount1In", "type": "uint256"},
+                        {"indexed": false, "name": "amount0Out", "type": "uint256"},
+                        {"indexed": false, "name": "amount1Out", "type": "uint256"},
+                        {"indexed": true, "name": "to", "type": "address"}]}
+        ]'''
+
+        [[classifier.action]]
+        function = "swap"
+        event = "Swap"
+        action = "swap"
+        fields.token0 = "pool.token0"
+        fields.token1 = "pool.token1"
+        fields.amount0 = "log.amount0In - log.amount0Out"
+        fields.amount1 = "log.amount1In - log.amount1Out"
+        fields.recipient = "call.to"
+    "#;
+
+    fn token(address: Address) -> eyre::Result<TokenInfoWithAddress> {
+        let decimals = if address == TOKEN0 { 6 } else { 18 };
+        Ok(TokenInfoWithAddress { address, inner: TokenInfo::new(decimals, String::new()) })
+    }
+
+    fn swap_frame(logs: &[Log]) -> CallFrameInfo<'_> {
+        let call = UniswapV2::swapCall {
+            amount0Out: U256::from(2_000_000),
+            amount1Out: U256::ZERO,
+            to:         USER,
+            data:       Bytes::new(),
+        };
+
+        CallFrameInfo {
+            trace_idx: 3,
+            call_data: call.abi_encode().into(),
+            return_data: Bytes::new(),
+            target_address: PAIR,
+            from_address: ROUTER,
+            logs,
+            delegate_logs: vec![],
+            msg_sender: ROUTER,
+            msg_value: U256::ZERO,
+        }
+    }
+
+    #[test]
+    fn test_swap_from_pool_deltas() {
+        let classifiers = DeclarativeClassifiers::from_toml(V2_FORK, Path::new(".")).unwrap();
+        let classifier = classifiers.get(&PAIR).unwrap();
+
+        let swap = UniswapV2::Swap {
+            sender:     ROUTER,
+            amount0In:  U256::ZERO,
+            amount1In:  U256::from(10).pow(U256::from(18)),
+            amount0Out: U256::from(2_000_000),
+            amount1Out: U256::ZERO,
+            to:         USER,
+        };
+        let logs = vec![Log { address: PAIR, data: swap.encode_log_data() }];
+        let info = swap_frame(&logs);
+
+        let action = classifier.actions[0]
+            .classify(classifier.protocol, &info, token, |_| Ok([TOKEN0, TOKEN1]))
+            .unwrap();
+
+        let Action::Swap(swap) = action else { panic!("expected a swap, got {action:?}") };
+        assert_eq!(swap.protocol, Protocol::UniswapV2);
+        assert_eq!(swap.trace_index, 3);
+        assert_eq!(swap.pool, PAIR);
+        assert_eq!(swap.from, ROUTER);
+        assert_eq!(swap.recipient, USER);
+        assert_eq!(swap.token_in.address, TOKEN1);
+        assert_eq!(swap.token_out.address, TOKEN0);
+        assert_eq!(swap.amount_in, U256::from(1).to_scaled_rational(0));
+        assert_eq!(swap.amount_out, U256::from(2).to_scaled_rational(0));
+    }
+
+    #[test]
+    fn test_missing_log_fails_classification() {
+        let classifiers = DeclarativeClassifiers::from_toml(V2_FORK, Path::new(".")).unwrap();
+        let classifier = classifiers.get(&PAIR).unwrap();
+        let info = swap_frame(&[]);
+
+        assert!(classifier.actions[0]
+            .classify(classifier.protocol, &info, token, |_| Ok([TOKEN0, TOKEN1]))
+            .is_err());
+    }
+
+    #[test]
+    fn test_invalid_definitions() {
+        let invalid = [
+            // not a swap field
+            ("fields.recipient", "fields.receiver"),
+            // not a parameter of the event
+            ("log.amount1In - log.amount1Out", "log.amount2In"),
+            // an address has a single term
+            ("\"call.to\"", "\"call.to + call.to\""),
+            // unknown function
+            ("function = \"swap\"", "function = \"swapExact\""),
+        ];
+
+        for (from, to) in invalid {
+            let config = V2_FORK.replace(from, to);
+            assert!(
+                DeclarativeClassifiers::from_toml(&config, Path::new(".")).is_err(),
+                "accepted {to}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_duplicate_address() {
+        let config = format!("{V2_FORK}{}", V2_FORK.replace("v2-fork", "other-fork"));
+        let err = DeclarativeClassifiers::from_toml(&config, Path::new(".")).unwrap_err();
+
+        assert!(err
+            .to_string()
+            .contains("more than one declarative classifier"));
+    }
+}
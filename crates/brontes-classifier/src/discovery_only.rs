@@ -318,7 +318,9 @@ impl<'db, T: TracingProvider, DB: LibmdbxReader + DBWriter> DiscoveryOnlyClassif
         .await;
     }
 
-    async fn insert_new_pool(&self, block: u64, pool: NormalizedNewPool) {
+    async fn insert_new_pool(&self, block: u64, mut pool: NormalizedNewPool) {
+        load_underlying(&self.provider, self.libmdbx, block, &mut pool).await;
+
         if self
             .libmdbx
             .insert_pool(block, pool.pool_address, &pool.tokens, None, pool.protocol)
@@ -0,0 +1,114 @@
+use std::hash::{Hash, Hasher};
+
+use brontes_types::FastHashSet;
+
+/// Length of the opcode sequences the fingerprint is made of
+const SHINGLE_LEN: usize = 8;
+
+const PUSH1: u8 = 0x60;
+const PUSH32: u8 = 0x7f;
+
+/// The opcode shingles of a contract's runtime bytecode. Push data is
+/// dropped, so immutables, constants like the fee of a fork and the
+/// addresses compiled into it don't change the fingerprint, and neither does
+/// the solc metadata appended to the code
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BytecodeFingerprint {
+    shingles: FastHashSet<u64>,
+}
+
+impl BytecodeFingerprint {
+    pub fn new(code: &[u8]) -> Self {
+        let code = strip_metadata(code);
+
+        let mut opcodes = Vec::with_capacity(code.len());
+        let mut pc = 0;
+        while pc < code.len() {
+            let opcode = code[pc];
+            opcodes.push(opcode);
+            pc += 1;
+            if (PUSH1..=PUSH32).contains(&opcode) {
+                pc += (opcode - PUSH1 + 1) as usize;
+            }
+        }
+
+        let shingles = opcodes
+            .windows(SHINGLE_LEN)
+            .map(|window| {
+                let mut hasher = std::collections::hash_map::DefaultHasher::new();
+                window.hash(&mut hasher);
+                hasher.finish()
+            })
+            .collect();
+
+        Self { shingles }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.shingles.is_empty()
+    }
+
+    /// Jaccard similarity of the shingles, 1 for the same opcodes
+    pub fn similarity(&self, other: &Self) -> f64 {
+        if self.is_empty() || other.is_empty() {
+            return 0.0
+        }
+
+        let shared = self.shingles.intersection(&other.shingles).count();
+        let total = self.shingles.len() + other.shingles.len() - shared;
+
+        shared as f64 / total as f64
+    }
+}
+
+/// Solidity appends the CBOR encoded metadata followed by its length as two
+/// big endian bytes
+fn strip_metadata(code: &[u8]) -> &[u8] {
+    let Some(len_bytes) = code.len().checked_sub(2).map(|i| &code[i..]) else { return code };
+    let metadata_len = u16::from_be_bytes([len_bytes[0], len_bytes[1]]) as usize;
+
+    match code.len().checked_sub(metadata_len + 2) {
+        // a CBOR map with one or two entries
+        Some(start) if matches!(code.get(start), Some(0xa1 | 0xa2)) => &code[..start],
+        _ => code,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // PUSH1 0x80 PUSH1 0x40 MSTORE CALLVALUE DUP1 ISZERO PUSH2 0x0010 JUMPI PUSH1
+    // 0x00 DUP1 REVERT JUMPDEST POP
+    const CODE: [u8; 20] = [
+        0x60, 0x80, 0x60, 0x40, 0x52, 0x34, 0x80, 0x15, 0x61, 0x00, 0x10, 0x57, 0x60, 0x00, 0x80,
+        0xfd, 0x5b, 0x50, 0x00, 0x00,
+    ];
+
+    #[test]
+    fn test_push_data_and_metadata_are_ignored() {
+        let mut fork = CODE.to_vec();
+        // different constants
+        fork[1] = 0x81;
+        fork[10] = 0x20;
+        // and solc metadata
+        fork.extend([0xa2, 0x64, 0x69, 0x70, 0x66, 0x73, 0x00, 0x06]);
+
+        let original = BytecodeFingerprint::new(&CODE);
+        assert!(!original.is_empty());
+        assert_eq!(original.similarity(&BytecodeFingerprint::new(&fork)), 1.0);
+    }
+
+    #[test]
+    fn test_different_code() {
+        let mut other = CODE.to_vec();
+        // REVERT -> RETURN, SWAP1 instead of the second DUP1
+        other[15] = 0xf3;
+        other[6] = 0x90;
+
+        let similarity =
+            BytecodeFingerprint::new(&CODE).similarity(&BytecodeFingerprint::new(&other));
+        assert!(similarity < 0.5, "{similarity}");
+        assert_eq!(BytecodeFingerprint::new(&[]).similarity(&BytecodeFingerprint::new(&CODE)), 0.0);
+    }
+}
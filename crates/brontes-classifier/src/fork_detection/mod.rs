@@ -0,0 +1,304 @@
+//! Detects Uniswap V2 and V3 forks deployed by factories brontes doesn't
+//! know. Unclassified calls to contracts that emit a V2 or V3 `Swap` log get
+//! their runtime bytecode fingerprinted and compared against known pools of
+//! the same shape. Matches are registered as `UniswapV2` or `UniswapV3` pools
+//! and recorded in the `ForkDetections` table for review.
+use std::sync::{Arc, OnceLock};
+
+use alloy_primitives::{address, Address};
+use alloy_sol_types::SolEvent;
+use brontes_types::{
+    db::fork_detection::{ForkDetection, ForkReviewStatus},
+    make_call_request,
+    structured_trace::CallFrameInfo,
+    traits::TracingProvider,
+    FastHashSet, Protocol,
+};
+use parking_lot::Mutex;
+use tokio::sync::OnceCell;
+use tracing::{debug, info};
+
+mod fingerprint;
+pub use fingerprint::BytecodeFingerprint;
+
+use crate::{UniswapV2, UniswapV3};
+
+pub const DEFAULT_SIMILARITY_THRESHOLD: f64 = 0.9;
+
+/// Pools of the known implementations unknown pools are compared against
+pub const REFERENCE_POOLS: [(Protocol, Address); 4] = [
+    // Uniswap V2 USDC/WETH
+    (Protocol::UniswapV2, address!("b4e16d0168e52d35cacd2c6185b44281ec28c9dc")),
+    // SushiSwap USDC/WETH
+    (Protocol::UniswapV2, address!("397ff1542f962076d0bfe58ea045ffa2d347aca0")),
+    // Uniswap V3 USDC/WETH 0.05%
+    (Protocol::UniswapV3, address!("88e6a0c2ddd26feeb64f039a2c41296fcb3f5640")),
+    // Uniswap V3 USDC/WETH 0.3%
+    (Protocol::UniswapV3, address!("8ad599c3a0ff1de082011efddc58f1908eb6e6d8")),
+];
+
+static FORK_DETECTOR: OnceLock<ForkDetector> = OnceLock::new();
+
+/// A pool whose bytecode matched one of the [`REFERENCE_POOLS`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ForkMatch {
+    pub protocol:   Protocol,
+    pub pool:       Address,
+    pub tokens:     [Address; 2],
+    pub reference:  Address,
+    pub similarity: f64,
+}
+
+impl ForkMatch {
+    /// The record written for review, the pool is registered at the block it
+    /// was detected in as its creation block isn't known
+    pub fn detection(&self, block: u64) -> ForkDetection {
+        ForkDetection {
+            protocol: self.protocol,
+            reference: self.reference,
+            similarity: self.similarity,
+            block,
+            status: ForkReviewStatus::Pending,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ForkDetector {
+    threshold:  f64,
+    references: OnceCell<Vec<(Protocol, Address, BytecodeFingerprint)>>,
+    /// Contracts that were already fingerprinted, so each is fetched once
+    checked:    Mutex<FastHashSet<Address>>,
+}
+
+impl ForkDetector {
+    pub fn new(threshold: f64) -> Self {
+        Self { threshold, references: OnceCell::new(), checked: Mutex::default() }
+    }
+
+    pub fn is_checked(&self, pool: Address) -> bool {
+        self.checked.lock().contains(&pool)
+    }
+
+    /// Skips the pool from now on, for pools that are known without having to
+    /// be fingerprinted
+    pub fn mark_checked(&self, pool: Address) {
+        self.checked.lock().insert(pool);
+    }
+
+    /// Fingerprints the pool if it wasn't checked before and compares it
+    /// against the reference pools of the protocol its swap log belongs to.
+    /// A pool is only marked as checked once its bytecode, and the tokens of a
+    /// match, were fetched, so failed requests are retried on its next swap
+    pub async fn detect<T: TracingProvider>(
+        &self,
+        provider: &Arc<T>,
+        block: u64,
+        pool: Address,
+        protocol: Protocol,
+    ) -> Option<ForkMatch> {
+        if self.is_checked(pool) {
+            return None
+        }
+
+        let references = self
+            .references
+            .get_or_try_init(|| load_references(provider))
+            .await
+            .ok()?;
+        let code = provider
+            .get_bytecode(Some(block), pool)
+            .await
+            .ok()
+            .flatten()?;
+        let fingerprint = BytecodeFingerprint::new(&code.original_bytes());
+
+        let (reference, similarity) = references
+            .iter()
+            .filter(|(reference_protocol, ..)| *reference_protocol == protocol)
+            .map(|(_, reference, reference_fp)| (*reference, fingerprint.similarity(reference_fp)))
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))?;
+
+        if similarity < self.threshold {
+            debug!(
+                target: "brontes_classifier::fork_detection",
+                ?pool, %protocol, similarity, "bytecode doesn't match a known pool"
+            );
+            self.mark_checked(pool);
+            return None
+        }
+
+        let token0 = make_call_request(UniswapV2::token0Call::new(()), provider, pool, Some(block))
+            .await
+            .ok()?
+            ._0;
+        let token1 = make_call_request(UniswapV2::token1Call::new(()), provider, pool, Some(block))
+            .await
+            .ok()?
+            ._0;
+        // another swap of the pool could have been checked in the meantime
+        if !self.checked.lock().insert(pool) {
+            return None
+        }
+
+        info!(
+            target: "brontes_classifier::fork_detection",
+            ?pool, %protocol, ?reference, similarity, "detected a fork of a known pool"
+        );
+
+        Some(ForkMatch { protocol, pool, tokens: [token0, token1], reference, similarity })
+    }
+}
+
+/// Fails if any of the reference pools couldn't be loaded, so that a
+/// transient provider error isn't cached as a detector without references
+async fn load_references<T: TracingProvider>(
+    provider: &Arc<T>,
+) -> eyre::Result<Vec<(Protocol, Address, BytecodeFingerprint)>> {
+    let mut references = Vec::with_capacity(REFERENCE_POOLS.len());
+    for (protocol, pool) in REFERENCE_POOLS {
+        let code = provider.get_bytecode(None, pool).await.and_then(|code| {
+            code.ok_or_else(|| eyre::eyre!("reference pool {pool:?} has no bytecode"))
+        });
+
+        match code {
+            Ok(code) => {
+                references.push((protocol, pool, BytecodeFingerprint::new(&code.original_bytes())))
+            }
+            Err(e) => {
+                tracing::warn!(
+                    target: "brontes_classifier::fork_detection",
+                    ?pool, %e, "failed to load the bytecode of a reference pool"
+                );
+                return Err(e)
+            }
+        }
+    }
+
+    Ok(references)
+}
+
+/// The pool protocol of the call if its target emitted a V2 or V3 `Swap` log
+pub fn swap_log_protocol(call_info: &CallFrameInfo<'_>) -> Option<Protocol> {
+    call_info
+        .logs
+        .iter()
+        .filter(|log| log.address == call_info.target_address)
+        .find_map(|log| match log.data.topics().first()? {
+            topic if *topic == UniswapV2::Swap::SIGNATURE_HASH => Some(Protocol::UniswapV2),
+            topic if *topic == UniswapV3::Swap::SIGNATURE_HASH => Some(Protocol::UniswapV3),
+            _ => None,
+        })
+}
+
+/// Enables the fork detection of the [`Classifier`](crate::Classifier), can
+/// only be called once
+pub fn init_fork_detector(detector: ForkDetector) -> eyre::Result<()> {
+    FORK_DETECTOR
+        .set(detector)
+        .map_err(|_| eyre::eyre!("fork detection was already initialized"))
+}
+
+/// The fork detector, if fork detection is enabled
+pub fn fork_detector() -> Option<&'static ForkDetector> {
+    FORK_DETECTOR.get()
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{hex, Bytes, Log, LogData, U256};
+    use brontes_types::constants::WETH_ADDRESS;
+
+    use super::*;
+    use crate::test_utils::ClassifierTestUtils;
+
+    fn call_info(target: Address, logs: &[Log]) -> CallFrameInfo<'_> {
+        CallFrameInfo {
+            trace_idx: 0,
+            call_data: Bytes::new(),
+            return_data: Bytes::new(),
+            target_address: target,
+            from_address: Address::ZERO,
+            logs,
+            delegate_logs: vec![],
+            msg_sender: Address::ZERO,
+            msg_value: U256::ZERO,
+        }
+    }
+
+    fn log(address: Address, topic: alloy_primitives::B256) -> Log {
+        Log { address, data: LogData::new_unchecked(vec![topic], Bytes::new()) }
+    }
+
+    #[test]
+    fn test_swap_log_protocol() {
+        let pool = Address::with_last_byte(1);
+        let other = Address::with_last_byte(2);
+
+        let v2 = [log(pool, UniswapV2::Swap::SIGNATURE_HASH)];
+        assert_eq!(swap_log_protocol(&call_info(pool, &v2)), Some(Protocol::UniswapV2));
+
+        let v3 = [log(pool, UniswapV3::Swap::SIGNATURE_HASH)];
+        assert_eq!(swap_log_protocol(&call_info(pool, &v3)), Some(Protocol::UniswapV3));
+
+        // emitted by another contract in the frame
+        assert_eq!(swap_log_protocol(&call_info(other, &v2)), None);
+
+        let sync = [log(pool, UniswapV2::Sync::SIGNATURE_HASH)];
+        assert_eq!(swap_log_protocol(&call_info(pool, &sync)), None);
+    }
+
+    #[brontes_macros::test]
+    async fn test_detect_sushiswap_pair() {
+        let classifier_utils = ClassifierTestUtils::new().await;
+        let provider = classifier_utils.trace_loader.get_provider();
+        let detector = ForkDetector::new(DEFAULT_SIMILARITY_THRESHOLD);
+
+        // SushiSwap SUSHI/WETH
+        let pool = Address::new(hex!("795065dCc9f64b5614C407a6EFDC400DA6221FB0"));
+        let sushi = Address::new(hex!("6B3595068778DD592e39A122f4f5a5cF09C90fE2"));
+
+        // a V2 fork isn't compared against the V3 pools
+        assert_eq!(
+            detector
+                .detect(&provider, 19_000_000, pool, Protocol::UniswapV3)
+                .await,
+            None
+        );
+
+        let detector = ForkDetector::new(DEFAULT_SIMILARITY_THRESHOLD);
+        let fork = detector
+            .detect(&provider, 19_000_000, pool, Protocol::UniswapV2)
+            .await
+            .unwrap();
+        assert_eq!(fork.protocol, Protocol::UniswapV2);
+        assert_eq!(fork.tokens, [sushi, WETH_ADDRESS]);
+        assert_eq!(fork.reference, REFERENCE_POOLS[1].1);
+        assert!(fork.similarity >= DEFAULT_SIMILARITY_THRESHOLD);
+        assert!(detector.is_checked(pool));
+
+        // each pool is only fingerprinted once
+        assert_eq!(
+            detector
+                .detect(&provider, 19_000_001, pool, Protocol::UniswapV2)
+                .await,
+            None
+        );
+    }
+
+    #[brontes_macros::test]
+    async fn test_detect_ignores_other_contracts() {
+        let classifier_utils = ClassifierTestUtils::new().await;
+        let provider = classifier_utils.trace_loader.get_provider();
+        let detector = ForkDetector::new(DEFAULT_SIMILARITY_THRESHOLD);
+
+        assert_eq!(
+            detector
+                .detect(&provider, 19_000_000, WETH_ADDRESS, Protocol::UniswapV2)
+                .await,
+            None
+        );
+        // contracts that don't match are settled as well
+        assert!(detector.is_checked(WETH_ADDRESS));
+    }
+}
@@ -18,7 +18,9 @@ use futures::Future;
 
 pub mod tree_builder;
 pub use tree_builder::Classifier;
+pub mod declarative;
 pub mod discovery_only;
+pub mod fork_detection;
 pub mod multi_frame_classification;
 
 #[cfg(feature = "tests")]
@@ -33,6 +35,7 @@ pub use classifiers::*;
 sol!(UniswapV2, "./classifier-abis/UniswapV2.json");
 sol!(SushiSwapV2, "./classifier-abis/SushiSwapV2.json");
 sol!(UniswapV3, "./classifier-abis/UniswapV3.json");
+sol!(UniswapV4PoolManager, "./classifier-abis/UniswapV4PoolManager.json");
 sol!(SushiSwapV3, "./classifier-abis/SushiSwapV3.json");
 sol!(PancakeSwapV2, "./classifier-abis/PancakeSwapV2.json");
 sol!(PancakeSwapV3, "./classifier-abis/PancakeSwapV3.json");
@@ -66,6 +69,17 @@ sol!(ZeroXLiquidityProviderFeature, "./classifier-abis/zero-x/ZeroXLiquidityProv
 sol!(ZeroXInterface, "./classifier-abis/zero-x/ZeroXInterface.json");
 sol!(DodoDPPPool, "./classifier-abis/dodo/DPPPool.json");
 sol!(DodoDSPPool, "./classifier-abis/dodo/DSPPool.json");
+sol!(ChainlinkOffchainAggregator, "./classifier-abis/chainlink/OffchainAggregator.json");
+sol!(Erc4626Vault, "./classifier-abis/erc4626/ERC4626.json");
+sol!(Weth9, "./classifier-abis/weth/WETH9.json");
+sol!(LidoStETH, "./classifier-abis/lido/StETH.json");
+sol!(LidoWstETH, "./classifier-abis/lido/WstETH.json");
+sol!(RocketPoolRETH, "./classifier-abis/rocket-pool/RocketTokenRETH.json");
+sol!(ParaswapAugustusV5, "./classifier-abis/paraswap/AugustusSwapperV5.json");
+sol!(ParaswapAugustusV6, "./classifier-abis/paraswap/AugustusV6.json");
+sol!(KyberSwapMetaAggregationRouterV2, "./classifier-abis/kyberswap/MetaAggregationRouterV2.json");
+sol!(ZeroXSettler, "./classifier-abis/zero-x/ZeroXSettler.json");
+sol!(UniswapUniversalRouter, "./classifier-abis/uniswap/UniversalRouter.json");
 
 // Discovery
 sol!(UniswapV2Factory, "./classifier-abis/UniswapV2Factory.json");
@@ -2,3 +2,5 @@ pub mod one_inch;
 pub use one_inch::*;
 pub mod zero_x;
 pub use zero_x::*;
+pub mod routers;
+pub use routers::*;
@@ -0,0 +1,298 @@
+use brontes_types::{
+    normalized_actions::{
+        Action, MultiCallFrameClassification, MultiFrameAction, MultiFrameRequest, NodeDataIndex,
+    },
+    FastHashSet, Protocol, TreeSearchBuilder,
+};
+
+use crate::multi_frame_classification::MultiCallFrameClassifier;
+
+pub struct ParaswapV5Agg;
+pub struct ParaswapV6Agg;
+pub struct KyberSwapAgg;
+pub struct ZeroXSettlerAgg;
+pub struct UniversalRouterAgg;
+
+macro_rules! router_classifier {
+    ($name:ident, $protocol:ident, $parse:ident) => {
+        impl MultiCallFrameClassifier for $name {
+            const KEY: [u8; 2] = [Protocol::$protocol as u8, MultiFrameAction::Aggregator as u8];
+
+            fn create_classifier(
+                request: MultiFrameRequest,
+            ) -> Option<MultiCallFrameClassification<Action>> {
+                Some(MultiCallFrameClassification {
+                    trace_index:         request.trace_idx,
+                    tree_search_builder: TreeSearchBuilder::new().with_actions([
+                        Action::is_swap,
+                        Action::is_transfer,
+                        Action::is_eth_transfer,
+                        Action::is_vault,
+                    ]),
+                    parse_fn:            Box::new($parse),
+                })
+            }
+        }
+    };
+}
+
+router_classifier!(ParaswapV5Agg, ParaswapV5, parse_router);
+router_classifier!(ParaswapV6Agg, ParaswapV6, parse_router);
+router_classifier!(KyberSwapAgg, KyberSwap, parse_router);
+router_classifier!(ZeroXSettlerAgg, ZeroXSettler, parse_router);
+router_classifier!(UniversalRouterAgg, UniswapUniversalRouter, parse_universal_router);
+
+/// Moves the swaps, transfers and wraps the router made into the aggregator.
+/// Tokens pulled from the user through Permit2 are transfers of the token, so
+/// they are collected the same way.
+fn parse_router(
+    this_action: &mut Action,
+    child_nodes: Vec<(NodeDataIndex, Action)>,
+) -> Vec<NodeDataIndex> {
+    let this = this_action.try_aggregator_mut().unwrap();
+    let mut prune_nodes = Vec::new();
+
+    for (trace_index, action) in child_nodes {
+        match action {
+            Action::Swap(_)
+            | Action::SwapWithFee(_)
+            | Action::Transfer(_)
+            | Action::EthTransfer(_)
+            | Action::VaultDeposit(_)
+            | Action::VaultWithdraw(_) => {
+                this.child_actions.push(action);
+                prune_nodes.push(trace_index);
+            }
+            _ => {}
+        }
+    }
+    prune_nodes
+}
+
+/// The Universal Router has no recipient in its call data, the recipients are
+/// encoded per command. When the output is swept or unwrapped to the
+/// recipient, the last payout of the router that isn't into one of the swapped
+/// pools is the recipient.
+fn parse_universal_router(
+    this_action: &mut Action,
+    child_nodes: Vec<(NodeDataIndex, Action)>,
+) -> Vec<NodeDataIndex> {
+    let prune_nodes = parse_router(this_action, child_nodes);
+    let this = this_action.try_aggregator_mut().unwrap();
+
+    let pools = this
+        .child_actions
+        .iter()
+        .filter_map(Action::try_swaps_merged_ref)
+        .map(|swap| swap.pool)
+        .collect::<FastHashSet<_>>();
+
+    let payout = this
+        .child_actions
+        .iter()
+        .filter_map(|action| match action {
+            Action::Transfer(t) if t.from == this.to => Some(t.to),
+            Action::EthTransfer(e) if e.from == this.to => Some(e.to),
+            _ => None,
+        })
+        .filter(|to| !pools.contains(to))
+        .last();
+
+    if let Some(recipient) = payout {
+        this.recipient = recipient;
+    }
+
+    prune_nodes
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{Address, U256};
+    use brontes_types::normalized_actions::{
+        NormalizedAggregator, NormalizedEthTransfer, NormalizedSwap, NormalizedTransfer,
+        NormalizedVaultDeposit,
+    };
+
+    use super::*;
+    use crate::multi_frame_classification::parse_multi_frame_requests;
+
+    fn node(trace_index: u64) -> NodeDataIndex {
+        NodeDataIndex { trace_index, data_idx: 0, multi_data_idx: 0 }
+    }
+
+    fn aggregator(router: Address, sender: Address) -> Action {
+        router_aggregator(Protocol::UniswapUniversalRouter, router, sender)
+    }
+
+    fn router_aggregator(protocol: Protocol, router: Address, sender: Address) -> Action {
+        Action::Aggregator(NormalizedAggregator {
+            protocol,
+            trace_index: 0,
+            from: sender,
+            to: router,
+            recipient: sender,
+            child_actions: vec![],
+            msg_value: U256::ZERO,
+        })
+    }
+
+    #[test]
+    fn test_universal_router_recipient_from_payout() {
+        let router = Address::with_last_byte(1);
+        let sender = Address::with_last_byte(2);
+        let pool = Address::with_last_byte(3);
+        let recipient = Address::with_last_byte(4);
+
+        let mut action = aggregator(router, sender);
+        let pruned = parse_universal_router(
+            &mut action,
+            vec![
+                // pulled from the sender through permit2 into the pool
+                (
+                    node(1),
+                    Action::Transfer(NormalizedTransfer {
+                        from: sender,
+                        to: pool,
+                        ..Default::default()
+                    }),
+                ),
+                (
+                    node(2),
+                    Action::Swap(NormalizedSwap {
+                        pool,
+                        from: router,
+                        recipient: router,
+                        ..Default::default()
+                    }),
+                ),
+                (
+                    node(3),
+                    Action::Transfer(NormalizedTransfer {
+                        from: pool,
+                        to: router,
+                        ..Default::default()
+                    }),
+                ),
+                // unwrapped and sent on
+                (
+                    node(4),
+                    Action::EthTransfer(NormalizedEthTransfer {
+                        from: router,
+                        to: recipient,
+                        ..Default::default()
+                    }),
+                ),
+                (node(5), Action::Revert),
+            ],
+        );
+
+        let agg = action.try_aggregator_ref().unwrap();
+        assert_eq!(pruned.len(), 4);
+        assert_eq!(agg.child_actions.len(), 4);
+        assert_eq!(agg.recipient, recipient);
+    }
+
+    #[test]
+    fn test_universal_router_keeps_sender_without_payout() {
+        let router = Address::with_last_byte(1);
+        let sender = Address::with_last_byte(2);
+        let pool = Address::with_last_byte(3);
+
+        let mut action = aggregator(router, sender);
+        parse_universal_router(
+            &mut action,
+            vec![
+                (
+                    node(1),
+                    Action::Transfer(NormalizedTransfer {
+                        from: router,
+                        to: pool,
+                        ..Default::default()
+                    }),
+                ),
+                (
+                    node(2),
+                    Action::Swap(NormalizedSwap {
+                        pool,
+                        from: router,
+                        recipient: sender,
+                        ..Default::default()
+                    }),
+                ),
+            ],
+        );
+
+        assert_eq!(action.try_aggregator_ref().unwrap().recipient, sender);
+    }
+
+    #[test]
+    fn test_routers_collect_child_actions() {
+        let router = Address::with_last_byte(1);
+        let sender = Address::with_last_byte(2);
+        let pool = Address::with_last_byte(3);
+        let vault = Address::with_last_byte(4);
+
+        for protocol in [
+            Protocol::ParaswapV5,
+            Protocol::ParaswapV6,
+            Protocol::KyberSwap,
+            Protocol::ZeroXSettler,
+            Protocol::UniswapUniversalRouter,
+        ] {
+            let mut action = router_aggregator(protocol, router, sender);
+            let request = MultiFrameRequest::new(&action, 0).unwrap();
+            let classifications = parse_multi_frame_requests(vec![request]);
+            assert_eq!(classifications.len(), 1, "{protocol} has no multi frame classifier");
+
+            let pruned = classifications[0].parse(
+                &mut action,
+                vec![
+                    (
+                        node(1),
+                        Action::Transfer(NormalizedTransfer {
+                            from: sender,
+                            to: pool,
+                            ..Default::default()
+                        }),
+                    ),
+                    (
+                        node(2),
+                        Action::Swap(NormalizedSwap {
+                            pool,
+                            from: router,
+                            recipient: router,
+                            ..Default::default()
+                        }),
+                    ),
+                    (
+                        node(3),
+                        Action::VaultDeposit(NormalizedVaultDeposit {
+                            vault,
+                            depositor: router,
+                            recipient: sender,
+                            ..Default::default()
+                        }),
+                    ),
+                    (
+                        node(4),
+                        Action::EthTransfer(NormalizedEthTransfer {
+                            from: router,
+                            to: vault,
+                            ..Default::default()
+                        }),
+                    ),
+                    (node(5), Action::Revert),
+                ],
+            );
+
+            let agg = action.try_aggregator_ref().unwrap();
+            assert_eq!(
+                pruned.iter().map(|n| n.trace_index).collect::<Vec<_>>(),
+                vec![1, 2, 3, 4],
+                "{protocol}"
+            );
+            assert_eq!(agg.child_actions.len(), 4, "{protocol}");
+            assert_eq!(agg.recipient, sender, "{protocol}");
+        }
+    }
+}
@@ -9,7 +9,10 @@ pub mod batch;
 pub mod flash_loan;
 pub mod liquidations;
 
-use aggregator::{OneInchAggregator, OneInchFusion, ZeroXAgg};
+use aggregator::{
+    KyberSwapAgg, OneInchAggregator, OneInchFusion, ParaswapV5Agg, ParaswapV6Agg,
+    UniversalRouterAgg, ZeroXAgg, ZeroXSettlerAgg,
+};
 use batch::{Cowswap, UniswapX, ZeroXBatch};
 use brontes_types::normalized_actions::{Action, MultiCallFrameClassification, MultiFrameRequest};
 use flash_loan::{BalancerV2, MakerDss};
@@ -45,6 +48,11 @@ pub fn parse_multi_frame_requests(
             AaveV2::KEY => AaveV2::create_classifier(request),
             AaveV3::KEY => AaveV3::create_classifier(request),
             ZeroXAgg::KEY => ZeroXAgg::create_classifier(request),
+            ZeroXSettlerAgg::KEY => ZeroXSettlerAgg::create_classifier(request),
+            ParaswapV5Agg::KEY => ParaswapV5Agg::create_classifier(request),
+            ParaswapV6Agg::KEY => ParaswapV6Agg::create_classifier(request),
+            KyberSwapAgg::KEY => KyberSwapAgg::create_classifier(request),
+            UniversalRouterAgg::KEY => UniversalRouterAgg::create_classifier(request),
             ZeroXBatch::KEY => ZeroXBatch::create_classifier(request),
             MakerDss::KEY => MakerDss::create_classifier(request),
             Dodo::KEY => Dodo::create_classifier(request),
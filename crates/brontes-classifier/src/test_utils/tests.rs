@@ -23,8 +23,8 @@ use brontes_types::{
     db::{
         address_to_protocol_info::ProtocolInfo, dex::DexQuotes, token_info::TokenInfoWithAddress,
     },
-    normalized_actions::{pool::NormalizedNewPool, NormalizedTransfer},
-    structured_trace::TraceActions,
+    normalized_actions::{pool::NormalizedNewPool, NormalizedAggregator, NormalizedTransfer},
+    structured_trace::{CallFrameInfo, TraceActions},
     tree::BlockTree,
     BrontesTaskManager, FastHashMap, TreeCollector, TreeSearchBuilder, UnboundedYapperReceiver,
 };
@@ -102,6 +102,7 @@ impl ClassifierTestUtils {
                 pair_graph,
                 UnboundedYapperReceiver::new(rx, 10000, "test".into()),
                 self.get_provider(),
+                Arc::new(self.libmdbx),
                 block,
                 created_pools,
                 ctr.clone(),
@@ -172,6 +173,23 @@ impl ClassifierTestUtils {
             .await)
     }
 
+    /// Builds the tree of the transaction as if its top level call reverted.
+    /// The calls stay in the trace, as they do for a transaction that reverted
+    /// after making them
+    pub async fn build_tree_tx_reverted(
+        &self,
+        tx_hash: TxHash,
+    ) -> Result<BlockTree<Action>, ClassifierTestUtilsError> {
+        let TxTracesWithHeaderAnd { mut trace, header, .. } =
+            self.trace_loader.get_tx_trace_with_header(tx_hash).await?;
+        trace.is_success = false;
+
+        Ok(self
+            .classifier
+            .build_block_tree(vec![trace], header, true)
+            .await)
+    }
+
     pub async fn setup_pricing_for_bench(
         &self,
         block: u64,
@@ -510,6 +528,72 @@ impl ClassifierTestUtils {
         Ok(())
     }
 
+    /// Returns the aggregator actions of the calls made to `router` in `block`
+    /// after checking that they are all classified as `protocol`
+    pub async fn detects_aggregator_in_block(
+        &self,
+        block: u64,
+        router: Address,
+        protocol: Protocol,
+    ) -> Result<Vec<NormalizedAggregator>, ClassifierTestUtilsError> {
+        let tree = self.build_block_tree(block).await?;
+        let search = TreeSearchBuilder::default().with_action(Action::is_aggregator);
+
+        let aggregators = tree
+            .tx_roots
+            .iter()
+            .flat_map(|root| root.collect(&search))
+            .filter_map(|action| match action {
+                Action::Aggregator(aggregator) if aggregator.to == router => Some(aggregator),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        assert!(!aggregators.is_empty(), "no calls to {router:?} classified in block {block}");
+        for aggregator in &aggregators {
+            assert_eq!(
+                protocol, aggregator.protocol,
+                "got: {:#?} != given: {:#?}",
+                aggregator.protocol, protocol
+            );
+        }
+
+        Ok(aggregators)
+    }
+
+    /// Returns the deposits into and withdrawals from `vault` in `block` after
+    /// checking that they are all classified as `protocol`
+    pub async fn detects_vault_in_block(
+        &self,
+        block: u64,
+        vault: Address,
+        protocol: Protocol,
+    ) -> Result<Vec<Action>, ClassifierTestUtilsError> {
+        let tree = self.build_block_tree(block).await?;
+        let search = TreeSearchBuilder::default()
+            .with_actions([Action::is_vault_deposit, Action::is_vault_withdraw]);
+
+        let actions = tree
+            .tx_roots
+            .iter()
+            .flat_map(|root| root.collect(&search))
+            .filter(|action| action.get_to_address() == vault)
+            .collect::<Vec<_>>();
+
+        assert!(!actions.is_empty(), "no calls to {vault:?} classified in block {block}");
+        for action in &actions {
+            assert_eq!(
+                protocol,
+                action.get_protocol(),
+                "got: {:#?} != given: {:#?}",
+                action.get_protocol(),
+                protocol
+            );
+        }
+
+        Ok(actions)
+    }
+
     pub async fn contains_action(
         &self,
         tx_hash: TxHash,
@@ -584,6 +668,15 @@ impl ClassifierTestUtils {
         Ok(())
     }
 
+    /// Dispatches a call frame against the protocols and tokens in the test
+    /// db. Used for calls whose classification depends on db state that a
+    /// recorded transaction can't pin down
+    pub fn dispatch_call_frame(&self, call_info: CallFrameInfo<'_>, block: u64) -> Option<Action> {
+        ProtocolClassifier::default()
+            .dispatch(call_info, self.trace_loader.libmdbx, block, 0)
+            .map(|(_, action)| action)
+    }
+
     pub async fn test_discovery_classification(
         &self,
         txes: TxHash,
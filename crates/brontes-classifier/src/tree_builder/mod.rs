@@ -4,6 +4,7 @@ use alloy_primitives::{Log, U256};
 use brontes_core::missing_token_info::load_missing_token_info;
 use brontes_pricing::types::PoolUpdate;
 use brontes_types::{
+    db::uniswap_v4::UniswapV4PoolKey,
     normalized_actions::{
         pool::NormalizedNewPool, MultiCallFrameClassification, MultiFrameRequest, NormalizedAction,
         NormalizedEthTransfer, NormalizedTransfer,
@@ -19,9 +20,10 @@ use brontes_database::libmdbx::{DBWriter, LibmdbxReader};
 use brontes_pricing::types::DexPriceMsg;
 use brontes_types::{
     normalized_actions::{Action, SelfdestructWithIndex},
-    structured_trace::{TraceActions, TransactionTraceWithLogs, TxTrace},
+    structured_trace::{CallFrameInfo, TraceActions, TransactionTraceWithLogs, TxTrace},
     traits::TracingProvider,
-    tree::{BlockTree, GasDetails, Node, Root},
+    tree::{BlockTree, FailedTx, GasDetails, Node, Root},
+    Protocol,
 };
 use futures::future::join_all;
 use itertools::Itertools;
@@ -35,8 +37,11 @@ use utils::{decode_transfer, get_coinbase_transfer};
 
 use self::erc20::try_decode_transfer;
 use crate::{
-    classifiers::*, multi_frame_classification::parse_multi_frame_requests, ActionCollection,
-    FactoryDiscoveryDispatch,
+    classifiers::*,
+    declarative::declarative_classifiers,
+    fork_detection::{fork_detector, swap_log_protocol},
+    multi_frame_classification::parse_multi_frame_requests,
+    ActionCollection, FactoryDiscoveryDispatch,
 };
 
 //TODO: Document this module
@@ -75,8 +80,16 @@ impl<'db, T: TracingProvider, DB: LibmdbxReader + DBWriter> Classifier<'db, T, D
                 .unwrap();
         }
 
+        let base_fee = header.base_fee_per_gas.unwrap_or_default() as u128;
+        let failed_txs = traces
+            .iter()
+            .enumerate()
+            .filter_map(|(tx_idx, trace)| FailedTx::from_trace(tx_idx, trace, base_fee))
+            .collect_vec();
+
         let tx_roots = self.build_tx_trees(traces, &header).await;
         let mut tree = BlockTree::new(header, tx_roots.len());
+        tree.insert_failed_txs(failed_txs);
 
         // send out all updates
         let further_classification_requests =
@@ -386,11 +399,28 @@ impl<'db, T: TracingProvider, DB: LibmdbxReader + DBWriter> Classifier<'db, T, D
             }
         }
 
-        if let Some(results) =
-            ProtocolClassifier::default().dispatch(call_info, self.libmdbx, block, tx_idx)
+        // the dispatch consumes the call info, keep it around in case the call is to
+        // an unknown fork of a supported pool
+        let fork_candidate = fork_detector()
+            .filter(|detector| !detector.is_checked(call_info.target_address))
+            .and_then(|_| swap_log_protocol(&call_info))
+            .map(|protocol| (protocol, call_info.clone()));
+        let uniswap_v4_pool = pool_key_of_call(&call_info);
+
+        // definitions loaded from config are bound to specific addresses, so they
+        // take precedence over the compiled classifiers
+        if let Some(mut results) = declarative_classifiers()
+            .dispatch(&call_info, self.libmdbx, block, tx_idx)
+            .or_else(|| {
+                ProtocolClassifier::default().dispatch(call_info, self.libmdbx, block, tx_idx)
+            })
         {
+            if let Some((pool, key)) = uniswap_v4_pool {
+                self.insert_uniswap_v4_pool_key(pool, key).await;
+            }
+
             if results.1.is_new_pool() {
-                let Action::NewPool(p) = &results.1 else { unreachable!() };
+                let Action::NewPool(p) = &mut results.1 else { unreachable!() };
                 self.insert_new_pool(block, p).await;
             } else if results.1.is_pool_config_update() {
                 let Action::PoolConfigUpdate(p) = &results.1 else { unreachable!() };
@@ -402,9 +432,13 @@ impl<'db, T: TracingProvider, DB: LibmdbxReader + DBWriter> Classifier<'db, T, D
                 {
                     error!(pool=?p.pool_address,"failed to update pool config");
                 }
+            } else if let Action::OracleUpdate(update) = &mut results.1 {
+                load_previous_answer(&self.provider, block, update).await;
             }
 
             (vec![results.0], vec![results.1])
+        } else if let Some(fork) = self.classify_fork(block, tx_idx, fork_candidate).await {
+            fork
         } else if let Some(transfer) = self
             .classify_transfer(tx_idx, trace_index, &trace, block)
             .await
@@ -420,6 +454,79 @@ impl<'db, T: TracingProvider, DB: LibmdbxReader + DBWriter> Classifier<'db, T, D
         }
     }
 
+    /// v4 pools can only be loaded with their key, which we only see in the
+    /// calls to the PoolManager
+    async fn insert_uniswap_v4_pool_key(&self, pool: Address, key: UniswapV4PoolKey) {
+        if matches!(self.libmdbx.try_fetch_uniswap_v4_pool_key(pool), Ok(Some(_))) {
+            return
+        }
+
+        if self
+            .libmdbx
+            .write_uniswap_v4_pool_key(pool, key)
+            .await
+            .is_err()
+        {
+            error!(?pool, "failed to write uniswap v4 pool key");
+        }
+    }
+
+    /// Registers the target of an unclassified call that emitted a Uniswap V2
+    /// or V3 swap log if its bytecode matches a known pool, then classifies
+    /// the call with the matched protocol
+    async fn classify_fork(
+        &self,
+        block: u64,
+        tx_idx: u64,
+        candidate: Option<(Protocol, CallFrameInfo<'_>)>,
+    ) -> Option<(Vec<DexPriceMsg>, Vec<Action>)> {
+        let (protocol, call_info) = candidate?;
+        let pool_address = call_info.target_address;
+        let detector = fork_detector()?;
+        if detector.is_checked(pool_address) {
+            return None
+        }
+        // a known pool whose classification failed
+        if self.libmdbx.get_protocol(pool_address).is_ok() {
+            detector.mark_checked(pool_address);
+            return None
+        }
+        // already detected, pools rejected during review stay unclassified
+        match self.libmdbx.try_fetch_fork_detection(pool_address) {
+            Ok(None) => {}
+            Ok(Some(_)) => {
+                detector.mark_checked(pool_address);
+                return None
+            }
+            Err(_) => return None,
+        }
+
+        let fork = detector
+            .detect(&self.provider, block, pool_address, protocol)
+            .await?;
+        let mut pool = NormalizedNewPool {
+            trace_index: call_info.trace_idx,
+            protocol,
+            pool_address,
+            tokens: fork.tokens.to_vec(),
+        };
+        self.insert_new_pool(block, &mut pool).await;
+
+        if self
+            .libmdbx
+            .write_fork_detection(pool_address, fork.detection(block))
+            .await
+            .is_err()
+        {
+            error!(pool=?pool_address, "failed to record fork detection");
+        }
+
+        let (update, action) =
+            ProtocolClassifier::default().dispatch(call_info, self.libmdbx, block, tx_idx)?;
+
+        Some((vec![DexPriceMsg::DiscoveredPool(pool.try_into().ok()?), update], vec![action]))
+    }
+
     async fn classify_transfer(
         &self,
         tx_idx: u64,
@@ -614,7 +721,7 @@ impl<'db, T: TracingProvider, DB: LibmdbxReader + DBWriter> Classifier<'db, T, D
                 .await
                 .into_iter()
                 // insert the pool returning if it has token values.
-                .map(|pool| async {
+                .map(|mut pool| async move {
                     trace!(
                         target: "brontes_classifier::discovery",
                         "Discovered new {} pool:
@@ -623,7 +730,7 @@ impl<'db, T: TracingProvider, DB: LibmdbxReader + DBWriter> Classifier<'db, T, D
                         pool.pool_address,
                         pool.protocol,
                     );
-                    self.insert_new_pool(block, &pool).await;
+                    self.insert_new_pool(block, &mut pool).await;
                     Some((pool.clone().try_into().ok()?, pool))
                 }),
         )
@@ -634,7 +741,11 @@ impl<'db, T: TracingProvider, DB: LibmdbxReader + DBWriter> Classifier<'db, T, D
         .unzip()
     }
 
-    async fn insert_new_pool(&self, block: u64, pool: &NormalizedNewPool) {
+    async fn insert_new_pool(&self, block: u64, pool: &mut NormalizedNewPool) {
+        // cTokens registered without their underlying can't classify their lending
+        // actions
+        load_underlying(&self.provider, self.libmdbx, block, pool).await;
+
         if self
             .libmdbx
             .insert_pool(block, pool.pool_address, &pool.tokens, None, pool.protocol)
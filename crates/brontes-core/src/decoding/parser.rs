@@ -107,6 +107,26 @@ impl<T: TracingProvider, DB: LibmdbxReader + DBWriter> TraceParser<T, DB> {
             return None
         }
 
+        let (traces, header) = self.trace_block_with_provider(block_num).await?;
+
+        if self
+            .libmdbx
+            .save_traces(block_num, traces.clone())
+            .await
+            .is_err()
+        {
+            error!(%block_num, "failed to store traces for block");
+        }
+
+        Some((traces, header))
+    }
+
+    /// Traces the block with the [`TracingProvider`] without looking in the
+    /// db first, nor storing the result.
+    pub async fn trace_block_with_provider(
+        &self,
+        block_num: u64,
+    ) -> Option<(Vec<TxTrace>, Header)> {
         let parity_trace = self.trace_block(block_num).await;
         let receipts = self.get_receipts(block_num).await;
 
@@ -134,15 +154,6 @@ impl<T: TracingProvider, DB: LibmdbxReader + DBWriter> TraceParser<T, DB> {
             .metrics_tx
             .send(TraceMetricEvent::BlockMetricRecieved(traces.1).into());
 
-        if self
-            .libmdbx
-            .save_traces(block_num, traces.0.clone())
-            .await
-            .is_err()
-        {
-            error!(%block_num, "failed to store traces for block");
-        }
-
         Some((traces.0, traces.2))
     }
 
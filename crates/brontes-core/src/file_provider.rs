@@ -0,0 +1,885 @@
+//! A [`TracingProvider`] backed by a directory of recorded blocks, so that
+//! tests can run without a node.
+//!
+//! Every block is stored as `{block}.json` and holds the traces, receipts and
+//! header of the block together with the storage slots, bytecode and
+//! `eth_call` results that were requested at that block. Requests without a
+//! block go to `latest.json`. Lookups by transaction hash and the best block
+//! number are kept in `index.json`.
+//!
+//! In record mode all requests are forwarded to a real provider. The
+//! responses are kept in memory and merged into the files of their block once
+//! the requests move on to another block, when [`FileTracingProvider::flush`]
+//! is called and when the provider is dropped. In replay mode anything that
+//! wasn't recorded is an error.
+use std::{
+    collections::hash_map::Entry,
+    fs, mem,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use alloy_primitives::keccak256;
+use alloy_rpc_types::AnyReceiptEnvelope;
+use brontes_types::{
+    structured_trace::{TransactionTraceWithLogs, TxTrace},
+    traits::{ReplayedTransaction, TracingProvider},
+    FastHashMap,
+};
+use reth_primitives::{
+    Address, BlockId, BlockNumber, BlockNumberOrTag, Bytecode, Bytes, Header, StorageValue, TxHash,
+    B256,
+};
+use reth_rpc_types::{
+    state::StateOverride, BlockOverrides, Log, TransactionReceipt, TransactionRequest,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::json;
+
+/// Recordings are read, merged and written back as a whole. Every test
+/// creates its own provider, so the lock is shared by all of them.
+static RECORD_LOCK: Mutex<()> = Mutex::new(());
+
+pub struct FileTracingProvider {
+    dir:      PathBuf,
+    /// Set in record mode
+    recorder: Option<Box<dyn TracingProvider>>,
+    /// Responses recorded since the last flush
+    pending:  Mutex<PendingRecordings>,
+    /// Recordings loaded in replay mode
+    blocks:   Mutex<FastHashMap<RecordingKey, RecordedBlock>>,
+    index:    Mutex<Option<RecordedIndex>>,
+}
+
+impl FileTracingProvider {
+    /// Replays the recordings in `dir`
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir:      dir.into(),
+            recorder: None,
+            pending:  Mutex::default(),
+            blocks:   Mutex::default(),
+            index:    Mutex::default(),
+        }
+    }
+
+    /// Forwards all requests to `provider` and records the responses in `dir`
+    pub fn record(
+        dir: impl Into<PathBuf>,
+        provider: Box<dyn TracingProvider>,
+    ) -> eyre::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+
+        Ok(Self { recorder: Some(provider), ..Self::new(dir) })
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recorder.is_some()
+    }
+
+    fn block<R>(
+        &self,
+        key: RecordingKey,
+        f: impl FnOnce(&RecordedBlock) -> Option<R>,
+    ) -> eyre::Result<R> {
+        let mut blocks = self.blocks.lock().unwrap();
+        let block = match blocks.entry(key) {
+            Entry::Occupied(o) => o.into_mut(),
+            Entry::Vacant(v) => v.insert(read_recording(&self.dir.join(key.file_name()))?),
+        };
+
+        f(block).ok_or_else(|| not_recorded(key))
+    }
+
+    fn index<R>(&self, f: impl FnOnce(&RecordedIndex) -> Option<R>) -> eyre::Result<R> {
+        let mut index = self.index.lock().unwrap();
+        if index.is_none() {
+            *index = Some(read_recording(&self.dir.join(INDEX_FILE))?);
+        }
+
+        f(index.as_ref().unwrap()).ok_or_else(|| {
+            eyre::eyre!("request not recorded in {INDEX_FILE}, re-run the test in record mode")
+        })
+    }
+
+    /// Writes everything recorded so far to the directory
+    pub async fn flush(&self) -> eyre::Result<()> {
+        let recordings = self.pending.lock().unwrap().take_all();
+        self.write_recordings(recordings).await
+    }
+
+    async fn record_block(
+        &self,
+        key: RecordingKey,
+        f: impl FnOnce(&mut RecordedBlock),
+    ) -> eyre::Result<()> {
+        let finished = {
+            let mut pending = self.pending.lock().unwrap();
+            f(pending.blocks.entry(key).or_default());
+            pending.take_finished(key)
+        };
+
+        self.write_recordings(finished).await
+    }
+
+    /// The index is small, it's written together with the next block
+    fn record_index(&self, f: impl FnOnce(&mut RecordedIndex)) -> eyre::Result<()> {
+        let mut pending = self.pending.lock().unwrap();
+        f(pending.index.get_or_insert_with(Default::default));
+
+        Ok(())
+    }
+
+    async fn write_recordings(&self, recordings: PendingRecordings) -> eyre::Result<()> {
+        if recordings.is_empty() {
+            return Ok(())
+        }
+
+        let dir = self.dir.clone();
+        tokio::task::spawn_blocking(move || recordings.write(&dir)).await?
+    }
+}
+
+impl Drop for FileTracingProvider {
+    fn drop(&mut self) {
+        let recordings = self.pending.get_mut().map(mem::take).unwrap_or_default();
+        if recordings.is_empty() {
+            return
+        }
+
+        if let Err(e) = recordings.write(&self.dir) {
+            tracing::error!(dir = ?self.dir, error = %e, "failed to write recordings");
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl TracingProvider for FileTracingProvider {
+    async fn eth_call(
+        &self,
+        request: TransactionRequest,
+        block_number: Option<BlockId>,
+        state_overrides: Option<StateOverride>,
+        block_overrides: Option<Box<BlockOverrides>>,
+    ) -> eyre::Result<Bytes> {
+        let key = RecordingKey::from_block_id(block_number);
+        let call = call_key(&request, block_number, &state_overrides, &block_overrides)?;
+
+        let Some(provider) = &self.recorder else {
+            return self.block(key, |block| block.calls.get(&call).cloned())
+        };
+
+        let res = provider
+            .eth_call(request, block_number, state_overrides, block_overrides)
+            .await?;
+        self.record_block(key, |block| {
+            block.calls.insert(call, res.clone());
+        })
+        .await?;
+
+        Ok(res)
+    }
+
+    async fn eth_call_light(
+        &self,
+        request: TransactionRequest,
+        block_number: BlockId,
+    ) -> eyre::Result<Bytes> {
+        let key = RecordingKey::from_block_id(Some(block_number));
+        let call = call_key(&request, Some(block_number), &None, &None)?;
+
+        let Some(provider) = &self.recorder else {
+            return self.block(key, |block| block.calls.get(&call).cloned())
+        };
+
+        let res = provider.eth_call_light(request, block_number).await?;
+        self.record_block(key, |block| {
+            block.calls.insert(call, res.clone());
+        })
+        .await?;
+
+        Ok(res)
+    }
+
+    async fn block_hash_for_id(&self, block_num: u64) -> eyre::Result<Option<B256>> {
+        let key = RecordingKey::Block(block_num);
+        let Some(provider) = &self.recorder else {
+            return self.block(key, |block| block.block_hash)
+        };
+
+        let res = provider.block_hash_for_id(block_num).await?;
+        self.record_block(key, |block| block.block_hash = res)
+            .await?;
+
+        Ok(res)
+    }
+
+    #[cfg(feature = "local-reth")]
+    fn best_block_number(&self) -> eyre::Result<u64> {
+        let Some(provider) = &self.recorder else {
+            return self.index(|index| index.best_block_number)
+        };
+
+        let res = provider.best_block_number()?;
+        self.record_index(|index| index.best_block_number = Some(res))?;
+
+        Ok(res)
+    }
+
+    #[cfg(not(feature = "local-reth"))]
+    async fn best_block_number(&self) -> eyre::Result<u64> {
+        let Some(provider) = &self.recorder else {
+            return self.index(|index| index.best_block_number)
+        };
+
+        let res = provider.best_block_number().await?;
+        self.record_index(|index| index.best_block_number = Some(res))?;
+
+        Ok(res)
+    }
+
+    async fn replay_block_transactions(
+        &self,
+        block_id: BlockId,
+    ) -> eyre::Result<Option<Vec<TxTrace>>> {
+        let key = RecordingKey::from_block_id(Some(block_id));
+        let Some(provider) = &self.recorder else {
+            return self.block(key, |block| {
+                block
+                    .traces
+                    .clone()
+                    .map(|traces| traces.map(|traces| traces.into_iter().map(Into::into).collect()))
+            })
+        };
+
+        let res = provider.replay_block_transactions(block_id).await?;
+        self.record_block(key, |block| {
+            block.traces = Some(
+                res.as_ref()
+                    .map(|traces| traces.iter().cloned().map(Into::into).collect()),
+            )
+        })
+        .await?;
+
+        Ok(res)
+    }
+
+    async fn block_receipts(
+        &self,
+        number: BlockNumberOrTag,
+    ) -> eyre::Result<Option<Vec<TransactionReceipt<AnyReceiptEnvelope<Log>>>>> {
+        let key = RecordingKey::from_block_id(Some(BlockId::Number(number)));
+        let Some(provider) = &self.recorder else {
+            return self.block(key, |block| block.receipts.clone())
+        };
+
+        let res = provider.block_receipts(number).await?;
+        self.record_block(key, |block| block.receipts = Some(res.clone()))
+            .await?;
+
+        Ok(res)
+    }
+
+    async fn header_by_number(&self, number: BlockNumber) -> eyre::Result<Option<Header>> {
+        let key = RecordingKey::Block(number);
+        let Some(provider) = &self.recorder else {
+            return self.block(key, |block| block.header.clone())
+        };
+
+        let res = provider.header_by_number(number).await?;
+        self.record_block(key, |block| block.header = Some(res.clone()))
+            .await?;
+
+        Ok(res)
+    }
+
+    async fn block_and_tx_index(&self, hash: TxHash) -> eyre::Result<(u64, usize)> {
+        let Some(provider) = &self.recorder else {
+            return self.index(|index| index.transactions.get(&hash).copied())
+        };
+
+        let res = provider.block_and_tx_index(hash).await?;
+        self.record_index(|index| {
+            index.transactions.insert(hash, res);
+        })?;
+
+        Ok(res)
+    }
+
+    async fn replay_transaction_without(
+        &self,
+        block_number: u64,
+        tx_index: usize,
+        skip: Vec<usize>,
+    ) -> eyre::Result<ReplayedTransaction> {
+        let key = RecordingKey::Block(block_number);
+        let replay = format!("{tx_index}:{skip:?}");
+
+        let Some(provider) = &self.recorder else {
+            return self.block(key, |block| block.replays.get(&replay).cloned())
+        };
+
+        let res = provider
+            .replay_transaction_without(block_number, tx_index, skip)
+            .await?;
+        self.record_block(key, |block| {
+            block.replays.insert(replay, res.clone());
+        })
+        .await?;
+
+        Ok(res)
+    }
+
+    async fn get_storage(
+        &self,
+        block_number: Option<u64>,
+        address: Address,
+        storage_key: B256,
+    ) -> eyre::Result<Option<StorageValue>> {
+        let key = RecordingKey::from_number(block_number);
+        let Some(provider) = &self.recorder else {
+            return self.block(key, |block| {
+                block
+                    .storage
+                    .get(&address)
+                    .and_then(|slots| slots.get(&storage_key))
+                    .copied()
+            })
+        };
+
+        let res = provider
+            .get_storage(block_number, address, storage_key)
+            .await?;
+        self.record_block(key, |block| {
+            block
+                .storage
+                .entry(address)
+                .or_default()
+                .insert(storage_key, res);
+        })
+        .await?;
+
+        Ok(res)
+    }
+
+    async fn get_bytecode(
+        &self,
+        block_number: Option<u64>,
+        address: Address,
+    ) -> eyre::Result<Option<Bytecode>> {
+        let key = RecordingKey::from_number(block_number);
+        let Some(provider) = &self.recorder else {
+            return self.block(key, |block| {
+                block
+                    .bytecode
+                    .get(&address)
+                    .map(|code| code.clone().map(Bytecode::new_raw))
+            })
+        };
+
+        let res = provider.get_bytecode(block_number, address).await?;
+        self.record_block(key, |block| {
+            block
+                .bytecode
+                .insert(address, res.as_ref().map(|code| code.original_bytes()));
+        })
+        .await?;
+
+        Ok(res)
+    }
+}
+
+const INDEX_FILE: &str = "index.json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum RecordingKey {
+    Block(u64),
+    /// requests that aren't pinned to a block number
+    Latest,
+}
+
+impl RecordingKey {
+    fn from_number(number: Option<u64>) -> Self {
+        number.map(Self::Block).unwrap_or(Self::Latest)
+    }
+
+    fn from_block_id(block_id: Option<BlockId>) -> Self {
+        match block_id {
+            Some(BlockId::Number(BlockNumberOrTag::Number(number))) => Self::Block(number),
+            _ => Self::Latest,
+        }
+    }
+
+    fn file_name(&self) -> String {
+        match self {
+            Self::Block(number) => format!("{number}.json"),
+            Self::Latest => "latest.json".to_string(),
+        }
+    }
+}
+
+fn not_recorded(key: RecordingKey) -> eyre::Report {
+    eyre::eyre!("request not recorded in {}, re-run the test in record mode", key.file_name())
+}
+
+/// Calls are keyed by the hash of all of their arguments
+fn call_key(
+    request: &TransactionRequest,
+    block_number: Option<BlockId>,
+    state_overrides: &Option<StateOverride>,
+    block_overrides: &Option<Box<BlockOverrides>>,
+) -> eyre::Result<B256> {
+    let call = json!([request, block_number, state_overrides, block_overrides]);
+    Ok(keccak256(serde_json::to_vec(&call)?))
+}
+
+fn read_recording<T: DeserializeOwned + Default>(path: &Path) -> eyre::Result<T> {
+    match fs::read(path) {
+        Ok(bytes) => Ok(serde_json::from_slice(&bytes)?),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(T::default()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Merges the responses into the recording at `path`, other providers might
+/// have recorded the same block in the meantime
+fn merge_recording<T: Recording>(path: &Path, responses: T) -> eyre::Result<()> {
+    let mut recording: T = read_recording(path)?;
+    recording.merge(responses);
+
+    // write to a temp file first so a crash can't leave a half written recording
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, serde_json::to_vec_pretty(&recording)?)?;
+    fs::rename(tmp, path)?;
+
+    Ok(())
+}
+
+trait Recording: Serialize + DeserializeOwned + Default {
+    /// Adds the newer responses, replacing the ones recorded before
+    fn merge(&mut self, newer: Self);
+}
+
+/// The responses recorded since the last flush, requests for a block are
+/// mostly made together so a block is written once the requests move on
+#[derive(Default)]
+struct PendingRecordings {
+    blocks:  FastHashMap<RecordingKey, RecordedBlock>,
+    index:   Option<RecordedIndex>,
+    current: Option<RecordingKey>,
+}
+
+impl PendingRecordings {
+    fn is_empty(&self) -> bool {
+        self.blocks.is_empty() && self.index.is_none()
+    }
+
+    /// Takes the recordings of all other blocks once the requests move on to
+    /// `key`
+    fn take_finished(&mut self, key: RecordingKey) -> Self {
+        if self.current.replace(key) == Some(key) {
+            return Self::default()
+        }
+
+        let current = self.blocks.remove(&key);
+        let finished = self.take_all();
+        self.current = Some(key);
+        if let Some(current) = current {
+            self.blocks.insert(key, current);
+        }
+
+        finished
+    }
+
+    fn take_all(&mut self) -> Self {
+        Self { blocks: mem::take(&mut self.blocks), index: self.index.take(), current: None }
+    }
+
+    fn write(self, dir: &Path) -> eyre::Result<()> {
+        let _guard = RECORD_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+        for (key, block) in self.blocks {
+            merge_recording(&dir.join(key.file_name()), block)?;
+        }
+        if let Some(index) = self.index {
+            merge_recording(&dir.join(INDEX_FILE), index)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct RecordedIndex {
+    #[serde(default)]
+    best_block_number: Option<u64>,
+    #[serde(default)]
+    transactions:      FastHashMap<TxHash, (u64, usize)>,
+}
+
+impl Recording for RecordedIndex {
+    fn merge(&mut self, newer: Self) {
+        if newer.best_block_number.is_some() {
+            self.best_block_number = newer.best_block_number;
+        }
+        self.transactions.extend(newer.transactions);
+    }
+}
+
+/// `None` values are responses of the provider, missing entries haven't been
+/// recorded.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct RecordedBlock {
+    #[serde(default)]
+    block_hash: Option<Option<B256>>,
+    #[serde(default)]
+    header:     Option<Option<Header>>,
+    #[serde(default)]
+    traces:     Option<Option<Vec<RecordedTxTrace>>>,
+    #[serde(default)]
+    receipts:   Option<Option<Vec<TransactionReceipt<AnyReceiptEnvelope<Log>>>>>,
+    #[serde(default)]
+    storage:    FastHashMap<Address, FastHashMap<B256, Option<StorageValue>>>,
+    #[serde(default)]
+    bytecode:   FastHashMap<Address, Option<Bytes>>,
+    /// keyed by [`call_key`]
+    #[serde(default)]
+    calls:      FastHashMap<B256, Bytes>,
+    /// keyed by the tx index and the skipped transactions
+    #[serde(default)]
+    replays:    FastHashMap<String, ReplayedTransaction>,
+}
+
+impl Recording for RecordedBlock {
+    fn merge(&mut self, newer: Self) {
+        if newer.block_hash.is_some() {
+            self.block_hash = newer.block_hash;
+        }
+        if newer.header.is_some() {
+            self.header = newer.header;
+        }
+        if newer.traces.is_some() {
+            self.traces = newer.traces;
+        }
+        if newer.receipts.is_some() {
+            self.receipts = newer.receipts;
+        }
+        for (address, slots) in newer.storage {
+            self.storage.entry(address).or_default().extend(slots);
+        }
+        self.bytecode.extend(newer.bytecode);
+        self.calls.extend(newer.calls);
+        self.replays.extend(newer.replays);
+    }
+}
+
+/// [`TxTrace`] serializes into the clickhouse format, which can't be read
+/// back.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RecordedTxTrace {
+    block_number:    u64,
+    trace:           Vec<TransactionTraceWithLogs>,
+    tx_hash:         B256,
+    gas_used:        u128,
+    effective_price: u128,
+    tx_index:        u64,
+    is_success:      bool,
+}
+
+impl From<TxTrace> for RecordedTxTrace {
+    fn from(trace: TxTrace) -> Self {
+        Self {
+            block_number:    trace.block_number,
+            trace:           trace.trace,
+            tx_hash:         trace.tx_hash,
+            gas_used:        trace.gas_used,
+            effective_price: trace.effective_price,
+            tx_index:        trace.tx_index,
+            is_success:      trace.is_success,
+        }
+    }
+}
+
+impl From<RecordedTxTrace> for TxTrace {
+    fn from(trace: RecordedTxTrace) -> Self {
+        TxTrace::new(
+            trace.block_number,
+            trace.trace,
+            trace.tx_hash,
+            trace.tx_index,
+            trace.gas_used,
+            trace.effective_price,
+            trace.is_success,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{address, b256, bytes, U256};
+    use reth_rpc_types::request::TransactionInput;
+
+    use super::*;
+
+    const POOL: Address = address!("88e6A0c2dDD26FEEb64F039a2c41296FcB3f5640");
+    const TX: TxHash = b256!("1111111111111111111111111111111111111111111111111111111111111111");
+
+    /// Answers every request without a node
+    struct MockProvider;
+
+    #[async_trait::async_trait]
+    impl TracingProvider for MockProvider {
+        async fn eth_call(
+            &self,
+            request: TransactionRequest,
+            _block_number: Option<BlockId>,
+            _state_overrides: Option<StateOverride>,
+            _block_overrides: Option<Box<BlockOverrides>>,
+        ) -> eyre::Result<Bytes> {
+            Ok(request.input.input().cloned().unwrap_or_default())
+        }
+
+        async fn block_hash_for_id(&self, block_num: u64) -> eyre::Result<Option<B256>> {
+            Ok(Some(B256::with_last_byte(block_num as u8)))
+        }
+
+        #[cfg(feature = "local-reth")]
+        fn best_block_number(&self) -> eyre::Result<u64> {
+            Ok(20)
+        }
+
+        #[cfg(not(feature = "local-reth"))]
+        async fn best_block_number(&self) -> eyre::Result<u64> {
+            Ok(20)
+        }
+
+        async fn replay_block_transactions(
+            &self,
+            block_id: BlockId,
+        ) -> eyre::Result<Option<Vec<TxTrace>>> {
+            let Some(block_number) = block_id.as_u64() else { return Ok(None) };
+            Ok(Some(vec![TxTrace::new(block_number, vec![], TX, 0, 21_000, 10, true)]))
+        }
+
+        async fn block_receipts(
+            &self,
+            _number: BlockNumberOrTag,
+        ) -> eyre::Result<Option<Vec<TransactionReceipt<AnyReceiptEnvelope<Log>>>>> {
+            Ok(Some(vec![]))
+        }
+
+        async fn header_by_number(&self, number: BlockNumber) -> eyre::Result<Option<Header>> {
+            Ok(Some(Header { number, ..Default::default() }))
+        }
+
+        async fn block_and_tx_index(&self, _hash: TxHash) -> eyre::Result<(u64, usize)> {
+            Ok((10, 3))
+        }
+
+        async fn replay_transaction_without(
+            &self,
+            _block_number: u64,
+            tx_index: usize,
+            skip: Vec<usize>,
+        ) -> eyre::Result<ReplayedTransaction> {
+            Ok(ReplayedTransaction {
+                from:               POOL,
+                is_success:         !skip.contains(&tx_index),
+                logs:               vec![],
+                eth_balance_deltas: vec![],
+            })
+        }
+
+        async fn get_storage(
+            &self,
+            _block_number: Option<u64>,
+            _address: Address,
+            storage_key: B256,
+        ) -> eyre::Result<Option<StorageValue>> {
+            Ok((storage_key != B256::ZERO).then(|| U256::from_be_bytes(storage_key.0)))
+        }
+
+        async fn get_bytecode(
+            &self,
+            _block_number: Option<u64>,
+            _address: Address,
+        ) -> eyre::Result<Option<Bytecode>> {
+            Ok(Some(Bytecode::new_raw(bytes!("6080604052"))))
+        }
+    }
+
+    fn recordings_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("brontes-recordings-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    async fn best_block_number(provider: &FileTracingProvider) -> eyre::Result<u64> {
+        #[cfg(feature = "local-reth")]
+        return provider.best_block_number();
+        #[cfg(not(feature = "local-reth"))]
+        return provider.best_block_number().await
+    }
+
+    #[tokio::test]
+    async fn test_record_and_replay() {
+        let dir = recordings_dir("replay");
+        let call = TransactionRequest {
+            to: Some(POOL),
+            input: TransactionInput::new(bytes!("0902f1ac")),
+            ..Default::default()
+        };
+        let block = BlockId::from(10u64);
+        let slot = B256::with_last_byte(1);
+
+        let recorder = FileTracingProvider::record(&dir, Box::new(MockProvider)).unwrap();
+        assert!(recorder.is_recording());
+
+        let traces = recorder.replay_block_transactions(block).await.unwrap();
+        let header = recorder.header_by_number(10).await.unwrap();
+        let receipts = recorder.block_receipts(10.into()).await.unwrap();
+        let hash = recorder.block_hash_for_id(10).await.unwrap();
+        let res = recorder
+            .eth_call(call.clone(), Some(block), None, None)
+            .await
+            .unwrap();
+        let storage = recorder.get_storage(Some(10), POOL, slot).await.unwrap();
+        let empty_slot = recorder
+            .get_storage(Some(10), POOL, B256::ZERO)
+            .await
+            .unwrap();
+        let code = recorder.get_bytecode(None, POOL).await.unwrap();
+        let index = recorder.block_and_tx_index(TX).await.unwrap();
+        let replay = recorder
+            .replay_transaction_without(10, 3, vec![1])
+            .await
+            .unwrap();
+        let best = best_block_number(&recorder).await.unwrap();
+        recorder.flush().await.unwrap();
+
+        assert!(dir.join("10.json").exists());
+        assert!(dir.join("latest.json").exists());
+        assert!(dir.join(INDEX_FILE).exists());
+
+        let replayer = FileTracingProvider::new(&dir);
+        assert!(!replayer.is_recording());
+
+        assert_eq!(replayer.replay_block_transactions(block).await.unwrap(), traces);
+        assert_eq!(replayer.header_by_number(10).await.unwrap(), header);
+        assert_eq!(replayer.block_receipts(10.into()).await.unwrap(), receipts);
+        assert_eq!(replayer.block_hash_for_id(10).await.unwrap(), hash);
+        assert_eq!(
+            replayer
+                .eth_call(call.clone(), Some(block), None, None)
+                .await
+                .unwrap(),
+            res
+        );
+        // the light call is the same request
+        assert_eq!(replayer.eth_call_light(call, block).await.unwrap(), res);
+        assert_eq!(replayer.get_storage(Some(10), POOL, slot).await.unwrap(), storage);
+        assert_eq!(
+            replayer
+                .get_storage(Some(10), POOL, B256::ZERO)
+                .await
+                .unwrap(),
+            empty_slot
+        );
+        assert_eq!(
+            replayer
+                .get_bytecode(None, POOL)
+                .await
+                .unwrap()
+                .map(|c| c.original_bytes()),
+            code.map(|c| c.original_bytes())
+        );
+        assert_eq!(replayer.block_and_tx_index(TX).await.unwrap(), index);
+        assert_eq!(
+            replayer
+                .replay_transaction_without(10, 3, vec![1])
+                .await
+                .unwrap(),
+            replay
+        );
+        assert_eq!(best_block_number(&replayer).await.unwrap(), best);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_writes_blocks_once_requests_move_on() {
+        let dir = recordings_dir("flush");
+        let recorder = FileTracingProvider::record(&dir, Box::new(MockProvider)).unwrap();
+
+        recorder.header_by_number(10).await.unwrap();
+        recorder.block_hash_for_id(10).await.unwrap();
+        assert!(!dir.join("10.json").exists());
+
+        recorder.header_by_number(11).await.unwrap();
+        assert!(dir.join("10.json").exists());
+        assert!(!dir.join("11.json").exists());
+
+        drop(recorder);
+        assert!(dir.join("11.json").exists());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_merges_recordings_of_the_same_block() {
+        let dir = recordings_dir("merge");
+        let first = FileTracingProvider::record(&dir, Box::new(MockProvider)).unwrap();
+        let second = FileTracingProvider::record(&dir, Box::new(MockProvider)).unwrap();
+
+        let header = first.header_by_number(10).await.unwrap();
+        let hash = second.block_hash_for_id(10).await.unwrap();
+        let slot = B256::with_last_byte(1);
+        let storage = first.get_storage(Some(10), POOL, slot).await.unwrap();
+        let empty_slot = second
+            .get_storage(Some(10), POOL, B256::ZERO)
+            .await
+            .unwrap();
+        first.flush().await.unwrap();
+        second.flush().await.unwrap();
+
+        let replayer = FileTracingProvider::new(&dir);
+        assert_eq!(replayer.header_by_number(10).await.unwrap(), header);
+        assert_eq!(replayer.block_hash_for_id(10).await.unwrap(), hash);
+        assert_eq!(replayer.get_storage(Some(10), POOL, slot).await.unwrap(), storage);
+        assert_eq!(
+            replayer
+                .get_storage(Some(10), POOL, B256::ZERO)
+                .await
+                .unwrap(),
+            empty_slot
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_missing_recordings() {
+        let dir = recordings_dir("missing");
+        let recorder = FileTracingProvider::record(&dir, Box::new(MockProvider)).unwrap();
+        recorder.header_by_number(10).await.unwrap();
+        recorder.flush().await.unwrap();
+
+        let replayer = FileTracingProvider::new(&dir);
+        // recorded block, but not the request
+        assert!(replayer
+            .get_storage(Some(10), POOL, B256::ZERO)
+            .await
+            .is_err());
+        assert!(replayer
+            .eth_call(TransactionRequest::default(), Some(10u64.into()), None, None)
+            .await
+            .is_err());
+        // nothing recorded for the block
+        assert!(replayer.header_by_number(11).await.is_err());
+        assert!(replayer.block_and_tx_index(TX).await.is_err());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -4,9 +4,12 @@
 pub mod decoding;
 pub mod errors;
 pub mod executor;
+pub mod file_provider;
 #[cfg(not(feature = "local-reth"))]
 pub mod local_provider;
 pub mod missing_token_info;
+#[cfg(not(feature = "local-reth"))]
+pub mod rpc_provider;
 
 #[cfg(feature = "tests")]
 pub mod test_utils;
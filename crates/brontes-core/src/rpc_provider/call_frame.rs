@@ -0,0 +1,360 @@
+//! The output of geth's `callTracer` and the conversion into the flat parity
+//! style traces that the rest of brontes works with.
+
+use alloy_primitives::{Address, Bytes, Log, LogData, B256, I256, U256, U64};
+use brontes_types::structured_trace::{TransactionTraceWithLogs, TxTrace};
+use reth_rpc_types::trace::parity::{
+    Action, CallAction, CallOutput, CallType, CreateAction, CreateOutput, SelfdestructAction,
+    TraceOutput, TransactionTrace,
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+/// A single entry of the `debug_traceBlockByNumber` response. `txHash` is only
+/// returned by newer clients and `error` is set when tracing the transaction
+/// failed.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockTraceResult {
+    #[serde(default)]
+    pub tx_hash: Option<B256>,
+    #[serde(default)]
+    pub result:  Option<CallFrame>,
+    #[serde(default)]
+    pub error:   Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallFrame {
+    #[serde(rename = "type")]
+    pub typ:      String,
+    pub from:     Address,
+    #[serde(default)]
+    pub to:       Option<Address>,
+    #[serde(default)]
+    pub value:    Option<U256>,
+    #[serde(default)]
+    pub gas:      U64,
+    #[serde(default)]
+    pub gas_used: U64,
+    #[serde(default)]
+    pub input:    Bytes,
+    #[serde(default)]
+    pub output:   Option<Bytes>,
+    #[serde(default)]
+    pub error:    Option<String>,
+    #[serde(default)]
+    pub calls:    Vec<CallFrame>,
+    #[serde(default)]
+    pub logs:     Vec<CallLog>,
+}
+
+/// Only returned when the tracer is configured with `withLog`
+#[derive(Debug, Clone, Deserialize)]
+pub struct CallLog {
+    pub address: Address,
+    #[serde(default)]
+    pub topics:  Vec<B256>,
+    #[serde(default)]
+    pub data:    Bytes,
+}
+
+/// The fields of a `eth_getBlockReceipts` entry that we need
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockReceipt {
+    pub transaction_hash:  B256,
+    pub transaction_index: U64,
+    pub block_number:      U64,
+    /// not set for pre byzantium receipts
+    #[serde(default)]
+    pub status:            Option<U64>,
+}
+
+/// A `eth_getBlockByNumber` response with the full transactions
+#[derive(Debug, Clone, Deserialize)]
+pub struct BlockWithTransactions {
+    pub transactions: Vec<BlockTransaction>,
+}
+
+/// The fields of a transaction that are needed to execute it as a call
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockTransaction {
+    pub from: Address,
+    #[serde(default)]
+    pub to: Option<Address>,
+    pub gas: U64,
+    #[serde(default)]
+    pub gas_price: Option<U256>,
+    #[serde(default)]
+    pub max_fee_per_gas: Option<U256>,
+    #[serde(default)]
+    pub max_priority_fee_per_gas: Option<U256>,
+    #[serde(default)]
+    pub value: U256,
+    #[serde(default)]
+    pub input: Bytes,
+    #[serde(default)]
+    pub access_list: Option<Value>,
+}
+
+impl BlockTransaction {
+    /// The transaction as a call request. Dynamic fee transactions also
+    /// return the effective `gasPrice`, which can't be set together with the
+    /// fee caps.
+    pub fn call_request(&self) -> Value {
+        let mut request = json!({
+            "from": self.from,
+            "to": self.to,
+            "gas": self.gas,
+            "value": self.value,
+            "input": self.input,
+        });
+
+        if let Some(max_fee) = self.max_fee_per_gas {
+            request["maxFeePerGas"] = json!(max_fee);
+            request["maxPriorityFeePerGas"] = json!(self.max_priority_fee_per_gas);
+        } else {
+            request["gasPrice"] = json!(self.gas_price);
+        }
+        if let Some(access_list) = &self.access_list {
+            request["accessList"] = access_list.clone();
+        }
+
+        request
+    }
+}
+
+impl CallFrame {
+    pub fn is_selfdestruct(&self) -> bool {
+        self.typ == "SELFDESTRUCT"
+    }
+
+    pub fn is_create(&self) -> bool {
+        self.typ == "CREATE" || self.typ == "CREATE2"
+    }
+
+    pub fn is_delegate_call(&self) -> bool {
+        self.typ == "DELEGATECALL"
+    }
+
+    /// The reth tracer doesn't record calls into precompiles, so we skip them
+    /// to keep the trace indexes the same.
+    pub fn is_precompile(&self) -> bool {
+        if self.is_create() || self.is_selfdestruct() {
+            return false
+        }
+        let Some(to) = self.to else { return false };
+        let (prefix, id) = to.as_slice().split_at(18);
+
+        prefix.iter().all(|byte| *byte == 0)
+            && matches!(u16::from_be_bytes([id[0], id[1]]), 0x01..=0x11 | 0x100)
+    }
+
+    /// A revert still returns data, any other error doesn't.
+    fn is_error_without_output(&self) -> bool {
+        self.error
+            .as_ref()
+            .is_some_and(|err| err != "execution reverted")
+    }
+
+    fn call_type(&self) -> CallType {
+        match self.typ.as_str() {
+            "CALL" => CallType::Call,
+            "CALLCODE" => CallType::CallCode,
+            "DELEGATECALL" => CallType::DelegateCall,
+            "STATICCALL" => CallType::StaticCall,
+            _ => CallType::None,
+        }
+    }
+
+    fn parity_action(&self) -> Action {
+        if self.is_create() {
+            Action::Create(CreateAction {
+                from:  self.from,
+                value: self.value.unwrap_or_default(),
+                gas:   self.gas,
+                init:  self.input.clone(),
+            })
+        } else {
+            Action::Call(CallAction {
+                from:      self.from,
+                to:        self.to.unwrap_or_default(),
+                value:     self.value.unwrap_or_default(),
+                gas:       self.gas,
+                input:     self.input.clone(),
+                call_type: self.call_type(),
+            })
+        }
+    }
+
+    fn parity_trace_output(&self) -> Option<TraceOutput> {
+        if self.is_error_without_output() {
+            return None
+        }
+
+        let output = self.output.clone().unwrap_or_default();
+        if self.is_create() {
+            Some(TraceOutput::Create(CreateOutput {
+                gas_used: self.gas_used,
+                code:     output,
+                address:  self.to.unwrap_or_default(),
+            }))
+        } else {
+            Some(TraceOutput::Call(CallOutput { gas_used: self.gas_used, output }))
+        }
+    }
+
+    fn parity_selfdestruct_action(&self) -> Action {
+        Action::Selfdestruct(SelfdestructAction {
+            address:        self.from,
+            refund_address: self.to.unwrap_or_default(),
+            balance:        self.value.unwrap_or_default(),
+        })
+    }
+
+    /// The logs of the frame and all of its subcalls. Calls that failed don't
+    /// emit any logs, neither do the calls they made.
+    pub fn all_logs(&self) -> Vec<Log> {
+        let mut logs = Vec::new();
+        self.collect_logs(&mut logs);
+        logs
+    }
+
+    fn collect_logs(&self, logs: &mut Vec<Log>) {
+        if self.error.is_some() {
+            return
+        }
+
+        logs.extend(self.logs());
+        self.calls.iter().for_each(|call| call.collect_logs(logs));
+    }
+
+    /// The change of the eth balance of every account the frame and its
+    /// subcalls moved eth between. Calls that failed don't move any eth,
+    /// neither do the calls they made. The gas of the transaction isn't part
+    /// of the trace.
+    pub fn eth_balance_deltas(&self) -> Vec<(Address, I256)> {
+        let mut deltas = Vec::new();
+        self.collect_eth_transfers(&mut deltas);
+        deltas.retain(|(_, delta)| !delta.is_zero());
+        deltas
+    }
+
+    fn collect_eth_transfers(&self, deltas: &mut Vec<(Address, I256)>) {
+        if self.error.is_some() {
+            return
+        }
+
+        // a delegate call runs with the value of its caller without moving it
+        if let (Some(to), Some(value)) = (self.to, self.value) {
+            if !self.is_delegate_call() && !value.is_zero() {
+                let value = I256::from_raw(value);
+                add_eth_delta(deltas, self.from, -value);
+                add_eth_delta(deltas, to, value);
+            }
+        }
+
+        self.calls
+            .iter()
+            .for_each(|call| call.collect_eth_transfers(deltas));
+    }
+
+    fn logs(&self) -> Vec<Log> {
+        self.logs
+            .iter()
+            .map(|log| Log {
+                address: log.address,
+                data:    LogData::new_unchecked(log.topics.clone(), log.data.clone()),
+            })
+            .collect()
+    }
+}
+
+fn add_eth_delta(deltas: &mut Vec<(Address, I256)>, account: Address, delta: I256) {
+    match deltas.iter_mut().find(|(address, _)| *address == account) {
+        Some((_, total)) => *total += delta,
+        None => deltas.push((account, delta)),
+    }
+}
+
+/// Flattens the call frame of a transaction into the same traces the reth
+/// tracer produces. The trace index is the position of the call in execution
+/// order, selfdestructs share the index of the call they happened in.
+pub fn into_tx_trace(
+    frame: CallFrame,
+    block_number: u64,
+    tx_hash: B256,
+    tx_index: u64,
+    is_success: bool,
+) -> TxTrace {
+    let mut traces = Vec::new();
+    let mut next_idx = 0;
+    flatten_frame(frame, vec![], None, &mut next_idx, &mut traces);
+
+    // gas used and the effective price are filled in from the receipts by the
+    // parser
+    TxTrace::new(block_number, traces, tx_hash, tx_index, 0, 0, is_success)
+}
+
+fn flatten_frame(
+    mut frame: CallFrame,
+    trace_address: Vec<usize>,
+    parent_msg_sender: Option<Address>,
+    next_idx: &mut u64,
+    traces: &mut Vec<TransactionTraceWithLogs>,
+) {
+    let trace_idx = *next_idx;
+    *next_idx += 1;
+
+    let (selfdestructs, calls): (Vec<_>, Vec<_>) = std::mem::take(&mut frame.calls)
+        .into_iter()
+        .filter(|call| !call.is_precompile())
+        .partition(CallFrame::is_selfdestruct);
+
+    // a delegate call executes in the context of its caller, so the msg.sender
+    // is inherited
+    let msg_sender =
+        if frame.is_delegate_call() { parent_msg_sender.unwrap_or(frame.from) } else { frame.from };
+
+    traces.push(TransactionTraceWithLogs {
+        trace: TransactionTrace {
+            action:        frame.parity_action(),
+            error:         frame.error.clone(),
+            result:        frame.parity_trace_output(),
+            trace_address: trace_address.clone(),
+            subtraces:     calls.len() + selfdestructs.len(),
+        },
+        logs: frame.logs(),
+        msg_sender,
+        trace_idx,
+        decoded_data: None,
+    });
+
+    for (i, selfdestruct) in selfdestructs.iter().enumerate() {
+        let mut address = trace_address.clone();
+        address.push(calls.len() + i);
+
+        traces.push(TransactionTraceWithLogs {
+            trace: TransactionTrace {
+                action:        selfdestruct.parity_selfdestruct_action(),
+                error:         None,
+                result:        None,
+                trace_address: address,
+                subtraces:     0,
+            },
+            logs: vec![],
+            msg_sender,
+            trace_idx,
+            decoded_data: None,
+        });
+    }
+
+    for (i, call) in calls.into_iter().enumerate() {
+        let mut address = trace_address.clone();
+        address.push(i);
+        flatten_frame(call, address, Some(msg_sender), next_idx, traces);
+    }
+}
@@ -0,0 +1,522 @@
+mod call_frame;
+
+use alloy_primitives::U64;
+use alloy_rpc_types::AnyReceiptEnvelope;
+use brontes_types::{
+    structured_trace::TxTrace,
+    traits::{ReplayedTransaction, TracingProvider},
+};
+pub use call_frame::*;
+use eyre::WrapErr;
+use reth_primitives::{
+    Address, BlockId, BlockNumber, BlockNumberOrTag, Bytecode, Bytes, Header, StorageValue, TxHash,
+    B256,
+};
+use reth_rpc_types::{
+    state::StateOverride, BlockOverrides, Log, TransactionReceipt, TransactionRequest,
+};
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::{json, Value};
+
+use crate::local_provider::LocalProvider;
+
+/// A [`TracingProvider`] for nodes that only expose the standard debug
+/// namespace, such as geth, erigon or nethermind. Blocks are traced with
+/// `debug_traceBlockByNumber` using the `callTracer` and joined with the
+/// receipts of the block, all other calls go through the [`LocalProvider`].
+///
+/// [`TracingProvider::replay_transaction_without`] is the exception, it needs
+/// the node to support `debug_traceCallMany`, which only reth and erigon do.
+///
+/// The `prestateTracer` isn't needed, a [`TxTrace`] only holds the calls, their
+/// logs and what the receipts fill in. The state the classifier and the pricing
+/// need is read at the block through [`TracingProvider::eth_call`],
+/// [`TracingProvider::get_storage`] and [`TracingProvider::get_bytecode`], as
+/// it is with the reth tracer.
+#[derive(Debug, Clone)]
+pub struct RpcTracingProvider {
+    inner:  LocalProvider,
+    client: reqwest::Client,
+    url:    String,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonRpcResponse<T> {
+    result: Option<T>,
+    error:  Option<JsonRpcError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonRpcError {
+    code:    i64,
+    message: String,
+}
+
+impl RpcTracingProvider {
+    pub fn new(url: String, retries: u8) -> Self {
+        Self {
+            inner: LocalProvider::new(url.clone(), retries),
+            client: reqwest::Client::new(),
+            url,
+        }
+    }
+
+    async fn request<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: Value,
+    ) -> eyre::Result<Option<T>> {
+        let res = self
+            .client
+            .post(&self.url)
+            .json(&json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params }))
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<JsonRpcResponse<T>>()
+            .await?;
+
+        if let Some(err) = res.error {
+            eyre::bail!("{method} failed with code {}: {}", err.code, err.message)
+        }
+
+        Ok(res.result)
+    }
+
+    /// Traces all transactions of the block and joins them with the receipts.
+    /// Returns `None` if the node doesn't know the block.
+    pub async fn trace_block(&self, block_id: BlockId) -> eyre::Result<Option<Vec<TxTrace>>> {
+        let (trace_method, block) = match block_id {
+            BlockId::Number(number) => ("debug_traceBlockByNumber", serde_json::to_value(number)?),
+            BlockId::Hash(hash) => {
+                ("debug_traceBlockByHash", serde_json::to_value(hash.block_hash)?)
+            }
+        };
+
+        let Some(receipts) = self
+            .request::<Vec<BlockReceipt>>("eth_getBlockReceipts", json!([block]))
+            .await?
+        else {
+            return Ok(None)
+        };
+
+        let Some(traces) = self
+            .request::<Vec<BlockTraceResult>>(
+                trace_method,
+                json!([block, { "tracer": "callTracer", "tracerConfig": { "withLog": true } }]),
+            )
+            .await?
+        else {
+            return Ok(None)
+        };
+
+        if traces.len() != receipts.len() {
+            eyre::bail!(
+                "got {} traces for {} receipts in block {block_id:?}",
+                traces.len(),
+                receipts.len()
+            )
+        }
+
+        traces
+            .into_iter()
+            .zip(receipts)
+            .map(|(trace, receipt)| {
+                if let Some(hash) = trace
+                    .tx_hash
+                    .filter(|hash| *hash != receipt.transaction_hash)
+                {
+                    eyre::bail!("trace of {hash:?} doesn't match {:?}", receipt.transaction_hash)
+                }
+
+                let Some(frame) = trace.result else {
+                    eyre::bail!(
+                        "failed to trace {:?}: {}",
+                        receipt.transaction_hash,
+                        trace.error.unwrap_or_default()
+                    )
+                };
+
+                let is_success = receipt
+                    .status
+                    .map(|status| status == U64::from(1))
+                    .unwrap_or(frame.error.is_none());
+
+                Ok(into_tx_trace(
+                    frame,
+                    receipt.block_number.to(),
+                    receipt.transaction_hash,
+                    receipt.transaction_index.to(),
+                    is_success,
+                ))
+            })
+            .collect::<eyre::Result<Vec<_>>>()
+            .map(Some)
+    }
+
+    /// Replays the transaction with `debug_traceCallMany`, which is supported
+    /// by reth and erigon but not by geth or nethermind. The node executes
+    /// everything before the first skipped transaction, the rest is sent as a
+    /// bundle of calls. As calls, their nonces aren't checked, and the node
+    /// fails the whole bundle if one of them is invalid.
+    pub async fn replay_without(
+        &self,
+        block_number: u64,
+        tx_index: usize,
+        skip: &[usize],
+    ) -> eyre::Result<ReplayedTransaction> {
+        let Some(block) = self
+            .request::<BlockWithTransactions>(
+                "eth_getBlockByNumber",
+                json!([U64::from(block_number), true]),
+            )
+            .await?
+        else {
+            eyre::bail!("block {block_number} not found")
+        };
+
+        let (start, calls) = replay_calls(&block.transactions, tx_index, skip)?;
+
+        let Some(bundles) = self
+            .request::<Vec<Vec<CallFrame>>>(
+                "debug_traceCallMany",
+                json!([
+                    [{ "transactions": calls }],
+                    { "blockNumber": U64::from(block_number), "transactionIndex": start },
+                    { "tracer": "callTracer", "tracerConfig": { "withLog": true } }
+                ]),
+            )
+            .await
+            .wrap_err("replaying transactions requires a node that supports debug_traceCallMany")?
+        else {
+            eyre::bail!("failed to replay transaction {tx_index} of block {block_number}")
+        };
+
+        let Some(frame) = bundles.into_iter().flatten().last() else {
+            eyre::bail!("debug_traceCallMany returned no traces")
+        };
+
+        Ok(ReplayedTransaction {
+            from:               frame.from,
+            is_success:         frame.error.is_none(),
+            logs:               frame.all_logs(),
+            eth_balance_deltas: frame.eth_balance_deltas(),
+        })
+    }
+}
+
+/// The index the replay starts at and the calls to execute from there on, the
+/// last call being the replayed transaction.
+fn replay_calls(
+    transactions: &[BlockTransaction],
+    tx_index: usize,
+    skip: &[usize],
+) -> eyre::Result<(usize, Vec<Value>)> {
+    if tx_index >= transactions.len() {
+        eyre::bail!("block has no transaction at index {tx_index}")
+    }
+
+    let start = skip
+        .iter()
+        .copied()
+        .filter(|idx| *idx < tx_index)
+        .min()
+        .unwrap_or(tx_index);
+
+    let calls = (start..=tx_index)
+        .filter(|idx| !skip.contains(idx) || *idx == tx_index)
+        .map(|idx| transactions[idx].call_request())
+        .collect();
+
+    Ok((start, calls))
+}
+
+#[async_trait::async_trait]
+impl TracingProvider for RpcTracingProvider {
+    async fn eth_call(
+        &self,
+        request: TransactionRequest,
+        block_number: Option<BlockId>,
+        state_overrides: Option<StateOverride>,
+        block_overrides: Option<Box<BlockOverrides>>,
+    ) -> eyre::Result<Bytes> {
+        self.inner
+            .eth_call(request, block_number, state_overrides, block_overrides)
+            .await
+    }
+
+    async fn block_hash_for_id(&self, block_num: u64) -> eyre::Result<Option<B256>> {
+        self.inner.block_hash_for_id(block_num).await
+    }
+
+    async fn best_block_number(&self) -> eyre::Result<u64> {
+        self.inner.best_block_number().await
+    }
+
+    async fn replay_block_transactions(
+        &self,
+        block_id: BlockId,
+    ) -> eyre::Result<Option<Vec<TxTrace>>> {
+        self.trace_block(block_id).await
+    }
+
+    async fn block_receipts(
+        &self,
+        number: BlockNumberOrTag,
+    ) -> eyre::Result<Option<Vec<TransactionReceipt<AnyReceiptEnvelope<Log>>>>> {
+        self.inner.block_receipts(number).await
+    }
+
+    async fn block_and_tx_index(&self, hash: TxHash) -> eyre::Result<(u64, usize)> {
+        self.inner.block_and_tx_index(hash).await
+    }
+
+    async fn replay_transaction_without(
+        &self,
+        block_number: u64,
+        tx_index: usize,
+        skip: Vec<usize>,
+    ) -> eyre::Result<ReplayedTransaction> {
+        self.replay_without(block_number, tx_index, &skip).await
+    }
+
+    async fn header_by_number(&self, number: BlockNumber) -> eyre::Result<Option<Header>> {
+        self.inner.header_by_number(number).await
+    }
+
+    async fn get_storage(
+        &self,
+        block_number: Option<u64>,
+        address: Address,
+        storage_key: B256,
+    ) -> eyre::Result<Option<StorageValue>> {
+        self.inner
+            .get_storage(block_number, address, storage_key)
+            .await
+    }
+
+    async fn get_bytecode(
+        &self,
+        block_number: Option<u64>,
+        address: Address,
+    ) -> eyre::Result<Option<Bytecode>> {
+        self.inner.get_bytecode(block_number, address).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use alloy_primitives::{address, b256, I256, U256};
+    use reth_rpc_types::trace::parity::{Action, CallType};
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::{TcpListener, TcpStream},
+    };
+
+    use super::*;
+
+    /// Serves the recorded response of each method over http
+    async fn serve_fixtures(fixtures: HashMap<&'static str, &'static str>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                tokio::spawn(handle_connection(stream, fixtures.clone()));
+            }
+        });
+
+        url
+    }
+
+    async fn handle_connection(
+        mut stream: TcpStream,
+        fixtures: HashMap<&'static str, &'static str>,
+    ) {
+        let mut buf = Vec::new();
+        let mut chunk = [0u8; 4096];
+
+        loop {
+            let Ok(read) = stream.read(&mut chunk).await else { return };
+            if read == 0 {
+                return
+            }
+            buf.extend_from_slice(&chunk[..read]);
+
+            let Some(header_end) = buf.windows(4).position(|w| w == b"\r\n\r\n") else { continue };
+            let headers = String::from_utf8_lossy(&buf[..header_end]).to_lowercase();
+            let content_length = headers
+                .lines()
+                .find_map(|line| line.strip_prefix("content-length:"))
+                .and_then(|len| len.trim().parse::<usize>().ok())
+                .unwrap_or_default();
+
+            let body_start = header_end + 4;
+            if buf.len() < body_start + content_length {
+                continue
+            }
+
+            let request: Value =
+                serde_json::from_slice(&buf[body_start..body_start + content_length]).unwrap();
+            buf.drain(..body_start + content_length);
+
+            let result = fixtures
+                .get(request["method"].as_str().unwrap())
+                .copied()
+                .unwrap_or("null");
+            let body = format!(r#"{{"jsonrpc":"2.0","id":{},"result":{result}}}"#, request["id"]);
+            let response = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: \
+                 {}\r\n\r\n{body}",
+                body.len()
+            );
+
+            if stream.write_all(response.as_bytes()).await.is_err() {
+                return
+            }
+        }
+    }
+
+    async fn provider() -> RpcTracingProvider {
+        let url = serve_fixtures(HashMap::from([
+            ("debug_traceBlockByNumber", include_str!("fixtures/debug_traceBlockByNumber.json")),
+            ("eth_getBlockReceipts", include_str!("fixtures/eth_getBlockReceipts.json")),
+            ("eth_getBlockByNumber", include_str!("fixtures/eth_getBlockByNumber.json")),
+            ("debug_traceCallMany", include_str!("fixtures/debug_traceCallMany.json")),
+        ]))
+        .await;
+
+        RpcTracingProvider::new(url, 0)
+    }
+
+    #[tokio::test]
+    async fn test_trace_block_from_fixtures() {
+        let traces = provider()
+            .await
+            .replay_block_transactions(BlockId::Number(BlockNumberOrTag::Number(19_000_000)))
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(traces.len(), 3);
+        assert!(traces.iter().all(|trace| trace.block_number == 19_000_000));
+        assert_eq!(
+            traces[1].tx_hash,
+            b256!("2222222222222222222222222222222222222222222222222222222222222222")
+        );
+
+        // the ecrecover call is dropped
+        let tx = &traces[0];
+        assert!(tx.is_success);
+        assert_eq!(tx.tx_index, 0);
+        assert_eq!(tx.trace.len(), 4);
+        assert_eq!(tx.trace.iter().map(|t| t.trace_idx).collect::<Vec<_>>(), vec![0, 1, 2, 3]);
+        assert_eq!(
+            tx.trace
+                .iter()
+                .map(|t| t.trace.trace_address.clone())
+                .collect::<Vec<_>>(),
+            vec![vec![], vec![0], vec![0, 0], vec![1]]
+        );
+        assert_eq!(tx.trace[0].trace.subtraces, 2);
+
+        // the delegate call inherits the msg.sender of the router call
+        let Action::Call(delegate) = &tx.trace[1].trace.action else { panic!("expected call") };
+        assert_eq!(delegate.call_type, CallType::DelegateCall);
+        assert_eq!(tx.trace[1].msg_sender, address!("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"));
+        assert_eq!(tx.trace[2].msg_sender, address!("bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb"));
+
+        assert_eq!(tx.trace[2].logs.len(), 1);
+        assert_eq!(
+            tx.trace[2].logs[0].address,
+            address!("dddddddddddddddddddddddddddddddddddddddd")
+        );
+        assert_eq!(tx.trace[2].logs[0].topics().len(), 3);
+        assert!(matches!(tx.trace[3].trace.action, Action::Create(_)));
+
+        // reverts keep their output
+        let tx = &traces[1];
+        assert!(!tx.is_success);
+        assert_eq!(tx.trace[0].trace.error.as_deref(), Some("execution reverted"));
+        assert!(tx.trace[0].trace.result.is_some());
+
+        // selfdestructs are added after the call they happened in
+        let tx = &traces[2];
+        assert_eq!(tx.trace.len(), 2);
+        assert_eq!(tx.trace[1].trace_idx, 0);
+        assert_eq!(tx.trace[1].trace.trace_address, vec![0]);
+        let Action::Selfdestruct(selfdestruct) = &tx.trace[1].trace.action else {
+            panic!("expected selfdestruct")
+        };
+        assert_eq!(
+            selfdestruct.refund_address,
+            address!("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_replay_without_frontrun() {
+        let replay = provider()
+            .await
+            .replay_transaction_without(19_000_000, 1, vec![0])
+            .await
+            .unwrap();
+
+        assert!(replay.is_success);
+        assert_eq!(replay.from, address!("eeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee"));
+        // the log of the reverted call is dropped
+        assert_eq!(replay.logs.len(), 1);
+        assert_eq!(replay.logs[0].address, address!("dddddddddddddddddddddddddddddddddddddddd"));
+        // the victim sent 1 eth to the router and got 0.4 back, the eth of the
+        // reverted call never moved
+        let victim = address!("eeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee");
+        let router = address!("ffffffffffffffffffffffffffffffffffffffff");
+        assert_eq!(
+            replay.eth_balance_deltas,
+            vec![
+                (victim, I256::from_raw(U256::from(6 * 10u128.pow(17))).wrapping_neg()),
+                (router, I256::from_raw(U256::from(6 * 10u128.pow(17)))),
+            ]
+        );
+        assert_eq!(replay.eth_received(victim), U256::ZERO);
+        assert_eq!(replay.eth_received(router), U256::from(6 * 10u128.pow(17)));
+    }
+
+    #[test]
+    fn test_replay_calls() {
+        let block: BlockWithTransactions =
+            serde_json::from_str(include_str!("fixtures/eth_getBlockByNumber.json")).unwrap();
+
+        let (start, calls) = replay_calls(&block.transactions, 1, &[0]).unwrap();
+        assert_eq!(start, 0);
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0]["from"], "0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee");
+        assert_eq!(calls[0]["gasPrice"], "0x3b9aca00");
+
+        // without skipping anything the node replays everything before the tx
+        let (start, calls) = replay_calls(&block.transactions, 2, &[]).unwrap();
+        assert_eq!(start, 2);
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0]["maxFeePerGas"], "0x77359400");
+        assert!(calls[0].get("gasPrice").is_none());
+
+        // skipped txs after the replayed one don't matter
+        let (start, calls) = replay_calls(&block.transactions, 1, &[0, 2]).unwrap();
+        assert_eq!((start, calls.len()), (0, 1));
+
+        assert!(replay_calls(&block.transactions, 3, &[]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_unknown_block() {
+        let provider = RpcTracingProvider::new(serve_fixtures(HashMap::new()).await, 0);
+
+        assert!(provider
+            .replay_block_transactions(BlockId::Number(BlockNumberOrTag::Number(1)))
+            .await
+            .unwrap()
+            .is_none());
+    }
+}
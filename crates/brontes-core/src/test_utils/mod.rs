@@ -42,14 +42,29 @@ use tokio::{
 use tracing::Level;
 use tracing_subscriber::filter::Directive;
 
-use crate::decoding::parser::TraceParser;
+use crate::{decoding::parser::TraceParser, file_provider::FileTracingProvider};
 #[cfg(not(feature = "local-reth"))]
-use crate::local_provider::LocalProvider;
+use crate::{local_provider::LocalProvider, rpc_provider::RpcTracingProvider};
+
+mod recorded_metadata;
+
+/// Directory of recorded blocks. When set, blocks are traced with a
+/// [`FileTracingProvider`] instead of a node. Defaults to
+/// [`DEFAULT_RECORDINGS_DIR`] once it holds recordings.
+pub const RECORDINGS_ENV: &str = "BRONTES_TEST_RECORDINGS";
+/// Set to `true` to record everything requested from the node into
+/// [`RECORDINGS_ENV`].
+pub const RECORD_ENV: &str = "BRONTES_TEST_RECORD";
+/// The recordings of the blocks used by the tests of the workspace
+pub const DEFAULT_RECORDINGS_DIR: &str =
+    concat!(env!("CARGO_MANIFEST_DIR"), "/../../testing/recordings");
 
 /// Functionality to load all state needed for any testing requirements
 pub struct TraceLoader {
     pub libmdbx:          &'static LibmdbxReadWriter,
     pub tracing_provider: TraceParser<Box<dyn TracingProvider>, LibmdbxReadWriter>,
+    /// traces come from the provider instead of libmdbx, so they are recorded
+    uses_recordings:      bool,
     // store so when we trace we don't get a closed rx error
     _metrics:             UnboundedReceiver<ParserMetricEvents>,
 }
@@ -63,7 +78,7 @@ impl TraceLoader {
         let (a, b) = unbounded_channel();
         let tracing_provider = init_trace_parser(handle, a, libmdbx, 10).await;
 
-        Self { libmdbx, tracing_provider, _metrics: b }
+        Self { libmdbx, tracing_provider, uses_recordings: recordings_dir().is_some(), _metrics: b }
     }
 
     pub fn get_provider(&self) -> Arc<Box<dyn TracingProvider>> {
@@ -74,6 +89,14 @@ impl TraceLoader {
         &self,
         block: u64,
     ) -> Result<(Vec<TxTrace>, Header), TraceLoaderError> {
+        if self.uses_recordings {
+            return self
+                .tracing_provider
+                .trace_block_with_provider(block)
+                .await
+                .ok_or_else(|| TraceLoaderError::BlockTraceError(block))
+        }
+
         if let Some(traces) = self.tracing_provider.clone().execute_block(block).await {
             Ok(traces)
         } else {
@@ -86,11 +109,29 @@ impl TraceLoader {
         }
     }
 
+    /// Metadata of blocks traced from recordings is recorded as well
     pub async fn get_metadata(
         &self,
         block: u64,
         pricing: bool,
     ) -> Result<Metadata, TraceLoaderError> {
+        let Some(dir) = recordings_dir() else { return self.load_metadata(block, pricing).await };
+        let dir = std::path::Path::new(&dir);
+
+        if !is_recording() {
+            return recorded_metadata::read_metadata(dir, block, pricing).map_err(|e| {
+                tracing::error!(%block, error = %e, "failed to read recorded metadata");
+                TraceLoaderError::NoMetadataFound(block)
+            })
+        }
+
+        let metadata = self.load_metadata(block, pricing).await?;
+        recorded_metadata::write_metadata(dir, block, pricing, &metadata)?;
+
+        Ok(metadata)
+    }
+
+    async fn load_metadata(&self, block: u64, pricing: bool) -> Result<Metadata, TraceLoaderError> {
         if pricing {
             if let Ok(res) = self.test_metadata_with_pricing(block, USDT_ADDRESS) {
                 Ok(res)
@@ -546,46 +587,85 @@ pub fn init_tracing() {
     brontes_tracing::init(layers);
 }
 
-#[cfg(feature = "local-reth")]
 pub async fn init_trace_parser(
     handle: Handle,
     metrics_tx: UnboundedSender<ParserMetricEvents>,
     libmdbx: &'static LibmdbxReadWriter,
     max_tasks: u32,
 ) -> TraceParser<Box<dyn TracingProvider>, LibmdbxReadWriter> {
-    let executor = brontes_types::BrontesTaskManager::new(handle.clone(), true);
-
-    let db_path = env::var("DB_PATH").expect("No DB_PATH in .env");
-    let db_path = std::path::Path::new(&db_path);
-    let mut static_files = db_path.to_path_buf();
-    static_files.pop();
-    static_files.push("static_files");
-
-    let client = TracingClient::new_with_db(
-        get_reth_db_handle(),
-        max_tasks as u64,
-        executor.executor(),
-        static_files,
-    );
-    handle.spawn(executor);
-    let tracer = Box::new(client) as Box<dyn TracingProvider>;
+    let tracer = init_tracer(handle, max_tasks);
 
     TraceParser::new(libmdbx, Arc::new(tracer), Arc::new(metrics_tx)).await
 }
 
+/// The provider used by the tests, see [`RECORDINGS_ENV`]
+#[cfg(feature = "local-reth")]
+pub fn init_tracer(handle: Handle, max_tasks: u32) -> Box<dyn TracingProvider> {
+    test_tracer(|_| {
+        let executor = brontes_types::BrontesTaskManager::new(handle.clone(), true);
+
+        let db_path = env::var("DB_PATH").expect("No DB_PATH in .env");
+        let db_path = std::path::Path::new(&db_path);
+        let mut static_files = db_path.to_path_buf();
+        static_files.pop();
+        static_files.push("static_files");
+
+        let client = TracingClient::new_with_db(
+            get_reth_db_handle(),
+            max_tasks as u64,
+            executor.executor(),
+            static_files,
+        );
+        handle.spawn(executor);
+        Box::new(client)
+    })
+}
+
+/// The provider used by the tests, see [`RECORDINGS_ENV`]
 #[cfg(not(feature = "local-reth"))]
-pub async fn init_trace_parser(
-    _handle: Handle,
-    metrics_tx: UnboundedSender<ParserMetricEvents>,
-    libmdbx: &'static LibmdbxReadWriter,
-    _max_tasks: u32,
-) -> TraceParser<Box<dyn TracingProvider>, LibmdbxReadWriter> {
-    let db_endpoint = env::var("RETH_ENDPOINT").expect("No db Endpoint in .env");
-    let db_port = env::var("RETH_PORT").expect("No DB port.env");
-    let url = format!("{db_endpoint}:{db_port}");
-    let tracer = Box::new(LocalProvider::new(url, 15)) as Box<dyn TracingProvider>;
+pub fn init_tracer(_handle: Handle, _max_tasks: u32) -> Box<dyn TracingProvider> {
+    test_tracer(|recording| {
+        let db_endpoint = env::var("RETH_ENDPOINT").expect("No db Endpoint in .env");
+        let db_port = env::var("RETH_PORT").expect("No DB port.env");
+        let url = format!("{db_endpoint}:{db_port}");
+
+        // the local provider can't trace blocks, which the recordings need
+        if recording {
+            Box::new(RpcTracingProvider::new(url, 15))
+        } else {
+            Box::new(LocalProvider::new(url, 15))
+        }
+    })
+}
 
-    TraceParser::new(libmdbx, Arc::new(tracer), Arc::new(metrics_tx)).await
+fn recordings_dir() -> Option<String> {
+    if let Some(dir) = env::var(RECORDINGS_ENV).ok().filter(|dir| !dir.is_empty()) {
+        return Some(dir)
+    }
+
+    let default = std::path::Path::new(DEFAULT_RECORDINGS_DIR);
+    (is_recording() || default.join("index.json").exists())
+        .then(|| DEFAULT_RECORDINGS_DIR.to_string())
+}
+
+fn is_recording() -> bool {
+    env::var(RECORD_ENV).is_ok_and(|record| record == "true" || record == "1")
+}
+
+/// Builds the provider for tests from [`RECORDINGS_ENV`] and [`RECORD_ENV`].
+/// `node` is only called if the node is needed and is told whether the
+/// requests are being recorded.
+fn test_tracer(node: impl FnOnce(bool) -> Box<dyn TracingProvider>) -> Box<dyn TracingProvider> {
+    let Some(dir) = recordings_dir() else { return node(false) };
+
+    if !is_recording() {
+        return Box::new(FileTracingProvider::new(dir))
+    }
+
+    Box::new(
+        FileTracingProvider::record(&dir, node(true))
+            .unwrap_or_else(|e| panic!("failed to create recordings dir {}, err={}", dir, e)),
+    )
 }
 
 #[cfg(feature = "local-clickhouse")]
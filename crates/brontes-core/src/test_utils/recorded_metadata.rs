@@ -0,0 +1,147 @@
+//! The metadata of the blocks traced from recordings, stored next to them in
+//! `metadata/{block}_{dex,no_dex}.json` so that replaying doesn't need
+//! clickhouse to fill in what's missing from the test db.
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use alloy_primitives::{Address, Bytes, TxHash, U256};
+use brontes_types::{
+    db::{
+        builder::BuilderInfo,
+        cex::{quotes::CexPriceMapRedefined, trades::CexTradeMapRedefined},
+        dex::{DexPrices, DexQuotes},
+        metadata::{BlockMetadata, Metadata},
+    },
+    pair::Pair,
+    FastHashSet,
+};
+use malachite::Rational;
+use redefined::RedefinedConvert;
+use reth_db::table::{Compress, Decompress};
+use serde::{Deserialize, Serialize};
+
+/// The cex maps are stored in their libmdbx encoding as they don't roundtrip
+/// through json
+#[derive(Debug, Serialize, Deserialize)]
+struct RecordedMetadata {
+    block_num:              u64,
+    block_hash:             U256,
+    block_timestamp:        u64,
+    relay_timestamp:        Option<u64>,
+    p2p_timestamp:          Option<u64>,
+    proposer_fee_recipient: Option<Address>,
+    proposer_mev_reward:    Option<u128>,
+    /// as `numerator/denominator`
+    eth_prices:             String,
+    private_flow:           FastHashSet<TxHash>,
+    cex_quotes:             Bytes,
+    /// the prices of every transaction, pairs can't be json keys
+    dex_quotes:             Option<Vec<Option<Vec<(Pair, DexPrices)>>>>,
+    builder_info:           Option<BuilderInfo>,
+    cex_trades:             Option<Bytes>,
+}
+
+impl From<&Metadata> for RecordedMetadata {
+    fn from(metadata: &Metadata) -> Self {
+        let block = &metadata.block_metadata;
+
+        Self {
+            block_num:              block.block_num,
+            block_hash:             block.block_hash,
+            block_timestamp:        block.block_timestamp,
+            relay_timestamp:        block.relay_timestamp,
+            p2p_timestamp:          block.p2p_timestamp,
+            proposer_fee_recipient: block.proposer_fee_recipient,
+            proposer_mev_reward:    block.proposer_mev_reward,
+            eth_prices:             block.eth_prices.to_string(),
+            private_flow:           block.private_flow.clone(),
+            cex_quotes:             CexPriceMapRedefined::from_source(metadata.cex_quotes.clone())
+                .compress()
+                .into(),
+            dex_quotes:             metadata.dex_quotes.as_ref().map(|quotes| {
+                quotes
+                    .0
+                    .iter()
+                    .map(|prices| {
+                        prices
+                            .as_ref()
+                            .map(|prices| prices.clone().into_iter().collect())
+                    })
+                    .collect()
+            }),
+            builder_info:           metadata.builder_info.clone(),
+            cex_trades:             metadata
+                .cex_trades
+                .clone()
+                .map(|trades| CexTradeMapRedefined::from_source(trades).compress().into()),
+        }
+    }
+}
+
+impl TryFrom<RecordedMetadata> for Metadata {
+    type Error = eyre::Report;
+
+    fn try_from(recorded: RecordedMetadata) -> eyre::Result<Self> {
+        let eth_prices = recorded
+            .eth_prices
+            .parse::<Rational>()
+            .map_err(|_| eyre::eyre!("invalid eth price {}", recorded.eth_prices))?;
+
+        Ok(Metadata {
+            block_metadata: BlockMetadata::new(
+                recorded.block_num,
+                recorded.block_hash,
+                recorded.block_timestamp,
+                recorded.relay_timestamp,
+                recorded.p2p_timestamp,
+                recorded.proposer_fee_recipient,
+                recorded.proposer_mev_reward,
+                eth_prices,
+                recorded.private_flow,
+            ),
+            cex_quotes:     CexPriceMapRedefined::decompress(recorded.cex_quotes)?.to_source(),
+            dex_quotes:     recorded.dex_quotes.map(|quotes| {
+                DexQuotes(
+                    quotes
+                        .into_iter()
+                        .map(|prices| prices.map(|prices| prices.into_iter().collect()))
+                        .collect(),
+                )
+            }),
+            builder_info:   recorded.builder_info,
+            cex_trades:     recorded
+                .cex_trades
+                .map(|trades| CexTradeMapRedefined::decompress(trades).map(|t| t.to_source()))
+                .transpose()?,
+        })
+    }
+}
+
+fn metadata_path(dir: &Path, block: u64, pricing: bool) -> PathBuf {
+    let kind = if pricing { "dex" } else { "no_dex" };
+    dir.join("metadata").join(format!("{block}_{kind}.json"))
+}
+
+pub(super) fn read_metadata(dir: &Path, block: u64, pricing: bool) -> eyre::Result<Metadata> {
+    let path = metadata_path(dir, block, pricing);
+    let bytes = fs::read(&path).map_err(|e| {
+        eyre::eyre!("metadata not recorded in {path:?}, re-run the test in record mode: {e}")
+    })?;
+
+    serde_json::from_slice::<RecordedMetadata>(&bytes)?.try_into()
+}
+
+pub(super) fn write_metadata(
+    dir: &Path,
+    block: u64,
+    pricing: bool,
+    metadata: &Metadata,
+) -> eyre::Result<()> {
+    let path = metadata_path(dir, block, pricing);
+    fs::create_dir_all(dir.join("metadata"))?;
+    fs::write(path, serde_json::to_vec_pretty(&RecordedMetadata::from(metadata))?)?;
+
+    Ok(())
+}
@@ -8,6 +8,7 @@ use brontes_types::db::{block_times::BlockTimes, cex::CexSymbols};
 use brontes_types::{
     db::{
         address_to_protocol_info::ProtocolInfoClickhouse,
+        analysis_rollup::BlockAnalysisRollup,
         block_analysis::BlockAnalysis,
         builder::BuilderInfo,
         cex::{
@@ -18,6 +19,7 @@ use brontes_types::{
         dex::{DexQuotes, DexQuotesWithBlockNumber},
         metadata::{BlockMetadata, Metadata},
         normalized_actions::TransactionRoot,
+        pool_lvr::BlockPoolLvr,
         searcher::SearcherInfo,
         token_info::{TokenInfo, TokenInfoWithAddress},
     },
@@ -63,6 +65,26 @@ use crate::{
 const SECONDS_TO_US: f64 = 1_000_000.0;
 const MAX_MARKOUT_TIME: f64 = 300.0;
 
+/// tables that hold results keyed by block number
+const BLOCK_RESULT_TABLES: [&str; 16] = [
+    "brontes.dex_price_mapping",
+    "brontes.block_analysis",
+    "brontes.pool_lvr",
+    "brontes.tree",
+    "mev.mev_blocks",
+    "mev.bundle_header",
+    "mev.searcher_tx",
+    "mev.failed_attempts",
+    "mev.snipes",
+    "mev.cex_dex",
+    "mev.cex_dex_quotes",
+    "mev.liquidations",
+    "mev.jit_sandwich",
+    "mev.jit",
+    "mev.sandwiches",
+    "mev.atomic_arbs",
+];
+
 #[derive(Clone)]
 pub struct Clickhouse {
     pub tip:                 bool,
@@ -185,6 +207,10 @@ impl Clickhouse {
                         tx.send(vec![(s, self.tip, self.run_id).into()])?
                     }
                     BundleData::Unknown(s) => tx.send(vec![(s, self.tip, self.run_id).into()])?,
+                    BundleData::FailedAttempt(s) => {
+                        tx.send(vec![(s, self.tip, self.run_id).into()])?
+                    }
+                    BundleData::Sniping(s) => tx.send(vec![(s, self.tip, self.run_id).into()])?,
                 };
 
                 Ok(()) as eyre::Result<()>
@@ -277,10 +303,55 @@ impl Clickhouse {
         Ok(())
     }
 
+    pub async fn analysis_rollups(&self, rollups: Vec<BlockAnalysisRollup>) -> eyre::Result<()> {
+        if let Some(tx) = self.buffered_insert_tx.as_ref() {
+            tx.send(
+                rollups
+                    .iter()
+                    .flat_map(BlockAnalysisRollup::into_rows)
+                    .map(|row| (row, self.tip, self.run_id))
+                    .map(Into::into)
+                    .collect(),
+            )?
+        };
+
+        Ok(())
+    }
+
+    pub async fn pool_lvr(&self, pool_lvr: BlockPoolLvr) -> eyre::Result<()> {
+        if let Some(tx) = self.buffered_insert_tx.as_ref() {
+            tx.send(
+                pool_lvr
+                    .pools
+                    .into_iter()
+                    .map(|pool| (pool, self.tip, self.run_id).into())
+                    .collect(),
+            )?
+        };
+
+        Ok(())
+    }
+
     pub async fn save_traces(&self, _block: u64, _traces: Vec<TxTrace>) -> eyre::Result<()> {
         Ok(())
     }
 
+    pub async fn delete_block(&self, block_number: u64) -> eyre::Result<()> {
+        for table in BLOCK_RESULT_TABLES {
+            self.client
+                .execute_remote(
+                    &format!(
+                        "ALTER TABLE {table} ON CLUSTER eth_cluster0 DELETE WHERE block_number = \
+                         {block_number}"
+                    ),
+                    &(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
     async fn query_many_with_retry<Q, P>(
         &self,
         query: impl AsRef<str> + Send,
@@ -836,18 +907,26 @@ impl ClickhouseCritTableCount {
 mod tests {
     use std::sync::Arc;
 
-    use alloy_primitives::{hex, Uint};
+    use alloy_primitives::{hex, Uint, B256};
     use brontes_classifier::test_utils::ClassifierTestUtils;
     use brontes_types::{
-        db::{cex::CexExchange, dex::DexPrices, DbDataWithRunId},
+        db::{
+            analysis_rollup::{BlockAnalysisRollup, RollupWindow},
+            cex::CexExchange,
+            dex::DexPrices,
+            pool_lvr::PoolLvr,
+            DbDataWithRunId,
+        },
         init_thread_pools,
         mev::{
-            ArbDetails, AtomicArb, BundleHeader, CexDex, CexDexQuote, JitLiquidity,
-            JitLiquiditySandwich, Liquidation, OptimisticTrade, PossibleMev, PossibleMevCollection,
-            Sandwich,
+            ArbDetails, AtomicArb, BundleHeader, CexDex, CexDexQuote, FailedAttempt, JitLiquidity,
+            JitLiquiditySandwich, Liquidation, MevType, OptimisticTrade, PossibleMev,
+            PossibleMevCollection, Sandwich, Sniping,
         },
         normalized_actions::{
-            NormalizedBurn, NormalizedLiquidation, NormalizedMint, NormalizedSwap,
+            NormalizedBurn, NormalizedDeposit, NormalizedLiquidation, NormalizedLoan,
+            NormalizedMint, NormalizedOracleUpdate, NormalizedRepayment, NormalizedSwap,
+            NormalizedWithdraw,
         },
         pair::Pair,
         FastHashMap, GasDetails,
@@ -1042,6 +1121,12 @@ mod tests {
         let case0 = Liquidation {
             liquidation_swaps: vec![swap],
             liquidations: vec![liquidation],
+            loans: vec![NormalizedLoan::default()],
+            repayments: vec![NormalizedRepayment::default()],
+            deposits: vec![NormalizedDeposit::default()],
+            withdrawals: vec![NormalizedWithdraw::default()],
+            oracle_update: Some(NormalizedOracleUpdate::default()),
+            txs_after_oracle_update: Some(0),
             gas_details,
             ..Liquidation::default()
         };
@@ -1051,6 +1136,44 @@ mod tests {
             .unwrap();
     }
 
+    async fn failed_attempt(db: &ClickhouseTestClient<BrontesClickhouseTables>) {
+        let case0 = FailedAttempt {
+            attempted_mev_type: MevType::AtomicArb,
+            protocols: vec![Protocol::UniswapV2, Protocol::UniswapV3],
+            ..FailedAttempt::default()
+        };
+
+        db.insert_one::<MevFailed_Attempts>(&DbDataWithRunId::new_with_run_id(case0, 0))
+            .await
+            .unwrap();
+    }
+
+    async fn sniping(db: &ClickhouseTestClient<BrontesClickhouseTables>) {
+        let swap = NormalizedSwap {
+            protocol: Protocol::UniswapV2,
+            amount_in: Rational::from(1),
+            amount_out: Rational::from(1000),
+            ..NormalizedSwap::default()
+        };
+
+        let case0 = Sniping {
+            protocol: Protocol::UniswapV2,
+            snipe_tx_hashes: vec![B256::ZERO],
+            snipe_swaps: vec![vec![swap.clone()]],
+            snipe_gas_details: vec![GasDetails::default()],
+            exit_tx_hashes: vec![B256::ZERO],
+            exit_swaps: vec![vec![swap]],
+            exit_gas_details: vec![GasDetails::default()],
+            tokens_held: 500.0,
+            markout_blocks: 10,
+            ..Sniping::default()
+        };
+
+        db.insert_one::<MevSnipes>(&DbDataWithRunId::new_with_run_id(case0, 0))
+            .await
+            .unwrap();
+    }
+
     async fn bundle_header(db: &ClickhouseTestClient<BrontesClickhouseTables>) {
         let case0 = BundleHeader::default();
 
@@ -1116,6 +1239,32 @@ mod tests {
             .unwrap();
     }
 
+    async fn analysis_rollups(db: &ClickhouseTestClient<BrontesClickhouseTables>) {
+        let rollup = BlockAnalysisRollup::from_block(
+            RollupWindow::Daily,
+            1_700_000_000,
+            &BlockAnalysis::default(),
+        );
+
+        let rows = rollup
+            .into_rows()
+            .into_iter()
+            .map(|row| DbDataWithRunId::new_with_run_id(row, 0))
+            .collect::<Vec<_>>();
+
+        db.insert_many::<BrontesAnalysis_Rollups>(&rows)
+            .await
+            .unwrap();
+    }
+
+    async fn pool_lvr(db: &ClickhouseTestClient<BrontesClickhouseTables>) {
+        let case0 = PoolLvr::default();
+
+        db.insert_one::<BrontesPool_Lvr>(&DbDataWithRunId::new_with_run_id(case0, 0))
+            .await
+            .unwrap();
+    }
+
     async fn tree(db: &ClickhouseTestClient<BrontesClickhouseTables>) {
         let tree = load_tree().await;
 
@@ -1139,6 +1288,8 @@ mod tests {
         sandwich(database).await;
         bundle_header(database).await;
         liquidations(database).await;
+        failed_attempt(database).await;
+        sniping(database).await;
         jit_sandwich(database).await;
         jit(database).await;
         cex_dex(database).await;
@@ -1148,6 +1299,8 @@ mod tests {
         token_info(database).await;
         tree(database).await;
         block_analysis(database).await;
+        analysis_rollups(database).await;
+        pool_lvr(database).await;
     }
 
     #[brontes_macros::test]
@@ -1,8 +1,9 @@
 use brontes_types::{
     db::{
-        address_to_protocol_info::ProtocolInfoClickhouse, block_analysis::BlockAnalysis,
-        dex::DexQuotesWithBlockNumber, normalized_actions::TransactionRoot,
-        token_info::TokenInfoWithAddress, DbDataWithRunId, RunId,
+        address_to_protocol_info::ProtocolInfoClickhouse, analysis_rollup::AnalysisRollupRow,
+        block_analysis::BlockAnalysis, dex::DexQuotesWithBlockNumber,
+        normalized_actions::TransactionRoot, pool_lvr::PoolLvr, token_info::TokenInfoWithAddress,
+        DbDataWithRunId, RunId,
     },
     mev::*,
 };
@@ -14,9 +15,13 @@ clickhouse_dbms!(
     [
         BrontesDex_Price_Mapping,
         BrontesBlock_Analysis,
+        BrontesAnalysis_Rollups,
+        BrontesPool_Lvr,
         MevMev_Blocks,
         MevBundle_Header,
         MevSearcher_Tx,
+        MevFailed_Attempts,
+        MevSnipes,
         MevCex_Dex_Quotes,
         MevCex_Dex,
         MevLiquidations,
@@ -55,6 +60,20 @@ remote_clickhouse_table!(
     "crates/brontes-database/brontes-db/src/clickhouse/tables/"
 );
 
+remote_clickhouse_table!(
+    BrontesClickhouseTables,
+    [Brontes, Analysis_Rollups],
+    DbDataWithRunId<AnalysisRollupRow>,
+    "crates/brontes-database/brontes-db/src/clickhouse/tables/"
+);
+
+remote_clickhouse_table!(
+    BrontesClickhouseTables,
+    [Brontes, Pool_Lvr],
+    DbDataWithRunId<PoolLvr>,
+    "crates/brontes-database/brontes-db/src/clickhouse/tables/"
+);
+
 remote_clickhouse_table!(
     BrontesClickhouseTables,
     [Mev, Mev_Blocks],
@@ -76,6 +95,20 @@ remote_clickhouse_table!(
     "crates/brontes-database/brontes-db/src/clickhouse/tables/"
 );
 
+remote_clickhouse_table!(
+    BrontesClickhouseTables,
+    [Mev, Failed_Attempts],
+    DbDataWithRunId<FailedAttempt>,
+    "crates/brontes-database/brontes-db/src/clickhouse/tables/"
+);
+
+remote_clickhouse_table!(
+    BrontesClickhouseTables,
+    [Mev, Snipes],
+    DbDataWithRunId<Sniping>,
+    "crates/brontes-database/brontes-db/src/clickhouse/tables/"
+);
+
 remote_clickhouse_table!(
     BrontesClickhouseTables,
     [Mev, Cex_Dex],
@@ -233,6 +266,8 @@ db_types!(
     (MevBlock, MevMev_Blocks, true),
     (BundleHeader, MevBundle_Header, true),
     (SearcherTx, MevSearcher_Tx, true),
+    (FailedAttempt, MevFailed_Attempts, true),
+    (Sniping, MevSnipes, true),
     (CexDex, MevCex_Dex, true),
     (CexDexQuote, MevCex_Dex_Quotes, true),
     (Liquidation, MevLiquidations, true),
@@ -244,5 +279,7 @@ db_types!(
     (ProtocolInfoClickhouse, EthereumPools, false),
     (TransactionRoot, BrontesTree, true),
     (BlockAnalysis, BrontesBlock_Analysis, true),
+    (AnalysisRollupRow, BrontesAnalysis_Rollups, true),
+    (PoolLvr, BrontesPool_Lvr, true),
     (RunId, BrontesRun_Id, false)
 );
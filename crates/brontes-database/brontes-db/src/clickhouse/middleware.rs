@@ -1,18 +1,22 @@
 use std::sync::Arc;
 
-use alloy_primitives::Address;
+use alloy_primitives::{Address, B256};
 use brontes_types::{
     db::{
         address_metadata::AddressMetadata,
         address_to_protocol_info::ProtocolInfo,
+        analysis_rollup::{BlockAnalysisRollup, RollupWindow},
         block_analysis::BlockAnalysis,
         builder::BuilderInfo,
         dex::DexQuotes,
+        fork_detection::ForkDetection,
         metadata::Metadata,
         mev_block::MevBlockWithClassified,
+        pool_lvr::BlockPoolLvr,
         searcher::SearcherInfo,
         token_info::TokenInfoWithAddress,
         traits::{DBWriter, LibmdbxReader, ProtocolCreatedRange},
+        uniswap_v4::UniswapV4PoolKey,
     },
     mev::{Bundle, MevBlock},
     normalized_actions::Action,
@@ -54,6 +58,18 @@ impl<I: DBWriter + Send + Sync> DBWriter for ClickhouseMiddleware<I> {
         self.client.block_analysis(block_analysis).await
     }
 
+    async fn write_analysis_rollups(&self, rollups: Vec<BlockAnalysisRollup>) -> eyre::Result<()> {
+        self.client.analysis_rollups(rollups.clone()).await?;
+
+        self.inner().write_analysis_rollups(rollups).await
+    }
+
+    async fn write_pool_lvr(&self, pool_lvr: BlockPoolLvr) -> eyre::Result<()> {
+        self.client.pool_lvr(pool_lvr.clone()).await?;
+
+        self.inner().write_pool_lvr(pool_lvr).await
+    }
+
     async fn write_dex_quotes(
         &self,
         block_number: u64,
@@ -166,6 +182,12 @@ impl<I: DBWriter + Send + Sync> DBWriter for ClickhouseMiddleware<I> {
 
         self.inner().save_traces(block, traces).await
     }
+
+    async fn delete_block(&self, block_number: u64) -> eyre::Result<()> {
+        self.client.delete_block(block_number).await?;
+
+        self.inner().delete_block(block_number).await
+    }
 }
 
 impl<I: LibmdbxInit> LibmdbxInit for ClickhouseMiddleware<I> {
@@ -329,6 +351,45 @@ impl<I: LibmdbxInit> LibmdbxReader for ClickhouseMiddleware<I> {
         todo!("Joe");
     }
 
+    fn try_fetch_analysis_rollups(
+        &self,
+        window: RollupWindow,
+        start: u64,
+        end: u64,
+    ) -> eyre::Result<Vec<BlockAnalysisRollup>> {
+        self.inner.try_fetch_analysis_rollups(window, start, end)
+    }
+
+    fn fetch_all_analysis_rollups(&self) -> eyre::Result<Vec<BlockAnalysisRollup>> {
+        self.inner.fetch_all_analysis_rollups()
+    }
+
+    fn try_fetch_pool_lvr(
+        &self,
+        start_block: Option<u64>,
+        end_block: u64,
+    ) -> eyre::Result<Vec<BlockPoolLvr>> {
+        self.inner.try_fetch_pool_lvr(start_block, end_block)
+    }
+
+    fn fetch_all_pool_lvr(&self, start_block: Option<u64>) -> eyre::Result<Vec<BlockPoolLvr>> {
+        self.inner.fetch_all_pool_lvr(start_block)
+    }
+
+    fn try_fetch_bundles_by_address(
+        &self,
+        address: Address,
+        start_block: Option<u64>,
+        end_block: Option<u64>,
+    ) -> eyre::Result<Vec<Bundle>> {
+        self.inner
+            .try_fetch_bundles_by_address(address, start_block, end_block)
+    }
+
+    fn try_fetch_bundles_by_tx_hash(&self, tx_hash: B256) -> eyre::Result<Vec<Bundle>> {
+        self.inner.try_fetch_bundles_by_tx_hash(tx_hash)
+    }
+
     fn get_metadata(&self, block_num: u64, quote_asset: Address) -> eyre::Result<Metadata> {
         self.inner.get_metadata(block_num, quote_asset)
     }
@@ -351,6 +412,21 @@ impl<I: LibmdbxInit> LibmdbxReader for ClickhouseMiddleware<I> {
         self.inner.fetch_all_address_metadata()
     }
 
+    fn fetch_fork_detections(&self) -> eyre::Result<Vec<(Address, ForkDetection)>> {
+        self.inner.fetch_fork_detections()
+    }
+
+    fn try_fetch_fork_detection(&self, pool: Address) -> eyre::Result<Option<ForkDetection>> {
+        self.inner.try_fetch_fork_detection(pool)
+    }
+
+    fn try_fetch_uniswap_v4_pool_key(
+        &self,
+        pool: Address,
+    ) -> eyre::Result<Option<UniswapV4PoolKey>> {
+        self.inner.try_fetch_uniswap_v4_pool_key(pool)
+    }
+
     fn get_dex_quotes(&self, block: u64) -> eyre::Result<DexQuotes> {
         self.inner.get_dex_quotes(block)
     }
@@ -406,6 +482,14 @@ impl<I: DBWriter + Send + Sync> DBWriter for ReadOnlyMiddleware<I> {
         self.client.block_analysis(block_analysis).await
     }
 
+    async fn write_analysis_rollups(&self, rollups: Vec<BlockAnalysisRollup>) -> eyre::Result<()> {
+        self.client.analysis_rollups(rollups).await
+    }
+
+    async fn write_pool_lvr(&self, pool_lvr: BlockPoolLvr) -> eyre::Result<()> {
+        self.client.pool_lvr(pool_lvr).await
+    }
+
     async fn write_dex_quotes(
         &self,
         block_number: u64,
@@ -490,6 +574,10 @@ impl<I: DBWriter + Send + Sync> DBWriter for ReadOnlyMiddleware<I> {
     async fn save_traces(&self, block: u64, traces: Vec<TxTrace>) -> eyre::Result<()> {
         self.client.save_traces(block, traces.clone()).await
     }
+
+    async fn delete_block(&self, block_number: u64) -> eyre::Result<()> {
+        self.client.delete_block(block_number).await
+    }
 }
 
 impl<I: LibmdbxInit> LibmdbxInit for ReadOnlyMiddleware<I> {
@@ -647,6 +735,45 @@ impl<I: LibmdbxInit> LibmdbxReader for ReadOnlyMiddleware<I> {
         todo!("Joe");
     }
 
+    fn try_fetch_analysis_rollups(
+        &self,
+        window: RollupWindow,
+        start: u64,
+        end: u64,
+    ) -> eyre::Result<Vec<BlockAnalysisRollup>> {
+        self.inner.try_fetch_analysis_rollups(window, start, end)
+    }
+
+    fn fetch_all_analysis_rollups(&self) -> eyre::Result<Vec<BlockAnalysisRollup>> {
+        self.inner.fetch_all_analysis_rollups()
+    }
+
+    fn try_fetch_pool_lvr(
+        &self,
+        start_block: Option<u64>,
+        end_block: u64,
+    ) -> eyre::Result<Vec<BlockPoolLvr>> {
+        self.inner.try_fetch_pool_lvr(start_block, end_block)
+    }
+
+    fn fetch_all_pool_lvr(&self, start_block: Option<u64>) -> eyre::Result<Vec<BlockPoolLvr>> {
+        self.inner.fetch_all_pool_lvr(start_block)
+    }
+
+    fn try_fetch_bundles_by_address(
+        &self,
+        address: Address,
+        start_block: Option<u64>,
+        end_block: Option<u64>,
+    ) -> eyre::Result<Vec<Bundle>> {
+        self.inner
+            .try_fetch_bundles_by_address(address, start_block, end_block)
+    }
+
+    fn try_fetch_bundles_by_tx_hash(&self, tx_hash: B256) -> eyre::Result<Vec<Bundle>> {
+        self.inner.try_fetch_bundles_by_tx_hash(tx_hash)
+    }
+
     fn get_metadata(&self, block_num: u64, quote_asset: Address) -> eyre::Result<Metadata> {
         self.inner.get_metadata(block_num, quote_asset)
     }
@@ -669,6 +796,21 @@ impl<I: LibmdbxInit> LibmdbxReader for ReadOnlyMiddleware<I> {
         self.inner.fetch_all_address_metadata()
     }
 
+    fn fetch_fork_detections(&self) -> eyre::Result<Vec<(Address, ForkDetection)>> {
+        self.inner.fetch_fork_detections()
+    }
+
+    fn try_fetch_fork_detection(&self, pool: Address) -> eyre::Result<Option<ForkDetection>> {
+        self.inner.try_fetch_fork_detection(pool)
+    }
+
+    fn try_fetch_uniswap_v4_pool_key(
+        &self,
+        pool: Address,
+    ) -> eyre::Result<Option<UniswapV4PoolKey>> {
+        self.inner.try_fetch_uniswap_v4_pool_key(pool)
+    }
+
     fn get_dex_quotes(&self, block: u64) -> eyre::Result<DexQuotes> {
         self.inner.get_dex_quotes(block)
     }
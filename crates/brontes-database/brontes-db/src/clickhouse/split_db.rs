@@ -110,6 +110,8 @@ impl ClickhouseBuffered {
             (MevCex_Dex_Quotes, CexDexQuote),
             (MevCex_Dex, CexDex),
             (MevSearcher_Tx, SearcherTx),
+            (MevFailed_Attempts, FailedAttempt),
+            (MevSnipes, Sniping),
             (MevJit, JitLiquidity),
             (MevJit_Sandwich, JitLiquiditySandwich),
             (MevSandwiches, Sandwich),
@@ -120,6 +122,8 @@ impl ClickhouseBuffered {
             (EthereumPools, ProtocolInfoClickhouse),
             (BrontesTree, TransactionRoot),
             (BrontesBlock_Analysis, BlockAnalysis),
+            (BrontesAnalysis_Rollups, AnalysisRollupRow),
+            (BrontesPool_Lvr, PoolLvr),
             (BrontesRun_Id, RunId)
         );
 
@@ -0,0 +1,91 @@
+CREATE TABLE brontes.analysis_rollups ON CLUSTER eth_cluster0
+(
+    `window` String,
+    `window_start` UInt64,
+    `mev_type` String,
+    `start_block` UInt64,
+    `end_block` UInt64,
+    `block_count` UInt64,
+    `bundle_count` UInt64,
+    `total_profit` Float64,
+    `total_revenue` Float64,
+    `average_profit_margin` Float64,
+    `searcher_eoa_profit` Nested (
+        `address` String,
+        `amt` Float64
+    ),
+    `searcher_eoa_revenue` Nested (
+        `address` String,
+        `amt` Float64
+    ),
+    `mev_contract_profit` Nested (
+        `address` String,
+        `amt` Float64
+    ),
+    `mev_contract_revenue` Nested (
+        `address` String,
+        `amt` Float64
+    ),
+    `fund_profit` Nested (
+        `fund` String,
+        `amt` Float64
+    ),
+    `fund_revenue` Nested (
+        `fund` String,
+        `amt` Float64
+    ),
+    `arbed_pool_profit` Nested (
+        `address` String,
+        `amt` Float64
+    ),
+    `arbed_pool_revenue` Nested (
+        `address` String,
+        `amt` Float64
+    ),
+    `arbed_pair_profit` Nested (
+        `pair` Tuple(Tuple(String, String), Tuple(String, String)),
+        `amt` Float64
+    ),
+    `arbed_pair_revenue` Nested (
+        `pair` Tuple(Tuple(String, String), Tuple(String, String)),
+        `amt` Float64
+    ),
+    `arbed_dex_profit` Nested (
+        `dex` String,
+        `amt` Float64
+    ),
+    `arbed_dex_revenue` Nested (
+        `dex` String,
+        `amt` Float64
+    ),
+    `biggest_arb_profit` Nullable(String),
+    `biggest_arb_profit_amt` Nullable(Float64),
+    `biggest_arb_revenue` Nullable(String),
+    `biggest_arb_revenue_amt` Nullable(Float64),
+
+    -- only set on the liquidation rows
+    `liquidated_tokens_profit` Nested (
+        `token` Tuple(String, String),
+        `amt` Float64
+    ),
+    `liquidated_tokens_revenue` Nested (
+        `token` Tuple(String, String),
+        `amt` Float64
+    ),
+
+    -- window totals, repeated for each mev type
+    `total_usd_liquidated` Float64,
+    `builder_profit_usd` Float64,
+    `builder_profit_eth` Float64,
+    `builder_revenue_usd` Float64,
+    `builder_revenue_eth` Float64,
+    `builder_mev_profit_usd` Float64,
+    `builder_mev_profit_eth` Float64,
+    `proposer_profit_usd` Float64,
+    `proposer_profit_eth` Float64,
+
+    `run_id` UInt64
+)
+ENGINE = ReplicatedReplacingMergeTree('/clickhouse/eth_cluster0/tables/all/brontes/analysis_rollups', '{replica}', `run_id`)
+PRIMARY KEY (`window`, `window_start`, `mev_type`)
+ORDER BY (`window`, `window_start`, `mev_type`)
@@ -0,0 +1,12 @@
+CREATE TABLE mev.failed_attempts ON CLUSTER eth_cluster0
+(
+    `tx_hash` String,
+    `block_number` UInt64,
+    `attempted_mev_type` String,
+    `protocols` Array(String),
+    `gas_details` Tuple(Nullable(UInt128), UInt128, UInt128, UInt128),
+    `run_id` UInt64
+) 
+ENGINE = ReplicatedReplacingMergeTree('/clickhouse/eth_cluster0/tables/all/mev/failed_attempts', '{replica}', `run_id`)
+PRIMARY KEY (`block_number`,`tx_hash`)
+ORDER BY (`block_number`, `tx_hash`)
@@ -22,6 +22,45 @@ CREATE TABLE mev.liquidations ON CLUSTER eth_cluster0
         `covered_debt` Tuple(UInt256, UInt256),
         `liquidated_collateral` Tuple(UInt256, UInt256)
       ),
+    `loans` Nested(
+        `trace_idx` UInt64,
+        `pool` String,
+        `borrower` String,
+        `recipient` String,
+        `loaned_token` Tuple(String, String),
+        `loan_amount` Tuple(UInt256, UInt256)
+    ),
+    `repayments` Nested(
+        `trace_idx` UInt64,
+        `pool` String,
+        `payer` String,
+        `borrower` String,
+        `repayed_token` Tuple(String, String),
+        `repayment_amount` Tuple(UInt256, UInt256)
+    ),
+    `deposits` Nested(
+        `trace_idx` UInt64,
+        `pool` String,
+        `depositor` String,
+        `on_behalf_of` String,
+        `deposited_token` Tuple(String, String),
+        `deposit_amount` Tuple(UInt256, UInt256)
+    ),
+    `withdrawals` Nested(
+        `trace_idx` UInt64,
+        `pool` String,
+        `withdrawer` String,
+        `recipient` String,
+        `withdrawn_token` Tuple(String, String),
+        `withdraw_amount` Tuple(UInt256, UInt256)
+    ),
+    `oracle_update_tx_hash` Nullable(String),
+    `oracle` Nullable(String),
+    `oracle_asset` Nullable(String),
+    `oracle_previous_price` Nullable(Float64),
+    `oracle_price` Nullable(Float64),
+    `oracle_price_delta` Nullable(Float64),
+    `txs_after_oracle_update` Nullable(UInt64),
     `gas_details` Tuple(
         `coinbase_transfer` Nullable(UInt128), 
         `priority_fee` UInt128,
@@ -0,0 +1,19 @@
+CREATE TABLE brontes.pool_lvr ON CLUSTER eth_cluster0
+(
+    `block_number` UInt64,
+    `pool` String,
+    `protocol` String,
+    `reserves` Nested (
+        `token` Tuple(String, String),
+        `delta` Float64,
+        `cex_price` Float64
+    ),
+    `swap_count` UInt64,
+    `volume_usd` Float64,
+    `toxic_volume_usd` Float64,
+    `lvr_usd` Float64,
+    `run_id` UInt64
+)
+ENGINE = ReplicatedReplacingMergeTree('/clickhouse/eth_cluster0/tables/all/brontes/pool_lvr', '{replica}', `run_id`)
+PRIMARY KEY (`block_number`, `pool`)
+ORDER BY (`block_number`, `pool`)
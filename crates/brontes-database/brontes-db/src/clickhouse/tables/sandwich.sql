@@ -2,6 +2,7 @@ CREATE TABLE mev.sandwiches ON CLUSTER eth_cluster0
 (
     `frontrun_tx_hash` String,
     `block_number` UInt64,
+    `frontrun_block_number` UInt64,
     `frontrun_swaps` Nested(
         `tx_hash` String,
         `trace_idx` UInt64,
@@ -57,6 +58,15 @@ CREATE TABLE mev.sandwiches ON CLUSTER eth_cluster0
         `gas_used` UInt128,
         `effective_gas_price` UInt128
     ),
+    `victim_losses` Nested(
+        `tx_hash` String,
+        `victim_eoa` String,
+        `token` Tuple(String, String),
+        `amount_out` Tuple(UInt256, UInt256),
+        `counterfactual_amount_out` Tuple(UInt256, UInt256),
+        `token_amount_lost` Float64,
+        `amount_lost_usd` Float64
+    ),
     `run_id` UInt64
 ) 
 ENGINE = ReplicatedReplacingMergeTree('/clickhouse/eth_cluster0/tables/all/mev/sandwiches', '{replica}', `run_id`)
@@ -0,0 +1,54 @@
+CREATE TABLE mev.snipes ON CLUSTER eth_cluster0
+(
+    `block_number` UInt64,
+    `launch_block_number` UInt64,
+    `launch_tx_hash` String,
+    `protocol` String,
+    `pool` String,
+    `token` Tuple(String, String),
+    `blocks_after_launch` UInt64,
+    `snipe_swaps` Nested(
+        `tx_hash` String,
+        `trace_idx` UInt64,
+        `from` String,
+        `recipient` String,
+        `pool` String,
+        `token_in` Tuple(String, String),
+        `token_out` Tuple(String, String),
+        `amount_in` Tuple(UInt256, UInt256),
+        `amount_out` Tuple(UInt256, UInt256)
+    ),
+    `snipe_gas_details` Nested(
+        `tx_hash` String,
+        `coinbase_transfer` Nullable(UInt128), 
+        `priority_fee` UInt128,
+        `gas_used` UInt128,
+        `effective_gas_price` UInt128
+    ),
+    `exit_swaps` Nested(
+        `tx_hash` String,
+        `trace_idx` UInt64,
+        `from` String,
+        `recipient` String,
+        `pool` String,
+        `token_in` Tuple(String, String),
+        `token_out` Tuple(String, String),
+        `amount_in` Tuple(UInt256, UInt256),
+        `amount_out` Tuple(UInt256, UInt256)
+    ),
+    `exit_gas_details` Nested(
+        `tx_hash` String,
+        `coinbase_transfer` Nullable(UInt128), 
+        `priority_fee` UInt128,
+        `gas_used` UInt128,
+        `effective_gas_price` UInt128
+    ),
+    `tokens_held` Float64,
+    `markout_blocks` UInt64,
+    `prior_snipes` UInt64,
+    `repeat_sniper` Bool,
+    `run_id` UInt64
+) 
+ENGINE = ReplicatedReplacingMergeTree('/clickhouse/eth_cluster0/tables/all/mev/snipes', '{replica}', `run_id`)
+PRIMARY KEY (`block_number`, `launch_tx_hash`)
+ORDER BY (`block_number`, `launch_tx_hash`)
\ No newline at end of file
@@ -4,7 +4,7 @@ use alloy_primitives::Address;
 use brontes_metrics::db_cache::CacheData;
 use brontes_types::db::{
     address_metadata::AddressMetadata, address_to_protocol_info::ProtocolInfo,
-    searcher::SearcherInfo, token_info::TokenInfo,
+    searcher::SearcherInfo, token_info::TokenInfo, uniswap_v4::UniswapV4PoolKey,
 };
 use moka::{policy::EvictionPolicy, sync::SegmentedCache};
 
@@ -12,11 +12,12 @@ const MEGABYTE: usize = 1024 * 1024;
 
 #[derive(Clone)]
 pub struct ReadWriteCache {
-    address_meta:      Arc<SegmentedCache<Address, Option<AddressMetadata>, ahash::RandomState>>,
-    searcher_eoa:      Arc<SegmentedCache<Address, Option<SearcherInfo>, ahash::RandomState>>,
-    searcher_contract: Arc<SegmentedCache<Address, Option<SearcherInfo>, ahash::RandomState>>,
-    protocol_info:     Arc<SegmentedCache<Address, Option<ProtocolInfo>, ahash::RandomState>>,
-    token_info:        Arc<SegmentedCache<Address, Option<TokenInfo>, ahash::RandomState>>,
+    address_meta:        Arc<SegmentedCache<Address, Option<AddressMetadata>, ahash::RandomState>>,
+    searcher_eoa:        Arc<SegmentedCache<Address, Option<SearcherInfo>, ahash::RandomState>>,
+    searcher_contract:   Arc<SegmentedCache<Address, Option<SearcherInfo>, ahash::RandomState>>,
+    protocol_info:       Arc<SegmentedCache<Address, Option<ProtocolInfo>, ahash::RandomState>>,
+    token_info:          Arc<SegmentedCache<Address, Option<TokenInfo>, ahash::RandomState>>,
+    uniswap_v4_pool_key: Arc<SegmentedCache<Address, Option<UniswapV4PoolKey>, ahash::RandomState>>,
 
     pub metrics: Option<CacheData>,
 }
@@ -65,6 +66,15 @@ impl ReadWriteCache {
                 )
                 .build_with_hasher(ahash::RandomState::new())
                 .into(),
+
+            uniswap_v4_pool_key: SegmentedCache::builder(200)
+                .eviction_policy(EvictionPolicy::lru())
+                .max_capacity(
+                    ((memory_per_table_mb * MEGABYTE) / std::mem::size_of::<UniswapV4PoolKey>())
+                        as u64,
+                )
+                .build_with_hasher(ahash::RandomState::new())
+                .into(),
         }
     }
 
@@ -130,4 +140,17 @@ impl ReadWriteCache {
     ) -> R {
         self.record_metrics::<R, _, TokenInfo>(read, "token_info", &*self.token_info, f)
     }
+
+    pub fn uniswap_v4_pool_key<R>(
+        &self,
+        read: bool,
+        f: impl FnOnce(&SegmentedCache<Address, Option<UniswapV4PoolKey>, ahash::RandomState>) -> R,
+    ) -> R {
+        self.record_metrics::<R, _, UniswapV4PoolKey>(
+            read,
+            "uniswap_v4_pool_key",
+            &*self.uniswap_v4_pool_key,
+            f,
+        )
+    }
 }
@@ -33,7 +33,13 @@ pub fn merge_libmdbx_dbs(
             Builder,
             AddressToProtocolInfo,
             TokenDecimals,
-            DexPrice
+            DexPrice,
+            AnalysisRollups,
+            PoolLvr,
+            AddressBundles,
+            TxBundles,
+            ForkDetections,
+            UniswapV4PoolKeys
             );
 
             eyre::Ok(())
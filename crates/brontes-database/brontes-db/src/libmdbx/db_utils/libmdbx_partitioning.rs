@@ -129,7 +129,13 @@ impl LibmdbxPartitioner {
             SearcherContracts,
             Builder,
             AddressToProtocolInfo,
-            TokenDecimals
+            TokenDecimals,
+            AnalysisRollups,
+            PoolLvr,
+            AddressBundles,
+            TxBundles,
+            ForkDetections,
+            UniswapV4PoolKeys
         );
 
         Ok(())
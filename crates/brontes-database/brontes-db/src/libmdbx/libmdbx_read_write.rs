@@ -1,6 +1,7 @@
 use std::{ops::RangeInclusive, path::Path, sync::Arc};
 
-use alloy_primitives::Address;
+use alloy_primitives::{Address, B256};
+use brontes_libmdbx::RO;
 use brontes_metrics::db_reads::LibmdbxMetrics;
 use brontes_pricing::Protocol;
 use brontes_types::{
@@ -8,18 +9,26 @@ use brontes_types::{
     db::{
         address_metadata::AddressMetadata,
         address_to_protocol_info::ProtocolInfo,
+        analysis_rollup::{make_rollup_key, BlockAnalysisRollup, RollupWindow},
         builder::BuilderInfo,
+        bundle_index::{
+            address_bundle_key_range, decompose_address_bundle_key, decompose_tx_bundle_key,
+            tx_bundle_key_range,
+        },
         cex::{quotes::CexPriceMap, trades::CexTradeMap},
         dex::{make_filter_key_range, DexPrices, DexQuotes},
+        fork_detection::ForkDetection,
         initialized_state::{
             InitializedStateMeta, CEX_QUOTES_FLAG, CEX_TRADES_FLAG, DATA_NOT_PRESENT_NOT_AVAILABLE,
             DATA_PRESENT, DEX_PRICE_FLAG, META_FLAG,
         },
         metadata::{BlockMetadata, BlockMetadataInner, Metadata},
         mev_block::MevBlockWithClassified,
+        pool_lvr::BlockPoolLvr,
         searcher::SearcherInfo,
         token_info::{TokenInfo, TokenInfoWithAddress},
         traits::{DBWriter, LibmdbxReader},
+        uniswap_v4::UniswapV4PoolKey,
     },
     mev::{Bundle, MevBlock},
     normalized_actions::Action,
@@ -39,6 +48,7 @@ use tokio::sync::mpsc::{unbounded_channel, UnboundedSender};
 use tracing::{info, instrument};
 
 use super::{
+    implementation::compressed_wrappers::tx::CompressedLibmdbxTx,
     libmdbx_writer::{LibmdbxWriter, StampedWriterMessage, WriterMessage},
     types::ReturnKV,
     ReadWriteCache,
@@ -837,16 +847,30 @@ impl LibmdbxReader for LibmdbxReadWriter {
         start_block: Option<u64>,
         end_block: u64,
     ) -> eyre::Result<Vec<MevBlockWithClassified>> {
+        // the recycled cursors continue after the last block that was read, only
+        // the first one has to include the start block
+        let mut is_first_cursor = true;
         self.db.export_db(
             start_block,
             |start_key, tx| {
                 let mut cur = tx.cursor_read::<MevBlocks>()?;
-                if let Some(key) = start_key {
-                    let _ = cur.seek(key);
-                } else {
-                    // move to first entry and make sure .next() is first
-                    let _ = cur.first();
-                    let _ = cur.prev();
+                match start_key {
+                    Some(key) if std::mem::take(&mut is_first_cursor) => {
+                        // move in front of the first block from the start block on
+                        if cur.seek(key)?.is_some() {
+                            let _ = cur.prev();
+                        } else {
+                            let _ = cur.last();
+                        }
+                    }
+                    Some(key) => {
+                        let _ = cur.seek(key);
+                    }
+                    None => {
+                        // move to first entry and make sure .next() is first
+                        let _ = cur.first();
+                        let _ = cur.prev();
+                    }
                 }
                 Ok(cur)
             },
@@ -880,6 +904,111 @@ impl LibmdbxReader for LibmdbxReadWriter {
         )
     }
 
+    fn try_fetch_analysis_rollups(
+        &self,
+        window: RollupWindow,
+        start: u64,
+        end: u64,
+    ) -> eyre::Result<Vec<BlockAnalysisRollup>> {
+        let range = make_rollup_key(window, start)..=make_rollup_key(window, end);
+        self.db.view_db(|tx| {
+            Ok(tx
+                .cursor_read::<AnalysisRollups>()?
+                .walk_range(range)?
+                .map(|entry| entry.map(|row| row.1))
+                .collect::<Result<Vec<_>, _>>()?)
+        })
+    }
+
+    fn fetch_all_analysis_rollups(&self) -> eyre::Result<Vec<BlockAnalysisRollup>> {
+        self.db.export_db(
+            None,
+            |start_key, tx| {
+                let mut cur = tx.cursor_read::<AnalysisRollups>()?;
+                if let Some(key) = start_key {
+                    let _ = cur.seek(key);
+                } else {
+                    // move to first entry and make sure .next() is first
+                    let _ = cur.first();
+                    let _ = cur.prev();
+                }
+                Ok(cur)
+            },
+            |cursor| Ok(cursor.next().map(|inner| inner.map(|i| i.1))?),
+        )
+    }
+
+    fn try_fetch_pool_lvr(
+        &self,
+        start_block: Option<u64>,
+        end_block: u64,
+    ) -> eyre::Result<Vec<BlockPoolLvr>> {
+        self.db.view_db(|tx| {
+            Ok(tx
+                .cursor_read::<PoolLvr>()?
+                .walk_range(start_block.unwrap_or_default()..=end_block)?
+                .map(|entry| entry.map(|row| row.1))
+                .collect::<Result<Vec<_>, _>>()?)
+        })
+    }
+
+    fn fetch_all_pool_lvr(&self, start_block: Option<u64>) -> eyre::Result<Vec<BlockPoolLvr>> {
+        self.db.view_db(|tx| {
+            Ok(tx
+                .cursor_read::<PoolLvr>()?
+                .walk_range(start_block.unwrap_or_default()..)?
+                .map(|entry| entry.map(|row| row.1))
+                .collect::<Result<Vec<_>, _>>()?)
+        })
+    }
+
+    fn try_fetch_bundles_by_address(
+        &self,
+        address: Address,
+        start_block: Option<u64>,
+        end_block: Option<u64>,
+    ) -> eyre::Result<Vec<Bundle>> {
+        let (start_key, end_key) = address_bundle_key_range(
+            address,
+            start_block.unwrap_or_default(),
+            end_block.unwrap_or(u64::MAX),
+        );
+
+        self.db.view_db(|tx| {
+            let bundles = tx
+                .cursor_read::<AddressBundles>()?
+                .walk_range(start_key..=end_key)?
+                .map(|entry| {
+                    entry.map(|(key, _)| {
+                        let (_, block_number, bundle_idx) = decompose_address_bundle_key(key);
+                        (block_number, bundle_idx)
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            fetch_indexed_bundles(tx, bundles)
+        })
+    }
+
+    fn try_fetch_bundles_by_tx_hash(&self, tx_hash: B256) -> eyre::Result<Vec<Bundle>> {
+        let (start_key, end_key) = tx_bundle_key_range(tx_hash);
+
+        self.db.view_db(|tx| {
+            let bundles = tx
+                .cursor_read::<TxBundles>()?
+                .walk_range(start_key..=end_key)?
+                .map(|entry| {
+                    entry.map(|(key, _)| {
+                        let (_, block_number, bundle_idx) = decompose_tx_bundle_key(key);
+                        (block_number, bundle_idx)
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            fetch_indexed_bundles(tx, bundles)
+        })
+    }
+
     #[instrument(level = "error", skip_all)]
     fn fetch_all_address_metadata(&self) -> eyre::Result<Vec<(Address, AddressMetadata)>> {
         self.db.export_db(
@@ -898,6 +1027,61 @@ impl LibmdbxReader for LibmdbxReadWriter {
             |cursor| Ok(cursor.next().map(|inner| inner.map(|i| (i.0, i.1)))?),
         )
     }
+
+    fn fetch_fork_detections(&self) -> eyre::Result<Vec<(Address, ForkDetection)>> {
+        self.db.view_db(|tx| {
+            Ok(tx
+                .cursor_read::<ForkDetections>()?
+                .walk(None)?
+                .collect::<Result<Vec<_>, _>>()?)
+        })
+    }
+
+    fn try_fetch_fork_detection(&self, pool: Address) -> eyre::Result<Option<ForkDetection>> {
+        self.db
+            .view_db(|tx| tx.get::<ForkDetections>(pool).map_err(ErrReport::from))
+    }
+
+    fn try_fetch_uniswap_v4_pool_key(
+        &self,
+        pool: Address,
+    ) -> eyre::Result<Option<UniswapV4PoolKey>> {
+        if let Some(key) = self.cache.uniswap_v4_pool_key(true, |lock| lock.get(&pool)) {
+            return Ok(key)
+        }
+
+        self.db.view_db(|tx| {
+            let key = tx.get::<UniswapV4PoolKeys>(pool)?;
+            self.cache
+                .uniswap_v4_pool_key(false, |lock| lock.insert(pool, key));
+
+            Ok(key)
+        })
+    }
+}
+
+/// Loads the bundles the index entries point to, the entries have to be sorted
+/// by block
+fn fetch_indexed_bundles(
+    tx: &CompressedLibmdbxTx<RO>,
+    bundles: Vec<(u64, u16)>,
+) -> eyre::Result<Vec<Bundle>> {
+    let mut res = Vec::with_capacity(bundles.len());
+
+    for (block_number, bundle_idxs) in &bundles.into_iter().group_by(|(block, _)| *block) {
+        let block = tx
+            .get::<MevBlocks>(block_number)?
+            .ok_or_else(|| eyre!("bundle index points to missing mev block {block_number}"))?;
+
+        for (_, bundle_idx) in bundle_idxs {
+            let bundle = block.mev.get(bundle_idx as usize).ok_or_else(|| {
+                eyre!("bundle index points to missing bundle {bundle_idx} of block {block_number}")
+            })?;
+            res.push(bundle.clone());
+        }
+    }
+
+    Ok(res)
 }
 
 impl DBWriter for LibmdbxReadWriter {
@@ -1019,6 +1203,29 @@ impl DBWriter for LibmdbxReadWriter {
             .send(WriterMessage::TokenInfo { address, decimals, symbol }.stamp())?)
     }
 
+    async fn write_fork_detection(
+        &self,
+        pool: Address,
+        detection: ForkDetection,
+    ) -> eyre::Result<()> {
+        Ok(self
+            .tx
+            .send(WriterMessage::ForkDetection { pool, detection }.stamp())?)
+    }
+
+    async fn write_uniswap_v4_pool_key(
+        &self,
+        pool: Address,
+        key: UniswapV4PoolKey,
+    ) -> eyre::Result<()> {
+        self.cache
+            .uniswap_v4_pool_key(false, |handle| handle.insert(pool, Some(key)));
+
+        Ok(self
+            .tx
+            .send(WriterMessage::UniswapV4PoolKey { pool, key }.stamp())?)
+    }
+
     async fn insert_pool(
         &self,
         block: u64,
@@ -1072,6 +1279,12 @@ impl DBWriter for LibmdbxReadWriter {
         )?)
     }
 
+    async fn delete_block(&self, block_number: u64) -> eyre::Result<()> {
+        Ok(self
+            .tx
+            .send(WriterMessage::DeleteBlock { block_number }.stamp())?)
+    }
+
     /// only for internal functionality (i.e. clickhouse)
     async fn insert_tree(&self, _tree: BlockTree<Action>) -> eyre::Result<()> {
         Ok(())
@@ -1084,6 +1297,16 @@ impl DBWriter for LibmdbxReadWriter {
     ) -> eyre::Result<()> {
         Ok(())
     }
+
+    async fn write_analysis_rollups(&self, rollups: Vec<BlockAnalysisRollup>) -> eyre::Result<()> {
+        Ok(self
+            .tx
+            .send(WriterMessage::AnalysisRollups { rollups }.stamp())?)
+    }
+
+    async fn write_pool_lvr(&self, pool_lvr: BlockPoolLvr) -> eyre::Result<()> {
+        Ok(self.tx.send(WriterMessage::PoolLvr { pool_lvr }.stamp())?)
+    }
 }
 
 impl LibmdbxReadWriter {
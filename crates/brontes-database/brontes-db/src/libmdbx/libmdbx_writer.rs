@@ -6,19 +6,26 @@ use std::{
 };
 
 use alloy_primitives::Address;
+use brontes_libmdbx::RW;
 use brontes_metrics::db_writer::WriterMetrics;
 use brontes_types::{
     db::{
         address_metadata::AddressMetadata,
         address_to_protocol_info::ProtocolInfo,
+        analysis_rollup::{make_rollup_key, BlockAnalysisRollup, RollupWindow},
+        block_analysis::BlockAnalysis,
         builder::BuilderInfo,
-        dex::{make_key, DexQuoteWithIndex, DexQuotes},
-        initialized_state::{DATA_PRESENT, DEX_PRICE_FLAG, TRACE_FLAG},
+        bundle_index::BlockBundleIndex,
+        dex::{make_filter_key_range, make_key, DexQuoteWithIndex, DexQuotes},
+        fork_detection::ForkDetection,
+        initialized_state::{DATA_NOT_PRESENT_UNKNOWN, DATA_PRESENT, DEX_PRICE_FLAG, TRACE_FLAG},
         mev_block::MevBlockWithClassified,
         pool_creation_block::PoolsToAddresses,
+        pool_lvr::BlockPoolLvr,
         searcher::SearcherInfo,
         token_info::TokenInfo,
         traces::TxTracesInner,
+        uniswap_v4::UniswapV4PoolKey,
     },
     mev::{Bundle, MevBlock},
     structured_trace::TxTrace,
@@ -36,6 +43,7 @@ use tracing::instrument;
 
 use crate::{
     libmdbx::{
+        implementation::compressed_wrappers::tx::CompressedLibmdbxTx,
         tables::*,
         types::{LibmdbxData, ReturnKV},
         Libmdbx,
@@ -86,6 +94,14 @@ pub enum WriterMessage {
         address:  Address,
         metadata: Box<AddressMetadata>,
     },
+    ForkDetection {
+        pool:      Address,
+        detection: ForkDetection,
+    },
+    UniswapV4PoolKey {
+        pool: Address,
+        key:  UniswapV4PoolKey,
+    },
     Pool {
         block:           u64,
         address:         Address,
@@ -97,6 +113,15 @@ pub enum WriterMessage {
         block:  u64,
         traces: Vec<TxTrace>,
     },
+    AnalysisRollups {
+        rollups: Vec<BlockAnalysisRollup>,
+    },
+    PoolLvr {
+        pool_lvr: BlockPoolLvr,
+    },
+    DeleteBlock {
+        block_number: u64,
+    },
     Init(InitTables, Arc<Notify>),
 }
 
@@ -214,7 +239,9 @@ init!(
     MevBlocks,
     SearcherEOAs,
     SearcherContracts,
-    InitializedState
+    InitializedState,
+    AnalysisRollups,
+    PoolLvr
 );
 
 /// due to libmdbx's 1 write tx limit. it makes sense
@@ -222,6 +249,7 @@ init!(
 pub struct LibmdbxWriter {
     db:           Arc<Libmdbx>,
     insert_queue: InsetQueue,
+    mev_queue:    Vec<(u64, MevBlockWithClassified)>,
     rx:           UnboundedYapperReceiver<StampedWriterMessage>,
     metrics:      WriterMetrics,
 }
@@ -232,7 +260,13 @@ impl LibmdbxWriter {
         rx: UnboundedYapperReceiver<StampedWriterMessage>,
         metrics: bool,
     ) -> Self {
-        Self { rx, db, insert_queue: FastHashMap::default(), metrics: WriterMetrics::new(metrics) }
+        Self {
+            rx,
+            db,
+            insert_queue: FastHashMap::default(),
+            mev_queue: Vec::new(),
+            metrics: WriterMetrics::new(metrics),
+        }
     }
 
     fn handle_msg(&mut self, stamped_msg: StampedWriterMessage) -> eyre::Result<()> {
@@ -242,6 +276,14 @@ impl LibmdbxWriter {
                 self.insert_pool(block, address, &tokens, curve_lp_token, classifier_name)?;
                 "pool"
             }
+            WriterMessage::ForkDetection { pool, detection } => {
+                self.write_fork_detection(pool, detection)?;
+                "forkdetection"
+            }
+            WriterMessage::UniswapV4PoolKey { pool, key } => {
+                self.write_uniswap_v4_pool_key(pool, key)?;
+                "uniswapv4poolkey"
+            }
             WriterMessage::Traces { block, traces } => {
                 self.save_traces(block, traces)?;
                 "traces"
@@ -283,6 +325,18 @@ impl LibmdbxWriter {
                 self.write_searcher_contract_info(searcher_contract, *searcher_info)?;
                 "searchercontractinfo"
             }
+            WriterMessage::AnalysisRollups { rollups } => {
+                self.write_analysis_rollups(rollups)?;
+                "analysisrollups"
+            }
+            WriterMessage::PoolLvr { pool_lvr } => {
+                self.write_pool_lvr(pool_lvr)?;
+                "poollvr"
+            }
+            WriterMessage::DeleteBlock { block_number } => {
+                self.delete_block(block_number)?;
+                "deleteblock"
+            }
             WriterMessage::Init(init, not) => {
                 init.write_data(self.db.clone())?;
                 not.notify_one();
@@ -400,6 +454,47 @@ impl LibmdbxWriter {
         Ok(())
     }
 
+    fn write_fork_detection(&self, pool: Address, detection: ForkDetection) -> eyre::Result<()> {
+        let data = ForkDetectionsData::new(pool, detection);
+
+        self.instrumented_write::<ForkDetections, ForkDetectionsData>(&[data])
+            .expect("libmdbx write failure");
+
+        Ok(())
+    }
+
+    fn write_uniswap_v4_pool_key(&self, pool: Address, key: UniswapV4PoolKey) -> eyre::Result<()> {
+        let data = UniswapV4PoolKeysData::new(pool, key);
+
+        self.instrumented_write::<UniswapV4PoolKeys, UniswapV4PoolKeysData>(&[data])
+            .expect("libmdbx write failure");
+
+        Ok(())
+    }
+
+    #[instrument(target = "libmdbx_read_write::write_analysis_rollups", skip_all, level = "warn")]
+    fn write_analysis_rollups(&self, rollups: Vec<BlockAnalysisRollup>) -> eyre::Result<()> {
+        let data = rollups
+            .into_iter()
+            .map(|rollup| AnalysisRollupsData::new(rollup.key(), rollup))
+            .collect::<Vec<_>>();
+
+        self.instrumented_write::<AnalysisRollups, AnalysisRollupsData>(&data)
+            .expect("libmdbx write failure");
+
+        Ok(())
+    }
+
+    #[instrument(target = "libmdbx_read_write::write_pool_lvr", skip_all, level = "warn")]
+    fn write_pool_lvr(&self, pool_lvr: BlockPoolLvr) -> eyre::Result<()> {
+        let data = PoolLvrData::new(pool_lvr.block_number, pool_lvr);
+
+        self.instrumented_write::<PoolLvr, PoolLvrData>(&[data])
+            .expect("libmdbx write failure");
+
+        Ok(())
+    }
+
     #[instrument(target = "libmdbx_read_write::write_address_meta", skip_all, level = "warn")]
     fn save_mev_blocks(
         &mut self,
@@ -407,18 +502,44 @@ impl LibmdbxWriter {
         block: MevBlock,
         mev: Vec<Bundle>,
     ) -> eyre::Result<()> {
-        let data =
-            MevBlocksData::new(block_number, MevBlockWithClassified { block, mev }).into_key_val();
-        let (key, value) = Self::convert_into_save_bytes(data);
+        self.mev_queue
+            .push((block_number, MevBlockWithClassified { block, mev }));
 
-        let entry = self.insert_queue.entry(Tables::MevBlocks).or_default();
-        entry.push((key.to_vec(), value));
+        if self.mev_queue.len() > CLEAR_AM {
+            let blocks = std::mem::take(&mut self.mev_queue);
+            self.insert_mev_blocks(blocks)?;
+        }
 
-        if entry.len() > CLEAR_AM {
-            let data = std::mem::take(entry);
-            self.insert_batched_data::<MevBlocks>(data)?;
+        Ok(())
+    }
+
+    /// Writes the blocks and the index of their bundles in a single
+    /// transaction, so the index never points to bundles that don't exist.
+    /// The index entries of the bundles of a block that is overwritten are
+    /// removed.
+    fn insert_mev_blocks(&self, blocks: Vec<(u64, MevBlockWithClassified)>) -> eyre::Result<()> {
+        let start_time = Instant::now();
+        let tx = self.db.rw_tx()?;
+
+        for (block_number, block) in blocks {
+            if let Some(old) = tx.get::<MevBlocks>(block_number)? {
+                delete_bundle_index(&tx, block_number, &old.mev)?;
+            }
+
+            let index = BlockBundleIndex::new(block_number, &block.mev);
+            for (key, entry) in index.addresses {
+                tx.put::<AddressBundles>(key, entry)?;
+            }
+            for (key, entry) in index.txs {
+                tx.put::<TxBundles>(key, entry)?;
+            }
+
+            tx.put::<MevBlocks>(block_number, block)?;
         }
+        tx.commit()?;
 
+        let total_time = Instant::now() - start_time;
+        self.metrics.observe_write_latency_batch(total_time);
         Ok(())
     }
 
@@ -530,6 +651,40 @@ impl LibmdbxWriter {
         self.init_state_updating(block, TRACE_FLAG)
     }
 
+    #[instrument(target = "libmdbx_read_write::delete_block", skip_all, level = "warn")]
+    fn delete_block(&mut self, block_number: u64) -> eyre::Result<()> {
+        // flush the queued writes first so they can't land after the delete
+        self.insert_remaining();
+
+        let (start_key, end_key) = make_filter_key_range(block_number);
+        self.db.update_db(|tx| {
+            if let Some(block) = tx.get::<MevBlocks>(block_number)? {
+                delete_bundle_index(tx, block_number, &block.mev)?;
+            }
+            tx.delete::<MevBlocks>(block_number, None)?;
+            revert_analysis_rollups(tx, block_number)?;
+            tx.delete::<TxTraces>(block_number, None)?;
+            tx.delete::<PoolLvr>(block_number, None)?;
+
+            let dex_keys = tx
+                .cursor_read::<DexPrice>()?
+                .walk_range(start_key..=end_key)?
+                .filter_map(|row| row.ok().map(|row| row.0))
+                .collect_vec();
+            for key in dex_keys {
+                tx.delete::<DexPrice>(key, None)?;
+            }
+
+            if let Some(mut state) = tx.get::<InitializedState>(block_number)? {
+                state.set(TRACE_FLAG, DATA_NOT_PRESENT_UNKNOWN);
+                state.set(DEX_PRICE_FLAG, DATA_NOT_PRESENT_UNKNOWN);
+                tx.put::<InitializedState>(block_number, state)?;
+            }
+
+            Ok(())
+        })?
+    }
+
     #[instrument(target = "libmdbx_read_write::write_builder_info", skip_all, level = "warn")]
     fn write_builder_info(
         &self,
@@ -626,6 +781,11 @@ impl LibmdbxWriter {
     }
 
     fn insert_remaining(&mut self) {
+        let blocks = std::mem::take(&mut self.mev_queue);
+        if !blocks.is_empty() {
+            self.insert_mev_blocks(blocks).unwrap();
+        }
+
         std::mem::take(&mut self.insert_queue)
             .into_iter()
             .for_each(|(table, values)| {
@@ -642,9 +802,6 @@ impl LibmdbxWriter {
                     Tables::CexTrades => {
                         self.insert_batched_data::<CexTrades>(values).unwrap();
                     }
-                    Tables::MevBlocks => {
-                        self.insert_batched_data::<MevBlocks>(values).unwrap();
-                    }
                     Tables::TxTraces => {
                         self.insert_batched_data::<TxTraces>(values).unwrap();
                     }
@@ -659,6 +816,57 @@ impl LibmdbxWriter {
     }
 }
 
+fn delete_bundle_index(
+    tx: &CompressedLibmdbxTx<RW>,
+    block_number: u64,
+    bundles: &[Bundle],
+) -> Result<(), DatabaseError> {
+    let index = BlockBundleIndex::new(block_number, bundles);
+    for (key, _) in index.addresses {
+        tx.delete::<AddressBundles>(key, None)?;
+    }
+    for (key, _) in index.txs {
+        tx.delete::<TxBundles>(key, None)?;
+    }
+
+    Ok(())
+}
+
+/// Rebuilds the rollups the block was folded into from the other mev blocks
+/// they hold, the block's own mev block has to be deleted already. The ranked
+/// lists are cut off, so a block can't be subtracted from a rollup
+fn revert_analysis_rollups(
+    tx: &CompressedLibmdbxTx<RW>,
+    block_number: u64,
+) -> Result<(), DatabaseError> {
+    let range =
+        make_rollup_key(RollupWindow::Hourly, 0)..=make_rollup_key(RollupWindow::Weekly, u64::MAX);
+    let rollups = tx
+        .cursor_read::<AnalysisRollups>()?
+        .walk_range(range)?
+        .filter_map(|row| row.ok().map(|row| row.1))
+        .filter(|rollup| rollup.contains_block(block_number))
+        .collect_vec();
+
+    for rollup in rollups {
+        let mut reverted = BlockAnalysisRollup::new(rollup.window, rollup.window_start);
+        for block in rollup.blocks.iter().filter(|block| **block != block_number) {
+            let Some(mev_block) = tx.get::<MevBlocks>(*block)? else { continue };
+            let analysis = BlockAnalysis::new(&mev_block.block, &mev_block.mev);
+            // the window start is inside of the window
+            let _ = reverted.fold(rollup.window_start, &analysis);
+        }
+
+        if reverted.block_count == 0 {
+            tx.delete::<AnalysisRollups>(rollup.key(), None)?;
+        } else {
+            tx.put::<AnalysisRollups>(rollup.key(), reverted)?;
+        }
+    }
+
+    Ok(())
+}
+
 impl Future for LibmdbxWriter {
     type Output = ();
 
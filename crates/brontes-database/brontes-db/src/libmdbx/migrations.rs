@@ -0,0 +1,734 @@
+//! Migrations between the [schema versions](super::schema) of the tables.
+//!
+//! Every [`Migration`] reads a table at one version and writes it at the next,
+//! recording the new version in the same transaction. A table is brought up to
+//! date by running its migrations in order, either in place or while copying
+//! the database into a new one.
+use std::fmt;
+
+use alloy_primitives::Address;
+use brontes_types::{
+    db::{
+        address_to_protocol_info::ProtocolInfo,
+        analysis_rollup::{BlockAnalysisRollup, MevTypeRollup},
+        legacy::{
+            mev_blocks_v1::{MevBlockWithClassifiedV1, MevBlockWithClassifiedV1Redefined},
+            searcher_v1::{SearcherInfoV1, SearcherInfoV1Redefined},
+        },
+        mev_block::MevBlockWithClassified,
+        pool_lvr::BlockPoolLvr,
+        schema_version::SchemaVersion,
+    },
+    mev::BundleData,
+    normalized_actions::NormalizedSwap,
+    Protocol,
+};
+use tracing::info;
+
+use super::{
+    tables::{
+        AddressToProtocolInfo, AnalysisRollups, MevBlocks, PoolLvr, SearcherContracts,
+        SearcherEOAs, Tables,
+    },
+    types::CompressedTable,
+    Libmdbx,
+};
+
+/// Reads the table from the first db at `from` and writes it to the second at
+/// `to`, which is `from`'s next version. Both dbs are the same when migrating
+/// in place. Returns the amount of entries migrated.
+pub type MigrateFn = fn(&Libmdbx, &Libmdbx, SchemaVersion) -> eyre::Result<usize>;
+
+pub struct Migration {
+    pub table:       Tables,
+    pub from:        SchemaVersion,
+    pub description: &'static str,
+    pub migrate:     MigrateFn,
+}
+
+impl Migration {
+    pub fn to(&self) -> SchemaVersion {
+        self.from.next()
+    }
+}
+
+impl fmt::Debug for Migration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Migration")
+            .field("table", &self.table)
+            .field("from", &self.from)
+            .field("description", &self.description)
+            .finish()
+    }
+}
+
+impl fmt::Display for Migration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} -> {}: {}", self.table, self.from, self.to(), self.description)
+    }
+}
+
+/// Every migration, a table at an old version has to have a migration to the
+/// next version up to its current one
+pub static MIGRATIONS: &[Migration] = &[
+    Migration {
+        table:       Tables::MevBlocks,
+        from:        SchemaVersion(1),
+        description: "adds the failed attempt and sniping counts, the lending actions of \
+                      liquidations and the victim losses of sandwiches",
+        // v1 had no Uniswap V4, its discriminant was the one of `Unknown`
+        migrate:     |src, dst, to| {
+            rewrite_table::<MevBlocksV1, MevBlocks>(src, dst, to, |v1| {
+                let mut block: MevBlockWithClassified = v1.into();
+                reset_unknown_protocol(mev_block_protocols(&mut block), Protocol::UniswapV4);
+                block
+            })
+        },
+    },
+    Migration {
+        table:       Tables::MevBlocks,
+        from:        SchemaVersion(2),
+        description: "moves unknown protocols to the new discriminant of `Protocol::Unknown`",
+        // the vault protocols were added in front of `Unknown`
+        migrate:     |src, dst, to| {
+            move_unknown_protocol::<MevBlocks>(src, dst, to, Protocol::Erc4626, mev_block_protocols)
+        },
+    },
+    Migration {
+        table:       Tables::MevBlocks,
+        from:        SchemaVersion(3),
+        description: "moves unknown protocols to the new discriminant of `Protocol::Unknown`",
+        // the routers were added in front of `Unknown`
+        migrate:     |src, dst, to| {
+            move_unknown_protocol::<MevBlocks>(
+                src,
+                dst,
+                to,
+                Protocol::ParaswapV5,
+                mev_block_protocols,
+            )
+        },
+    },
+    Migration {
+        table:       Tables::SearcherEOAs,
+        from:        SchemaVersion(1),
+        description: "adds the failed attempt and sniping counts and pnl",
+        migrate:     |src, dst, to| {
+            rewrite_table::<SearcherEOAsV1, SearcherEOAs>(src, dst, to, Into::into)
+        },
+    },
+    Migration {
+        table:       Tables::SearcherContracts,
+        from:        SchemaVersion(1),
+        description: "adds the failed attempt and sniping counts and pnl",
+        migrate:     |src, dst, to| {
+            rewrite_table::<SearcherContractsV1, SearcherContracts>(src, dst, to, Into::into)
+        },
+    },
+    Migration {
+        table:       Tables::AddressToProtocolInfo,
+        from:        SchemaVersion(1),
+        description: "moves unknown protocols to the new discriminant of `Protocol::Unknown`",
+        // v1 had no Uniswap V4, its discriminant was the one of `Unknown`
+        migrate:     |src, dst, to| {
+            move_unknown_protocol::<AddressToProtocolInfo>(
+                src,
+                dst,
+                to,
+                Protocol::UniswapV4,
+                protocol_info_protocols,
+            )
+        },
+    },
+    Migration {
+        table:       Tables::AddressToProtocolInfo,
+        from:        SchemaVersion(2),
+        description: "moves unknown protocols to the new discriminant of `Protocol::Unknown`",
+        // the vault protocols were added in front of `Unknown`
+        migrate:     |src, dst, to| {
+            move_unknown_protocol::<AddressToProtocolInfo>(
+                src,
+                dst,
+                to,
+                Protocol::Erc4626,
+                protocol_info_protocols,
+            )
+        },
+    },
+    Migration {
+        table:       Tables::AddressToProtocolInfo,
+        from:        SchemaVersion(3),
+        description: "moves unknown protocols to the new discriminant of `Protocol::Unknown`",
+        // the routers were added in front of `Unknown`
+        migrate:     |src, dst, to| {
+            move_unknown_protocol::<AddressToProtocolInfo>(
+                src,
+                dst,
+                to,
+                Protocol::ParaswapV5,
+                protocol_info_protocols,
+            )
+        },
+    },
+    // the rollups and the lvr were added after Uniswap V4, so their v1 has it
+    Migration {
+        table:       Tables::AnalysisRollups,
+        from:        SchemaVersion(1),
+        description: "moves unknown protocols to the new discriminant of `Protocol::Unknown`",
+        migrate:     |src, dst, to| {
+            move_unknown_protocol::<AnalysisRollups>(
+                src,
+                dst,
+                to,
+                Protocol::Erc4626,
+                rollup_protocols,
+            )
+        },
+    },
+    Migration {
+        table:       Tables::AnalysisRollups,
+        from:        SchemaVersion(2),
+        description: "moves unknown protocols to the new discriminant of `Protocol::Unknown`",
+        migrate:     |src, dst, to| {
+            move_unknown_protocol::<AnalysisRollups>(
+                src,
+                dst,
+                to,
+                Protocol::ParaswapV5,
+                rollup_protocols,
+            )
+        },
+    },
+    Migration {
+        table:       Tables::PoolLvr,
+        from:        SchemaVersion(1),
+        description: "moves unknown protocols to the new discriminant of `Protocol::Unknown`",
+        migrate:     |src, dst, to| {
+            move_unknown_protocol::<PoolLvr>(src, dst, to, Protocol::Erc4626, lvr_protocols)
+        },
+    },
+    Migration {
+        table:       Tables::PoolLvr,
+        from:        SchemaVersion(2),
+        description: "moves unknown protocols to the new discriminant of `Protocol::Unknown`",
+        migrate:     |src, dst, to| {
+            move_unknown_protocol::<PoolLvr>(src, dst, to, Protocol::ParaswapV5, lvr_protocols)
+        },
+    },
+];
+
+/// The migrations that bring `table` from `from` to its current version, in
+/// order
+pub fn migration_path(table: Tables, from: SchemaVersion) -> eyre::Result<Vec<&'static Migration>> {
+    let current = table.schema_version();
+    if from > current {
+        eyre::bail!("{table} is at {from}, which is newer than this version of brontes reads")
+    }
+
+    let mut path = Vec::new();
+    let mut version = from;
+    while version < current {
+        let migration = MIGRATIONS
+            .iter()
+            .find(|m| m.table == table && m.from == version)
+            .ok_or_else(|| eyre::eyre!("no migration of {table} from {version}"))?;
+        path.push(migration);
+        version = migration.to();
+    }
+
+    Ok(path)
+}
+
+/// The migrations each outdated table of `db` needs
+pub fn plan_migrations(db: &Libmdbx) -> eyre::Result<Vec<(Tables, Vec<&'static Migration>)>> {
+    db.schema_mismatches()?
+        .into_iter()
+        .map(|mismatch| Ok((mismatch.table, migration_path(mismatch.table, mismatch.recorded)?)))
+        .collect()
+}
+
+/// Migrates every outdated table of `src`. Migrates in place if there's no
+/// `dst`, otherwise copies the up to date tables and writes the migrated ones
+/// to `dst`, which has to be empty.
+pub fn migrate(src: &Libmdbx, dst: Option<&Libmdbx>) -> eyre::Result<()> {
+    let plan = plan_migrations(src)?;
+
+    if let Some(dst) = dst {
+        for table in Tables::ALL {
+            if table == Tables::SchemaVersions || plan.iter().any(|(t, _)| *t == table) {
+                continue
+            }
+            let copied = table.copy_table(src, dst)?;
+            info!(target: "brontes::migrate", %table, copied, "copied table");
+        }
+    }
+
+    for (table, path) in plan {
+        for (i, migration) in path.into_iter().enumerate() {
+            // only the first migration reads from the source, the following ones
+            // continue from its output
+            let (read, write) = match dst {
+                Some(dst) if i == 0 => (src, dst),
+                Some(dst) => (dst, dst),
+                None => (src, src),
+            };
+
+            let entries = (migration.migrate)(read, write, migration.to())?;
+
+            let recorded = write
+                .schema_versions()?
+                .into_iter()
+                .find_map(|(t, version)| (t == table).then_some(version).flatten());
+            if recorded != Some(migration.to()) {
+                eyre::bail!("migration didn't record its version: {migration}")
+            }
+
+            info!(target: "brontes::migrate", %migration, entries, "migrated table");
+        }
+    }
+
+    Ok(())
+}
+
+/// Rewrites every entry of `Old` in `src` as an entry of `New` in `dst`. The
+/// whole table is loaded into memory and written in a single transaction, so
+/// an interrupted migration leaves the table at its old version.
+fn rewrite_table<Old, New>(
+    src: &Libmdbx,
+    dst: &Libmdbx,
+    to: SchemaVersion,
+    f: impl Fn(Old::DecompressedValue) -> New::DecompressedValue,
+) -> eyre::Result<usize>
+where
+    Old: CompressedTable,
+    Old::Value: From<Old::DecompressedValue> + Into<Old::DecompressedValue>,
+    New: CompressedTable<Key = Old::Key>,
+    New::Value: From<New::DecompressedValue> + Into<New::DecompressedValue>,
+{
+    let table: Tables = New::NAME
+        .parse()
+        .map_err(|e| eyre::eyre!("{e}: {}", New::NAME))?;
+
+    let entries = src.view_db(|tx| {
+        let mut cursor = tx.cursor_read::<Old>()?;
+        Ok(cursor.walk(None)?.collect::<Result<Vec<_>, _>>()?)
+    })?;
+
+    dst.update_schema(table, to, |tx| {
+        tx.clear::<New>()?;
+
+        let migrated = entries.len();
+        for (key, value) in entries {
+            tx.put::<New>(key, f(value))?;
+        }
+
+        Ok(migrated)
+    })
+}
+
+/// Protocols are stored by their discriminant, so adding protocols in front of
+/// `Protocol::Unknown` makes the unknown protocols of the previous version
+/// decode as `unknown_at`, the first protocol that was added. `protocols`
+/// returns every protocol stored in a value of the table.
+fn move_unknown_protocol<T>(
+    src: &Libmdbx,
+    dst: &Libmdbx,
+    to: SchemaVersion,
+    unknown_at: Protocol,
+    protocols: fn(&mut T::DecompressedValue) -> Vec<&mut Protocol>,
+) -> eyre::Result<usize>
+where
+    T: CompressedTable,
+    T::Value: From<T::DecompressedValue> + Into<T::DecompressedValue>,
+{
+    rewrite_table::<T, T>(src, dst, to, |mut value| {
+        reset_unknown_protocol(protocols(&mut value), unknown_at);
+        value
+    })
+}
+
+fn reset_unknown_protocol(protocols: Vec<&mut Protocol>, unknown_at: Protocol) {
+    protocols
+        .into_iter()
+        .filter(|protocol| **protocol == unknown_at)
+        .for_each(|protocol| *protocol = Protocol::Unknown);
+}
+
+fn protocol_info_protocols(info: &mut ProtocolInfo) -> Vec<&mut Protocol> {
+    vec![&mut info.protocol]
+}
+
+fn rollup_protocols(rollup: &mut BlockAnalysisRollup) -> Vec<&mut Protocol> {
+    [
+        &mut rollup.all,
+        &mut rollup.atomic,
+        &mut rollup.sandwich,
+        &mut rollup.jit,
+        &mut rollup.jit_sandwich,
+        &mut rollup.cex_dex,
+        &mut rollup.liquidation,
+    ]
+    .into_iter()
+    .flat_map(|MevTypeRollup { arbed_dex_profit, arbed_dex_revenue, .. }| {
+        arbed_dex_profit.iter_mut().chain(arbed_dex_revenue)
+    })
+    .collect()
+}
+
+fn lvr_protocols(block: &mut BlockPoolLvr) -> Vec<&mut Protocol> {
+    block
+        .pools
+        .iter_mut()
+        .map(|pool| &mut pool.protocol)
+        .collect()
+}
+
+fn mev_block_protocols(block: &mut MevBlockWithClassified) -> Vec<&mut Protocol> {
+    block
+        .mev
+        .iter_mut()
+        .flat_map(|bundle| bundle_protocols(&mut bundle.data))
+        .collect()
+}
+
+fn bundle_protocols(data: &mut BundleData) -> Vec<&mut Protocol> {
+    match data {
+        BundleData::Sandwich(sandwich) => swap_protocols(
+            sandwich
+                .frontrun_swaps
+                .iter_mut()
+                .chain(&mut sandwich.victim_swaps)
+                .flatten()
+                .chain(&mut sandwich.backrun_swaps),
+        )
+        .collect(),
+        BundleData::AtomicArb(arb) => swap_protocols(&mut arb.swaps).collect(),
+        BundleData::JitSandwich(jit_sandwich) => swap_protocols(
+            jit_sandwich
+                .frontrun_swaps
+                .iter_mut()
+                .chain(&mut jit_sandwich.victim_swaps)
+                .flatten()
+                .chain(&mut jit_sandwich.backrun_swaps),
+        )
+        .chain(
+            jit_sandwich
+                .frontrun_mints
+                .iter_mut()
+                .flatten()
+                .flatten()
+                .map(|mint| &mut mint.protocol),
+        )
+        .chain(
+            jit_sandwich
+                .backrun_burns
+                .iter_mut()
+                .map(|burn| &mut burn.protocol),
+        )
+        .collect(),
+        BundleData::Jit(jit) => swap_protocols(jit.victim_swaps.iter_mut().flatten())
+            .chain(jit.frontrun_mints.iter_mut().map(|mint| &mut mint.protocol))
+            .chain(jit.backrun_burns.iter_mut().map(|burn| &mut burn.protocol))
+            .collect(),
+        BundleData::CexDexQuote(quote) => swap_protocols(&mut quote.swaps).collect(),
+        BundleData::CexDex(cex_dex) => swap_protocols(&mut cex_dex.swaps)
+            .chain(
+                cex_dex
+                    .global_vmap_details
+                    .iter_mut()
+                    .chain(&mut cex_dex.optimal_route_details)
+                    .chain(&mut cex_dex.optimistic_route_details)
+                    .chain(cex_dex.per_exchange_details.iter_mut().flatten())
+                    .map(|details| &mut details.dex_exchange),
+            )
+            .collect(),
+        BundleData::Liquidation(liquidation) => swap_protocols(&mut liquidation.liquidation_swaps)
+            .chain(liquidation.liquidations.iter_mut().map(|l| &mut l.protocol))
+            .chain(liquidation.loans.iter_mut().map(|loan| &mut loan.protocol))
+            .chain(
+                liquidation
+                    .repayments
+                    .iter_mut()
+                    .map(|repay| &mut repay.protocol),
+            )
+            .chain(
+                liquidation
+                    .deposits
+                    .iter_mut()
+                    .map(|deposit| &mut deposit.protocol),
+            )
+            .chain(
+                liquidation
+                    .withdrawals
+                    .iter_mut()
+                    .map(|withdraw| &mut withdraw.protocol),
+            )
+            .chain(
+                liquidation
+                    .oracle_update
+                    .iter_mut()
+                    .map(|update| &mut update.protocol),
+            )
+            .collect(),
+        BundleData::FailedAttempt(attempt) => attempt.protocols.iter_mut().collect(),
+        BundleData::Sniping(sniping) => std::iter::once(&mut sniping.protocol)
+            .chain(swap_protocols(
+                sniping
+                    .snipe_swaps
+                    .iter_mut()
+                    .chain(&mut sniping.exit_swaps)
+                    .flatten(),
+            ))
+            .collect(),
+        BundleData::Unknown(_) => vec![],
+    }
+}
+
+fn swap_protocols<'a>(
+    swaps: impl IntoIterator<Item = &'a mut NormalizedSwap>,
+) -> impl Iterator<Item = &'a mut Protocol> {
+    swaps.into_iter().map(|swap| &mut swap.protocol)
+}
+
+/// A table as it was encoded at an older schema version, under the same name
+macro_rules! legacy_table {
+    ($(#[$attrs:meta])* $name:ident($table:ident) {
+        key: $key:ident,
+        value: $value:ident,
+        compressed_value: $c_value:ident
+    }) => {
+        $(#[$attrs])*
+        #[derive(Clone, Copy, Debug, Default)]
+        pub struct $name;
+
+        impl reth_db::table::Table for $name {
+            const TABLE: reth_db::Tables = reth_db::Tables::CanonicalHeaders;
+            const NAME: &'static str = <$table as reth_db::table::Table>::NAME;
+            type Key = $key;
+            type Value = $c_value;
+        }
+
+        impl CompressedTable for $name {
+            type DecompressedValue = $value;
+            const INIT_CHUNK_SIZE: Option<usize> = None;
+            const INIT_QUERY: Option<&'static str> = None;
+            const HTTP_ENDPOINT: Option<&'static str> = None;
+            const INIT_FLAG: Option<u16> = None;
+        }
+    };
+}
+
+legacy_table!(
+    /// [`MevBlocks`] at v1
+    MevBlocksV1(MevBlocks) {
+        key: u64,
+        value: MevBlockWithClassifiedV1,
+        compressed_value: MevBlockWithClassifiedV1Redefined
+    }
+);
+
+legacy_table!(
+    /// [`SearcherEOAs`] at v1
+    SearcherEOAsV1(SearcherEOAs) {
+        key: Address,
+        value: SearcherInfoV1,
+        compressed_value: SearcherInfoV1Redefined
+    }
+);
+
+legacy_table!(
+    /// [`SearcherContracts`] at v1
+    SearcherContractsV1(SearcherContracts) {
+        key: Address,
+        value: SearcherInfoV1,
+        compressed_value: SearcherInfoV1Redefined
+    }
+);
+
+#[cfg(test)]
+mod tests {
+    use brontes_types::{
+        db::{
+            legacy::{
+                mev_blocks_v1::{BundleDataV1, BundleHeaderV1, BundleV1, MevBlockV1, SandwichV1},
+                searcher_v1::{MevCountV1, MevTypeV1},
+            },
+            pool_lvr,
+            searcher::SearcherInfo,
+        },
+        mev::{AtomicArb, Bundle, MevType},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_every_table_can_be_migrated_from_baseline() {
+        for table in Tables::ALL {
+            let path = migration_path(table, SchemaVersion::BASELINE).unwrap();
+            assert_eq!(
+                path.last().map_or(SchemaVersion::BASELINE, |m| m.to()),
+                table.schema_version(),
+                "{table}"
+            );
+            assert!(migration_path(table, table.schema_version())
+                .unwrap()
+                .is_empty());
+        }
+
+        assert!(migration_path(Tables::MevBlocks, SchemaVersion(u16::MAX)).is_err());
+    }
+
+    #[test]
+    fn test_migrate_searchers_in_place() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Libmdbx::init_db(dir.path(), None).unwrap();
+
+        let searcher = Address::with_last_byte(1);
+        let v1 = SearcherInfoV1 {
+            mev_count: MevCountV1 {
+                bundle_count: 2,
+                sandwich_count: Some(2),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        db.update_schema(Tables::SearcherEOAs, SchemaVersion::BASELINE, |tx| {
+            Ok(tx.put::<SearcherEOAsV1>(searcher, v1)?)
+        })
+        .unwrap();
+
+        let plan = plan_migrations(&db).unwrap();
+        assert_eq!(plan.len(), 1);
+        assert!(db.check_schema_versions().is_err());
+
+        migrate(&db, None).unwrap();
+        db.check_schema_versions().unwrap();
+
+        let migrated: SearcherInfo = db
+            .view_db(|tx| Ok(tx.get::<SearcherEOAs>(searcher)?))
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(migrated.mev_count.bundle_count, 2);
+        assert_eq!(migrated.mev_count.sandwich_count, Some(2));
+        assert!(migrated.is_searcher_of_type(MevType::Sandwich));
+        assert!(!migrated.is_searcher_of_type(MevType::Sniping));
+    }
+
+    #[test]
+    fn test_migrate_unknown_lvr_protocols() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Libmdbx::init_db(dir.path(), None).unwrap();
+
+        let lvr = |pool, protocol| pool_lvr::PoolLvr {
+            block_number: 10,
+            pool: Address::with_last_byte(pool),
+            protocol,
+            ..Default::default()
+        };
+        // at v1, `Erc4626` has the discriminant `Unknown` had
+        let v1 = BlockPoolLvr {
+            block_number: 10,
+            pools:        vec![lvr(1, Protocol::Erc4626), lvr(2, Protocol::UniswapV3)],
+        };
+        db.update_schema(Tables::PoolLvr, SchemaVersion::BASELINE, |tx| {
+            Ok(tx.put::<PoolLvr>(10, v1)?)
+        })
+        .unwrap();
+
+        let plan = plan_migrations(&db).unwrap();
+        assert_eq!(plan.len(), 1);
+        assert_eq!(plan[0].1.len(), 2);
+
+        migrate(&db, None).unwrap();
+        db.check_schema_versions().unwrap();
+
+        let migrated: BlockPoolLvr = db
+            .view_db(|tx| Ok(tx.get::<PoolLvr>(10)?))
+            .unwrap()
+            .unwrap();
+
+        let protocols = migrated
+            .pools
+            .iter()
+            .map(|pool| pool.protocol)
+            .collect::<Vec<_>>();
+        assert_eq!(protocols, vec![Protocol::Unknown, Protocol::UniswapV3]);
+    }
+
+    #[test]
+    fn test_migrate_mev_blocks_from_v1() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Libmdbx::init_db(dir.path(), None).unwrap();
+
+        let swap = |protocol| NormalizedSwap { protocol, ..Default::default() };
+        let header = |mev_type| BundleHeaderV1 { block_number: 10, mev_type, ..Default::default() };
+        // at v1, `UniswapV4` has the discriminant `Unknown` had
+        let v1 = MevBlockWithClassifiedV1 {
+            block: MevBlockV1 {
+                block_number: 10,
+                mev_count: MevCountV1 {
+                    bundle_count: 2,
+                    sandwich_count: Some(1),
+                    atomic_backrun_count: Some(1),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            mev:   vec![
+                BundleV1 {
+                    header: header(MevTypeV1::Sandwich),
+                    data:   BundleDataV1::Sandwich(SandwichV1 {
+                        block_number: 10,
+                        frontrun_swaps: vec![vec![swap(Protocol::UniswapV2)]],
+                        backrun_swaps: vec![swap(Protocol::UniswapV4)],
+                        ..Default::default()
+                    }),
+                },
+                BundleV1 {
+                    header: header(MevTypeV1::AtomicArb),
+                    data:   BundleDataV1::AtomicArb(AtomicArb {
+                        swaps: vec![swap(Protocol::UniswapV4), swap(Protocol::UniswapV3)],
+                        ..Default::default()
+                    }),
+                },
+            ],
+        };
+        db.update_schema(Tables::MevBlocks, SchemaVersion::BASELINE, |tx| {
+            Ok(tx.put::<MevBlocksV1>(10, v1)?)
+        })
+        .unwrap();
+
+        let plan = plan_migrations(&db).unwrap();
+        assert_eq!(plan.len(), 1);
+        assert_eq!(plan[0].1.len(), 3);
+
+        migrate(&db, None).unwrap();
+        db.check_schema_versions().unwrap();
+
+        let migrated: MevBlockWithClassified = db
+            .view_db(|tx| Ok(tx.get::<MevBlocks>(10)?))
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(migrated.block.mev_count.sandwich_count, Some(1));
+        assert_eq!(migrated.block.mev_count.sniping_count, None);
+
+        let [sandwich, arb]: [Bundle; 2] = migrated.mev.try_into().unwrap();
+        assert_eq!(sandwich.header.mev_type, MevType::Sandwich);
+        let BundleData::Sandwich(sandwich) = sandwich.data else { panic!("not a sandwich") };
+        assert_eq!(sandwich.frontrun_block_number, 10);
+        assert!(sandwich.victim_losses.is_empty());
+        assert_eq!(sandwich.frontrun_swaps[0][0].protocol, Protocol::UniswapV2);
+        assert_eq!(sandwich.backrun_swaps[0].protocol, Protocol::Unknown);
+
+        assert_eq!(arb.header.mev_type, MevType::AtomicArb);
+        let BundleData::AtomicArb(arb) = arb.data else { panic!("not an atomic arb") };
+        let protocols = arb
+            .swaps
+            .iter()
+            .map(|swap| swap.protocol)
+            .collect::<Vec<_>>();
+        assert_eq!(protocols, vec![Protocol::Unknown, Protocol::UniswapV3]);
+    }
+}
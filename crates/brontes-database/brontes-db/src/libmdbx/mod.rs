@@ -18,11 +18,14 @@ pub mod libmdbx_writer;
 
 pub mod initialize;
 mod libmdbx_read_write;
+pub mod migrations;
+pub mod schema;
 use brontes_libmdbx::{RO, RW};
 use env::{DatabaseArguments, DatabaseEnv, DatabaseEnvKind};
 use eyre::Context;
 use implementation::compressed_wrappers::tx::CompressedLibmdbxTx;
 use initialize::LibmdbxInitializer;
+use itertools::Itertools;
 pub use libmdbx_read_write::{
     determine_eth_prices, LibmdbxInit, LibmdbxReadWriter, StateToInitialize,
 };
@@ -67,8 +70,22 @@ pub(crate) fn mdbx_result(err_code: c_int) -> eyre::Result<bool> {
 
 impl Libmdbx {
     /// Opens up an existing database or creates a new one at the specified
-    /// path. Creates tables if necessary. Opens in read/write mode.
+    /// path. Creates tables if necessary. Opens in read/write mode. Errors if
+    /// the tables were written with a different schema version than the one
+    /// of this build.
     pub fn init_db<P: AsRef<Path>>(path: P, log_level: Option<LogLevel>) -> eyre::Result<Self> {
+        let this = Self::init_db_unchecked(path, log_level)?;
+        this.check_schema_versions()?;
+
+        Ok(this)
+    }
+
+    /// Opens the database like [`Libmdbx::init_db`] without checking the
+    /// schema versions of its tables, which is only safe for migrating it
+    pub fn init_db_unchecked<P: AsRef<Path>>(
+        path: P,
+        log_level: Option<LogLevel>,
+    ) -> eyre::Result<Self> {
         let rpath = path.as_ref();
         if is_database_empty(rpath) {
             std::fs::create_dir_all(rpath).wrap_err_with(|| {
@@ -100,6 +117,7 @@ impl Libmdbx {
 
         let this = Self(db);
         this.create_tables()?;
+        this.record_missing_schema_versions()?;
 
         Ok(this)
     }
@@ -123,12 +141,44 @@ impl Libmdbx {
         T: CompressedTable,
         T::Value: From<T::DecompressedValue> + Into<T::DecompressedValue>,
     {
+        let table: Tables = T::NAME
+            .parse()
+            .map_err(|e| eyre::eyre!("{e}: {}", T::NAME))?;
+        if table == Tables::SchemaVersions {
+            eyre::bail!("the schema versions can't be cleared")
+        }
+
         info!(target: "brontes::init", "{} -- Clearing Table", T::NAME);
-        let tx = self.rw_tx()?;
-        tx.clear::<T>()?;
+        // an empty table can be written with the current schema
+        self.update_schema(table, table.schema_version(), |tx| Ok(tx.clear::<T>()?))
+    }
+
+    /// Copies all entries of a table into the same table of `dst`, in batches
+    /// so the write transactions stay small
+    pub fn copy_table<T>(&self, dst: &Libmdbx) -> eyre::Result<usize>
+    where
+        T: CompressedTable,
+        T::Value: From<T::DecompressedValue> + Into<T::DecompressedValue>,
+    {
+        const BATCH_SIZE: usize = 10_000;
+
+        let tx = self.no_timeout_ro_tx()?;
+        let mut cursor = tx.cursor_read::<T>()?;
+
+        let mut copied = 0;
+        for batch in &cursor.walk(None)?.chunks(BATCH_SIZE) {
+            let batch = batch.collect::<Result<Vec<_>, _>>()?;
+            copied += batch.len();
+
+            dst.update_db(|dst_tx| {
+                batch
+                    .into_iter()
+                    .try_for_each(|(key, value)| dst_tx.put::<T>(key, value))
+            })??;
+        }
         tx.commit()?;
 
-        Ok(())
+        Ok(copied)
     }
 
     /// writes to a table
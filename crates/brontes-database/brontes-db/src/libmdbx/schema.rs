@@ -0,0 +1,182 @@
+//! Schema versions of the libmdbx tables.
+//!
+//! The values of every table are stored in their rkyv encoding, which isn't
+//! self describing, so reading a table written with an older encoding of its
+//! value returns garbage. Each table's version is recorded in the
+//! [`SchemaVersions`] table, and a database whose tables don't match the
+//! versions of this build is refused when opened. `brontes db migrate` brings
+//! it up to date with the [`MIGRATIONS`](super::migrations::MIGRATIONS).
+use std::fmt::Write;
+
+use brontes_libmdbx::{TransactionKind, RW};
+use brontes_types::db::schema_version::SchemaVersion;
+use itertools::Itertools;
+
+use super::{
+    implementation::compressed_wrappers::tx::CompressedLibmdbxTx,
+    tables::{SchemaVersions, Tables},
+    Libmdbx,
+};
+
+impl Tables {
+    /// The version of the encoding of the table's values. Whenever the
+    /// encoding of a table's value changes, its version has to be bumped and a
+    /// migration from the previous version registered in
+    /// [`MIGRATIONS`](super::migrations::MIGRATIONS).
+    pub const fn schema_version(&self) -> SchemaVersion {
+        match self {
+            // new mev types and fields of the mev bundles and counts, then new
+            // protocols moved the discriminant of `Protocol::Unknown` twice
+            Tables::MevBlocks => SchemaVersion(4),
+            // failed attempts and sniping in the mev counts and pnl
+            Tables::SearcherEOAs | Tables::SearcherContracts => SchemaVersion(2),
+            // new protocols moved the discriminant of `Protocol::Unknown`
+            Tables::AddressToProtocolInfo => SchemaVersion(4),
+            Tables::AnalysisRollups | Tables::PoolLvr => SchemaVersion(3),
+            Tables::TokenDecimals
+            | Tables::CexPrice
+            | Tables::BlockInfo
+            | Tables::DexPrice
+            | Tables::PoolCreationBlocks
+            | Tables::TxTraces
+            | Tables::Builder
+            | Tables::AddressMeta
+            | Tables::InitializedState
+            | Tables::CexTrades
+            | Tables::AddressBundles
+            | Tables::TxBundles
+            | Tables::SchemaVersions
+            | Tables::ForkDetections
+            | Tables::UniswapV4PoolKeys => SchemaVersion::BASELINE,
+        }
+    }
+}
+
+/// A table whose recorded version doesn't match the version of this build
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SchemaMismatch {
+    pub table:    Tables,
+    pub recorded: SchemaVersion,
+    pub expected: SchemaVersion,
+}
+
+impl SchemaMismatch {
+    /// Whether the table was written by a newer version of brontes
+    pub fn is_newer(&self) -> bool {
+        self.recorded > self.expected
+    }
+}
+
+impl Libmdbx {
+    /// The recorded schema version of every table, `None` if it has none
+    pub fn schema_versions(&self) -> eyre::Result<Vec<(Tables, Option<SchemaVersion>)>> {
+        self.view_db(|tx| {
+            Tables::ALL
+                .into_iter()
+                .map(|table| Ok((table, read_schema_version(tx, table)?)))
+                .collect()
+        })
+    }
+
+    /// The tables whose recorded schema version doesn't match the version of
+    /// this build
+    pub fn schema_mismatches(&self) -> eyre::Result<Vec<SchemaMismatch>> {
+        Ok(self
+            .schema_versions()?
+            .into_iter()
+            .filter_map(|(table, recorded)| {
+                let recorded = recorded?;
+                let expected = table.schema_version();
+                (recorded != expected).then_some(SchemaMismatch { table, recorded, expected })
+            })
+            .collect())
+    }
+
+    /// Errors if any table was written with a different schema version than
+    /// the one of this build
+    pub fn check_schema_versions(&self) -> eyre::Result<()> {
+        let mismatches = self.schema_mismatches()?;
+        if mismatches.is_empty() {
+            return Ok(())
+        }
+
+        let mut msg = String::from("the brontes db was written with incompatible table schemas:");
+        for SchemaMismatch { table, recorded, expected } in &mismatches {
+            write!(msg, "\n  {table}: {recorded}, this version of brontes reads {expected}")?;
+        }
+
+        if mismatches.iter().any(SchemaMismatch::is_newer) {
+            write!(
+                msg,
+                "\nit was written by a newer version of brontes, upgrade brontes to read it"
+            )?;
+        } else {
+            write!(msg, "\nrun `brontes db migrate` to migrate it")?;
+        }
+
+        Err(eyre::eyre!(msg))
+    }
+
+    /// Records the schema version of every table that has none yet. Empty
+    /// tables are recorded at the version of this build. Tables with entries
+    /// were written before versions were recorded, so they are at the
+    /// baseline version.
+    pub(crate) fn record_missing_schema_versions(&self) -> eyre::Result<()> {
+        let tx = self.rw_tx()?;
+
+        let missing = Tables::ALL
+            .into_iter()
+            .map(|table| Ok((table, read_schema_version(&tx, table)?)))
+            .filter_map_ok(|(table, version)| version.is_none().then_some(table))
+            .collect::<eyre::Result<Vec<_>>>()?;
+
+        for table in missing {
+            let version = if table.entries(&tx)? == 0 {
+                table.schema_version()
+            } else {
+                SchemaVersion::BASELINE
+            };
+            write_schema_version(&tx, table, version)?;
+        }
+
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    /// Runs `f` in a single transaction that also records `version` as the
+    /// schema version of `table`, so the table is either fully rewritten at
+    /// the new version or not at all
+    pub fn update_schema<F, R>(
+        &self,
+        table: Tables,
+        version: SchemaVersion,
+        f: F,
+    ) -> eyre::Result<R>
+    where
+        F: FnOnce(&CompressedLibmdbxTx<RW>) -> eyre::Result<R>,
+    {
+        let tx = self.rw_tx()?;
+
+        let res = f(&tx)?;
+        write_schema_version(&tx, table, version)?;
+        tx.commit()?;
+
+        Ok(res)
+    }
+}
+
+pub(crate) fn read_schema_version<K: TransactionKind>(
+    tx: &CompressedLibmdbxTx<K>,
+    table: Tables,
+) -> eyre::Result<Option<SchemaVersion>> {
+    Ok(tx.get::<SchemaVersions>(table.name().to_string())?)
+}
+
+pub(crate) fn write_schema_version(
+    tx: &CompressedLibmdbxTx<RW>,
+    table: Tables,
+    version: SchemaVersion,
+) -> eyre::Result<()> {
+    Ok(tx.put::<SchemaVersions>(table.name().to_string(), version)?)
+}
@@ -10,13 +10,18 @@ use brontes_types::{
     db::{
         address_metadata::{AddressMetadata, AddressMetadataRedefined},
         address_to_protocol_info::{ProtocolInfo, ProtocolInfoRedefined},
+        analysis_rollup::{BlockAnalysisRollup, BlockAnalysisRollupRedefined, RollupKey},
         builder::{BuilderInfo, BuilderInfoRedefined},
+        bundle_index::{
+            AddressBundleKey, BundleIndexEntry, BundleIndexEntryRedefined, TxBundleKey,
+        },
         cex::{
             quotes::{CexPriceMap, CexPriceMapRedefined},
             trades::{CexTradeMap, CexTradeMapRedefined},
         },
         clickhouse_serde::tx_trace::tx_traces_inner,
         dex::{DexKey, DexQuoteWithIndex, DexQuoteWithIndexRedefined},
+        fork_detection::{ForkDetection, ForkDetectionRedefined},
         initialized_state::{
             InitializedStateMeta, CEX_QUOTES_FLAG, CEX_TRADES_FLAG, DEX_PRICE_FLAG, META_FLAG,
             TRACE_FLAG,
@@ -24,10 +29,13 @@ use brontes_types::{
         metadata::{BlockMetadataInner, BlockMetadataInnerRedefined},
         mev_block::{MevBlockWithClassified, MevBlockWithClassifiedRedefined},
         pool_creation_block::{PoolsToAddresses, PoolsToAddressesRedefined},
+        pool_lvr::{BlockPoolLvr, BlockPoolLvrRedefined},
+        schema_version::SchemaVersion,
         searcher::{SearcherInfo, SearcherInfoRedefined},
         token_info::TokenInfo,
         traces::{TxTracesInner, TxTracesInnerRedefined},
         traits::LibmdbxReader,
+        uniswap_v4::{UniswapV4PoolKey, UniswapV4PoolKeyRedefined},
     },
     serde_utils::*,
     traits::TracingProvider,
@@ -45,20 +53,21 @@ use crate::{
 };
 mod const_sql;
 use alloy_primitives::Address;
+use brontes_libmdbx::TransactionKind;
 //
 // use brontes_types::db::initialized_state::CEX_QUOTES_FLAG;
 //
 // use brontes_types::db::initialized_state::CEX_TRADES_FLAG;
 use const_sql::*;
 use paste::paste;
-use reth_db::TableType;
+use reth_db::{DatabaseError, TableType};
 
 use super::{
-    initialize::LibmdbxInitializer, libmdbx_writer::WriterMessage, types::IntoTableKey,
-    CompressedTable,
+    implementation::compressed_wrappers::tx::CompressedLibmdbxTx, initialize::LibmdbxInitializer,
+    libmdbx_writer::WriterMessage, types::IntoTableKey, CompressedTable, Libmdbx,
 };
 
-pub const NUM_TABLES: usize = 14;
+pub const NUM_TABLES: usize = 21;
 
 macro_rules! tables {
     ($($table:ident),*) => {
@@ -94,6 +103,27 @@ macro_rules! tables {
                 }
             }
 
+            /// The amount of entries in the given table
+            pub fn entries<K: TransactionKind>(
+                &self,
+                tx: &CompressedLibmdbxTx<K>,
+            ) -> Result<usize, DatabaseError> {
+                match self {
+                    $(
+                        Tables::$table => tx.entries::<$table>(),
+                    )*
+                }
+            }
+
+            /// Copies all entries of the given table from `src` to `dst`
+            pub fn copy_table(&self, src: &Libmdbx, dst: &Libmdbx) -> eyre::Result<usize> {
+                match self {
+                    $(
+                        Tables::$table => src.copy_table::<$table>(dst),
+                    )*
+                }
+            }
+
             pub fn init_table(&self, db: &LibmdbxReadWriter) -> eyre::Result<()> {
                 match self {
                     $(
@@ -252,7 +282,14 @@ impl Tables {
                     )
                     .await
             }
-            Tables::MevBlocks => Ok(()),
+            Tables::MevBlocks
+            | Tables::AnalysisRollups
+            | Tables::PoolLvr
+            | Tables::AddressBundles
+            | Tables::TxBundles
+            | Tables::SchemaVersions
+            | Tables::ForkDetections
+            | Tables::UniswapV4PoolKeys => Ok(()),
             Tables::TxTraces => {
                 initializer
                     .initialize_table_from_clickhouse::<TxTraces, TxTracesData>(
@@ -373,6 +410,8 @@ impl Tables {
             Self::MevBlocks => exporter.export_mev_blocks().await,
             Self::SearcherContracts | Self::SearcherEOAs => exporter.export_searcher_info().await,
             Self::Builder => exporter.export_builder_info().await,
+            Self::AnalysisRollups => exporter.export_analysis_rollups().await,
+            Self::PoolLvr => exporter.export_pool_lvr().await,
             _ => unreachable!("Parquet export not yet supported for this table"),
         }
     }
@@ -537,7 +576,14 @@ tables!(
     SearcherEOAs,
     SearcherContracts,
     InitializedState,
-    CexTrades
+    CexTrades,
+    AnalysisRollups,
+    PoolLvr,
+    AddressBundles,
+    TxBundles,
+    SchemaVersions,
+    ForkDetections,
+    UniswapV4PoolKeys
 );
 
 /// Must be in this order when defining
@@ -1031,3 +1077,135 @@ compressed_table!(
         }
     }
 );
+
+compressed_table!(
+    Table AnalysisRollups {
+        Data {
+            #[serde(with = "rollup_key")]
+            key: RollupKey,
+            value: BlockAnalysisRollup,
+            compressed_value: BlockAnalysisRollupRedefined
+        },
+        Init {
+            init_size: None,
+            init_method: Other,
+            http_endpoint: None
+        },
+        CLI {
+            can_insert: False
+        }
+    }
+);
+
+compressed_table!(
+    Table PoolLvr {
+        Data {
+            key: u64,
+            value: BlockPoolLvr,
+            compressed_value: BlockPoolLvrRedefined
+        },
+        Init {
+            init_size: None,
+            init_method: Other,
+            http_endpoint: None
+        },
+        CLI {
+            can_insert: False
+        }
+    }
+);
+
+compressed_table!(
+    Table AddressBundles {
+        Data {
+            #[serde(with = "address_bundle_key")]
+            key: AddressBundleKey,
+            value: BundleIndexEntry,
+            compressed_value: BundleIndexEntryRedefined
+        },
+        Init {
+            init_size: None,
+            init_method: Other,
+            http_endpoint: None
+        },
+        CLI {
+            can_insert: False
+        }
+    }
+);
+
+compressed_table!(
+    Table TxBundles {
+        Data {
+            #[serde(with = "tx_bundle_key")]
+            key: TxBundleKey,
+            value: BundleIndexEntry,
+            compressed_value: BundleIndexEntryRedefined
+        },
+        Init {
+            init_size: None,
+            init_method: Other,
+            http_endpoint: None
+        },
+        CLI {
+            can_insert: False
+        }
+    }
+);
+
+compressed_table!(
+    Table SchemaVersions {
+        Data {
+            // the name of the table
+            key: String,
+            value: SchemaVersion,
+            compressed_value: SchemaVersion
+        },
+        Init {
+            init_size: None,
+            init_method: Other,
+            http_endpoint: None
+        },
+        CLI {
+            can_insert: False
+        }
+    }
+);
+
+compressed_table!(
+    Table ForkDetections {
+        Data {
+            #[serde(with = "address_string")]
+            key: Address,
+            value: ForkDetection,
+            compressed_value: ForkDetectionRedefined
+        },
+        Init {
+            init_size: None,
+            init_method: Other,
+            http_endpoint: None
+        },
+        CLI {
+            can_insert: False
+        }
+    }
+);
+
+compressed_table!(
+    Table UniswapV4PoolKeys {
+        Data {
+            #[serde(with = "address_string")]
+            key: Address,
+            value: UniswapV4PoolKey,
+            compressed_value: UniswapV4PoolKeyRedefined
+        },
+        Init {
+            init_size: None,
+            init_method: Other,
+            http_endpoint: None
+        },
+        CLI {
+            can_insert: False
+        }
+    }
+);
@@ -0,0 +1,260 @@
+use std::sync::Arc;
+
+use arrow::{
+    array::{Array, Float64Array},
+    datatypes::{DataType, Field, Schema},
+    error::ArrowError,
+    record_batch::RecordBatch,
+};
+use brontes_types::db::analysis_rollup::{AnalysisRollupRow, BlockAnalysisRollup};
+use itertools::Itertools;
+
+use super::utils::{
+    build_float64_array, build_string_array, build_uint64_array, get_list_float_array_from_owned,
+    get_list_string_array_from_owned, get_string_array_from_owned,
+};
+
+/// One row per window and mev type, the same layout as the clickhouse table
+pub fn analysis_rollups_to_record_batch(
+    rollups: Vec<BlockAnalysisRollup>,
+) -> Result<RecordBatch, ArrowError> {
+    let rows = rollups
+        .iter()
+        .flat_map(BlockAnalysisRollup::into_rows)
+        .collect_vec();
+
+    let strings = |f: fn(&AnalysisRollupRow) -> String| {
+        Arc::new(build_string_array(rows.iter().map(f).collect_vec())) as Arc<dyn Array>
+    };
+    let uints = |f: fn(&AnalysisRollupRow) -> u64| {
+        Arc::new(build_uint64_array(rows.iter().map(f).collect_vec())) as Arc<dyn Array>
+    };
+    let floats = |f: fn(&AnalysisRollupRow) -> f64| {
+        Arc::new(build_float64_array(rows.iter().map(f).collect_vec())) as Arc<dyn Array>
+    };
+    let string_lists = |f: fn(&AnalysisRollupRow) -> Vec<String>| {
+        Arc::new(get_list_string_array_from_owned(rows.iter().map(f).collect_vec()))
+            as Arc<dyn Array>
+    };
+    let float_lists = |f: fn(&AnalysisRollupRow) -> Vec<f64>| {
+        Arc::new(get_list_float_array_from_owned(rows.iter().map(f).collect_vec()))
+            as Arc<dyn Array>
+    };
+
+    let mut fields = vec![
+        Field::new("window", DataType::Utf8, false),
+        Field::new("window_start", DataType::UInt64, false),
+        Field::new("mev_type", DataType::Utf8, false),
+        Field::new("start_block", DataType::UInt64, false),
+        Field::new("end_block", DataType::UInt64, false),
+        Field::new("block_count", DataType::UInt64, false),
+        Field::new("bundle_count", DataType::UInt64, false),
+        Field::new("total_profit", DataType::Float64, false),
+        Field::new("total_revenue", DataType::Float64, false),
+        Field::new("average_profit_margin", DataType::Float64, false),
+    ];
+    let mut columns = vec![
+        strings(|row| row.window.clone()),
+        uints(|row| row.window_start),
+        strings(|row| row.mev_type.clone()),
+        uints(|row| row.start_block),
+        uints(|row| row.end_block),
+        uints(|row| row.block_count),
+        uints(|row| row.bundle_count),
+        floats(|row| row.total_profit),
+        floats(|row| row.total_revenue),
+        floats(|row| row.average_profit_margin),
+    ];
+
+    let ranked: [(&str, fn(&AnalysisRollupRow) -> Vec<String>, fn(&AnalysisRollupRow) -> Vec<f64>);
+        14] = [
+        (
+            "searcher_eoa_profit",
+            |row| {
+                row.searcher_eoa_profit
+                    .iter()
+                    .map(|a| a.to_string())
+                    .collect()
+            },
+            |row| row.searcher_eoa_profit_amt.clone(),
+        ),
+        (
+            "searcher_eoa_revenue",
+            |row| {
+                row.searcher_eoa_revenue
+                    .iter()
+                    .map(|a| a.to_string())
+                    .collect()
+            },
+            |row| row.searcher_eoa_revenue_amt.clone(),
+        ),
+        (
+            "mev_contract_profit",
+            |row| {
+                row.mev_contract_profit
+                    .iter()
+                    .map(|a| a.to_string())
+                    .collect()
+            },
+            |row| row.mev_contract_profit_amt.clone(),
+        ),
+        (
+            "mev_contract_revenue",
+            |row| {
+                row.mev_contract_revenue
+                    .iter()
+                    .map(|a| a.to_string())
+                    .collect()
+            },
+            |row| row.mev_contract_revenue_amt.clone(),
+        ),
+        (
+            "fund_profit",
+            |row| row.fund_profit.iter().map(|f| f.to_string()).collect(),
+            |row| row.fund_profit_amt.clone(),
+        ),
+        (
+            "fund_revenue",
+            |row| row.fund_revenue.iter().map(|f| f.to_string()).collect(),
+            |row| row.fund_revenue_amt.clone(),
+        ),
+        (
+            "arbed_pool_profit",
+            |row| {
+                row.arbed_pool_profit
+                    .iter()
+                    .map(|a| a.to_string())
+                    .collect()
+            },
+            |row| row.arbed_pool_profit_amt.clone(),
+        ),
+        (
+            "arbed_pool_revenue",
+            |row| {
+                row.arbed_pool_revenue
+                    .iter()
+                    .map(|a| a.to_string())
+                    .collect()
+            },
+            |row| row.arbed_pool_revenue_amt.clone(),
+        ),
+        (
+            "arbed_pair_profit",
+            |row| {
+                row.arbed_pair_profit
+                    .iter()
+                    .map(|p| format!("{}/{}", p.address0, p.address1))
+                    .collect()
+            },
+            |row| row.arbed_pair_profit_amt.clone(),
+        ),
+        (
+            "arbed_pair_revenue",
+            |row| {
+                row.arbed_pair_revenue
+                    .iter()
+                    .map(|p| format!("{}/{}", p.address0, p.address1))
+                    .collect()
+            },
+            |row| row.arbed_pair_revenue_amt.clone(),
+        ),
+        (
+            "arbed_dex_profit",
+            |row| row.arbed_dex_profit.iter().map(|p| p.to_string()).collect(),
+            |row| row.arbed_dex_profit_amt.clone(),
+        ),
+        (
+            "arbed_dex_revenue",
+            |row| {
+                row.arbed_dex_revenue
+                    .iter()
+                    .map(|p| p.to_string())
+                    .collect()
+            },
+            |row| row.arbed_dex_revenue_amt.clone(),
+        ),
+        (
+            "liquidated_tokens_profit",
+            |row| {
+                row.liquidated_tokens_profit
+                    .iter()
+                    .map(|t| t.address.to_string())
+                    .collect()
+            },
+            |row| row.liquidated_tokens_profit_amt.clone(),
+        ),
+        (
+            "liquidated_tokens_revenue",
+            |row| {
+                row.liquidated_tokens_revenue
+                    .iter()
+                    .map(|t| t.address.to_string())
+                    .collect()
+            },
+            |row| row.liquidated_tokens_revenue_amt.clone(),
+        ),
+    ];
+
+    for (name, keys, amounts) in ranked {
+        fields.push(Field::new(
+            name,
+            DataType::List(Arc::new(Field::new("item", DataType::Utf8, true))),
+            true,
+        ));
+        fields.push(Field::new(
+            format!("{name}_amt"),
+            DataType::List(Arc::new(Field::new("item", DataType::Float64, true))),
+            true,
+        ));
+        columns.push(string_lists(keys));
+        columns.push(float_lists(amounts));
+    }
+
+    fields.extend([
+        Field::new("biggest_arb_profit", DataType::Utf8, true),
+        Field::new("biggest_arb_profit_amt", DataType::Float64, true),
+        Field::new("biggest_arb_revenue", DataType::Utf8, true),
+        Field::new("biggest_arb_revenue_amt", DataType::Float64, true),
+    ]);
+    columns.extend([
+        Arc::new(get_string_array_from_owned(
+            rows.iter()
+                .map(|row| row.biggest_arb_profit.map(|tx| tx.to_string()))
+                .collect_vec(),
+        )) as Arc<dyn Array>,
+        Arc::new(Float64Array::from(
+            rows.iter()
+                .map(|row| row.biggest_arb_profit_amt)
+                .collect_vec(),
+        )),
+        Arc::new(get_string_array_from_owned(
+            rows.iter()
+                .map(|row| row.biggest_arb_revenue.map(|tx| tx.to_string()))
+                .collect_vec(),
+        )),
+        Arc::new(Float64Array::from(
+            rows.iter()
+                .map(|row| row.biggest_arb_revenue_amt)
+                .collect_vec(),
+        )),
+    ]);
+
+    let totals: [(&str, fn(&AnalysisRollupRow) -> f64); 9] = [
+        ("total_usd_liquidated", |row| row.total_usd_liquidated),
+        ("builder_profit_usd", |row| row.builder_profit_usd),
+        ("builder_profit_eth", |row| row.builder_profit_eth),
+        ("builder_revenue_usd", |row| row.builder_revenue_usd),
+        ("builder_revenue_eth", |row| row.builder_revenue_eth),
+        ("builder_mev_profit_usd", |row| row.builder_mev_profit_usd),
+        ("builder_mev_profit_eth", |row| row.builder_mev_profit_eth),
+        ("proposer_profit_usd", |row| row.proposer_profit_usd),
+        ("proposer_profit_eth", |row| row.proposer_profit_eth),
+    ];
+
+    for (name, total) in totals {
+        fields.push(Field::new(name, DataType::Float64, false));
+        columns.push(floats(total));
+    }
+
+    RecordBatch::try_new(Arc::new(Schema::new(fields)), columns)
+}
@@ -129,6 +129,8 @@ fn get_mev_count_array(mev_blocks: &Vec<MevBlock>) -> StructArray {
     let mut jit_count_builder = UInt64Builder::new();
     let mut jit_sandwich_count_builder = UInt64Builder::new();
     let mut searcher_tx_count_builder = UInt64Builder::new();
+    let mut failed_attempt_count_builder = UInt64Builder::new();
+    let mut sniping_count_builder = UInt64Builder::new();
 
     for block in mev_blocks {
         mev_count_builder.append_value(block.mev_count.bundle_count);
@@ -139,6 +141,8 @@ fn get_mev_count_array(mev_blocks: &Vec<MevBlock>) -> StructArray {
         jit_count_builder.append_option(block.mev_count.jit_count);
         jit_sandwich_count_builder.append_option(block.mev_count.jit_sandwich_count);
         searcher_tx_count_builder.append_option(block.mev_count.searcher_tx_count);
+        failed_attempt_count_builder.append_option(block.mev_count.failed_attempt_count);
+        sniping_count_builder.append_option(block.mev_count.sniping_count);
     }
 
     let mev_count_array = mev_count_builder.finish();
@@ -149,6 +153,8 @@ fn get_mev_count_array(mev_blocks: &Vec<MevBlock>) -> StructArray {
     let jit_count_array = jit_count_builder.finish();
     let jit_sandwich_count_array = jit_sandwich_count_builder.finish();
     let searcher_tx_count_array = searcher_tx_count_builder.finish();
+    let failed_attempt_count_array = failed_attempt_count_builder.finish();
+    let sniping_count_array = sniping_count_builder.finish();
 
     let fields = vec![
         Field::new("mev_count", DataType::UInt64, false),
@@ -159,6 +165,8 @@ fn get_mev_count_array(mev_blocks: &Vec<MevBlock>) -> StructArray {
         Field::new("jit_count", DataType::UInt64, true),
         Field::new("jit_sandwich_count", DataType::UInt64, true),
         Field::new("searcher_tx_count", DataType::UInt64, true),
+        Field::new("failed_attempt_count", DataType::UInt64, true),
+        Field::new("sniping_count", DataType::UInt64, true),
     ];
 
     let arrays = vec![
@@ -170,6 +178,8 @@ fn get_mev_count_array(mev_blocks: &Vec<MevBlock>) -> StructArray {
         Arc::new(jit_count_array) as ArrayRef,
         Arc::new(jit_sandwich_count_array) as ArrayRef,
         Arc::new(searcher_tx_count_array) as ArrayRef,
+        Arc::new(failed_attempt_count_array) as ArrayRef,
+        Arc::new(sniping_count_array) as ArrayRef,
     ];
 
     StructArray::try_new(fields.into(), arrays, None).expect("Failed to init struct arrays")
@@ -0,0 +1,72 @@
+use std::sync::Arc;
+
+use arrow::{
+    array::Array,
+    datatypes::{Field, Schema},
+    error::ArrowError,
+    record_batch::RecordBatch,
+};
+use brontes_types::mev::FailedAttempt;
+use itertools::Itertools;
+
+use crate::parquet::{
+    normalized_actions::gas_details::get_gas_details_array,
+    utils::{
+        build_string_array, build_uint64_array, get_list_string_array_from_owned,
+        get_string_array_from_owned,
+    },
+};
+
+pub fn failed_attempt_to_record_batch(
+    failed_attempts: Vec<FailedAttempt>,
+) -> Result<RecordBatch, ArrowError> {
+    let tx_hash_array = get_string_array_from_owned(
+        failed_attempts
+            .iter()
+            .map(|tx| Some(tx.tx_hash.to_string()))
+            .collect_vec(),
+    );
+
+    let block_number_array = build_uint64_array(
+        failed_attempts
+            .iter()
+            .map(|tx| tx.block_number)
+            .collect_vec(),
+    );
+
+    let attempted_mev_type_array = build_string_array(
+        failed_attempts
+            .iter()
+            .map(|tx| tx.attempted_mev_type.to_string())
+            .collect_vec(),
+    );
+
+    let protocols_array = get_list_string_array_from_owned(
+        failed_attempts
+            .iter()
+            .map(|tx| tx.protocols.iter().map(|p| p.to_string()).collect_vec())
+            .collect_vec(),
+    );
+
+    let gas_details_array =
+        get_gas_details_array(failed_attempts.iter().map(|tx| tx.gas_details).collect());
+
+    let schema = Schema::new(vec![
+        Field::new("tx_hash", tx_hash_array.data_type().clone(), false),
+        Field::new("block_number", block_number_array.data_type().clone(), false),
+        Field::new("attempted_mev_type", attempted_mev_type_array.data_type().clone(), false),
+        Field::new("protocols", protocols_array.data_type().clone(), false),
+        Field::new("gas_details", gas_details_array.data_type().clone(), false),
+    ]);
+
+    RecordBatch::try_new(
+        Arc::new(schema),
+        vec![
+            Arc::new(tx_hash_array),
+            Arc::new(block_number_array),
+            Arc::new(attempted_mev_type_array),
+            Arc::new(protocols_array),
+            Arc::new(gas_details_array),
+        ],
+    )
+}
@@ -1,17 +1,22 @@
 use std::sync::Arc;
 
 use arrow::{
-    array::Array,
+    array::{Array, Float64Array, UInt64Array},
     datatypes::{DataType, Field, Schema},
     error::ArrowError,
     record_batch::RecordBatch,
 };
-use brontes_types::mev::Liquidation;
+use brontes_types::{mev::Liquidation, ToFloatNearest};
 use itertools::Itertools;
 
 use crate::parquet::{
     normalized_actions::{
-        gas_details::get_gas_details_array, liquidations::get_normalized_liquidation_list_array,
+        gas_details::get_gas_details_array,
+        lending::{
+            get_normalized_deposit_list_array, get_normalized_loan_list_array,
+            get_normalized_repayment_list_array, get_normalized_withdraw_list_array,
+        },
+        liquidations::get_normalized_liquidation_list_array,
         swaps::get_normalized_swap_list_array,
     },
     utils::get_string_array_from_owned,
@@ -48,6 +53,95 @@ pub fn liquidation_to_record_batch(
             .collect_vec(),
     );
 
+    let loans_array = get_normalized_loan_list_array(
+        liquidations
+            .iter()
+            .map(|liq| liq.loans.iter().collect_vec())
+            .collect_vec(),
+    );
+
+    let repayments_array = get_normalized_repayment_list_array(
+        liquidations
+            .iter()
+            .map(|liq| liq.repayments.iter().collect_vec())
+            .collect_vec(),
+    );
+
+    let deposits_array = get_normalized_deposit_list_array(
+        liquidations
+            .iter()
+            .map(|liq| liq.deposits.iter().collect_vec())
+            .collect_vec(),
+    );
+
+    let withdrawals_array = get_normalized_withdraw_list_array(
+        liquidations
+            .iter()
+            .map(|liq| liq.withdrawals.iter().collect_vec())
+            .collect_vec(),
+    );
+
+    let oracle_update_tx_hash_array = get_string_array_from_owned(
+        liquidations
+            .iter()
+            .map(|liq| liq.is_oracle_backrun().then(|| liq.trigger.to_string()))
+            .collect(),
+    );
+
+    let oracle_array = get_string_array_from_owned(
+        liquidations
+            .iter()
+            .map(|liq| liq.oracle_update.as_ref().map(|u| u.oracle.to_string()))
+            .collect(),
+    );
+
+    let oracle_asset_array = get_string_array_from_owned(
+        liquidations
+            .iter()
+            .map(|liq| {
+                liq.oracle_update
+                    .as_ref()
+                    .map(|u| u.base_token.address.to_string())
+            })
+            .collect(),
+    );
+
+    let oracle_previous_price_array = Float64Array::from(
+        liquidations
+            .iter()
+            .map(|liq| {
+                liq.oracle_update
+                    .as_ref()
+                    .map(|u| u.previous_price.clone().to_float())
+            })
+            .collect_vec(),
+    );
+
+    let oracle_price_array = Float64Array::from(
+        liquidations
+            .iter()
+            .map(|liq| {
+                liq.oracle_update
+                    .as_ref()
+                    .map(|u| u.price.clone().to_float())
+            })
+            .collect_vec(),
+    );
+
+    let oracle_price_delta_array = Float64Array::from(
+        liquidations
+            .iter()
+            .map(|liq| liq.oracle_price_delta())
+            .collect_vec(),
+    );
+
+    let txs_after_oracle_update_array = UInt64Array::from(
+        liquidations
+            .iter()
+            .map(|liq| liq.txs_after_oracle_update)
+            .collect_vec(),
+    );
+
     let gas_details_array =
         get_gas_details_array(liquidations.iter().map(|liq| liq.gas_details).collect());
 
@@ -56,6 +150,17 @@ pub fn liquidation_to_record_batch(
         Field::new("trigger", DataType::Utf8, false),
         Field::new("liquidation_swaps", liquidation_swaps_array.data_type().clone(), false),
         Field::new("liquidations", liquidations_array.data_type().clone(), false),
+        Field::new("loans", loans_array.data_type().clone(), false),
+        Field::new("repayments", repayments_array.data_type().clone(), false),
+        Field::new("deposits", deposits_array.data_type().clone(), false),
+        Field::new("withdrawals", withdrawals_array.data_type().clone(), false),
+        Field::new("oracle_update_tx_hash", DataType::Utf8, true),
+        Field::new("oracle", DataType::Utf8, true),
+        Field::new("oracle_asset", DataType::Utf8, true),
+        Field::new("oracle_previous_price", DataType::Float64, true),
+        Field::new("oracle_price", DataType::Float64, true),
+        Field::new("oracle_price_delta", DataType::Float64, true),
+        Field::new("txs_after_oracle_update", DataType::UInt64, true),
         Field::new("gas_details", gas_details_array.data_type().clone(), false),
     ]);
 
@@ -66,6 +171,17 @@ pub fn liquidation_to_record_batch(
             Arc::new(trigger_array),
             Arc::new(liquidation_swaps_array),
             Arc::new(liquidations_array),
+            Arc::new(loans_array),
+            Arc::new(repayments_array),
+            Arc::new(deposits_array),
+            Arc::new(withdrawals_array),
+            Arc::new(oracle_update_tx_hash_array),
+            Arc::new(oracle_array),
+            Arc::new(oracle_asset_array),
+            Arc::new(oracle_previous_price_array),
+            Arc::new(oracle_price_array),
+            Arc::new(oracle_price_delta_array),
+            Arc::new(txs_after_oracle_update_array),
             Arc::new(gas_details_array),
         ],
     )
@@ -1,16 +1,20 @@
 mod atomic_arb;
 
 mod cex_dex;
+mod failed_attempt;
 mod jit;
 mod jit_sandwich;
 mod liquidation;
 mod sandwich;
 mod searcher_tx;
+mod sniping;
 
 pub use atomic_arb::*;
 //pub use cex_dex::*;
+pub use failed_attempt::*;
 pub use jit::*;
 pub use jit_sandwich::*;
 pub use liquidation::*;
 pub use sandwich::*;
 pub use searcher_tx::*;
+pub use sniping::*;
@@ -2,11 +2,11 @@ use std::sync::Arc;
 
 use arrow::{
     array::Array,
-    datatypes::{Field, Schema},
+    datatypes::{DataType, Field, Schema},
     error::ArrowError,
     record_batch::RecordBatch,
 };
-use brontes_types::mev::Sandwich;
+use brontes_types::{mev::Sandwich, ToFloatNearest};
 use itertools::Itertools;
 
 use crate::parquet::{
@@ -14,10 +14,16 @@ use crate::parquet::{
         gas_details::{get_gas_details_array, get_gas_details_list_array},
         swaps::get_normalized_swap_list_array,
     },
-    utils::{get_list_string_array_from_owned, get_string_array_from_owned},
+    utils::{
+        build_uint64_array, get_list_float_array_from_owned, get_list_string_array_from_owned,
+        get_string_array_from_owned,
+    },
 };
 
 pub fn sandwich_to_record_batch(sandwiches: Vec<Sandwich>) -> Result<RecordBatch, ArrowError> {
+    let frontrun_block_number_array =
+        build_uint64_array(sandwiches.iter().map(|s| s.frontrun_block_number).collect());
+
     let frontrun_tx_hash_array = get_list_string_array_from_owned(
         sandwiches
             .iter()
@@ -87,7 +93,44 @@ pub fn sandwich_to_record_batch(sandwiches: Vec<Sandwich>) -> Result<RecordBatch
     let backrun_gas_details_array =
         get_gas_details_array(sandwiches.iter().map(|s| s.backrun_gas_details).collect());
 
+    let victim_loss_tx_hashes_array = get_list_string_array_from_owned(
+        sandwiches
+            .iter()
+            .map(|s| {
+                s.victim_losses
+                    .iter()
+                    .map(|loss| loss.tx_hash.to_string())
+                    .collect_vec()
+            })
+            .collect_vec(),
+    );
+
+    let victim_token_amount_lost_array = get_list_float_array_from_owned(
+        sandwiches
+            .iter()
+            .map(|s| {
+                s.victim_losses
+                    .iter()
+                    .map(|loss| loss.token_amount_lost.clone().to_float())
+                    .collect_vec()
+            })
+            .collect_vec(),
+    );
+
+    let victim_amount_lost_usd_array = get_list_float_array_from_owned(
+        sandwiches
+            .iter()
+            .map(|s| {
+                s.victim_losses
+                    .iter()
+                    .map(|loss| loss.amount_lost_usd.clone().to_float())
+                    .collect_vec()
+            })
+            .collect_vec(),
+    );
+
     let schema = Schema::new(vec![
+        Field::new("frontrun_block_number", DataType::UInt64, false),
         Field::new("frontrun_tx_hash", frontrun_tx_hash_array.data_type().clone(), false),
         Field::new("frontrun_swaps", frontrun_swaps_array.data_type().clone(), false),
         Field::new("frontrun_gas_details", frontrun_gas_details_array.data_type().clone(), false),
@@ -105,11 +148,23 @@ pub fn sandwich_to_record_batch(sandwiches: Vec<Sandwich>) -> Result<RecordBatch
         Field::new("backrun_tx_hash", backrun_tx_hash_array.data_type().clone(), false),
         Field::new("backrun_swaps", backrun_swaps_array.data_type().clone(), false),
         Field::new("backrun_gas_details", backrun_gas_details_array.data_type().clone(), false),
+        Field::new("victim_loss_tx_hashes", victim_loss_tx_hashes_array.data_type().clone(), true),
+        Field::new(
+            "victim_token_amount_lost",
+            victim_token_amount_lost_array.data_type().clone(),
+            true,
+        ),
+        Field::new(
+            "victim_amount_lost_usd",
+            victim_amount_lost_usd_array.data_type().clone(),
+            true,
+        ),
     ]);
 
     RecordBatch::try_new(
         Arc::new(schema),
         vec![
+            Arc::new(frontrun_block_number_array),
             Arc::new(frontrun_tx_hash_array),
             Arc::new(frontrun_swaps_array),
             Arc::new(frontrun_gas_details_array),
@@ -119,6 +174,9 @@ pub fn sandwich_to_record_batch(sandwiches: Vec<Sandwich>) -> Result<RecordBatch
             Arc::new(backrun_tx_hash_array),
             Arc::new(backrun_swaps_array),
             Arc::new(backrun_gas_details_array),
+            Arc::new(victim_loss_tx_hashes_array),
+            Arc::new(victim_token_amount_lost_array),
+            Arc::new(victim_amount_lost_usd_array),
         ],
     )
 }
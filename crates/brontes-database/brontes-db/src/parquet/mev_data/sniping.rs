@@ -0,0 +1,155 @@
+use std::sync::Arc;
+
+use arrow::{
+    array::{Array, BooleanArray},
+    datatypes::{DataType, Field, Schema},
+    error::ArrowError,
+    record_batch::RecordBatch,
+};
+use brontes_types::mev::Sniping;
+use itertools::Itertools;
+
+use crate::parquet::{
+    normalized_actions::{
+        gas_details::get_gas_details_list_array, swaps::get_normalized_swap_list_array,
+    },
+    utils::{
+        build_float64_array, build_string_array, build_uint64_array,
+        get_list_string_array_from_owned,
+    },
+};
+
+pub fn sniping_to_record_batch(snipes: Vec<Sniping>) -> Result<RecordBatch, ArrowError> {
+    let block_number_array = build_uint64_array(snipes.iter().map(|s| s.block_number).collect());
+
+    let launch_block_number_array =
+        build_uint64_array(snipes.iter().map(|s| s.launch_block_number).collect());
+
+    let launch_tx_hash_array = build_string_array(
+        snipes
+            .iter()
+            .map(|s| s.launch_tx_hash.to_string())
+            .collect_vec(),
+    );
+
+    let protocol_array =
+        build_string_array(snipes.iter().map(|s| s.protocol.to_string()).collect_vec());
+
+    let pool_array = build_string_array(snipes.iter().map(|s| s.pool.to_string()).collect_vec());
+
+    let token_address_array = build_string_array(
+        snipes
+            .iter()
+            .map(|s| s.token.address.to_string())
+            .collect_vec(),
+    );
+
+    let token_symbol_array = build_string_array(
+        snipes
+            .iter()
+            .map(|s| s.token.inner.symbol.clone())
+            .collect_vec(),
+    );
+
+    let blocks_after_launch_array =
+        build_uint64_array(snipes.iter().map(|s| s.blocks_after_launch).collect());
+
+    let snipe_tx_hashes_array = get_list_string_array_from_owned(
+        snipes
+            .iter()
+            .map(|s| {
+                s.snipe_tx_hashes
+                    .iter()
+                    .map(|hash| hash.to_string())
+                    .collect_vec()
+            })
+            .collect_vec(),
+    );
+
+    let snipe_swaps_array = get_normalized_swap_list_array(
+        snipes
+            .iter()
+            .map(|s| s.snipe_swaps.iter().flatten().collect_vec())
+            .collect_vec(),
+    );
+
+    let snipe_gas_details_array =
+        get_gas_details_list_array(snipes.iter().map(|s| &s.snipe_gas_details).collect_vec());
+
+    let exit_tx_hashes_array = get_list_string_array_from_owned(
+        snipes
+            .iter()
+            .map(|s| {
+                s.exit_tx_hashes
+                    .iter()
+                    .map(|hash| hash.to_string())
+                    .collect_vec()
+            })
+            .collect_vec(),
+    );
+
+    let exit_swaps_array = get_normalized_swap_list_array(
+        snipes
+            .iter()
+            .map(|s| s.exit_swaps.iter().flatten().collect_vec())
+            .collect_vec(),
+    );
+
+    let exit_gas_details_array =
+        get_gas_details_list_array(snipes.iter().map(|s| &s.exit_gas_details).collect_vec());
+
+    let tokens_held_array = build_float64_array(snipes.iter().map(|s| s.tokens_held).collect());
+
+    let markout_blocks_array =
+        build_uint64_array(snipes.iter().map(|s| s.markout_blocks).collect());
+
+    let prior_snipes_array = build_uint64_array(snipes.iter().map(|s| s.prior_snipes).collect());
+
+    let repeat_sniper_array =
+        BooleanArray::from(snipes.iter().map(|s| s.repeat_sniper).collect_vec());
+
+    let schema = Schema::new(vec![
+        Field::new("block_number", DataType::UInt64, false),
+        Field::new("launch_block_number", DataType::UInt64, false),
+        Field::new("launch_tx_hash", DataType::Utf8, false),
+        Field::new("protocol", DataType::Utf8, false),
+        Field::new("pool", DataType::Utf8, false),
+        Field::new("token_address", DataType::Utf8, false),
+        Field::new("token_symbol", DataType::Utf8, false),
+        Field::new("blocks_after_launch", DataType::UInt64, false),
+        Field::new("snipe_tx_hashes", snipe_tx_hashes_array.data_type().clone(), false),
+        Field::new("snipe_swaps", snipe_swaps_array.data_type().clone(), false),
+        Field::new("snipe_gas_details", snipe_gas_details_array.data_type().clone(), false),
+        Field::new("exit_tx_hashes", exit_tx_hashes_array.data_type().clone(), false),
+        Field::new("exit_swaps", exit_swaps_array.data_type().clone(), false),
+        Field::new("exit_gas_details", exit_gas_details_array.data_type().clone(), false),
+        Field::new("tokens_held", DataType::Float64, false),
+        Field::new("markout_blocks", DataType::UInt64, false),
+        Field::new("prior_snipes", DataType::UInt64, false),
+        Field::new("repeat_sniper", DataType::Boolean, false),
+    ]);
+
+    RecordBatch::try_new(
+        Arc::new(schema),
+        vec![
+            Arc::new(block_number_array),
+            Arc::new(launch_block_number_array),
+            Arc::new(launch_tx_hash_array),
+            Arc::new(protocol_array),
+            Arc::new(pool_array),
+            Arc::new(token_address_array),
+            Arc::new(token_symbol_array),
+            Arc::new(blocks_after_launch_array),
+            Arc::new(snipe_tx_hashes_array),
+            Arc::new(snipe_swaps_array),
+            Arc::new(snipe_gas_details_array),
+            Arc::new(exit_tx_hashes_array),
+            Arc::new(exit_swaps_array),
+            Arc::new(exit_gas_details_array),
+            Arc::new(tokens_held_array),
+            Arc::new(markout_blocks_array),
+            Arc::new(prior_snipes_array),
+            Arc::new(repeat_sniper_array),
+        ],
+    )
+}
@@ -22,19 +22,23 @@ use crate::Tables;
 
 #[allow(dead_code)]
 mod address_meta;
+mod analysis_rollup;
 mod builder;
 mod bundle_header;
 mod mev_block;
 mod mev_data;
 mod normalized_actions;
+mod pool_lvr;
 mod searcher;
 pub mod utils;
 
 use address_meta::address_metadata_to_record_batch;
+use analysis_rollup::analysis_rollups_to_record_batch;
 use builder::builder_info_to_record_batch;
 use bundle_header::bundle_headers_to_record_batch;
 use mev_block::mev_block_to_record_batch;
 use mev_data::*;
+use pool_lvr::pool_lvr_to_record_batch;
 use searcher::searcher_info_to_record_batch;
 
 pub struct ParquetExporter<DB: LibmdbxReader> {
@@ -84,6 +88,8 @@ where
             jit_sandwich,
             searcher_tx,
             liquidation,
+            failed_attempt,
+            sniping,
         ) = {
             let mut blocks = Vec::new();
             let mut bundle_headers = Vec::new();
@@ -94,6 +100,8 @@ where
             let mut jit_sandwich = Vec::new();
             let mut searcher_tx = Vec::new();
             let mut liquidation = Vec::new();
+            let mut failed_attempt = Vec::new();
+            let mut sniping = Vec::new();
 
             for mb in mev_blocks_iter {
                 blocks.push(mb.block);
@@ -111,6 +119,10 @@ where
                         BundleData::Liquidation(liquidation_data) => {
                             liquidation.push(liquidation_data)
                         }
+                        BundleData::FailedAttempt(failed_attempt_data) => {
+                            failed_attempt.push(failed_attempt_data)
+                        }
+                        BundleData::Sniping(sniping_data) => sniping.push(sniping_data),
                         _ => continue,
                     }
                 }
@@ -126,6 +138,8 @@ where
                 jit_sandwich,
                 searcher_tx,
                 liquidation,
+                failed_attempt,
+                sniping,
             )
         };
 
@@ -231,6 +245,34 @@ where
             }));
         }
 
+        if !failed_attempt.is_empty() {
+            bundle_futures.push(tokio::task::spawn_blocking({
+                let base_dir_path = base_dir_path.clone();
+                move || {
+                    let failed_attempt_batch = failed_attempt_to_record_batch(failed_attempt)
+                        .wrap_err("Failed to convert Failed Attempt data to record batch")?;
+                    sync_write_parquet(
+                        failed_attempt_batch,
+                        get_path(base_dir_path, Tables::MevBlocks, Some(MevType::FailedAttempt))?,
+                    )
+                }
+            }));
+        }
+
+        if !sniping.is_empty() {
+            bundle_futures.push(tokio::task::spawn_blocking({
+                let base_dir_path = base_dir_path.clone();
+                move || {
+                    let sniping_batch = sniping_to_record_batch(sniping)
+                        .wrap_err("Failed to convert Sniping data to record batch")?;
+                    sync_write_parquet(
+                        sniping_batch,
+                        get_path(base_dir_path, Tables::MevBlocks, Some(MevType::Sniping))?,
+                    )
+                }
+            }));
+        }
+
         if !liquidation.is_empty() {
             bundle_futures.push(tokio::task::spawn_blocking({
                 let base_dir_path = base_dir_path.clone();
@@ -334,6 +376,58 @@ where
 
         Ok(())
     }
+
+    /// exports all rollups, the block range doesn't apply as they are keyed
+    /// by window
+    pub async fn export_analysis_rollups(&self) -> Result<(), Error> {
+        let rollups = self
+            .db
+            .fetch_all_analysis_rollups()
+            .wrap_err("Failed to fetch analysis rollups from the database")?;
+
+        if rollups.is_empty() {
+            error!("Analysis rollup table is empty.");
+            return Err(Error::msg("No analysis rollups"))
+        }
+
+        let rollup_batch = analysis_rollups_to_record_batch(rollups)
+            .wrap_err("Failed to convert analysis rollups to record batch")?;
+
+        write_parquet(
+            rollup_batch,
+            get_path(self.base_dir_path.clone(), Tables::AnalysisRollups, None)?,
+        )
+        .await
+        .wrap_err("Failed to write analysis rollups to parquet file")?;
+
+        Ok(())
+    }
+
+    pub async fn export_pool_lvr(&self) -> Result<(), Error> {
+        let pool_lvr = if let Some(end_block) = self.end_block {
+            self.db
+                .try_fetch_pool_lvr(self.start_block, end_block)
+                .wrap_err("Failed to fetch pool lvr from the database")?
+        } else {
+            self.db
+                .fetch_all_pool_lvr(self.start_block)
+                .wrap_err("Failed to fetch pool lvr from the database")?
+        };
+
+        if pool_lvr.is_empty() {
+            error!("No pool lvr fetched for the given range.");
+            return Err(Error::msg("No pool lvr fetched for the given range."))
+        }
+
+        let pool_lvr_batch = pool_lvr_to_record_batch(pool_lvr)
+            .wrap_err("Failed to convert pool lvr to record batch")?;
+
+        write_parquet(pool_lvr_batch, get_path(self.base_dir_path.clone(), Tables::PoolLvr, None)?)
+            .await
+            .wrap_err("Failed to write pool lvr to parquet file")?;
+
+        Ok(())
+    }
 }
 
 async fn write_parquet(record_batch: RecordBatch, file_path: PathBuf) -> Result<()> {
@@ -424,6 +518,8 @@ impl Tables {
             Tables::SearcherEOAs => DEFAULT_SEARCHER_INFO_DIR,
             Tables::SearcherContracts => DEFAULT_SEARCHER_INFO_DIR,
             Tables::Builder => DEFAULT_BUILDER_INFO_DIR,
+            Tables::AnalysisRollups => DEFAULT_ANALYSIS_ROLLUP_DIR,
+            Tables::PoolLvr => DEFAULT_POOL_LVR_DIR,
             _ => panic!("Unsupported table type"),
         }
     }
@@ -433,3 +529,5 @@ pub const DEFAULT_BLOCK_DIR: &str = "mev";
 pub const DEFAULT_METADATA_DIR: &str = "address_metadata";
 pub const DEFAULT_SEARCHER_INFO_DIR: &str = "searcher_info";
 pub const DEFAULT_BUILDER_INFO_DIR: &str = "builder-info";
+pub const DEFAULT_ANALYSIS_ROLLUP_DIR: &str = "analysis_rollups";
+pub const DEFAULT_POOL_LVR_DIR: &str = "pool_lvr";
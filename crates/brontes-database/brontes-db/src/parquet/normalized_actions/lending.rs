@@ -0,0 +1,210 @@
+use arrow::{
+    array::{
+        ArrayBuilder, Float64Builder, ListArray, ListBuilder, StringBuilder, StructBuilder,
+        UInt16Builder,
+    },
+    datatypes::{DataType, Field},
+};
+use brontes_types::{
+    db::token_info::TokenInfoWithAddress,
+    normalized_actions::{
+        NormalizedDeposit, NormalizedLoan, NormalizedRepayment, NormalizedWithdraw,
+    },
+    Protocol, ToFloatNearest,
+};
+use malachite::Rational;
+use reth_primitives::{Address, U256};
+
+/// The columns shared by all lending actions. `from` and `to` are named per
+/// action type.
+struct LendingRow<'a> {
+    protocol:    Protocol,
+    trace_index: u64,
+    pool:        Address,
+    from:        Address,
+    to:          Address,
+    token:       &'a TokenInfoWithAddress,
+    amount:      &'a Rational,
+    msg_value:   U256,
+}
+
+pub fn get_normalized_loan_list_array(loans_list: Vec<Vec<&NormalizedLoan>>) -> ListArray {
+    get_lending_list_array(
+        ["borrower", "recipient", "loaned_token", "loan_amount"],
+        loans_list
+            .into_iter()
+            .map(|loans| {
+                loans
+                    .into_iter()
+                    .map(|loan| LendingRow {
+                        protocol:    loan.protocol,
+                        trace_index: loan.trace_index,
+                        pool:        loan.pool,
+                        from:        loan.borrower,
+                        to:          loan.recipient,
+                        token:       &loan.loaned_token,
+                        amount:      &loan.loan_amount,
+                        msg_value:   loan.msg_value,
+                    })
+                    .collect()
+            })
+            .collect(),
+    )
+}
+
+pub fn get_normalized_repayment_list_array(
+    repayments_list: Vec<Vec<&NormalizedRepayment>>,
+) -> ListArray {
+    get_lending_list_array(
+        ["payer", "borrower", "repayed_token", "repayment_amount"],
+        repayments_list
+            .into_iter()
+            .map(|repayments| {
+                repayments
+                    .into_iter()
+                    .map(|repayment| LendingRow {
+                        protocol:    repayment.protocol,
+                        trace_index: repayment.trace_index,
+                        pool:        repayment.pool,
+                        from:        repayment.payer,
+                        to:          repayment.borrower,
+                        token:       &repayment.repayed_token,
+                        amount:      &repayment.repayment_amount,
+                        msg_value:   repayment.msg_value,
+                    })
+                    .collect()
+            })
+            .collect(),
+    )
+}
+
+pub fn get_normalized_deposit_list_array(deposits_list: Vec<Vec<&NormalizedDeposit>>) -> ListArray {
+    get_lending_list_array(
+        ["depositor", "on_behalf_of", "deposited_token", "deposit_amount"],
+        deposits_list
+            .into_iter()
+            .map(|deposits| {
+                deposits
+                    .into_iter()
+                    .map(|deposit| LendingRow {
+                        protocol:    deposit.protocol,
+                        trace_index: deposit.trace_index,
+                        pool:        deposit.pool,
+                        from:        deposit.depositor,
+                        to:          deposit.on_behalf_of,
+                        token:       &deposit.deposited_token,
+                        amount:      &deposit.deposit_amount,
+                        msg_value:   deposit.msg_value,
+                    })
+                    .collect()
+            })
+            .collect(),
+    )
+}
+
+pub fn get_normalized_withdraw_list_array(
+    withdrawals_list: Vec<Vec<&NormalizedWithdraw>>,
+) -> ListArray {
+    get_lending_list_array(
+        ["withdrawer", "recipient", "withdrawn_token", "withdraw_amount"],
+        withdrawals_list
+            .into_iter()
+            .map(|withdrawals| {
+                withdrawals
+                    .into_iter()
+                    .map(|withdraw| LendingRow {
+                        protocol:    withdraw.protocol,
+                        trace_index: withdraw.trace_index,
+                        pool:        withdraw.pool,
+                        from:        withdraw.withdrawer,
+                        to:          withdraw.recipient,
+                        token:       &withdraw.withdrawn_token,
+                        amount:      &withdraw.withdraw_amount,
+                        msg_value:   withdraw.msg_value,
+                    })
+                    .collect()
+            })
+            .collect(),
+    )
+}
+
+fn get_lending_list_array(
+    [from_name, to_name, token_name, amount_name]: [&str; 4],
+    rows_list: Vec<Vec<LendingRow<'_>>>,
+) -> ListArray {
+    let fields = vec![
+        Field::new("protocol", DataType::Utf8, false),
+        Field::new("trace_index", DataType::UInt16, false),
+        Field::new("pool", DataType::Utf8, false),
+        Field::new(from_name, DataType::Utf8, false),
+        Field::new(to_name, DataType::Utf8, false),
+        Field::new(token_name, DataType::Utf8, false),
+        Field::new(amount_name, DataType::Float64, false),
+        Field::new("msg_value", DataType::Utf8, false),
+    ];
+
+    let builder_array: Vec<Box<dyn ArrayBuilder>> = vec![
+        Box::new(StringBuilder::new()),
+        Box::new(UInt16Builder::new()),
+        Box::new(StringBuilder::new()),
+        Box::new(StringBuilder::new()),
+        Box::new(StringBuilder::new()),
+        Box::new(StringBuilder::new()),
+        Box::new(Float64Builder::new()),
+        Box::new(StringBuilder::new()),
+    ];
+
+    let mut list_builder = ListBuilder::new(StructBuilder::new(fields, builder_array));
+
+    for rows in rows_list {
+        let struct_builder = list_builder.values();
+
+        for row in rows {
+            struct_builder
+                .field_builder::<StringBuilder>(0)
+                .unwrap()
+                .append_value(row.protocol.to_string());
+
+            struct_builder
+                .field_builder::<UInt16Builder>(1)
+                .unwrap()
+                .append_value(row.trace_index as u16);
+
+            struct_builder
+                .field_builder::<StringBuilder>(2)
+                .unwrap()
+                .append_value(row.pool.to_string());
+
+            struct_builder
+                .field_builder::<StringBuilder>(3)
+                .unwrap()
+                .append_value(row.from.to_string());
+
+            struct_builder
+                .field_builder::<StringBuilder>(4)
+                .unwrap()
+                .append_value(row.to.to_string());
+
+            struct_builder
+                .field_builder::<StringBuilder>(5)
+                .unwrap()
+                .append_value(row.token.address.to_string());
+
+            struct_builder
+                .field_builder::<Float64Builder>(6)
+                .unwrap()
+                .append_value(row.amount.clone().to_float());
+
+            struct_builder
+                .field_builder::<StringBuilder>(7)
+                .unwrap()
+                .append_value(row.msg_value.to_string());
+
+            struct_builder.append(true);
+        }
+
+        list_builder.append(true);
+    }
+
+    list_builder.finish()
+}
@@ -1,5 +1,6 @@
 pub mod burns;
 pub mod gas_details;
+pub mod lending;
 pub mod liquidations;
 pub mod mints;
 pub mod swaps;
@@ -0,0 +1,86 @@
+use std::sync::Arc;
+
+use arrow::{
+    array::Array,
+    datatypes::{DataType, Field, Schema},
+    error::ArrowError,
+    record_batch::RecordBatch,
+};
+use brontes_types::db::pool_lvr::BlockPoolLvr;
+use itertools::Itertools;
+
+use super::utils::{
+    build_float64_array, build_string_array, build_uint64_array, get_list_float_array_from_owned,
+    get_list_string_array_from_owned,
+};
+
+/// One row per pool and block, the same layout as the clickhouse table
+pub fn pool_lvr_to_record_batch(pool_lvr: Vec<BlockPoolLvr>) -> Result<RecordBatch, ArrowError> {
+    let pools = pool_lvr
+        .into_iter()
+        .flat_map(|block| block.pools)
+        .collect_vec();
+
+    let block_number_array = build_uint64_array(pools.iter().map(|p| p.block_number).collect());
+    let pool_array = build_string_array(pools.iter().map(|p| p.pool.to_string()).collect_vec());
+    let protocol_array =
+        build_string_array(pools.iter().map(|p| p.protocol.to_string()).collect_vec());
+
+    let token_addresses_array = get_list_string_array_from_owned(
+        pools
+            .iter()
+            .map(|p| p.tokens.iter().map(|t| t.address.to_string()).collect_vec())
+            .collect_vec(),
+    );
+    let token_symbols_array = get_list_string_array_from_owned(
+        pools
+            .iter()
+            .map(|p| p.tokens.iter().map(|t| t.symbol.clone()).collect_vec())
+            .collect_vec(),
+    );
+    let reserve_deltas_array = get_list_float_array_from_owned(
+        pools.iter().map(|p| p.reserve_deltas.clone()).collect_vec(),
+    );
+    let cex_prices_array =
+        get_list_float_array_from_owned(pools.iter().map(|p| p.cex_prices.clone()).collect_vec());
+
+    let swap_count_array = build_uint64_array(pools.iter().map(|p| p.swap_count).collect());
+    let volume_usd_array = build_float64_array(pools.iter().map(|p| p.volume_usd).collect());
+    let toxic_volume_usd_array =
+        build_float64_array(pools.iter().map(|p| p.toxic_volume_usd).collect());
+    let toxicity_array = build_float64_array(pools.iter().map(|p| p.toxicity()).collect());
+    let lvr_usd_array = build_float64_array(pools.iter().map(|p| p.lvr_usd).collect());
+
+    let schema = Schema::new(vec![
+        Field::new("block_number", DataType::UInt64, false),
+        Field::new("pool", DataType::Utf8, false),
+        Field::new("protocol", DataType::Utf8, false),
+        Field::new("token_addresses", token_addresses_array.data_type().clone(), false),
+        Field::new("token_symbols", token_symbols_array.data_type().clone(), false),
+        Field::new("reserve_deltas", reserve_deltas_array.data_type().clone(), false),
+        Field::new("cex_prices", cex_prices_array.data_type().clone(), false),
+        Field::new("swap_count", DataType::UInt64, false),
+        Field::new("volume_usd", DataType::Float64, false),
+        Field::new("toxic_volume_usd", DataType::Float64, false),
+        Field::new("toxicity", DataType::Float64, false),
+        Field::new("lvr_usd", DataType::Float64, false),
+    ]);
+
+    RecordBatch::try_new(
+        Arc::new(schema),
+        vec![
+            Arc::new(block_number_array),
+            Arc::new(pool_array),
+            Arc::new(protocol_array),
+            Arc::new(token_addresses_array),
+            Arc::new(token_symbols_array),
+            Arc::new(reserve_deltas_array),
+            Arc::new(cex_prices_array),
+            Arc::new(swap_count_array),
+            Arc::new(volume_usd_array),
+            Arc::new(toxic_volume_usd_array),
+            Arc::new(toxicity_array),
+            Arc::new(lvr_usd_array),
+        ],
+    )
+}
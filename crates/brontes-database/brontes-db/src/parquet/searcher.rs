@@ -72,6 +72,10 @@ pub fn searcher_info_to_record_batch(
         UInt64Builder::with_capacity(eoa_info.len() + contract_info.len());
     let mut searcher_tx_count_builder =
         UInt64Builder::with_capacity(eoa_info.len() + contract_info.len());
+    let mut failed_attempt_count_builder =
+        UInt64Builder::with_capacity(eoa_info.len() + contract_info.len());
+    let mut sniping_count_builder =
+        UInt64Builder::with_capacity(eoa_info.len() + contract_info.len());
 
     // Flatten TollByType fields for pnl and gas_bids
     let mut pnl_total_builder = Float64Builder::with_capacity(eoa_info.len() + contract_info.len());
@@ -88,6 +92,10 @@ pub fn searcher_info_to_record_batch(
         Float64Builder::with_capacity(eoa_info.len() + contract_info.len());
     let mut pnl_searcher_tx_builder =
         Float64Builder::with_capacity(eoa_info.len() + contract_info.len());
+    let mut pnl_failed_attempt_builder =
+        Float64Builder::with_capacity(eoa_info.len() + contract_info.len());
+    let mut pnl_sniping_builder =
+        Float64Builder::with_capacity(eoa_info.len() + contract_info.len());
 
     let mut gas_bids_total_builder =
         Float64Builder::with_capacity(eoa_info.len() + contract_info.len());
@@ -105,6 +113,10 @@ pub fn searcher_info_to_record_batch(
         Float64Builder::with_capacity(eoa_info.len() + contract_info.len());
     let mut gas_bids_searcher_tx_builder =
         Float64Builder::with_capacity(eoa_info.len() + contract_info.len());
+    let mut gas_bids_failed_attempt_builder =
+        Float64Builder::with_capacity(eoa_info.len() + contract_info.len());
+    let mut gas_bids_sniping_builder =
+        Float64Builder::with_capacity(eoa_info.len() + contract_info.len());
 
     for info in eoa_info.iter().chain(&contract_info) {
         let mev_count = &info.1.mev_count;
@@ -116,6 +128,8 @@ pub fn searcher_info_to_record_batch(
         atomic_backrun_count_builder.append_option(mev_count.atomic_backrun_count);
         liquidation_count_builder.append_option(mev_count.liquidation_count);
         searcher_tx_count_builder.append_option(mev_count.searcher_tx_count);
+        failed_attempt_count_builder.append_option(mev_count.failed_attempt_count);
+        sniping_count_builder.append_option(mev_count.sniping_count);
 
         let pnl = &info.1.pnl;
         pnl_total_builder.append_value(pnl.total);
@@ -126,6 +140,8 @@ pub fn searcher_info_to_record_batch(
         pnl_atomic_backrun_builder.append_option(pnl.atomic_backrun);
         pnl_liquidation_builder.append_option(pnl.liquidation);
         pnl_searcher_tx_builder.append_option(pnl.searcher_tx);
+        pnl_failed_attempt_builder.append_option(pnl.failed_attempt);
+        pnl_sniping_builder.append_option(pnl.sniping);
 
         let gas_bids = &info.1.gas_bids;
         gas_bids_total_builder.append_value(gas_bids.total);
@@ -136,6 +152,8 @@ pub fn searcher_info_to_record_batch(
         gas_bids_atomic_backrun_builder.append_option(gas_bids.atomic_backrun);
         gas_bids_liquidation_builder.append_option(gas_bids.liquidation);
         gas_bids_searcher_tx_builder.append_option(gas_bids.searcher_tx);
+        gas_bids_failed_attempt_builder.append_option(gas_bids.failed_attempt);
+        gas_bids_sniping_builder.append_option(gas_bids.sniping);
     }
 
     let schema = Schema::new(vec![
@@ -156,6 +174,8 @@ pub fn searcher_info_to_record_batch(
         Field::new("atomic_backrun_count", DataType::UInt64, true),
         Field::new("liquidation_count", DataType::UInt64, true),
         Field::new("searcher_tx_count", DataType::UInt64, true),
+        Field::new("failed_attempt_count", DataType::UInt64, true),
+        Field::new("sniping_count", DataType::UInt64, true),
         Field::new("pnl_total", DataType::Float64, false),
         Field::new("pnl_sandwich", DataType::Float64, true),
         Field::new("pnl_cex_dex", DataType::Float64, true),
@@ -164,6 +184,8 @@ pub fn searcher_info_to_record_batch(
         Field::new("pnl_atomic_backrun", DataType::Float64, true),
         Field::new("pnl_liquidation", DataType::Float64, true),
         Field::new("pnl_searcher_tx", DataType::Float64, true),
+        Field::new("pnl_failed_attempt", DataType::Float64, true),
+        Field::new("pnl_sniping", DataType::Float64, true),
         Field::new("gas_bids_total", DataType::Float64, false),
         Field::new("gas_bids_sandwich", DataType::Float64, true),
         Field::new("gas_bids_cex_dex", DataType::Float64, true),
@@ -172,6 +194,8 @@ pub fn searcher_info_to_record_batch(
         Field::new("gas_bids_atomic_backrun", DataType::Float64, true),
         Field::new("gas_bids_liquidation", DataType::Float64, true),
         Field::new("gas_bids_searcher_tx", DataType::Float64, true),
+        Field::new("gas_bids_failed_attempt", DataType::Float64, true),
+        Field::new("gas_bids_sniping", DataType::Float64, true),
     ]);
 
     RecordBatch::try_new(
@@ -190,6 +214,8 @@ pub fn searcher_info_to_record_batch(
             Arc::new(atomic_backrun_count_builder.finish()),
             Arc::new(liquidation_count_builder.finish()),
             Arc::new(searcher_tx_count_builder.finish()),
+            Arc::new(failed_attempt_count_builder.finish()),
+            Arc::new(sniping_count_builder.finish()),
             Arc::new(pnl_total_builder.finish()),
             Arc::new(pnl_sandwich_builder.finish()),
             Arc::new(pnl_cex_dex_builder.finish()),
@@ -198,6 +224,8 @@ pub fn searcher_info_to_record_batch(
             Arc::new(pnl_atomic_backrun_builder.finish()),
             Arc::new(pnl_liquidation_builder.finish()),
             Arc::new(pnl_searcher_tx_builder.finish()),
+            Arc::new(pnl_failed_attempt_builder.finish()),
+            Arc::new(pnl_sniping_builder.finish()),
             Arc::new(gas_bids_total_builder.finish()),
             Arc::new(gas_bids_sandwich_builder.finish()),
             Arc::new(gas_bids_cex_dex_builder.finish()),
@@ -206,6 +234,8 @@ pub fn searcher_info_to_record_batch(
             Arc::new(gas_bids_atomic_backrun_builder.finish()),
             Arc::new(gas_bids_liquidation_builder.finish()),
             Arc::new(gas_bids_searcher_tx_builder.finish()),
+            Arc::new(gas_bids_failed_attempt_builder.finish()),
+            Arc::new(gas_bids_sniping_builder.finish()),
         ],
     )
 }
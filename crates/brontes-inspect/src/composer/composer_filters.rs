@@ -1,49 +1,343 @@
-use brontes_types::mev::{compose_sandwich_jit, Bundle, MevType};
-use lazy_static::lazy_static;
+use alloy_primitives::FixedBytes;
+use brontes_types::{
+    mev::{compose_sandwich_jit, Bundle, Mev, MevType},
+    FastHashSet,
+};
+use itertools::Itertools;
+use serde::Deserialize;
 
-/// Defines rules for composing multiple child MEV types into a single, complex
-/// parent MEV type.
+use super::config::{mev_type, mev_types};
+
+/// A rule for composing multiple child MEV types into a single, complex parent
+/// MEV type.
 ///
-/// This macro creates a static reference (`MEV_COMPOSABILITY_FILTER`) that maps
-/// a list of child MEV types to each parent MEV type along with a composition
-/// function. The composition function is used to combine instances of the child
-/// MEV types into a new instance of the parent MEV type.
+/// The bundles of the first child type are matched against the bundles of the
+/// other child types according to `overlap`. If exactly one bundle of each
+/// child type matches, they are combined into a bundle of the parent type with
+/// the `compose` function and removed from the results.
 ///
-/// # Usage
-/// ```ignore
-/// mev_composability!(
-///     ChildMevType1, ChildMevType2 => ParentMevType;
-/// );
+/// ```toml
+/// [[compose]]
+/// parent = "JitSandwich"
+/// children = ["Sandwich", "Jit"]
+/// compose = "sandwich_jit"
 /// ```
-/// In this example, `ParentMevType` is composed of `ChildMevType1` and
-/// `ChildMevType2` using a specific composition function.
-#[macro_export]
-macro_rules! mev_composability {
-    ($($($child_mev_type:ident),+ => $parent_mev_type:ident;)+) => {
-        lazy_static! {
-            pub static ref MEV_COMPOSABILITY_FILTER:
-                &'static [(MevType, ComposeFunction, Vec<MevType>)] = {
-                &*Box::leak(Box::new([
-                    $((
-                        MevType::$parent_mev_type,
-                        get_compose_fn(MevType::$parent_mev_type),
-                        vec![$(MevType::$child_mev_type),+],
-                    ),)+
-                ]))
-            };
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ComposeRule {
+    #[serde(deserialize_with = "mev_type")]
+    pub parent:   MevType,
+    #[serde(deserialize_with = "mev_types")]
+    pub children: Vec<MevType>,
+    pub compose:  ComposeFunction,
+    #[serde(default)]
+    pub overlap:  Overlap,
+}
+
+impl ComposeRule {
+    pub(crate) fn validate(&self) -> eyre::Result<()> {
+        if self.children.is_empty() {
+            eyre::bail!("compose rule for {} has no children", self.parent)
         }
-    };
+        if self.children.contains(&self.parent) {
+            eyre::bail!("compose rule for {} has itself as a child", self.parent)
+        }
+        if self.children.iter().duplicates().next().is_some() {
+            eyre::bail!("compose rule for {} has duplicate children", self.parent)
+        }
+
+        if self.compose == ComposeFunction::SandwichJit
+            && (self.parent != MevType::JitSandwich
+                || self.children != [MevType::Sandwich, MevType::Jit])
+        {
+            eyre::bail!(
+                "the sandwich_jit compose function only composes Sandwich and Jit into JitSandwich"
+            )
+        }
+
+        if self.compose == ComposeFunction::Merge && self.children[0] != stored_as(self.parent) {
+            eyre::bail!(
+                "the merge compose function keeps the data of the first child, which has to be {} \
+                 for {}",
+                stored_as(self.parent),
+                self.parent
+            )
+        }
+
+        Ok(())
+    }
 }
 
-pub type ComposeFunction = Box<dyn Fn(Vec<Bundle>) -> Option<Bundle> + Send + Sync>;
+/// How the bundles of the other child types have to overlap with a bundle of
+/// the first child type to be composed with it
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Overlap {
+    /// The bundles share at least one transaction
+    #[default]
+    AnyTx,
+    /// All transactions of the other bundle are part of the first bundle
+    AllTxs,
+}
+
+impl Overlap {
+    pub fn matches(&self, first_tx_hashes: &[FixedBytes<32>], other: &Bundle) -> bool {
+        let other_tx_hashes = other.data.mev_transaction_hashes();
+
+        match self {
+            Overlap::AnyTx => other_tx_hashes
+                .iter()
+                .any(|hash| first_tx_hashes.contains(hash)),
+            Overlap::AllTxs => other_tx_hashes
+                .iter()
+                .all(|hash| first_tx_hashes.contains(hash)),
+        }
+    }
+}
+
+/// Combines the child bundles of a [`ComposeRule`], given in the order of its
+/// children, into a bundle of the parent type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ComposeFunction {
+    /// Builds a [`JitLiquiditySandwich`](brontes_types::mev::JitLiquiditySandwich)
+    /// from a sandwich and the jit liquidity around its victims
+    SandwichJit,
+    /// Generic composition, see [`merge_bundles`]
+    Merge,
+}
+
+impl ComposeFunction {
+    pub fn compose(&self, parent: MevType, bundles: Vec<Bundle>) -> Option<Bundle> {
+        match self {
+            ComposeFunction::SandwichJit => compose_sandwich_jit(bundles),
+            ComposeFunction::Merge => merge_bundles(parent, bundles),
+        }
+    }
+}
+
+/// Merges bundles that share transactions into a single bundle of the parent
+/// type. The data is kept from the first bundle, which is the first child of
+/// the rule and has to be of the type the parent is stored as. The header is
+/// taken from the bundle that starts first in the block, with the profits and
+/// bribes of all bundles summed and their balance deltas combined.
+///
+/// The gas of a transaction that is part of several bundles is only counted
+/// once. As the profit of a bundle is net of its gas, the gas that was already
+/// paid for by a previous bundle is added back to the profit of the bundle.
+pub fn merge_bundles(parent: MevType, bundles: Vec<Bundle>) -> Option<Bundle> {
+    let data = bundles.first()?.data.clone();
+    if data.mev_type() != stored_as(parent) {
+        return None
+    }
+
+    let mut header = bundles
+        .iter()
+        .min_by_key(|bundle| bundle.header.tx_index)?
+        .header
+        .clone();
+
+    let mut paid_for = FastHashSet::default();
+    let mut profit_usd = 0.0;
+    let mut bribe_usd = 0.0;
 
-pub fn get_compose_fn(mev_type: MevType) -> ComposeFunction {
+    for bundle in &bundles {
+        let gas_details = bundle.data.gas_details_by_tx();
+        let gas_paid: u128 = gas_details.iter().map(|(_, gas)| gas.gas_paid()).sum();
+        let shared_gas_paid: u128 = gas_details
+            .iter()
+            .filter(|(tx_hash, _)| !paid_for.insert(*tx_hash))
+            .map(|(_, gas)| gas.gas_paid())
+            .sum();
+
+        // the bribe is priced from the gas paid by each transaction of the
+        // bundle, so the shared transactions make up the same share of it
+        let shared_bribe_usd = if gas_paid == 0 {
+            0.0
+        } else {
+            bundle.header.bribe_usd * shared_gas_paid as f64 / gas_paid as f64
+        };
+
+        bribe_usd += bundle.header.bribe_usd - shared_bribe_usd;
+        profit_usd += bundle.header.profit_usd;
+        if !bundle.header.no_pricing_calculated {
+            profit_usd += shared_bribe_usd;
+        }
+    }
+
+    header.mev_type = parent;
+    header.profit_usd = profit_usd;
+    header.bribe_usd = bribe_usd;
+    header.no_pricing_calculated = bundles
+        .iter()
+        .any(|bundle| bundle.header.no_pricing_calculated);
+    header.balance_deltas = bundles
+        .into_iter()
+        .flat_map(|bundle| bundle.header.balance_deltas)
+        .unique_by(|deltas| deltas.tx_hash)
+        .sorted_by_key(|deltas| deltas.tx_hash)
+        .collect();
+
+    Some(Bundle { header, data })
+}
+
+/// The type whose [`BundleData`](brontes_types::mev::BundleData) a bundle of
+/// the given type carries. Composed types without data of their own are stored
+/// with the data of one of their children.
+fn stored_as(mev_type: MevType) -> MevType {
     match mev_type {
-        MevType::JitSandwich => Box::new(compose_sandwich_jit),
-        _ => unreachable!("This mev type does not have a compose function"),
+        MevType::JitCexDex => MevType::CexDexTrades,
+        mev_type => mev_type,
     }
 }
 
-mev_composability!(
-    Sandwich, Jit => JitSandwich;
-);
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::B256;
+    use brontes_types::{
+        mev::{
+            AtomicArb, BundleData, BundleHeader, CexDex, JitLiquidity, Sandwich,
+            TransactionAccounting,
+        },
+        GasDetails,
+    };
+
+    use super::*;
+
+    /// A bundle of the given type, whose transactions each pay 1 wei of gas
+    /// priced at $1
+    fn bundle(mev_type: MevType, tx_index: u64, profit_usd: f64, tx_hashes: &[B256]) -> Bundle {
+        let gas_details = GasDetails { gas_used: 1, effective_gas_price: 1, ..Default::default() };
+        let data = match mev_type {
+            MevType::CexDexTrades => BundleData::CexDex(CexDex {
+                tx_hash: tx_hashes[0],
+                gas_details,
+                ..Default::default()
+            }),
+            MevType::Jit => BundleData::Jit(JitLiquidity {
+                frontrun_mint_tx_hash: tx_hashes[0],
+                frontrun_mint_gas_details: gas_details,
+                backrun_burn_tx_hash: tx_hashes[1],
+                backrun_burn_gas_details: gas_details,
+                ..Default::default()
+            }),
+            _ => BundleData::AtomicArb(AtomicArb {
+                tx_hash: tx_hashes[0],
+                gas_details,
+                ..Default::default()
+            }),
+        };
+
+        Bundle {
+            header: BundleHeader {
+                tx_index,
+                tx_hash: tx_hashes[0],
+                profit_usd,
+                bribe_usd: data.gas_details_by_tx().len() as f64,
+                mev_type,
+                balance_deltas: tx_hashes
+                    .iter()
+                    .map(|tx_hash| TransactionAccounting {
+                        tx_hash: *tx_hash,
+                        ..Default::default()
+                    })
+                    .collect(),
+                ..Default::default()
+            },
+            data,
+        }
+    }
+
+    #[test]
+    fn test_merge_sums_pnl_of_children() {
+        let first = B256::with_last_byte(1);
+        let second = B256::with_last_byte(2);
+        let third = B256::with_last_byte(3);
+
+        let merged = merge_bundles(
+            MevType::JitCexDex,
+            vec![
+                bundle(MevType::CexDexTrades, 4, 10.0, &[third]),
+                bundle(MevType::Jit, 2, 5.5, &[first, second]),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(merged.header.mev_type, MevType::JitCexDex);
+        assert_eq!(merged.header.tx_index, 2);
+        assert_eq!(merged.header.tx_hash, first);
+        assert_eq!(merged.header.profit_usd, 15.5);
+        assert_eq!(merged.header.bribe_usd, 3.0);
+        assert_eq!(
+            merged
+                .header
+                .balance_deltas
+                .iter()
+                .map(|deltas| deltas.tx_hash)
+                .collect_vec(),
+            vec![first, second, third]
+        );
+        assert!(matches!(merged.data, BundleData::CexDex(_)));
+    }
+
+    #[test]
+    fn test_merge_counts_gas_of_shared_tx_once() {
+        let first = B256::with_last_byte(1);
+        let second = B256::with_last_byte(2);
+
+        let merged = merge_bundles(
+            MevType::JitCexDex,
+            vec![
+                bundle(MevType::CexDexTrades, 2, 10.0, &[second]),
+                bundle(MevType::Jit, 1, 5.5, &[first, second]),
+            ],
+        )
+        .unwrap();
+
+        // the gas of `second` is paid once, and the $1 the jit bundle was charged
+        // for it again is added back to its profit
+        assert_eq!(merged.header.bribe_usd, 2.0);
+        assert_eq!(merged.header.profit_usd, 16.5);
+        assert_eq!(
+            merged
+                .header
+                .balance_deltas
+                .iter()
+                .map(|deltas| deltas.tx_hash)
+                .collect_vec(),
+            vec![first, second]
+        );
+        assert_eq!(merged.data.mev_type(), MevType::CexDexTrades);
+    }
+
+    #[test]
+    fn test_merge_requires_data_of_parent() {
+        let first = B256::with_last_byte(1);
+        let second = B256::with_last_byte(2);
+
+        assert!(merge_bundles(
+            MevType::JitCexDex,
+            vec![
+                bundle(MevType::Jit, 1, 5.5, &[first, second]),
+                bundle(MevType::CexDexTrades, 2, 10.0, &[second]),
+            ],
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn test_overlap() {
+        let first = B256::with_last_byte(1);
+        let second = B256::with_last_byte(2);
+        let mut other = bundle(MevType::Sandwich, 0, 0.0, &[first, second]);
+        other.data = BundleData::Sandwich(Sandwich {
+            frontrun_tx_hash: vec![first],
+            backrun_tx_hash: second,
+            ..Default::default()
+        });
+
+        assert!(Overlap::AnyTx.matches(&[first], &other));
+        assert!(!Overlap::AllTxs.matches(&[first], &other));
+        assert!(Overlap::AllTxs.matches(&[second, first], &other));
+    }
+}
@@ -0,0 +1,192 @@
+use std::{path::Path, sync::OnceLock};
+
+use brontes_types::mev::MevType;
+use serde::{de::Error, Deserialize, Deserializer};
+use strum::IntoEnumIterator;
+
+use super::{composer_filters::ComposeRule, mev_filters::DedupRule};
+
+/// The rules brontes is built with
+pub const DEFAULT_COMPOSITION_CONFIG: &str =
+    include_str!("../../../../config/composition_config.toml");
+
+static COMPOSITION_CONFIG: OnceLock<CompositionConfig> = OnceLock::new();
+
+/// The composition and deduplication rules of the composer. Compose rules are
+/// applied before the dedup rules, each in the order they are defined in.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CompositionConfig {
+    #[serde(default)]
+    pub compose: Vec<ComposeRule>,
+    #[serde(default)]
+    pub dedup:   Vec<DedupRule>,
+}
+
+impl Default for CompositionConfig {
+    fn default() -> Self {
+        Self::from_toml(DEFAULT_COMPOSITION_CONFIG).expect("invalid default composition config")
+    }
+}
+
+impl CompositionConfig {
+    pub fn from_toml(config: &str) -> eyre::Result<Self> {
+        let config: Self = toml::from_str(config)?;
+
+        config.compose.iter().try_for_each(ComposeRule::validate)?;
+        config.dedup.iter().try_for_each(DedupRule::validate)?;
+
+        Ok(config)
+    }
+
+    pub fn load(path: &Path) -> eyre::Result<Self> {
+        let config = std::fs::read_to_string(path).map_err(|e| {
+            eyre::eyre!("failed to read composition config at {}: {e}", path.display())
+        })?;
+
+        Self::from_toml(&config)
+            .map_err(|e| eyre::eyre!("invalid composition config at {}: {e}", path.display()))
+    }
+}
+
+/// Sets the rules the composer runs with. Has to be called before the first
+/// block is composed, after which the rules can't be changed.
+pub fn init_composition_config(config: CompositionConfig) -> eyre::Result<()> {
+    COMPOSITION_CONFIG
+        .set(config)
+        .map_err(|_| eyre::eyre!("composition config was already initialized"))
+}
+
+/// The rules the composer runs with, the defaults unless
+/// [`init_composition_config`] was called
+pub fn composition_config() -> &'static CompositionConfig {
+    COMPOSITION_CONFIG.get_or_init(CompositionConfig::default)
+}
+
+/// Unlike the [`MevType`] deserialization, which falls back to
+/// [`MevType::Unknown`], a misspelled type is an error here
+fn parse_mev_type(name: &str) -> Result<MevType, String> {
+    MevType::iter()
+        .find(|mev_type| mev_type.as_ref() == name)
+        .ok_or_else(|| format!("unknown mev type {name}"))
+}
+
+pub(super) fn mev_type<'de, D: Deserializer<'de>>(deserializer: D) -> Result<MevType, D::Error> {
+    let name = String::deserialize(deserializer)?;
+
+    parse_mev_type(&name).map_err(D::Error::custom)
+}
+
+pub(super) fn mev_types<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<MevType>, D::Error> {
+    Vec::<String>::deserialize(deserializer)?
+        .iter()
+        .map(|name| parse_mev_type(name))
+        .collect::<Result<_, _>>()
+        .map_err(D::Error::custom)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::composer::{
+        composer_filters::{ComposeFunction, Overlap},
+        mev_filters::DedupFilter,
+    };
+
+    #[test]
+    fn test_default_config() {
+        let config = CompositionConfig::default();
+
+        assert_eq!(
+            config.compose,
+            vec![ComposeRule {
+                parent:   MevType::JitSandwich,
+                children: vec![MevType::Sandwich, MevType::Jit],
+                compose:  ComposeFunction::SandwichJit,
+                overlap:  Overlap::AnyTx,
+            }]
+        );
+        assert_eq!(config.dedup.len(), 11);
+        assert_eq!(
+            config.dedup[0],
+            DedupRule {
+                dominant:     MevType::AtomicArb,
+                subordinates: vec![MevType::CexDexTrades],
+                filter:       Some(DedupFilter::AtomicCexDex),
+            }
+        );
+    }
+
+    #[test]
+    fn test_generic_rule() {
+        let config = CompositionConfig::from_toml(
+            r#"
+            [[compose]]
+            parent = "JitCexDex"
+            children = ["CexDexTrades", "Jit"]
+            compose = "merge"
+            overlap = "all_txs"
+
+            [[dedup]]
+            dominant = "JitCexDex"
+            subordinates = ["AtomicArb"]
+            filter = "higher_profit"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.compose[0].compose, ComposeFunction::Merge);
+        assert_eq!(config.compose[0].overlap, Overlap::AllTxs);
+        assert_eq!(config.dedup[0].filter, Some(DedupFilter::HigherProfit));
+    }
+
+    #[test]
+    fn test_invalid_rules() {
+        // misspelled mev type
+        assert!(CompositionConfig::from_toml(
+            r#"
+            [[dedup]]
+            dominant = "Sandwhich"
+            subordinates = ["AtomicArb"]
+            "#,
+        )
+        .is_err());
+
+        // the jit sandwich compose function can't build other types
+        assert!(CompositionConfig::from_toml(
+            r#"
+            [[compose]]
+            parent = "JitCexDex"
+            children = ["Jit", "CexDexQuotes"]
+            compose = "sandwich_jit"
+            "#,
+        )
+        .is_err());
+
+        // merged bundles keep the data of the first child, which jit cex dex isn't
+        // stored as
+        assert!(CompositionConfig::from_toml(
+            r#"
+            [[compose]]
+            parent = "JitCexDex"
+            children = ["Jit", "CexDexTrades"]
+            compose = "merge"
+            "#,
+        )
+        .is_err());
+
+        // unknown field
+        assert!(CompositionConfig::from_toml(
+            r#"
+            [[compose]]
+            parent = "JitCexDex"
+            children = ["Jit", "CexDexQuotes"]
+            compose = "merge"
+            winner = "parent"
+            "#,
+        )
+        .is_err());
+    }
+}
@@ -6,68 +6,73 @@ use brontes_types::{
     normalized_actions::Action,
     BlockTree,
 };
-use lazy_static::lazy_static;
+use itertools::Itertools;
+use serde::Deserialize;
 
-/// Defines precedence rules among different MEV types for the purpose of
+use super::config::{mev_type, mev_types};
+
+/// A precedence rule among different MEV types for the purpose of
 /// deduplication.
 ///
-/// This macro creates a static reference (`MEV_DEDUPLICATION_FILTER`) that maps
-/// a list of subordinate MEV types to each dominant MEV type. These rules are
-/// used to determine which MEV types should be considered for deduplication
-/// when multiple types are present for overlapping transactions.
-///
-/// # Usage
-/// ```ignore
-/// define_mev_precedence!(
-///     SubordinateMevType1, SubordinateMevType2 => DominantMevType;
-/// );
-/// ```
-/// In this example, `DominantMevType` takes precedence over
-/// `SubordinateMevType1` and `SubordinateMevType2` for deduplication purposes.
+/// The dominant MEV type takes precedence over the subordinate MEV types: any
+/// subordinate bundle that shares a transaction with a dominant bundle is
+/// dropped, unless the `filter` says otherwise.
 ///
-/// Example of defining multiple precedence rules:
-/// ```ignore
-/// define_mev_precedence!(
-///     Backrun => Sandwich;
-///     Backrun => Jit;
-///     Backrun => JitSandwich;
-/// );
+/// ```toml
+/// [[dedup]]
+/// dominant = "Sandwich"
+/// subordinates = ["Unknown", "SearcherTx", "AtomicArb"]
 /// ```
-/// In these examples, `Backrun` is considered subordinate to `Sandwich`, `Jit`,
-/// `JitSandwich`.  
-#[macro_export]
-macro_rules! define_mev_precedence {
-    ($($($subordinate_mev_type:ident),+ => $dominant_mev_type:ident;)+) => {
-        lazy_static! {
-            pub static ref MEV_DEDUPLICATION_FILTER:
-                &'static [(MevType, FilterFn, Vec<MevType>)] = {
-                &*Box::leak(Box::new([
-                    $((
-                        MevType::$dominant_mev_type,
-                        get_filter_fn(MevType::$dominant_mev_type),
-                        vec![$(MevType::$subordinate_mev_type),+],
-                    ),)+
-                ]))
-            };
-        }
-    };
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DedupRule {
+    #[serde(deserialize_with = "mev_type")]
+    pub dominant:     MevType,
+    #[serde(deserialize_with = "mev_types")]
+    pub subordinates: Vec<MevType>,
+    #[serde(default)]
+    pub filter:       Option<DedupFilter>,
 }
 
-pub type FilterFn = Option<
-    Box<
-        dyn Fn(Arc<BlockTree<Action>>, Arc<Box<dyn LibmdbxReader>>, [&Bundle; 2]) -> bool
-            + Send
-            + Sync,
-    >,
->;
+impl DedupRule {
+    pub(crate) fn validate(&self) -> eyre::Result<()> {
+        if self.subordinates.is_empty() {
+            eyre::bail!("dedup rule for {} has no subordinates", self.dominant)
+        }
+        if self.subordinates.contains(&self.dominant) {
+            eyre::bail!("dedup rule for {} has itself as a subordinate", self.dominant)
+        }
+        if self.subordinates.iter().duplicates().next().is_some() {
+            eyre::bail!("dedup rule for {} has duplicate subordinates", self.dominant)
+        }
+
+        Ok(())
+    }
 
-pub fn get_filter_fn(mev_type: MevType) -> FilterFn {
-    match mev_type {
-        MevType::AtomicArb => Some(Box::new(atomic_dedup_fn)),
-        _ => None,
+    pub fn filter_fn(&self) -> FilterFn {
+        self.filter.map(|filter| match filter {
+            DedupFilter::AtomicCexDex => atomic_dedup_fn as FilterFnInner,
+            DedupFilter::HigherProfit => higher_profit_dedup_fn,
+        })
     }
 }
 
+/// Decides which of two overlapping bundles wins, on top of the precedence of
+/// their types
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DedupFilter {
+    /// See [`atomic_dedup_fn`]
+    AtomicCexDex,
+    /// Only drops the subordinate bundle if the dominant one made at least as
+    /// much profit
+    HigherProfit,
+}
+
+type FilterFnInner = fn(Arc<BlockTree<Action>>, Arc<Box<dyn LibmdbxReader>>, [&Bundle; 2]) -> bool;
+
+pub type FilterFn = Option<FilterFnInner>;
+
 /// returns true if should dedup.
 pub fn atomic_dedup_fn(
     _tree: Arc<BlockTree<Action>>,
@@ -100,18 +105,13 @@ pub fn atomic_dedup_fn(
     true
 }
 
-define_mev_precedence!(
-    // will filter out unless function says otherwise
-    CexDexTrades => AtomicArb;
+/// returns true if should dedup.
+pub fn higher_profit_dedup_fn(
+    _tree: Arc<BlockTree<Action>>,
+    _db: Arc<Box<dyn LibmdbxReader>>,
+    bundles: [&Bundle; 2],
+) -> bool {
+    let [dominant, subordinate] = bundles;
 
-    // filter out all atomic arbs that we kept as cex dex
-    AtomicArb => CexDexTrades;
-    Unknown, SearcherTx => CexDexQuotes;
-    Unknown, SearcherTx => CexDexTrades;
-    Unknown, SearcherTx => AtomicArb;
-    Unknown, SearcherTx, AtomicArb => Jit;
-    Unknown, SearcherTx, AtomicArb, CexDexQuotes,CexDexTrades  => Liquidation;
-    Unknown, SearcherTx, AtomicArb, CexDexQuotes,CexDexTrades  => Sandwich;
-    Unknown, SearcherTx, AtomicArb, Jit, CexDexQuotes, CexDexTrades=> JitCexDex;
-    Unknown, SearcherTx, AtomicArb, CexDexQuotes, CexDexTrades, Jit, Sandwich => JitSandwich;
-);
+    dominant.header.profit_usd >= subordinate.header.profit_usd
+}
@@ -9,18 +9,17 @@
 //! ## Key Components
 //! - `Composer`: A struct that orchestrates specialized inspectors. It waits
 //!   for all results and then proceeds to compose and deduplicate MEV data.
-//! - `MEV_COMPOSABILITY_FILTER` and `MEV_DEDUPLICATION_FILTER`: These filters,
-//!   defined using the `mev_composability` and `define_mev_precedence` macros,
-//!   respectively, establish rules for composing multiple MEV types and setting
-//!   precedence among them for deduplication.
+//! - `CompositionConfig`: The compose rules and dedup rules, which establish
+//!   how multiple MEV types are composed and the precedence among them for
+//!   deduplication. They are loaded from TOML, see
+//!   `config/composition_config.toml` for the defaults.
 //! - Utility Functions: A collection of functions designed to assist in the
 //!   composition and deduplication processes of MEV data.
 //!
 //! ## Usage
 //! The `Composer` struct is central to this module. It processes a list of
 //! `Inspector` futures to extract MEV data, which is then composed and
-//! deduplicated based on the rules defined in the `MEV_COMPOSABILITY_FILTER`
-//! and `MEV_DEDUPLICATION_FILTER`.
+//! deduplicated based on the rules of the `CompositionConfig`.
 //!
 //! ### Example
 //! ```ignore
@@ -31,7 +30,7 @@ use std::sync::Arc;
 
 use alloy_primitives::Address;
 use brontes_types::{
-    db::{block_analysis::BlockAnalysis, traits::LibmdbxReader},
+    db::{block_analysis::BlockAnalysis, pool_lvr::BlockPoolLvr, traits::LibmdbxReader},
     mev::Mev,
     BlockData, FastHashMap, MultiBlockData,
 };
@@ -39,6 +38,7 @@ use itertools::Itertools;
 use tracing::{span, Level};
 
 mod composer_filters;
+mod config;
 mod mev_filters;
 mod utils;
 use brontes_types::{
@@ -47,17 +47,17 @@ use brontes_types::{
     normalized_actions::Action,
     tree::BlockTree,
 };
-use composer_filters::{ComposeFunction, MEV_COMPOSABILITY_FILTER};
-use mev_filters::{FilterFn, MEV_DEDUPLICATION_FILTER};
-use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
-use utils::{
-    build_mev_header, filter_and_count_bundles, find_mev_with_matching_tx_hashes, sort_mev_by_type,
-    try_deduping_mev,
+pub use composer_filters::{merge_bundles, ComposeFunction, ComposeRule, Overlap};
+pub use config::{
+    composition_config, init_composition_config, CompositionConfig, DEFAULT_COMPOSITION_CONFIG,
 };
+pub use mev_filters::{DedupFilter, DedupRule, FilterFn};
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use utils::{build_mev_header, filter_and_count_bundles, sort_mev_by_type, try_deduping_mev};
 
 const DISCOVERY_PRIORITY_FEE_MULTIPLIER: f64 = 2.0;
 
-use crate::{discovery::DiscoveryInspector, Inspector};
+use crate::{discovery::DiscoveryInspector, lvr::block_pool_lvr, Inspector};
 
 #[derive(Debug)]
 pub struct ComposerResults {
@@ -66,6 +66,7 @@ pub struct ComposerResults {
     /// all txes with coinbase.transfers that weren't classified
     pub possible_mev_txes: PossibleMevCollection,
     pub block_analysis:    BlockAnalysis,
+    pub pool_lvr:          BlockPoolLvr,
 }
 
 pub fn run_block_inspection<DB: LibmdbxReader>(
@@ -82,12 +83,20 @@ pub fn run_block_inspection<DB: LibmdbxReader>(
 
     let quote_token = orchestra[0].get_quote_token();
 
+    let pool_lvr = block_pool_lvr(tree.clone(), &metadata, quote_token, db);
+
     let (block_details, mev_details) =
         on_orchestra_resolution(tree, possible_mev_txes, metadata, classified_mev, quote_token, db);
 
     let block_analysis = BlockAnalysis::new(&block_details, &mev_details);
 
-    ComposerResults { block_details, mev_details, possible_mev_txes: possible_arbs, block_analysis }
+    ComposerResults {
+        block_details,
+        mev_details,
+        possible_mev_txes: possible_arbs,
+        block_analysis,
+        pool_lvr,
+    }
 }
 
 fn run_inspectors(
@@ -144,24 +153,23 @@ fn on_orchestra_resolution<DB: LibmdbxReader>(
 ) -> (MevBlock, Vec<Bundle>) {
     let mut sorted_mev = sort_mev_by_type(orchestra_data);
 
-    MEV_COMPOSABILITY_FILTER
-        .iter()
-        .for_each(|(parent_mev_type, compose_fn, child_mev_type)| {
-            try_compose_mev(parent_mev_type, child_mev_type, compose_fn, &mut sorted_mev);
-        });
+    let config = composition_config();
 
-    MEV_DEDUPLICATION_FILTER.iter().for_each(
-        |(dominant_mev_type, extra_filter_fn, subordinate_mev_type)| {
-            deduplicate_mev(
-                tree.clone(),
-                db,
-                dominant_mev_type,
-                extra_filter_fn,
-                subordinate_mev_type,
-                &mut sorted_mev,
-            );
-        },
-    );
+    config
+        .compose
+        .iter()
+        .for_each(|rule| try_compose_mev(rule, &mut sorted_mev));
+
+    config.dedup.iter().for_each(|rule| {
+        deduplicate_mev(
+            tree.clone(),
+            db,
+            &rule.dominant,
+            &rule.filter_fn(),
+            &rule.subordinates,
+            &mut sorted_mev,
+        );
+    });
 
     let (mev_count, mut filtered_bundles) = filter_and_count_bundles(sorted_mev);
 
@@ -231,8 +239,8 @@ fn deduplicate_mev<DB: LibmdbxReader>(
 /// The function first checks if there are any MEV of the first type in
 /// `composable_types` in `sorted_mev`. If there are, it iterates over them.
 /// For each MEV, it gets the transaction hashes associated with that MEV
-/// and attempts to find other MEV in `sorted_mev` that have matching
-/// transaction hashes. If it finds matching MEV for all types in
+/// and attempts to find other MEV in `sorted_mev` that overlap with them as
+/// set by the rule. If it finds matching MEV for all types in
 /// `composable_types`, it uses the `compose` function to create a new MEV
 /// and adds it to `sorted_mev` under `parent_mev_type`. It also records the
 /// indices of the composed MEV in `removal_indices`.
@@ -243,12 +251,8 @@ fn deduplicate_mev<DB: LibmdbxReader>(
 ///
 /// This function does not return any value. Its purpose is to modify
 /// `sorted_mev` by composing new MEV and removing the composed MEV.
-fn try_compose_mev(
-    parent_mev_type: &MevType,
-    child_mev_type: &[MevType],
-    compose: &ComposeFunction,
-    sorted_mev: &mut FastHashMap<MevType, Vec<Bundle>>,
-) {
+fn try_compose_mev(rule: &ComposeRule, sorted_mev: &mut FastHashMap<MevType, Vec<Bundle>>) {
+    let ComposeRule { parent: parent_mev_type, children: child_mev_type, compose, overlap } = rule;
     let first_mev_type = child_mev_type[0];
     let mut removal_indices: FastHashMap<MevType, Vec<usize>> = FastHashMap::default();
 
@@ -260,9 +264,11 @@ fn try_compose_mev(
 
             for &other_mev_type in child_mev_type.iter().skip(1) {
                 if let Some(other_mev_data_list) = sorted_mev.get(&other_mev_type) {
-                    for index in find_mev_with_matching_tx_hashes(other_mev_data_list, &tx_hashes) {
-                        let other_bundle = &other_mev_data_list[index];
-
+                    for (index, other_bundle) in other_mev_data_list
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, other)| overlap.matches(&tx_hashes, other))
+                    {
                         to_compose.push(other_bundle.clone());
                         temp_removal_indices.push((other_mev_type, index));
                     }
@@ -272,7 +278,7 @@ fn try_compose_mev(
             }
 
             if to_compose.len() == child_mev_type.len() {
-                if let Some(composed) = compose(to_compose) {
+                if let Some(composed) = compose.compose(*parent_mev_type, to_compose) {
                     sorted_mev
                         .entry(*parent_mev_type)
                         .or_default()
@@ -101,24 +101,6 @@ pub(crate) fn sort_mev_by_type(orchestra_data: Vec<Bundle>) -> FastHashMap<MevTy
         )
 }
 
-/// Finds the index of the first classified mev in the list whose transaction
-/// hashes match any of the provided hashes.
-pub(crate) fn find_mev_with_matching_tx_hashes<'a>(
-    mev_data_list: &'a [Bundle],
-    tx_hashes: &'a [FixedBytes<32>],
-) -> impl Iterator<Item = usize> + 'a {
-    mev_data_list
-        .iter()
-        .enumerate()
-        .filter_map(move |(index, bundle)| {
-            let tx_hashes_in_mev = bundle.data.mev_transaction_hashes();
-            tx_hashes_in_mev
-                .iter()
-                .any(|hash| tx_hashes.contains(hash))
-                .then_some(index)
-        })
-}
-
 /// Finds the index of the first classified mev in the list whose transaction
 /// hashes match any of the provided hashes.
 pub(crate) fn try_deduping_mev<'a>(
@@ -191,6 +173,8 @@ fn update_mev_count(mev_count: &mut MevCount, mev_type: MevType, count: u64) {
         MevType::AtomicArb => mev_count.atomic_backrun_count = Some(count),
         MevType::Liquidation => mev_count.liquidation_count = Some(count),
         MevType::SearcherTx => mev_count.searcher_tx_count = Some(count),
+        MevType::FailedAttempt => mev_count.failed_attempt_count = Some(count),
+        MevType::Sniping => mev_count.sniping_count = Some(count),
         MevType::Unknown => (),
     }
 }
@@ -346,11 +330,12 @@ fn calculate_mev_searching_profit(bundles: &[Bundle], builder_info: &BuilderInfo
                     .unwrap_or(false)
         })
         .fold((0.0, 0), |(accumulated_profit, accumulated_gas), bundle| {
-            let profit = if bundle.mev_type() != MevType::SearcherTx {
-                bundle.header.profit_usd
-            } else {
-                0.0
-            };
+            let profit =
+                if !matches!(bundle.mev_type(), MevType::SearcherTx | MevType::FailedAttempt) {
+                    bundle.header.profit_usd
+                } else {
+                    0.0
+                };
             let gas_paid = bundle.data.total_gas_paid();
             (accumulated_profit + profit, accumulated_gas + gas_paid)
         })
@@ -452,17 +437,19 @@ pub(crate) fn pre_process(tree: Arc<BlockTree<Action>>) -> BlockPreprocessing {
 /// Calculates the Mev gas & profit stats for the block
 ///
 /// Returns the total priority fee, tips & profit of mev bundles in the block
-/// Ignores the profit of SearcherTx bundles as they are not considered MEV.
+/// Ignores the profit of SearcherTx and FailedAttempt bundles as they are not
+/// considered MEV.
 fn calculate_block_mev_stats(orchestra_data: &[Bundle], base_fee: u128) -> (u128, f64, u128) {
     orchestra_data.iter().fold(
         (0u128, 0.0, 0u128),
         |(total_fee_paid, total_profit_usd, mev_bribe), bundle| {
             let fee_paid = bundle.data.total_priority_fee_paid(base_fee);
-            let profit_usd = if bundle.mev_type() != MevType::SearcherTx {
-                bundle.header.profit_usd
-            } else {
-                0.0
-            };
+            let profit_usd =
+                if !matches!(bundle.mev_type(), MevType::SearcherTx | MevType::FailedAttempt) {
+                    bundle.header.profit_usd
+                } else {
+                    0.0
+                };
             (
                 total_fee_paid + fee_paid,
                 total_profit_usd + profit_usd,
@@ -38,6 +38,13 @@
 //! Each inspector implements the `Inspector` trait and provides its own
 //! implementation of the `inspect_block` method.
 //!
+//! ## Plugins
+//!
+//! Inspectors can also be written out of tree, compiled to WASM and loaded at
+//! runtime, see [`plugin`](plugin/index.html). With the `wasm-plugins` feature
+//! they are selected as `plugin:<name>` alongside the built-in inspectors, see
+//! [`InspectorSelection`].
+//!
 //! ## Composer
 //!
 //! The `Composer` is a special type of inspector that combines the results of
@@ -59,7 +66,10 @@
 
 pub mod composer;
 pub mod discovery;
+pub mod lvr;
 pub mod mev_inspectors;
+pub mod plugin;
+pub mod rejections;
 use brontes_metrics::inspectors::OutlierMetrics;
 use mev_inspectors::searcher_activity::SearcherActivity;
 pub use mev_inspectors::*;
@@ -67,6 +77,8 @@ pub use mev_inspectors::*;
 #[cfg(feature = "tests")]
 pub mod test_utils;
 
+use std::str::FromStr;
+
 use alloy_primitives::Address;
 use atomic_arb::AtomicArbInspector;
 use brontes_types::{
@@ -81,9 +93,13 @@ use brontes_types::{
     MultiBlockData,
 };
 use cex_dex::{markout::CexDexMarkoutInspector, quotes::CexDexQuotesInspector};
+use failed_attempt::FailedAttemptInspector;
+use itertools::Itertools;
 use jit::JitCexDex;
 use liquidations::LiquidationInspector;
-use sandwich::SandwichInspector;
+use sandwich::{MultiBlockSandwichInspector, SandwichInspector};
+use sniping::SnipingInspector;
+use strum::IntoEnumIterator;
 
 use crate::jit::jit_liquidity::JitInspector;
 
@@ -112,6 +128,46 @@ pub enum Inspectors {
     SearcherActivity,
     CexDexMarkout,
     JitCexDex,
+    MultiBlockSandwich,
+    FailedAttempt,
+    Sniping,
+}
+
+/// An inspector selected with `--inspectors`, either a built-in one or the name
+/// of a [`plugin`] prefixed with [`PLUGIN_PREFIX`]
+#[derive(Debug, PartialEq, Clone, Eq, Hash)]
+pub enum InspectorSelection {
+    Builtin(Inspectors),
+    Plugin(String),
+}
+
+/// Marks an inspector selection as a plugin, e.g. `plugin:my_arb`
+pub const PLUGIN_PREFIX: &str = "plugin:";
+
+impl FromStr for InspectorSelection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(name) = s.strip_prefix(PLUGIN_PREFIX) {
+            if name.is_empty() {
+                return Err(format!("{PLUGIN_PREFIX} needs the name of a plugin"))
+            }
+            return Ok(Self::Plugin(name.to_string()))
+        }
+
+        Inspectors::from_str(s).map(Self::Builtin).map_err(|_| {
+            format!(
+                "unknown inspector {s}, expected one of {} or {PLUGIN_PREFIX}<name>",
+                Inspectors::iter().join(", ")
+            )
+        })
+    }
+}
+
+impl From<Inspectors> for InspectorSelection {
+    fn from(inspector: Inspectors) -> Self {
+        Self::Builtin(inspector)
+    }
 }
 
 type DynMevInspector = &'static (dyn Inspector<Result = Vec<Bundle>> + 'static);
@@ -123,6 +179,7 @@ impl Inspectors {
         db: &'static DB,
         cex_exchanges: &[CexExchange],
         trade_config: CexDexTradeConfig,
+        sniping_markout_blocks: usize,
         metrics: Option<OutlierMetrics>,
     ) -> DynMevInspector {
         match &self {
@@ -167,6 +224,20 @@ impl Inspectors {
                 ),
                 jit:     JitInspector::new(quote_token, db, metrics),
             }) as DynMevInspector,
+            Self::MultiBlockSandwich => {
+                static_object(MultiBlockSandwichInspector::new(quote_token, db, metrics))
+                    as DynMevInspector
+            }
+            Self::FailedAttempt => {
+                static_object(FailedAttemptInspector::new(quote_token, db, metrics))
+                    as DynMevInspector
+            }
+            Self::Sniping => static_object(SnipingInspector::new(
+                quote_token,
+                db,
+                sniping_markout_blocks,
+                metrics,
+            )) as DynMevInspector,
         }
     }
 }
@@ -0,0 +1,241 @@
+//! Per pool loss versus rebalancing (LVR) analytics.
+//!
+//! For every pool that was swapped through in a block, the swaps are netted
+//! into the change of the reserves of the pool, which is then marked against
+//! the CEX mid price at block time. See [`PoolLvr`] for the definition.
+use std::sync::Arc;
+
+use alloy_primitives::Address;
+use brontes_database::libmdbx::LibmdbxReader;
+use brontes_types::{
+    db::{
+        block_analysis::SingleTokenDetails,
+        cex::quotes::FeeAdjustedQuote,
+        pool_lvr::{BlockPoolLvr, PoolLvr},
+    },
+    normalized_actions::{Action, NormalizedBatch, NormalizedSwap},
+    pair::Pair,
+    tree::BlockTree,
+    ActionIter, FastHashMap, ToFloatNearest, TreeSearchBuilder,
+};
+use itertools::Itertools;
+use malachite::{
+    num::basic::traits::{One, Zero},
+    Rational,
+};
+
+use crate::{shared_utils::SharedInspectorUtils, Metadata};
+
+/// Computes the LVR of every pool that was swapped through in the block.
+/// Pools with a token that has no CEX quote at block time are left out.
+pub fn block_pool_lvr<DB: LibmdbxReader>(
+    tree: Arc<BlockTree<Action>>,
+    metadata: &Metadata,
+    quote: Address,
+    db: &DB,
+) -> BlockPoolLvr {
+    let block_number = metadata.block_num;
+    let utils = SharedInspectorUtils::new(quote, db, None);
+
+    let swaps = tree
+        .collect_all(
+            TreeSearchBuilder::default().with_actions([Action::is_swap, Action::is_nested_action]),
+        )
+        .flat_map(|(_, actions)| {
+            // the user swaps of a batch settle against the settlement contract, not
+            // against a pool, so only the solver swaps are kept
+            let actions = actions.into_iter().flatten_specified(
+                Action::try_batch_ref,
+                |batch: NormalizedBatch| {
+                    batch
+                        .solver_swaps
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(Action::from)
+                        .collect_vec()
+                },
+            );
+
+            utils
+                .flatten_nested_actions(actions, &|action| action.is_swap())
+                .filter_map(Action::try_swap)
+                .collect_vec()
+        })
+        .collect_vec();
+
+    let mut by_pool: FastHashMap<Address, Vec<NormalizedSwap>> = FastHashMap::default();
+    for swap in swaps {
+        by_pool.entry(swap.pool).or_default().push(swap);
+    }
+
+    let timestamp = metadata.microseconds_block_timestamp();
+    let pools = by_pool
+        .into_iter()
+        .sorted_by_key(|(pool, _)| *pool)
+        .filter_map(|(pool, swaps)| {
+            pool_lvr(block_number, pool, &swaps, |token| {
+                if token == quote {
+                    return Some(Rational::ONE)
+                }
+
+                metadata
+                    .cex_quotes
+                    .get_quote_from_most_liquid_exchange(&Pair(token, quote), timestamp, None)
+                    .as_ref()
+                    .map(FeeAdjustedQuote::maker_taker_mid)
+                    .map(|(maker_mid, _)| maker_mid)
+            })
+        })
+        .collect_vec();
+
+    BlockPoolLvr { block_number, pools }
+}
+
+/// Computes the LVR of a single pool from its swaps in the block, pricing each
+/// token with `price`. Returns `None` if any of the tokens can't be priced.
+pub fn pool_lvr(
+    block_number: u64,
+    pool: Address,
+    swaps: &[NormalizedSwap],
+    price: impl Fn(Address) -> Option<Rational>,
+) -> Option<PoolLvr> {
+    let first = swaps.first()?;
+
+    let mut deltas: FastHashMap<Address, (SingleTokenDetails, Rational)> = FastHashMap::default();
+    let mut prices: FastHashMap<Address, Rational> = FastHashMap::default();
+    let mut volume = Rational::ZERO;
+    let mut toxic_volume = Rational::ZERO;
+
+    for swap in swaps {
+        for token in [&swap.token_in, &swap.token_out] {
+            if !prices.contains_key(&token.address) {
+                prices.insert(token.address, price(token.address)?);
+            }
+        }
+
+        let value_in = &swap.amount_in * &prices[&swap.token_in.address];
+        let value_out = &swap.amount_out * &prices[&swap.token_out.address];
+        if value_out > value_in {
+            toxic_volume += &value_in;
+        }
+        volume += value_in;
+
+        deltas
+            .entry(swap.token_in.address)
+            .or_insert_with(|| (swap.token_in.clone().into(), Rational::ZERO))
+            .1 += &swap.amount_in;
+        deltas
+            .entry(swap.token_out.address)
+            .or_insert_with(|| (swap.token_out.clone().into(), Rational::ZERO))
+            .1 -= &swap.amount_out;
+    }
+
+    // the value the LPs would have kept had they traded at the CEX mid
+    let lvr = -deltas
+        .iter()
+        .map(|(token, (_, delta))| delta * &prices[token])
+        .sum::<Rational>();
+
+    let (tokens, reserve_deltas, cex_prices) = deltas
+        .into_iter()
+        .sorted_by_key(|(token, _)| *token)
+        .map(|(token, (details, delta))| {
+            (details, delta.to_float(), prices[&token].clone().to_float())
+        })
+        .multiunzip();
+
+    Some(PoolLvr {
+        block_number,
+        pool,
+        protocol: first.protocol,
+        tokens,
+        reserve_deltas,
+        cex_prices,
+        swap_count: swaps.len() as u64,
+        volume_usd: volume.to_float(),
+        toxic_volume_usd: toxic_volume.to_float(),
+        lvr_usd: lvr.to_float(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::address;
+    use brontes_types::{
+        constants::{USDC_ADDRESS, WETH_ADDRESS},
+        db::token_info::{TokenInfo, TokenInfoWithAddress},
+        Protocol,
+    };
+
+    use super::*;
+
+    fn token(address: Address, symbol: &str) -> TokenInfoWithAddress {
+        TokenInfoWithAddress {
+            address,
+            inner: TokenInfo { decimals: 18, symbol: symbol.to_string() },
+        }
+    }
+
+    fn swap(
+        token_in: &TokenInfoWithAddress,
+        amount_in: u64,
+        token_out: &TokenInfoWithAddress,
+        amount_out: u64,
+    ) -> NormalizedSwap {
+        NormalizedSwap {
+            protocol: Protocol::UniswapV2,
+            token_in: token_in.clone(),
+            token_out: token_out.clone(),
+            amount_in: Rational::from(amount_in),
+            amount_out: Rational::from(amount_out),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_pool_lvr_marks_reserves_to_cex() {
+        let weth = token(WETH_ADDRESS, "WETH");
+        let usdc = token(USDC_ADDRESS, "USDC");
+        let pool = address!("B4e16d0168e52d35CaCD2c6185b44281Ec28C9Dc");
+
+        // CEX mid is 2000 USDC per WETH. The first swap buys 1 WETH for 1900 USDC
+        // off the pool, the second sells 1 WETH for 2050 USDC to it.
+        let swaps = vec![swap(&usdc, 1900, &weth, 1), swap(&weth, 1, &usdc, 2050)];
+        let price = |token: Address| {
+            if token == weth.address {
+                Some(Rational::from(2000))
+            } else if token == usdc.address {
+                Some(Rational::ONE)
+            } else {
+                None
+            }
+        };
+
+        let lvr = pool_lvr(1, pool, &swaps, price).unwrap();
+
+        // the pool is short 150 USDC and flat on WETH
+        assert_eq!(lvr.swap_count, 2);
+        assert_eq!(lvr.lvr_usd, 150.0);
+        assert_eq!(lvr.volume_usd, 3900.0);
+        assert_eq!(lvr.toxic_volume_usd, 3900.0);
+        assert_eq!(lvr.toxicity(), 1.0);
+
+        // USDC sorts before WETH
+        assert_eq!(lvr.tokens[0].address, usdc.address);
+        assert_eq!(lvr.tokens[1].address, weth.address);
+        assert_eq!(lvr.reserve_deltas, vec![-150.0, 0.0]);
+        assert_eq!(lvr.cex_prices, vec![1.0, 2000.0]);
+    }
+
+    #[test]
+    fn test_pool_lvr_needs_cex_price_for_every_token() {
+        let weth = token(WETH_ADDRESS, "WETH");
+        let new = token(address!("1111111111111111111111111111111111111111"), "NEW");
+        let pool = address!("2222222222222222222222222222222222222222");
+
+        let swaps = vec![swap(&weth, 1, &new, 1000)];
+        let price = |token: Address| (token == weth.address).then(|| Rational::from(2000));
+
+        assert!(pool_lvr(1, pool, &swaps, price).is_none());
+    }
+}
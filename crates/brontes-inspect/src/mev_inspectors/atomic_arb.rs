@@ -3,12 +3,15 @@ use std::sync::Arc;
 use brontes_database::libmdbx::LibmdbxReader;
 use brontes_metrics::inspectors::OutlierMetrics;
 use brontes_types::{
-    constants::{get_stable_type, is_euro_stable, is_gold_stable, is_usd_stable, StableType},
+    constants::{
+        get_stable_type, is_euro_stable, is_gold_stable, is_usd_stable, StableType, ETH_ADDRESS,
+        WETH_ADDRESS,
+    },
     db::dex::PriceAt,
     mev::{AtomicArb, AtomicArbType, Bundle, BundleData, MevType},
     normalized_actions::{
         accounting::ActionAccounting, Action, NormalizedEthTransfer, NormalizedSwap,
-        NormalizedTransfer,
+        NormalizedTransfer, NormalizedVaultDeposit, NormalizedVaultWithdraw,
     },
     BlockData, FastHashSet, IntoZip, MultiBlockData, ToFloatNearest, TreeBase, TreeCollector,
     TreeSearchBuilder, TxInfo,
@@ -17,7 +20,11 @@ use itertools::Itertools;
 use malachite::{num::basic::traits::Zero, Rational};
 use reth_primitives::{Address, B256};
 
-use crate::{shared_utils::SharedInspectorUtils, BlockTree, Inspector, Metadata, MAX_PROFIT};
+use crate::{
+    rejections::{reject, RejectionCheck},
+    shared_utils::SharedInspectorUtils,
+    BlockTree, Inspector, Metadata, MAX_PROFIT,
+};
 
 const MAX_PRICE_DIFF: Rational = Rational::const_from_unsigneds(99995, 100000);
 
@@ -57,6 +64,7 @@ impl<DB: LibmdbxReader> Inspector for AtomicArbInspector<'_, DB> {
                     Action::is_swap,
                     Action::is_transfer,
                     Action::is_eth_transfer,
+                    Action::is_vault,
                     Action::is_nested_action,
                 ]))
                 .t_full_map(|(tree, v)| {
@@ -65,7 +73,12 @@ impl<DB: LibmdbxReader> Inspector for AtomicArbInspector<'_, DB> {
                         tree.get_tx_info_batch(&tx_hashes, self.utils.db),
                         v.into_iter().map(|v| {
                             self.utils
-                                .flatten_nested_actions_default(v.into_iter())
+                                .flatten_nested_actions(v.into_iter(), &|action| {
+                                    action.is_swap()
+                                        || action.is_transfer()
+                                        || action.is_eth_transfer()
+                                        || action.is_vault()
+                                })
                                 .collect::<Vec<_>>()
                         }),
                     )
@@ -84,10 +97,12 @@ impl<DB: LibmdbxReader> Inspector for AtomicArbInspector<'_, DB> {
                         metadata.clone(),
                         actions
                             .into_iter()
-                            .split_actions::<(Vec<_>, Vec<_>, Vec<_>), _>((
+                            .split_actions::<(Vec<_>, Vec<_>, Vec<_>, Vec<_>, Vec<_>), _>((
                                 Action::try_swaps_merged,
                                 Action::try_transfer,
                                 Action::try_eth_transfer,
+                                Action::try_vault_deposit,
+                                Action::try_vault_withdraw,
                             )),
                     )
                 })
@@ -107,10 +122,16 @@ impl<DB: LibmdbxReader> AtomicArbInspector<'_, DB> {
         trees: Vec<Arc<BlockTree<Action>>>,
         info: TxInfo,
         metadata: Arc<Metadata>,
-        data: (Vec<NormalizedSwap>, Vec<NormalizedTransfer>, Vec<NormalizedEthTransfer>),
+        data: (
+            Vec<NormalizedSwap>,
+            Vec<NormalizedTransfer>,
+            Vec<NormalizedEthTransfer>,
+            Vec<NormalizedVaultDeposit>,
+            Vec<NormalizedVaultWithdraw>,
+        ),
     ) -> Option<Bundle> {
         tracing::trace!(?info, "trying atomic");
-        let (mut swaps, transfers, eth_transfers) = data;
+        let (mut swaps, transfers, eth_transfers, deposits, withdraws) = data;
         let mev_addresses: FastHashSet<Address> = info.collect_address_set_for_accounting();
 
         let mut ignore_addresses = mev_addresses.clone();
@@ -118,21 +139,40 @@ impl<DB: LibmdbxReader> AtomicArbInspector<'_, DB> {
         swaps.iter().for_each(|s| {
             ignore_addresses.insert(s.pool);
         });
+        // the shares minted and burnt by a vault aren't swaps with it
+        deposits.iter().for_each(|d| {
+            ignore_addresses.insert(d.vault);
+        });
+        withdraws.iter().for_each(|w| {
+            ignore_addresses.insert(w.vault);
+        });
 
         swaps.extend(self.utils.try_create_swaps(&transfers, ignore_addresses));
 
-        let possible_arb_type = self.is_possible_arb(&swaps)?;
+        // only the dex swaps are checked against the dex prices, vaults trade at
+        // their share price
+        let dex_swaps = swaps.clone();
+        self.insert_vault_legs(&mut swaps, &deposits, &withdraws);
 
-        let account_deltas = transfers
+        let Some(possible_arb_type) = self.is_possible_arb(&swaps) else {
+            reject(RejectionCheck::Actions, &[info.tx_hash], || {
+                format!("the {} swaps don't form an arbitrage", swaps.len())
+            });
+            return None
+        };
+
+        let account_deltas = deposits
             .into_iter()
             .map(Action::from)
+            .chain(withdraws.into_iter().map(Action::from))
+            .chain(transfers.into_iter().map(Action::from))
             .chain(eth_transfers.into_iter().map(Action::from))
             .chain(info.get_total_eth_value().iter().cloned().map(Action::from))
             .account_for_actions();
 
         let mut has_dex_price = self.utils.valid_pricing(
             metadata.clone(),
-            &swaps,
+            &dex_swaps,
             account_deltas
                 .values()
                 .flat_map(|k| {
@@ -177,23 +217,34 @@ impl<DB: LibmdbxReader> AtomicArbInspector<'_, DB> {
 
         let requirement_multiplier = if has_dex_price { 1 } else { 2 };
 
-        let profit = match possible_arb_type {
-            AtomicArbType::Triangle => (is_profitable
-                || self.process_triangle_arb(&info, requirement_multiplier))
-            .then_some(profit),
-            AtomicArbType::CrossPair(jump_index) => (is_profitable
-                || self.is_stable_arb(&swaps, jump_index)
-                || self.is_cross_pair_or_stable_arb(&info, requirement_multiplier))
-            .then_some(profit),
-
-            AtomicArbType::StablecoinArb => (is_profitable
-                || self.is_cross_pair_or_stable_arb(&info, requirement_multiplier))
-            .then_some(profit),
-            AtomicArbType::LongTail => (self.is_long_tail(&info, requirement_multiplier)
-                && is_profitable
-                || self.is_long_tail(&info, requirement_multiplier) & !has_dex_price)
-                .then_some(profit),
-        }?;
+        let is_arb = match possible_arb_type {
+            AtomicArbType::Triangle => {
+                is_profitable || self.process_triangle_arb(&info, requirement_multiplier)
+            }
+            AtomicArbType::CrossPair(jump_index) => {
+                is_profitable
+                    || self.is_stable_arb(&swaps, jump_index)
+                    || self.is_cross_pair_or_stable_arb(&info, requirement_multiplier)
+            }
+            AtomicArbType::StablecoinArb => {
+                is_profitable || self.is_cross_pair_or_stable_arb(&info, requirement_multiplier)
+            }
+            AtomicArbType::LongTail => {
+                self.is_long_tail(&info, requirement_multiplier) && is_profitable
+                    || self.is_long_tail(&info, requirement_multiplier) & !has_dex_price
+            }
+        };
+
+        if !is_arb {
+            reject(RejectionCheck::Profit, &[info.tx_hash], || {
+                format!(
+                    "the {possible_arb_type:?} arbitrage made {:.2} usd and isn't from a known \
+                     searcher",
+                    profit.clone().to_float()
+                )
+            });
+            return None
+        }
 
         // given we have a atomic arb now, we will go and try to find the trigger
         // transaction that lead to this arb.
@@ -309,6 +360,37 @@ impl<DB: LibmdbxReader> AtomicArbInspector<'_, DB> {
             .unwrap_or_default()
     }
 
+    /// Inserts the vault deposits and withdrawals as legs of the arb, in trace
+    /// order. ETH is routed as WETH, so wrapping or unwrapping ETH isn't a leg.
+    fn insert_vault_legs(
+        &self,
+        swaps: &mut Vec<NormalizedSwap>,
+        deposits: &[NormalizedVaultDeposit],
+        withdraws: &[NormalizedVaultWithdraw],
+    ) {
+        let legs = deposits
+            .iter()
+            .map(NormalizedVaultDeposit::as_swap)
+            .chain(withdraws.iter().map(NormalizedVaultWithdraw::as_swap))
+            .filter_map(|mut leg| {
+                for token in [&mut leg.token_in, &mut leg.token_out] {
+                    if token.address == ETH_ADDRESS {
+                        *token = self.utils.db.try_fetch_token_info(WETH_ADDRESS).ok()?;
+                    }
+                }
+                (leg.token_in.address != leg.token_out.address).then_some(leg)
+            })
+            .collect_vec();
+
+        for leg in legs {
+            let at = swaps
+                .iter()
+                .position(|swap| swap.trace_index > leg.trace_index)
+                .unwrap_or(swaps.len());
+            swaps.insert(at, leg);
+        }
+    }
+
     fn is_possible_arb(&self, swaps: &[NormalizedSwap]) -> Option<AtomicArbType> {
         match swaps.len() {
             0 | 1 => None,
@@ -46,7 +46,11 @@ use super::{
 // to classify a a negative pnl cex-dex trade as a CEX-DEX trade
 pub const FILTER_THRESHOLD: u64 = 20;
 
-use crate::{shared_utils::SharedInspectorUtils, Inspector, Metadata};
+use crate::{
+    rejections::{reject, RejectionCheck},
+    shared_utils::SharedInspectorUtils,
+    Inspector, Metadata,
+};
 
 pub struct CexDexMarkoutInspector<'db, DB: LibmdbxReader> {
     pub utils:     SharedInspectorUtils<'db, DB>,
@@ -113,6 +117,7 @@ impl<DB: LibmdbxReader> CexDexMarkoutInspector<'_, DB> {
                 Action::is_swap,
                 Action::is_transfer,
                 Action::is_eth_transfer,
+                Action::is_vault,
                 Action::is_aggregator,
                 Action::is_batch,
             ]))
@@ -198,6 +203,9 @@ impl<DB: LibmdbxReader> CexDexMarkoutInspector<'_, DB> {
             self.utils.get_metrics().inspect(|m| {
                 m.branch_filtering_trigger(MevType::CexDexTrades, "is_triangular_arb")
             });
+            reject(RejectionCheck::Actions, &[tx_info.tx_hash], || {
+                "the swaps form a triangular arbitrage, which isn't cex-dex".to_string()
+            });
             return None
         }
 
@@ -248,10 +256,13 @@ impl<DB: LibmdbxReader> CexDexMarkoutInspector<'_, DB> {
                 "no dex swaps found\n Tx: {}",
                 format_etherscan_url(&tx_info.tx_hash)
             );
+            reject(RejectionCheck::Actions, &[tx_info.tx_hash], || {
+                "the transaction has no dex swaps".to_string()
+            });
             return None
         }
 
-        let mut possible_cex_dex: CexDexProcessing = self.detect_cex_dex(
+        let Some(mut possible_cex_dex) = self.detect_cex_dex(
             dex_swaps,
             &metadata,
             tx_info.is_searcher_of_type(MevType::CexDexTrades)
@@ -259,12 +270,25 @@ impl<DB: LibmdbxReader> CexDexMarkoutInspector<'_, DB> {
                 || tx_info.is_labelled_searcher_of_type(MevType::CexDexRfq)
                 || tx_info.is_searcher_of_type(MevType::JitCexDex),
             &tx_info,
-        )?;
+        ) else {
+            reject(RejectionCheck::Pricing, &[tx_info.tx_hash], || {
+                "no cex trades for the swapped pairs in the markout window".to_string()
+            });
+            return None
+        };
 
         self.gas_accounting(&mut possible_cex_dex, &tx_info.gas_details, metadata.clone());
 
-        let (profit_usd, cex_dex, trade_prices) =
-            self.filter_possible_cex_dex(possible_cex_dex, &tx_info, metadata.clone())?;
+        let Some((profit_usd, cex_dex, trade_prices)) =
+            self.filter_possible_cex_dex(possible_cex_dex, &tx_info, metadata.clone())
+        else {
+            reject(RejectionCheck::Profit, &[tx_info.tx_hash], || {
+                "not profitable against the cex trades on enough exchanges for a transaction that \
+                 isn't from a known cex-dex searcher"
+                    .to_string()
+            });
+            return None
+        };
 
         let price_map = trade_prices
             .into_iter()
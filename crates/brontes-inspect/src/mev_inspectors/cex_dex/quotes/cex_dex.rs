@@ -73,7 +73,11 @@ pub const FILTER_THRESHOLD: u64 = 20;
 
 use itertools::Itertools;
 
-use crate::{shared_utils::SharedInspectorUtils, Inspector, Metadata};
+use crate::{
+    rejections::{reject, RejectionCheck},
+    shared_utils::SharedInspectorUtils,
+    Inspector, Metadata,
+};
 pub struct CexDexQuotesInspector<'db, DB: LibmdbxReader> {
     utils:                SharedInspectorUtils<'db, DB>,
     _quotes_fetch_offset: u64,
@@ -163,6 +167,7 @@ impl<DB: LibmdbxReader> CexDexQuotesInspector<'_, DB> {
                 Action::is_swap,
                 Action::is_transfer,
                 Action::is_eth_transfer,
+                Action::is_vault,
                 Action::is_aggregator,
             ]))
             .filter_map(|(tx, swaps)| {
@@ -208,6 +213,9 @@ impl<DB: LibmdbxReader> CexDexQuotesInspector<'_, DB> {
                 if dex_swaps.is_empty() {
                     trace!(    target: "brontes::cex-dex-quotes",
                 "no dex swaps found\n Tx: {}", format_etherscan_url(&tx_info.tx_hash));
+                    reject(RejectionCheck::Actions, &[tx_info.tx_hash], || {
+                        "the transaction has no dex swaps".to_string()
+                    });
                     return None
                 }
 
@@ -220,12 +228,21 @@ impl<DB: LibmdbxReader> CexDexQuotesInspector<'_, DB> {
                     self.utils.get_metrics().inspect(|m| {
                         m.branch_filtering_trigger(MevType::CexDexQuotes, "is_triangular_arb")
                     });
+                    reject(RejectionCheck::Actions, &[tx_info.tx_hash], || {
+                        "the swaps form a triangular arbitrage, which isn't cex-dex".to_string()
+                    });
 
                     return None
                 }
 
-                let mut possible_cex_dex: CexDexProcessing =
-                    self.detect_cex_dex(dex_swaps, &metadata, &tx_info)?;
+                let Some(mut possible_cex_dex) =
+                    self.detect_cex_dex(dex_swaps, &metadata, &tx_info)
+                else {
+                    reject(RejectionCheck::Pricing, &[tx_info.tx_hash], || {
+                        "no cex quotes for the swapped pairs".to_string()
+                    });
+                    return None
+                };
 
                 self.gas_accounting(&mut possible_cex_dex, &tx_info.gas_details, metadata.clone());
 
@@ -238,8 +255,18 @@ impl<DB: LibmdbxReader> CexDexQuotesInspector<'_, DB> {
                     },
                 );
 
-                let (profit_usd, cex_dex) =
-                    self.filter_possible_cex_dex(possible_cex_dex, &tx_info, &metadata)?;
+                let pnl = possible_cex_dex.pnl.aggregate_pnl;
+                let Some((profit_usd, cex_dex)) =
+                    self.filter_possible_cex_dex(possible_cex_dex, &tx_info, &metadata)
+                else {
+                    reject(RejectionCheck::Profit, &[tx_info.tx_hash], || {
+                        format!(
+                            "made {pnl:.2} usd against the cex quotes, which isn't enough for a \
+                             transaction that isn't from a known cex-dex searcher"
+                        )
+                    });
+                    return None
+                };
 
                 let header = self.utils.build_bundle_header(
                     vec![deltas],
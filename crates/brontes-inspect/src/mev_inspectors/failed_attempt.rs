@@ -0,0 +1,378 @@
+use std::sync::Arc;
+
+use brontes_database::libmdbx::LibmdbxReader;
+use brontes_metrics::inspectors::OutlierMetrics;
+use brontes_types::{
+    db::{dex::BlockPrice, searcher::SearcherInfo},
+    mev::{Bundle, BundleData, FailedAttempt, MevType},
+    normalized_actions::Action,
+    tree::{BlockTree, FailedTx},
+    BlockData, FastHashMap, MultiBlockData, Protocol, ToFloatNearest, TxInfo,
+};
+use itertools::Itertools;
+use reth_primitives::Address;
+
+use crate::{shared_utils::SharedInspectorUtils, Inspector, Metadata};
+
+/// The mev types a failed transaction can be attributed to from the searchers
+/// history, in order of preference when counts are tied
+const ATTEMPTABLE_MEV_TYPES: [MevType; 9] = [
+    MevType::Sandwich,
+    MevType::JitSandwich,
+    MevType::Jit,
+    MevType::AtomicArb,
+    MevType::Liquidation,
+    MevType::CexDexQuotes,
+    MevType::CexDexTrades,
+    MevType::CexDexRfq,
+    MevType::JitCexDex,
+];
+
+/// Reports the transactions of searchers that reverted. Transactions from
+/// known searchers are always reported, others only if they called into a
+/// lending protocol or more than one pool through a contract that isn't a
+/// known protocol.
+pub struct FailedAttemptInspector<'db, DB: LibmdbxReader> {
+    utils: SharedInspectorUtils<'db, DB>,
+}
+
+impl<'db, DB: LibmdbxReader> FailedAttemptInspector<'db, DB> {
+    pub fn new(quote: Address, db: &'db DB, metrics: Option<OutlierMetrics>) -> Self {
+        Self { utils: SharedInspectorUtils::new(quote, db, metrics) }
+    }
+}
+
+impl<DB: LibmdbxReader> Inspector for FailedAttemptInspector<'_, DB> {
+    type Result = Vec<Bundle>;
+
+    fn get_id(&self) -> &str {
+        "FailedAttempt"
+    }
+
+    fn get_quote_token(&self) -> Address {
+        self.utils.quote
+    }
+
+    fn inspect_block(&self, mut data: MultiBlockData) -> Self::Result {
+        let block = data.per_block_data.pop().expect("no blocks");
+        let BlockData { metadata, tree } = block;
+        self.utils
+            .get_metrics()
+            .map(|m| {
+                m.run_inspector(MevType::FailedAttempt, || {
+                    self.inspect_block_inner(tree.clone(), metadata.clone())
+                })
+            })
+            .unwrap_or_else(|| self.inspect_block_inner(tree, metadata))
+    }
+}
+
+impl<DB: LibmdbxReader> FailedAttemptInspector<'_, DB> {
+    fn inspect_block_inner(
+        &self,
+        tree: Arc<BlockTree<Action>>,
+        metadata: Arc<Metadata>,
+    ) -> Vec<Bundle> {
+        if tree.failed_txs.is_empty() {
+            return vec![]
+        }
+
+        let eoas = tree
+            .failed_txs
+            .iter()
+            .map(|tx| tx.eoa)
+            .unique()
+            .collect_vec();
+        let contracts = tree
+            .failed_txs
+            .iter()
+            .filter_map(|tx| tx.to)
+            .unique()
+            .collect_vec();
+
+        let Ok(eoa_info) = self.utils.db.try_fetch_searcher_eoa_infos(eoas) else { return vec![] };
+        let Ok(contract_info) = self.utils.db.try_fetch_searcher_contract_infos(contracts) else {
+            return vec![]
+        };
+
+        tree.failed_txs
+            .iter()
+            .filter_map(|tx| self.process_failed_tx(tx, &eoa_info, &contract_info, &metadata))
+            .collect()
+    }
+
+    fn process_failed_tx(
+        &self,
+        tx: &FailedTx,
+        eoa_info: &FastHashMap<Address, SearcherInfo>,
+        contract_info: &FastHashMap<Address, SearcherInfo>,
+        metadata: &Arc<Metadata>,
+    ) -> Option<Bundle> {
+        let searcher_eoa_info = eoa_info.get(&tx.eoa).cloned();
+        let searcher_contract_info = tx.to.and_then(|to| contract_info.get(&to).cloned());
+
+        let called_protocols = tx
+            .called
+            .iter()
+            .filter_map(|address| self.utils.db.get_protocol(*address).ok())
+            .collect_vec();
+
+        let is_known_searcher = searcher_eoa_info.is_some() || searcher_contract_info.is_some();
+        if !is_known_searcher && !self.has_mev_call_shape(tx, &called_protocols) {
+            return None
+        }
+
+        let attempted_mev_type = attempted_mev_type(
+            searcher_eoa_info
+                .iter()
+                .chain(searcher_contract_info.iter()),
+            &called_protocols,
+        );
+
+        let info = TxInfo::new(
+            metadata.block_num,
+            tx.position as u64,
+            tx.eoa,
+            tx.to,
+            None,
+            tx.tx_hash,
+            tx.gas_details,
+            false,
+            false,
+            metadata.private_flow.contains(&tx.tx_hash),
+            false,
+            searcher_eoa_info,
+            searcher_contract_info,
+            vec![],
+        );
+
+        // all the transaction did was burn gas
+        let gas_burnt = metadata.get_gas_price_usd(tx.gas_details.gas_paid(), self.utils.quote);
+
+        let header = self.utils.build_bundle_header_searcher_activity(
+            vec![],
+            vec![tx.tx_hash],
+            &info,
+            -gas_burnt.to_float(),
+            BlockPrice::Lowest,
+            &[tx.gas_details],
+            metadata.clone(),
+            MevType::FailedAttempt,
+            false,
+        );
+
+        Some(Bundle {
+            header,
+            data: BundleData::FailedAttempt(FailedAttempt {
+                tx_hash: tx.tx_hash,
+                block_number: metadata.block_num,
+                attempted_mev_type,
+                protocols: called_protocols.into_iter().unique().collect(),
+                gas_details: tx.gas_details,
+            }),
+        })
+    }
+
+    /// Searcher contracts call into pools or lending protocols directly, where
+    /// users go through a router or aggregator that is a known protocol itself.
+    fn has_mev_call_shape(&self, tx: &FailedTx, called_protocols: &[Protocol]) -> bool {
+        let Some(to) = tx.to else { return false };
+        if self.utils.db.get_protocol(to).is_ok() {
+            return false
+        }
+
+        called_protocols.iter().any(is_lending_protocol) || pool_count(called_protocols) > 1
+    }
+}
+
+fn is_lending_protocol(protocol: &Protocol) -> bool {
+    matches!(protocol, Protocol::AaveV2 | Protocol::AaveV3 | Protocol::CompoundV2)
+}
+
+/// Wrapping ETH or depositing into a vault is how users get into a token, it
+/// isn't a leg of an arb by itself
+fn is_vault_protocol(protocol: &Protocol) -> bool {
+    matches!(protocol, Protocol::Erc4626 | Protocol::Weth | Protocol::Lido | Protocol::RocketPool)
+}
+
+fn pool_count(called_protocols: &[Protocol]) -> usize {
+    called_protocols
+        .iter()
+        .filter(|protocol| !is_lending_protocol(protocol) && !is_vault_protocol(protocol))
+        .count()
+}
+
+/// A transaction that called into a lending protocol was going for a
+/// liquidation. Otherwise we go with the type the searcher lands most often,
+/// and fall back to an arb if it went through more than one pool.
+fn attempted_mev_type<'a>(
+    searcher_info: impl Iterator<Item = &'a SearcherInfo>,
+    called_protocols: &[Protocol],
+) -> MevType {
+    if called_protocols.iter().any(is_lending_protocol) {
+        return MevType::Liquidation
+    }
+
+    let searcher_info = searcher_info.collect_vec();
+    let most_landed = ATTEMPTABLE_MEV_TYPES
+        .iter()
+        .rev()
+        .filter_map(|mev_type| {
+            let count = searcher_info
+                .iter()
+                .filter_map(|info| info.get_bundle_count_for_type(*mev_type))
+                .sum::<u64>();
+
+            (count > 0).then_some((*mev_type, count))
+        })
+        .max_by_key(|(_, count)| *count)
+        .map(|(mev_type, _)| mev_type);
+
+    let labelled = || {
+        ATTEMPTABLE_MEV_TYPES.iter().copied().find(|mev_type| {
+            searcher_info
+                .iter()
+                .any(|info| info.is_labelled_searcher_of_type(*mev_type))
+        })
+    };
+
+    most_landed
+        .or_else(labelled)
+        .unwrap_or(if pool_count(called_protocols) > 1 {
+            MevType::AtomicArb
+        } else {
+            MevType::Unknown
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::hex;
+    use brontes_types::{db::cex::trades::CexDexTradeConfig, mev::MevCount};
+
+    use super::*;
+    use crate::{
+        sniping::DEFAULT_SNIPING_MARKOUT_BLOCKS,
+        test_utils::{InspectorTestUtils, USDC_ADDRESS},
+        Inspectors,
+    };
+
+    fn searcher(mev_count: MevCount, config_labels: Vec<MevType>) -> SearcherInfo {
+        SearcherInfo { mev_count, config_labels, ..Default::default() }
+    }
+
+    #[test]
+    fn test_lending_calls_are_liquidation_attempts() {
+        let sandwicher = searcher(
+            MevCount { sandwich_count: Some(100), ..Default::default() },
+            vec![MevType::Sandwich],
+        );
+
+        assert_eq!(
+            attempted_mev_type([&sandwicher].into_iter(), &[Protocol::UniswapV2, Protocol::AaveV3]),
+            MevType::Liquidation
+        );
+    }
+
+    #[test]
+    fn test_attributes_to_most_landed_type() {
+        let eoa = searcher(
+            MevCount {
+                sandwich_count: Some(10),
+                atomic_backrun_count: Some(4),
+                ..Default::default()
+            },
+            vec![],
+        );
+        let contract = searcher(
+            MevCount { atomic_backrun_count: Some(8), ..Default::default() },
+            vec![MevType::Sandwich],
+        );
+
+        assert_eq!(
+            attempted_mev_type([&eoa, &contract].into_iter(), &[Protocol::UniswapV3]),
+            MevType::AtomicArb
+        );
+        assert_eq!(attempted_mev_type([&eoa].into_iter(), &[]), MevType::Sandwich);
+    }
+
+    #[test]
+    fn test_falls_back_to_labels_then_call_shape() {
+        let labelled = searcher(MevCount::default(), vec![MevType::SearcherTx, MevType::Jit]);
+        assert_eq!(attempted_mev_type([&labelled].into_iter(), &[]), MevType::Jit);
+
+        assert_eq!(
+            attempted_mev_type(std::iter::empty(), &[Protocol::UniswapV2, Protocol::SushiSwapV2]),
+            MevType::AtomicArb
+        );
+        assert_eq!(
+            attempted_mev_type(std::iter::empty(), &[Protocol::UniswapV2]),
+            MevType::Unknown
+        );
+    }
+
+    #[test]
+    fn test_vaults_are_not_arb_legs() {
+        assert_eq!(
+            attempted_mev_type(std::iter::empty(), &[Protocol::Weth, Protocol::UniswapV3]),
+            MevType::Unknown
+        );
+        assert_eq!(
+            attempted_mev_type(
+                std::iter::empty(),
+                &[Protocol::Lido, Protocol::UniswapV3, Protocol::CurveBasePool2]
+            ),
+            MevType::AtomicArb
+        );
+    }
+
+    #[brontes_macros::test]
+    async fn test_reverted_arb() {
+        let inspector_util = InspectorTestUtils::new(USDC_ADDRESS, 0.5).await;
+        let classifier_utils = &inspector_util.classifier_inspector;
+
+        // the arb of the atomic arb backrun test, reverted
+        let tx = hex!("76971a4f00a0a836322c9825b6edf06c8c49bf4261ef86fc88893154283a7124").into();
+        let tree = classifier_utils.build_tree_tx_reverted(tx).await.unwrap();
+
+        let [failed] = tree.failed_txs.as_slice() else {
+            panic!("expected one failed tx, found: {:#?}", tree.failed_txs)
+        };
+        assert_eq!(failed.tx_hash, tx);
+        assert_eq!(failed.position, 0);
+        assert!(failed.to.is_some());
+        assert!(failed.called.len() > 1, "the arb goes through more than one pool");
+
+        let metadata = classifier_utils
+            .get_metadata(tree.header.number, false)
+            .await
+            .unwrap_or_default();
+        let inspector = Inspectors::FailedAttempt.init_mev_inspector(
+            USDC_ADDRESS,
+            classifier_utils.libmdbx,
+            &[],
+            CexDexTradeConfig::default(),
+            DEFAULT_SNIPING_MARKOUT_BLOCKS,
+            None,
+        );
+        let mut bundles = inspector.inspect_block(MultiBlockData {
+            per_block_data: vec![BlockData {
+                metadata: Arc::new(metadata),
+                tree:     Arc::new(tree),
+            }],
+            blocks:         1,
+        });
+
+        assert_eq!(bundles.len(), 1, "expected one bundle, found: {bundles:#?}");
+        let bundle = bundles.remove(0);
+        let BundleData::FailedAttempt(attempt) = &bundle.data else {
+            panic!("expected a failed attempt, found: {:#?}", bundle.data)
+        };
+        assert_eq!(bundle.header.mev_type, MevType::FailedAttempt);
+        assert_eq!(attempt.tx_hash, tx);
+        assert_eq!(attempt.attempted_mev_type, MevType::AtomicArb);
+        assert!(!attempt.protocols.is_empty());
+        // all the transaction did was burn gas
+        assert!((bundle.header.profit_usd + bundle.header.bribe_usd).abs() < 1e-6);
+    }
+}
@@ -19,8 +19,9 @@ use reth_primitives::TxHash;
 
 use super::types::{PossibleJit, PossibleJitWithInfo};
 use crate::{
-    shared_utils::SharedInspectorUtils, Action, BlockTree, BundleData, Inspector, Metadata,
-    MAX_PROFIT,
+    rejections::{reject, RejectionCheck},
+    shared_utils::SharedInspectorUtils,
+    Action, BlockTree, BundleData, Inspector, Metadata, MAX_PROFIT,
 };
 
 pub struct JitInspector<'db, DB: LibmdbxReader> {
@@ -84,13 +85,29 @@ impl<DB: LibmdbxReader> JitInspector<'_, DB> {
 
                         tracing::trace!(?frontrun_txes, ?backrun_tx, "checking if jit");
 
+                        let tx_hashes = frontrun_txes
+                            .iter()
+                            .chain(victims.iter().flatten())
+                            .chain([&backrun_tx])
+                            .copied()
+                            .collect_vec();
+
                         if searcher_actions.is_empty() {
                             tracing::trace!("no searcher actions found");
+                            reject(RejectionCheck::Actions, &tx_hashes, || {
+                                "the searcher transactions have no actions".to_string()
+                            });
                             return None
                         }
 
-                        let victim_actions =
-                            self.get_victim_actions(victims, tree.clone(), executor_contract)?;
+                        let Some(victim_actions) =
+                            self.get_victim_actions(victims, tree.clone(), executor_contract)
+                        else {
+                            reject(RejectionCheck::Victims, &tx_hashes, || {
+                                "a victim reverted or called the searcher contract".to_string()
+                            });
+                            return None
+                        };
 
                         self.calculate_jit(
                             front_runs,
@@ -123,6 +140,7 @@ impl<DB: LibmdbxReader> JitInspector<'_, DB> {
                             Action::is_burn,
                             Action::is_transfer,
                             Action::is_eth_transfer,
+                            Action::is_vault,
                             Action::is_nested_action,
                         ]),
                     ),
@@ -132,6 +150,7 @@ impl<DB: LibmdbxReader> JitInspector<'_, DB> {
                             || actions.is_collect()
                             || actions.is_transfer()
                             || actions.is_eth_transfer()
+                            || actions.is_vault()
                     },
                 )
                 .collect::<Vec<_>>()
@@ -193,11 +212,22 @@ impl<DB: LibmdbxReader> JitInspector<'_, DB> {
             .flatten()
             .action_split_out((Action::try_mint, Action::try_burn, Action::try_collect));
 
+        let tx_hashes = frontrun_info
+            .iter()
+            .chain(victim_info.iter().flatten())
+            .chain([&backrun_info])
+            .map(|info| info.tx_hash)
+            .collect_vec();
+
         if mints.is_empty() || (burns.is_empty() && collect.is_empty()) {
             tracing::trace!("missing mints & burns");
+            reject(RejectionCheck::Actions, &tx_hashes, || {
+                "the searcher transactions don't mint and then burn or collect liquidity"
+                    .to_string()
+            });
             return None
         }
-        self.ensure_valid_structure(&mints, &burns, &victim_actions)?;
+        self.ensure_valid_structure(&mints, &burns, &victim_actions, &tx_hashes)?;
 
         let mut info_set = frontrun_info.clone();
         info_set.push(backrun_info.clone());
@@ -206,7 +236,7 @@ impl<DB: LibmdbxReader> JitInspector<'_, DB> {
 
         let deltas = rem
             .into_iter()
-            .filter(|f| f.is_transfer() || f.is_eth_transfer())
+            .filter(|f| f.is_transfer() || f.is_eth_transfer() || f.is_vault())
             .chain(
                 info_set
                     .iter()
@@ -350,6 +380,7 @@ impl<DB: LibmdbxReader> JitInspector<'_, DB> {
         mints: &[NormalizedMint],
         burns: &[NormalizedBurn],
         victim_actions: &[Vec<Action>],
+        tx_hashes: &[B256],
     ) -> Option<()> {
         // assert mints and burns are same pool
         let mut pools = FastHashSet::default();
@@ -359,6 +390,9 @@ impl<DB: LibmdbxReader> JitInspector<'_, DB> {
 
         if !burns.iter().any(|b| pools.contains(&b.pool)) {
             tracing::trace!("no burn overlaps");
+            reject(RejectionCheck::Actions, tx_hashes, || {
+                "the liquidity isn't burnt from a pool it was minted in".to_string()
+            });
             return None
         }
 
@@ -370,12 +404,18 @@ impl<DB: LibmdbxReader> JitInspector<'_, DB> {
             .map(|a| a.clone().force_swap())
             .collect::<Vec<_>>();
 
-        (v_swaps
+        let has_overlap = v_swaps
             .into_iter()
             .map(|swap| pools.contains(&swap.pool) as usize)
             .sum::<usize>()
-            != 0)
-            .then_some(())
+            != 0;
+        if !has_overlap {
+            reject(RejectionCheck::Victims, tx_hashes, || {
+                "no victim swaps through a pool the liquidity was minted in".to_string()
+            });
+        }
+
+        has_overlap.then_some(())
     }
 
     fn recursive_possible_jits(
@@ -5,20 +5,35 @@ use brontes_metrics::inspectors::OutlierMetrics;
 use brontes_types::{
     db::dex::PriceAt,
     mev::{Bundle, BundleData, Liquidation, MevType},
-    normalized_actions::{accounting::ActionAccounting, Action},
+    normalized_actions::{
+        accounting::ActionAccounting, Action, NormalizedLiquidation, NormalizedOracleUpdate,
+    },
+    tree::BlockTree,
     ActionIter, BlockData, FastHashSet, MultiBlockData, ToFloatNearest, TreeSearchBuilder, TxInfo,
 };
 use itertools::multizip;
 use malachite::{num::basic::traits::Zero, Rational};
-use reth_primitives::{b256, Address};
+use reth_primitives::{Address, B256};
 
 use super::MAX_PROFIT;
-use crate::{shared_utils::SharedInspectorUtils, Inspector, Metadata};
+use crate::{
+    rejections::{reject, RejectionCheck},
+    shared_utils::SharedInspectorUtils,
+    Inspector, Metadata,
+};
 
 pub struct LiquidationInspector<'db, DB: LibmdbxReader> {
     utils: SharedInspectorUtils<'db, DB>,
 }
 
+/// An oracle update of the block along with the transaction it was made in
+#[derive(Debug, Clone)]
+struct BlockOracleUpdate {
+    tx_index: u64,
+    tx_hash:  B256,
+    update:   NormalizedOracleUpdate,
+}
+
 impl<'db, DB: LibmdbxReader> LiquidationInspector<'db, DB> {
     pub fn new(quote: Address, db: &'db DB, metrics: Option<OutlierMetrics>) -> Self {
         Self { utils: SharedInspectorUtils::new(quote, db, metrics) }
@@ -41,13 +56,17 @@ impl<DB: LibmdbxReader> Inspector for LiquidationInspector<'_, DB> {
         let BlockData { metadata, tree } = block;
 
         let ex = || {
+            let oracle_updates = collect_oracle_updates(&tree);
+
             let (tx, liq): (Vec<_>, Vec<_>) = tree
                 .clone()
                 .collect_all(TreeSearchBuilder::default().with_actions([
                     Action::is_swap,
                     Action::is_liquidation,
+                    Action::is_lending,
                     Action::is_transfer,
                     Action::is_eth_transfer,
+                    Action::is_vault,
                     Action::is_aggregator,
                 ]))
                 .unzip();
@@ -61,7 +80,7 @@ impl<DB: LibmdbxReader> Inspector for LiquidationInspector<'_, DB> {
                         .flatten_nested_actions_default(liq.into_iter())
                         .collect::<Vec<_>>();
 
-                    self.calculate_liquidation(info, metadata.clone(), actions)
+                    self.calculate_liquidation(info, metadata.clone(), actions, &oracle_updates)
                 })
                 .collect::<Vec<_>>()
         };
@@ -78,14 +97,29 @@ impl<DB: LibmdbxReader> LiquidationInspector<'_, DB> {
         info: TxInfo,
         metadata: Arc<Metadata>,
         actions: Vec<Action>,
+        oracle_updates: &[BlockOracleUpdate],
     ) -> Option<Bundle> {
-        let (swaps, liqs): (Vec<_>, Vec<_>) = actions
-            .clone()
-            .into_iter()
-            .action_split((Action::try_swaps_merged, Action::try_liquidation));
+        let (swaps, liqs, loans, repayments, deposits, withdrawals): (
+            Vec<_>,
+            Vec<_>,
+            Vec<_>,
+            Vec<_>,
+            Vec<_>,
+            Vec<_>,
+        ) = actions.clone().into_iter().action_split((
+            Action::try_swaps_merged,
+            Action::try_liquidation,
+            Action::try_loan,
+            Action::try_repayment,
+            Action::try_deposit,
+            Action::try_withdraw,
+        ));
 
         if liqs.is_empty() {
             tracing::debug!("no liquidation events");
+            reject(RejectionCheck::Actions, &[info.tx_hash], || {
+                "the transaction has no liquidations".to_string()
+            });
             return None
         }
 
@@ -94,7 +128,7 @@ impl<DB: LibmdbxReader> LiquidationInspector<'_, DB> {
         let deltas = actions
             .into_iter()
             .chain(info.get_total_eth_value().iter().cloned().map(Action::from))
-            .filter(|a| a.is_eth_transfer() || a.is_transfer())
+            .filter(|a| a.is_eth_transfer() || a.is_transfer() || a.is_vault())
             .account_for_actions();
 
         let (rev, mut has_dex_price) = if let Some(rev) = self.utils.get_deltas_usd(
@@ -143,29 +177,110 @@ impl<DB: LibmdbxReader> LiquidationInspector<'_, DB> {
             },
         );
 
+        let trigger = find_triggering_update(oracle_updates, info.tx_index, &liqs);
+
         let new_liquidation = Liquidation {
-            block_number:        metadata.block_num,
+            block_number: metadata.block_num,
             liquidation_tx_hash: info.tx_hash,
-            trigger:             b256!(),
-            liquidation_swaps:   swaps,
-            liquidations:        liqs,
-            gas_details:         info.gas_details,
+            trigger: trigger.map(|t| t.tx_hash).unwrap_or_default(),
+            liquidation_swaps: swaps,
+            liquidations: liqs,
+            loans,
+            repayments,
+            deposits,
+            withdrawals,
+            oracle_update: trigger.map(|t| t.update.clone()),
+            txs_after_oracle_update: trigger.map(|t| info.tx_index - t.tx_index - 1),
+            gas_details: info.gas_details,
         };
 
         Some(Bundle { header, data: BundleData::Liquidation(new_liquidation) })
     }
 }
 
+fn collect_oracle_updates(tree: &Arc<BlockTree<Action>>) -> Vec<BlockOracleUpdate> {
+    tree.clone()
+        .collect_all(TreeSearchBuilder::default().with_action(Action::is_oracle_update))
+        .filter_map(|(tx_hash, updates)| Some((tree.get_root(tx_hash)?.position, tx_hash, updates)))
+        .flat_map(|(position, tx_hash, updates)| {
+            updates
+                .into_iter()
+                .filter_map(Action::try_oracle_update)
+                .map(move |update| BlockOracleUpdate { tx_index: position as u64, tx_hash, update })
+        })
+        .collect()
+}
+
+/// The last oracle update before the liquidation transaction that prices the
+/// collateral or debt asset of one of the liquidations. Liquidations without
+/// one are organic, the position became liquidatable before the block.
+fn find_triggering_update<'a>(
+    oracle_updates: &'a [BlockOracleUpdate],
+    tx_index: u64,
+    liquidations: &[NormalizedLiquidation],
+) -> Option<&'a BlockOracleUpdate> {
+    oracle_updates
+        .iter()
+        .filter(|oracle| oracle.tx_index < tx_index)
+        .filter(|oracle| {
+            liquidations.iter().any(|liq| {
+                oracle.update.prices(liq.collateral_asset.address)
+                    || oracle.update.prices(liq.debt_asset.address)
+            })
+        })
+        .max_by_key(|oracle| (oracle.tx_index, oracle.update.trace_index))
+}
+
 #[cfg(test)]
 mod tests {
 
     use alloy_primitives::hex;
+    use brontes_types::db::token_info::TokenInfoWithAddress;
 
+    use super::*;
     use crate::{
         test_utils::{InspectorTestUtils, InspectorTxRunConfig, USDC_ADDRESS},
         Inspectors,
     };
 
+    fn token(address: Address) -> TokenInfoWithAddress {
+        TokenInfoWithAddress { address, ..Default::default() }
+    }
+
+    fn oracle_update(tx_index: u64, base: Address) -> BlockOracleUpdate {
+        BlockOracleUpdate {
+            tx_index,
+            tx_hash: B256::with_last_byte(tx_index as u8),
+            update: NormalizedOracleUpdate { base_token: token(base), ..Default::default() },
+        }
+    }
+
+    #[test]
+    fn test_finds_last_preceding_update_of_liquidated_assets() {
+        let weth = Address::with_last_byte(1);
+        let wbtc = Address::with_last_byte(2);
+        let usdc = Address::with_last_byte(3);
+
+        let liquidation = NormalizedLiquidation {
+            collateral_asset: token(weth),
+            debt_asset: token(usdc),
+            ..Default::default()
+        };
+        let updates = vec![
+            oracle_update(1, weth),
+            oracle_update(3, weth),
+            oracle_update(4, wbtc),
+            oracle_update(8, weth),
+        ];
+
+        let trigger = find_triggering_update(&updates, 6, &[liquidation.clone()]).unwrap();
+        assert_eq!(trigger.tx_index, 3);
+
+        // updates after the liquidation, or of other assets, didn't trigger it
+        assert!(find_triggering_update(&updates, 1, &[liquidation.clone()]).is_none());
+        assert!(find_triggering_update(&updates[2..3], 6, &[liquidation]).is_none());
+    }
+
     #[brontes_macros::test]
     async fn test_aave_v3_liquidation() {
         let inspector_util = InspectorTestUtils::new(USDC_ADDRESS, 6.0).await;
@@ -1,11 +1,13 @@
 pub mod atomic_arb;
 pub mod cex_dex;
+pub mod failed_attempt;
 
 pub mod jit;
 pub mod liquidations;
 pub mod sandwich;
 pub mod searcher_activity;
 pub mod shared_utils;
+pub mod sniping;
 
 use malachite::Rational;
 /// Jokes for testing cur
@@ -5,6 +5,7 @@ use std::{
 
 use alloy_primitives::TxHash;
 use tracing::trace;
+mod multi_block;
 mod types;
 use brontes_database::libmdbx::LibmdbxReader;
 use brontes_metrics::inspectors::OutlierMetrics;
@@ -20,11 +21,16 @@ use brontes_types::{
 };
 use itertools::Itertools;
 use malachite::{num::basic::traits::Zero, Rational};
+pub use multi_block::MultiBlockSandwichInspector;
 use reth_primitives::{Address, B256};
 use types::{PossibleSandwich, PossibleSandwichWithTxInfo};
 
 use super::MAX_PROFIT;
-use crate::{shared_utils::SharedInspectorUtils, Inspector, Metadata};
+use crate::{
+    rejections::{reject, RejectionCheck},
+    shared_utils::SharedInspectorUtils,
+    Inspector, Metadata,
+};
 
 type GroupedVictims<'a> = HashMap<Address, Vec<&'a (Vec<NormalizedSwap>, Vec<NormalizedTransfer>)>>;
 
@@ -82,6 +88,7 @@ impl<DB: LibmdbxReader> SandwichInspector<'_, DB> {
             Action::is_swap,
             Action::is_transfer,
             Action::is_eth_transfer,
+            Action::is_vault,
             Action::is_nested_action,
         ]);
 
@@ -122,16 +129,31 @@ impl<DB: LibmdbxReader> SandwichInspector<'_, DB> {
             possible_backrun_info,
         } = ps;
 
+        let tx_hashes = possible_frontruns
+            .iter()
+            .chain(victims.iter().flatten())
+            .chain(std::iter::once(&possible_backrun))
+            .copied()
+            .collect_vec();
+
         if victims.iter().flatten().count() == 0 {
+            reject(RejectionCheck::Victims, &tx_hashes, || {
+                "no transactions between the frontrun and backrun".to_string()
+            });
             return None
         };
 
-        let victim_swaps_transfers: Vec<_> = self.get_victim_swap_transfer(
+        let Some(victim_swaps_transfers) = self.get_victim_swap_transfer(
             victims,
             tree.clone(),
             search_args.clone(),
             mev_executor_contract,
-        )?;
+        ) else {
+            reject(RejectionCheck::Victims, &tx_hashes, || {
+                "a victim reverted or called the searcher contract".to_string()
+            });
+            return None
+        };
 
         let searcher_actions: Vec<Vec<Action>> = tree
             .clone()
@@ -157,6 +179,7 @@ impl<DB: LibmdbxReader> SandwichInspector<'_, DB> {
         self.calculate_sandwich(
             tree.clone(),
             metadata.clone(),
+            metadata.clone(),
             possible_frontruns_info,
             possible_backrun_info,
             searcher_actions,
@@ -171,6 +194,7 @@ impl<DB: LibmdbxReader> SandwichInspector<'_, DB> {
         &self,
         tree: Arc<BlockTree<Action>>,
         metadata: Arc<Metadata>,
+        frontrun_metadata: Arc<Metadata>,
         possible_front_runs_info: Vec<TxInfo>,
         backrun_info: TxInfo,
         mut searcher_actions: Vec<Vec<Action>>,
@@ -179,6 +203,13 @@ impl<DB: LibmdbxReader> SandwichInspector<'_, DB> {
         black_list: FastHashSet<Address>,
         recusive: u8,
     ) -> Option<Vec<Bundle>> {
+        let tx_hashes = possible_front_runs_info
+            .iter()
+            .chain(victim_info.iter().flatten())
+            .chain(std::iter::once(&backrun_info))
+            .map(|info| info.tx_hash)
+            .collect_vec();
+
         // if all of the sandwichers have the same eoa or the to address is an mev
         // contract then we can continue. otherwise false positive
         if !(possible_front_runs_info
@@ -194,6 +225,10 @@ impl<DB: LibmdbxReader> SandwichInspector<'_, DB> {
                 == 1)
         {
             tracing::debug!(target: "brontes_inspect::sandwich", "all sandwiches don't have same eoa and aren't all verified contracts");
+            reject(RejectionCheck::Searcher, &tx_hashes, || {
+                "the frontruns and backrun aren't from the same eoa or all from mev contracts"
+                    .to_string()
+            });
             return None
         }
 
@@ -224,6 +259,9 @@ impl<DB: LibmdbxReader> SandwichInspector<'_, DB> {
             .iter()
             .all(|searcher_tx_swaps| !searcher_tx_swaps.is_empty())
         {
+            reject(RejectionCheck::Actions, &tx_hashes, || {
+                "a frontrun or the backrun has no swaps".to_string()
+            });
             return None
         }
 
@@ -240,9 +278,13 @@ impl<DB: LibmdbxReader> SandwichInspector<'_, DB> {
             // as a sandwich, we will recursively remove orders in both directions
             // to cover the full order-set to ensure that we don't miss any
             // opportunities
+            reject(RejectionCheck::Ordering, &tx_hashes, || {
+                "the frontruns, victims and backrun don't swap on a common pool".to_string()
+            });
             return self.recursive_possible_sandwiches(
                 tree.clone(),
                 metadata.clone(),
+                frontrun_metadata.clone(),
                 &possible_front_runs_info,
                 backrun_info,
                 &back_run_actions,
@@ -300,7 +342,7 @@ impl<DB: LibmdbxReader> SandwichInspector<'_, DB> {
             .into_iter()
             .flatten()
             .chain(back_run_actions)
-            .filter(|f| f.is_transfer() || f.is_eth_transfer())
+            .filter(|f| f.is_transfer() || f.is_eth_transfer() || f.is_vault())
             .chain(
                 possible_front_runs_info
                     .iter()
@@ -315,7 +357,7 @@ impl<DB: LibmdbxReader> SandwichInspector<'_, DB> {
         let mut has_dex_price = true;
         for (swaps, info) in front_run_swaps.iter().zip(&possible_front_runs_info) {
             has_dex_price &= self.utils.valid_pricing(
-                metadata.clone(),
+                frontrun_metadata.clone(),
                 swaps,
                 searcher_deltas
                     .values()
@@ -425,6 +467,7 @@ impl<DB: LibmdbxReader> SandwichInspector<'_, DB> {
 
         let sandwich = Sandwich {
             block_number: metadata.block_num,
+            frontrun_block_number: frontrun_metadata.block_num,
             frontrun_tx_hash,
             frontrun_gas_details,
             frontrun_swaps: front_run_swaps,
@@ -434,6 +477,7 @@ impl<DB: LibmdbxReader> SandwichInspector<'_, DB> {
             backrun_tx_hash: backrun_info.tx_hash,
             backrun_swaps: back_run_swaps,
             backrun_gas_details: backrun_info.gas_details,
+            victim_losses: vec![],
         };
         tracing::debug!("{:#?}\n{:#?}", header, sandwich);
 
@@ -450,6 +494,7 @@ impl<DB: LibmdbxReader> SandwichInspector<'_, DB> {
         &self,
         tree: Arc<BlockTree<Action>>,
         metadata: Arc<Metadata>,
+        frontrun_metadata: Arc<Metadata>,
         possible_front_runs_info: &[TxInfo],
         backrun_info: TxInfo,
         back_run_actions: &[Action],
@@ -495,6 +540,7 @@ impl<DB: LibmdbxReader> SandwichInspector<'_, DB> {
                 self.calculate_sandwich(
                     tree.clone(),
                     metadata.clone(),
+                    frontrun_metadata.clone(),
                     possible_front_runs_info,
                     back_run_info,
                     searcher_actions.to_vec(),
@@ -533,6 +579,7 @@ impl<DB: LibmdbxReader> SandwichInspector<'_, DB> {
                 self.calculate_sandwich(
                     tree.clone(),
                     metadata.clone(),
+                    frontrun_metadata.clone(),
                     possible_front_runs_info,
                     backrun_info,
                     searcher_actions,
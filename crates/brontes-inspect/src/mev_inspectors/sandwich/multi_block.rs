@@ -0,0 +1,367 @@
+use std::sync::Arc;
+
+use brontes_database::libmdbx::LibmdbxReader;
+use brontes_metrics::inspectors::OutlierMetrics;
+use brontes_types::{
+    mev::{Bundle, MevType},
+    normalized_actions::Action,
+    tree::{collect_address_set_for_accounting, BlockTree},
+    BlockData, FastHashMap, FastHashSet, MultiBlockData, TreeSearchBuilder,
+};
+use itertools::Itertools;
+use reth_primitives::{Address, B256};
+
+use super::{
+    types::{PossibleSandwich, PossibleSandwichWithTxInfo},
+    SandwichInspector,
+};
+use crate::Inspector;
+
+/// How many transactions from the end of the first block and from the start
+/// of the second block we consider for the frontrun and backrun legs.
+const MAX_BOUNDARY_DISTANCE: usize = 10;
+
+/// Detects sandwiches where the frontrun lands at the end of block N and the
+/// backrun at the start of block N + 1. This is the shape produced by builders
+/// that hold consecutive slots.
+///
+/// The verification and PnL logic is shared with [`SandwichInspector`]; this
+/// inspector only differs in how the possible sandwiches are generated and in
+/// pricing the frontrun against the metadata of the block it landed in.
+pub struct MultiBlockSandwichInspector<'db, DB: LibmdbxReader> {
+    inner: SandwichInspector<'db, DB>,
+}
+
+impl<'db, DB: LibmdbxReader> MultiBlockSandwichInspector<'db, DB> {
+    pub fn new(quote: Address, db: &'db DB, metrics: Option<OutlierMetrics>) -> Self {
+        Self { inner: SandwichInspector::new(quote, db, metrics) }
+    }
+}
+
+impl<DB: LibmdbxReader> Inspector for MultiBlockSandwichInspector<'_, DB> {
+    type Result = Vec<Bundle>;
+
+    // we need the previous block to find the frontrun
+    fn block_window(&self) -> usize {
+        2
+    }
+
+    fn get_id(&self) -> &str {
+        "MultiBlockSandwich"
+    }
+
+    fn get_quote_token(&self) -> Address {
+        self.inner.utils.quote
+    }
+
+    fn inspect_block(&self, data: MultiBlockData) -> Self::Result {
+        let [prev, cur] = match data.per_block_data.as_slice() {
+            [.., prev, cur] => [prev, cur],
+            _ => return vec![],
+        };
+
+        // only consecutive blocks can be sandwiched across
+        if prev.block_number() + 1 != cur.block_number() {
+            return vec![]
+        }
+
+        self.inner
+            .utils
+            .get_metrics()
+            .map(|m| m.run_inspector(MevType::Sandwich, || self.inspect_blocks_inner(prev, cur)))
+            .unwrap_or_else(|| self.inspect_blocks_inner(prev, cur))
+    }
+}
+
+impl<DB: LibmdbxReader> MultiBlockSandwichInspector<'_, DB> {
+    fn inspect_blocks_inner(&self, prev: &BlockData, cur: &BlockData) -> Vec<Bundle> {
+        tracing::trace!("starting multi block sandwich");
+        let search_args = TreeSearchBuilder::default().with_actions([
+            Action::is_swap,
+            Action::is_transfer,
+            Action::is_eth_transfer,
+            Action::is_vault,
+            Action::is_nested_action,
+        ]);
+
+        self.inner.utils.dedup_bundles(
+            self.get_possible_sandwich(prev.tree.clone(), cur.tree.clone())
+                .into_iter()
+                .filter_map(|ps| {
+                    self.collect_cross_block_sandwich_data(prev, cur, search_args.clone(), ps)
+                })
+                .flatten()
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    fn collect_cross_block_sandwich_data(
+        &self,
+        prev: &BlockData,
+        cur: &BlockData,
+        search_args: TreeSearchBuilder<Action>,
+        ps: PossibleSandwichWithTxInfo,
+    ) -> Option<Vec<Bundle>> {
+        let PossibleSandwichWithTxInfo {
+            inner:
+                PossibleSandwich {
+                    possible_frontruns,
+                    possible_backrun,
+                    mev_executor_contract,
+                    victims,
+                    ..
+                },
+            victims_info,
+            possible_frontruns_info,
+            possible_backrun_info,
+        } = ps;
+
+        // victims are split by the block boundary. Each side is collected from its
+        // own tree and then merged back into the single victim set of the frontrun.
+        let (prev_victims, cur_victims): (Vec<_>, Vec<_>) = victims
+            .into_iter()
+            .flatten()
+            .partition(|victim| prev.tree.get_root(*victim).is_some());
+
+        let victim_swaps_transfers = [(prev_victims, &prev.tree), (cur_victims, &cur.tree)]
+            .into_iter()
+            .filter(|(victims, _)| !victims.is_empty())
+            .map(|(victims, tree)| {
+                self.inner.get_victim_swap_transfer(
+                    vec![victims],
+                    tree.clone(),
+                    search_args.clone(),
+                    mev_executor_contract,
+                )
+            })
+            .collect::<Option<Vec<_>>>()?
+            .into_iter()
+            .flatten()
+            .flatten()
+            .collect_vec();
+
+        if victim_swaps_transfers.is_empty() {
+            return None
+        }
+
+        let searcher_actions: Vec<Vec<Action>> = prev
+            .tree
+            .clone()
+            .collect_txes(&possible_frontruns, search_args.clone())
+            .chain(
+                cur.tree
+                    .clone()
+                    .collect_txes(&[possible_backrun], search_args.clone()),
+            )
+            .map(|actions| {
+                self.inner
+                    .utils
+                    .flatten_nested_actions_default(actions.into_iter())
+                    .collect_vec()
+            })
+            .collect::<Vec<_>>();
+
+        let black_list: FastHashSet<Address> =
+            collect_address_set_for_accounting(&possible_frontruns_info);
+
+        self.inner.calculate_sandwich(
+            cur.tree.clone(),
+            cur.metadata.clone(),
+            prev.metadata.clone(),
+            possible_frontruns_info,
+            possible_backrun_info,
+            searcher_actions,
+            victims_info,
+            vec![victim_swaps_transfers],
+            black_list,
+            0,
+        )
+    }
+
+    /// Pairs the last transaction of a searcher eoa or mev contract near the
+    /// end of the previous block with its first transaction near the start
+    /// of the current block. Every transaction in between, on either side
+    /// of the block boundary, is a possible victim.
+    fn get_possible_sandwich(
+        &self,
+        prev: Arc<BlockTree<Action>>,
+        cur: Arc<BlockTree<Action>>,
+    ) -> Vec<PossibleSandwichWithTxInfo> {
+        let tail_start = prev.tx_roots.len().saturating_sub(MAX_BOUNDARY_DISTANCE);
+
+        // later txes overwrite earlier ones so that we always use the last frontrun
+        let mut frontruns: FastHashMap<Address, (usize, B256, Address)> = FastHashMap::default();
+        for (idx, root) in prev.tx_roots.iter().enumerate().skip(tail_start) {
+            if root.get_root_action().is_revert() {
+                continue
+            }
+            let frontrun = (idx, root.tx_hash, root.head.address);
+            frontruns.insert(root.head.address, frontrun);
+            frontruns.insert(root.get_to_address(), frontrun);
+        }
+
+        let mut seen_frontruns = FastHashSet::default();
+        let set = cur
+            .tx_roots
+            .iter()
+            .enumerate()
+            .take(MAX_BOUNDARY_DISTANCE)
+            .filter(|(_, root)| !root.get_root_action().is_revert())
+            .filter_map(|(backrun_idx, root)| {
+                let (frontrun_idx, frontrun, eoa) = frontruns
+                    .get(&root.head.address)
+                    .or_else(|| frontruns.get(&root.get_to_address()))
+                    .copied()?;
+
+                // only the first backrun for a given frontrun
+                if !seen_frontruns.insert(frontrun) {
+                    return None
+                }
+
+                let victims = prev.tx_roots[frontrun_idx + 1..]
+                    .iter()
+                    .chain(&cur.tx_roots[..backrun_idx])
+                    .map(|root| root.tx_hash)
+                    .collect_vec();
+
+                if victims.is_empty() {
+                    return None
+                }
+
+                Some(PossibleSandwich {
+                    eoa,
+                    possible_frontruns: vec![frontrun],
+                    possible_backrun: root.tx_hash,
+                    mev_executor_contract: root.get_to_address(),
+                    victims: vec![victims],
+                })
+            })
+            .collect_vec();
+
+        let (prev_txes, cur_txes): (Vec<_>, Vec<_>) = set
+            .iter()
+            .flat_map(|ps| {
+                ps.possible_frontruns
+                    .iter()
+                    .chain(ps.victims.iter().flatten())
+                    .chain(std::iter::once(&ps.possible_backrun))
+                    .copied()
+            })
+            .unique()
+            .partition(|tx| prev.get_root(*tx).is_some());
+
+        let tx_info_map = prev
+            .get_tx_info_batch(&prev_txes, self.inner.utils.db)
+            .into_iter()
+            .chain(cur.get_tx_info_batch(&cur_txes, self.inner.utils.db))
+            .flatten()
+            .map(|info| (info.tx_hash, info))
+            .collect::<FastHashMap<_, _>>();
+
+        set.into_iter()
+            .filter_map(|ps| PossibleSandwichWithTxInfo::from_ps(ps, &tx_info_map))
+            .collect_vec()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::hex;
+
+    use crate::{
+        test_utils::{InspectorTestUtils, InspectorTxRunConfig, USDC_ADDRESS},
+        Inspectors,
+    };
+
+    #[brontes_macros::test]
+    async fn test_multi_block_sandwich() {
+        let inspector_util = InspectorTestUtils::new(USDC_ADDRESS, 1.0).await;
+
+        // the frontrun and victim land in the first block, the backrun in the next
+        let config = InspectorTxRunConfig::new(Inspectors::MultiBlockSandwich)
+            .with_mev_tx_hashes(vec![
+                hex!("ff79c471b191c0021cfb62408cb1d7418d09334665a02106191f6ed16a47e36c").into(),
+                hex!("19122ffe65a714f0551edbb16a24551031056df16ccaab39db87a73ac657b722").into(),
+                hex!("67771f2e3b0ea51c11c5af156d679ccef6933db9a4d4d6cd7605b4eee27f9ac8").into(),
+            ])
+            .with_block_split_after(
+                hex!("19122ffe65a714f0551edbb16a24551031056df16ccaab39db87a73ac657b722").into(),
+            )
+            .with_dex_prices()
+            .needs_token(hex!("28cf5263108c1c40cf30e0fe390bd9ccf929bf82").into())
+            .with_gas_paid_usd(16.64)
+            .with_expected_profit_usd(15.648);
+
+        inspector_util
+            .run_inspector(
+                config,
+                Some(Box::new(|bundle| {
+                    let brontes_types::mev::BundleData::Sandwich(sando) = &bundle.data else {
+                        panic!("expected a sandwich, found {:#?}", bundle.data)
+                    };
+                    assert_eq!(sando.frontrun_block_number + 1, sando.block_number);
+                    assert_eq!(sando.victim_swaps_tx_hashes.iter().flatten().count(), 1);
+                })),
+            )
+            .await
+            .unwrap();
+    }
+
+    #[brontes_macros::test]
+    async fn test_multi_block_sandwich_victim_in_next_block() {
+        let inspector_util = InspectorTestUtils::new(USDC_ADDRESS, 1.0).await;
+
+        // only the frontrun lands in the first block
+        let config = InspectorTxRunConfig::new(Inspectors::MultiBlockSandwich)
+            .with_mev_tx_hashes(vec![
+                hex!("ff79c471b191c0021cfb62408cb1d7418d09334665a02106191f6ed16a47e36c").into(),
+                hex!("19122ffe65a714f0551edbb16a24551031056df16ccaab39db87a73ac657b722").into(),
+                hex!("67771f2e3b0ea51c11c5af156d679ccef6933db9a4d4d6cd7605b4eee27f9ac8").into(),
+            ])
+            .with_block_split_after(
+                hex!("ff79c471b191c0021cfb62408cb1d7418d09334665a02106191f6ed16a47e36c").into(),
+            )
+            .with_dex_prices()
+            .needs_token(hex!("28cf5263108c1c40cf30e0fe390bd9ccf929bf82").into())
+            .with_gas_paid_usd(16.64)
+            .with_expected_profit_usd(15.648);
+
+        inspector_util.run_inspector(config, None).await.unwrap();
+    }
+
+    #[brontes_macros::test]
+    async fn test_multi_block_sandwich_no_victims() {
+        let inspector_util = InspectorTestUtils::new(USDC_ADDRESS, 1.0).await;
+
+        // without the victim there is nothing between the frontrun and backrun
+        let config = InspectorTxRunConfig::new(Inspectors::MultiBlockSandwich)
+            .with_mev_tx_hashes(vec![
+                hex!("ff79c471b191c0021cfb62408cb1d7418d09334665a02106191f6ed16a47e36c").into(),
+                hex!("67771f2e3b0ea51c11c5af156d679ccef6933db9a4d4d6cd7605b4eee27f9ac8").into(),
+            ])
+            .with_block_split_after(
+                hex!("ff79c471b191c0021cfb62408cb1d7418d09334665a02106191f6ed16a47e36c").into(),
+            )
+            .with_dex_prices()
+            .needs_token(hex!("28cf5263108c1c40cf30e0fe390bd9ccf929bf82").into());
+
+        inspector_util.assert_no_mev(config).await.unwrap();
+    }
+
+    #[brontes_macros::test]
+    async fn test_multi_block_sandwich_needs_two_blocks() {
+        let inspector_util = InspectorTestUtils::new(USDC_ADDRESS, 1.0).await;
+
+        // a sandwich within a single block is left to the sandwich inspector
+        let config = InspectorTxRunConfig::new(Inspectors::MultiBlockSandwich)
+            .with_mev_tx_hashes(vec![
+                hex!("ff79c471b191c0021cfb62408cb1d7418d09334665a02106191f6ed16a47e36c").into(),
+                hex!("19122ffe65a714f0551edbb16a24551031056df16ccaab39db87a73ac657b722").into(),
+                hex!("67771f2e3b0ea51c11c5af156d679ccef6933db9a4d4d6cd7605b4eee27f9ac8").into(),
+            ])
+            .with_dex_prices()
+            .needs_token(hex!("28cf5263108c1c40cf30e0fe390bd9ccf929bf82").into());
+
+        inspector_util.assert_no_mev(config).await.unwrap();
+    }
+}
@@ -56,8 +56,11 @@ impl<DB: LibmdbxReader> SearcherActivity<'_, DB> {
         tree: Arc<BlockTree<Action>>,
         metadata: Arc<Metadata>,
     ) -> Vec<Bundle> {
-        let search_args = TreeSearchBuilder::default()
-            .with_actions([Action::is_transfer, Action::is_eth_transfer]);
+        let search_args = TreeSearchBuilder::default().with_actions([
+            Action::is_transfer,
+            Action::is_eth_transfer,
+            Action::is_vault,
+        ]);
 
         let (hashes, transfers): (Vec<_>, Vec<_>) = tree.clone().collect_all(search_args).unzip();
         let tx_info = tree.get_tx_info_batch(&hashes, self.utils.db);
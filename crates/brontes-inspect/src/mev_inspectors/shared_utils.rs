@@ -106,14 +106,17 @@ impl<DB: LibmdbxReader> SharedInspectorUtils<'_, DB> {
         Some(usd_deltas)
     }
 
-    // will flatten nested and filter out actions that aren't swap, transfer or
-    // eth_transfer
+    // will flatten nested and filter out actions that aren't swap, transfer,
+    // eth_transfer or vault deposits and withdrawals
     pub fn flatten_nested_actions_default<'a>(
         &self,
         iter: impl Iterator<Item = Action> + 'a,
     ) -> impl Iterator<Item = Action> + 'a {
         self.flatten_nested_actions(iter, &|action| {
-            action.is_swap() || action.is_transfer() || action.is_eth_transfer()
+            action.is_swap()
+                || action.is_transfer()
+                || action.is_eth_transfer()
+                || action.is_vault()
         })
     }
 
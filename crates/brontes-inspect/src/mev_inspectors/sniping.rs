@@ -0,0 +1,663 @@
+use std::sync::Arc;
+
+use alloy_primitives::TxHash;
+use brontes_database::libmdbx::LibmdbxReader;
+use brontes_metrics::inspectors::OutlierMetrics;
+use brontes_types::{
+    constants::{DAI_ADDRESS, ETH_ADDRESS, USDC_ADDRESS, USDT_ADDRESS, WETH_ADDRESS},
+    db::dex::BlockPrice,
+    mev::{Bundle, BundleData, MevType, Sniping},
+    normalized_actions::{accounting::ActionAccounting, Action, NormalizedSwap},
+    ActionIter, BlockData, FastHashMap, FastHashSet, MultiBlockData, Protocol, ToFloatNearest,
+    TreeSearchBuilder, TxInfo,
+};
+use itertools::Itertools;
+use malachite::{num::basic::traits::Zero, Rational};
+use reth_primitives::Address;
+
+use super::MAX_PROFIT;
+use crate::{shared_utils::SharedInspectorUtils, Inspector, Metadata};
+
+/// Number of blocks after the launch block a snipe is marked to market at,
+/// unless configured otherwise
+pub const DEFAULT_SNIPING_MARKOUT_BLOCKS: usize = 10;
+
+/// A mint only launches a pool that was created at most this many blocks
+/// before it (~1 hour). Older pools have been trading already, so a mint on
+/// them is just more liquidity.
+const MAX_POOL_AGE_BLOCKS: u64 = 300;
+
+/// Tokens new tokens get paired against. A pool is only a launch if exactly
+/// one of its tokens isn't one of these.
+const BASE_TOKENS: [Address; 5] =
+    [WETH_ADDRESS, ETH_ADDRESS, USDC_ADDRESS, USDT_ADDRESS, DAI_ADDRESS];
+
+/// Detects buys of a freshly launched token that land in the launch block or
+/// in the block right after it. A pool is launched by its first liquidity
+/// mint, as that is when it becomes tradeable.
+///
+/// The inspector runs over a window of `markout_blocks + 1` blocks, the oldest
+/// of which is the launch block. The position of each sniper is marked to
+/// market at the most recent block of the window, which is also the block the
+/// bundle is attributed to, as the results of an inspection are stored in the
+/// `MevBlock` of the most recent block.
+pub struct SnipingInspector<'db, DB: LibmdbxReader> {
+    utils:          SharedInspectorUtils<'db, DB>,
+    markout_blocks: usize,
+}
+
+impl<'db, DB: LibmdbxReader> SnipingInspector<'db, DB> {
+    pub fn new(
+        quote: Address,
+        db: &'db DB,
+        markout_blocks: usize,
+        metrics: Option<OutlierMetrics>,
+    ) -> Self {
+        Self { utils: SharedInspectorUtils::new(quote, db, metrics), markout_blocks }
+    }
+}
+
+/// The first liquidity mint of a new pool
+#[derive(Debug, Clone)]
+struct Launch {
+    tx_index: u64,
+    tx_hash:  TxHash,
+    launcher: Address,
+    pool:     Address,
+    protocol: Protocol,
+    token:    Address,
+}
+
+/// A transaction of the window that traded one of the launched tokens
+#[derive(Debug)]
+struct TokenTrade {
+    /// Number of blocks after the launch block the trade landed in
+    block_offset: u64,
+    metadata:     Arc<Metadata>,
+    info:         TxInfo,
+    swaps:        Vec<NormalizedSwap>,
+    /// Transfers and vault deposits and withdrawals of the transaction, used
+    /// for accounting
+    transfers:    Vec<Action>,
+}
+
+impl TokenTrade {
+    fn trades(&self, token: Address) -> bool {
+        self.swaps
+            .iter()
+            .any(|swap| swap.token_in.address == token || swap.token_out.address == token)
+    }
+
+    fn buys(&self, token: Address) -> bool {
+        self.swaps
+            .iter()
+            .any(|swap| swap.token_out.address == token)
+    }
+
+    fn position(&self) -> (u64, u64) {
+        (self.block_offset, self.info.tx_index)
+    }
+}
+
+impl<DB: LibmdbxReader> Inspector for SnipingInspector<'_, DB> {
+    type Result = Vec<Bundle>;
+
+    // the launch block and the blocks of the markout
+    fn block_window(&self) -> usize {
+        self.markout_blocks + 1
+    }
+
+    fn get_id(&self) -> &str {
+        "Sniping"
+    }
+
+    fn get_quote_token(&self) -> Address {
+        self.utils.quote
+    }
+
+    fn inspect_block(&self, data: MultiBlockData) -> Self::Result {
+        let blocks = data.per_block_data;
+
+        // the markout is only meaningful over consecutive blocks
+        if blocks.is_empty()
+            || !blocks
+                .iter()
+                .tuple_windows()
+                .all(|(prev, cur)| prev.block_number() + 1 == cur.block_number())
+        {
+            return vec![]
+        }
+
+        self.utils
+            .get_metrics()
+            .map(|m| m.run_inspector(MevType::Sniping, || self.inspect_window(&blocks)))
+            .unwrap_or_else(|| self.inspect_window(&blocks))
+    }
+}
+
+impl<DB: LibmdbxReader> SnipingInspector<'_, DB> {
+    fn inspect_window(&self, blocks: &[BlockData]) -> Vec<Bundle> {
+        let launches = self.find_launches(&blocks[0]);
+        if launches.is_empty() {
+            return vec![]
+        }
+
+        let tokens = launches.iter().map(|launch| launch.token).collect();
+        let trades = self.collect_token_trades(blocks, &tokens);
+
+        let snipes = launches
+            .iter()
+            .flat_map(|launch| {
+                trades
+                    .iter()
+                    .filter(|trade| {
+                        is_snipe_position(launch.tx_index, trade.block_offset, trade.info.tx_index)
+                            && trade.info.eoa != launch.launcher
+                            && trade.buys(launch.token)
+                    })
+                    .into_group_map_by(|trade| trade.info.eoa)
+                    .into_iter()
+                    .map(move |(eoa, snipes)| (launch, eoa, snipes))
+            })
+            .collect_vec();
+
+        // snipers that went after more than one launch of the block
+        let launches_per_sniper = snipes.iter().map(|(_, eoa, _)| *eoa).counts();
+
+        let end = blocks.last().unwrap();
+        snipes
+            .into_iter()
+            .filter_map(|(launch, eoa, snipes)| {
+                let first_snipe = snipes.first()?.position();
+                let exits = trades
+                    .iter()
+                    .filter(|trade| {
+                        trade.info.eoa == eoa
+                            && trade.position() > first_snipe
+                            && trade.trades(launch.token)
+                            && !snipes
+                                .iter()
+                                .any(|snipe| snipe.info.tx_hash == trade.info.tx_hash)
+                    })
+                    .collect_vec();
+
+                self.build_bundle(
+                    launch,
+                    blocks[0].block_number(),
+                    snipes,
+                    exits,
+                    launches_per_sniper[&eoa] > 1,
+                    end,
+                )
+            })
+            .collect()
+    }
+
+    /// Pools that got their first liquidity in the block, along with the
+    /// token they launched
+    fn find_launches(&self, block: &BlockData) -> Vec<Launch> {
+        let tree = &block.tree;
+        let launch_block = block.block_number();
+
+        let mut created_pools = FastHashSet::default();
+        let mut traded_pools = FastHashSet::default();
+        let mut funded_pools = FastHashSet::default();
+        let mut launches = Vec::new();
+
+        for (root, tx_hash, actions) in tree
+            .clone()
+            .collect_all(TreeSearchBuilder::default().with_actions([
+                Action::is_new_pool,
+                Action::is_mint,
+                Action::is_swap,
+                Action::is_nested_action,
+            ]))
+            .filter_map(|(tx_hash, actions)| Some((tree.get_root(tx_hash)?, tx_hash, actions)))
+            .sorted_by_key(|(root, ..)| root.position)
+        {
+            let (new_pools, mints, swaps): (Vec<_>, Vec<_>, Vec<_>) = self
+                .utils
+                .flatten_nested_actions(actions.into_iter(), &|action| {
+                    action.is_new_pool() || action.is_mint() || action.is_swap()
+                })
+                .action_split((Action::try_new_pool, Action::try_mint, Action::try_swaps_merged));
+
+            created_pools.extend(new_pools.into_iter().map(|pool| pool.pool_address));
+
+            for mint in mints {
+                // only the first mint of a pool that hasn't been traded yet launches it
+                if traded_pools.contains(&mint.pool) || !funded_pools.insert(mint.pool) {
+                    continue
+                }
+
+                let is_new_pool = created_pools.contains(&mint.pool)
+                    || self
+                        .utils
+                        .db
+                        .get_protocol_details(mint.pool)
+                        .is_ok_and(|details| {
+                            details.init_block + MAX_POOL_AGE_BLOCKS >= launch_block
+                        });
+                if !is_new_pool {
+                    continue
+                }
+
+                let tokens = mint.token.iter().map(|token| token.address).collect_vec();
+                let Some(token) = launched_token(&tokens) else { continue };
+
+                launches.push(Launch {
+                    tx_index: root.position as u64,
+                    tx_hash,
+                    launcher: root.get_from_address(),
+                    pool: mint.pool,
+                    protocol: mint.protocol,
+                    token,
+                });
+            }
+
+            traded_pools.extend(swaps.into_iter().map(|swap| swap.pool));
+        }
+
+        launches
+    }
+
+    /// All transactions of the window that swapped one of the tokens
+    fn collect_token_trades(
+        &self,
+        blocks: &[BlockData],
+        tokens: &FastHashSet<Address>,
+    ) -> Vec<TokenTrade> {
+        blocks
+            .iter()
+            .enumerate()
+            .flat_map(|(block_offset, block)| {
+                let (tx_hashes, actions): (Vec<_>, Vec<_>) = block
+                    .tree
+                    .clone()
+                    .collect_all(TreeSearchBuilder::default().with_actions([
+                        Action::is_swap,
+                        Action::is_transfer,
+                        Action::is_eth_transfer,
+                        Action::is_vault,
+                        Action::is_nested_action,
+                    ]))
+                    .unzip();
+                let tx_info = block.tree.get_tx_info_batch(&tx_hashes, self.utils.db);
+
+                actions
+                    .into_iter()
+                    .zip(tx_info)
+                    .filter_map(|(actions, info)| {
+                        let info = info?;
+                        let actions = self
+                            .utils
+                            .flatten_nested_actions_default(actions.into_iter())
+                            .collect_vec();
+
+                        let (swaps, transfers, eth_transfers, deposits, withdraws): (
+                            Vec<_>,
+                            Vec<_>,
+                            Vec<_>,
+                            Vec<_>,
+                            Vec<_>,
+                        ) = actions.into_iter().action_split((
+                            Action::try_swaps_merged,
+                            Action::try_transfer,
+                            Action::try_eth_transfer,
+                            Action::try_vault_deposit,
+                            Action::try_vault_withdraw,
+                        ));
+
+                        swaps
+                            .iter()
+                            .any(|swap| {
+                                tokens.contains(&swap.token_in.address)
+                                    || tokens.contains(&swap.token_out.address)
+                            })
+                            .then(|| TokenTrade {
+                                block_offset: block_offset as u64,
+                                metadata: block.metadata.clone(),
+                                info,
+                                swaps,
+                                transfers: transfers
+                                    .into_iter()
+                                    .map(Action::from)
+                                    .chain(eth_transfers.into_iter().map(Action::from))
+                                    .chain(deposits.into_iter().map(Action::from))
+                                    .chain(withdraws.into_iter().map(Action::from))
+                                    .collect(),
+                            })
+                    })
+                    .sorted_by_key(|trade| trade.info.tx_index)
+                    .collect_vec()
+            })
+            .collect()
+    }
+
+    fn build_bundle(
+        &self,
+        launch: &Launch,
+        launch_block: u64,
+        snipes: Vec<&TokenTrade>,
+        exits: Vec<&TokenTrade>,
+        sniped_other_launches: bool,
+        end: &BlockData,
+    ) -> Option<Bundle> {
+        let first_snipe = snipes.first()?;
+        let token = first_snipe
+            .swaps
+            .iter()
+            .find(|swap| swap.token_out.address == launch.token)?
+            .token_out
+            .clone();
+
+        let trades = snipes.iter().chain(exits.iter()).collect_vec();
+
+        let mev_addresses: FastHashSet<Address> = trades
+            .iter()
+            .flat_map(|trade| trade.info.collect_address_set_for_accounting())
+            .collect();
+
+        let tx_deltas = trades
+            .iter()
+            .map(|trade| {
+                trade
+                    .transfers
+                    .iter()
+                    .cloned()
+                    .chain(
+                        trade
+                            .info
+                            .get_total_eth_value()
+                            .iter()
+                            .cloned()
+                            .map(Action::from),
+                    )
+                    .account_for_actions()
+            })
+            .collect_vec();
+
+        // the net position of the sniper over the whole window
+        let mut position: FastHashMap<Address, Rational> = FastHashMap::default();
+        tx_deltas
+            .iter()
+            .flatten()
+            .filter(|(address, _)| mev_addresses.contains(*address))
+            .flat_map(|(_, token_deltas)| token_deltas.iter())
+            .for_each(|(token, amount)| {
+                *position.entry(*token).or_insert(Rational::ZERO) += amount;
+            });
+
+        let tokens_held = position
+            .get(&launch.token)
+            .map(|amount| amount.clone().to_float())
+            .unwrap_or_default();
+
+        // tokens still held are valued at the lowest price of the last block, as
+        // prices of new tokens swing a lot within a block
+        let value = position
+            .iter()
+            .filter(|(_, amount)| **amount != Rational::ZERO)
+            .map(|(token, amount)| {
+                self.utils.get_token_value_dex_block(
+                    BlockPrice::Lowest,
+                    *token,
+                    amount,
+                    &end.metadata,
+                )
+            })
+            .fold(Some(Rational::ZERO), |acc, value| Some(acc? + value?));
+
+        let gas_usd = trades
+            .iter()
+            .map(|trade| {
+                trade
+                    .metadata
+                    .get_gas_price_usd(trade.info.gas_details.gas_paid(), self.utils.quote)
+            })
+            .fold(Rational::ZERO, |acc, gas| acc + gas);
+
+        let profit = value
+            .map(|value| value - gas_usd)
+            .filter(|profit| profit < &MAX_PROFIT);
+        let no_pricing_calculated = profit.is_none();
+
+        let prior_snipes = first_snipe
+            .info
+            .get_searcher_eao_info()
+            .and_then(|info| info.mev_count.sniping_count)
+            .unwrap_or_default();
+
+        let gas_details = trades
+            .iter()
+            .map(|trade| trade.info.gas_details)
+            .collect_vec();
+
+        let markout_metadata = end.metadata.clone();
+        let header = self.utils.build_bundle_header(
+            tx_deltas,
+            trades.iter().map(|trade| trade.info.tx_hash).collect(),
+            &first_snipe.info,
+            profit.unwrap_or_default().to_float(),
+            &gas_details,
+            end.metadata.clone(),
+            MevType::Sniping,
+            no_pricing_calculated,
+            |this, token, amount| {
+                this.get_token_value_dex_block(
+                    BlockPrice::Lowest,
+                    token,
+                    &amount,
+                    &markout_metadata,
+                )
+            },
+        );
+
+        let swaps_and_gas = |trades: &[&TokenTrade]| {
+            (
+                trades.iter().map(|trade| trade.info.tx_hash).collect_vec(),
+                trades.iter().map(|trade| trade.swaps.clone()).collect_vec(),
+                trades
+                    .iter()
+                    .map(|trade| trade.info.gas_details)
+                    .collect_vec(),
+            )
+        };
+        let (snipe_tx_hashes, snipe_swaps, snipe_gas_details) = swaps_and_gas(&snipes);
+        let (exit_tx_hashes, exit_swaps, exit_gas_details) = swaps_and_gas(&exits);
+
+        Some(Bundle {
+            header,
+            data: BundleData::Sniping(Sniping {
+                block_number: end.block_number(),
+                launch_block_number: launch_block,
+                launch_tx_hash: launch.tx_hash,
+                protocol: launch.protocol,
+                pool: launch.pool,
+                token,
+                blocks_after_launch: first_snipe.block_offset,
+                snipe_tx_hashes,
+                snipe_swaps,
+                snipe_gas_details,
+                exit_tx_hashes,
+                exit_swaps,
+                exit_gas_details,
+                tokens_held,
+                markout_blocks: end.block_number() - launch_block,
+                prior_snipes,
+                repeat_sniper: prior_snipes > 0 || sniped_other_launches,
+            }),
+        })
+    }
+}
+
+/// The token a pool launched, if it pairs a single new token with base tokens
+fn launched_token(tokens: &[Address]) -> Option<Address> {
+    let (base, new): (Vec<_>, Vec<_>) =
+        tokens.iter().partition(|token| BASE_TOKENS.contains(token));
+
+    (!base.is_empty() && new.len() == 1).then(|| *new[0])
+}
+
+/// Snipes land after the launch transaction in the launch block, or in the
+/// block right after it
+fn is_snipe_position(launch_tx_index: u64, block_offset: u64, tx_index: u64) -> bool {
+    match block_offset {
+        0 => tx_index > launch_tx_index,
+        1 => true,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{hex, B256, U256};
+    use brontes_types::{
+        db::{
+            metadata::BlockMetadata,
+            token_info::{TokenInfo, TokenInfoWithAddress},
+        },
+        normalized_actions::{NormalizedMint, NormalizedNewPool},
+        tree::{BlockTree, GasDetails, Node, NodeData, Root},
+    };
+    use reth_primitives::Header;
+
+    use super::*;
+    use crate::test_utils::InspectorTestUtils;
+
+    const PEPE: Address = Address::new(hex!("6982508145454Ce325dDbE47a25d4ec3d2311933"));
+    const SHIB: Address = Address::new(hex!("95aD61b0a150d79219dCF64E1E6Cc01f0B64C4cE"));
+    const POOL: Address = Address::new(hex!("a43fe16908251ee70ef74718545e4fe6c5ccec9f"));
+    const LAUNCHER: Address = Address::repeat_byte(0x11);
+    const SNIPER: Address = Address::repeat_byte(0x22);
+
+    fn pepe() -> TokenInfoWithAddress {
+        TokenInfoWithAddress { address: PEPE, inner: TokenInfo::new(18, "PEPE".to_string()) }
+    }
+
+    fn root(position: usize, eoa: Address, actions: Vec<Action>) -> Root<Action> {
+        let mut root = Root {
+            head: Node::new(0, eoa, vec![]),
+            position,
+            tx_hash: B256::repeat_byte(position as u8 + 1),
+            private: false,
+            gas_details: GasDetails::default(),
+            total_msg_value_transfers: vec![],
+            data_store: NodeData(vec![Some(actions)]),
+        };
+        root.finalize();
+        root
+    }
+
+    fn block(number: u64, roots: Vec<Root<Action>>) -> BlockData {
+        let mut tree = BlockTree::new(Header { number, ..Default::default() }, roots.len());
+        roots.into_iter().for_each(|root| tree.insert_root(root));
+
+        BlockData {
+            metadata: Arc::new(Metadata {
+                block_metadata: BlockMetadata { block_num: number, ..Default::default() },
+                ..Default::default()
+            }),
+            tree:     Arc::new(tree),
+        }
+    }
+
+    #[test]
+    fn test_launched_token() {
+        assert_eq!(launched_token(&[PEPE, WETH_ADDRESS]), Some(PEPE));
+        assert_eq!(launched_token(&[USDC_ADDRESS, PEPE]), Some(PEPE));
+
+        // nothing new is launched by a pool of base tokens
+        assert_eq!(launched_token(&[WETH_ADDRESS, USDC_ADDRESS]), None);
+        // or ambiguous which token was launched
+        assert_eq!(launched_token(&[PEPE, SHIB]), None);
+        assert_eq!(launched_token(&[PEPE, SHIB, WETH_ADDRESS]), None);
+    }
+
+    #[test]
+    fn test_snipe_position() {
+        // same block, only after the launch
+        assert!(!is_snipe_position(10, 0, 9));
+        assert!(!is_snipe_position(10, 0, 10));
+        assert!(is_snipe_position(10, 0, 11));
+
+        // anywhere in the next block
+        assert!(is_snipe_position(10, 1, 0));
+
+        // later buys aren't snipes
+        assert!(!is_snipe_position(10, 2, 0));
+    }
+
+    #[brontes_macros::test]
+    async fn test_bundle_is_attributed_to_storage_block() {
+        let inspector_util = InspectorTestUtils::new(USDC_ADDRESS, 0.5).await;
+        let inspector = SnipingInspector::new(
+            USDC_ADDRESS,
+            inspector_util.classifier_inspector.libmdbx,
+            2,
+            None,
+        );
+
+        let launch = root(
+            0,
+            LAUNCHER,
+            vec![
+                Action::NewPool(NormalizedNewPool {
+                    trace_index:  0,
+                    protocol:     Protocol::UniswapV2,
+                    pool_address: POOL,
+                    tokens:       vec![PEPE, WETH_ADDRESS],
+                }),
+                Action::Mint(NormalizedMint {
+                    protocol:    Protocol::UniswapV2,
+                    trace_index: 1,
+                    from:        LAUNCHER,
+                    recipient:   LAUNCHER,
+                    pool:        POOL,
+                    token:       vec![pepe(), TokenInfoWithAddress::weth()],
+                    amount:      vec![Rational::from(1_000_000), Rational::from(10)],
+                }),
+            ],
+        );
+        let snipe = root(
+            3,
+            SNIPER,
+            vec![Action::Swap(NormalizedSwap {
+                protocol:    Protocol::UniswapV2,
+                trace_index: 0,
+                from:        SNIPER,
+                recipient:   SNIPER,
+                pool:        POOL,
+                token_in:    TokenInfoWithAddress::weth(),
+                token_out:   pepe(),
+                amount_in:   Rational::from(1),
+                amount_out:  Rational::from(90_000),
+                msg_value:   U256::ZERO,
+            })],
+        );
+
+        let window = MultiBlockData {
+            per_block_data: vec![
+                block(100, vec![launch]),
+                block(101, vec![snipe]),
+                block(102, vec![]),
+            ],
+            blocks:         3,
+        };
+        // the results of the window are stored in the mev block of its most recent
+        // block
+        let storage_block = window.get_most_recent_block().block_number();
+        let bundles = inspector.inspect_block(window);
+
+        assert_eq!(bundles.len(), 1, "expected one bundle, found: {bundles:#?}");
+        let BundleData::Sniping(sniping) = &bundles[0].data else {
+            panic!("expected a snipe, found: {:#?}", bundles[0].data)
+        };
+        assert_eq!(storage_block, 102);
+        assert_eq!(bundles[0].header.block_number, storage_block);
+        assert_eq!(sniping.block_number, storage_block);
+        assert_eq!(sniping.launch_block_number, 100);
+        assert_eq!(sniping.blocks_after_launch, 1);
+        assert_eq!(sniping.markout_blocks, 2);
+    }
+}
@@ -0,0 +1,273 @@
+//! Out of tree inspectors, compiled to WASM and loaded at runtime.
+//!
+//! A plugin is a `<name>.wasm` module in the plugin directory, selected with
+//! `--inspectors plugin:<name>` alongside the built-in inspectors. It runs
+//! sandboxed: it can't import any host functions, so it has no access to the
+//! filesystem, network or clock, and every call is bounded by the
+//! [`PluginLimits`].
+//!
+//! ## ABI
+//!
+//! The module has to export:
+//!
+//! - `memory`: its linear memory
+//! - `brontes_alloc(len: i32) -> i32`: allocates `len` bytes and returns a
+//!   pointer to them, used to pass the input
+//! - `brontes_inspect(ptr: i32, len: i32) -> i64`: inspects the [`PluginInput`]
+//!   serialized as JSON at `ptr`. Returns the pointer to the output in the
+//!   upper and its length in the lower 32 bits. The output is a JSON array of
+//!   [`PluginBundle`]s
+//!
+//! and optionally:
+//!
+//! - `brontes_block_window() -> i32`: the amount of blocks the plugin inspects
+//!   at once, 1 if not exported
+//!
+//! Every block is inspected in a fresh instance of the module, so no state is
+//! kept across blocks.
+use std::path::{Path, PathBuf};
+
+use alloy_primitives::{Address, TxHash, U256};
+use brontes_types::{
+    db::{builder::BuilderInfo, normalized_actions::TransactionRoot, searcher::Fund},
+    mev::{Bundle, BundleData, BundleHeader, Mev, SearcherTx},
+    FastHashSet, GasDetails, MultiBlockData, ToFloatNearest,
+};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "wasm-plugins")]
+mod wasm;
+#[cfg(feature = "wasm-plugins")]
+pub use wasm::WasmInspector;
+
+/// File extension of the plugins in the plugin directory
+pub const PLUGIN_EXTENSION: &str = "wasm";
+/// Instructions a plugin can execute per call, roughly a few seconds of work
+pub const DEFAULT_PLUGIN_FUEL: u64 = 10_000_000_000;
+/// Linear memory a plugin can grow to
+pub const DEFAULT_PLUGIN_MEMORY_MB: usize = 512;
+
+/// The resources a plugin can use for a single call
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PluginLimits {
+    pub fuel:             u64,
+    pub max_memory_bytes: usize,
+}
+
+impl Default for PluginLimits {
+    fn default() -> Self {
+        Self {
+            fuel:             DEFAULT_PLUGIN_FUEL,
+            max_memory_bytes: DEFAULT_PLUGIN_MEMORY_MB << 20,
+        }
+    }
+}
+
+/// The path of the plugin with the given name in `dir`
+pub fn plugin_path(dir: &Path, name: &str) -> PathBuf {
+    dir.join(format!("{name}.{PLUGIN_EXTENSION}"))
+}
+
+/// The names of all plugins in `dir`, sorted
+pub fn discover_plugins(dir: &Path) -> eyre::Result<Vec<String>> {
+    let entries = std::fs::read_dir(dir)
+        .map_err(|e| eyre::eyre!("failed to read plugin dir {}: {e}", dir.display()))?;
+
+    Ok(entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file())
+        .filter(|path| path.extension().is_some_and(|ext| ext == PLUGIN_EXTENSION))
+        .filter_map(|path| Some(path.file_stem()?.to_str()?.to_string()))
+        .sorted()
+        .collect())
+}
+
+/// What a plugin is given to inspect
+#[derive(Debug, Serialize)]
+pub struct PluginInput<'a> {
+    pub quote_token: Address,
+    /// Oldest block first, as many as the block window of the plugin
+    pub blocks:      Vec<PluginBlock<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PluginBlock<'a> {
+    pub metadata:     PluginMetadata<'a>,
+    /// The classified transactions of the block, in the format of the
+    /// `brontes.tree` table
+    pub transactions: Vec<TransactionRoot>,
+}
+
+/// The parts of the block [`Metadata`](brontes_types::db::metadata::Metadata)
+/// that can be passed to a plugin. Quotes aren't included.
+#[derive(Debug, Serialize)]
+pub struct PluginMetadata<'a> {
+    pub block_number:           u64,
+    pub block_hash:             U256,
+    pub block_timestamp:        u64,
+    pub relay_timestamp:        Option<u64>,
+    pub p2p_timestamp:          Option<u64>,
+    pub proposer_fee_recipient: Option<Address>,
+    pub proposer_mev_reward:    Option<u128>,
+    pub eth_price:              f64,
+    pub private_flow:           &'a FastHashSet<TxHash>,
+    pub builder_info:           Option<&'a BuilderInfo>,
+}
+
+impl<'a> PluginInput<'a> {
+    pub fn new(quote_token: Address, data: &'a MultiBlockData) -> Self {
+        let blocks = data
+            .per_block_data
+            .iter()
+            .map(|block| {
+                let metadata = &block.metadata;
+                let block_number = metadata.block_num;
+
+                PluginBlock {
+                    metadata:     PluginMetadata {
+                        block_number,
+                        block_hash: metadata.block_hash,
+                        block_timestamp: metadata.block_timestamp,
+                        relay_timestamp: metadata.relay_timestamp,
+                        p2p_timestamp: metadata.p2p_timestamp,
+                        proposer_fee_recipient: metadata.proposer_fee_recipient,
+                        proposer_mev_reward: metadata.proposer_mev_reward,
+                        eth_price: metadata.eth_prices.clone().to_float(),
+                        private_flow: &metadata.private_flow,
+                        builder_info: metadata.builder_info.as_ref(),
+                    },
+                    transactions: block
+                        .tree
+                        .tx_roots
+                        .iter()
+                        .map(|root| (root, block_number).into())
+                        .collect(),
+                }
+            })
+            .collect();
+
+        Self { quote_token, blocks }
+    }
+}
+
+/// A bundle found by a plugin. The serde format of [`Bundle`] is tailored to
+/// clickhouse and can't be read back, so plugins return this instead, which
+/// reads and writes the same JSON. It's stored as a searcher transaction
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PluginBundle {
+    pub block_number: u64,
+    pub tx_index:     u64,
+    pub tx_hash:      TxHash,
+    pub eoa:          Address,
+    pub mev_contract: Option<Address>,
+    pub profit_usd:   f64,
+    pub bribe_usd:    f64,
+    pub gas_details:  GasDetails,
+}
+
+impl From<PluginBundle> for Bundle {
+    fn from(bundle: PluginBundle) -> Self {
+        let data = BundleData::Unknown(SearcherTx {
+            tx_hash:      bundle.tx_hash,
+            block_number: bundle.block_number,
+            transfers:    vec![],
+            gas_details:  bundle.gas_details,
+        });
+
+        Bundle {
+            header: BundleHeader {
+                block_number:          bundle.block_number,
+                tx_index:              bundle.tx_index,
+                tx_hash:               bundle.tx_hash,
+                eoa:                   bundle.eoa,
+                mev_contract:          bundle.mev_contract,
+                fund:                  Fund::None,
+                profit_usd:            bundle.profit_usd,
+                bribe_usd:             bundle.bribe_usd,
+                mev_type:              data.mev_type(),
+                no_pricing_calculated: false,
+                balance_deltas:        vec![],
+            },
+            data,
+        }
+    }
+}
+
+/// Reads the output of a plugin
+pub fn decode_plugin_output(output: &[u8]) -> eyre::Result<Vec<Bundle>> {
+    let bundles: Vec<PluginBundle> = serde_json::from_slice(output)?;
+
+    Ok(bundles.into_iter().map(Into::into).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_discover_plugins() {
+        let dir = std::env::temp_dir().join(format!("brontes-plugins-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        for file in ["b_arb.wasm", "a_arb.wasm", "notes.txt"] {
+            std::fs::write(dir.join(file), []).unwrap();
+        }
+
+        let plugins = discover_plugins(&dir).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(plugins, vec!["a_arb".to_string(), "b_arb".to_string()]);
+        assert_eq!(plugin_path(&dir, "a_arb"), dir.join("a_arb.wasm"));
+    }
+
+    #[test]
+    fn test_plugin_output_round_trip() {
+        let bundle = PluginBundle {
+            block_number: 19_000_000,
+            tx_index:     3,
+            tx_hash:      TxHash::with_last_byte(1),
+            eoa:          Address::with_last_byte(2),
+            mev_contract: Some(Address::with_last_byte(3)),
+            profit_usd:   12.5,
+            bribe_usd:    1.25,
+            gas_details:  GasDetails {
+                coinbase_transfer:   Some(10),
+                priority_fee:        1,
+                gas_used:            100_000,
+                effective_gas_price: u128::from(u64::MAX) + 1,
+            },
+        };
+
+        let output = serde_json::to_vec(&[bundle.clone()]).unwrap();
+        let bundles = decode_plugin_output(&output).unwrap();
+
+        assert_eq!(bundles, vec![Bundle::from(bundle)]);
+        assert_eq!(bundles[0].header.mev_type, bundles[0].data.mev_type());
+        assert_eq!(
+            serde_json::from_slice::<Vec<PluginBundle>>(&output)
+                .unwrap()
+                .len(),
+            1
+        );
+        assert!(decode_plugin_output(b"[{}]").is_err());
+    }
+
+    #[test]
+    fn test_inspector_selection() {
+        use std::str::FromStr;
+
+        use crate::{InspectorSelection, Inspectors};
+
+        assert_eq!(
+            InspectorSelection::from_str("Sandwich"),
+            Ok(InspectorSelection::Builtin(Inspectors::Sandwich))
+        );
+        assert_eq!(
+            InspectorSelection::from_str("plugin:my_arb"),
+            Ok(InspectorSelection::Plugin("my_arb".to_string()))
+        );
+        // a misspelled built-in isn't taken for a plugin
+        assert!(InspectorSelection::from_str("Sandwhich").is_err());
+        assert!(InspectorSelection::from_str("plugin:").is_err());
+    }
+}
@@ -0,0 +1,236 @@
+use std::path::Path;
+
+use alloy_primitives::Address;
+use brontes_types::{mev::Bundle, MultiBlockData};
+use tracing::error;
+use wasmtime::{Config, Engine, Instance, Module, Store, StoreLimits, StoreLimitsBuilder};
+
+use super::{decode_plugin_output, PluginInput, PluginLimits};
+use crate::Inspector;
+
+const MEMORY: &str = "memory";
+const ALLOC: &str = "brontes_alloc";
+const INSPECT: &str = "brontes_inspect";
+const BLOCK_WINDOW: &str = "brontes_block_window";
+
+/// Runs a WASM plugin as an [`Inspector`], see the [module docs](super) for
+/// the ABI it has to implement
+pub struct WasmInspector {
+    name:         String,
+    quote_token:  Address,
+    block_window: usize,
+    limits:       PluginLimits,
+    engine:       Engine,
+    module:       Module,
+}
+
+impl WasmInspector {
+    /// Loads the plugin at `path`, named after its file stem
+    pub fn load(path: &Path, quote_token: Address, limits: PluginLimits) -> eyre::Result<Self> {
+        let name = path
+            .file_stem()
+            .and_then(|name| name.to_str())
+            .ok_or_else(|| eyre::eyre!("invalid plugin path {}", path.display()))?;
+        let bytes = std::fs::read(path)
+            .map_err(|e| eyre::eyre!("failed to read plugin {}: {e}", path.display()))?;
+
+        Self::new(name, &bytes, quote_token, limits)
+    }
+
+    pub fn new(
+        name: &str,
+        bytes: &[u8],
+        quote_token: Address,
+        limits: PluginLimits,
+    ) -> eyre::Result<Self> {
+        let mut config = Config::new();
+        config.consume_fuel(true);
+
+        let engine = Engine::new(&config).map_err(wasm_err)?;
+        let module =
+            Module::new(&engine, bytes).map_err(|e| eyre::eyre!("invalid plugin {name}: {e:#}"))?;
+
+        if module.imports().next().is_some() {
+            eyre::bail!("plugin {name} imports host functions, which aren't available to plugins")
+        }
+        if let Some(missing) = [MEMORY, ALLOC, INSPECT]
+            .into_iter()
+            .find(|export| module.get_export(export).is_none())
+        {
+            eyre::bail!("plugin {name} doesn't export {missing}")
+        }
+
+        let mut inspector =
+            Self { name: name.to_string(), quote_token, block_window: 1, limits, engine, module };
+        inspector.block_window = inspector.query_block_window()?;
+
+        Ok(inspector)
+    }
+
+    fn instantiate(&self) -> eyre::Result<(Store<StoreLimits>, Instance)> {
+        let limits = StoreLimitsBuilder::new()
+            .memory_size(self.limits.max_memory_bytes)
+            .instances(1)
+            .build();
+
+        let mut store = Store::new(&self.engine, limits);
+        store.limiter(|limits| limits);
+        store.set_fuel(self.limits.fuel).map_err(wasm_err)?;
+
+        let instance = Instance::new(&mut store, &self.module, &[]).map_err(wasm_err)?;
+
+        Ok((store, instance))
+    }
+
+    fn query_block_window(&self) -> eyre::Result<usize> {
+        if self.module.get_export(BLOCK_WINDOW).is_none() {
+            return Ok(1)
+        }
+
+        let (mut store, instance) = self.instantiate()?;
+        let window = instance
+            .get_typed_func::<(), u32>(&mut store, BLOCK_WINDOW)
+            .and_then(|block_window| block_window.call(&mut store, ()))
+            .map_err(wasm_err)?;
+
+        Ok((window as usize).max(1))
+    }
+
+    /// Passes `input` to `brontes_inspect` in a fresh instance and returns its
+    /// output
+    fn call(&self, input: &[u8]) -> eyre::Result<Vec<u8>> {
+        let (mut store, instance) = self.instantiate()?;
+
+        let memory = instance
+            .get_memory(&mut store, MEMORY)
+            .ok_or_else(|| eyre::eyre!("{MEMORY} isn't a memory"))?;
+        let alloc = instance
+            .get_typed_func::<u32, u32>(&mut store, ALLOC)
+            .map_err(wasm_err)?;
+        let inspect = instance
+            .get_typed_func::<(u32, u32), u64>(&mut store, INSPECT)
+            .map_err(wasm_err)?;
+
+        let len = u32::try_from(input.len())?;
+        let ptr = alloc.call(&mut store, len).map_err(wasm_err)?;
+        memory.write(&mut store, ptr as usize, input)?;
+
+        let output = inspect.call(&mut store, (ptr, len)).map_err(wasm_err)?;
+        let (output_ptr, output_len) = ((output >> 32) as usize, output as u32 as usize);
+
+        let mut buf = vec![0; output_len];
+        memory.read(&store, output_ptr, &mut buf)?;
+
+        Ok(buf)
+    }
+
+    fn try_inspect(&self, data: &MultiBlockData) -> eyre::Result<Vec<Bundle>> {
+        let input = serde_json::to_vec(&PluginInput::new(self.quote_token, data))?;
+        let output = self.call(&input)?;
+
+        decode_plugin_output(&output)
+    }
+}
+
+impl Inspector for WasmInspector {
+    type Result = Vec<Bundle>;
+
+    fn get_id(&self) -> &str {
+        &self.name
+    }
+
+    fn block_window(&self) -> usize {
+        self.block_window
+    }
+
+    fn get_quote_token(&self) -> Address {
+        self.quote_token
+    }
+
+    fn inspect_block(&self, data: MultiBlockData) -> Self::Result {
+        self.try_inspect(&data).unwrap_or_else(|e| {
+            error!(
+                plugin = %self.name,
+                block = data.get_most_recent_block().block_number(),
+                "plugin failed: {e}"
+            );
+            vec![]
+        })
+    }
+}
+
+fn wasm_err(err: wasmtime::Error) -> eyre::Report {
+    eyre::eyre!("{err:#}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns `[]` for any input
+    const EMPTY_PLUGIN: &str = r#"
+        (module
+            (memory (export "memory") 1)
+            (data (i32.const 0) "[]")
+            (func (export "brontes_alloc") (param i32) (result i32)
+                i32.const 1024)
+            (func (export "brontes_inspect") (param i32 i32) (result i64)
+                i64.const 2)
+            (func (export "brontes_block_window") (result i32)
+                i32.const 3))
+    "#;
+
+    /// Never returns
+    const LOOPING_PLUGIN: &str = r#"
+        (module
+            (memory (export "memory") 1)
+            (func (export "brontes_alloc") (param i32) (result i32)
+                i32.const 1024)
+            (func (export "brontes_inspect") (param i32 i32) (result i64)
+                (loop (br 0))
+                i64.const 0))
+    "#;
+
+    /// Tries to read the host filesystem
+    const IMPORTING_PLUGIN: &str = r#"
+        (module
+            (import "wasi_snapshot_preview1" "fd_read"
+                (func (param i32 i32 i32 i32) (result i32)))
+            (memory (export "memory") 1))
+    "#;
+
+    fn plugin(wat: &str, limits: PluginLimits) -> eyre::Result<WasmInspector> {
+        WasmInspector::new("test", wat.as_bytes(), Address::ZERO, limits)
+    }
+
+    #[test]
+    fn test_plugin_output() {
+        let inspector = plugin(EMPTY_PLUGIN, PluginLimits::default()).unwrap();
+
+        assert_eq!(inspector.get_id(), "test");
+        assert_eq!(inspector.block_window(), 3);
+        assert_eq!(inspector.call(br#"{"blocks":[]}"#).unwrap(), b"[]");
+    }
+
+    #[test]
+    fn test_plugin_runs_out_of_fuel() {
+        let inspector =
+            plugin(LOOPING_PLUGIN, PluginLimits { fuel: 100_000, ..Default::default() }).unwrap();
+
+        assert!(inspector.call(b"{}").is_err());
+    }
+
+    #[test]
+    fn test_plugin_memory_limit() {
+        // the plugin needs a 64KiB page to instantiate
+        let inspector =
+            plugin(EMPTY_PLUGIN, PluginLimits { max_memory_bytes: 1024, ..Default::default() });
+
+        assert!(inspector.is_err());
+    }
+
+    #[test]
+    fn test_plugin_cant_import_host_functions() {
+        assert!(plugin(IMPORTING_PLUGIN, PluginLimits::default()).is_err());
+    }
+}
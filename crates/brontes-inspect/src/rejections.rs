@@ -0,0 +1,137 @@
+//! Records why the inspectors rejected a possible bundle, so `brontes explain`
+//! can say which check a transaction failed. Nothing is recorded outside of
+//! [`record_rejections`], the inspectors only pay for a thread local lookup.
+use std::{
+    cell::RefCell,
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+use alloy_primitives::B256;
+
+type Recorder = Arc<Mutex<Vec<Rejection>>>;
+
+thread_local! {
+    /// Set on the threads of the pool a recording runs on, so rejections made
+    /// elsewhere at the same time, or by another recording, aren't collected
+    static RECORDER: RefCell<Option<Recorder>> = const { RefCell::new(None) };
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RejectionCheck {
+    /// The transactions weren't sent by a searcher
+    Searcher,
+    /// The actions don't have the shape of the mev type
+    Actions,
+    /// There are no victims, or their swaps don't match the searcher's
+    Victims,
+    /// The transactions aren't in the order the mev type needs
+    Ordering,
+    /// There are no prices to value the transactions with
+    Pricing,
+    /// The bundle isn't profitable enough
+    Profit,
+}
+
+impl fmt::Display for RejectionCheck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let check = match self {
+            Self::Searcher => "searcher",
+            Self::Actions => "action",
+            Self::Victims => "victim",
+            Self::Ordering => "ordering",
+            Self::Pricing => "pricing",
+            Self::Profit => "profit",
+        };
+        f.write_str(check)
+    }
+}
+
+/// A possible bundle an inspector rejected
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rejection {
+    pub check:     RejectionCheck,
+    /// The transactions of the possible bundle
+    pub tx_hashes: Vec<B256>,
+    pub reason:    String,
+}
+
+impl Rejection {
+    pub fn contains(&self, tx_hash: &B256) -> bool {
+        self.tx_hashes.contains(tx_hash)
+    }
+}
+
+impl fmt::Display for Rejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "failed {} check: {}", self.check, self.reason)
+    }
+}
+
+/// Records that a possible bundle of `tx_hashes` failed `check`. The reason is
+/// only built while recording
+pub fn reject(check: RejectionCheck, tx_hashes: &[B256], reason: impl FnOnce() -> String) {
+    RECORDER.with(|recorder| {
+        let Some(recorder) = recorder.borrow().clone() else { return };
+
+        recorder
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(Rejection { check, tx_hashes: tx_hashes.to_vec(), reason: reason() });
+    });
+}
+
+/// Runs `f` and returns the rejections recorded while it ran. The inspectors
+/// run on rayon, so `f` runs on a pool of its own whose threads all record
+pub fn record_rejections<R: Send>(f: impl FnOnce() -> R + Send) -> (R, Vec<Rejection>) {
+    let rejections = Recorder::default();
+    let recorder = rejections.clone();
+    let pool = rayon::ThreadPoolBuilder::new()
+        .thread_name(|i| format!("rejections-{i}"))
+        .start_handler(move |_| {
+            RECORDER.with(|r| *r.borrow_mut() = Some(recorder.clone()));
+        })
+        .build()
+        .expect("failed to build the rejection recording pool");
+
+    let res = pool.install(f);
+    drop(pool);
+
+    let rejections = std::mem::take(&mut *rejections.lock().unwrap_or_else(|e| e.into_inner()));
+
+    (res, rejections)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_records_only_while_recording() {
+        let tx = B256::with_last_byte(1);
+
+        reject(RejectionCheck::Profit, &[tx], || "not recorded".to_string());
+        let ((), rejections) = record_rejections(|| {
+            reject(RejectionCheck::Victims, &[tx], || "no victims".to_string())
+        });
+        reject(RejectionCheck::Profit, &[tx], || "not recorded".to_string());
+
+        assert_eq!(rejections.len(), 1);
+        assert!(rejections[0].contains(&tx));
+        assert_eq!(rejections[0].to_string(), "failed victim check: no victims");
+        assert!(record_rejections(|| ()).1.is_empty());
+    }
+
+    #[test]
+    fn test_records_rejections_made_in_parallel() {
+        use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+        let ((), rejections) = record_rejections(|| {
+            (0..16u8).into_par_iter().for_each(|i| {
+                reject(RejectionCheck::Profit, &[B256::with_last_byte(i)], || "unprofitable".into())
+            })
+        });
+
+        assert_eq!(rejections.len(), 16);
+    }
+}
@@ -12,7 +12,7 @@ use brontes_types::{
 use criterion::{black_box, Criterion};
 
 use super::InspectorTestUtilsError;
-use crate::{composer::run_block_inspection, Inspectors};
+use crate::{composer::run_block_inspection, sniping::DEFAULT_SNIPING_MARKOUT_BLOCKS, Inspectors};
 
 pub struct InspectorBenchUtils {
     classifier_inspector: ClassifierTestUtils,
@@ -48,6 +48,7 @@ impl InspectorBenchUtils {
                     self.classifier_inspector.libmdbx,
                     &[CexExchange::Binance],
                     CexDexTradeConfig::default(),
+                    DEFAULT_SNIPING_MARKOUT_BLOCKS,
                     None,
                 )
             })
@@ -99,6 +100,7 @@ impl InspectorBenchUtils {
             self.classifier_inspector.libmdbx,
             &[CexExchange::Binance],
             CexDexTradeConfig::default(),
+            DEFAULT_SNIPING_MARKOUT_BLOCKS,
             None,
         );
 
@@ -162,6 +164,7 @@ impl InspectorBenchUtils {
             self.classifier_inspector.libmdbx,
             &[CexExchange::Binance],
             CexDexTradeConfig::default(),
+            DEFAULT_SNIPING_MARKOUT_BLOCKS,
             None,
         );
 
@@ -216,6 +219,7 @@ impl InspectorBenchUtils {
             self.classifier_inspector.libmdbx,
             &[CexExchange::Binance],
             CexDexTradeConfig::default(),
+            DEFAULT_SNIPING_MARKOUT_BLOCKS,
             None,
         );
 
@@ -263,6 +267,7 @@ impl InspectorBenchUtils {
                     self.classifier_inspector.libmdbx,
                     &[CexExchange::Binance],
                     CexDexTradeConfig::default(),
+                    DEFAULT_SNIPING_MARKOUT_BLOCKS,
                     None,
                 )
             })
@@ -322,6 +327,7 @@ impl InspectorBenchUtils {
                     self.classifier_inspector.libmdbx,
                     &[CexExchange::Binance],
                     CexDexTradeConfig::default(),
+                    DEFAULT_SNIPING_MARKOUT_BLOCKS,
                     None,
                 )
             })
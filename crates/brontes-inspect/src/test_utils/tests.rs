@@ -38,7 +38,7 @@ use brontes_types::{
 };
 use thiserror::Error;
 
-use crate::{composer::run_block_inspection, Inspectors};
+use crate::{composer::run_block_inspection, sniping::DEFAULT_SNIPING_MARKOUT_BLOCKS, Inspectors};
 
 type StateTests = Option<Box<dyn for<'a> Fn(&'a Bundle)>>;
 
@@ -168,10 +168,10 @@ impl InspectorTestUtils {
                 CexExchange::Kucoin,
             ],
             CexDexTradeConfig::default(),
+            DEFAULT_SNIPING_MARKOUT_BLOCKS,
             None,
         );
-        let data = BlockData { metadata: metadata.into(), tree: tree.into() };
-        let multi = MultiBlockData { per_block_data: vec![data], blocks: 1 };
+        let multi = split_block_data(tree, metadata, config.split_block_after)?;
         let results = inspector.inspect_block(multi);
 
         assert_eq!(results.len(), 0, "found mev when we shouldn't of {:#?}", results);
@@ -270,11 +270,11 @@ impl InspectorTestUtils {
                 CexExchange::Upbit,
             ],
             cex_trade_config,
+            DEFAULT_SNIPING_MARKOUT_BLOCKS,
             None,
         );
 
-        let data = BlockData { metadata: metadata.into(), tree: tree.into() };
-        let multi = MultiBlockData { per_block_data: vec![data], blocks: 1 };
+        let multi = split_block_data(tree, metadata, config.split_block_after)?;
         let mut results = inspector.inspect_block(multi);
 
         assert_eq!(
@@ -390,6 +390,7 @@ impl InspectorTestUtils {
                     self.classifier_inspector.libmdbx,
                     &[CexExchange::Binance],
                     CexDexTradeConfig::default(),
+                    DEFAULT_SNIPING_MARKOUT_BLOCKS,
                     None,
                 )
             })
@@ -452,6 +453,43 @@ impl InspectorTestUtils {
     }
 }
 
+/// Splits the tree after the given transaction into two consecutive blocks
+/// that share the metadata of the original block, so that inspectors looking
+/// across the block boundary can be tested on a single block.
+fn split_block_data(
+    tree: BlockTree<Action>,
+    metadata: Metadata,
+    split_after: Option<TxHash>,
+) -> Result<MultiBlockData, InspectorTestUtilsError> {
+    let Some(split_after) = split_after else {
+        let data = BlockData { metadata: metadata.into(), tree: tree.into() };
+        return Ok(MultiBlockData { per_block_data: vec![data], blocks: 1 })
+    };
+
+    let split = tree
+        .tx_roots
+        .iter()
+        .position(|root| root.tx_hash == split_after)
+        .ok_or(InspectorTestUtilsError::MissingTx(split_after))?
+        + 1;
+
+    let mut prev = tree;
+    let mut cur = prev.clone();
+    cur.tx_roots = prev.tx_roots.split_off(split);
+    prev.header.number -= 1;
+
+    let mut prev_metadata = metadata.clone();
+    prev_metadata.block_metadata.block_num -= 1;
+
+    Ok(MultiBlockData {
+        per_block_data: vec![
+            BlockData { metadata: prev_metadata.into(), tree: prev.into() },
+            BlockData { metadata: metadata.into(), tree: cur.into() },
+        ],
+        blocks:         2,
+    })
+}
+
 /// This inspector test config is to configure an inspector test for a single
 /// bundle. MevTxHashes is a list of tx hashes that are expected be in the
 /// bundle.
@@ -466,6 +504,7 @@ pub struct InspectorTxRunConfig {
     pub needs_dex_prices: bool,
     pub needs_tokens: Vec<Address>,
     pub use_block_time_weights_for_cex_pricing: bool,
+    pub split_block_after: Option<TxHash>,
 }
 
 impl InspectorTxRunConfig {
@@ -480,6 +519,7 @@ impl InspectorTxRunConfig {
             needs_tokens: Vec::new(),
             needs_dex_prices: false,
             use_block_time_weights_for_cex_pricing: false,
+            split_block_after: None,
         }
     }
 
@@ -498,6 +538,13 @@ impl InspectorTxRunConfig {
         self
     }
 
+    /// Runs the inspector on two consecutive blocks, the first one ending with
+    /// the given transaction
+    pub fn with_block_split_after(mut self, tx: TxHash) -> Self {
+        self.split_block_after = Some(tx);
+        self
+    }
+
     pub fn with_block(mut self, block: u64) -> Self {
         self.block = Some(block);
         self
@@ -621,4 +668,6 @@ pub enum InspectorTestUtilsError {
     MissingInspector(MevType),
     #[error("more than one block found in inspector config. blocks: {0:?}")]
     MultipleBlockError(Vec<u64>),
+    #[error("transaction {0:?} isn't in the block")]
+    MissingTx(TxHash),
 }
@@ -70,12 +70,20 @@ impl ActionMacro {
         call.value_mut().ident = Ident::new(&solidity, call.span());
         return_import.segments.push(call.into_value());
 
-        let dex_price_return = if action_type.to_string().to_lowercase().as_str()
-            == "poolconfigupdate"
-        {
-            quote!(Ok(::brontes_pricing::types::DexPriceMsg::DiscoveredPool(result)))
-        } else {
-            quote!(
+        let dex_price_return = match action_type.to_string().to_lowercase().as_str() {
+            "poolconfigupdate" => {
+                quote!(Ok(::brontes_pricing::types::DexPriceMsg::DiscoveredPool(result)))
+            }
+            // the closure decides on the action variant itself
+            "action" => quote!(Ok(::brontes_pricing::types::DexPriceMsg::Update(
+                ::brontes_pricing::types::PoolUpdate {
+                    block,
+                    tx_idx,
+                    logs: call_info.logs.clone().to_vec(),
+                    action: result
+                },
+            ))),
+            _ => quote!(
                 Ok(::brontes_pricing::types::DexPriceMsg::Update(
                     ::brontes_pricing::types::PoolUpdate {
                         block,
@@ -84,7 +92,7 @@ impl ActionMacro {
                         action: ::brontes_types::normalized_actions::Action::#action_type(result)
                     },
                 ))
-            )
+            ),
         };
 
         Ok(quote! {
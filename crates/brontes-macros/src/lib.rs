@@ -30,6 +30,10 @@ use crate::action_classifier::{ActionDispatch, ActionMacro};
 /// The Array of log types are expected to be in the order that they are emitted
 /// in. Otherwise the decoding will fail
 ///
+/// If a single call can result in different actions, use `Action` as the
+/// CallType. The closure then returns a
+/// `brontes_types::normalized_actions::Action` instead of the normalized type.
+///
 ///  ## Examples
 /// ```ignore
 /// action_impl!(
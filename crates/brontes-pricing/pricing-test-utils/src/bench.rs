@@ -107,6 +107,7 @@ impl BrontesPricingBencher {
             .block_on(protocol.try_load_state(
                 pool,
                 self.inner.get_tracing_provider(),
+                Arc::new(self.inner.libmdbx),
                 block_number,
                 pool_pair,
                 brontes_pricing::types::PairWithFirstPoolHop::from_pair_gt(pool_pair, pool_pair),
@@ -136,6 +137,7 @@ impl BrontesPricingBencher {
                         .try_load_state(
                             pool,
                             self.inner.get_tracing_provider(),
+                            Arc::new(self.inner.libmdbx),
                             block_number,
                             pool_pair,
                             brontes_pricing::types::PairWithFirstPoolHop::from_pair_gt(
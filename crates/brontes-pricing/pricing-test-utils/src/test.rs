@@ -62,6 +62,7 @@ impl PricingTestUtils {
             pair_graph,
             UnboundedYapperReceiver::new(rx, 100_000, "test".into()),
             self.tracer.get_provider(),
+            Arc::new(self.tracer.libmdbx),
             block,
             created_pools,
             Arc::new(AtomicBool::new(false)),
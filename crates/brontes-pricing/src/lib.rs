@@ -67,6 +67,7 @@ pub use protocols::{Protocol, *};
 use subgraph_query::*;
 use tracing::{debug, error, info};
 use types::{DexPriceMsg, PairWithFirstPoolHop, PoolUpdate};
+use uniswap_v4::PoolKeyStore;
 
 use crate::types::PoolState;
 /// max movement of price in the block before its considered invalid.
@@ -122,6 +123,7 @@ impl<T: TracingProvider> BrontesBatchPricer<T> {
         graph_manager: GraphManager,
         update_rx: UnboundedYapperReceiver<DexPriceMsg>,
         provider: Arc<T>,
+        pool_keys: Arc<dyn PoolKeyStore>,
         current_block: u64,
         new_graph_pairs: FastHashMap<Address, (Protocol, Pair)>,
         needs_more_data: Arc<AtomicBool>,
@@ -138,7 +140,7 @@ impl<T: TracingProvider> BrontesBatchPricer<T> {
             update_rx,
             graph_manager,
             dex_quotes: FastHashMap::default(),
-            lazy_loader: LazyExchangeLoader::new(provider, executor),
+            lazy_loader: LazyExchangeLoader::new(provider, pool_keys, executor),
             current_block,
             completed_block: current_block,
             overlap_update: None,
@@ -0,0 +1,303 @@
+use std::sync::Arc;
+
+use alloy_primitives::{hex, Address, Log, B256};
+use alloy_sol_macro::sol;
+use async_trait::async_trait;
+use brontes_types::{
+    normalized_actions::Action, traits::TracingProvider, ToFloatNearest, ToScaledRational,
+};
+use itertools::Itertools;
+use malachite::{num::basic::traits::Zero, Rational};
+
+use super::make_call_request;
+use crate::{
+    errors::{AmmError, ArithmeticError, EventLogError},
+    stable_math, sync_balances_from_action,
+    uniswap_v2::IErc20,
+    UpdatableProtocol,
+};
+
+sol!(
+    interface IBalancerV2Pool {
+        function getPoolId() external view returns (bytes32);
+        function getNormalizedWeights() external view returns (uint256[]);
+        function getAmplificationParameter() external view returns (
+            uint256 value,
+            bool isUpdating,
+            uint256 precision
+        );
+        function getScalingFactors() external view returns (uint256[]);
+    }
+);
+
+sol!(
+    interface IBalancerV2Vault {
+        function getPoolTokens(bytes32 poolId) external view returns (
+            address[] tokens,
+            uint256[] balances,
+            uint256 lastChangeBlock
+        );
+    }
+);
+
+/// The vault that holds the balances of all v2 pools
+pub const BALANCER_V2_VAULT: Address =
+    Address::new(hex!("BA12222222228d8Ba445958a75a0704d566BF2C8"));
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BalancerV2PoolKind {
+    /// The normalized weight of each token
+    Weighted(Vec<Rational>),
+    /// The amplification parameter and the rate of each token. The rate
+    /// includes the rate providers of the pool which is what allows the lst
+    /// pools to be priced.
+    Stable { amp: f64, rates: Vec<f64> },
+}
+
+/// A weighted or stable pool registered in the vault. As the vault doesn't
+/// emit balances, we keep them in sync by applying the token flows of the
+/// classified actions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BalancerV2Pool {
+    pub address:  Address,
+    pub pool_id:  B256,
+    pub token_a:  Address,
+    pub token_b:  Address,
+    pub tokens:   Vec<Address>,
+    pub balances: Vec<Rational>,
+    pub kind:     BalancerV2PoolKind,
+}
+
+#[async_trait]
+impl UpdatableProtocol for BalancerV2Pool {
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    fn sync_from_action(&mut self, action: Action) -> Result<(), AmmError> {
+        sync_balances_from_action(&self.tokens, &mut self.balances, action);
+        Ok(())
+    }
+
+    fn sync_from_log(&mut self, _log: Log) -> Result<(), AmmError> {
+        Err(AmmError::EventLogError(EventLogError::InvalidEventSignature))
+    }
+
+    fn tokens(&self) -> Vec<Address> {
+        vec![self.token_a, self.token_b]
+    }
+
+    fn calculate_price(&self, base_token: Address) -> Result<Rational, ArithmeticError> {
+        let quote_token = if base_token == self.token_a { self.token_b } else { self.token_a };
+        let (Some(base), Some(quote)) = (self.index_of(base_token), self.index_of(quote_token))
+        else {
+            return Err(ArithmeticError::NoLiquidity)
+        };
+
+        match &self.kind {
+            BalancerV2PoolKind::Weighted(weights) => {
+                let base_balance = &self.balances[base] / &weights[base];
+                if base_balance == Rational::ZERO {
+                    return Err(ArithmeticError::NoLiquidity)
+                }
+
+                Ok(&self.balances[quote] / &weights[quote] / base_balance)
+            }
+            BalancerV2PoolKind::Stable { amp, rates } => {
+                let balances = self
+                    .balances
+                    .iter()
+                    .zip(rates)
+                    .map(|(balance, rate)| balance.clone().to_float() * rate)
+                    .collect_vec();
+                let ann = amp * balances.len() as f64;
+
+                // the invariant is on the balances scaled by the rates
+                let price = stable_math::spot_price(&balances, ann, base, quote)
+                    .ok_or(ArithmeticError::NoLiquidity)?
+                    * rates[base]
+                    / rates[quote];
+
+                Rational::try_from(price).map_err(|_| ArithmeticError::RoundingError)
+            }
+        }
+    }
+}
+
+impl BalancerV2Pool {
+    /// Loads the pool for the `token_a`, `token_b` edge of the pair graph.
+    pub async fn new_from_address<M: 'static + TracingProvider>(
+        address: Address,
+        token_a: Address,
+        token_b: Address,
+        block_number: u64,
+        middleware: Arc<M>,
+    ) -> Result<Self, AmmError> {
+        let block = Some(block_number);
+        let pool_id =
+            make_call_request(IBalancerV2Pool::getPoolIdCall::new(()), &middleware, address, block)
+                .await?
+                ._0;
+
+        let pool_tokens = make_call_request(
+            IBalancerV2Vault::getPoolTokensCall { poolId: pool_id },
+            &middleware,
+            BALANCER_V2_VAULT,
+            block,
+        )
+        .await?;
+
+        // composable pools hold their own bpt, which isn't part of the invariant
+        let bpt_index = pool_tokens
+            .tokens
+            .iter()
+            .position(|token| *token == address);
+        let is_pool_token = |idx: &usize| Some(*idx) != bpt_index;
+
+        let mut tokens = Vec::new();
+        let mut decimals = Vec::new();
+        let mut balances = Vec::new();
+        for (idx, (token, balance)) in pool_tokens
+            .tokens
+            .into_iter()
+            .zip(pool_tokens.balances)
+            .enumerate()
+        {
+            if !is_pool_token(&idx) {
+                continue
+            }
+
+            let token_decimals =
+                make_call_request(IErc20::decimalsCall::new(()), &middleware, token, block)
+                    .await?
+                    ._0;
+
+            tokens.push(token);
+            decimals.push(token_decimals);
+            balances.push(balance.to_scaled_rational(token_decimals));
+        }
+
+        if !tokens.contains(&token_a) || !tokens.contains(&token_b) {
+            return Err(AmmError::PoolDataError)
+        }
+
+        let kind = if let Ok(weights) = make_call_request(
+            IBalancerV2Pool::getNormalizedWeightsCall::new(()),
+            &middleware,
+            address,
+            block,
+        )
+        .await
+        {
+            BalancerV2PoolKind::Weighted(
+                weights
+                    ._0
+                    .into_iter()
+                    .map(|weight| weight.to_scaled_rational(18))
+                    .collect(),
+            )
+        } else {
+            let amp = make_call_request(
+                IBalancerV2Pool::getAmplificationParameterCall::new(()),
+                &middleware,
+                address,
+                block,
+            )
+            .await?;
+            let amp = amp.value.saturating_to::<u64>() as f64
+                / amp.precision.saturating_to::<u64>() as f64;
+
+            // the scaling factors contain both the decimals and the rate of the
+            // token, fall back to a rate of one for pools without them
+            let rates = match make_call_request(
+                IBalancerV2Pool::getScalingFactorsCall::new(()),
+                &middleware,
+                address,
+                block,
+            )
+            .await
+            {
+                Ok(factors) => factors
+                    ._0
+                    .into_iter()
+                    .enumerate()
+                    .filter(|(idx, _)| is_pool_token(idx))
+                    .map(|(_, factor)| factor)
+                    .zip(&decimals)
+                    .map(|(factor, decimals)| factor.to_scaled_rational(36 - decimals).to_float())
+                    .collect(),
+                Err(_) => vec![1.0; tokens.len()],
+            };
+
+            BalancerV2PoolKind::Stable { amp, rates }
+        };
+
+        Ok(Self { address, pool_id, token_a, token_b, tokens, balances, kind })
+    }
+
+    pub fn index_of(&self, token: Address) -> Option<usize> {
+        self.tokens.iter().position(|t| *t == token)
+    }
+
+    pub fn get_tvl(&self, base: Address) -> (Rational, Rational) {
+        let balance_of = |token| {
+            self.index_of(token)
+                .map(|idx| self.balances[idx].clone())
+                .unwrap_or(Rational::ZERO)
+        };
+
+        if self.token_a == base {
+            (balance_of(self.token_a), balance_of(self.token_b))
+        } else {
+            (balance_of(self.token_b), balance_of(self.token_a))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::address;
+
+    use super::*;
+
+    const BAL: Address = address!("ba100000625a3754423978a60c9317c58a424e3D");
+    const WETH: Address = address!("C02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2");
+    const WSTETH: Address = address!("7f39C581F595B53c5cb19bD0b3f8dA6c935E2Ca0");
+
+    #[test]
+    fn test_weighted_price() {
+        // 80/20 BAL/WETH
+        let pool = BalancerV2Pool {
+            address:  address!("5c6Ee304399DBdB9C8Ef030aB642B10820DB8F56"),
+            pool_id:  B256::ZERO,
+            token_a:  BAL,
+            token_b:  WETH,
+            tokens:   vec![BAL, WETH],
+            balances: vec![Rational::from(8_000_000), Rational::from(1_000)],
+            kind:     BalancerV2PoolKind::Weighted(vec![
+                Rational::from_signeds(4, 5),
+                Rational::from_signeds(1, 5),
+            ]),
+        };
+
+        assert_eq!(pool.calculate_price(BAL).unwrap(), Rational::from_signeds(1, 2_000));
+        assert_eq!(pool.calculate_price(WETH).unwrap(), Rational::from(2_000));
+    }
+
+    #[test]
+    fn test_stable_price_uses_rates() {
+        // wstETH is worth 1.15 eth, so a pool balanced by value holds less of it
+        let pool = BalancerV2Pool {
+            address:  address!("93d199263632a4EF4Bb438F1feB99e57b4b5f0BD"),
+            pool_id:  B256::ZERO,
+            token_a:  WSTETH,
+            token_b:  WETH,
+            tokens:   vec![WSTETH, WETH],
+            balances: vec![Rational::from(10_000), Rational::from(11_500)],
+            kind:     BalancerV2PoolKind::Stable { amp: 50.0, rates: vec![1.15, 1.0] },
+        };
+
+        let price = pool.calculate_price(WSTETH).unwrap().to_float();
+        assert!((price - 1.15).abs() < 1e-9);
+    }
+}
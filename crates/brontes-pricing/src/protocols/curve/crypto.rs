@@ -0,0 +1,205 @@
+use std::sync::Arc;
+
+use alloy_primitives::{Address, Log, U256};
+use async_trait::async_trait;
+use brontes_types::{normalized_actions::Action, traits::TracingProvider, ToScaledRational};
+use malachite::{
+    num::basic::traits::{One, Zero},
+    Rational,
+};
+
+use super::{CurveCoins, ICurvePool};
+use crate::{
+    errors::{AmmError, ArithmeticError, EventLogError},
+    make_call_request, sync_balances_from_action, Protocol, UpdatableProtocol,
+};
+
+/// A cryptoswap pool. The price of these pools is concentrated around an
+/// internal price scale, so instead of the balances we price off the last
+/// traded price of every coin, which is denominated in the first coin.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CurveCryptoPool {
+    pub address:     Address,
+    pub protocol:    Protocol,
+    pub token_a:     Address,
+    pub token_b:     Address,
+    pub coins:       CurveCoins,
+    pub last_prices: Vec<Rational>,
+}
+
+#[async_trait]
+impl UpdatableProtocol for CurveCryptoPool {
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    fn sync_from_action(&mut self, action: Action) -> Result<(), AmmError> {
+        let swap = match &action {
+            Action::Swap(s) => Some(s),
+            Action::SwapWithFee(s) => Some(&s.swap),
+            _ => None,
+        };
+
+        if let Some(swap) =
+            swap.filter(|s| s.amount_in != Rational::ZERO && s.amount_out != Rational::ZERO)
+        {
+            if let (Some(token_in), Some(token_out)) = (
+                self.coins.index_of(swap.token_in.address),
+                self.coins.index_of(swap.token_out.address),
+            ) {
+                // the first coin is the numeraire, so its price never changes
+                if token_out != 0 {
+                    self.last_prices[token_out] =
+                        &self.last_prices[token_in] * &swap.amount_in / &swap.amount_out;
+                } else {
+                    self.last_prices[token_in] =
+                        &self.last_prices[token_out] * &swap.amount_out / &swap.amount_in;
+                }
+            }
+        }
+
+        sync_balances_from_action(&self.coins.coins, &mut self.coins.balances, action);
+        Ok(())
+    }
+
+    fn sync_from_log(&mut self, _log: Log) -> Result<(), AmmError> {
+        Err(AmmError::EventLogError(EventLogError::InvalidEventSignature))
+    }
+
+    fn tokens(&self) -> Vec<Address> {
+        vec![self.token_a, self.token_b]
+    }
+
+    fn calculate_price(&self, base_token: Address) -> Result<Rational, ArithmeticError> {
+        let quote_token = if base_token == self.token_a { self.token_b } else { self.token_a };
+        let (Some(base), Some(quote)) =
+            (self.coins.index_of(base_token), self.coins.index_of(quote_token))
+        else {
+            return Err(ArithmeticError::NoLiquidity)
+        };
+
+        if self.last_prices[quote] == Rational::ZERO {
+            return Err(ArithmeticError::NoLiquidity)
+        }
+
+        Ok(&self.last_prices[base] / &self.last_prices[quote])
+    }
+}
+
+impl CurveCryptoPool {
+    /// Loads the pool for the `token_a`, `token_b` edge of the pair graph.
+    pub async fn new_from_address<M: 'static + TracingProvider>(
+        address: Address,
+        protocol: Protocol,
+        token_a: Address,
+        token_b: Address,
+        block_number: u64,
+        middleware: Arc<M>,
+    ) -> Result<Self, AmmError> {
+        let coins = CurveCoins::load(address, block_number, &middleware).await?;
+        if coins.index_of(token_a).is_none() || coins.index_of(token_b).is_none() {
+            return Err(AmmError::PoolDataError)
+        }
+
+        // two coin pools only have a single price while the tricrypto pools take
+        // the index of the coin minus one
+        let mut last_prices = vec![Rational::ONE];
+        if coins.coins.len() == 2 {
+            let price = make_call_request(
+                ICurvePool::last_prices_0Call::new(()),
+                &middleware,
+                address,
+                Some(block_number),
+            )
+            .await?
+            ._0;
+            last_prices.push(price.to_scaled_rational(18));
+        } else {
+            for k in 0..coins.coins.len() - 1 {
+                let price = make_call_request(
+                    ICurvePool::last_prices_1Call { k: U256::from(k) },
+                    &middleware,
+                    address,
+                    Some(block_number),
+                )
+                .await?
+                ._0;
+                last_prices.push(price.to_scaled_rational(18));
+            }
+        }
+
+        Ok(Self { address, protocol, token_a, token_b, coins, last_prices })
+    }
+
+    pub fn get_tvl(&self, base: Address) -> (Rational, Rational) {
+        let balance_of = |token| {
+            self.coins
+                .index_of(token)
+                .map(|idx| self.coins.balances[idx].clone())
+                .unwrap_or(Rational::ZERO)
+        };
+
+        if self.token_a == base {
+            (balance_of(self.token_a), balance_of(self.token_b))
+        } else {
+            (balance_of(self.token_b), balance_of(self.token_a))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::address;
+    use brontes_types::{
+        db::token_info::{TokenInfo, TokenInfoWithAddress},
+        normalized_actions::NormalizedSwap,
+    };
+
+    use super::*;
+
+    const USDT: Address = address!("dAC17F958D2ee523a2206206994597C13D831ec7");
+    const WBTC: Address = address!("2260FAC5E5542a773Aa44fBCfeDf7C193bc2C599");
+    const WETH: Address = address!("C02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2");
+
+    fn token(address: Address, decimals: u8) -> TokenInfoWithAddress {
+        TokenInfoWithAddress { address, inner: TokenInfo { decimals, symbol: String::new() } }
+    }
+
+    #[test]
+    fn test_swap_updates_last_price() {
+        let mut pool = CurveCryptoPool {
+            address:     address!("D51a44d3FaE010294C616388b506AcdA1bfAAE46"),
+            protocol:    Protocol::CurveTriCryptoPool,
+            token_a:     WBTC,
+            token_b:     WETH,
+            coins:       CurveCoins {
+                coins:    vec![USDT, WBTC, WETH],
+                decimals: vec![6, 8, 18],
+                balances: vec![
+                    Rational::from(30_000_000),
+                    Rational::from(500),
+                    Rational::from(10_000),
+                ],
+            },
+            last_prices: vec![Rational::ONE, Rational::from(60_000), Rational::from(3_000)],
+        };
+
+        assert_eq!(pool.calculate_price(WBTC).unwrap(), Rational::from(20));
+        assert_eq!(pool.calculate_price(WETH).unwrap(), Rational::from_signeds(1, 20));
+
+        // 1 wbtc for 21 weth
+        pool.sync_from_action(Action::Swap(NormalizedSwap {
+            pool: pool.address,
+            token_in: token(WBTC, 8),
+            token_out: token(WETH, 18),
+            amount_in: Rational::ONE,
+            amount_out: Rational::from(21),
+            ..Default::default()
+        }))
+        .unwrap();
+
+        assert_eq!(pool.last_prices[2], Rational::from_signeds(60_000, 21));
+        assert_eq!(pool.calculate_price(WBTC).unwrap(), Rational::from(21));
+        assert_eq!(pool.get_tvl(WBTC), (Rational::from(501), Rational::from(9_979)));
+    }
+}
@@ -0,0 +1,115 @@
+mod crypto;
+mod stable;
+
+use std::sync::Arc;
+
+use alloy_primitives::{Address, U256};
+use alloy_sol_macro::sol;
+use brontes_types::{constants::ETH_ADDRESS, traits::TracingProvider, ToScaledRational};
+pub use crypto::CurveCryptoPool;
+use malachite::Rational;
+pub use stable::CurveStablePool;
+
+use super::make_call_request;
+use crate::{errors::AmmError, uniswap_v2::IErc20};
+
+sol!(
+    interface ICurvePool {
+        function coins(uint256 i) external view returns (address);
+        function coins(int128 i) external view returns (address);
+        function balances(uint256 i) external view returns (uint256);
+        function balances(int128 i) external view returns (uint256);
+        function A() external view returns (uint256);
+        function last_prices() external view returns (uint256);
+        function last_prices(uint256 k) external view returns (uint256);
+    }
+);
+
+/// Upper bound on the amount of coins a pool can have
+const MAX_COINS: usize = 8;
+
+/// The coins of a curve pool together with their balances in whole tokens.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CurveCoins {
+    pub coins:    Vec<Address>,
+    pub decimals: Vec<u8>,
+    pub balances: Vec<Rational>,
+}
+
+impl CurveCoins {
+    pub fn index_of(&self, token: Address) -> Option<usize> {
+        self.coins.iter().position(|coin| *coin == token)
+    }
+
+    /// Older pools take a `int128` as the coin index while the newer ones use
+    /// `uint256`, so we try both.
+    async fn load<M: TracingProvider>(
+        address: Address,
+        block: u64,
+        middleware: &Arc<M>,
+    ) -> Result<Self, AmmError> {
+        let mut this = Self::default();
+
+        for i in 0..MAX_COINS {
+            let coin = if let Ok(coin) = make_call_request(
+                ICurvePool::coins_0Call { i: U256::from(i) },
+                middleware,
+                address,
+                Some(block),
+            )
+            .await
+            {
+                coin._0
+            } else if let Ok(coin) = make_call_request(
+                ICurvePool::coins_1Call { i: i as i128 },
+                middleware,
+                address,
+                Some(block),
+            )
+            .await
+            {
+                coin._0
+            } else {
+                break
+            };
+
+            let balance = if let Ok(balance) = make_call_request(
+                ICurvePool::balances_0Call { i: U256::from(i) },
+                middleware,
+                address,
+                Some(block),
+            )
+            .await
+            {
+                balance._0
+            } else {
+                make_call_request(
+                    ICurvePool::balances_1Call { i: i as i128 },
+                    middleware,
+                    address,
+                    Some(block),
+                )
+                .await?
+                ._0
+            };
+
+            let decimals = if coin == ETH_ADDRESS {
+                18
+            } else {
+                make_call_request(IErc20::decimalsCall::new(()), middleware, coin, Some(block))
+                    .await?
+                    ._0
+            };
+
+            this.coins.push(coin);
+            this.decimals.push(decimals);
+            this.balances.push(balance.to_scaled_rational(decimals));
+        }
+
+        if this.coins.len() < 2 {
+            return Err(AmmError::NoStateError(address))
+        }
+
+        Ok(this)
+    }
+}
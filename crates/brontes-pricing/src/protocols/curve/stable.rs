@@ -0,0 +1,166 @@
+use std::sync::Arc;
+
+use alloy_primitives::{Address, Log};
+use async_trait::async_trait;
+use brontes_types::{normalized_actions::Action, traits::TracingProvider, ToFloatNearest};
+use itertools::Itertools;
+use malachite::{num::basic::traits::Zero, Rational};
+
+use super::{CurveCoins, ICurvePool};
+use crate::{
+    errors::{AmmError, ArithmeticError, EventLogError},
+    make_call_request, stable_math, sync_balances_from_action, Protocol, UpdatableProtocol,
+};
+
+/// A stableswap pool. Curve pools don't emit their balances, so we keep them in
+/// sync by applying the token flows of the classified actions.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CurveStablePool {
+    pub address:  Address,
+    pub protocol: Protocol,
+    pub token_a:  Address,
+    pub token_b:  Address,
+    pub coins:    CurveCoins,
+    pub a:        u64,
+}
+
+#[async_trait]
+impl UpdatableProtocol for CurveStablePool {
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    fn sync_from_action(&mut self, action: Action) -> Result<(), AmmError> {
+        sync_balances_from_action(&self.coins.coins, &mut self.coins.balances, action);
+        Ok(())
+    }
+
+    fn sync_from_log(&mut self, _log: Log) -> Result<(), AmmError> {
+        Err(AmmError::EventLogError(EventLogError::InvalidEventSignature))
+    }
+
+    fn tokens(&self) -> Vec<Address> {
+        vec![self.token_a, self.token_b]
+    }
+
+    fn calculate_price(&self, base_token: Address) -> Result<Rational, ArithmeticError> {
+        let quote_token = if base_token == self.token_a { self.token_b } else { self.token_a };
+        let (Some(base), Some(quote)) =
+            (self.coins.index_of(base_token), self.coins.index_of(quote_token))
+        else {
+            return Err(ArithmeticError::NoLiquidity)
+        };
+
+        let balances = self
+            .coins
+            .balances
+            .iter()
+            .map(|balance| balance.clone().to_float())
+            .collect_vec();
+        let ann = self.a as f64 * balances.len() as f64;
+
+        let price = stable_math::spot_price(&balances, ann, base, quote)
+            .ok_or(ArithmeticError::NoLiquidity)?;
+
+        Rational::try_from(price).map_err(|_| ArithmeticError::RoundingError)
+    }
+}
+
+impl CurveStablePool {
+    /// Loads the pool for the `token_a`, `token_b` edge of the pair graph.
+    pub async fn new_from_address<M: 'static + TracingProvider>(
+        address: Address,
+        protocol: Protocol,
+        token_a: Address,
+        token_b: Address,
+        block_number: u64,
+        middleware: Arc<M>,
+    ) -> Result<Self, AmmError> {
+        let coins = CurveCoins::load(address, block_number, &middleware).await?;
+        if coins.index_of(token_a).is_none() || coins.index_of(token_b).is_none() {
+            return Err(AmmError::PoolDataError)
+        }
+
+        let a =
+            make_call_request(ICurvePool::ACall::new(()), &middleware, address, Some(block_number))
+                .await?
+                ._0
+                .saturating_to::<u64>();
+
+        Ok(Self { address, protocol, token_a, token_b, coins, a })
+    }
+
+    pub fn get_tvl(&self, base: Address) -> (Rational, Rational) {
+        let balance_of = |token| {
+            self.coins
+                .index_of(token)
+                .map(|idx| self.coins.balances[idx].clone())
+                .unwrap_or(Rational::ZERO)
+        };
+
+        if self.token_a == base {
+            (balance_of(self.token_a), balance_of(self.token_b))
+        } else {
+            (balance_of(self.token_b), balance_of(self.token_a))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::address;
+    use brontes_types::{
+        db::token_info::{TokenInfo, TokenInfoWithAddress},
+        normalized_actions::NormalizedSwap,
+    };
+
+    use super::*;
+
+    const DAI: Address = address!("6B175474E89094C44Da98b954EedeAC495271d0F");
+    const USDC: Address = address!("A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48");
+    const USDT: Address = address!("dAC17F958D2ee523a2206206994597C13D831ec7");
+
+    fn three_pool() -> CurveStablePool {
+        CurveStablePool {
+            address:  address!("bEbc44782C7dB0a1A60Cb6fe97d0b483032FF1C7"),
+            protocol: Protocol::CurveBasePool3,
+            token_a:  DAI,
+            token_b:  USDC,
+            coins:    CurveCoins {
+                coins:    vec![DAI, USDC, USDT],
+                decimals: vec![18, 6, 6],
+                balances: vec![Rational::from(50_000_000); 3],
+            },
+            a:        2000,
+        }
+    }
+
+    #[test]
+    fn test_price_follows_swaps() {
+        let mut pool = three_pool();
+        assert_eq!(pool.calculate_price(DAI).unwrap(), Rational::from(1));
+
+        pool.sync_from_action(Action::Swap(NormalizedSwap {
+            pool: pool.address,
+            token_in: TokenInfoWithAddress {
+                address: DAI,
+                inner:   TokenInfo { decimals: 18, symbol: "DAI".into() },
+            },
+            token_out: TokenInfoWithAddress {
+                address: USDC,
+                inner:   TokenInfo { decimals: 6, symbol: "USDC".into() },
+            },
+            amount_in: Rational::from(20_000_000),
+            amount_out: Rational::from(19_990_000),
+            ..Default::default()
+        }))
+        .unwrap();
+
+        assert_eq!(pool.get_tvl(USDC), (Rational::from(30_010_000), Rational::from(70_000_000)));
+
+        let dai_price = pool.calculate_price(DAI).unwrap();
+        let usdc_price = pool.calculate_price(USDC).unwrap();
+        assert!(dai_price < Rational::from(1));
+        assert!(usdc_price > Rational::from(1));
+    }
+}
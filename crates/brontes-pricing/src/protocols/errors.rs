@@ -73,6 +73,8 @@ pub enum ArithmeticError {
     UniswapV3MathError(#[from] UniswapV3MathError),
     #[error("v2 div by zero")]
     UniV2DivZero,
+    #[error("Pool has no liquidity for the pair")]
+    NoLiquidity,
 }
 
 #[derive(Error, Debug)]
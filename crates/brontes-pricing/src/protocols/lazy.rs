@@ -14,6 +14,7 @@ use crate::{
     errors::AmmError,
     protocols::LoadState,
     types::{PairWithFirstPoolHop, PoolState},
+    uniswap_v4::PoolKeyStore,
     Protocol,
 };
 
@@ -63,6 +64,9 @@ type BlockNumber = u64;
 /// state for a given block.
 pub struct LazyExchangeLoader<T: TracingProvider> {
     provider:          Arc<T>,
+    /// the keys of the uniswap v4 pools, which can't be loaded from their
+    /// address
+    pool_keys:         Arc<dyn PoolKeyStore>,
     pool_load_futures: MultiBlockPoolFutures,
     /// addresses currently being processed. to the blocks of the address we are
     /// fetching state for
@@ -74,8 +78,13 @@ pub struct LazyExchangeLoader<T: TracingProvider> {
 }
 
 impl<T: TracingProvider> LazyExchangeLoader<T> {
-    pub fn new(provider: Arc<T>, ex: BrontesTaskExecutor) -> Self {
+    pub fn new(
+        provider: Arc<T>,
+        pool_keys: Arc<dyn PoolKeyStore>,
+        ex: BrontesTaskExecutor,
+    ) -> Self {
         Self {
+            pool_keys,
             state_tracking: LoadingStateTracker::default(),
             pool_buf: FastHashMap::default(),
             pool_load_futures: MultiBlockPoolFutures::new(),
@@ -165,7 +174,14 @@ impl<T: TracingProvider> LazyExchangeLoader<T> {
         let provider = self.provider.clone();
         self.add_state_trackers(block_number, id, address, pair);
 
-        let fut = ex_type.try_load_state(address, provider, block_number, pool_pair, pair);
+        let fut = ex_type.try_load_state(
+            address,
+            provider,
+            self.pool_keys.clone(),
+            block_number,
+            pool_pair,
+            pair,
+        );
         self.pool_load_futures.add_future(
             block_number,
             Box::pin(self.ex.handle().spawn(async move {
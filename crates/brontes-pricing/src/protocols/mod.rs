@@ -1,7 +1,11 @@
+pub mod balancer_v2;
+pub mod curve;
 pub mod errors;
 pub mod lazy;
+pub mod stable_math;
 pub mod uniswap_v2;
 pub mod uniswap_v3;
+pub mod uniswap_v4;
 
 use std::{future::Future, sync::Arc};
 
@@ -9,15 +13,18 @@ use alloy_primitives::{Address, Log};
 use async_trait::async_trait;
 use brontes_types::{normalized_actions::Action, pair::Pair, traits::TracingProvider};
 pub use brontes_types::{queries::make_call_request, Protocol};
-use malachite::Rational;
+use malachite::{num::basic::traits::Zero, Rational};
 use tracing::{debug, warn};
 
 use crate::{
+    balancer_v2::BalancerV2Pool,
+    curve::{CurveCryptoPool, CurveStablePool},
     lazy::{PoolFetchError, PoolFetchSuccess},
     protocols::errors::{AmmError, ArithmeticError},
     types::PairWithFirstPoolHop,
     uniswap_v2::UniswapV2Pool,
     uniswap_v3::UniswapV3Pool,
+    uniswap_v4::{PoolKeyStore, UniswapV4Pool},
     LoadResult, PoolState,
 };
 
@@ -30,12 +37,56 @@ pub trait UpdatableProtocol {
     fn sync_from_log(&mut self, log: Log) -> Result<(), AmmError>;
 }
 
+/// Applies the token flows of an action to the balances of a pool holding
+/// `tokens`. This is used for the pools that don't emit their new reserves,
+/// tokens that the pool doesn't hold are ignored.
+pub(crate) fn sync_balances_from_action(
+    tokens: &[Address],
+    balances: &mut [Rational],
+    action: Action,
+) {
+    let mut apply = |token: Address, amount: Rational, is_deposit: bool| {
+        let Some(idx) = tokens.iter().position(|t| *t == token) else { return };
+        if is_deposit {
+            balances[idx] += amount;
+        } else {
+            balances[idx] -= amount;
+            if balances[idx] < Rational::ZERO {
+                balances[idx] = Rational::ZERO;
+            }
+        }
+    };
+
+    match action {
+        Action::Swap(s) => {
+            apply(s.token_in.address, s.amount_in, true);
+            apply(s.token_out.address, s.amount_out, false);
+        }
+        Action::SwapWithFee(s) => {
+            apply(s.swap.token_in.address, s.swap.amount_in, true);
+            apply(s.swap.token_out.address, s.swap.amount_out, false);
+        }
+        Action::Mint(m) => m
+            .token
+            .into_iter()
+            .zip(m.amount)
+            .for_each(|(token, amount)| apply(token.address, amount, true)),
+        Action::Burn(b) => b
+            .token
+            .into_iter()
+            .zip(b.amount)
+            .for_each(|(token, amount)| apply(token.address, amount, false)),
+        _ => {}
+    }
+}
+
 pub trait LoadState {
     fn has_state_updater(&self) -> bool;
     fn try_load_state<T: TracingProvider>(
         self,
         address: Address,
         provider: Arc<T>,
+        pool_keys: Arc<dyn PoolKeyStore>,
         block_number: u64,
         pool_pair: Pair,
         full_pair: PairWithFirstPoolHop,
@@ -52,6 +103,15 @@ impl LoadState for Protocol {
                 | Self::SushiSwapV3
                 | Self::PancakeSwapV2
                 | Self::PancakeSwapV3
+                | Self::UniswapV4
+                | Self::CurveBasePool2
+                | Self::CurveBasePool3
+                | Self::CurveBasePool4
+                | Self::CurveV2PlainPool
+                | Self::CurvecrvUSDPlainPool
+                | Self::CurveCryptoSwapPool
+                | Self::CurveTriCryptoPool
+                | Self::BalancerV2
         )
     }
 
@@ -59,6 +119,7 @@ impl LoadState for Protocol {
         self,
         address: Address,
         provider: Arc<T>,
+        pool_keys: Arc<dyn PoolKeyStore>,
         block_number: u64,
         pool_pair: Pair,
         fp: PairWithFirstPoolHop,
@@ -120,6 +181,163 @@ impl LoadState for Protocol {
                     res,
                 ))
             }
+            Self::UniswapV4 => {
+                let (pool, res) = if let Ok(pool) = UniswapV4Pool::new_from_address(
+                    address,
+                    block_number - 1,
+                    provider.clone(),
+                    &*pool_keys,
+                )
+                .await
+                {
+                    (pool, LoadResult::Ok)
+                } else {
+                    (
+                        UniswapV4Pool::new_from_address(address, block_number, provider, &*pool_keys)
+                            .await
+                            .map_err(|e| {
+                                debug!(?pool_pair, protocol=%self, %block_number, pool_address=?address, err=%e, "lazy load failed");
+                                (address, Protocol::UniswapV4, block_number, pool_pair, fp, e)
+                            })?,
+                        LoadResult::PoolInitOnBlock,
+                    )
+                };
+
+                Ok((
+                    block_number,
+                    address,
+                    PoolState::new(
+                        crate::types::PoolVariants::UniswapV4(Box::new(pool)),
+                        block_number,
+                    ),
+                    res,
+                ))
+            }
+            Self::CurveBasePool2
+            | Self::CurveBasePool3
+            | Self::CurveBasePool4
+            | Self::CurveV2PlainPool
+            | Self::CurvecrvUSDPlainPool => {
+                let (pool, res) = if let Ok(pool) = CurveStablePool::new_from_address(
+                    address,
+                    self,
+                    pool_pair.0,
+                    pool_pair.1,
+                    block_number - 1,
+                    provider.clone(),
+                )
+                .await
+                {
+                    (pool, LoadResult::Ok)
+                } else {
+                    (
+                        CurveStablePool::new_from_address(
+                            address,
+                            self,
+                            pool_pair.0,
+                            pool_pair.1,
+                            block_number,
+                            provider,
+                        )
+                        .await
+                        .map_err(|e| {
+                            debug!(?pool_pair, protocol=%self, %block_number, pool_address=?address, err=%e, "lazy load failed");
+                            (address, self, block_number, pool_pair, fp, e)
+                        })?,
+                        LoadResult::PoolInitOnBlock,
+                    )
+                };
+
+                Ok((
+                    block_number,
+                    address,
+                    PoolState::new(
+                        crate::types::PoolVariants::CurveStable(Box::new(pool)),
+                        block_number,
+                    ),
+                    res,
+                ))
+            }
+            Self::CurveCryptoSwapPool | Self::CurveTriCryptoPool => {
+                let (pool, res) = if let Ok(pool) = CurveCryptoPool::new_from_address(
+                    address,
+                    self,
+                    pool_pair.0,
+                    pool_pair.1,
+                    block_number - 1,
+                    provider.clone(),
+                )
+                .await
+                {
+                    (pool, LoadResult::Ok)
+                } else {
+                    (
+                        CurveCryptoPool::new_from_address(
+                            address,
+                            self,
+                            pool_pair.0,
+                            pool_pair.1,
+                            block_number,
+                            provider,
+                        )
+                        .await
+                        .map_err(|e| {
+                            debug!(?pool_pair, protocol=%self, %block_number, pool_address=?address, err=%e, "lazy load failed");
+                            (address, self, block_number, pool_pair, fp, e)
+                        })?,
+                        LoadResult::PoolInitOnBlock,
+                    )
+                };
+
+                Ok((
+                    block_number,
+                    address,
+                    PoolState::new(
+                        crate::types::PoolVariants::CurveCrypto(Box::new(pool)),
+                        block_number,
+                    ),
+                    res,
+                ))
+            }
+            Self::BalancerV2 => {
+                let (pool, res) = if let Ok(pool) = BalancerV2Pool::new_from_address(
+                    address,
+                    pool_pair.0,
+                    pool_pair.1,
+                    block_number - 1,
+                    provider.clone(),
+                )
+                .await
+                {
+                    (pool, LoadResult::Ok)
+                } else {
+                    (
+                        BalancerV2Pool::new_from_address(
+                            address,
+                            pool_pair.0,
+                            pool_pair.1,
+                            block_number,
+                            provider,
+                        )
+                        .await
+                        .map_err(|e| {
+                            debug!(?pool_pair, protocol=%self, %block_number, pool_address=?address, err=%e, "lazy load failed");
+                            (address, Protocol::BalancerV2, block_number, pool_pair, fp, e)
+                        })?,
+                        LoadResult::PoolInitOnBlock,
+                    )
+                };
+
+                Ok((
+                    block_number,
+                    address,
+                    PoolState::new(
+                        crate::types::PoolVariants::BalancerV2(Box::new(pool)),
+                        block_number,
+                    ),
+                    res,
+                ))
+            }
             rest => {
                 warn!(protocol=?rest, "no state updater is build for");
                 Err((address, self, block_number, pool_pair, fp, AmmError::UnsupportedProtocol))
@@ -0,0 +1,92 @@
+//! The stableswap invariant shared by the Curve stable pools and the Balancer
+//! stable pools.
+//!
+//! `ann` is the amplification coefficient multiplied by the amount of coins.
+//! Both protocols expose their amplification parameter as `A * n^(n - 1)`,
+//! which makes `A * n^n` of the whitepaper equal to `amp * n`.
+//!
+//! As we only need spot prices we do the math with floats on balances that are
+//! scaled to whole tokens, the invariant is homogeneous so the scale doesn't
+//! change the price.
+
+const MAX_ITERATIONS: usize = 255;
+const PRECISION: f64 = 1e-12;
+
+/// Computes the invariant `D` through newton iteration the same way the pools
+/// do on chain.
+pub fn invariant(balances: &[f64], ann: f64) -> Option<f64> {
+    let n = balances.len() as f64;
+    let sum: f64 = balances.iter().sum();
+
+    if sum == 0.0 || balances.iter().any(|b| *b <= 0.0) {
+        return None
+    }
+
+    let mut d = sum;
+    for _ in 0..MAX_ITERATIONS {
+        let d_p = d_product(balances, d);
+        let prev = d;
+        d = (ann * sum + d_p * n) * d / ((ann - 1.0) * d + (n + 1.0) * d_p);
+
+        if (d - prev).abs() <= PRECISION * d {
+            return Some(d)
+        }
+    }
+
+    None
+}
+
+/// The spot price of `base` denominated in `quote`, meaning the amount of
+/// `quote` received for an infinitesimal amount of `base`.
+///
+/// This is the ratio of the partial derivatives of the invariant
+/// `(ann + D_P / x_base) / (ann + D_P / x_quote)` with
+/// `D_P = D^(n + 1) / (n^n * prod(x))`.
+pub fn spot_price(balances: &[f64], ann: f64, base: usize, quote: usize) -> Option<f64> {
+    let d = invariant(balances, ann)?;
+    let d_p = d_product(balances, d);
+
+    let price = (ann + d_p / balances[base]) / (ann + d_p / balances[quote]);
+    price.is_finite().then_some(price)
+}
+
+fn d_product(balances: &[f64], d: f64) -> f64 {
+    let n = balances.len() as f64;
+    balances.iter().fold(d, |d_p, x| d_p * d / (x * n))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_balanced_pool_is_at_peg() {
+        let balances = [1_000_000.0, 1_000_000.0, 1_000_000.0];
+        let d = invariant(&balances, 2000.0 * 3.0).unwrap();
+        assert!((d - 3_000_000.0).abs() < 1e-3);
+
+        let price = spot_price(&balances, 2000.0 * 3.0, 0, 2).unwrap();
+        assert!((price - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_imbalanced_pool_price() {
+        let balances = [2_000_000.0, 1_000_000.0];
+        let ann = 100.0 * 2.0;
+
+        let price = spot_price(&balances, ann, 0, 1).unwrap();
+        let inverse = spot_price(&balances, ann, 1, 0).unwrap();
+
+        // the coin the pool has more of is worth less
+        assert!(price < 1.0);
+        // but a lot less so than on a constant product curve
+        assert!(price > 0.99);
+        assert!((price * inverse - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_empty_pool() {
+        assert!(invariant(&[0.0, 10.0], 200.0).is_none());
+        assert!(spot_price(&[], 200.0, 0, 1).is_none());
+    }
+}
@@ -0,0 +1,405 @@
+use std::{cmp::Ordering, sync::Arc};
+
+use alloy_primitives::{hex, keccak256, Address, Log, B256, U256};
+use alloy_sol_macro::sol;
+use alloy_sol_types::{SolEvent, SolValue};
+use async_trait::async_trait;
+use brontes_types::{
+    constants::WETH_ADDRESS,
+    db::{traits::LibmdbxReader, uniswap_v4::UniswapV4PoolKey},
+    normalized_actions::Action,
+    traits::TracingProvider,
+    ToScaledRational,
+};
+use malachite::Rational;
+use serde::{Deserialize, Serialize};
+
+use super::make_call_request;
+use crate::{
+    errors::{AmmError, ArithmeticError, EventLogError},
+    uniswap_v2::IErc20,
+    uniswap_v3::{uniswap_v3_math, MAX_SQRT_RATIO, MIN_SQRT_RATIO},
+    UpdatableProtocol,
+};
+
+sol!(
+    #[derive(Debug, PartialEq, Eq)]
+    struct PoolKey {
+        address currency0;
+        address currency1;
+        uint24 fee;
+        int24 tickSpacing;
+        address hooks;
+    }
+);
+
+sol!(
+    interface IUniswapV4PoolManager {
+        event Swap(
+            bytes32 indexed id,
+            address indexed sender,
+            int128 amount0,
+            int128 amount1,
+            uint160 sqrtPriceX96,
+            uint128 liquidity,
+            int24 tick,
+            uint24 fee
+        );
+        event ModifyLiquidity(
+            bytes32 indexed id,
+            address indexed sender,
+            int24 tickLower,
+            int24 tickUpper,
+            int256 liquidityDelta,
+            bytes32 salt
+        );
+    }
+);
+
+/// The singleton that holds the state of all v4 pools
+pub const POOL_MANAGER: Address = Address::new(hex!("000000000004444c5dc75cB358380D2e3dE08A90"));
+
+/// Slot of the `pools` mapping in the PoolManager
+const POOLS_SLOT: U256 = U256::from_limbs([6, 0, 0, 0]);
+/// Offset of `liquidity` in the `Pool.State` struct
+const LIQUIDITY_OFFSET: U256 = U256::from_limbs([3, 0, 0, 0]);
+
+/// Pools only exist as entries in the PoolManager, which means that we don't
+/// have a pool address and the pool id can't be recovered from the address we
+/// derive from it. The classifier writes the key of every pool it comes across
+/// to the database, the store gives us access to them so that we are able to
+/// load the state of the pool when it is needed for pricing.
+pub trait PoolKeyStore: Send + Sync {
+    fn pool_key(&self, pool: Address) -> Option<UniswapV4PoolKey>;
+}
+
+impl<DB: LibmdbxReader> PoolKeyStore for DB {
+    fn pool_key(&self, pool: Address) -> Option<UniswapV4PoolKey> {
+        self.try_fetch_uniswap_v4_pool_key(pool)
+            .inspect_err(
+                |e| tracing::error!(?pool, error = %e, "failed to read uniswap v4 pool key"),
+            )
+            .ok()
+            .flatten()
+    }
+}
+
+impl PoolKey {
+    pub fn pool_id(&self) -> B256 {
+        keccak256(self.abi_encode())
+    }
+
+    pub fn pool_address(&self) -> Address {
+        pool_id_to_address(self.pool_id())
+    }
+
+    /// The tokens of the pool, with native eth represented as weth
+    pub fn tokens(&self) -> [Address; 2] {
+        [currency_to_token(self.currency0), currency_to_token(self.currency1)]
+    }
+}
+
+/// The address we use to identify the pool, the first 20 bytes of its id.
+pub fn pool_id_to_address(pool_id: B256) -> Address {
+    Address::from_slice(&pool_id[0..20])
+}
+
+/// v4 uses the zero address for native eth
+pub fn currency_to_token(currency: Address) -> Address {
+    if currency.is_zero() {
+        WETH_ADDRESS
+    } else {
+        currency
+    }
+}
+
+impl From<PoolKey> for UniswapV4PoolKey {
+    fn from(key: PoolKey) -> Self {
+        Self {
+            currency0:    key.currency0,
+            currency1:    key.currency1,
+            fee:          key.fee,
+            tick_spacing: key.tickSpacing,
+            hooks:        key.hooks,
+        }
+    }
+}
+
+impl From<UniswapV4PoolKey> for PoolKey {
+    fn from(key: UniswapV4PoolKey) -> Self {
+        Self {
+            currency0:   key.currency0,
+            currency1:   key.currency1,
+            fee:         key.fee,
+            tickSpacing: key.tick_spacing,
+            hooks:       key.hooks,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct UniswapV4Pool {
+    pub address:          Address,
+    pub pool_id:          B256,
+    pub token_a:          Address,
+    pub token_a_decimals: u8,
+    pub token_b:          Address,
+    pub token_b_decimals: u8,
+    pub liquidity:        u128,
+    pub sqrt_price:       U256,
+    pub fee:              u32,
+    pub tick:             i32,
+    pub tick_spacing:     i32,
+    pub hooks:            Address,
+}
+
+#[async_trait]
+impl UpdatableProtocol for UniswapV4Pool {
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    fn sync_from_action(&mut self, _action: Action) -> Result<(), AmmError> {
+        // actions don't carry the price and liquidity after a swap, only the logs do
+        Err(AmmError::SyncError(self.address))
+    }
+
+    fn sync_from_log(&mut self, log: Log) -> Result<(), AmmError> {
+        let event_signature = log.topics()[0];
+
+        // all pools share the same emitter, so we need to filter out the
+        // events of other pools
+        if log.topics().get(1) != Some(&self.pool_id) {
+            return Ok(())
+        }
+
+        if event_signature == IUniswapV4PoolManager::Swap::SIGNATURE_HASH {
+            self.sync_from_swap_log(log)?;
+        } else if event_signature == IUniswapV4PoolManager::ModifyLiquidity::SIGNATURE_HASH {
+            self.sync_from_modify_liquidity_log(log)?;
+        } else {
+            Err(EventLogError::InvalidEventSignature)?
+        }
+
+        Ok(())
+    }
+
+    fn tokens(&self) -> Vec<Address> {
+        vec![self.token_a, self.token_b]
+    }
+
+    fn calculate_price(&self, base_token: Address) -> Result<Rational, ArithmeticError> {
+        if self.liquidity <= 10_000 {
+            return Err(ArithmeticError::UniswapV3MathError(
+                uniswap_v3_math::error::UniswapV3MathError::LiquidityTooLow(self.liquidity),
+            ))
+        }
+
+        let tick = uniswap_v3_math::tick_math::get_tick_at_sqrt_ratio(self.sqrt_price)?;
+        let shift = self.token_a_decimals as i8 - self.token_b_decimals as i8;
+        let price = match shift.cmp(&0) {
+            Ordering::Less => 1.0001_f64.powi(tick) / 10_f64.powi(-shift as i32),
+            Ordering::Greater => 1.0001_f64.powi(tick) * 10_f64.powi(shift as i32),
+            Ordering::Equal => 1.0001_f64.powi(tick),
+        };
+
+        if base_token == self.token_a {
+            Ok(Rational::try_from(price).unwrap())
+        } else {
+            Ok(Rational::try_from(1.0 / price).unwrap())
+        }
+    }
+}
+
+impl UniswapV4Pool {
+    /// Loads the pool from the PoolManager. The key of the pool needs to be in
+    /// the store, which is the case once the classifier has seen the pool.
+    pub async fn new_from_address<M: 'static + TracingProvider>(
+        address: Address,
+        block_number: u64,
+        middleware: Arc<M>,
+        pool_keys: &dyn PoolKeyStore,
+    ) -> Result<Self, AmmError> {
+        let key: PoolKey = pool_keys
+            .pool_key(address)
+            .ok_or(AmmError::NoStateError(address))?
+            .into();
+        let [token_a, token_b] = key.tokens();
+
+        let mut pool = UniswapV4Pool {
+            address,
+            pool_id: key.pool_id(),
+            token_a,
+            token_b,
+            fee: key.fee,
+            tick_spacing: key.tickSpacing,
+            hooks: key.hooks,
+            ..Default::default()
+        };
+
+        pool.populate_data(block_number, middleware).await?;
+
+        if !pool.data_is_populated() {
+            return Err(AmmError::NoStateError(address))
+        }
+
+        Ok(pool)
+    }
+
+    async fn populate_data<M: TracingProvider>(
+        &mut self,
+        block: u64,
+        middleware: Arc<M>,
+    ) -> Result<(), AmmError> {
+        let state_slot = keccak256((self.pool_id, POOLS_SLOT).abi_encode());
+        let liquidity_slot = B256::from(U256::from_be_bytes(state_slot.0) + LIQUIDITY_OFFSET);
+
+        // slot0 is packed as | lpFee | protocolFee | tick | sqrtPriceX96 |
+        if let Some(slot0) = middleware
+            .get_storage(Some(block), POOL_MANAGER, state_slot)
+            .await?
+        {
+            self.sqrt_price = slot0 & ((U256::from(1) << 160) - U256::from(1));
+            let tick = ((slot0 >> 160) & U256::from(0xFFFFFF)).to::<u32>();
+            // sign extend the int24
+            self.tick = ((tick << 8) as i32) >> 8;
+        }
+
+        if let Some(liquidity) = middleware
+            .get_storage(Some(block), POOL_MANAGER, liquidity_slot)
+            .await?
+        {
+            self.liquidity = (liquidity & U256::from(u128::MAX)).to::<u128>();
+        }
+
+        self.token_a_decimals = make_call_request(
+            IErc20::decimalsCall::new(()),
+            &middleware,
+            self.token_a,
+            Some(block),
+        )
+        .await?
+        ._0;
+        self.token_b_decimals = make_call_request(
+            IErc20::decimalsCall::new(()),
+            &middleware,
+            self.token_b,
+            Some(block),
+        )
+        .await?
+        ._0;
+
+        Ok(())
+    }
+
+    pub fn fee(&self) -> u32 {
+        self.fee
+    }
+
+    pub fn data_is_populated(&self) -> bool {
+        !(self.token_a.is_zero() || self.token_b.is_zero())
+            && self.sqrt_price >= MIN_SQRT_RATIO
+            && self.sqrt_price < MAX_SQRT_RATIO
+    }
+
+    pub fn sync_from_swap_log(&mut self, log: Log) -> Result<(), AmmError> {
+        let swap_event = IUniswapV4PoolManager::Swap::decode_log_data(&log, false)?;
+
+        self.sqrt_price = U256::from(swap_event.sqrtPriceX96);
+        self.liquidity = swap_event.liquidity;
+        self.tick = swap_event.tick;
+        // dynamic fee pools can change the fee on every swap
+        self.fee = swap_event.fee;
+
+        Ok(())
+    }
+
+    pub fn sync_from_modify_liquidity_log(&mut self, log: Log) -> Result<(), AmmError> {
+        let event = IUniswapV4PoolManager::ModifyLiquidity::decode_log_data(&log, false)?;
+
+        // only positions around the current tick are part of the active liquidity
+        if self.tick >= event.tickLower && self.tick < event.tickUpper {
+            let delta = i128::try_from(event.liquidityDelta)
+                .map_err(|_| AmmError::SyncError(self.address))?;
+            self.liquidity = if delta < 0 {
+                self.liquidity.saturating_sub(delta.unsigned_abs())
+            } else {
+                self.liquidity.saturating_add(delta as u128)
+            };
+        }
+
+        Ok(())
+    }
+
+    /// v4 pools don't hold their own balances, instead we use the virtual
+    /// reserves of the active liquidity.
+    pub fn get_tvl(&self, base: Address) -> (Rational, Rational) {
+        let liquidity = U256::from(self.liquidity);
+        let (reserve_0, reserve_1) = if self.sqrt_price.is_zero() {
+            (U256::ZERO, U256::ZERO)
+        } else {
+            (
+                (liquidity << 96) / self.sqrt_price,
+                liquidity.saturating_mul(self.sqrt_price >> 32) >> 64,
+            )
+        };
+
+        if self.token_a == base {
+            (
+                reserve_0.to_scaled_rational(self.token_a_decimals),
+                reserve_1.to_scaled_rational(self.token_b_decimals),
+            )
+        } else {
+            (
+                reserve_1.to_scaled_rational(self.token_b_decimals),
+                reserve_0.to_scaled_rational(self.token_a_decimals),
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{address, b256};
+
+    use super::*;
+
+    #[test]
+    fn test_pool_id() {
+        // ETH/USDC 0.05%
+        let key = PoolKey {
+            currency0:   Address::ZERO,
+            currency1:   address!("A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48"),
+            fee:         500,
+            tickSpacing: 10,
+            hooks:       Address::ZERO,
+        };
+
+        assert_eq!(
+            key.pool_id(),
+            b256!("21c67e77068de97969ba93d4aab21826d33ca12bb9f565d8496e8fda8a82ca27")
+        );
+        assert_eq!(key.tokens(), [WETH_ADDRESS, key.currency1]);
+    }
+
+    #[test]
+    fn test_sync_from_action_errors() {
+        let mut pool = UniswapV4Pool::default();
+        assert!(pool.sync_from_action(Action::Revert).is_err());
+    }
+
+    #[test]
+    fn test_stored_pool_key() {
+        let key = PoolKey {
+            currency0:   address!("6B175474E89094C44Da98b954EedeAC495271d0F"),
+            currency1:   address!("A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48"),
+            fee:         100,
+            tickSpacing: 1,
+            hooks:       Address::ZERO,
+        };
+
+        let stored = UniswapV4PoolKey::from(key.clone());
+        assert_eq!(key.pool_address(), pool_id_to_address(key.pool_id()));
+        assert_eq!(PoolKey::from(stored), key);
+    }
+}
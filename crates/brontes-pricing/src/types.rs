@@ -9,8 +9,13 @@ use brontes_types::{
 use malachite::Rational;
 
 use crate::{
-    errors::ArithmeticError, uniswap_v2::UniswapV2Pool, uniswap_v3::UniswapV3Pool, LoadState,
-    Protocol, UpdatableProtocol,
+    balancer_v2::BalancerV2Pool,
+    curve::{CurveCryptoPool, CurveStablePool},
+    errors::ArithmeticError,
+    uniswap_v2::UniswapV2Pool,
+    uniswap_v3::UniswapV3Pool,
+    uniswap_v4::UniswapV4Pool,
+    LoadState, Protocol, UpdatableProtocol,
 };
 
 wrap_fixed_bytes!(extra_derives:[],
@@ -92,6 +97,10 @@ impl PoolState {
         match &self.variant {
             PoolVariants::UniswapV2(v) => Pair(v.token_a, v.token_b),
             PoolVariants::UniswapV3(v) => Pair(v.token_a, v.token_b),
+            PoolVariants::UniswapV4(v) => Pair(v.token_a, v.token_b),
+            PoolVariants::CurveStable(v) => Pair(v.token_a, v.token_b),
+            PoolVariants::CurveCrypto(v) => Pair(v.token_a, v.token_b),
+            PoolVariants::BalancerV2(v) => Pair(v.token_a, v.token_b),
         }
     }
 
@@ -99,6 +108,10 @@ impl PoolState {
         match &self.variant {
             PoolVariants::UniswapV2(_) => Protocol::UniswapV2,
             PoolVariants::UniswapV3(_) => Protocol::UniswapV3,
+            PoolVariants::UniswapV4(_) => Protocol::UniswapV4,
+            PoolVariants::CurveStable(v) => v.protocol,
+            PoolVariants::CurveCrypto(v) => v.protocol,
+            PoolVariants::BalancerV2(_) => Protocol::BalancerV2,
         }
     }
 
@@ -108,13 +121,17 @@ impl PoolState {
             return
         }
         self.last_update = state.block;
-        self.variant.increment_state(state.logs);
+        self.variant.increment_state(state);
     }
 
     pub fn address(&self) -> Address {
         match &self.variant {
             PoolVariants::UniswapV2(v) => v.address(),
             PoolVariants::UniswapV3(v) => v.address(),
+            PoolVariants::UniswapV4(v) => v.address(),
+            PoolVariants::CurveStable(v) => v.address(),
+            PoolVariants::CurveCrypto(v) => v.address(),
+            PoolVariants::BalancerV2(v) => v.address(),
         }
     }
 
@@ -122,6 +139,10 @@ impl PoolState {
         match &self.variant {
             PoolVariants::UniswapV2(v) => v.get_tvl(base),
             PoolVariants::UniswapV3(v) => v.get_tvl(base),
+            PoolVariants::UniswapV4(v) => v.get_tvl(base),
+            PoolVariants::CurveStable(v) => v.get_tvl(base),
+            PoolVariants::CurveCrypto(v) => v.get_tvl(base),
+            PoolVariants::BalancerV2(v) => v.get_tvl(base),
         }
     }
 
@@ -129,6 +150,10 @@ impl PoolState {
         match &self.variant {
             PoolVariants::UniswapV2(v) => v.calculate_price(base),
             PoolVariants::UniswapV3(v) => v.calculate_price(base),
+            PoolVariants::UniswapV4(v) => v.calculate_price(base),
+            PoolVariants::CurveStable(v) => v.calculate_price(base),
+            PoolVariants::CurveCrypto(v) => v.calculate_price(base),
+            PoolVariants::BalancerV2(v) => v.calculate_price(base),
         }
     }
 }
@@ -137,16 +162,31 @@ impl PoolState {
 pub enum PoolVariants {
     UniswapV2(Box<UniswapV2Pool>),
     UniswapV3(Box<UniswapV3Pool>),
+    UniswapV4(Box<UniswapV4Pool>),
+    CurveStable(Box<CurveStablePool>),
+    CurveCrypto(Box<CurveCryptoPool>),
+    BalancerV2(Box<BalancerV2Pool>),
 }
 
 impl PoolVariants {
-    fn increment_state(&mut self, logs: Vec<Log>) {
-        for log in logs {
+    /// The uniswap pools emit their new state, the others are synced from the
+    /// classified action.
+    fn increment_state(&mut self, update: PoolUpdate) {
+        for log in update.logs {
             let _ = match self {
                 PoolVariants::UniswapV3(a) => a.sync_from_log(log),
                 PoolVariants::UniswapV2(a) => a.sync_from_log(log),
+                PoolVariants::UniswapV4(a) => a.sync_from_log(log),
+                _ => break,
             };
         }
+
+        let _ = match self {
+            PoolVariants::CurveStable(a) => a.sync_from_action(update.action),
+            PoolVariants::CurveCrypto(a) => a.sync_from_action(update.action),
+            PoolVariants::BalancerV2(a) => a.sync_from_action(update.action),
+            _ => Ok(()),
+        };
     }
 }
 
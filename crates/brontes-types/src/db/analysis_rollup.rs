@@ -0,0 +1,849 @@
+use std::hash::Hash;
+
+use alloy_primitives::{wrap_fixed_bytes, Address, FixedBytes, B256};
+use clickhouse::Row;
+use itertools::Itertools;
+use redefined::{self_convert_redefined, Redefined};
+use reth_db::DatabaseError;
+use rkyv::{Archive, Deserialize as rDeserialize, Serialize as rSerialize};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    db::{
+        block_analysis::{
+            BlockAnalysis, SingleTokenDetails, SingleTokenDetailsRedefined, TokenPairDetails,
+            TokenPairDetailsRedefined,
+        },
+        redefined_types::primitives::{AddressRedefined, B256Redefined},
+        searcher::Fund,
+    },
+    implement_table_value_codecs_with_zc,
+    serde_utils::{option_txhash, vec_address, vec_fund, vec_protocol},
+    FastHashMap, Protocol,
+};
+
+/// The amount of entries that are kept for each of the ranked lists of a
+/// rollup. Everything below the cut off is dropped when two rollups are
+/// merged, so the amounts of the last few entries are a lower bound.
+pub const ROLLUP_TOP_N: usize = 25;
+
+const HOUR: u64 = 60 * 60;
+const DAY: u64 = 24 * HOUR;
+const WEEK: u64 = 7 * DAY;
+/// the unix epoch is on a thursday, so weeks are shifted to start on monday
+const WEEK_OFFSET: u64 = 3 * DAY;
+
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+    rSerialize,
+    rDeserialize,
+    Archive,
+    strum::Display,
+    strum::EnumString,
+    strum::EnumIter,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case", ascii_case_insensitive)]
+pub enum RollupWindow {
+    #[default]
+    Hourly,
+    Daily,
+    /// weeks start on monday 00:00 UTC
+    Weekly,
+}
+
+self_convert_redefined!(RollupWindow);
+
+impl RollupWindow {
+    pub const fn seconds(&self) -> u64 {
+        match self {
+            Self::Hourly => HOUR,
+            Self::Daily => DAY,
+            Self::Weekly => WEEK,
+        }
+    }
+
+    /// The start of the window that the unix `timestamp` falls into
+    pub const fn window_start(&self, timestamp: u64) -> u64 {
+        match self {
+            Self::Weekly => timestamp.saturating_sub((timestamp + WEEK_OFFSET) % WEEK),
+            _ => timestamp - timestamp % self.seconds(),
+        }
+    }
+
+    const fn to_byte(self) -> u8 {
+        match self {
+            Self::Hourly => 0,
+            Self::Daily => 1,
+            Self::Weekly => 2,
+        }
+    }
+
+    fn from_byte(byte: u8) -> Result<Self, DatabaseError> {
+        match byte {
+            0 => Ok(Self::Hourly),
+            1 => Ok(Self::Daily),
+            2 => Ok(Self::Weekly),
+            _ => Err(DatabaseError::Decode),
+        }
+    }
+}
+
+/// Aggregated [`BlockAnalysis`] for all blocks with a timestamp inside of
+/// `[window_start, window_start + window.seconds())`.
+///
+/// Totals are summed, the average profit margin is weighted by bundle count
+/// and the ranked lists hold the amounts summed per entry, sorted from the
+/// biggest to the smallest amount and cut off at [`ROLLUP_TOP_N`].
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct BlockAnalysisRollup {
+    #[redefined(same_fields)]
+    pub window: RollupWindow,
+    /// unix timestamp in seconds
+    pub window_start: u64,
+    pub start_block: u64,
+    pub end_block: u64,
+    pub block_count: u64,
+    /// the blocks that were folded into the rollup, sorted
+    pub blocks: Vec<u64>,
+    pub all: MevTypeRollup,
+    pub atomic: MevTypeRollup,
+    pub sandwich: MevTypeRollup,
+    pub jit: MevTypeRollup,
+    pub jit_sandwich: MevTypeRollup,
+    pub cex_dex: MevTypeRollup,
+    pub liquidation: MevTypeRollup,
+    pub liquidated_tokens_profit: Vec<SingleTokenDetails>,
+    pub liquidated_tokens_profit_amt: Vec<f64>,
+    pub liquidated_tokens_revenue: Vec<SingleTokenDetails>,
+    pub liquidated_tokens_revenue_amt: Vec<f64>,
+    pub total_usd_liquidated: f64,
+    pub builder_profit_usd: f64,
+    pub builder_profit_eth: f64,
+    pub builder_revenue_usd: f64,
+    pub builder_revenue_eth: f64,
+    pub builder_mev_profit_usd: f64,
+    pub builder_mev_profit_eth: f64,
+    pub proposer_profit_usd: f64,
+    pub proposer_profit_eth: f64,
+}
+
+/// The rollup of a single mev type. Liquidations don't track pools, pairs or
+/// dexes so those lists are always empty for them.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct MevTypeRollup {
+    pub bundle_count:             u64,
+    pub total_profit:             f64,
+    pub total_revenue:            f64,
+    pub average_profit_margin:    f64,
+    pub searcher_eoa_profit:      Vec<Address>,
+    pub searcher_eoa_profit_amt:  Vec<f64>,
+    pub searcher_eoa_revenue:     Vec<Address>,
+    pub searcher_eoa_revenue_amt: Vec<f64>,
+    pub mev_contract_profit:      Vec<Address>,
+    pub mev_contract_profit_amt:  Vec<f64>,
+    pub mev_contract_revenue:     Vec<Address>,
+    pub mev_contract_revenue_amt: Vec<f64>,
+    #[redefined(same_fields)]
+    pub fund_profit:              Vec<Fund>,
+    pub fund_profit_amt:          Vec<f64>,
+    #[redefined(same_fields)]
+    pub fund_revenue:             Vec<Fund>,
+    pub fund_revenue_amt:         Vec<f64>,
+    pub arbed_pool_profit:        Vec<Address>,
+    pub arbed_pool_profit_amt:    Vec<f64>,
+    pub arbed_pool_revenue:       Vec<Address>,
+    pub arbed_pool_revenue_amt:   Vec<f64>,
+    pub arbed_pair_profit:        Vec<TokenPairDetails>,
+    pub arbed_pair_profit_amt:    Vec<f64>,
+    pub arbed_pair_revenue:       Vec<TokenPairDetails>,
+    pub arbed_pair_revenue_amt:   Vec<f64>,
+    #[redefined(same_fields)]
+    pub arbed_dex_profit:         Vec<Protocol>,
+    pub arbed_dex_profit_amt:     Vec<f64>,
+    #[redefined(same_fields)]
+    pub arbed_dex_revenue:        Vec<Protocol>,
+    pub arbed_dex_revenue_amt:    Vec<f64>,
+    pub biggest_arb_profit:       Option<B256>,
+    pub biggest_arb_profit_amt:   Option<f64>,
+    pub biggest_arb_revenue:      Option<B256>,
+    pub biggest_arb_revenue_amt:  Option<f64>,
+}
+
+implement_table_value_codecs_with_zc!(BlockAnalysisRollupRedefined);
+
+/// Builds the [`MevTypeRollup`] of a single block from the fields of the
+/// given prefix. Liquidations name their biggest arb differently and don't
+/// have pool, pair or dex fields.
+macro_rules! mev_type_rollup {
+    ($analysis:ident, $prefix:ident, $biggest:ident) => {
+        paste::paste! {
+            MevTypeRollup {
+                bundle_count:             $analysis.[<$prefix _bundle_count>],
+                total_profit:             $analysis.[<$prefix _total_profit>],
+                total_revenue:            $analysis.[<$prefix _total_revenue>],
+                average_profit_margin:    $analysis.[<$prefix _average_profit_margin>],
+                searcher_eoa_profit:      $analysis.[<$prefix _searcher_eoa_all_profit>].clone(),
+                searcher_eoa_profit_amt:  $analysis.[<$prefix _searcher_eoa_all_profit_amt>].clone(),
+                searcher_eoa_revenue:     $analysis.[<$prefix _searcher_eoa_all_revenue>].clone(),
+                searcher_eoa_revenue_amt: $analysis.[<$prefix _searcher_eoa_all_revenue_amt>].clone(),
+                mev_contract_profit:      $analysis.[<$prefix _mev_contract_all_profit>].clone(),
+                mev_contract_profit_amt:  $analysis.[<$prefix _mev_contract_all_profit_amt>].clone(),
+                mev_contract_revenue:     $analysis.[<$prefix _mev_contract_all_revenue>].clone(),
+                mev_contract_revenue_amt: $analysis.[<$prefix _mev_contract_all_revenue_amt>].clone(),
+                fund_profit:              $analysis.[<$prefix _fund_all_profit>].clone(),
+                fund_profit_amt:          $analysis.[<$prefix _fund_all_profit_amt>].clone(),
+                fund_revenue:             $analysis.[<$prefix _fund_all_revenue>].clone(),
+                fund_revenue_amt:         $analysis.[<$prefix _fund_all_revenue_amt>].clone(),
+                biggest_arb_profit:       $analysis.[<$biggest _biggest_arb_profit>],
+                biggest_arb_profit_amt:   $analysis.[<$biggest _biggest_arb_profit_amt>],
+                biggest_arb_revenue:      $analysis.[<$biggest _biggest_arb_revenue>],
+                biggest_arb_revenue_amt:  $analysis.[<$biggest _biggest_arb_revenue_amt>],
+                ..Default::default()
+            }
+        }
+    };
+    ($analysis:ident, $prefix:ident) => {
+        paste::paste! {
+            MevTypeRollup {
+                arbed_pool_profit:      $analysis.[<$prefix _arbed_pool_all_profit>].clone(),
+                arbed_pool_profit_amt:  $analysis.[<$prefix _arbed_pool_all_profit_amt>].clone(),
+                arbed_pool_revenue:     $analysis.[<$prefix _arbed_pool_all_revenue>].clone(),
+                arbed_pool_revenue_amt: $analysis.[<$prefix _arbed_pool_all_revenue_amt>].clone(),
+                arbed_pair_profit:      $analysis.[<$prefix _arbed_pair_all_profit>].clone(),
+                arbed_pair_profit_amt:  $analysis.[<$prefix _arbed_pair_all_profit_amt>].clone(),
+                arbed_pair_revenue:     $analysis.[<$prefix _arbed_pair_all_revenue>].clone(),
+                arbed_pair_revenue_amt: $analysis.[<$prefix _arbed_pair_all_revenue_amt>].clone(),
+                arbed_dex_profit:       $analysis.[<$prefix _arbed_dex_all_profit>].clone(),
+                arbed_dex_profit_amt:   $analysis.[<$prefix _arbed_dex_all_profit_amt>].clone(),
+                arbed_dex_revenue:      $analysis.[<$prefix _arbed_dex_all_revenue>].clone(),
+                arbed_dex_revenue_amt:  $analysis.[<$prefix _arbed_dex_all_revenue_amt>].clone(),
+                ..mev_type_rollup!($analysis, $prefix, $prefix)
+            }
+        }
+    };
+}
+
+impl BlockAnalysisRollup {
+    pub fn new(window: RollupWindow, window_start: u64) -> Self {
+        Self { window, window_start, ..Default::default() }
+    }
+
+    pub fn key(&self) -> RollupKey {
+        make_rollup_key(self.window, self.window_start)
+    }
+
+    /// The rollup of a single block
+    pub fn from_block(window: RollupWindow, timestamp: u64, analysis: &BlockAnalysis) -> Self {
+        let per_type = [
+            mev_type_rollup!(analysis, atomic),
+            mev_type_rollup!(analysis, sandwich),
+            mev_type_rollup!(analysis, jit),
+            mev_type_rollup!(analysis, jit_sandwich),
+            mev_type_rollup!(analysis, cex_dex),
+            mev_type_rollup!(analysis, liquidation, liquidated),
+        ]
+        .map(MevTypeRollup::ranked);
+
+        // the block analysis only keeps the top entry over all types, so the lists
+        // are rebuilt from the ones of each type
+        let mut all = MevTypeRollup {
+            bundle_count: analysis.all_bundle_count,
+            total_profit: analysis.all_total_profit,
+            total_revenue: analysis.all_total_revenue,
+            average_profit_margin: analysis.all_average_profit_margin,
+            biggest_arb_profit: analysis.all_biggest_arb_profit,
+            biggest_arb_profit_amt: analysis.all_biggest_arb_profit_amt,
+            biggest_arb_revenue: analysis.all_biggest_arb_revenue,
+            biggest_arb_revenue_amt: analysis.all_biggest_arb_revenue_amt,
+            ..Default::default()
+        };
+        per_type.iter().for_each(|rollup| all.merge_ranked(rollup));
+
+        let [atomic, sandwich, jit, jit_sandwich, cex_dex, liquidation] = per_type;
+
+        let mut this = Self {
+            window,
+            window_start: window.window_start(timestamp),
+            start_block: analysis.block_number,
+            end_block: analysis.block_number,
+            block_count: 1,
+            blocks: vec![analysis.block_number],
+            all,
+            atomic,
+            sandwich,
+            jit,
+            jit_sandwich,
+            cex_dex,
+            liquidation,
+            liquidated_tokens_profit: analysis.liquidated_tokens_profit.clone(),
+            liquidated_tokens_profit_amt: analysis.liquidated_tokens_profit_amt.clone(),
+            liquidated_tokens_revenue: analysis.liquidated_tokens_revenue.clone(),
+            liquidated_tokens_revenue_amt: analysis.liquidated_tokens_revenue_amt.clone(),
+            total_usd_liquidated: analysis.total_usd_liquidated,
+            builder_profit_usd: analysis.builder_profit_usd,
+            builder_profit_eth: analysis.builder_profit_eth,
+            builder_revenue_usd: analysis.builder_revenue_usd,
+            builder_revenue_eth: analysis.builder_revenue_eth,
+            builder_mev_profit_usd: analysis.builder_mev_profit_usd,
+            builder_mev_profit_eth: analysis.builder_mev_profit_eth,
+            proposer_profit_usd: analysis.proposer_profit_usd.unwrap_or_default(),
+            proposer_profit_eth: analysis.proposer_profit_eth.unwrap_or_default(),
+        };
+        rank(&mut this.liquidated_tokens_profit, &mut this.liquidated_tokens_profit_amt, &[], &[]);
+        rank(
+            &mut this.liquidated_tokens_revenue,
+            &mut this.liquidated_tokens_revenue_amt,
+            &[],
+            &[],
+        );
+
+        this
+    }
+
+    /// Adds the block to the rollup. Returns false if the block was already
+    /// folded, so re-runs over the same range don't count blocks twice. Errors
+    /// if the timestamp isn't in the window.
+    pub fn fold(&mut self, timestamp: u64, analysis: &BlockAnalysis) -> eyre::Result<bool> {
+        if self.window.window_start(timestamp) != self.window_start {
+            eyre::bail!(
+                "block {} isn't part of the {} window starting at {}",
+                analysis.block_number,
+                self.window,
+                self.window_start
+            )
+        }
+
+        if self.contains_block(analysis.block_number) {
+            return Ok(false)
+        }
+
+        self.merge(&Self::from_block(self.window, timestamp, analysis));
+        Ok(true)
+    }
+
+    pub fn contains_block(&self, block_number: u64) -> bool {
+        self.blocks.binary_search(&block_number).is_ok()
+    }
+
+    /// Merges two rollups of the same window that don't share any blocks
+    pub fn merge(&mut self, other: &Self) {
+        if other.block_count == 0 {
+            return
+        }
+
+        if self.block_count == 0 {
+            self.start_block = other.start_block;
+            self.end_block = other.end_block;
+        } else {
+            self.start_block = self.start_block.min(other.start_block);
+            self.end_block = self.end_block.max(other.end_block);
+        }
+        self.block_count += other.block_count;
+        self.blocks.extend_from_slice(&other.blocks);
+        self.blocks.sort_unstable();
+
+        self.all.merge(&other.all);
+        self.atomic.merge(&other.atomic);
+        self.sandwich.merge(&other.sandwich);
+        self.jit.merge(&other.jit);
+        self.jit_sandwich.merge(&other.jit_sandwich);
+        self.cex_dex.merge(&other.cex_dex);
+        self.liquidation.merge(&other.liquidation);
+
+        rank(
+            &mut self.liquidated_tokens_profit,
+            &mut self.liquidated_tokens_profit_amt,
+            &other.liquidated_tokens_profit,
+            &other.liquidated_tokens_profit_amt,
+        );
+        rank(
+            &mut self.liquidated_tokens_revenue,
+            &mut self.liquidated_tokens_revenue_amt,
+            &other.liquidated_tokens_revenue,
+            &other.liquidated_tokens_revenue_amt,
+        );
+
+        self.total_usd_liquidated += other.total_usd_liquidated;
+        self.builder_profit_usd += other.builder_profit_usd;
+        self.builder_profit_eth += other.builder_profit_eth;
+        self.builder_revenue_usd += other.builder_revenue_usd;
+        self.builder_revenue_eth += other.builder_revenue_eth;
+        self.builder_mev_profit_usd += other.builder_mev_profit_usd;
+        self.builder_mev_profit_eth += other.builder_mev_profit_eth;
+        self.proposer_profit_usd += other.proposer_profit_usd;
+        self.proposer_profit_eth += other.proposer_profit_eth;
+    }
+
+    pub fn mev_types(&self) -> [(&'static str, &MevTypeRollup); 7] {
+        [
+            ("all", &self.all),
+            ("atomic", &self.atomic),
+            ("sandwich", &self.sandwich),
+            ("jit", &self.jit),
+            ("jit_sandwich", &self.jit_sandwich),
+            ("cex_dex", &self.cex_dex),
+            ("liquidation", &self.liquidation),
+        ]
+    }
+
+    /// One row per mev type, with the totals of the window repeated on each
+    pub fn into_rows(&self) -> Vec<AnalysisRollupRow> {
+        self.mev_types()
+            .into_iter()
+            .map(|(mev_type, rollup)| AnalysisRollupRow::new(self, mev_type, rollup))
+            .collect()
+    }
+}
+
+impl MevTypeRollup {
+    /// Merges the totals and the ranked lists of `other` into self
+    pub fn merge(&mut self, other: &Self) {
+        let bundle_count = self.bundle_count + other.bundle_count;
+        if bundle_count != 0 {
+            self.average_profit_margin = (self.average_profit_margin * self.bundle_count as f64
+                + other.average_profit_margin * other.bundle_count as f64)
+                / bundle_count as f64;
+        }
+        self.bundle_count = bundle_count;
+        self.total_profit += other.total_profit;
+        self.total_revenue += other.total_revenue;
+
+        self.merge_ranked(other);
+
+        if other
+            .biggest_arb_profit_amt
+            .is_some_and(|amt| self.biggest_arb_profit_amt.map_or(true, |cur| amt > cur))
+        {
+            self.biggest_arb_profit = other.biggest_arb_profit;
+            self.biggest_arb_profit_amt = other.biggest_arb_profit_amt;
+        }
+        if other
+            .biggest_arb_revenue_amt
+            .is_some_and(|amt| self.biggest_arb_revenue_amt.map_or(true, |cur| amt > cur))
+        {
+            self.biggest_arb_revenue = other.biggest_arb_revenue;
+            self.biggest_arb_revenue_amt = other.biggest_arb_revenue_amt;
+        }
+    }
+
+    fn merge_ranked(&mut self, other: &Self) {
+        macro_rules! rank_lists {
+            ($($list:ident),*) => {
+                paste::paste! {
+                    $(
+                        rank(
+                            &mut self.$list,
+                            &mut self.[<$list _amt>],
+                            &other.$list,
+                            &other.[<$list _amt>],
+                        );
+                    )*
+                }
+            };
+        }
+
+        rank_lists!(
+            searcher_eoa_profit,
+            searcher_eoa_revenue,
+            mev_contract_profit,
+            mev_contract_revenue,
+            fund_profit,
+            fund_revenue,
+            arbed_pool_profit,
+            arbed_pool_revenue,
+            arbed_pair_profit,
+            arbed_pair_revenue,
+            arbed_dex_profit,
+            arbed_dex_revenue
+        );
+    }
+
+    /// The lists of a block analysis aren't sorted
+    fn ranked(mut self) -> Self {
+        self.merge_ranked(&Self::default());
+        self
+    }
+}
+
+/// Sums the amounts of both lists per entry and keeps the [`ROLLUP_TOP_N`]
+/// biggest ones. Entries without an amount are dropped, the block analysis
+/// uses them as placeholders.
+fn rank<T: Clone + Hash + Eq>(
+    keys: &mut Vec<T>,
+    amounts: &mut Vec<f64>,
+    other_keys: &[T],
+    other_amounts: &[f64],
+) {
+    let mut totals: FastHashMap<T, f64> = FastHashMap::default();
+    keys.drain(..)
+        .zip(amounts.drain(..))
+        .chain(
+            other_keys
+                .iter()
+                .cloned()
+                .zip(other_amounts.iter().copied()),
+        )
+        .for_each(|(key, amount)| *totals.entry(key).or_default() += amount);
+
+    (*keys, *amounts) = totals
+        .into_iter()
+        .filter(|(_, amount)| *amount != 0.0)
+        .sorted_by(|a, b| b.1.total_cmp(&a.1))
+        .take(ROLLUP_TOP_N)
+        .unzip();
+}
+
+/// A rollup flattened into one row per mev type for clickhouse
+#[derive(Debug, Clone, Serialize, Row)]
+pub struct AnalysisRollupRow {
+    pub window: String,
+    pub window_start: u64,
+    pub mev_type: String,
+    pub start_block: u64,
+    pub end_block: u64,
+    pub block_count: u64,
+    pub bundle_count: u64,
+    pub total_profit: f64,
+    pub total_revenue: f64,
+    pub average_profit_margin: f64,
+    #[serde(rename = "searcher_eoa_profit.address", with = "vec_address")]
+    pub searcher_eoa_profit: Vec<Address>,
+    #[serde(rename = "searcher_eoa_profit.amt")]
+    pub searcher_eoa_profit_amt: Vec<f64>,
+    #[serde(rename = "searcher_eoa_revenue.address", with = "vec_address")]
+    pub searcher_eoa_revenue: Vec<Address>,
+    #[serde(rename = "searcher_eoa_revenue.amt")]
+    pub searcher_eoa_revenue_amt: Vec<f64>,
+    #[serde(rename = "mev_contract_profit.address", with = "vec_address")]
+    pub mev_contract_profit: Vec<Address>,
+    #[serde(rename = "mev_contract_profit.amt")]
+    pub mev_contract_profit_amt: Vec<f64>,
+    #[serde(rename = "mev_contract_revenue.address", with = "vec_address")]
+    pub mev_contract_revenue: Vec<Address>,
+    #[serde(rename = "mev_contract_revenue.amt")]
+    pub mev_contract_revenue_amt: Vec<f64>,
+    #[serde(rename = "fund_profit.fund", with = "vec_fund")]
+    pub fund_profit: Vec<Fund>,
+    #[serde(rename = "fund_profit.amt")]
+    pub fund_profit_amt: Vec<f64>,
+    #[serde(rename = "fund_revenue.fund", with = "vec_fund")]
+    pub fund_revenue: Vec<Fund>,
+    #[serde(rename = "fund_revenue.amt")]
+    pub fund_revenue_amt: Vec<f64>,
+    #[serde(rename = "arbed_pool_profit.address", with = "vec_address")]
+    pub arbed_pool_profit: Vec<Address>,
+    #[serde(rename = "arbed_pool_profit.amt")]
+    pub arbed_pool_profit_amt: Vec<f64>,
+    #[serde(rename = "arbed_pool_revenue.address", with = "vec_address")]
+    pub arbed_pool_revenue: Vec<Address>,
+    #[serde(rename = "arbed_pool_revenue.amt")]
+    pub arbed_pool_revenue_amt: Vec<f64>,
+    #[serde(rename = "arbed_pair_profit.pair")]
+    pub arbed_pair_profit: Vec<TokenPairDetails>,
+    #[serde(rename = "arbed_pair_profit.amt")]
+    pub arbed_pair_profit_amt: Vec<f64>,
+    #[serde(rename = "arbed_pair_revenue.pair")]
+    pub arbed_pair_revenue: Vec<TokenPairDetails>,
+    #[serde(rename = "arbed_pair_revenue.amt")]
+    pub arbed_pair_revenue_amt: Vec<f64>,
+    #[serde(rename = "arbed_dex_profit.dex", with = "vec_protocol")]
+    pub arbed_dex_profit: Vec<Protocol>,
+    #[serde(rename = "arbed_dex_profit.amt")]
+    pub arbed_dex_profit_amt: Vec<f64>,
+    #[serde(rename = "arbed_dex_revenue.dex", with = "vec_protocol")]
+    pub arbed_dex_revenue: Vec<Protocol>,
+    #[serde(rename = "arbed_dex_revenue.amt")]
+    pub arbed_dex_revenue_amt: Vec<f64>,
+    #[serde(with = "option_txhash")]
+    pub biggest_arb_profit: Option<B256>,
+    pub biggest_arb_profit_amt: Option<f64>,
+    #[serde(with = "option_txhash")]
+    pub biggest_arb_revenue: Option<B256>,
+    pub biggest_arb_revenue_amt: Option<f64>,
+    #[serde(rename = "liquidated_tokens_profit.token")]
+    pub liquidated_tokens_profit: Vec<SingleTokenDetails>,
+    #[serde(rename = "liquidated_tokens_profit.amt")]
+    pub liquidated_tokens_profit_amt: Vec<f64>,
+    #[serde(rename = "liquidated_tokens_revenue.token")]
+    pub liquidated_tokens_revenue: Vec<SingleTokenDetails>,
+    #[serde(rename = "liquidated_tokens_revenue.amt")]
+    pub liquidated_tokens_revenue_amt: Vec<f64>,
+    pub total_usd_liquidated: f64,
+    pub builder_profit_usd: f64,
+    pub builder_profit_eth: f64,
+    pub builder_revenue_usd: f64,
+    pub builder_revenue_eth: f64,
+    pub builder_mev_profit_usd: f64,
+    pub builder_mev_profit_eth: f64,
+    pub proposer_profit_usd: f64,
+    pub proposer_profit_eth: f64,
+}
+
+impl AnalysisRollupRow {
+    fn new(window: &BlockAnalysisRollup, mev_type: &str, rollup: &MevTypeRollup) -> Self {
+        // the liquidated tokens only belong to the liquidation row
+        let liquidated = |tokens: &Vec<SingleTokenDetails>, amounts: &Vec<f64>| {
+            if mev_type == "liquidation" {
+                (tokens.clone(), amounts.clone())
+            } else {
+                (vec![], vec![])
+            }
+        };
+        let (liquidated_tokens_profit, liquidated_tokens_profit_amt) =
+            liquidated(&window.liquidated_tokens_profit, &window.liquidated_tokens_profit_amt);
+        let (liquidated_tokens_revenue, liquidated_tokens_revenue_amt) =
+            liquidated(&window.liquidated_tokens_revenue, &window.liquidated_tokens_revenue_amt);
+
+        Self {
+            window: window.window.to_string(),
+            window_start: window.window_start,
+            mev_type: mev_type.to_string(),
+            start_block: window.start_block,
+            end_block: window.end_block,
+            block_count: window.block_count,
+            bundle_count: rollup.bundle_count,
+            total_profit: rollup.total_profit,
+            total_revenue: rollup.total_revenue,
+            average_profit_margin: rollup.average_profit_margin,
+            searcher_eoa_profit: rollup.searcher_eoa_profit.clone(),
+            searcher_eoa_profit_amt: rollup.searcher_eoa_profit_amt.clone(),
+            searcher_eoa_revenue: rollup.searcher_eoa_revenue.clone(),
+            searcher_eoa_revenue_amt: rollup.searcher_eoa_revenue_amt.clone(),
+            mev_contract_profit: rollup.mev_contract_profit.clone(),
+            mev_contract_profit_amt: rollup.mev_contract_profit_amt.clone(),
+            mev_contract_revenue: rollup.mev_contract_revenue.clone(),
+            mev_contract_revenue_amt: rollup.mev_contract_revenue_amt.clone(),
+            fund_profit: rollup.fund_profit.clone(),
+            fund_profit_amt: rollup.fund_profit_amt.clone(),
+            fund_revenue: rollup.fund_revenue.clone(),
+            fund_revenue_amt: rollup.fund_revenue_amt.clone(),
+            arbed_pool_profit: rollup.arbed_pool_profit.clone(),
+            arbed_pool_profit_amt: rollup.arbed_pool_profit_amt.clone(),
+            arbed_pool_revenue: rollup.arbed_pool_revenue.clone(),
+            arbed_pool_revenue_amt: rollup.arbed_pool_revenue_amt.clone(),
+            arbed_pair_profit: rollup.arbed_pair_profit.clone(),
+            arbed_pair_profit_amt: rollup.arbed_pair_profit_amt.clone(),
+            arbed_pair_revenue: rollup.arbed_pair_revenue.clone(),
+            arbed_pair_revenue_amt: rollup.arbed_pair_revenue_amt.clone(),
+            arbed_dex_profit: rollup.arbed_dex_profit.clone(),
+            arbed_dex_profit_amt: rollup.arbed_dex_profit_amt.clone(),
+            arbed_dex_revenue: rollup.arbed_dex_revenue.clone(),
+            arbed_dex_revenue_amt: rollup.arbed_dex_revenue_amt.clone(),
+            biggest_arb_profit: rollup.biggest_arb_profit,
+            biggest_arb_profit_amt: rollup.biggest_arb_profit_amt,
+            biggest_arb_revenue: rollup.biggest_arb_revenue,
+            biggest_arb_revenue_amt: rollup.biggest_arb_revenue_amt,
+            liquidated_tokens_profit,
+            liquidated_tokens_profit_amt,
+            liquidated_tokens_revenue,
+            liquidated_tokens_revenue_amt,
+            total_usd_liquidated: window.total_usd_liquidated,
+            builder_profit_usd: window.builder_profit_usd,
+            builder_profit_eth: window.builder_profit_eth,
+            builder_revenue_usd: window.builder_revenue_usd,
+            builder_revenue_eth: window.builder_revenue_eth,
+            builder_mev_profit_usd: window.builder_mev_profit_usd,
+            builder_mev_profit_eth: window.builder_mev_profit_eth,
+            proposer_profit_usd: window.proposer_profit_usd,
+            proposer_profit_eth: window.proposer_profit_eth,
+        }
+    }
+}
+
+wrap_fixed_bytes!(
+    extra_derives: [],
+    pub struct RollupKey<9>;
+);
+
+impl reth_db::table::Encode for RollupKey {
+    type Encoded = [u8; 9];
+
+    fn encode(self) -> Self::Encoded {
+        self.0 .0
+    }
+}
+
+impl reth_db::table::Decode for RollupKey {
+    fn decode<B: AsRef<[u8]>>(value: B) -> Result<Self, DatabaseError> {
+        let value = value.as_ref();
+        if value.len() != 9 {
+            return Err(DatabaseError::Decode)
+        }
+        RollupWindow::from_byte(value[0])?;
+
+        Ok(RollupKey::from_slice(value))
+    }
+}
+
+/// Keys are ordered by window and then by start, so the rollups of a window
+/// can be walked in order
+pub fn make_rollup_key(window: RollupWindow, window_start: u64) -> RollupKey {
+    let window_byte = FixedBytes::new([window.to_byte()]);
+    window_byte
+        .concat_const(window_start.to_be_bytes().into())
+        .into()
+}
+
+pub fn decompose_rollup_key(key: RollupKey) -> Result<(RollupWindow, u64), DatabaseError> {
+    let window = RollupWindow::from_byte(key[0])?;
+    let window_start = u64::from_be_bytes(*FixedBytes::<8>::from_slice(&key[1..]));
+
+    Ok((window, window_start))
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{address, b256};
+
+    use super::*;
+
+    const SEARCHER_A: Address = address!("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa");
+    const SEARCHER_B: Address = address!("bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb");
+    const SEARCHER_C: Address = address!("cccccccccccccccccccccccccccccccccccccccc");
+
+    fn block(block_number: u64, searchers: Vec<(Address, f64)>) -> BlockAnalysis {
+        let (atomic_searcher_eoa_all_profit, atomic_searcher_eoa_all_profit_amt) =
+            searchers.iter().copied().unzip();
+        let total = searchers.iter().map(|(_, amt)| amt).sum();
+
+        BlockAnalysis {
+            block_number,
+            all_bundle_count: searchers.len() as u64,
+            all_total_profit: total,
+            atomic_bundle_count: searchers.len() as u64,
+            atomic_total_profit: total,
+            atomic_average_profit_margin: 0.5,
+            atomic_searcher_eoa_all_profit,
+            atomic_searcher_eoa_all_profit_amt,
+            atomic_searcher_eoa_all_revenue: vec![],
+            atomic_searcher_eoa_all_revenue_amt: vec![],
+            atomic_mev_contract_all_profit: vec![],
+            atomic_mev_contract_all_profit_amt: vec![],
+            atomic_mev_contract_all_revenue: vec![],
+            atomic_mev_contract_all_revenue_amt: vec![],
+            atomic_fund_all_profit: vec![],
+            atomic_fund_all_profit_amt: vec![],
+            atomic_fund_all_revenue: vec![],
+            atomic_fund_all_revenue_amt: vec![],
+            atomic_arbed_pool_all_profit: vec![],
+            atomic_arbed_pool_all_profit_amt: vec![],
+            atomic_arbed_pool_all_revenue: vec![],
+            atomic_arbed_pool_all_revenue_amt: vec![],
+            builder_profit_usd: 10.0,
+            proposer_profit_usd: Some(1.0),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_window_start() {
+        // tuesday 2024-01-02 13:37:00 UTC
+        let ts = 1_704_202_620;
+        assert_eq!(RollupWindow::Hourly.window_start(ts), 1_704_200_400);
+        assert_eq!(RollupWindow::Daily.window_start(ts), 1_704_153_600);
+        // monday 2024-01-01 00:00:00 UTC
+        assert_eq!(RollupWindow::Weekly.window_start(ts), 1_704_067_200);
+        assert_eq!(RollupWindow::Weekly.window_start(1_704_067_200), 1_704_067_200);
+
+        assert_eq!("weekly".parse::<RollupWindow>().unwrap(), RollupWindow::Weekly);
+    }
+
+    #[test]
+    fn test_rollup_key() {
+        let key = make_rollup_key(RollupWindow::Daily, 1_704_153_600);
+        assert_eq!(decompose_rollup_key(key).unwrap(), (RollupWindow::Daily, 1_704_153_600));
+
+        // all hourly rollups sort before the daily ones
+        assert!(make_rollup_key(RollupWindow::Hourly, u64::MAX) < key);
+        assert!(make_rollup_key(RollupWindow::Daily, 1_704_153_599) < key);
+
+        let mut invalid = key;
+        invalid.0[0] = 3;
+        assert!(decompose_rollup_key(invalid).is_err());
+        assert!(<RollupKey as reth_db::table::Decode>::decode(invalid.0).is_err());
+    }
+
+    #[test]
+    fn test_fold() {
+        let ts = 1_704_202_620;
+        let mut rollup = BlockAnalysisRollup::new(RollupWindow::Daily, 1_704_153_600);
+
+        assert!(rollup
+            .fold(ts, &block(1, vec![(SEARCHER_A, 5.0), (SEARCHER_B, 3.0)]))
+            .unwrap());
+        assert!(rollup
+            .fold(ts, &block(2, vec![(SEARCHER_B, 4.0), (SEARCHER_C, 1.0)]))
+            .unwrap());
+        // already folded blocks are skipped
+        assert!(!rollup.fold(ts, &block(1, vec![(SEARCHER_A, 5.0)])).unwrap());
+        // blocks can be folded out of order, as long as they weren't folded yet
+        assert!(rollup.fold(ts, &block(4, vec![])).unwrap());
+        assert!(rollup.fold(ts, &block(3, vec![])).unwrap());
+        assert!(!rollup.fold(ts, &block(3, vec![])).unwrap());
+        // the next day is another window
+        assert!(rollup
+            .fold(ts + 86_400, &block(5, vec![(SEARCHER_A, 5.0)]))
+            .is_err());
+
+        assert_eq!((rollup.start_block, rollup.end_block, rollup.block_count), (1, 4, 4));
+        assert_eq!(rollup.blocks, vec![1, 2, 3, 4]);
+        assert_eq!(rollup.atomic.bundle_count, 4);
+        assert_eq!(rollup.atomic.total_profit, 13.0);
+        assert_eq!(rollup.atomic.average_profit_margin, 0.5);
+        assert_eq!(rollup.builder_profit_usd, 40.0);
+        assert_eq!(rollup.proposer_profit_usd, 4.0);
+
+        assert_eq!(rollup.atomic.searcher_eoa_profit, vec![SEARCHER_B, SEARCHER_A, SEARCHER_C]);
+        assert_eq!(rollup.atomic.searcher_eoa_profit_amt, vec![7.0, 5.0, 1.0]);
+        // all is built from the lists of each type
+        assert_eq!(rollup.all.searcher_eoa_profit, rollup.atomic.searcher_eoa_profit);
+        assert_eq!(rollup.all.bundle_count, 4);
+    }
+
+    #[test]
+    fn test_merge_truncates_and_keeps_biggest_arb() {
+        let mut a = MevTypeRollup {
+            bundle_count: 1,
+            average_profit_margin: 1.0,
+            biggest_arb_profit: Some(b256!(
+                "1111111111111111111111111111111111111111111111111111111111111111"
+            )),
+            biggest_arb_profit_amt: Some(10.0),
+            ..Default::default()
+        };
+        let b = MevTypeRollup {
+            bundle_count: 3,
+            average_profit_margin: 0.2,
+            searcher_eoa_profit: (0..ROLLUP_TOP_N as u64 + 5)
+                .map(|i| Address::with_last_byte(i as u8))
+                .collect(),
+            searcher_eoa_profit_amt: (0..ROLLUP_TOP_N + 5).map(|i| i as f64).collect(),
+            biggest_arb_profit: Some(b256!(
+                "2222222222222222222222222222222222222222222222222222222222222222"
+            )),
+            biggest_arb_profit_amt: Some(5.0),
+            ..Default::default()
+        };
+
+        a.merge(&b);
+
+        assert_eq!(a.bundle_count, 4);
+        assert!((a.average_profit_margin - 0.4).abs() < 1e-9);
+        assert_eq!(a.searcher_eoa_profit.len(), ROLLUP_TOP_N);
+        assert_eq!(a.searcher_eoa_profit[0], Address::with_last_byte(ROLLUP_TOP_N as u8 + 4));
+        assert_eq!(a.biggest_arb_profit_amt, Some(10.0));
+    }
+}
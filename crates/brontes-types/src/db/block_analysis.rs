@@ -3,12 +3,17 @@ use crate::serde_utils::address;
 use alloy_primitives::Address;
 use clickhouse::Row;
 use itertools::Itertools;
+use redefined::Redefined;
 use reth_primitives::TxHash;
+use rkyv::{Archive, Deserialize as rDeserialize, Serialize as rSerialize};
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 use crate::serde_utils::vec_address;
 use crate::{
-    db::{searcher::Fund, token_info::TokenInfoWithAddress},
+    db::{
+        redefined_types::primitives::AddressRedefined, searcher::Fund,
+        token_info::TokenInfoWithAddress,
+    },
     mev::{Bundle, BundleData, Mev, MevBlock, MevType},
     pair::Pair,
     serde_utils::{
@@ -658,24 +663,24 @@ impl BlockAnalysis {
     pub fn new(block: &MevBlock, bundles: &[Bundle]) -> Self {
         // All fields
         let (all_profit_addr, all_profit_am) =
-            Self::top_searcher_by_profit(|b| b != MevType::SearcherTx && b!= MevType::CexDexTrades, bundles).unzip();
+            Self::top_searcher_by_profit(|b| b != MevType::SearcherTx && b != MevType::FailedAttempt && b!= MevType::CexDexTrades, bundles).unzip();
         let (all_rev_addr, all_rev_am) =
-            Self::top_searcher_by_rev(|b| b != MevType::SearcherTx  && b!= MevType::CexDexTrades, bundles).unzip();
+            Self::top_searcher_by_rev(|b| b != MevType::SearcherTx && b != MevType::FailedAttempt  && b!= MevType::CexDexTrades, bundles).unzip();
 
         let (all_biggest_tx_prof, all_biggest_prof) =
-            Self::biggest_arb_profit(|b| b != MevType::SearcherTx  && b!= MevType::CexDexTrades, bundles).unzip();
+            Self::biggest_arb_profit(|b| b != MevType::SearcherTx && b != MevType::FailedAttempt  && b!= MevType::CexDexTrades, bundles).unzip();
 
         let (all_biggest_tx_rev, all_biggest_rev) =
-            Self::biggest_arb_revenue(|b| b != MevType::SearcherTx  && b!= MevType::CexDexTrades, bundles).unzip();
+            Self::biggest_arb_revenue(|b| b != MevType::SearcherTx && b != MevType::FailedAttempt  && b!= MevType::CexDexTrades, bundles).unzip();
 
         let (fund_rev, fund_rev_am) =
-            Self::top_fund_by_type_rev(|b| b != MevType::SearcherTx && b!= MevType::CexDexTrades, bundles).unzip();
+            Self::top_fund_by_type_rev(|b| b != MevType::SearcherTx && b != MevType::FailedAttempt && b!= MevType::CexDexTrades, bundles).unzip();
         let (fund_profit, fund_profit_am) =
-            Self::top_fund_by_type_rev(|b| b != MevType::SearcherTx && b!= MevType::CexDexTrades, bundles).unzip();
+            Self::top_fund_by_type_rev(|b| b != MevType::SearcherTx && b != MevType::FailedAttempt && b!= MevType::CexDexTrades, bundles).unzip();
 
         let (all_pool_addr_prof, all_pool_addr_rev, all_pool_prof, all_pool_rev) =
             Self::most_transacted_pool(
-                |b| b != MevType::SearcherTx && b != MevType::Liquidation && b!= MevType::CexDexTrades,
+                |b| b != MevType::SearcherTx && b != MevType::FailedAttempt && b != MevType::Liquidation && b!= MevType::CexDexTrades,
                 bundles,
                 Self::get_pool_fn,
             )
@@ -683,7 +688,7 @@ impl BlockAnalysis {
 
         let (all_pair_addr_prof, all_pair_addr_rev, all_pair_prof, all_pair_rev) =
             Self::most_transacted_pair(
-                |b| b != MevType::SearcherTx && b != MevType::Liquidation &&  b!= MevType::CexDexTrades,
+                |b| b != MevType::SearcherTx && b != MevType::FailedAttempt && b != MevType::Liquidation &&  b!= MevType::CexDexTrades,
                 bundles,
                 Self::get_pair_fn,
             )
@@ -691,7 +696,7 @@ impl BlockAnalysis {
 
         let (all_dex_addr_prof, all_dex_addr_rev, all_dex_prof, all_dex_rev) =
             Self::most_transacted_dex(
-                |b| b != MevType::SearcherTx && b != MevType::Liquidation  || b!= MevType::CexDexTrades,
+                |b| b != MevType::SearcherTx && b != MevType::FailedAttempt && b != MevType::Liquidation  || b!= MevType::CexDexTrades,
                 bundles,
                 Self::get_dex_fn,
             )
@@ -1174,15 +1179,15 @@ impl BlockAnalysis {
         Self {
             block_number: block.block_number,
             eth_price: block.eth_price,
-            all_bundle_count: Self::total_count_by_type(|f| f != MevType::SearcherTx, bundles),
-            all_total_profit: Self::total_profit_by_type(|f| f != MevType::SearcherTx, bundles),
-            all_total_revenue: Self::total_revenue_by_type(|f| f != MevType::SearcherTx, bundles),
+            all_bundle_count: Self::total_count_by_type(|f| f != MevType::SearcherTx && f != MevType::FailedAttempt, bundles),
+            all_total_profit: Self::total_profit_by_type(|f| f != MevType::SearcherTx && f != MevType::FailedAttempt, bundles),
+            all_total_revenue: Self::total_revenue_by_type(|f| f != MevType::SearcherTx && f != MevType::FailedAttempt, bundles),
             all_average_profit_margin: Self::average_profit_margin(
-                |f| f != MevType::SearcherTx,
+                |f| f != MevType::SearcherTx && f != MevType::FailedAttempt,
                 bundles,
             )
             .unwrap_or_default(),
-            all_searcher_count: Self::unique_eoa(|b| b != MevType::SearcherTx, bundles),
+            all_searcher_count: Self::unique_eoa(|b| b != MevType::SearcherTx && b != MevType::FailedAttempt, bundles),
             all_top_searcher_revenue: all_rev_addr,
             all_top_searcher_revenue_amt: all_rev_am,
             all_top_searcher_profit: all_profit_addr,
@@ -1191,7 +1196,7 @@ impl BlockAnalysis {
             all_top_fund_revenue_amt: fund_rev_am,
             all_top_fund_profit_amt: fund_profit_am,
             all_top_fund_profit: fund_profit,
-            all_fund_count: Self::unique_funds(|b| b != MevType::SearcherTx, bundles),
+            all_fund_count: Self::unique_funds(|b| b != MevType::SearcherTx && b != MevType::FailedAttempt, bundles),
             all_most_arbed_pool_profit: all_pool_addr_prof,
             all_most_arbed_pool_profit_amt: all_pool_prof,
             all_most_arbed_dex_revenue: all_dex_addr_rev,
@@ -2176,7 +2181,8 @@ impl<A, B, C, D> TupleTwoVecUnzip<A, B, C, D> for (Vec<(A, B)>, Vec<(C, D)>) {
     }
 }
 
-#[derive(Default, Debug, Clone, Hash, PartialEq, Eq)]
+#[derive(Default, Debug, Clone, Hash, PartialEq, Eq, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
 pub struct TokenPairDetails {
     pub address0: Address,
     pub symbol0:  String,
@@ -2233,7 +2239,8 @@ impl From<(TokenInfoWithAddress, TokenInfoWithAddress)> for TokenPairDetails {
     }
 }
 
-#[derive(Default, Debug, Clone, Hash, PartialEq, Eq)]
+#[derive(Default, Debug, Clone, Hash, PartialEq, Eq, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
 pub struct SingleTokenDetails {
     pub address: Address,
     pub symbol:  String,
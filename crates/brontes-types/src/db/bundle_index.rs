@@ -0,0 +1,239 @@
+use alloy_primitives::{wrap_fixed_bytes, Address, FixedBytes, B256};
+use redefined::Redefined;
+use reth_db::DatabaseError;
+use rkyv::{Archive, Deserialize as rDeserialize, Serialize as rSerialize};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    implement_table_value_codecs_with_zc,
+    mev::{Bundle, Mev, MevType},
+    FastHashSet,
+};
+
+/// Points from an address or transaction to a bundle in the `MevBlocks`
+/// table, with enough of the bundle to filter on without loading its block
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct BundleIndexEntry {
+    #[redefined(same_fields)]
+    pub mev_type:   MevType,
+    pub profit_usd: f64,
+}
+
+implement_table_value_codecs_with_zc!(BundleIndexEntryRedefined);
+
+/// The index entries of the bundles of a block
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct BlockBundleIndex {
+    pub addresses: Vec<(AddressBundleKey, BundleIndexEntry)>,
+    pub txs:       Vec<(TxBundleKey, BundleIndexEntry)>,
+}
+
+impl BlockBundleIndex {
+    /// Indexes every bundle by the searcher eoa and contract, every address
+    /// with a balance delta, which includes the pools it traded on, and its
+    /// transactions
+    pub fn new(block_number: u64, bundles: &[Bundle]) -> Self {
+        let mut index = Self::default();
+
+        for (bundle_idx, bundle) in bundles.iter().enumerate() {
+            let bundle_idx = bundle_idx as u16;
+            let entry = BundleIndexEntry {
+                mev_type:   bundle.header.mev_type,
+                profit_usd: bundle.header.profit_usd,
+            };
+
+            let addresses = std::iter::once(bundle.header.eoa)
+                .chain(bundle.header.mev_contract)
+                .chain(
+                    bundle
+                        .header
+                        .balance_deltas
+                        .iter()
+                        .flat_map(|deltas| &deltas.address_deltas)
+                        .map(|deltas| deltas.address),
+                )
+                .collect::<FastHashSet<_>>();
+            index.addresses.extend(addresses.into_iter().map(|address| {
+                (make_address_bundle_key(address, block_number, bundle_idx), entry)
+            }));
+
+            let txs = std::iter::once(bundle.header.tx_hash)
+                .chain(bundle.data.mev_transaction_hashes())
+                .collect::<FastHashSet<_>>();
+            index.txs.extend(
+                txs.into_iter()
+                    .map(|tx_hash| (make_tx_bundle_key(tx_hash, block_number, bundle_idx), entry)),
+            );
+        }
+
+        index.addresses.sort_unstable_by_key(|(key, _)| *key);
+        index.txs.sort_unstable_by_key(|(key, _)| *key);
+
+        index
+    }
+}
+
+wrap_fixed_bytes!(
+    extra_derives: [],
+    pub struct AddressBundleKey<30>;
+);
+
+wrap_fixed_bytes!(
+    extra_derives: [],
+    pub struct TxBundleKey<42>;
+);
+
+impl reth_db::table::Encode for AddressBundleKey {
+    type Encoded = [u8; 30];
+
+    fn encode(self) -> Self::Encoded {
+        self.0 .0
+    }
+}
+
+impl reth_db::table::Decode for AddressBundleKey {
+    fn decode<B: AsRef<[u8]>>(value: B) -> Result<Self, DatabaseError> {
+        Ok(AddressBundleKey::from_slice(value.as_ref()))
+    }
+}
+
+impl reth_db::table::Encode for TxBundleKey {
+    type Encoded = [u8; 42];
+
+    fn encode(self) -> Self::Encoded {
+        self.0 .0
+    }
+}
+
+impl reth_db::table::Decode for TxBundleKey {
+    fn decode<B: AsRef<[u8]>>(value: B) -> Result<Self, DatabaseError> {
+        Ok(TxBundleKey::from_slice(value.as_ref()))
+    }
+}
+
+/// Keys are ordered by address, then block and then the index of the bundle
+/// in the block, so all bundles of an address can be walked in order
+pub fn make_address_bundle_key(
+    address: Address,
+    block_number: u64,
+    bundle_idx: u16,
+) -> AddressBundleKey {
+    address
+        .0
+        .concat_const::<8, 28>(block_number.to_be_bytes().into())
+        .concat_const(bundle_idx.to_be_bytes().into())
+        .into()
+}
+
+pub fn decompose_address_bundle_key(key: AddressBundleKey) -> (Address, u64, u16) {
+    let address = Address::from_slice(&key[0..20]);
+    let block_number = u64::from_be_bytes(*FixedBytes::<8>::from_slice(&key[20..28]));
+    let bundle_idx = u16::from_be_bytes(*FixedBytes::<2>::from_slice(&key[28..]));
+
+    (address, block_number, bundle_idx)
+}
+
+/// The range of keys of all bundles of `address` in `[start_block,
+/// end_block]`
+pub fn address_bundle_key_range(
+    address: Address,
+    start_block: u64,
+    end_block: u64,
+) -> (AddressBundleKey, AddressBundleKey) {
+    (
+        make_address_bundle_key(address, start_block, 0),
+        make_address_bundle_key(address, end_block, u16::MAX),
+    )
+}
+
+/// Keys are ordered by transaction hash, then block and then the index of the
+/// bundle in the block
+pub fn make_tx_bundle_key(tx_hash: B256, block_number: u64, bundle_idx: u16) -> TxBundleKey {
+    tx_hash
+        .concat_const::<8, 40>(block_number.to_be_bytes().into())
+        .concat_const(bundle_idx.to_be_bytes().into())
+        .into()
+}
+
+pub fn decompose_tx_bundle_key(key: TxBundleKey) -> (B256, u64, u16) {
+    let tx_hash = B256::from_slice(&key[0..32]);
+    let block_number = u64::from_be_bytes(*FixedBytes::<8>::from_slice(&key[32..40]));
+    let bundle_idx = u16::from_be_bytes(*FixedBytes::<2>::from_slice(&key[40..]));
+
+    (tx_hash, block_number, bundle_idx)
+}
+
+/// The range of keys of all bundles that contain `tx_hash`
+pub fn tx_bundle_key_range(tx_hash: B256) -> (TxBundleKey, TxBundleKey) {
+    (make_tx_bundle_key(tx_hash, 0, 0), make_tx_bundle_key(tx_hash, u64::MAX, u16::MAX))
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{address, b256};
+
+    use super::*;
+    use crate::mev::{
+        AddressBalanceDeltas, AtomicArb, BundleData, BundleHeader, TransactionAccounting,
+    };
+
+    #[test]
+    fn test_bundle_keys() {
+        let searcher = address!("1111111111111111111111111111111111111111");
+        let key = make_address_bundle_key(searcher, 19_000_000, 3);
+        assert_eq!(decompose_address_bundle_key(key), (searcher, 19_000_000, 3));
+
+        // keys of the same address sort by block, then by bundle
+        let (start, end) = address_bundle_key_range(searcher, 18_000_000, 19_000_000);
+        assert!(start < make_address_bundle_key(searcher, 18_999_999, u16::MAX));
+        assert!(make_address_bundle_key(searcher, 18_999_999, u16::MAX) < key);
+        assert!(key <= end);
+        assert!(make_address_bundle_key(searcher, 19_000_001, 0) > end);
+
+        let tx_hash = b256!("0000000000000000000000000000000000000000000000000000000000000001");
+        let key = make_tx_bundle_key(tx_hash, 19_000_000, 3);
+        assert_eq!(decompose_tx_bundle_key(key), (tx_hash, 19_000_000, 3));
+    }
+
+    #[test]
+    fn test_block_bundle_index() {
+        let eoa = address!("1111111111111111111111111111111111111111");
+        let contract = address!("2222222222222222222222222222222222222222");
+        let pool = address!("3333333333333333333333333333333333333333");
+        let tx_hash = b256!("0000000000000000000000000000000000000000000000000000000000000001");
+
+        let bundle = Bundle {
+            header: BundleHeader {
+                tx_hash,
+                eoa,
+                mev_contract: Some(contract),
+                mev_type: MevType::AtomicArb,
+                profit_usd: 10.0,
+                balance_deltas: vec![TransactionAccounting {
+                    tx_hash,
+                    address_deltas: [contract, pool]
+                        .map(|address| AddressBalanceDeltas { address, ..Default::default() })
+                        .to_vec(),
+                }],
+                ..Default::default()
+            },
+            data:   BundleData::AtomicArb(AtomicArb { tx_hash, ..Default::default() }),
+        };
+
+        let index = BlockBundleIndex::new(100, &[bundle.clone(), bundle]);
+        let entry = BundleIndexEntry { mev_type: MevType::AtomicArb, profit_usd: 10.0 };
+
+        // the contract is only indexed once per bundle
+        assert_eq!(index.addresses.len(), 6);
+        assert_eq!(index.addresses[0], (make_address_bundle_key(eoa, 100, 0), entry));
+        assert_eq!(index.addresses[5], (make_address_bundle_key(pool, 100, 1), entry));
+        assert_eq!(
+            index.txs,
+            vec![
+                (make_tx_bundle_key(tx_hash, 100, 0), entry),
+                (make_tx_bundle_key(tx_hash, 100, 1), entry)
+            ]
+        );
+    }
+}
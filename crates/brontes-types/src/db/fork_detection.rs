@@ -0,0 +1,57 @@
+use alloy_primitives::Address;
+use clap::ValueEnum;
+use redefined::{self_convert_redefined, Redefined};
+use rkyv::{Archive, Deserialize as rDeserialize, Serialize as rSerialize};
+use serde::{Deserialize, Serialize};
+use strum::Display;
+
+use crate::{implement_table_value_codecs_with_zc, Protocol};
+
+/// A pool that was registered because its runtime bytecode matched a known
+/// pool implementation, keyed by the pool address
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct ForkDetection {
+    #[redefined(same_fields)]
+    pub protocol:   Protocol,
+    /// The known pool whose bytecode it matched
+    pub reference:  Address,
+    /// Jaccard similarity of the bytecode fingerprints, between 0 and 1
+    pub similarity: f64,
+    /// Block of the swap that led to the detection, which is also the init
+    /// block the pool was registered with
+    pub block:      u64,
+    #[redefined(same_fields)]
+    pub status:     ForkReviewStatus,
+}
+
+implement_table_value_codecs_with_zc!(ForkDetectionRedefined);
+
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    rSerialize,
+    rDeserialize,
+    Archive,
+    Display,
+    ValueEnum,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum ForkReviewStatus {
+    /// Registered and classified, but not looked at yet
+    #[default]
+    Pending,
+    Approved,
+    /// Removed from `AddressToProtocolInfo`
+    Rejected,
+}
+
+self_convert_redefined!(ForkReviewStatus);
@@ -0,0 +1,240 @@
+//! [`MevBlockWithClassified`] as stored in the `MevBlocks` table before failed
+//! attempts, sniping, multi block sandwiches, victim losses and the lending
+//! actions of liquidations were added.
+use alloy_primitives::{Address, B256};
+use redefined::Redefined;
+use rkyv::{Archive, Deserialize as rDeserialize, Serialize as rSerialize};
+use serde::Serialize;
+
+use super::searcher_v1::{MevCountV1, MevTypeV1};
+use crate::{
+    db::{mev_block::MevBlockWithClassified, redefined_types::primitives::*, searcher::Fund},
+    implement_table_value_codecs_with_zc,
+    mev::*,
+    normalized_actions::*,
+    GasDetails,
+};
+
+#[derive(Debug, Default, PartialEq, Clone, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct MevBlockWithClassifiedV1 {
+    pub block: MevBlockV1,
+    pub mev:   Vec<BundleV1>,
+}
+
+implement_table_value_codecs_with_zc!(MevBlockWithClassifiedV1Redefined);
+
+impl From<MevBlockWithClassifiedV1> for MevBlockWithClassified {
+    fn from(value: MevBlockWithClassifiedV1) -> Self {
+        MevBlockWithClassified {
+            block: value.block.into(),
+            mev:   value.mev.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Clone, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct MevBlockV1 {
+    pub block_hash:                  B256,
+    pub block_number:                u64,
+    #[redefined(same_fields)]
+    pub mev_count:                   MevCountV1,
+    pub eth_price:                   f64,
+    pub total_gas_used:              u128,
+    pub total_priority_fee:          u128,
+    pub total_bribe:                 u128,
+    pub total_mev_bribe:             u128,
+    pub total_mev_priority_fee_paid: u128,
+    pub builder_address:             Address,
+    pub builder_name:                Option<String>,
+    pub builder_eth_profit:          f64,
+    pub builder_profit_usd:          f64,
+    pub builder_mev_profit_usd:      f64,
+    pub builder_searcher_bribes:     u128,
+    pub builder_searcher_bribes_usd: f64,
+    pub builder_sponsorship_amount:  u128,
+    pub ultrasound_bid_adjusted:     bool,
+    pub proposer_fee_recipient:      Option<Address>,
+    pub proposer_mev_reward:         Option<u128>,
+    pub proposer_profit_usd:         Option<f64>,
+    pub total_mev_profit_usd:        f64,
+    pub possible_mev:                PossibleMevCollection,
+}
+
+impl From<MevBlockV1> for MevBlock {
+    fn from(value: MevBlockV1) -> Self {
+        MevBlock {
+            block_hash:                  value.block_hash,
+            block_number:                value.block_number,
+            mev_count:                   value.mev_count.into(),
+            eth_price:                   value.eth_price,
+            total_gas_used:              value.total_gas_used,
+            total_priority_fee:          value.total_priority_fee,
+            total_bribe:                 value.total_bribe,
+            total_mev_bribe:             value.total_mev_bribe,
+            total_mev_priority_fee_paid: value.total_mev_priority_fee_paid,
+            builder_address:             value.builder_address,
+            builder_name:                value.builder_name,
+            builder_eth_profit:          value.builder_eth_profit,
+            builder_profit_usd:          value.builder_profit_usd,
+            builder_mev_profit_usd:      value.builder_mev_profit_usd,
+            builder_searcher_bribes:     value.builder_searcher_bribes,
+            builder_searcher_bribes_usd: value.builder_searcher_bribes_usd,
+            builder_sponsorship_amount:  value.builder_sponsorship_amount,
+            ultrasound_bid_adjusted:     value.ultrasound_bid_adjusted,
+            proposer_fee_recipient:      value.proposer_fee_recipient,
+            proposer_mev_reward:         value.proposer_mev_reward,
+            proposer_profit_usd:         value.proposer_profit_usd,
+            total_mev_profit_usd:        value.total_mev_profit_usd,
+            possible_mev:                value.possible_mev,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct BundleV1 {
+    pub header: BundleHeaderV1,
+    pub data:   BundleDataV1,
+}
+
+impl From<BundleV1> for Bundle {
+    fn from(value: BundleV1) -> Self {
+        Bundle { header: value.header.into(), data: value.data.into() }
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Clone, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct BundleHeaderV1 {
+    pub block_number:          u64,
+    pub tx_index:              u64,
+    pub tx_hash:               B256,
+    pub eoa:                   Address,
+    pub mev_contract:          Option<Address>,
+    #[redefined(same_fields)]
+    pub fund:                  Fund,
+    pub profit_usd:            f64,
+    pub bribe_usd:             f64,
+    #[redefined(same_fields)]
+    pub mev_type:              MevTypeV1,
+    pub no_pricing_calculated: bool,
+    pub balance_deltas:        Vec<TransactionAccounting>,
+}
+
+impl From<BundleHeaderV1> for BundleHeader {
+    fn from(value: BundleHeaderV1) -> Self {
+        BundleHeader {
+            block_number:          value.block_number,
+            tx_index:              value.tx_index,
+            tx_hash:               value.tx_hash,
+            eoa:                   value.eoa,
+            mev_contract:          value.mev_contract,
+            fund:                  value.fund,
+            profit_usd:            value.profit_usd,
+            bribe_usd:             value.bribe_usd,
+            mev_type:              value.mev_type.into(),
+            no_pricing_calculated: value.no_pricing_calculated,
+            balance_deltas:        value.balance_deltas,
+        }
+    }
+}
+
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, PartialEq, Clone, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub enum BundleDataV1 {
+    Sandwich(SandwichV1),
+    AtomicArb(AtomicArb),
+    JitSandwich(JitLiquiditySandwich),
+    Jit(JitLiquidity),
+    CexDexQuote(CexDexQuote),
+    CexDex(CexDex),
+    Liquidation(LiquidationV1),
+    Unknown(SearcherTx),
+}
+
+impl From<BundleDataV1> for BundleData {
+    fn from(value: BundleDataV1) -> Self {
+        match value {
+            BundleDataV1::Sandwich(sandwich) => BundleData::Sandwich(sandwich.into()),
+            BundleDataV1::AtomicArb(arb) => BundleData::AtomicArb(arb),
+            BundleDataV1::JitSandwich(jit_sandwich) => BundleData::JitSandwich(jit_sandwich),
+            BundleDataV1::Jit(jit) => BundleData::Jit(jit),
+            BundleDataV1::CexDexQuote(quote) => BundleData::CexDexQuote(quote),
+            BundleDataV1::CexDex(cex_dex) => BundleData::CexDex(cex_dex),
+            BundleDataV1::Liquidation(liquidation) => BundleData::Liquidation(liquidation.into()),
+            BundleDataV1::Unknown(searcher_tx) => BundleData::Unknown(searcher_tx),
+        }
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Clone, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct SandwichV1 {
+    pub block_number:             u64,
+    pub frontrun_tx_hash:         Vec<B256>,
+    pub frontrun_swaps:           Vec<Vec<NormalizedSwap>>,
+    #[redefined(same_fields)]
+    pub frontrun_gas_details:     Vec<GasDetails>,
+    pub victim_swaps_tx_hashes:   Vec<Vec<B256>>,
+    pub victim_swaps:             Vec<Vec<NormalizedSwap>>,
+    #[redefined(same_fields)]
+    pub victim_swaps_gas_details: Vec<GasDetails>,
+    pub backrun_tx_hash:          B256,
+    pub backrun_swaps:            Vec<NormalizedSwap>,
+    #[redefined(same_fields)]
+    pub backrun_gas_details:      GasDetails,
+}
+
+/// Every sandwich of v1 landed in a single block and wasn't simulated
+impl From<SandwichV1> for Sandwich {
+    fn from(value: SandwichV1) -> Self {
+        Sandwich {
+            block_number:             value.block_number,
+            frontrun_block_number:    value.block_number,
+            frontrun_tx_hash:         value.frontrun_tx_hash,
+            frontrun_swaps:           value.frontrun_swaps,
+            frontrun_gas_details:     value.frontrun_gas_details,
+            victim_swaps_tx_hashes:   value.victim_swaps_tx_hashes,
+            victim_swaps:             value.victim_swaps,
+            victim_swaps_gas_details: value.victim_swaps_gas_details,
+            backrun_tx_hash:          value.backrun_tx_hash,
+            backrun_swaps:            value.backrun_swaps,
+            backrun_gas_details:      value.backrun_gas_details,
+            victim_losses:            vec![],
+        }
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Clone, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct LiquidationV1 {
+    pub liquidation_tx_hash: B256,
+    pub block_number:        u64,
+    pub trigger:             B256,
+    pub liquidation_swaps:   Vec<NormalizedSwap>,
+    pub liquidations:        Vec<NormalizedLiquidation>,
+    #[redefined(same_fields)]
+    pub gas_details:         GasDetails,
+}
+
+impl From<LiquidationV1> for Liquidation {
+    fn from(value: LiquidationV1) -> Self {
+        Liquidation {
+            liquidation_tx_hash:     value.liquidation_tx_hash,
+            block_number:            value.block_number,
+            trigger:                 value.trigger,
+            liquidation_swaps:       value.liquidation_swaps,
+            liquidations:            value.liquidations,
+            loans:                   vec![],
+            repayments:              vec![],
+            deposits:                vec![],
+            withdrawals:             vec![],
+            oracle_update:           None,
+            txs_after_oracle_update: None,
+            gas_details:             value.gas_details,
+        }
+    }
+}
@@ -0,0 +1,6 @@
+//! Table values as they were encoded by older schema versions. They are only
+//! kept to read databases written with those versions while migrating them,
+//! see the [`SchemaVersion`](super::schema_version::SchemaVersion) of each
+//! table.
+pub mod mev_blocks_v1;
+pub mod searcher_v1;
@@ -0,0 +1,212 @@
+//! [`SearcherInfo`] as stored in the `SearcherEOAs` and `SearcherContracts`
+//! tables before failed attempts and sniping were counted.
+use alloy_primitives::Address;
+use redefined::{self_convert_redefined, Redefined};
+use rkyv::{Archive, Deserialize as rDeserialize, Serialize as rSerialize};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    db::{
+        redefined_types::primitives::AddressRedefined,
+        searcher::{Fund, SearcherInfo, TollByType},
+    },
+    implement_table_value_codecs_with_zc,
+    mev::{MevCount, MevType},
+};
+
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct SearcherInfoV1 {
+    pub name:              Option<String>,
+    #[redefined(same_fields)]
+    pub fund:              Fund,
+    #[redefined(same_fields)]
+    pub mev_count:         MevCountV1,
+    #[redefined(same_fields)]
+    pub pnl:               TollByTypeV1,
+    #[redefined(same_fields)]
+    pub gas_bids:          TollByTypeV1,
+    pub builder:           Option<Address>,
+    #[redefined(same_fields)]
+    pub config_labels:     Vec<MevTypeV1>,
+    pub sibling_searchers: Vec<Address>,
+}
+
+implement_table_value_codecs_with_zc!(SearcherInfoV1Redefined);
+
+impl From<SearcherInfoV1> for SearcherInfo {
+    fn from(value: SearcherInfoV1) -> Self {
+        SearcherInfo {
+            name:              value.name,
+            fund:              value.fund,
+            mev_count:         value.mev_count.into(),
+            pnl:               value.pnl.into(),
+            gas_bids:          value.gas_bids.into(),
+            builder:           value.builder,
+            config_labels:     value.config_labels.into_iter().map(Into::into).collect(),
+            sibling_searchers: value.sibling_searchers,
+        }
+    }
+}
+
+#[derive(
+    Debug, Default, PartialEq, Clone, Serialize, Deserialize, rSerialize, rDeserialize, Archive,
+)]
+pub struct MevCountV1 {
+    pub bundle_count:         u64,
+    pub sandwich_count:       Option<u64>,
+    pub cex_dex_trade_count:  Option<u64>,
+    pub cex_dex_quote_count:  Option<u64>,
+    pub cex_dex_rfq_count:    Option<u64>,
+    pub jit_cex_dex_count:    Option<u64>,
+    pub jit_count:            Option<u64>,
+    pub jit_sandwich_count:   Option<u64>,
+    pub atomic_backrun_count: Option<u64>,
+    pub liquidation_count:    Option<u64>,
+    pub searcher_tx_count:    Option<u64>,
+}
+
+self_convert_redefined!(MevCountV1);
+
+impl From<MevCountV1> for MevCount {
+    fn from(value: MevCountV1) -> Self {
+        MevCount {
+            bundle_count:         value.bundle_count,
+            sandwich_count:       value.sandwich_count,
+            cex_dex_trade_count:  value.cex_dex_trade_count,
+            cex_dex_quote_count:  value.cex_dex_quote_count,
+            cex_dex_rfq_count:    value.cex_dex_rfq_count,
+            jit_cex_dex_count:    value.jit_cex_dex_count,
+            jit_count:            value.jit_count,
+            jit_sandwich_count:   value.jit_sandwich_count,
+            atomic_backrun_count: value.atomic_backrun_count,
+            liquidation_count:    value.liquidation_count,
+            searcher_tx_count:    value.searcher_tx_count,
+            failed_attempt_count: None,
+            sniping_count:        None,
+        }
+    }
+}
+
+#[derive(
+    Debug, Default, PartialEq, Clone, Serialize, Deserialize, rSerialize, rDeserialize, Archive,
+)]
+pub struct TollByTypeV1 {
+    pub total:          f64,
+    pub sandwich:       Option<f64>,
+    pub cex_dex_quotes: Option<f64>,
+    pub cex_dex_trades: Option<f64>,
+    pub jit:            Option<f64>,
+    pub jit_sandwich:   Option<f64>,
+    pub atomic_backrun: Option<f64>,
+    pub liquidation:    Option<f64>,
+    pub searcher_tx:    Option<f64>,
+}
+
+self_convert_redefined!(TollByTypeV1);
+
+impl From<TollByTypeV1> for TollByType {
+    fn from(value: TollByTypeV1) -> Self {
+        TollByType {
+            total:          value.total,
+            sandwich:       value.sandwich,
+            cex_dex_quotes: value.cex_dex_quotes,
+            cex_dex_trades: value.cex_dex_trades,
+            jit:            value.jit,
+            jit_sandwich:   value.jit_sandwich,
+            atomic_backrun: value.atomic_backrun,
+            liquidation:    value.liquidation,
+            searcher_tx:    value.searcher_tx,
+            failed_attempt: None,
+            sniping:        None,
+        }
+    }
+}
+
+/// [`MevType`] before `FailedAttempt` and `Sniping` were added in front of
+/// `Unknown`, which moved its discriminant
+#[derive(
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Clone,
+    Copy,
+    Serialize,
+    Deserialize,
+    rSerialize,
+    rDeserialize,
+    Archive,
+)]
+pub enum MevTypeV1 {
+    CexDexTrades,
+    CexDexQuotes,
+    CexDexRfq,
+    Sandwich,
+    Jit,
+    JitCexDex,
+    JitSandwich,
+    Liquidation,
+    AtomicArb,
+    SearcherTx,
+    #[default]
+    Unknown,
+}
+
+self_convert_redefined!(MevTypeV1);
+
+impl From<MevTypeV1> for MevType {
+    fn from(value: MevTypeV1) -> Self {
+        match value {
+            MevTypeV1::CexDexTrades => MevType::CexDexTrades,
+            MevTypeV1::CexDexQuotes => MevType::CexDexQuotes,
+            MevTypeV1::CexDexRfq => MevType::CexDexRfq,
+            MevTypeV1::Sandwich => MevType::Sandwich,
+            MevTypeV1::Jit => MevType::Jit,
+            MevTypeV1::JitCexDex => MevType::JitCexDex,
+            MevTypeV1::JitSandwich => MevType::JitSandwich,
+            MevTypeV1::Liquidation => MevType::Liquidation,
+            MevTypeV1::AtomicArb => MevType::AtomicArb,
+            MevTypeV1::SearcherTx => MevType::SearcherTx,
+            MevTypeV1::Unknown => MevType::Unknown,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use redefined::RedefinedConvert;
+    use reth_db::table::{Compress, Decompress};
+
+    use super::*;
+
+    #[test]
+    fn test_migrate_searcher_info_v1() {
+        let searcher = Address::with_last_byte(1);
+        let v1 = SearcherInfoV1 {
+            name: Some("searcher".to_string()),
+            mev_count: MevCountV1 {
+                bundle_count: 3,
+                sandwich_count: Some(3),
+                ..Default::default()
+            },
+            pnl: TollByTypeV1 { total: 10.0, sandwich: Some(10.0), ..Default::default() },
+            config_labels: vec![MevTypeV1::Sandwich, MevTypeV1::Unknown],
+            sibling_searchers: vec![searcher],
+            ..Default::default()
+        };
+
+        let stored = SearcherInfoV1Redefined::from_source(v1.clone()).compress();
+        let decoded = SearcherInfoV1Redefined::decompress(stored)
+            .unwrap()
+            .to_source();
+        assert_eq!(decoded, v1);
+
+        let migrated: SearcherInfo = decoded.into();
+        assert_eq!(migrated.mev_count.sandwich_count, Some(3));
+        assert_eq!(migrated.mev_count.sniping_count, None);
+        assert_eq!(migrated.pnl.sandwich, Some(10.0));
+        assert_eq!(migrated.config_labels, vec![MevType::Sandwich, MevType::Unknown]);
+        assert_eq!(migrated.sibling_searchers, vec![searcher]);
+    }
+}
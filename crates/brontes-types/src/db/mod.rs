@@ -3,27 +3,34 @@ use std::fmt::Debug;
 use ::clickhouse::{DbRow, InsertRow};
 pub mod address_metadata;
 pub mod address_to_protocol_info;
+pub mod analysis_rollup;
 
 #[rustfmt::skip]
 pub mod block_analysis;
 pub mod block_times;
 pub mod builder;
+pub mod bundle_index;
 pub mod cex;
 
 pub mod clickhouse;
 pub mod clickhouse_serde;
 pub mod codecs;
 pub mod dex;
+pub mod fork_detection;
 pub mod initialized_state;
+pub mod legacy;
 pub mod metadata;
 pub mod mev_block;
 pub mod normalized_actions;
 pub mod pool_creation_block;
+pub mod pool_lvr;
 pub mod redefined_types;
+pub mod schema_version;
 pub mod searcher;
 pub mod token_info;
 pub mod traces;
 pub mod traits;
+pub mod uniswap_v4;
 
 /// This table is used to add run id inserts for each clickhouse table in order
 /// for us to not have to clear runs multiple times
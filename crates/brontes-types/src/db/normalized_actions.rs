@@ -151,6 +151,13 @@ pub enum ActionKind {
     Burn,
     Collect,
     Liquidation,
+    Loan,
+    Repayment,
+    Deposit,
+    Withdraw,
+    VaultDeposit,
+    VaultWithdraw,
+    OracleUpdate,
     Unclassified,
     SelfDestruct,
     EthTransfer,
@@ -171,6 +178,13 @@ impl From<&Action> for ActionKind {
             Action::Burn(_) => ActionKind::Burn,
             Action::Transfer(_) => ActionKind::Transfer,
             Action::Liquidation(_) => ActionKind::Liquidation,
+            Action::Loan(_) => ActionKind::Loan,
+            Action::Repayment(_) => ActionKind::Repayment,
+            Action::Deposit(_) => ActionKind::Deposit,
+            Action::Withdraw(_) => ActionKind::Withdraw,
+            Action::VaultDeposit(_) => ActionKind::VaultDeposit,
+            Action::VaultWithdraw(_) => ActionKind::VaultWithdraw,
+            Action::OracleUpdate(_) => ActionKind::OracleUpdate,
             Action::Collect(_) => ActionKind::Collect,
             Action::SelfDestruct(_) => ActionKind::SelfDestruct,
             Action::EthTransfer(_) => ActionKind::EthTransfer,
@@ -0,0 +1,81 @@
+use alloy_primitives::Address;
+use clickhouse::Row;
+use redefined::Redefined;
+use rkyv::{Archive, Deserialize as rDeserialize, Serialize as rSerialize};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    db::{
+        block_analysis::{SingleTokenDetails, SingleTokenDetailsRedefined},
+        redefined_types::primitives::AddressRedefined,
+    },
+    implement_table_value_codecs_with_zc,
+    serde_utils::{address, protocol},
+    Protocol,
+};
+
+/// The loss versus rebalancing (LVR) of the LPs of a single pool over a block.
+///
+/// The swaps of the block move the reserves of the pool. Had the LPs made the
+/// same trades at the CEX mid price at block time instead, they would have
+/// ended up with the same reserves and a different balance of the quote asset.
+/// The LVR is that difference: the value of the tokens that left the pool
+/// minus the value of the tokens that came in, both at the CEX mid. A positive
+/// LVR is a loss for the LPs.
+///
+/// As the amounts swapped in include the fee of the pool, the LVR is net of
+/// the fees the LPs earned.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize, Row, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct PoolLvr {
+    pub block_number:     u64,
+    #[serde(with = "address")]
+    pub pool:             Address,
+    #[redefined(same_fields)]
+    #[serde(with = "protocol")]
+    pub protocol:         Protocol,
+    /// The tokens the pool swapped in the block, sorted by address
+    #[serde(rename = "reserves.token")]
+    pub tokens:           Vec<SingleTokenDetails>,
+    /// The net change of the reserves of each token from the swaps of the
+    /// block
+    #[serde(rename = "reserves.delta")]
+    pub reserve_deltas:   Vec<f64>,
+    /// The CEX mid price of each token in the quote asset at block time
+    #[serde(rename = "reserves.cex_price")]
+    pub cex_prices:       Vec<f64>,
+    pub swap_count:       u64,
+    /// The value of all tokens swapped into the pool at the CEX mid
+    pub volume_usd:       f64,
+    /// The volume of the swaps the LPs lost on at the CEX mid
+    pub toxic_volume_usd: f64,
+    pub lvr_usd:          f64,
+}
+
+impl PoolLvr {
+    /// The share of the volume of the pool that the LPs lost on
+    pub fn toxicity(&self) -> f64 {
+        if self.volume_usd == 0.0 {
+            return 0.0
+        }
+
+        self.toxic_volume_usd / self.volume_usd
+    }
+}
+
+/// The LVR of all pools that were swapped through in a block and could be
+/// priced on a CEX
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct BlockPoolLvr {
+    pub block_number: u64,
+    pub pools:        Vec<PoolLvr>,
+}
+
+implement_table_value_codecs_with_zc!(BlockPoolLvrRedefined);
+
+impl BlockPoolLvr {
+    pub fn total_lvr_usd(&self) -> f64 {
+        self.pools.iter().map(|pool| pool.lvr_usd).sum()
+    }
+}
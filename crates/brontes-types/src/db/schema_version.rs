@@ -0,0 +1,54 @@
+use std::fmt;
+
+use redefined::self_convert_redefined;
+use serde::{Deserialize, Serialize};
+
+use crate::implement_table_value_codecs_with_zc;
+
+/// The version of the encoding of the values of a table, recorded per table so
+/// a database written with an older encoding isn't read with a newer one
+#[derive(
+    Debug,
+    Default,
+    PartialEq,
+    Clone,
+    Copy,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    rkyv::Serialize,
+    rkyv::Deserialize,
+    rkyv::Archive,
+)]
+#[repr(transparent)]
+pub struct SchemaVersion(pub u16);
+
+impl SchemaVersion {
+    /// The version of every table in a database written before versions were
+    /// recorded
+    pub const BASELINE: SchemaVersion = SchemaVersion(1);
+
+    pub const fn next(self) -> Self {
+        Self(self.0 + 1)
+    }
+}
+
+impl fmt::Display for SchemaVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "v{}", self.0)
+    }
+}
+
+impl std::str::FromStr for SchemaVersion {
+    type Err = std::num::ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(s.trim_start_matches('v').parse()?))
+    }
+}
+
+self_convert_redefined!(SchemaVersion);
+implement_table_value_codecs_with_zc!(SchemaVersion);
@@ -74,6 +74,8 @@ impl SearcherInfo {
             MevType::AtomicArb => self.mev_count.atomic_backrun_count,
             MevType::Liquidation => self.mev_count.liquidation_count,
             MevType::SearcherTx => self.mev_count.searcher_tx_count,
+            MevType::FailedAttempt => self.mev_count.failed_attempt_count,
+            MevType::Sniping => self.mev_count.sniping_count,
             MevType::Unknown => None,
         }
     }
@@ -199,6 +201,8 @@ pub struct TollByType {
     pub atomic_backrun: Option<f64>,
     pub liquidation:    Option<f64>,
     pub searcher_tx:    Option<f64>,
+    pub failed_attempt: Option<f64>,
+    pub sniping:        Option<f64>,
 }
 
 self_convert_redefined!(TollByType);
@@ -242,6 +246,16 @@ impl TollByType {
             MevType::SearcherTx => {
                 self.searcher_tx = Some(self.searcher_tx.unwrap_or_default().add(header.profit_usd))
             }
+            MevType::FailedAttempt => {
+                self.failed_attempt = Some(
+                    self.failed_attempt
+                        .unwrap_or_default()
+                        .add(header.profit_usd),
+                )
+            }
+            MevType::Sniping => {
+                self.sniping = Some(self.sniping.unwrap_or_default().add(header.profit_usd))
+            }
             _ => (),
         }
     }
@@ -284,6 +298,16 @@ impl TollByType {
             MevType::SearcherTx => {
                 self.searcher_tx = Some(self.searcher_tx.unwrap_or_default().add(header.bribe_usd))
             }
+            MevType::FailedAttempt => {
+                self.failed_attempt = Some(
+                    self.failed_attempt
+                        .unwrap_or_default()
+                        .add(header.bribe_usd),
+                )
+            }
+            MevType::Sniping => {
+                self.sniping = Some(self.sniping.unwrap_or_default().add(header.bribe_usd))
+            }
             _ => (),
         }
     }
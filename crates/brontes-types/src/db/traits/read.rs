@@ -1,12 +1,22 @@
-use alloy_primitives::Address;
+use alloy_primitives::{Address, B256};
 
 use crate::{
     db::{
-        address_metadata::AddressMetadata, address_to_protocol_info::ProtocolInfo,
-        builder::BuilderInfo, cex::trades::CexTradeMap, dex::DexQuotes, metadata::Metadata,
-        mev_block::MevBlockWithClassified, searcher::SearcherInfo,
+        address_metadata::AddressMetadata,
+        address_to_protocol_info::ProtocolInfo,
+        analysis_rollup::{BlockAnalysisRollup, RollupWindow},
+        builder::BuilderInfo,
+        cex::trades::CexTradeMap,
+        dex::DexQuotes,
+        fork_detection::ForkDetection,
+        metadata::Metadata,
+        mev_block::MevBlockWithClassified,
+        pool_lvr::BlockPoolLvr,
+        searcher::SearcherInfo,
         token_info::TokenInfoWithAddress,
+        uniswap_v4::UniswapV4PoolKey,
     },
+    mev::Bundle,
     pair::Pair,
     structured_trace::TxTrace,
     FastHashMap, Protocol,
@@ -104,6 +114,19 @@ pub trait LibmdbxReader: Send + Sync + Unpin + 'static {
 
     fn fetch_all_address_metadata(&self) -> eyre::Result<Vec<(Address, AddressMetadata)>>;
 
+    /// Pools registered by the fork detection, with their review status
+    fn fetch_fork_detections(&self) -> eyre::Result<Vec<(Address, ForkDetection)>>;
+
+    /// The fork detection recorded for the pool, if any
+    fn try_fetch_fork_detection(&self, pool: Address) -> eyre::Result<Option<ForkDetection>>;
+
+    /// The key of the uniswap v4 pool we identify by the given address, if the
+    /// pool was seen before
+    fn try_fetch_uniswap_v4_pool_key(
+        &self,
+        pool: Address,
+    ) -> eyre::Result<Option<UniswapV4PoolKey>>;
+
     fn get_dex_quotes(&self, block: u64) -> eyre::Result<DexQuotes>;
 
     fn try_fetch_token_info(&self, address: Address) -> eyre::Result<TokenInfoWithAddress>;
@@ -112,6 +135,8 @@ pub trait LibmdbxReader: Send + Sync + Unpin + 'static {
         self.try_fetch_token_info(address).map(|info| info.decimals)
     }
 
+    /// the mev blocks in the inclusive range, starting at the first block if
+    /// there is no `start_block`
     fn try_fetch_mev_blocks(
         &self,
         start_block: Option<u64>,
@@ -123,6 +148,36 @@ pub trait LibmdbxReader: Send + Sync + Unpin + 'static {
         start_block: Option<u64>,
     ) -> eyre::Result<Vec<MevBlockWithClassified>>;
 
+    /// the rollups of the window that start in the inclusive range
+    fn try_fetch_analysis_rollups(
+        &self,
+        window: RollupWindow,
+        start: u64,
+        end: u64,
+    ) -> eyre::Result<Vec<BlockAnalysisRollup>>;
+
+    fn fetch_all_analysis_rollups(&self) -> eyre::Result<Vec<BlockAnalysisRollup>>;
+
+    fn try_fetch_pool_lvr(
+        &self,
+        start_block: Option<u64>,
+        end_block: u64,
+    ) -> eyre::Result<Vec<BlockPoolLvr>>;
+
+    fn fetch_all_pool_lvr(&self, start_block: Option<u64>) -> eyre::Result<Vec<BlockPoolLvr>>;
+
+    /// The bundles `address` is part of, as the searcher eoa or contract or
+    /// with a balance delta, from the oldest to the newest block
+    fn try_fetch_bundles_by_address(
+        &self,
+        address: Address,
+        start_block: Option<u64>,
+        end_block: Option<u64>,
+    ) -> eyre::Result<Vec<Bundle>>;
+
+    /// The bundles that contain the transaction
+    fn try_fetch_bundles_by_tx_hash(&self, tx_hash: B256) -> eyre::Result<Vec<Bundle>>;
+
     fn protocols_created_before(
         &self,
         start_block: u64,
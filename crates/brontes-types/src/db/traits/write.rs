@@ -3,8 +3,10 @@ use futures::Future;
 
 use crate::{
     db::{
-        address_metadata::AddressMetadata, block_analysis::BlockAnalysis, builder::BuilderInfo,
-        dex::DexQuotes, searcher::SearcherInfo,
+        address_metadata::AddressMetadata, analysis_rollup::BlockAnalysisRollup,
+        block_analysis::BlockAnalysis, builder::BuilderInfo, dex::DexQuotes,
+        fork_detection::ForkDetection, pool_lvr::BlockPoolLvr, searcher::SearcherInfo,
+        uniswap_v4::UniswapV4PoolKey,
     },
     mev::{Bundle, MevBlock},
     normalized_actions::Action,
@@ -26,6 +28,21 @@ pub trait DBWriter: Send + Unpin + 'static {
         self.inner().write_block_analysis(block_analysis)
     }
 
+    /// overwrites the stored rollups of the same window and window start
+    fn write_analysis_rollups(
+        &self,
+        rollups: Vec<BlockAnalysisRollup>,
+    ) -> impl Future<Output = eyre::Result<()>> + Send {
+        self.inner().write_analysis_rollups(rollups)
+    }
+
+    fn write_pool_lvr(
+        &self,
+        pool_lvr: BlockPoolLvr,
+    ) -> impl Future<Output = eyre::Result<()>> + Send {
+        self.inner().write_pool_lvr(pool_lvr)
+    }
+
     fn write_dex_quotes(
         &self,
         block_number: u64,
@@ -98,6 +115,24 @@ pub trait DBWriter: Send + Unpin + 'static {
         self.inner().write_address_meta(address, metadata)
     }
 
+    /// records a pool registered by the fork detection, or a review of one
+    fn write_fork_detection(
+        &self,
+        pool: Address,
+        detection: ForkDetection,
+    ) -> impl Future<Output = eyre::Result<()>> + Send {
+        self.inner().write_fork_detection(pool, detection)
+    }
+
+    /// records the key of a uniswap v4 pool, so that its state can be loaded
+    fn write_uniswap_v4_pool_key(
+        &self,
+        pool: Address,
+        key: UniswapV4PoolKey,
+    ) -> impl Future<Output = eyre::Result<()>> + Send {
+        self.inner().write_uniswap_v4_pool_key(pool, key)
+    }
+
     fn insert_pool(
         &self,
         block: u64,
@@ -124,4 +159,11 @@ pub trait DBWriter: Send + Unpin + 'static {
     ) -> impl Future<Output = eyre::Result<()>> + Send {
         self.inner().save_traces(block, traces)
     }
+
+    /// removes the mev blocks, dex prices and traces written for the block and
+    /// takes it out of the analysis rollups. used to roll back blocks that got
+    /// reorged out
+    fn delete_block(&self, block_number: u64) -> impl Future<Output = eyre::Result<()>> + Send {
+        self.inner().delete_block(block_number)
+    }
 }
@@ -0,0 +1,22 @@
+use alloy_primitives::Address;
+use redefined::Redefined;
+use rkyv::{Archive, Deserialize as rDeserialize, Serialize as rSerialize};
+use serde::{Deserialize, Serialize};
+
+use crate::implement_table_value_codecs_with_zc;
+
+/// The key of a Uniswap V4 pool, keyed by the address brontes uses for the
+/// pool. V4 pools only exist as entries in the PoolManager, their state can't
+/// be loaded without the key the pool id is derived from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct UniswapV4PoolKey {
+    /// The zero address for native eth
+    pub currency0:    Address,
+    pub currency1:    Address,
+    pub fee:          u32,
+    pub tick_spacing: i32,
+    pub hooks:        Address,
+}
+
+implement_table_value_codecs_with_zc!(UniswapV4PoolKeyRedefined);
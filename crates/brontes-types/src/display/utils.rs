@@ -45,6 +45,14 @@ pub fn display_sandwich(bundle: &Bundle, f: &mut fmt::Formatter) -> fmt::Result
         }
     }
 
+    if sandwich_data.is_multi_block() {
+        writeln!(
+            f,
+            "   - Spans Blocks: {} -> {}",
+            sandwich_data.frontrun_block_number, sandwich_data.block_number
+        )?;
+    }
+
     writeln!(f, "\n{}:", "Attacks".bright_yellow().underline())?;
     for (i, ((tx_hash, swaps), gas_details)) in sandwich_data
         .frontrun_tx_hash
@@ -105,6 +113,17 @@ pub fn display_sandwich(bundle: &Bundle, f: &mut fmt::Formatter) -> fmt::Result
                 if let Some(gas_details) = victim_gas_details {
                     gas_details.pretty_print_with_spaces(f, 16)?;
                 }
+
+                if let Some(loss) = sandwich_data.victim_loss(*tx_hash) {
+                    writeln!(
+                        f,
+                        "          - {}: {} {} (${})",
+                        "Simulated loss".bright_blue(),
+                        loss.token_amount_lost.clone().to_float(),
+                        loss.token.inner.symbol,
+                        format!("{:.2}", loss.amount_lost_usd.clone().to_float()).red()
+                    )?;
+                }
             }
         }
     }
@@ -521,6 +540,41 @@ pub fn display_liquidation(bundle: &Bundle, f: &mut fmt::Formatter) -> fmt::Resu
         liquidation.pretty_print(f, 8)?;
     }
 
+    // Lending Section
+    let lending_actions = liquidation_data
+        .loans
+        .iter()
+        .map(|l| l.to_string())
+        .chain(liquidation_data.repayments.iter().map(|r| r.to_string()))
+        .chain(liquidation_data.deposits.iter().map(|d| d.to_string()))
+        .chain(liquidation_data.withdrawals.iter().map(|w| w.to_string()))
+        .collect::<Vec<_>>();
+
+    if !lending_actions.is_empty() {
+        writeln!(f, "\n{}\n", "Lending Actions".bright_yellow().underline())?;
+        for (i, action) in lending_actions.iter().enumerate() {
+            writeln!(f, "    {}: {}", format!(" - {}", i + 1).green(), action)?;
+        }
+    }
+
+    // Oracle Update Section
+    if let Some(update) = &liquidation_data.oracle_update {
+        writeln!(f, "\n{}\n", "Oracle Update".bright_yellow().underline())?;
+        writeln!(
+            f,
+            " - {}: {}",
+            "Tx".bright_blue(),
+            format!("{:?}", liquidation_data.trigger).cyan()
+        )?;
+        writeln!(f, " - {}: {}", "Update".bright_blue(), update)?;
+        if let Some(delta) = liquidation_data.oracle_price_delta() {
+            writeln!(f, " - {}: {:.4}%", "Price Change".bright_blue(), delta * 100.0)?;
+        }
+        if let Some(txs_after) = liquidation_data.txs_after_oracle_update {
+            writeln!(f, " - {}: {}", "Txs After Update".bright_blue(), txs_after)?;
+        }
+    }
+
     // Gas Details Section
     writeln!(f, "\n - {}:", "Gas Details".bright_blue())?;
     liquidation_data
@@ -1014,6 +1068,172 @@ pub fn display_searcher_tx(bundle: &Bundle, f: &mut fmt::Formatter) -> fmt::Resu
     Ok(())
 }
 
+pub fn display_failed_attempt(bundle: &Bundle, f: &mut fmt::Formatter) -> fmt::Result {
+    let failed_attempt_data = match &bundle.data {
+        BundleData::FailedAttempt(data) => data,
+        _ => panic!("Wrong bundle type"),
+    };
+
+    writeln!(f, "\n{}\n", "Failed MEV Attempt".bold().underline().red())?;
+
+    // Tx details
+    writeln!(f, "\n{}: \n", "Transaction Details".bold().underline().bright_yellow())?;
+    writeln!(f, "   - Tx Index: {}", bundle.header.tx_index.to_string().bold())?;
+    writeln!(f, "   - EOA: {}", bundle.header.eoa)?;
+
+    match bundle.header.mev_contract {
+        Some(contract) => {
+            writeln!(f, "   - Mev Contract: {}", formate_etherscan_address_url(&contract))?;
+        }
+        None => {
+            writeln!(f, "   - Mev Contract: None")?;
+        }
+    }
+
+    writeln!(f, "   - Etherscan: {}", format_etherscan_url(&bundle.header.tx_hash))?;
+    writeln!(f, "   - Attempted: {}", failed_attempt_data.attempted_mev_type.to_string().bold())?;
+
+    if !failed_attempt_data.protocols.is_empty() {
+        writeln!(
+            f,
+            "   - Protocols: {}",
+            failed_attempt_data
+                .protocols
+                .iter()
+                .map(|protocol| protocol.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )?;
+    }
+
+    writeln!(f, "  - {}:", "PnL".bright_blue())?;
+    writeln!(f, "   - Gas Burnt (USD): {}", format_bribe(bundle.header.bribe_usd))?;
+
+    // Gas Details
+    writeln!(f, "\n{}: \n", "Gas Details".underline().bright_yellow())?;
+
+    failed_attempt_data
+        .gas_details
+        .pretty_print_with_spaces(f, 8)?;
+
+    Ok(())
+}
+
+pub fn display_sniping(bundle: &Bundle, f: &mut fmt::Formatter) -> fmt::Result {
+    let sniping_data = match &bundle.data {
+        BundleData::Sniping(data) => data,
+        _ => panic!("Wrong bundle type"),
+    };
+
+    writeln!(f, "\n{}\n", "Token Launch Snipe".bold().underline().bright_green())?;
+
+    // Launch details
+    writeln!(f, "{}: \n", "Launch Details".bold().underline().bright_yellow())?;
+    writeln!(
+        f,
+        "   - Token: {} ({})",
+        sniping_data.token.inner.symbol.bold(),
+        sniping_data.token.address
+    )?;
+    writeln!(f, "   - Pool: {} ({})", sniping_data.pool, sniping_data.protocol)?;
+    writeln!(f, "   - Launch Block: {}", sniping_data.launch_block_number.to_string().bold())?;
+    writeln!(f, "   - Launch Tx: {}", format_etherscan_url(&sniping_data.launch_tx_hash))?;
+
+    // Sniper details
+    writeln!(f, "\n{}: \n", "Sniper Details".bold().underline().bright_yellow())?;
+    writeln!(f, "   - EOA: {}", bundle.header.eoa)?;
+
+    match bundle.header.mev_contract {
+        Some(contract) => {
+            writeln!(f, "   - Mev Contract: {}", formate_etherscan_address_url(&contract))?;
+        }
+        None => {
+            writeln!(f, "   - Mev Contract: None")?;
+        }
+    }
+
+    writeln!(f, "   - Prior Snipes: {}", sniping_data.prior_snipes.to_string().bold())?;
+    if sniping_data.repeat_sniper {
+        writeln!(f, "   - {}", "Repeat Sniper".bold().red())?;
+    }
+
+    // Snipes
+    writeln!(
+        f,
+        "\n{} ({} block(s) after launch)\n",
+        "Snipes".bright_yellow().underline(),
+        sniping_data.blocks_after_launch
+    )?;
+    for ((tx_hash, swaps), gas_details) in sniping_data
+        .snipe_tx_hashes
+        .iter()
+        .zip(&sniping_data.snipe_swaps)
+        .zip(&sniping_data.snipe_gas_details)
+    {
+        writeln!(f, " - {}", format_etherscan_url(tx_hash))?;
+        for (i, swap) in swaps.iter().enumerate() {
+            writeln!(f, "    {}: {}", format!(" - {}", i + 1).green(), swap)?;
+        }
+        gas_details.pretty_print_with_spaces(f, 8)?;
+    }
+
+    // Exits
+    if !sniping_data.exit_tx_hashes.is_empty() {
+        writeln!(f, "\n{}\n", "Exits".bright_yellow().underline())?;
+        for ((tx_hash, swaps), gas_details) in sniping_data
+            .exit_tx_hashes
+            .iter()
+            .zip(&sniping_data.exit_swaps)
+            .zip(&sniping_data.exit_gas_details)
+        {
+            writeln!(f, " - {}", format_etherscan_url(tx_hash))?;
+            for (i, swap) in swaps.iter().enumerate() {
+                writeln!(f, "    {}: {}", format!(" - {}", i + 1).green(), swap)?;
+            }
+            gas_details.pretty_print_with_spaces(f, 8)?;
+        }
+    }
+
+    // Profitability Section
+    writeln!(
+        f,
+        "\n{} ({} blocks)\n",
+        "Mark to Market".bright_yellow().underline(),
+        sniping_data.markout_blocks
+    )?;
+    writeln!(
+        f,
+        " - {}: {:.4} {}",
+        "Tokens Held".bright_white(),
+        sniping_data.tokens_held,
+        sniping_data.token.inner.symbol
+    )?;
+    writeln!(
+        f,
+        " - {}: {}",
+        "Bundle Profit (USD)".bright_white(),
+        format_profit(bundle.header.profit_usd)
+            .to_string()
+            .bright_white()
+    )?;
+    writeln!(
+        f,
+        " - {}: {}",
+        "Bribe (USD)".bright_white(),
+        format_bribe(bundle.header.bribe_usd)
+            .to_string()
+            .bright_red()
+    )?;
+
+    bundle
+        .header
+        .balance_deltas
+        .iter()
+        .for_each(|tx_delta| writeln!(f, "{}", tx_delta).expect("Failed to write balance deltas"));
+
+    Ok(())
+}
+
 // Helper function to format profit values
 fn format_profit(value: f64) -> ColoredString {
     if value < 0.0 {
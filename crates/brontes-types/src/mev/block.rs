@@ -199,6 +199,8 @@ pub struct MevCount {
     pub atomic_backrun_count: Option<u64>,
     pub liquidation_count:    Option<u64>,
     pub searcher_tx_count:    Option<u64>,
+    pub failed_attempt_count: Option<u64>,
+    pub sniping_count:        Option<u64>,
 }
 
 impl MevCount {
@@ -231,6 +233,13 @@ impl MevCount {
             MevType::JitCexDex => {
                 self.jit_cex_dex_count = Some(self.jit_cex_dex_count.unwrap_or_default().add(1))
             }
+            MevType::FailedAttempt => {
+                self.failed_attempt_count =
+                    Some(self.failed_attempt_count.unwrap_or_default().add(1))
+            }
+            MevType::Sniping => {
+                self.sniping_count = Some(self.sniping_count.unwrap_or_default().add(1))
+            }
             _ => {}
         }
     }
@@ -268,6 +277,12 @@ impl fmt::Display for MevCount {
         if let Some(count) = self.searcher_tx_count {
             writeln!(f, "    - Searcher TXs: {}", count.to_string().bold())?;
         }
+        if let Some(count) = self.failed_attempt_count {
+            writeln!(f, "    - Failed Attempts: {}", count.to_string().bold())?;
+        }
+        if let Some(count) = self.sniping_count {
+            writeln!(f, "    - Sniping: {}", count.to_string().bold())?;
+        }
 
         Ok(())
     }
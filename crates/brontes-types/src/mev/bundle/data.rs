@@ -32,6 +32,8 @@ pub enum BundleData {
     CexDex(CexDex),
     Liquidation(Liquidation),
     Unknown(SearcherTx),
+    FailedAttempt(FailedAttempt),
+    Sniping(Sniping),
 }
 
 impl Default for BundleData {
@@ -40,6 +42,51 @@ impl Default for BundleData {
     }
 }
 
+impl BundleData {
+    /// The gas details of the transactions the bundle paid gas for, keyed by
+    /// their hash. Victim transactions aren't included.
+    pub fn gas_details_by_tx(&self) -> Vec<(B256, GasDetails)> {
+        match self {
+            BundleData::Sandwich(m) => m
+                .frontrun_tx_hash
+                .iter()
+                .copied()
+                .zip(m.frontrun_gas_details.iter().copied())
+                .chain(std::iter::once((m.backrun_tx_hash, m.backrun_gas_details)))
+                .collect(),
+            BundleData::JitSandwich(m) => m
+                .frontrun_tx_hash
+                .iter()
+                .copied()
+                .zip(m.frontrun_gas_details.iter().copied())
+                .chain(std::iter::once((m.backrun_tx_hash, m.backrun_gas_details)))
+                .collect(),
+            BundleData::Jit(m) => vec![
+                (m.frontrun_mint_tx_hash, m.frontrun_mint_gas_details),
+                (m.backrun_burn_tx_hash, m.backrun_burn_gas_details),
+            ],
+            BundleData::Sniping(m) => m
+                .snipe_tx_hashes
+                .iter()
+                .copied()
+                .zip(m.snipe_gas_details.iter().copied())
+                .chain(
+                    m.exit_tx_hashes
+                        .iter()
+                        .copied()
+                        .zip(m.exit_gas_details.iter().copied()),
+                )
+                .collect(),
+            BundleData::AtomicArb(m) => vec![(m.tx_hash, m.gas_details)],
+            BundleData::CexDex(m) => vec![(m.tx_hash, m.gas_details)],
+            BundleData::CexDexQuote(m) => vec![(m.tx_hash, m.gas_details)],
+            BundleData::Liquidation(m) => vec![(m.liquidation_tx_hash, m.gas_details)],
+            BundleData::Unknown(s) => vec![(s.tx_hash, s.gas_details)],
+            BundleData::FailedAttempt(s) => vec![(s.tx_hash, s.gas_details)],
+        }
+    }
+}
+
 impl Mev for BundleData {
     fn mev_type(&self) -> MevType {
         match self {
@@ -51,6 +98,8 @@ impl Mev for BundleData {
             BundleData::CexDexQuote(m) => m.mev_type(),
             BundleData::Liquidation(m) => m.mev_type(),
             BundleData::Unknown(m) => m.mev_type(),
+            BundleData::FailedAttempt(m) => m.mev_type(),
+            BundleData::Sniping(m) => m.mev_type(),
         }
     }
 
@@ -64,6 +113,8 @@ impl Mev for BundleData {
             BundleData::CexDexQuote(m) => m.total_gas_paid(),
             BundleData::Liquidation(m) => m.total_gas_paid(),
             BundleData::Unknown(s) => s.total_gas_paid(),
+            BundleData::FailedAttempt(s) => s.total_gas_paid(),
+            BundleData::Sniping(s) => s.total_gas_paid(),
         }
     }
 
@@ -77,6 +128,8 @@ impl Mev for BundleData {
             BundleData::CexDexQuote(m) => m.total_priority_fee_paid(base_fee),
             BundleData::Liquidation(m) => m.total_priority_fee_paid(base_fee),
             BundleData::Unknown(s) => s.total_priority_fee_paid(base_fee),
+            BundleData::FailedAttempt(s) => s.total_priority_fee_paid(base_fee),
+            BundleData::Sniping(s) => s.total_priority_fee_paid(base_fee),
         }
     }
 
@@ -90,6 +143,8 @@ impl Mev for BundleData {
             BundleData::CexDexQuote(m) => m.bribe(),
             BundleData::Liquidation(m) => m.bribe(),
             BundleData::Unknown(s) => s.bribe(),
+            BundleData::FailedAttempt(s) => s.bribe(),
+            BundleData::Sniping(s) => s.bribe(),
         }
     }
 
@@ -103,6 +158,8 @@ impl Mev for BundleData {
             BundleData::CexDexQuote(m) => m.mev_transaction_hashes(),
             BundleData::Liquidation(m) => m.mev_transaction_hashes(),
             BundleData::Unknown(s) => s.mev_transaction_hashes(),
+            BundleData::FailedAttempt(s) => s.mev_transaction_hashes(),
+            BundleData::Sniping(s) => s.mev_transaction_hashes(),
         }
     }
 
@@ -116,6 +173,8 @@ impl Mev for BundleData {
             BundleData::CexDexQuote(m) => m.protocols(),
             BundleData::Liquidation(m) => m.protocols(),
             BundleData::Unknown(s) => s.protocols(),
+            BundleData::FailedAttempt(s) => s.protocols(),
+            BundleData::Sniping(s) => s.protocols(),
         }
     }
 }
@@ -162,6 +221,18 @@ impl From<Liquidation> for BundleData {
     }
 }
 
+impl From<FailedAttempt> for BundleData {
+    fn from(value: FailedAttempt) -> Self {
+        Self::FailedAttempt(value)
+    }
+}
+
+impl From<Sniping> for BundleData {
+    fn from(value: Sniping) -> Self {
+        Self::Sniping(value)
+    }
+}
+
 impl Serialize for BundleData {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -176,6 +247,8 @@ impl Serialize for BundleData {
             BundleData::CexDexQuote(cex_dex) => cex_dex.serialize(serializer),
             BundleData::Liquidation(liquidation) => liquidation.serialize(serializer),
             BundleData::Unknown(s) => s.serialize(serializer),
+            BundleData::FailedAttempt(s) => s.serialize(serializer),
+            BundleData::Sniping(s) => s.serialize(serializer),
         }
     }
 }
@@ -191,6 +264,8 @@ impl InsertRow for BundleData {
             BundleData::CexDexQuote(cex_dex) => cex_dex.get_column_names(),
             BundleData::Liquidation(liquidation) => liquidation.get_column_names(),
             BundleData::Unknown(s) => s.get_column_names(),
+            BundleData::FailedAttempt(s) => s.get_column_names(),
+            BundleData::Sniping(s) => s.get_column_names(),
         }
     }
 }
@@ -68,6 +68,8 @@ impl fmt::Display for Bundle {
             MevType::Liquidation => display_liquidation(self, f)?,
             MevType::JitSandwich => display_jit_liquidity_sandwich(self, f)?,
             MevType::SearcherTx => display_searcher_tx(self, f)?,
+            MevType::FailedAttempt => display_failed_attempt(self, f)?,
+            MevType::Sniping => display_sniping(self, f)?,
             MevType::Unknown => (),
         }
 
@@ -102,6 +104,8 @@ pub enum MevType {
     Liquidation,
     AtomicArb,
     SearcherTx,
+    FailedAttempt,
+    Sniping,
     #[default]
     Unknown,
 }
@@ -115,6 +119,8 @@ impl MevType {
             | MevType::AtomicArb
             | MevType::Liquidation
             | MevType::SearcherTx
+            | MevType::FailedAttempt
+            | MevType::Sniping
             | MevType::Unknown => false,
             MevType::CexDexRfq
             | MevType::CexDexTrades
@@ -134,6 +140,8 @@ impl MevType {
             MevType::Sandwich => "sandwich",
             MevType::JitSandwich => "jit-sandwich",
             MevType::SearcherTx => "searcher-tx",
+            MevType::FailedAttempt => "failed-attempt",
+            MevType::Sniping => "sniping",
             MevType::Liquidation => "liquidation",
             MevType::Unknown => "header",
         }
@@ -154,6 +162,8 @@ impl From<String> for MevType {
             "JitSandwich" => MevType::JitSandwich,
             "AtomicArb" => MevType::AtomicArb,
             "SearcherTx" => MevType::SearcherTx,
+            "FailedAttempt" => MevType::FailedAttempt,
+            "Sniping" => MevType::Sniping,
             _ => MevType::Unknown,
         }
     }
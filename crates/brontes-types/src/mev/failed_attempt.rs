@@ -0,0 +1,96 @@
+use std::fmt::Debug;
+
+use ::serde::ser::Serializer;
+use ahash::HashSet;
+use clickhouse::DbRow;
+use redefined::Redefined;
+use reth_primitives::B256;
+use rkyv::{Archive, Deserialize as rDeserialize, Serialize as rSerialize};
+use serde::{ser::SerializeStruct, Deserialize, Serialize};
+use serde_with::serde_as;
+
+use crate::{
+    db::redefined_types::primitives::*,
+    mev::{Mev, MevType},
+    GasDetails, Protocol,
+};
+
+/// A searcher transaction that reverted. All it did was burn gas, which is
+/// accounted for as the (negative) profit of the bundle.
+#[serde_as]
+#[derive(Debug, Deserialize, PartialEq, Clone, Default, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct FailedAttempt {
+    pub tx_hash:            B256,
+    pub block_number:       u64,
+    /// The type of mev the transaction was most likely going after
+    #[redefined(same_fields)]
+    pub attempted_mev_type: MevType,
+    /// The protocols the transaction called into before it reverted
+    #[redefined(same_fields)]
+    pub protocols:          Vec<Protocol>,
+    #[redefined(same_fields)]
+    pub gas_details:        GasDetails,
+}
+
+impl Mev for FailedAttempt {
+    fn mev_type(&self) -> MevType {
+        MevType::FailedAttempt
+    }
+
+    fn mev_transaction_hashes(&self) -> Vec<B256> {
+        vec![self.tx_hash]
+    }
+
+    fn total_gas_paid(&self) -> u128 {
+        self.gas_details.gas_paid()
+    }
+
+    fn total_priority_fee_paid(&self, base_fee: u128) -> u128 {
+        self.gas_details.priority_fee_paid(base_fee)
+    }
+
+    /// Coinbase transfers are reverted along with the rest of the transaction
+    fn bribe(&self) -> u128 {
+        0
+    }
+
+    fn protocols(&self) -> HashSet<Protocol> {
+        self.protocols.iter().copied().collect()
+    }
+}
+
+impl Serialize for FailedAttempt {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut ser_struct = serializer.serialize_struct("FailedAttempt", 5)?;
+
+        ser_struct.serialize_field("tx_hash", &format!("{:?}", self.tx_hash))?;
+        ser_struct.serialize_field("block_number", &self.block_number)?;
+        ser_struct.serialize_field("attempted_mev_type", &self.attempted_mev_type.to_string())?;
+
+        let protocols = self
+            .protocols
+            .iter()
+            .map(|protocol| protocol.to_string())
+            .collect::<Vec<_>>();
+        ser_struct.serialize_field("protocols", &protocols)?;
+
+        let gas_details = (
+            self.gas_details.coinbase_transfer,
+            self.gas_details.priority_fee,
+            self.gas_details.gas_used,
+            self.gas_details.effective_gas_price,
+        );
+        ser_struct.serialize_field("gas_details", &gas_details)?;
+
+        ser_struct.end()
+    }
+}
+
+impl DbRow for FailedAttempt {
+    const COLUMN_NAMES: &'static [&'static str] =
+        &["tx_hash", "block_number", "attempted_mev_type", "protocols", "gas_details"];
+}
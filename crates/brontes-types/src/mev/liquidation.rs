@@ -12,7 +12,7 @@ use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 
 use super::{Mev, MevType};
-use crate::{db::redefined_types::primitives::*, Protocol};
+use crate::{db::redefined_types::primitives::*, Protocol, ToFloatNearest};
 #[allow(unused_imports)]
 use crate::{display::utils::display_sandwich, normalized_actions::*, GasDetails};
 
@@ -20,13 +20,42 @@ use crate::{display::utils::display_sandwich, normalized_actions::*, GasDetails}
 #[derive(Debug, Deserialize, PartialEq, Clone, Default, Redefined)]
 #[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
 pub struct Liquidation {
-    pub liquidation_tx_hash: B256,
-    pub block_number:        u64,
-    pub trigger:             B256,
-    pub liquidation_swaps:   Vec<NormalizedSwap>,
-    pub liquidations:        Vec<NormalizedLiquidation>,
+    pub liquidation_tx_hash:     B256,
+    pub block_number:            u64,
+    /// The transaction of the oracle update the liquidation backran, zero for
+    /// organic liquidations
+    pub trigger:                 B256,
+    pub liquidation_swaps:       Vec<NormalizedSwap>,
+    pub liquidations:            Vec<NormalizedLiquidation>,
+    /// Lending pool interactions made in the same transaction, e.g borrowing
+    /// the debt asset or withdrawing the seized collateral
+    pub loans:                   Vec<NormalizedLoan>,
+    pub repayments:              Vec<NormalizedRepayment>,
+    pub deposits:                Vec<NormalizedDeposit>,
+    pub withdrawals:             Vec<NormalizedWithdraw>,
+    /// The last update earlier in the block of a feed pricing the collateral
+    /// or debt asset of the liquidation
+    pub oracle_update:           Option<NormalizedOracleUpdate>,
+    /// Number of transactions between the oracle update and the liquidation,
+    /// 0 if the liquidation directly backran it
+    pub txs_after_oracle_update: Option<u64>,
     #[redefined(same_fields)]
-    pub gas_details:         GasDetails,
+    pub gas_details:             GasDetails,
+}
+
+impl Liquidation {
+    pub fn is_oracle_backrun(&self) -> bool {
+        self.oracle_update.is_some()
+    }
+
+    /// Relative change of the price of the oracle update the liquidation
+    /// backran
+    pub fn oracle_price_delta(&self) -> Option<f64> {
+        self.oracle_update
+            .as_ref()?
+            .price_delta()
+            .map(|delta| delta.to_float())
+    }
 }
 
 impl Mev for Liquidation {
@@ -61,6 +90,16 @@ impl Mev for Liquidation {
             protocols.insert(liquidation.protocol);
         });
 
+        self.loans
+            .iter()
+            .map(|l| l.protocol)
+            .chain(self.repayments.iter().map(|r| r.protocol))
+            .chain(self.deposits.iter().map(|d| d.protocol))
+            .chain(self.withdrawals.iter().map(|w| w.protocol))
+            .for_each(|protocol| {
+                protocols.insert(protocol);
+            });
+
         protocols
     }
 }
@@ -70,7 +109,7 @@ impl Serialize for Liquidation {
     where
         S: Serializer,
     {
-        let mut ser_struct = serializer.serialize_struct("Liquidation", 34)?;
+        let mut ser_struct = serializer.serialize_struct("Liquidation", 50)?;
 
         // frontrun
         ser_struct
@@ -114,6 +153,82 @@ impl Serialize for Liquidation {
             &liquidations.liquidated_collateral,
         )?;
 
+        let loans: ClickhouseVecNormalizedLending = self
+            .loans
+            .clone()
+            .try_into()
+            .map_err(serde::ser::Error::custom)?;
+
+        ser_struct.serialize_field("loans.trace_idx", &loans.trace_index)?;
+        ser_struct.serialize_field("loans.pool", &loans.pool)?;
+        ser_struct.serialize_field("loans.borrower", &loans.from)?;
+        ser_struct.serialize_field("loans.recipient", &loans.to)?;
+        ser_struct.serialize_field("loans.loaned_token", &loans.token)?;
+        ser_struct.serialize_field("loans.loan_amount", &loans.amount)?;
+
+        let repayments: ClickhouseVecNormalizedLending = self
+            .repayments
+            .clone()
+            .try_into()
+            .map_err(serde::ser::Error::custom)?;
+
+        ser_struct.serialize_field("repayments.trace_idx", &repayments.trace_index)?;
+        ser_struct.serialize_field("repayments.pool", &repayments.pool)?;
+        ser_struct.serialize_field("repayments.payer", &repayments.from)?;
+        ser_struct.serialize_field("repayments.borrower", &repayments.to)?;
+        ser_struct.serialize_field("repayments.repayed_token", &repayments.token)?;
+        ser_struct.serialize_field("repayments.repayment_amount", &repayments.amount)?;
+
+        let deposits: ClickhouseVecNormalizedLending = self
+            .deposits
+            .clone()
+            .try_into()
+            .map_err(serde::ser::Error::custom)?;
+
+        ser_struct.serialize_field("deposits.trace_idx", &deposits.trace_index)?;
+        ser_struct.serialize_field("deposits.pool", &deposits.pool)?;
+        ser_struct.serialize_field("deposits.depositor", &deposits.from)?;
+        ser_struct.serialize_field("deposits.on_behalf_of", &deposits.to)?;
+        ser_struct.serialize_field("deposits.deposited_token", &deposits.token)?;
+        ser_struct.serialize_field("deposits.deposit_amount", &deposits.amount)?;
+
+        let withdrawals: ClickhouseVecNormalizedLending = self
+            .withdrawals
+            .clone()
+            .try_into()
+            .map_err(serde::ser::Error::custom)?;
+
+        ser_struct.serialize_field("withdrawals.trace_idx", &withdrawals.trace_index)?;
+        ser_struct.serialize_field("withdrawals.pool", &withdrawals.pool)?;
+        ser_struct.serialize_field("withdrawals.withdrawer", &withdrawals.from)?;
+        ser_struct.serialize_field("withdrawals.recipient", &withdrawals.to)?;
+        ser_struct.serialize_field("withdrawals.withdrawn_token", &withdrawals.token)?;
+        ser_struct.serialize_field("withdrawals.withdraw_amount", &withdrawals.amount)?;
+
+        let oracle_update = self.oracle_update.as_ref();
+        ser_struct.serialize_field(
+            "oracle_update_tx_hash",
+            &oracle_update.map(|_| format!("{:?}", self.trigger)),
+        )?;
+        ser_struct.serialize_field(
+            "oracle",
+            &oracle_update.map(|update| format!("{:?}", update.oracle)),
+        )?;
+        ser_struct.serialize_field(
+            "oracle_asset",
+            &oracle_update.map(|update| format!("{:?}", update.base_token.address)),
+        )?;
+        ser_struct.serialize_field(
+            "oracle_previous_price",
+            &oracle_update.map(|update| update.previous_price.clone().to_float()),
+        )?;
+        ser_struct.serialize_field(
+            "oracle_price",
+            &oracle_update.map(|update| update.price.clone().to_float()),
+        )?;
+        ser_struct.serialize_field("oracle_price_delta", &self.oracle_price_delta())?;
+        ser_struct.serialize_field("txs_after_oracle_update", &self.txs_after_oracle_update)?;
+
         let gas_details = (
             self.gas_details.coinbase_transfer,
             self.gas_details.priority_fee,
@@ -147,6 +262,37 @@ impl DbRow for Liquidation {
         "liquidations.debt_asset",
         "liquidations.covered_debt",
         "liquidations.liquidated_collateral",
+        "loans.trace_idx",
+        "loans.pool",
+        "loans.borrower",
+        "loans.recipient",
+        "loans.loaned_token",
+        "loans.loan_amount",
+        "repayments.trace_idx",
+        "repayments.pool",
+        "repayments.payer",
+        "repayments.borrower",
+        "repayments.repayed_token",
+        "repayments.repayment_amount",
+        "deposits.trace_idx",
+        "deposits.pool",
+        "deposits.depositor",
+        "deposits.on_behalf_of",
+        "deposits.deposited_token",
+        "deposits.deposit_amount",
+        "withdrawals.trace_idx",
+        "withdrawals.pool",
+        "withdrawals.withdrawer",
+        "withdrawals.recipient",
+        "withdrawals.withdrawn_token",
+        "withdrawals.withdraw_amount",
+        "oracle_update_tx_hash",
+        "oracle",
+        "oracle_asset",
+        "oracle_previous_price",
+        "oracle_price",
+        "oracle_price_delta",
+        "txs_after_oracle_update",
         "gas_details",
     ];
 }
@@ -16,6 +16,10 @@ pub mod block;
 pub use block::*;
 pub mod searcher_tx;
 pub use searcher_tx::*;
+pub mod failed_attempt;
+pub use failed_attempt::*;
+pub mod sniping;
+pub use sniping::*;
 
 pub mod cex_dex_quotes;
 pub use cex_dex_quotes::*;
@@ -12,9 +12,12 @@ use serde_with::serde_as;
 
 use super::{Mev, MevType};
 use crate::{
-    db::{redefined_types::primitives::*, token_info::TokenInfoWithAddress},
+    db::{
+        redefined_types::{malachite::*, primitives::*},
+        token_info::{TokenInfoWithAddress, TokenInfoWithAddressRedefined},
+    },
     normalized_actions::*,
-    ClickhouseVecGasDetails, Protocol,
+    rational_to_u256_fraction, ClickhouseVecGasDetails, Protocol, ToFloatNearest,
 };
 #[allow(unused_imports)]
 use crate::{
@@ -60,7 +63,11 @@ use crate::{
 #[derive(Debug, Deserialize, PartialEq, Clone, Default, Redefined)]
 #[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
 pub struct Sandwich {
+    /// Block the backrun landed in.
     pub block_number:             u64,
+    /// Block the first frontrun landed in. Equals `block_number` unless the
+    /// sandwich spans consecutive blocks.
+    pub frontrun_block_number:    u64,
     /// Transaction hashes of the frontrunning transactions.
     /// Supports multiple transactions for complex sandwich scenarios.
     pub frontrun_tx_hash:         Vec<B256>,
@@ -88,17 +95,91 @@ pub struct Sandwich {
     /// Gas details for each backrunning transaction.
     #[redefined(same_fields)]
     pub backrun_gas_details:      GasDetails,
+    /// Loss of each victim, measured by re-executing the victim without the
+    /// frontruns. Empty until the sandwich has been simulated with
+    /// `brontes db simulate-victims`.
+    #[serde(default)]
+    pub victim_losses:            Vec<VictimLossAmount>,
 }
 
 /// calcuation for the loss per user
-#[derive(Debug, Deserialize, PartialEq, Clone, Default)]
+#[derive(Debug, Deserialize, PartialEq, Clone, Default, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
 pub struct VictimLossAmount {
-    pub tx_hash:           B256,
-    pub vicitim_eoa:       Address,
-    pub token:             TokenInfoWithAddress,
-    pub token_amount_lost: Rational,
+    pub tx_hash:                   B256,
+    pub victim_eoa:                Address,
+    /// The token the victim swapped into
+    pub token:                     TokenInfoWithAddress,
+    /// Amount of `token` the victim received
+    pub amount_out:                Rational,
+    /// Amount of `token` the victim would have received without the
+    /// frontruns
+    pub counterfactual_amount_out: Rational,
+    /// `counterfactual_amount_out - amount_out`
+    pub token_amount_lost:         Rational,
     /// is zero if we don't have a price for the given token
-    pub amount_lost_usd:   Rational,
+    pub amount_lost_usd:           Rational,
+}
+
+/// The losses can be negative if the victim got a better price because of
+/// the frontrun, so they are stored as floats. The exact loss is the difference
+/// of the two amounts.
+pub struct ClickhouseVecVictimLoss {
+    pub tx_hash:                   Vec<String>,
+    pub victim_eoa:                Vec<String>,
+    pub token:                     Vec<(String, String)>,
+    pub amount_out:                Vec<([u8; 32], [u8; 32])>,
+    pub counterfactual_amount_out: Vec<([u8; 32], [u8; 32])>,
+    pub token_amount_lost:         Vec<f64>,
+    pub amount_lost_usd:           Vec<f64>,
+}
+
+impl TryFrom<Vec<VictimLossAmount>> for ClickhouseVecVictimLoss {
+    type Error = eyre::Report;
+
+    fn try_from(value: Vec<VictimLossAmount>) -> eyre::Result<Self> {
+        Ok(Self {
+            tx_hash:                   value
+                .iter()
+                .map(|val| format!("{:?}", val.tx_hash))
+                .collect(),
+            victim_eoa:                value
+                .iter()
+                .map(|val| format!("{:?}", val.victim_eoa))
+                .collect(),
+            token:                     value.iter().map(|val| val.token.clickhouse_fmt()).collect(),
+            amount_out:                value
+                .iter()
+                .map(|val| rational_to_u256_fraction(&val.amount_out))
+                .collect::<eyre::Result<Vec<_>>>()?,
+            counterfactual_amount_out: value
+                .iter()
+                .map(|val| rational_to_u256_fraction(&val.counterfactual_amount_out))
+                .collect::<eyre::Result<Vec<_>>>()?,
+            token_amount_lost:         value
+                .iter()
+                .map(|val| val.token_amount_lost.clone().to_float())
+                .collect(),
+            amount_lost_usd:           value
+                .iter()
+                .map(|val| val.amount_lost_usd.clone().to_float())
+                .collect(),
+        })
+    }
+}
+
+impl Sandwich {
+    /// Whether the frontrun and backrun were included in different blocks.
+    pub fn is_multi_block(&self) -> bool {
+        self.frontrun_block_number != self.block_number
+    }
+
+    /// The simulated loss of the given victim transaction
+    pub fn victim_loss(&self, tx_hash: B256) -> Option<&VictimLossAmount> {
+        self.victim_losses
+            .iter()
+            .find(|loss| loss.tx_hash == tx_hash)
+    }
 }
 
 impl Mev for Sandwich {
@@ -166,8 +247,9 @@ impl Serialize for Sandwich {
     where
         S: Serializer,
     {
-        let mut ser_struct = serializer.serialize_struct("Sandwich", 35)?;
+        let mut ser_struct = serializer.serialize_struct("Sandwich", 36)?;
         ser_struct.serialize_field("block_number", &self.block_number)?;
+        ser_struct.serialize_field("frontrun_block_number", &self.frontrun_block_number)?;
 
         // frontrun
         ser_struct.serialize_field(
@@ -282,6 +364,25 @@ impl Serialize for Sandwich {
             &vec![self.backrun_gas_details.effective_gas_price],
         )?;
 
+        // victim losses
+        let victim_losses: ClickhouseVecVictimLoss = self
+            .victim_losses
+            .clone()
+            .try_into()
+            .map_err(serde::ser::Error::custom)?;
+        ser_struct.serialize_field("victim_losses.tx_hash", &victim_losses.tx_hash)?;
+        ser_struct.serialize_field("victim_losses.victim_eoa", &victim_losses.victim_eoa)?;
+        ser_struct.serialize_field("victim_losses.token", &victim_losses.token)?;
+        ser_struct.serialize_field("victim_losses.amount_out", &victim_losses.amount_out)?;
+        ser_struct.serialize_field(
+            "victim_losses.counterfactual_amount_out",
+            &victim_losses.counterfactual_amount_out,
+        )?;
+        ser_struct
+            .serialize_field("victim_losses.token_amount_lost", &victim_losses.token_amount_lost)?;
+        ser_struct
+            .serialize_field("victim_losses.amount_lost_usd", &victim_losses.amount_lost_usd)?;
+
         ser_struct.end()
     }
 }
@@ -289,6 +390,7 @@ impl Serialize for Sandwich {
 impl DbRow for Sandwich {
     const COLUMN_NAMES: &'static [&'static str] = &[
         "block_number",
+        "frontrun_block_number",
         "frontrun_tx_hash",
         "frontrun_swaps.tx_hash",
         "frontrun_swaps.trace_idx",
@@ -333,5 +435,12 @@ impl DbRow for Sandwich {
         "backrun_gas_details.priority_fee",
         "backrun_gas_details.gas_used",
         "backrun_gas_details.effective_gas_price",
+        "victim_losses.tx_hash",
+        "victim_losses.victim_eoa",
+        "victim_losses.token",
+        "victim_losses.amount_out",
+        "victim_losses.counterfactual_amount_out",
+        "victim_losses.token_amount_lost",
+        "victim_losses.amount_lost_usd",
     ];
 }
@@ -0,0 +1,248 @@
+use std::fmt::Debug;
+
+use ::clickhouse::DbRow;
+use ::serde::ser::{SerializeStruct, Serializer};
+use ahash::HashSet;
+use redefined::Redefined;
+use reth_primitives::{Address, B256};
+use rkyv::{Archive, Deserialize as rDeserialize, Serialize as rSerialize};
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+use super::{Mev, MevType};
+use crate::{
+    db::{
+        redefined_types::primitives::*,
+        token_info::{TokenInfoWithAddress, TokenInfoWithAddressRedefined},
+    },
+    normalized_actions::*,
+    ClickhouseVecGasDetails, GasDetails, Protocol,
+};
+
+/// Buys of a freshly launched token that landed in the launch block, after the
+/// pool was created or first funded, or in the block right after it.
+///
+/// The PnL of the bundle is marked to market at the end of the markout
+/// window: everything the sniper did with the token within the window is
+/// accounted for and whatever is still held is valued at the last price.
+#[serde_as]
+#[derive(Debug, Deserialize, PartialEq, Clone, Default, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct Sniping {
+    /// Block the markout window ended at, the bundle is stored in and
+    /// attributed to this block. The first snipe landed
+    /// `blocks_after_launch` blocks after `launch_block_number`
+    pub block_number:        u64,
+    pub launch_block_number: u64,
+    /// The transaction that created or first funded the pool
+    pub launch_tx_hash:      B256,
+    #[redefined(same_fields)]
+    pub protocol:            Protocol,
+    pub pool:                Address,
+    /// The token that was launched
+    pub token:               TokenInfoWithAddress,
+    /// 0 if the first snipe landed in the launch block, 1 if it landed in the
+    /// block after
+    pub blocks_after_launch: u64,
+    pub snipe_tx_hashes:     Vec<B256>,
+    pub snipe_swaps:         Vec<Vec<NormalizedSwap>>,
+    #[redefined(same_fields)]
+    pub snipe_gas_details:   Vec<GasDetails>,
+    /// Later trades of the token by the sniper within the markout window
+    pub exit_tx_hashes:      Vec<B256>,
+    pub exit_swaps:          Vec<Vec<NormalizedSwap>>,
+    #[redefined(same_fields)]
+    pub exit_gas_details:    Vec<GasDetails>,
+    /// Amount of the token the sniper still held at the end of the window
+    pub tokens_held:         f64,
+    /// Number of blocks from the launch to the block the position was marked
+    /// at
+    pub markout_blocks:      u64,
+    /// Number of launches the sniper had been seen sniping before this one
+    pub prior_snipes:        u64,
+    /// The sniper has sniped other launches, either before or in the same
+    /// block
+    pub repeat_sniper:       bool,
+}
+
+impl Sniping {
+    fn all_gas_details(&self) -> impl Iterator<Item = &GasDetails> {
+        self.snipe_gas_details
+            .iter()
+            .chain(self.exit_gas_details.iter())
+    }
+}
+
+impl Mev for Sniping {
+    fn mev_type(&self) -> MevType {
+        MevType::Sniping
+    }
+
+    fn mev_transaction_hashes(&self) -> Vec<B256> {
+        self.snipe_tx_hashes
+            .iter()
+            .chain(self.exit_tx_hashes.iter())
+            .copied()
+            .collect()
+    }
+
+    fn total_gas_paid(&self) -> u128 {
+        self.all_gas_details().map(|gd| gd.gas_paid()).sum()
+    }
+
+    fn total_priority_fee_paid(&self, base_fee: u128) -> u128 {
+        self.all_gas_details()
+            .map(|gd| gd.priority_fee_paid(base_fee))
+            .sum()
+    }
+
+    fn bribe(&self) -> u128 {
+        self.all_gas_details()
+            .filter_map(|gd| gd.coinbase_transfer)
+            .sum()
+    }
+
+    fn protocols(&self) -> HashSet<Protocol> {
+        let mut protocols: HashSet<Protocol> = self
+            .snipe_swaps
+            .iter()
+            .chain(self.exit_swaps.iter())
+            .flatten()
+            .map(|swap| swap.protocol)
+            .collect();
+        protocols.insert(self.protocol);
+
+        protocols
+    }
+}
+
+impl Serialize for Sniping {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut ser_struct = serializer.serialize_struct("Sniping", 34)?;
+
+        ser_struct.serialize_field("block_number", &self.block_number)?;
+        ser_struct.serialize_field("launch_block_number", &self.launch_block_number)?;
+        ser_struct.serialize_field("launch_tx_hash", &format!("{:?}", self.launch_tx_hash))?;
+        ser_struct.serialize_field("protocol", &self.protocol.to_string())?;
+        ser_struct.serialize_field("pool", &format!("{:?}", self.pool))?;
+        ser_struct.serialize_field(
+            "token",
+            &(format!("{:?}", self.token.address), self.token.inner.symbol.clone()),
+        )?;
+        ser_struct.serialize_field("blocks_after_launch", &self.blocks_after_launch)?;
+
+        // snipes
+        let snipe_swaps: ClickhouseDoubleVecNormalizedSwap =
+            (self.snipe_tx_hashes.clone(), self.snipe_swaps.clone())
+                .try_into()
+                .map_err(serde::ser::Error::custom)?;
+        ser_struct.serialize_field("snipe_swaps.tx_hash", &snipe_swaps.tx_hash)?;
+        ser_struct.serialize_field("snipe_swaps.trace_idx", &snipe_swaps.trace_index)?;
+        ser_struct.serialize_field("snipe_swaps.from", &snipe_swaps.from)?;
+        ser_struct.serialize_field("snipe_swaps.recipient", &snipe_swaps.recipient)?;
+        ser_struct.serialize_field("snipe_swaps.pool", &snipe_swaps.pool)?;
+        ser_struct.serialize_field("snipe_swaps.token_in", &snipe_swaps.token_in)?;
+        ser_struct.serialize_field("snipe_swaps.token_out", &snipe_swaps.token_out)?;
+        ser_struct.serialize_field("snipe_swaps.amount_in", &snipe_swaps.amount_in)?;
+        ser_struct.serialize_field("snipe_swaps.amount_out", &snipe_swaps.amount_out)?;
+
+        let snipe_gas_details: ClickhouseVecGasDetails =
+            (self.snipe_tx_hashes.clone(), self.snipe_gas_details.clone()).into();
+        ser_struct.serialize_field("snipe_gas_details.tx_hash", &snipe_gas_details.tx_hash)?;
+        ser_struct.serialize_field(
+            "snipe_gas_details.coinbase_transfer",
+            &snipe_gas_details.coinbase_transfer,
+        )?;
+        ser_struct
+            .serialize_field("snipe_gas_details.priority_fee", &snipe_gas_details.priority_fee)?;
+        ser_struct.serialize_field("snipe_gas_details.gas_used", &snipe_gas_details.gas_used)?;
+        ser_struct.serialize_field(
+            "snipe_gas_details.effective_gas_price",
+            &snipe_gas_details.effective_gas_price,
+        )?;
+
+        // exits
+        let exit_swaps: ClickhouseDoubleVecNormalizedSwap =
+            (self.exit_tx_hashes.clone(), self.exit_swaps.clone())
+                .try_into()
+                .map_err(serde::ser::Error::custom)?;
+        ser_struct.serialize_field("exit_swaps.tx_hash", &exit_swaps.tx_hash)?;
+        ser_struct.serialize_field("exit_swaps.trace_idx", &exit_swaps.trace_index)?;
+        ser_struct.serialize_field("exit_swaps.from", &exit_swaps.from)?;
+        ser_struct.serialize_field("exit_swaps.recipient", &exit_swaps.recipient)?;
+        ser_struct.serialize_field("exit_swaps.pool", &exit_swaps.pool)?;
+        ser_struct.serialize_field("exit_swaps.token_in", &exit_swaps.token_in)?;
+        ser_struct.serialize_field("exit_swaps.token_out", &exit_swaps.token_out)?;
+        ser_struct.serialize_field("exit_swaps.amount_in", &exit_swaps.amount_in)?;
+        ser_struct.serialize_field("exit_swaps.amount_out", &exit_swaps.amount_out)?;
+
+        let exit_gas_details: ClickhouseVecGasDetails =
+            (self.exit_tx_hashes.clone(), self.exit_gas_details.clone()).into();
+        ser_struct.serialize_field("exit_gas_details.tx_hash", &exit_gas_details.tx_hash)?;
+        ser_struct.serialize_field(
+            "exit_gas_details.coinbase_transfer",
+            &exit_gas_details.coinbase_transfer,
+        )?;
+        ser_struct
+            .serialize_field("exit_gas_details.priority_fee", &exit_gas_details.priority_fee)?;
+        ser_struct.serialize_field("exit_gas_details.gas_used", &exit_gas_details.gas_used)?;
+        ser_struct.serialize_field(
+            "exit_gas_details.effective_gas_price",
+            &exit_gas_details.effective_gas_price,
+        )?;
+
+        ser_struct.serialize_field("tokens_held", &self.tokens_held)?;
+        ser_struct.serialize_field("markout_blocks", &self.markout_blocks)?;
+        ser_struct.serialize_field("prior_snipes", &self.prior_snipes)?;
+        ser_struct.serialize_field("repeat_sniper", &self.repeat_sniper)?;
+
+        ser_struct.end()
+    }
+}
+
+impl DbRow for Sniping {
+    const COLUMN_NAMES: &'static [&'static str] = &[
+        "block_number",
+        "launch_block_number",
+        "launch_tx_hash",
+        "protocol",
+        "pool",
+        "token",
+        "blocks_after_launch",
+        "snipe_swaps.tx_hash",
+        "snipe_swaps.trace_idx",
+        "snipe_swaps.from",
+        "snipe_swaps.recipient",
+        "snipe_swaps.pool",
+        "snipe_swaps.token_in",
+        "snipe_swaps.token_out",
+        "snipe_swaps.amount_in",
+        "snipe_swaps.amount_out",
+        "snipe_gas_details.tx_hash",
+        "snipe_gas_details.coinbase_transfer",
+        "snipe_gas_details.priority_fee",
+        "snipe_gas_details.gas_used",
+        "snipe_gas_details.effective_gas_price",
+        "exit_swaps.tx_hash",
+        "exit_swaps.trace_idx",
+        "exit_swaps.from",
+        "exit_swaps.recipient",
+        "exit_swaps.pool",
+        "exit_swaps.token_in",
+        "exit_swaps.token_out",
+        "exit_swaps.amount_in",
+        "exit_swaps.amount_out",
+        "exit_gas_details.tx_hash",
+        "exit_gas_details.coinbase_transfer",
+        "exit_gas_details.priority_fee",
+        "exit_gas_details.gas_used",
+        "exit_gas_details.effective_gas_price",
+        "tokens_held",
+        "markout_blocks",
+        "prior_snipes",
+        "repeat_sniper",
+    ];
+}
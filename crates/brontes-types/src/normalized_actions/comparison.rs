@@ -1,6 +1,11 @@
 use std::fmt::Debug;
 
-use super::{Action, NormalizedCollect, NormalizedMint, NormalizedSwap, NormalizedTransfer};
+use super::{
+    Action, NormalizedCollect, NormalizedDeposit, NormalizedEthTransfer, NormalizedLoan,
+    NormalizedMint, NormalizedRepayment, NormalizedSwap, NormalizedTransfer,
+    NormalizedVaultDeposit, NormalizedVaultWithdraw, NormalizedWithdraw,
+};
+use crate::{constants::ETH_ADDRESS, ToScaledRational};
 
 impl<T: Sized + SubordinateAction<O>, O: ActionCmp<T>> ActionComparison<O> for T {}
 
@@ -20,6 +25,12 @@ impl ActionCmp<Action> for Action {
             Action::Swap(s) => s.is_superior_action(other),
             Action::Mint(m) => m.is_superior_action(other),
             Action::Collect(c) => c.is_superior_action(other),
+            Action::Loan(l) => l.is_superior_action(other),
+            Action::Repayment(r) => r.is_superior_action(other),
+            Action::Deposit(d) => d.is_superior_action(other),
+            Action::Withdraw(w) => w.is_superior_action(other),
+            Action::VaultDeposit(d) => d.is_superior_action(other),
+            Action::VaultWithdraw(w) => w.is_superior_action(other),
             Action::SwapWithFee(s) => s.swap.is_superior_action(other),
             Action::FlashLoan(f) => f.child_actions.iter().any(|a| a.is_superior_action(other)),
             Action::Batch(b) => {
@@ -119,3 +130,72 @@ impl ActionCmp<Action> for NormalizedCollect {
         }
     }
 }
+
+/// Lending pools don't always custody the funds themselves (e.g aave keeps them
+/// in the aToken), so we only match the underlying transfer on token and
+/// amount.
+macro_rules! lending_action_cmp {
+    ($action:ident, $token:ident, $amount:ident) => {
+        impl ActionCmp<NormalizedTransfer> for $action {
+            fn is_superior_action(&self, transfer: &NormalizedTransfer) -> bool {
+                transfer.token == self.$token && transfer.amount == self.$amount
+            }
+        }
+
+        impl ActionCmp<Action> for $action {
+            fn is_superior_action(&self, other: &Action) -> bool {
+                match other {
+                    Action::Transfer(t) => self.is_superior_action(t),
+                    _ => false,
+                }
+            }
+        }
+    };
+}
+
+lending_action_cmp!(NormalizedLoan, loaned_token, loan_amount);
+lending_action_cmp!(NormalizedRepayment, repayed_token, repayment_amount);
+lending_action_cmp!(NormalizedDeposit, deposited_token, deposit_amount);
+lending_action_cmp!(NormalizedWithdraw, withdrawn_token, withdraw_amount);
+
+/// Vault shares are minted and burnt without a transfer, so the transfers a
+/// vault action covers are the asset or shares moving through the vault.
+macro_rules! vault_action_cmp {
+    ($action:ident) => {
+        impl ActionCmp<NormalizedTransfer> for $action {
+            fn is_superior_action(&self, transfer: &NormalizedTransfer) -> bool {
+                (transfer.token == self.asset && transfer.amount == self.asset_amount)
+                    || (transfer.token == self.share && transfer.amount == self.share_amount)
+            }
+        }
+
+        impl ActionCmp<Action> for $action {
+            fn is_superior_action(&self, other: &Action) -> bool {
+                match other {
+                    Action::Transfer(t) => self.is_superior_action(t),
+                    Action::EthTransfer(t) => self.is_superior_action(t),
+                    _ => false,
+                }
+            }
+        }
+    };
+}
+
+vault_action_cmp!(NormalizedVaultDeposit);
+vault_action_cmp!(NormalizedVaultWithdraw);
+
+/// The ETH sent along to wrap or stake it
+impl ActionCmp<NormalizedEthTransfer> for NormalizedVaultDeposit {
+    fn is_superior_action(&self, transfer: &NormalizedEthTransfer) -> bool {
+        !self.msg_value.is_zero() && transfer.to == self.vault && transfer.value == self.msg_value
+    }
+}
+
+/// The ETH paid out when unwrapping or unstaking
+impl ActionCmp<NormalizedEthTransfer> for NormalizedVaultWithdraw {
+    fn is_superior_action(&self, transfer: &NormalizedEthTransfer) -> bool {
+        self.asset.address == ETH_ADDRESS
+            && transfer.from == self.vault
+            && transfer.value.to_scaled_rational(18) == self.asset_amount
+    }
+}
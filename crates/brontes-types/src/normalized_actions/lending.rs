@@ -1,33 +1,255 @@
-use std::fmt::Debug;
+use std::fmt::{self, Debug};
 
 use alloy_primitives::U256;
 use clickhouse::Row;
+use colored::Colorize;
 use malachite::Rational;
+use redefined::Redefined;
 use reth_primitives::Address;
+use rkyv::{Archive, Deserialize as rDeserialize, Serialize as rSerialize};
 use serde::{Deserialize, Serialize};
 
-use crate::{db::token_info::TokenInfoWithAddress, FastHashMap, Protocol};
+use super::accounting::{apply_delta, AddressDeltas, TokenAccounting};
+use crate::{
+    db::{
+        redefined_types::{malachite::RationalRedefined, primitives::*},
+        token_info::{TokenInfoWithAddress, TokenInfoWithAddressRedefined},
+    },
+    rational_to_u256_fraction, Protocol,
+};
 
-#[derive(Debug, Serialize, Clone, Row, PartialEq, Eq, Deserialize)]
+/// Borrowing an asset from a lending pool. The debt is opened for `borrower`
+/// while the borrowed funds are sent to `recipient`.
+#[derive(Default, Debug, Serialize, Clone, Row, PartialEq, Eq, Deserialize, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
 pub struct NormalizedLoan {
+    #[redefined(same_fields)]
     pub protocol:     Protocol,
     pub trace_index:  u64,
-    pub lender:       Address,
+    pub pool:         Address,
     pub borrower:     Address,
+    pub recipient:    Address,
     pub loaned_token: TokenInfoWithAddress,
     pub loan_amount:  Rational,
-    pub collateral:   FastHashMap<TokenInfoWithAddress, Rational>,
     pub msg_value:    U256,
 }
 
-#[derive(Debug, Serialize, Clone, Row, PartialEq, Eq, Deserialize)]
+/// Repaying debt to a lending pool. `payer` sends the funds, the debt of
+/// `borrower` is reduced.
+#[derive(Default, Debug, Serialize, Clone, Row, PartialEq, Eq, Deserialize, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
 pub struct NormalizedRepayment {
+    #[redefined(same_fields)]
     pub protocol:         Protocol,
     pub trace_index:      u64,
-    pub lender:           Address,
+    pub pool:             Address,
+    pub payer:            Address,
     pub borrower:         Address,
     pub repayed_token:    TokenInfoWithAddress,
     pub repayment_amount: Rational,
-    pub collateral:       FastHashMap<TokenInfoWithAddress, Rational>,
     pub msg_value:        U256,
 }
+
+/// Supplying an asset to a lending pool. `depositor` sends the funds, the
+/// position is credited to `on_behalf_of`.
+#[derive(Default, Debug, Serialize, Clone, Row, PartialEq, Eq, Deserialize, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct NormalizedDeposit {
+    #[redefined(same_fields)]
+    pub protocol:        Protocol,
+    pub trace_index:     u64,
+    pub pool:            Address,
+    pub depositor:       Address,
+    pub on_behalf_of:    Address,
+    pub deposited_token: TokenInfoWithAddress,
+    pub deposit_amount:  Rational,
+    pub msg_value:       U256,
+}
+
+/// Withdrawing a supplied asset from a lending pool. The position of
+/// `withdrawer` is reduced and the funds are sent to `recipient`.
+#[derive(Default, Debug, Serialize, Clone, Row, PartialEq, Eq, Deserialize, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct NormalizedWithdraw {
+    #[redefined(same_fields)]
+    pub protocol:        Protocol,
+    pub trace_index:     u64,
+    pub pool:            Address,
+    pub withdrawer:      Address,
+    pub recipient:       Address,
+    pub withdrawn_token: TokenInfoWithAddress,
+    pub withdraw_amount: Rational,
+    pub msg_value:       U256,
+}
+
+impl TokenAccounting for NormalizedLoan {
+    fn apply_token_deltas(&self, delta_map: &mut AddressDeltas) {
+        apply_delta(self.pool, self.loaned_token.address, -self.loan_amount.clone(), delta_map);
+        apply_delta(self.recipient, self.loaned_token.address, self.loan_amount.clone(), delta_map);
+    }
+}
+
+impl TokenAccounting for NormalizedRepayment {
+    fn apply_token_deltas(&self, delta_map: &mut AddressDeltas) {
+        apply_delta(
+            self.payer,
+            self.repayed_token.address,
+            -self.repayment_amount.clone(),
+            delta_map,
+        );
+        apply_delta(
+            self.pool,
+            self.repayed_token.address,
+            self.repayment_amount.clone(),
+            delta_map,
+        );
+    }
+}
+
+impl TokenAccounting for NormalizedDeposit {
+    fn apply_token_deltas(&self, delta_map: &mut AddressDeltas) {
+        apply_delta(
+            self.depositor,
+            self.deposited_token.address,
+            -self.deposit_amount.clone(),
+            delta_map,
+        );
+        apply_delta(
+            self.pool,
+            self.deposited_token.address,
+            self.deposit_amount.clone(),
+            delta_map,
+        );
+    }
+}
+
+impl TokenAccounting for NormalizedWithdraw {
+    fn apply_token_deltas(&self, delta_map: &mut AddressDeltas) {
+        apply_delta(
+            self.pool,
+            self.withdrawn_token.address,
+            -self.withdraw_amount.clone(),
+            delta_map,
+        );
+        apply_delta(
+            self.recipient,
+            self.withdrawn_token.address,
+            self.withdraw_amount.clone(),
+            delta_map,
+        );
+    }
+}
+
+impl fmt::Display for NormalizedLoan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Protocol {} - Pool: {}, Borrower: {}, Recipient: {}, Borrowed: {} {}",
+            self.protocol.to_string().bold(),
+            format!("{}", self.pool).cyan(),
+            format!("{}", self.borrower).cyan(),
+            format!("{}", self.recipient).cyan(),
+            format!("{:.4}", self.loan_amount).red(),
+            self.loaned_token.inner.symbol.bold(),
+        )
+    }
+}
+
+impl fmt::Display for NormalizedRepayment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Protocol {} - Pool: {}, Payer: {}, Borrower: {}, Repaid: {} {}",
+            self.protocol.to_string().bold(),
+            format!("{}", self.pool).cyan(),
+            format!("{}", self.payer).cyan(),
+            format!("{}", self.borrower).cyan(),
+            format!("{:.4}", self.repayment_amount).green(),
+            self.repayed_token.inner.symbol.bold(),
+        )
+    }
+}
+
+impl fmt::Display for NormalizedDeposit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Protocol {} - Pool: {}, Depositor: {}, On Behalf Of: {}, Deposited: {} {}",
+            self.protocol.to_string().bold(),
+            format!("{}", self.pool).cyan(),
+            format!("{}", self.depositor).cyan(),
+            format!("{}", self.on_behalf_of).cyan(),
+            format!("{:.4}", self.deposit_amount).green(),
+            self.deposited_token.inner.symbol.bold(),
+        )
+    }
+}
+
+impl fmt::Display for NormalizedWithdraw {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Protocol {} - Pool: {}, Withdrawer: {}, Recipient: {}, Withdrawn: {} {}",
+            self.protocol.to_string().bold(),
+            format!("{}", self.pool).cyan(),
+            format!("{}", self.withdrawer).cyan(),
+            format!("{}", self.recipient).cyan(),
+            format!("{:.4}", self.withdraw_amount).red(),
+            self.withdrawn_token.inner.symbol.bold(),
+        )
+    }
+}
+
+/// Column layout shared by all lending actions when they are stored as nested
+/// ClickHouse columns. `from` and `to` are the two accounts involved besides
+/// the pool, in the order the action's fields are declared.
+pub struct ClickhouseVecNormalizedLending {
+    pub trace_index: Vec<u64>,
+    pub pool:        Vec<String>,
+    pub from:        Vec<String>,
+    pub to:          Vec<String>,
+    pub token:       Vec<(String, String)>,
+    pub amount:      Vec<([u8; 32], [u8; 32])>,
+}
+
+macro_rules! clickhouse_vec_lending {
+    ($action:ident, $from:ident, $to:ident, $token:ident, $amount:ident) => {
+        impl TryFrom<Vec<$action>> for ClickhouseVecNormalizedLending {
+            type Error = eyre::Report;
+
+            fn try_from(value: Vec<$action>) -> eyre::Result<Self> {
+                Ok(ClickhouseVecNormalizedLending {
+                    trace_index: value.iter().map(|val| val.trace_index).collect(),
+                    pool:        value.iter().map(|val| format!("{:?}", val.pool)).collect(),
+                    from:        value.iter().map(|val| format!("{:?}", val.$from)).collect(),
+                    to:          value.iter().map(|val| format!("{:?}", val.$to)).collect(),
+                    token:       value
+                        .iter()
+                        .map(|val| val.$token.clickhouse_fmt())
+                        .collect(),
+                    amount:      value
+                        .iter()
+                        .map(|val| rational_to_u256_fraction(&val.$amount))
+                        .collect::<eyre::Result<Vec<_>>>()?,
+                })
+            }
+        }
+    };
+}
+
+clickhouse_vec_lending!(NormalizedLoan, borrower, recipient, loaned_token, loan_amount);
+clickhouse_vec_lending!(NormalizedRepayment, payer, borrower, repayed_token, repayment_amount);
+clickhouse_vec_lending!(
+    NormalizedDeposit,
+    depositor,
+    on_behalf_of,
+    deposited_token,
+    deposit_amount
+);
+clickhouse_vec_lending!(
+    NormalizedWithdraw,
+    withdrawer,
+    recipient,
+    withdrawn_token,
+    withdraw_amount
+);
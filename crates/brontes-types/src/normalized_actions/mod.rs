@@ -8,10 +8,12 @@ pub mod lending;
 pub mod liquidation;
 pub mod liquidity;
 pub mod multi_callframe;
+pub mod oracle;
 pub mod pool;
 pub mod self_destruct;
 pub mod swaps;
 pub mod transfer;
+pub mod vault;
 use std::fmt::Debug;
 
 use ::clickhouse::DbRow;
@@ -26,11 +28,13 @@ pub use lending::*;
 pub use liquidation::*;
 pub use liquidity::*;
 pub use multi_callframe::*;
+pub use oracle::*;
 pub use pool::*;
 use reth_rpc_types::trace::parity::Action as TraceAction;
 pub use self_destruct::*;
 pub use swaps::*;
 pub use transfer::*;
+pub use vault::*;
 
 use crate::{
     structured_trace::{TraceActions, TransactionTraceWithLogs},
@@ -90,6 +94,13 @@ impl NormalizedAction for Action {
             Self::Burn(b) => b.trace_index,
             Self::Transfer(t) => t.trace_index,
             Self::Liquidation(t) => t.trace_index,
+            Self::Loan(l) => l.trace_index,
+            Self::Repayment(r) => r.trace_index,
+            Self::Deposit(d) => d.trace_index,
+            Self::Withdraw(w) => w.trace_index,
+            Self::VaultDeposit(d) => d.trace_index,
+            Self::VaultWithdraw(w) => w.trace_index,
+            Self::OracleUpdate(o) => o.trace_index,
             Self::Collect(c) => c.trace_index,
             Self::SelfDestruct(c) => c.trace_index,
             Self::EthTransfer(e) => e.trace_index,
@@ -114,6 +125,13 @@ pub enum Action {
     Burn(NormalizedBurn),
     Collect(NormalizedCollect),
     Liquidation(NormalizedLiquidation),
+    Loan(NormalizedLoan),
+    Repayment(NormalizedRepayment),
+    Deposit(NormalizedDeposit),
+    Withdraw(NormalizedWithdraw),
+    VaultDeposit(NormalizedVaultDeposit),
+    VaultWithdraw(NormalizedVaultWithdraw),
+    OracleUpdate(NormalizedOracleUpdate),
     SelfDestruct(SelfdestructWithIndex),
     EthTransfer(NormalizedEthTransfer),
     NewPool(NormalizedNewPool),
@@ -135,6 +153,13 @@ impl InsertRow for Action {
             Action::Burn(_) => NormalizedBurn::COLUMN_NAMES,
             Action::Collect(_) => NormalizedCollect::COLUMN_NAMES,
             Action::Liquidation(_) => NormalizedLiquidation::COLUMN_NAMES,
+            Action::Loan(_) => NormalizedLoan::COLUMN_NAMES,
+            Action::Repayment(_) => NormalizedRepayment::COLUMN_NAMES,
+            Action::Deposit(_) => NormalizedDeposit::COLUMN_NAMES,
+            Action::Withdraw(_) => NormalizedWithdraw::COLUMN_NAMES,
+            Action::VaultDeposit(_) => NormalizedVaultDeposit::COLUMN_NAMES,
+            Action::VaultWithdraw(_) => NormalizedVaultWithdraw::COLUMN_NAMES,
+            Action::OracleUpdate(_) => NormalizedOracleUpdate::COLUMN_NAMES,
             Action::SelfDestruct(_) => todo!("joe pls dome this"),
             Action::EthTransfer(_) => todo!("joe pls dome this"),
             Action::NewPool(_) => todo!(),
@@ -161,6 +186,13 @@ impl serde::Serialize for Action {
             Action::Burn(b) => b.serialize(serializer),
             Action::Collect(c) => c.serialize(serializer),
             Action::Liquidation(c) => c.serialize(serializer),
+            Action::Loan(l) => l.serialize(serializer),
+            Action::Repayment(r) => r.serialize(serializer),
+            Action::Deposit(d) => d.serialize(serializer),
+            Action::Withdraw(w) => w.serialize(serializer),
+            Action::VaultDeposit(d) => d.serialize(serializer),
+            Action::VaultWithdraw(w) => w.serialize(serializer),
+            Action::OracleUpdate(o) => o.serialize(serializer),
             Action::SelfDestruct(sd) => sd.serialize(serializer),
             Action::EthTransfer(et) => et.serialize(serializer),
             Action::Unclassified(trace) => (trace).serialize(serializer),
@@ -205,6 +237,42 @@ impl Action {
                     from: t.liquidator,
                     ..Default::default()
                 }),
+                Self::Loan(l) => (!l.msg_value.is_zero()).then(|| NormalizedEthTransfer {
+                    value: l.msg_value,
+                    to: l.pool,
+                    from: l.recipient,
+                    ..Default::default()
+                }),
+                Self::Repayment(r) => (!r.msg_value.is_zero()).then(|| NormalizedEthTransfer {
+                    value: r.msg_value,
+                    to: r.pool,
+                    from: r.payer,
+                    ..Default::default()
+                }),
+                Self::Deposit(d) => (!d.msg_value.is_zero()).then(|| NormalizedEthTransfer {
+                    value: d.msg_value,
+                    to: d.pool,
+                    from: d.depositor,
+                    ..Default::default()
+                }),
+                Self::Withdraw(w) => (!w.msg_value.is_zero()).then(|| NormalizedEthTransfer {
+                    value: w.msg_value,
+                    to: w.pool,
+                    from: w.withdrawer,
+                    ..Default::default()
+                }),
+                Self::VaultDeposit(d) => (!d.msg_value.is_zero()).then(|| NormalizedEthTransfer {
+                    value: d.msg_value,
+                    to: d.vault,
+                    from: d.depositor,
+                    ..Default::default()
+                }),
+                Self::VaultWithdraw(w) => (!w.msg_value.is_zero()).then(|| NormalizedEthTransfer {
+                    value: w.msg_value,
+                    to: w.vault,
+                    from: w.owner,
+                    ..Default::default()
+                }),
                 Self::Unclassified(u) => (!u.get_msg_value().is_zero() && !u.is_delegate_call())
                     .then(|| NormalizedEthTransfer {
                         value: u.get_msg_value(),
@@ -226,6 +294,7 @@ impl Action {
                 Self::EthTransfer(_) => None,
                 Self::NewPool(_) => None,
                 Self::PoolConfigUpdate(_) => None,
+                Self::OracleUpdate(_) => None,
                 Self::Revert => None,
             };
         if res.is_some() {
@@ -251,6 +320,13 @@ impl Action {
             Self::Burn(b) => b.trace_index,
             Self::Transfer(t) => t.trace_index,
             Self::Liquidation(t) => t.trace_index,
+            Self::Loan(l) => l.trace_index,
+            Self::Repayment(r) => r.trace_index,
+            Self::Deposit(d) => d.trace_index,
+            Self::Withdraw(w) => w.trace_index,
+            Self::VaultDeposit(d) => d.trace_index,
+            Self::VaultWithdraw(w) => w.trace_index,
+            Self::OracleUpdate(o) => o.trace_index,
             Self::Collect(c) => c.trace_index,
             Self::SelfDestruct(c) => c.trace_index,
             Self::EthTransfer(e) => e.trace_index,
@@ -325,6 +401,13 @@ impl Action {
             Action::Transfer(t) => t.to,
             Action::Collect(c) => c.pool,
             Action::Liquidation(c) => c.pool,
+            Action::Loan(l) => l.pool,
+            Action::Repayment(r) => r.pool,
+            Action::Deposit(d) => d.pool,
+            Action::Withdraw(w) => w.pool,
+            Action::VaultDeposit(d) => d.vault,
+            Action::VaultWithdraw(w) => w.vault,
+            Action::OracleUpdate(o) => o.oracle,
             Action::SelfDestruct(c) => c.get_refund_address(),
             Action::Unclassified(t) => match &t.trace.action {
                 reth_rpc_types::trace::parity::Action::Call(c) => c.to,
@@ -351,6 +434,13 @@ impl Action {
             Action::Transfer(t) => t.from,
             Action::Collect(c) => c.from,
             Action::Liquidation(c) => c.liquidator,
+            Action::Loan(l) => l.recipient,
+            Action::Repayment(r) => r.payer,
+            Action::Deposit(d) => d.depositor,
+            Action::Withdraw(w) => w.withdrawer,
+            Action::VaultDeposit(d) => d.depositor,
+            Action::VaultWithdraw(w) => w.owner,
+            Action::OracleUpdate(o) => o.from,
             Action::SelfDestruct(c) => c.get_address(),
             Action::Unclassified(t) => match &t.trace.action {
                 reth_rpc_types::trace::parity::Action::Call(c) => c.to,
@@ -395,6 +485,45 @@ impl Action {
         matches!(self, Action::Liquidation(_))
     }
 
+    pub const fn is_loan(&self) -> bool {
+        matches!(self, Action::Loan(_))
+    }
+
+    pub const fn is_repayment(&self) -> bool {
+        matches!(self, Action::Repayment(_))
+    }
+
+    pub const fn is_deposit(&self) -> bool {
+        matches!(self, Action::Deposit(_))
+    }
+
+    pub const fn is_withdraw(&self) -> bool {
+        matches!(self, Action::Withdraw(_))
+    }
+
+    /// Any interaction with a lending pool that isn't a liquidation
+    pub const fn is_lending(&self) -> bool {
+        self.is_loan() || self.is_repayment() || self.is_deposit() || self.is_withdraw()
+    }
+
+    pub const fn is_vault_deposit(&self) -> bool {
+        matches!(self, Action::VaultDeposit(_))
+    }
+
+    pub const fn is_vault_withdraw(&self) -> bool {
+        matches!(self, Action::VaultWithdraw(_))
+    }
+
+    /// Depositing into or withdrawing from a vault, liquid staking token or
+    /// WETH
+    pub const fn is_vault(&self) -> bool {
+        self.is_vault_deposit() || self.is_vault_withdraw()
+    }
+
+    pub const fn is_oracle_update(&self) -> bool {
+        matches!(self, Action::OracleUpdate(_))
+    }
+
     pub const fn is_batch(&self) -> bool {
         matches!(self, Action::Batch(_))
     }
@@ -445,6 +574,13 @@ impl Action {
             Action::Burn(b) => b.protocol,
             Action::Collect(c) => c.protocol,
             Action::Liquidation(c) => c.protocol,
+            Action::Loan(l) => l.protocol,
+            Action::Repayment(r) => r.protocol,
+            Action::Deposit(d) => d.protocol,
+            Action::Withdraw(w) => w.protocol,
+            Action::VaultDeposit(d) => d.protocol,
+            Action::VaultWithdraw(w) => w.protocol,
+            Action::OracleUpdate(o) => o.protocol,
             Action::NewPool(p) => p.protocol,
             Action::PoolConfigUpdate(p) => p.protocol,
             Action::Aggregator(a) => a.protocol,
@@ -523,6 +659,13 @@ extra_impls!(
     (Transfer, NormalizedTransfer),
     (EthTransfer, NormalizedEthTransfer),
     (Liquidation, NormalizedLiquidation),
+    (Loan, NormalizedLoan),
+    (Repayment, NormalizedRepayment),
+    (Deposit, NormalizedDeposit),
+    (Withdraw, NormalizedWithdraw),
+    (VaultDeposit, NormalizedVaultDeposit),
+    (VaultWithdraw, NormalizedVaultWithdraw),
+    (OracleUpdate, NormalizedOracleUpdate),
     (FlashLoan, NormalizedFlashLoan),
     (Aggregator, NormalizedAggregator),
     (Batch, NormalizedBatch),
@@ -572,6 +715,12 @@ impl TokenAccounting for Action {
             Action::FlashLoan(flash_loan) => flash_loan.apply_token_deltas(delta_map),
             Action::Aggregator(aggregator) => aggregator.apply_token_deltas(delta_map),
             Action::Liquidation(liquidation) => liquidation.apply_token_deltas(delta_map),
+            Action::Loan(loan) => loan.apply_token_deltas(delta_map),
+            Action::Repayment(repayment) => repayment.apply_token_deltas(delta_map),
+            Action::Deposit(deposit) => deposit.apply_token_deltas(delta_map),
+            Action::Withdraw(withdraw) => withdraw.apply_token_deltas(delta_map),
+            Action::VaultDeposit(deposit) => deposit.apply_token_deltas(delta_map),
+            Action::VaultWithdraw(withdraw) => withdraw.apply_token_deltas(delta_map),
             Action::Batch(batch) => batch.apply_token_deltas(delta_map),
             Action::Burn(burn) => burn.apply_token_deltas(delta_map),
             Action::Mint(mint) => mint.apply_token_deltas(delta_map),
@@ -583,6 +732,7 @@ impl TokenAccounting for Action {
             Action::SelfDestruct(_self_destruct) => (),
             Action::NewPool(_new_pool) => (),
             Action::PoolConfigUpdate(_pool_update) => (),
+            Action::OracleUpdate(_oracle_update) => (),
             Action::Revert => (), // No token deltas to apply for a revert
         }
     }
@@ -0,0 +1,70 @@
+use std::fmt::{self, Debug};
+
+use clickhouse::Row;
+use colored::Colorize;
+use malachite::{num::basic::traits::Zero, Rational};
+use redefined::Redefined;
+use reth_primitives::Address;
+use rkyv::{Archive, Deserialize as rDeserialize, Serialize as rSerialize};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    db::{
+        redefined_types::{malachite::RationalRedefined, primitives::*},
+        token_info::{TokenInfoWithAddress, TokenInfoWithAddressRedefined},
+    },
+    Protocol,
+};
+
+/// A price feed publishing a new answer. `from` is the account that
+/// transmitted the report to the `oracle` (aggregator) contract.
+#[derive(Default, Debug, Serialize, Clone, Row, PartialEq, Eq, Deserialize, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct NormalizedOracleUpdate {
+    #[redefined(same_fields)]
+    pub protocol:       Protocol,
+    pub trace_index:    u64,
+    pub from:           Address,
+    pub oracle:         Address,
+    pub round_id:       u64,
+    /// The asset the feed prices
+    pub base_token:     TokenInfoWithAddress,
+    /// The denomination of the answer, USD denominated feeds use chainlinks
+    /// placeholder address for USD
+    pub quote_token:    TokenInfoWithAddress,
+    /// The answer of the feed at the start of the block, zero if it couldn't
+    /// be queried
+    pub previous_price: Rational,
+    pub price:          Rational,
+}
+
+impl NormalizedOracleUpdate {
+    /// Relative change of the answer, e.g -0.02 for a 2% drop. None if the
+    /// previous answer is unknown.
+    pub fn price_delta(&self) -> Option<Rational> {
+        (self.previous_price != Rational::ZERO)
+            .then(|| (&self.price - &self.previous_price) / &self.previous_price)
+    }
+
+    /// Whether the update moved the price of the given token
+    pub fn prices(&self, token: Address) -> bool {
+        self.base_token.address == token || self.quote_token.address == token
+    }
+}
+
+impl fmt::Display for NormalizedOracleUpdate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Protocol {} - Oracle: {}, Transmitter: {}, Round: {}, Price: {} -> {} {}/{}",
+            self.protocol.to_string().bold(),
+            format!("{}", self.oracle).cyan(),
+            format!("{}", self.from).cyan(),
+            self.round_id,
+            format!("{:.4}", self.previous_price).red(),
+            format!("{:.4}", self.price).green(),
+            self.base_token.inner.symbol.bold(),
+            self.quote_token.inner.symbol.bold(),
+        )
+    }
+}
@@ -0,0 +1,251 @@
+use std::fmt::{self, Debug};
+
+use alloy_primitives::U256;
+use clickhouse::Row;
+use colored::Colorize;
+use malachite::{num::basic::traits::Zero, Rational};
+use redefined::Redefined;
+use reth_primitives::Address;
+use rkyv::{Archive, Deserialize as rDeserialize, Serialize as rSerialize};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    accounting::{apply_delta, AddressDeltas, TokenAccounting},
+    NormalizedSwap,
+};
+use crate::{
+    db::{
+        redefined_types::{malachite::RationalRedefined, primitives::*},
+        token_info::{TokenInfoWithAddress, TokenInfoWithAddressRedefined},
+    },
+    Protocol,
+};
+
+/// Depositing an asset into a vault for its shares. Covers ERC-4626 vaults,
+/// liquid staking tokens and wrapped ETH, where the asset is ETH. `depositor`
+/// sends the assets, the shares are minted to `recipient`.
+#[derive(Default, Debug, Serialize, Clone, Row, PartialEq, Eq, Deserialize, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct NormalizedVaultDeposit {
+    #[redefined(same_fields)]
+    pub protocol:     Protocol,
+    pub trace_index:  u64,
+    pub vault:        Address,
+    pub depositor:    Address,
+    pub recipient:    Address,
+    pub asset:        TokenInfoWithAddress,
+    pub asset_amount: Rational,
+    pub share:        TokenInfoWithAddress,
+    pub share_amount: Rational,
+    /// Assets per share the deposit was made at
+    pub share_price:  Rational,
+    pub msg_value:    U256,
+}
+
+/// Redeeming vault shares for the underlying asset. The shares of `owner` are
+/// burnt and the assets are sent to `recipient`.
+#[derive(Default, Debug, Serialize, Clone, Row, PartialEq, Eq, Deserialize, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct NormalizedVaultWithdraw {
+    #[redefined(same_fields)]
+    pub protocol:     Protocol,
+    pub trace_index:  u64,
+    pub vault:        Address,
+    pub owner:        Address,
+    pub recipient:    Address,
+    pub share:        TokenInfoWithAddress,
+    pub share_amount: Rational,
+    pub asset:        TokenInfoWithAddress,
+    pub asset_amount: Rational,
+    /// Assets per share the withdrawal was made at
+    pub share_price:  Rational,
+    pub msg_value:    U256,
+}
+
+/// Assets per share, zero if no shares were moved
+pub fn share_price(asset_amount: &Rational, share_amount: &Rational) -> Rational {
+    if share_amount == &Rational::ZERO {
+        return Rational::ZERO
+    }
+
+    asset_amount / share_amount
+}
+
+impl NormalizedVaultDeposit {
+    /// The deposit as a swap of the asset for the shares, so it can be a leg
+    /// of an arb
+    pub fn as_swap(&self) -> NormalizedSwap {
+        NormalizedSwap {
+            protocol:    self.protocol,
+            trace_index: self.trace_index,
+            from:        self.depositor,
+            recipient:   self.recipient,
+            pool:        self.vault,
+            token_in:    self.asset.clone(),
+            token_out:   self.share.clone(),
+            amount_in:   self.asset_amount.clone(),
+            amount_out:  self.share_amount.clone(),
+            msg_value:   self.msg_value,
+        }
+    }
+}
+
+impl NormalizedVaultWithdraw {
+    /// The withdrawal as a swap of the shares for the asset, so it can be a leg
+    /// of an arb
+    pub fn as_swap(&self) -> NormalizedSwap {
+        NormalizedSwap {
+            protocol:    self.protocol,
+            trace_index: self.trace_index,
+            from:        self.owner,
+            recipient:   self.recipient,
+            pool:        self.vault,
+            token_in:    self.share.clone(),
+            token_out:   self.asset.clone(),
+            amount_in:   self.share_amount.clone(),
+            amount_out:  self.asset_amount.clone(),
+            msg_value:   self.msg_value,
+        }
+    }
+}
+
+// The shares are minted and burnt by the vault, so only the recipient of a
+// deposit and the owner of a withdrawal see them move
+impl TokenAccounting for NormalizedVaultDeposit {
+    fn apply_token_deltas(&self, delta_map: &mut AddressDeltas) {
+        apply_delta(self.depositor, self.asset.address, -self.asset_amount.clone(), delta_map);
+        apply_delta(self.vault, self.asset.address, self.asset_amount.clone(), delta_map);
+        apply_delta(self.recipient, self.share.address, self.share_amount.clone(), delta_map);
+    }
+}
+
+impl TokenAccounting for NormalizedVaultWithdraw {
+    fn apply_token_deltas(&self, delta_map: &mut AddressDeltas) {
+        apply_delta(self.owner, self.share.address, -self.share_amount.clone(), delta_map);
+        apply_delta(self.vault, self.asset.address, -self.asset_amount.clone(), delta_map);
+        apply_delta(self.recipient, self.asset.address, self.asset_amount.clone(), delta_map);
+    }
+}
+
+impl fmt::Display for NormalizedVaultDeposit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Protocol {} - Vault: {}, Depositor: {}, Recipient: {}, Deposited: {} {} for {} {} at \
+             {} {} per share",
+            self.protocol.to_string().bold(),
+            format!("{}", self.vault).cyan(),
+            format!("{}", self.depositor).cyan(),
+            format!("{}", self.recipient).cyan(),
+            format!("{:.4}", self.asset_amount).red(),
+            self.asset.inner.symbol.bold(),
+            format!("{:.4}", self.share_amount).green(),
+            self.share.inner.symbol.bold(),
+            format!("{:.6}", self.share_price),
+            self.asset.inner.symbol.bold(),
+        )
+    }
+}
+
+impl fmt::Display for NormalizedVaultWithdraw {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Protocol {} - Vault: {}, Owner: {}, Recipient: {}, Redeemed: {} {} for {} {} at {} \
+             {} per share",
+            self.protocol.to_string().bold(),
+            format!("{}", self.vault).cyan(),
+            format!("{}", self.owner).cyan(),
+            format!("{}", self.recipient).cyan(),
+            format!("{:.4}", self.share_amount).red(),
+            self.share.inner.symbol.bold(),
+            format!("{:.4}", self.asset_amount).green(),
+            self.asset.inner.symbol.bold(),
+            format!("{:.6}", self.share_price),
+            self.asset.inner.symbol.bold(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::hex;
+
+    use super::*;
+    use crate::{
+        constants::{ETH_ADDRESS, WETH_ADDRESS},
+        db::token_info::TokenInfo,
+        normalized_actions::{
+            accounting::ActionAccounting, Action, NormalizedEthTransfer, NormalizedTransfer,
+        },
+    };
+
+    fn token(address: Address, symbol: &str) -> TokenInfoWithAddress {
+        TokenInfoWithAddress {
+            address,
+            inner: TokenInfo { decimals: 18, symbol: symbol.to_string() },
+        }
+    }
+
+    #[test]
+    fn test_vault_legs_dedup_underlying_transfers() {
+        let searcher = Address::with_last_byte(1);
+        let wsteth = Address::new(hex!("7f39C581F595B53c5cb19bD0b3f8dA6c935E2Ca0"));
+        let steth = Address::new(hex!("ae7ab96520DE3A18E5e111B5EaAb095312D7fE84"));
+        let one = U256::from(10u64.pow(18));
+
+        // wrap 1.2 stETH into 1 wstETH, then unwrap 1 WETH
+        let wrap = NormalizedVaultDeposit {
+            protocol: Protocol::Lido,
+            vault: wsteth,
+            depositor: searcher,
+            recipient: searcher,
+            asset: token(steth, "stETH"),
+            asset_amount: Rational::from_signeds(6, 5),
+            share: token(wsteth, "wstETH"),
+            share_amount: Rational::from(1),
+            share_price: share_price(&Rational::from_signeds(6, 5), &Rational::from(1)),
+            ..Default::default()
+        };
+        let unwrap = NormalizedVaultWithdraw {
+            protocol: Protocol::Weth,
+            vault: WETH_ADDRESS,
+            owner: searcher,
+            recipient: searcher,
+            share: token(WETH_ADDRESS, "WETH"),
+            share_amount: Rational::from(1),
+            asset: token(ETH_ADDRESS, "ETH"),
+            asset_amount: Rational::from(1),
+            share_price: Rational::from(1),
+            ..Default::default()
+        };
+
+        let deltas = vec![
+            Action::VaultDeposit(wrap.clone()),
+            Action::Transfer(NormalizedTransfer {
+                from: searcher,
+                to: wsteth,
+                token: wrap.asset.clone(),
+                amount: wrap.asset_amount.clone(),
+                ..Default::default()
+            }),
+            Action::VaultWithdraw(unwrap),
+            Action::EthTransfer(NormalizedEthTransfer {
+                from: WETH_ADDRESS,
+                to: searcher,
+                value: one,
+                ..Default::default()
+            }),
+        ]
+        .into_iter()
+        .account_for_actions();
+
+        let searcher_deltas = &deltas[&searcher];
+        assert_eq!(searcher_deltas[&steth], Rational::from_signeds(-6, 5));
+        assert_eq!(searcher_deltas[&wsteth], Rational::from(1));
+        assert_eq!(searcher_deltas[&WETH_ADDRESS], Rational::from(-1));
+        assert_eq!(searcher_deltas[&ETH_ADDRESS], Rational::from(1));
+        assert_eq!(wrap.share_price, Rational::from_signeds(6, 5));
+        assert_eq!(share_price(&Rational::from(1), &Rational::ZERO), Rational::ZERO);
+    }
+}
@@ -102,6 +102,17 @@ utils!(
         ClipperExchange,
         PropellerLabsSolver,
         Dodo,
+        UniswapV4,
+        Chainlink,
+        Erc4626,
+        Weth,
+        Lido,
+        RocketPool,
+        ParaswapV5,
+        ParaswapV6,
+        KyberSwap,
+        ZeroXSettler,
+        UniswapUniversalRouter,
         #[default]
         Unknown,
     }
@@ -147,6 +158,17 @@ impl Protocol {
             Protocol::ClipperExchange => ("ClipperExchange", ""),
             Protocol::PropellerLabsSolver => ("Propeller Labs Solver", ""),
             Protocol::Dodo => ("Dodo", "V1/V2"),
+            Protocol::UniswapV4 => ("Uniswap", "V4"),
+            Protocol::Chainlink => ("Chainlink", "OCR"),
+            Protocol::Erc4626 => ("ERC4626", ""),
+            Protocol::Weth => ("WETH", ""),
+            Protocol::Lido => ("Lido", ""),
+            Protocol::RocketPool => ("RocketPool", ""),
+            Protocol::ParaswapV5 => ("Paraswap", "V5"),
+            Protocol::ParaswapV6 => ("Paraswap", "V6"),
+            Protocol::KyberSwap => ("KyberSwap", "MetaAggregator"),
+            Protocol::ZeroXSettler => ("ZeroX", "Settler"),
+            Protocol::UniswapUniversalRouter => ("Uniswap", "UniversalRouter"),
             Protocol::Unknown => ("Unknown", "Unknown"),
         }
     }
@@ -156,6 +178,7 @@ impl Protocol {
             "uniswapv2" => Protocol::UniswapV2,
             "sushiswapv2" => Protocol::SushiSwapV2,
             "uniswapv3" => Protocol::UniswapV3,
+            "uniswapv4" => Protocol::UniswapV4,
             "sushiswapv3" => Protocol::SushiSwapV3,
             "curve.fibase2" => Protocol::CurveBasePool2,
             "curve.fibase3" => Protocol::CurveBasePool3,
@@ -174,6 +197,16 @@ impl Protocol {
             "dodov1/v2" => Protocol::Dodo,
             "pancakeswapv2" => Protocol::PancakeSwapV2,
             "pancakeswapv3" => Protocol::PancakeSwapV3,
+            "chainlinkocr" => Protocol::Chainlink,
+            "erc4626" => Protocol::Erc4626,
+            "weth" => Protocol::Weth,
+            "lido" => Protocol::Lido,
+            "rocketpool" => Protocol::RocketPool,
+            "paraswapv5" => Protocol::ParaswapV5,
+            "paraswapv6" => Protocol::ParaswapV6,
+            "kyberswapmetaaggregator" => Protocol::KyberSwap,
+            "zeroxsettler" => Protocol::ZeroXSettler,
+            "uniswapuniversalrouter" => Protocol::UniswapUniversalRouter,
             _ => Protocol::Unknown,
         }
     }
@@ -222,6 +255,17 @@ impl fmt::Display for Protocol {
                 Protocol::ClipperExchange => "Clipper",
                 Protocol::PropellerLabsSolver => "Propeller Labs",
                 Protocol::Dodo => "Dodo",
+                Protocol::UniswapV4 => "Uni V4",
+                Protocol::Chainlink => "Chainlink",
+                Protocol::Erc4626 => "ERC-4626",
+                Protocol::Weth => "WETH",
+                Protocol::Lido => "Lido",
+                Protocol::RocketPool => "Rocket Pool",
+                Protocol::ParaswapV5 => "Paraswap V5",
+                Protocol::ParaswapV6 => "Paraswap V6",
+                Protocol::KyberSwap => "KyberSwap",
+                Protocol::ZeroXSettler => "0x Settler",
+                Protocol::UniswapUniversalRouter => "Uni Universal Router",
                 Protocol::Unknown => "Unknown",
             }
         )
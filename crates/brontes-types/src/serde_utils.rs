@@ -19,6 +19,82 @@ pub mod dex_key {
     }
 }
 
+pub mod rollup_key {
+    use serde::{
+        de::{Deserialize, Deserializer},
+        ser::{Error, Serialize, Serializer},
+    };
+
+    use crate::db::analysis_rollup::{
+        decompose_rollup_key, make_rollup_key, RollupKey, RollupWindow,
+    };
+
+    pub fn serialize<S: Serializer>(u: &RollupKey, serializer: S) -> Result<S::Ok, S::Error> {
+        decompose_rollup_key(*u)
+            .map_err(S::Error::custom)?
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<RollupKey, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let (window, window_start): (RollupWindow, u64) = Deserialize::deserialize(deserializer)?;
+        Ok(make_rollup_key(window, window_start))
+    }
+}
+
+pub mod address_bundle_key {
+    use alloy_primitives::Address;
+    use serde::{
+        de::{Deserialize, Deserializer},
+        ser::{Serialize, Serializer},
+    };
+
+    use crate::db::bundle_index::{
+        decompose_address_bundle_key, make_address_bundle_key, AddressBundleKey,
+    };
+
+    pub fn serialize<S: Serializer>(
+        u: &AddressBundleKey,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        decompose_address_bundle_key(*u).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<AddressBundleKey, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let (address, block, bundle_idx): (Address, u64, u16) =
+            Deserialize::deserialize(deserializer)?;
+        Ok(make_address_bundle_key(address, block, bundle_idx))
+    }
+}
+
+pub mod tx_bundle_key {
+    use alloy_primitives::B256;
+    use serde::{
+        de::{Deserialize, Deserializer},
+        ser::{Serialize, Serializer},
+    };
+
+    use crate::db::bundle_index::{decompose_tx_bundle_key, make_tx_bundle_key, TxBundleKey};
+
+    pub fn serialize<S: Serializer>(u: &TxBundleKey, serializer: S) -> Result<S::Ok, S::Error> {
+        decompose_tx_bundle_key(*u).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<TxBundleKey, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let (tx_hash, block, bundle_idx): (B256, u64, u16) =
+            Deserialize::deserialize(deserializer)?;
+        Ok(make_tx_bundle_key(tx_hash, block, bundle_idx))
+    }
+}
+
 pub mod address_string {
     use std::str::FromStr;
 
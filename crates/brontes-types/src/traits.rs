@@ -1,4 +1,4 @@
-use alloy_primitives::TxHash;
+use alloy_primitives::{Log as PrimitiveLog, TxHash, I256, U256};
 use alloy_rpc_types::AnyReceiptEnvelope;
 use reth_primitives::{
     Address, BlockId, BlockNumber, BlockNumberOrTag, Bytecode, Bytes, Header, StorageValue, B256,
@@ -6,6 +6,7 @@ use reth_primitives::{
 use reth_rpc_types::{
     state::StateOverride, BlockOverrides, Log, TransactionReceipt, TransactionRequest,
 };
+use serde::{Deserialize, Serialize};
 
 use crate::structured_trace::TxTrace;
 
@@ -52,6 +53,20 @@ pub trait TracingProvider: Send + Sync + 'static {
 
     async fn block_and_tx_index(&self, hash: TxHash) -> eyre::Result<(u64, usize)>;
 
+    /// Re-executes the transaction at `tx_index` on top of the state it
+    /// originally ran against, leaving out the transactions of the block in
+    /// `skip`. The transactions are executed like calls, without checking
+    /// their nonces, so a skipped transaction doesn't invalidate the later
+    /// ones of its sender. Fails if any other transaction becomes invalid.
+    async fn replay_transaction_without(
+        &self,
+        _block_number: u64,
+        _tx_index: usize,
+        _skip: Vec<usize>,
+    ) -> eyre::Result<ReplayedTransaction> {
+        eyre::bail!("replaying transactions is not supported by this provider")
+    }
+
     // DB Access Methods
     async fn get_storage(
         &self,
@@ -66,3 +81,29 @@ pub trait TracingProvider: Send + Sync + 'static {
         address: Address,
     ) -> eyre::Result<Option<Bytecode>>;
 }
+
+/// The outcome of a transaction replayed with
+/// [`TracingProvider::replay_transaction_without`]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReplayedTransaction {
+    pub from:               Address,
+    pub is_success:         bool,
+    /// Logs of the transaction, empty if it reverted
+    pub logs:               Vec<PrimitiveLog>,
+    /// The change of the eth balance of the accounts the transaction moved
+    /// eth between, not counting the gas paid by the sender
+    #[serde(default)]
+    pub eth_balance_deltas: Vec<(Address, I256)>,
+}
+
+impl ReplayedTransaction {
+    /// The eth balance the account gained, zero if it lost eth
+    pub fn eth_received(&self, account: Address) -> U256 {
+        self.eth_balance_deltas
+            .iter()
+            .find(|(address, _)| *address == account)
+            .filter(|(_, delta)| delta.is_positive())
+            .map(|(_, delta)| delta.into_raw())
+            .unwrap_or_default()
+    }
+}
@@ -0,0 +1,57 @@
+use alloy_primitives::{Address, TxHash};
+use itertools::Itertools;
+
+use crate::{
+    structured_trace::{TraceActions, TxTrace},
+    GasDetails,
+};
+
+/// A transaction whose top level call reverted. None of its actions went
+/// through so it isn't part of the action tree, but the gas it burnt was still
+/// paid, so we keep what is needed to account for it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FailedTx {
+    pub tx_hash:     TxHash,
+    pub position:    usize,
+    pub eoa:         Address,
+    /// The contract that was called, is none for failed contract creations
+    pub to:          Option<Address>,
+    /// Every contract the transaction made a state changing call to before it
+    /// reverted, in call order. Static and delegate calls are left out as
+    /// they don't interact with other contracts.
+    pub called:      Vec<Address>,
+    pub gas_details: GasDetails,
+}
+
+impl FailedTx {
+    /// Returns none if the transaction succeeded or has no trace
+    pub fn from_trace(position: usize, trace: &TxTrace, base_fee: u128) -> Option<Self> {
+        if trace.is_success {
+            return None
+        }
+        let root = trace.trace.first()?;
+
+        let called = trace
+            .trace
+            .iter()
+            .skip(1)
+            .filter(|t| !(t.is_create() || t.is_static_call() || t.is_delegate_call()))
+            .map(|t| t.get_to_address())
+            .unique()
+            .collect();
+
+        Some(Self {
+            tx_hash: trace.tx_hash,
+            position,
+            eoa: root.get_from_addr(),
+            to: (!root.is_create()).then(|| root.get_to_address()),
+            called,
+            gas_details: GasDetails {
+                coinbase_transfer:   None,
+                gas_used:            trace.gas_used,
+                effective_gas_price: trace.effective_price,
+                priority_fee:        trace.effective_price.saturating_sub(base_fee),
+            },
+        })
+    }
+}
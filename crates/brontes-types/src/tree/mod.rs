@@ -19,6 +19,8 @@ pub use util::*;
 pub mod root;
 pub mod tx_info;
 pub use node::*;
+pub mod failed_tx;
+pub use failed_tx::*;
 pub use root::*;
 pub use tx_info::*;
 pub mod search_args;
@@ -32,6 +34,8 @@ type ClassifyData<V> = Option<(usize, Vec<MultiCallFrameClassification<V>>)>;
 #[derive(Debug, Clone)]
 pub struct BlockTree<V: NormalizedAction> {
     pub tx_roots:             Vec<Root<V>>,
+    /// Transactions that reverted, these aren't part of the action tree
+    pub failed_txs:           Vec<FailedTx>,
     pub header:               Header,
     pub priority_fee_std_dev: f64,
     pub avg_priority_fee:     f64,
@@ -41,6 +45,7 @@ impl<V: NormalizedAction> BlockTree<V> {
     pub fn new(header: Header, tx_num: usize) -> Self {
         Self {
             tx_roots: Vec::with_capacity(tx_num),
+            failed_txs: vec![],
             header,
             priority_fee_std_dev: 0.0,
             avg_priority_fee: 0.0,
@@ -133,6 +138,10 @@ impl<V: NormalizedAction> BlockTree<V> {
         &self.tx_roots
     }
 
+    pub fn insert_failed_txs(&mut self, failed_txs: Vec<FailedTx>) {
+        self.failed_txs = failed_txs;
+    }
+
     pub fn finalize_tree(&mut self) {
         self.run_in_span_mut(|this| {
             // in case the block is empty
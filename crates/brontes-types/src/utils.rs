@@ -1,4 +1,4 @@
-use alloy_primitives::{I256, U256};
+use alloy_primitives::{Signed, I256, U256};
 use eyre::ContextCompat;
 use malachite::{
     num::{arithmetic::traits::Pow, conversion::traits::RoundingFrom},
@@ -41,6 +41,15 @@ impl ToScaledRational for I256 {
     }
 }
 
+/// int192, the answer type of chainlink aggregators
+impl ToScaledRational for Signed<192, 3> {
+    fn to_scaled_rational(self, decimals: u8) -> Rational {
+        let top = Integer::from_twos_complement_limbs_asc(&self.into_limbs());
+
+        Rational::from_integers(top, Integer::from(10u8).pow(decimals as u64))
+    }
+}
+
 impl ToScaledRational for u64 {
     fn to_scaled_rational(self, decimals: u8) -> Rational {
         let top = Natural::from(self);
@@ -1,13 +1,17 @@
 use std::cmp::min;
 
+use alloy_primitives::I256;
 use alloy_rpc_types::AnyReceiptEnvelope;
-use brontes_types::{structured_trace::TxTrace, traits::TracingProvider};
+use brontes_types::{
+    structured_trace::TxTrace,
+    traits::{ReplayedTransaction, TracingProvider},
+};
 use eyre::eyre;
 use reth_primitives::{
-    Address, BlockId, BlockNumber, BlockNumberOrTag, Bytecode, Bytes, Header, StorageValue, TxHash,
-    B256, U256,
+    revm::env::tx_env_with_recovered, Address, BlockId, BlockNumber, BlockNumberOrTag, Bytecode,
+    Bytes, Header, StorageValue, TransactionVariant, TxHash, B256, U256,
 };
-use reth_provider::{BlockIdReader, BlockNumReader, HeaderProvider};
+use reth_provider::{BlockIdReader, BlockNumReader, BlockReader, HeaderProvider};
 use reth_revm::{database::StateProviderDatabase, db::CacheDB};
 use reth_rpc::eth::{
     error::{EthApiError, EthResult, RevertError, RpcInvalidTransactionError},
@@ -19,9 +23,10 @@ use reth_rpc_types::{
 };
 use revm::{
     primitives::{
-        db::DatabaseRef, BlockEnv, CfgEnvWithHandlerCfg, EnvWithHandlerCfg, TransactTo, TxEnv,
+        db::DatabaseRef, BlockEnv, CfgEnvWithHandlerCfg, EnvWithHandlerCfg, State as EvmState,
+        TransactTo, TxEnv,
     },
-    Database,
+    Database, DatabaseCommit,
 };
 use revm_primitives::ExecutionResult;
 
@@ -107,6 +112,75 @@ impl TracingProvider for TracingClient {
         Ok((tx.block_number.unwrap(), tx.transaction_index.unwrap() as usize))
     }
 
+    async fn replay_transaction_without(
+        &self,
+        block_number: u64,
+        tx_index: usize,
+        skip: Vec<usize>,
+    ) -> eyre::Result<ReplayedTransaction> {
+        let Some(block) = self
+            .provider_factory
+            .block_with_senders(block_number.into(), TransactionVariant::WithHash)?
+        else {
+            return Err(eyre!("block {block_number} not found"));
+        };
+
+        let (cfg, block_env, _) = self
+            .api
+            .evm_env_at(BlockId::Number(BlockNumberOrTag::Number(block_number)))
+            .await?;
+        let state = self.api.state_at(BlockId::Hash(block.parent_hash.into()))?;
+        let mut db = CacheDB::new(StateProviderDatabase::new(state));
+
+        for (idx, tx) in block
+            .into_transactions_ecrecovered()
+            .enumerate()
+            .take(tx_index + 1)
+        {
+            if skip.contains(&idx) {
+                continue
+            }
+
+            // executed like the calls of `debug_traceCallMany`, so that a skipped
+            // transaction doesn't leave a nonce gap
+            let mut tx_env = tx_env_with_recovered(&tx);
+            tx_env.nonce = None;
+            let env = EnvWithHandlerCfg::new_with_cfg_env(cfg.clone(), block_env.clone(), tx_env);
+
+            if idx == tx_index {
+                let gas_price = env.effective_gas_price();
+                let (res, _) = self.api.transact(&mut db, env)?;
+                let gas_used = U256::from(res.result.gas_used());
+                let eth_balance_deltas = eth_balance_deltas(&db, &res.state, |address| {
+                    if address == tx.signer() {
+                        I256::from_raw(gas_used * gas_price)
+                    } else if address == block_env.coinbase {
+                        -I256::from_raw(gas_used * gas_price.saturating_sub(block_env.basefee))
+                    } else {
+                        I256::ZERO
+                    }
+                })?;
+
+                return Ok(ReplayedTransaction {
+                    from: tx.signer(),
+                    is_success: res.result.is_success(),
+                    logs: res.result.into_logs(),
+                    eth_balance_deltas,
+                })
+            }
+
+            let (res, _) = self.api.transact(&mut db, env).map_err(|e| {
+                eyre!(
+                    "transaction {idx} of block {block_number} is invalid without the skipped \
+                     transactions: {e}"
+                )
+            })?;
+            db.commit(res.state);
+        }
+
+        Err(eyre!("block {block_number} has no transaction at index {tx_index}"))
+    }
+
     async fn header_by_number(&self, number: BlockNumber) -> eyre::Result<Option<Header>> {
         self.trace
             .provider()
@@ -443,3 +517,31 @@ pub(crate) fn ensure_success(result: ExecutionResult) -> EthResult<Bytes> {
         }
     }
 }
+
+/// The change of the eth balance of the accounts the transaction touched,
+/// with the eth an account paid for gas, as returned by `gas_paid`, added back
+fn eth_balance_deltas<DB: DatabaseRef>(
+    db: &DB,
+    state: &EvmState,
+    gas_paid: impl Fn(Address) -> I256,
+) -> eyre::Result<Vec<(Address, I256)>>
+where
+    eyre::Report: From<DB::Error>,
+{
+    let mut deltas = Vec::new();
+    for (address, account) in state.iter().filter(|(_, account)| account.is_touched()) {
+        let before = db
+            .basic_ref(*address)?
+            .map(|info| info.balance)
+            .unwrap_or_default();
+        let delta =
+            I256::from_raw(account.info.balance) - I256::from_raw(before) + gas_paid(*address);
+
+        if !delta.is_zero() {
+            deltas.push((*address, delta));
+        }
+    }
+    deltas.sort_unstable_by_key(|(address, _)| *address);
+
+    Ok(deltas)
+}